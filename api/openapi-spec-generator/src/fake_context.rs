@@ -17,5 +17,6 @@ pub fn get_fake_context() -> Context {
         mempool.ac_client,
         NodeConfig::default(),
         None, /* table info reader */
+        None, /* event by type reader */
     )
 }
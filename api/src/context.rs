@@ -18,10 +18,15 @@ use aptos_api_types::{
 };
 use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
-use aptos_db_indexer::table_info_reader::TableInfoReader;
+use aptos_db_indexer::{
+    event_by_type_reader::EventByTypeReader, table_info_reader::TableInfoReader,
+};
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::{error, info, Schema};
-use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use aptos_mempool::{
+    MempoolAccountStatus, MempoolClientRequest, MempoolClientSender, MempoolTransactionStatus,
+    SubmissionStatus,
+};
 use aptos_storage_interface::{
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
     DbReader, Order, MAX_REQUEST_LIMIT,
@@ -32,7 +37,7 @@ use aptos_types::{
     account_config::{AccountResource, NewBlockEvent},
     block_executor::config::BlockExecutorConfigFromOnchain,
     chain_id::ChainId,
-    contract_event::EventWithVersion,
+    contract_event::{ContractEvent, EventWithVersion},
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     on_chain_config::{GasSchedule, GasScheduleV2, OnChainConfig, OnChainExecutionConfig},
@@ -49,7 +54,7 @@ use futures::{channel::oneshot, SinkExt};
 use mini_moka::sync::Cache;
 use move_core_types::{
     identifier::Identifier,
-    language_storage::{ModuleId, StructTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
     move_resource::MoveResource,
 };
 use serde::Serialize;
@@ -77,7 +82,9 @@ pub struct Context {
     view_function_stats: Arc<FunctionStats>,
     simulate_txn_stats: Arc<FunctionStats>,
     pub table_info_reader: Option<Arc<dyn TableInfoReader>>,
+    pub event_by_type_reader: Option<Arc<dyn EventByTypeReader>>,
     pub wait_for_hash_active_connections: Arc<AtomicUsize>,
+    pub event_stream_active_connections: Arc<AtomicUsize>,
 }
 
 impl std::fmt::Debug for Context {
@@ -93,6 +100,7 @@ impl Context {
         mp_sender: MempoolClientSender,
         node_config: NodeConfig,
         table_info_reader: Option<Arc<dyn TableInfoReader>>,
+        event_by_type_reader: Option<Arc<dyn EventByTypeReader>>,
     ) -> Self {
         let (view_function_stats, simulate_txn_stats) = {
             let log_per_call_stats = node_config.api.periodic_function_stats_sec.is_some();
@@ -130,7 +138,9 @@ impl Context {
             view_function_stats,
             simulate_txn_stats,
             table_info_reader,
+            event_by_type_reader,
             wait_for_hash_active_connections: Arc::new(AtomicUsize::new(0)),
+            event_stream_active_connections: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -180,6 +190,9 @@ impl Context {
         Ok((latest_ledger_info, requested_ledger_version, state_view))
     }
 
+    /// The state view reads values from the state KV DB without proofs, so historical state is
+    /// served as long as the state KV pruner keeps it, even if the state Merkle pruner has removed
+    /// the tree at `version`.
     pub fn state_view_at_version(&self, version: Version) -> Result<DbStateView> {
         Ok(self.db.state_view_at_version(Some(version))?)
     }
@@ -751,12 +764,37 @@ impl Context {
             .saturating_sub(limit as u64)
         };
 
-        let txns = self
+        // Transactions older than the prune window are only there if the ledger pruner kept them,
+        // and come before the ones within the window.
+        let retained_txns = self
             .db
-            .get_account_transactions(
+            .get_retained_account_transactions(
                 address,
                 start_seq_number,
                 limit as u64,
+                ledger_version,
+            )
+            .context("Failed to retrieve retained account transactions")
+            .map_err(|err| {
+                E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info)
+            })?;
+        let num_retained_txns = retained_txns.len() as u64;
+        let next_seq_number = retained_txns
+            .last()
+            .and_then(|t| t.transaction.try_as_signed_user_txn())
+            .map_or(start_seq_number, |t| t.sequence_number() + 1);
+        let mut txn_data: Vec<TransactionOnChainData> =
+            retained_txns.into_iter().map(Into::into).collect();
+        if num_retained_txns == limit as u64 {
+            return Ok(txn_data);
+        }
+
+        let txns = self
+            .db
+            .get_account_transactions(
+                address,
+                next_seq_number,
+                limit as u64 - num_retained_txns,
                 true,
                 ledger_version,
             )
@@ -764,12 +802,16 @@ impl Context {
             .map_err(|err| {
                 E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info)
             })?;
-        txns.into_inner()
-            .into_iter()
-            .map(|t| self.convert_into_transaction_on_chain_data(t))
-            .collect::<Result<Vec<_>>>()
-            .context("Failed to parse account transactions")
-            .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))
+        for t in txns.into_inner() {
+            txn_data.push(
+                self.convert_into_transaction_on_chain_data(t)
+                    .context("Failed to parse account transactions")
+                    .map_err(|err| {
+                        E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info)
+                    })?,
+            );
+        }
+        Ok(txn_data)
     }
 
     pub fn get_transaction_by_hash(
@@ -777,10 +819,26 @@ impl Context {
         hash: HashValue,
         ledger_version: u64,
     ) -> Result<Option<TransactionOnChainData>> {
-        self.db
-            .get_transaction_by_hash(hash, ledger_version, true)?
-            .map(|t| self.convert_into_transaction_on_chain_data(t))
-            .transpose()
+        let version = match self
+            .db
+            .get_transaction_version_by_hash(hash, ledger_version)?
+        {
+            Some(version) => version,
+            None => return Ok(None),
+        };
+        // Only looks up the retained transactions if the version is older than the prune window.
+        if let Some(txn) = self
+            .db
+            .get_retained_transaction_by_version(version, ledger_version)?
+        {
+            return Ok(Some(txn.into()));
+        }
+        self.convert_into_transaction_on_chain_data(self.db.get_transaction_by_version(
+            version,
+            ledger_version,
+            true,
+        )?)
+        .map(Some)
     }
 
     pub async fn get_pending_transaction_by_hash(
@@ -798,6 +856,36 @@ impl Context {
         callback.await.map_err(anyhow::Error::from)
     }
 
+    pub async fn get_mempool_transaction_status(
+        &self,
+        hash: HashValue,
+    ) -> Result<Option<MempoolTransactionStatus>> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetTransactionStatus(hash, req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
+    pub async fn get_mempool_account_status(
+        &self,
+        address: AccountAddress,
+    ) -> Result<MempoolAccountStatus> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetAccountStatus(address, req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub fn get_transaction_by_version(
         &self,
         version: u64,
@@ -810,6 +898,19 @@ impl Context {
         )?)
     }
 
+    /// Returns the transaction at `version` if it's older than the ledger prune window but kept
+    /// by the ledger pruner.
+    pub fn get_retained_transaction_by_version(
+        &self,
+        version: u64,
+        ledger_version: u64,
+    ) -> Result<Option<TransactionOnChainData>> {
+        Ok(self
+            .db
+            .get_retained_transaction_by_version(version, ledger_version)?
+            .map(Into::into))
+    }
+
     pub fn get_accumulator_root_hash(&self, version: u64) -> Result<HashValue> {
         Ok(self.db.get_accumulator_root_hash(version)?)
     }
@@ -859,6 +960,62 @@ impl Context {
         }
     }
 
+    /// Events before `oldest_version` are pruned from the ledger, so they are never returned.
+    pub fn get_events_by_type(
+        &self,
+        event_type: &TypeTag,
+        start: Option<u64>,
+        limit: u16,
+        oldest_version: u64,
+        ledger_version: u64,
+    ) -> Result<Vec<EventWithVersion>> {
+        let event_by_type_reader = self
+            .event_by_type_reader
+            .as_ref()
+            .ok_or_else(|| anyhow!("Events by type index is not available"))?;
+        let mut event_indices = if let Some(start) = start {
+            event_by_type_reader.get_events_by_type(
+                event_type,
+                std::cmp::max(start, oldest_version),
+                Order::Ascending,
+                limit as u64,
+                ledger_version,
+            )?
+        } else {
+            let mut event_indices = event_by_type_reader.get_events_by_type(
+                event_type,
+                ledger_version,
+                Order::Descending,
+                limit as u64,
+                ledger_version,
+            )?;
+            event_indices.reverse();
+            event_indices
+        };
+        event_indices.retain(|(version, _)| *version >= oldest_version);
+
+        // Events of the same type are often emitted multiple times by one transaction, so the
+        // events of the last read version are kept around.
+        let mut txn_events: Option<(Version, Vec<ContractEvent>)> = None;
+        let mut events = vec![];
+        for (version, index) in event_indices {
+            if txn_events.as_ref().map(|(v, _)| *v) != Some(version) {
+                let events_at_version = self
+                    .db
+                    .get_events_iterator(version, 1)?
+                    .next()
+                    .ok_or_else(|| format_err!("Events at version {} not found", version))??;
+                txn_events = Some((version, events_at_version));
+            }
+            let event = txn_events
+                .as_ref()
+                .and_then(|(_, txn_events)| txn_events.get(index as usize))
+                .ok_or_else(|| format_err!("Event {} at version {} not found", index, version))?;
+            events.push(EventWithVersion::new(version, event.clone()));
+        }
+        Ok(events)
+    }
+
     fn next_bucket(&self, gas_unit_price: u64) -> u64 {
         match self
             .node_config
@@ -0,0 +1,411 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    accept_type::AcceptType,
+    context::{api_spawn_blocking, Context},
+    failpoint::fail_point_poem,
+    metrics,
+    response::{
+        api_disabled, version_pruned, BadRequestError, BasicErrorWith404, ServiceUnavailableError,
+    },
+    ApiTags,
+};
+use anyhow::{bail, Context as AnyhowContext};
+use aptos_api_types::{
+    Address, AptosErrorCode, AsConverter, MoveStructTag, StreamedEvent, VerifyInputWithRecursion,
+    U64,
+};
+use aptos_logger::warn;
+use aptos_types::{
+    account_address::AccountAddress,
+    contract_event::{ContractEvent, EventWithVersion},
+    transaction::Version,
+};
+use aptos_vm::data_cache::AsMoveResolver;
+use futures::stream::{self, BoxStream, StreamExt};
+use move_core_types::language_storage::{StructTag, TypeTag};
+use poem_openapi::{param::Query, payload::EventStream, OpenApi};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Interval at which an SSE comment is sent to keep idle connections open
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+type EventStreamResult =
+    poem::Result<EventStream<BoxStream<'static, StreamedEvent>>, BasicErrorWith404>;
+
+/// API for subscribing to events as they are committed.
+#[derive(Clone)]
+pub struct EventStreamApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl EventStreamApi {
+    /// Subscribe to events
+    ///
+    /// Streams events as server-sent events (`text/event-stream`), starting at the given
+    /// version and following new transactions as they are committed. Events can be filtered
+    /// by event type, by the account the event handle belongs to, and by the creation number
+    /// of the event handle. All given filters must match for an event to be streamed.
+    ///
+    /// Every streamed event carries its `version` and `event_index`. To resume a subscription
+    /// after a disconnect, reconnect with `start_version` set to the last received `version`
+    /// and `start_event_index` set to the last received `event_index` plus one.
+    ///
+    /// If the stream falls behind the pruning window, it is closed by the server.
+    #[oai(
+        path = "/events/stream",
+        method = "get",
+        operation_id = "stream_events",
+        tag = "ApiTags::Events"
+    )]
+    async fn stream_events(
+        &self,
+        /// Move struct type of the events to stream e.g. `0x1::coin::DepositEvent`
+        event_type: Query<Option<MoveStructTag>>,
+        /// Hex-encoded 32 byte Aptos account, with or without a `0x` prefix, that the event
+        /// handle belongs to. Module events have no event handle and never match this filter.
+        account: Query<Option<Address>>,
+        /// Creation number of the event handle within `account`
+        ///
+        /// Can only be used together with `account`.
+        creation_number: Query<Option<U64>>,
+        /// Version to start streaming events from
+        ///
+        /// If unspecified, only events committed after the subscription starts are streamed
+        start_version: Query<Option<U64>>,
+        /// Index of the first event to stream within `start_version`
+        ///
+        /// If unspecified, defaults to 0
+        start_event_index: Query<Option<U64>>,
+    ) -> EventStreamResult {
+        fail_point_poem("endpoint_stream_events")?;
+        self.context
+            .check_api_output_enabled("Stream events", &AcceptType::Json)?;
+        if !self.context.node_config.api.event_stream_enabled {
+            return Err(api_disabled("Stream events"));
+        }
+
+        let filter =
+            EventFilter::new(event_type.0, account.0, creation_number.0).map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+
+        let context = self.context.clone();
+        let cursor = api_spawn_blocking(move || {
+            let latest_ledger_info = context.get_latest_ledger_info::<BasicErrorWith404>()?;
+            let version = match start_version.0 {
+                Some(version) => version.0,
+                None => latest_ledger_info.version() + 1,
+            };
+            if version < latest_ledger_info.oldest_ledger_version.0 {
+                return Err(version_pruned(version, &latest_ledger_info));
+            }
+            Ok(EventCursor {
+                version,
+                event_index: start_event_index.0.map(|index| index.0).unwrap_or(0),
+            })
+        })
+        .await?;
+
+        let guard = ActiveSubscriptionGuard::acquire(
+            self.context.event_stream_active_connections.clone(),
+            self.context
+                .node_config
+                .api
+                .event_stream_max_active_connections,
+        )
+        .ok_or_else(|| {
+            BasicErrorWith404::service_unavailable_with_code_no_info(
+                "Too many active event stream subscriptions",
+                AptosErrorCode::InternalError,
+            )
+        })?;
+
+        let subscription = EventSubscription {
+            context: self.context.clone(),
+            filter,
+            cursor,
+            pending: VecDeque::new(),
+            _guard: guard,
+        };
+        Ok(EventStream::new(subscription.into_stream()).keep_alive(KEEP_ALIVE_INTERVAL))
+    }
+}
+
+/// Position of a subscription in the ledger: the next event to consider is the
+/// `event_index`-th event emitted by the transaction at `version`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct EventCursor {
+    version: Version,
+    event_index: u64,
+}
+
+/// Filter that every event has to match before it is sent to the subscriber
+#[derive(Clone, Debug, Default)]
+struct EventFilter {
+    event_type: Option<TypeTag>,
+    account: Option<AccountAddress>,
+    creation_number: Option<u64>,
+}
+
+impl EventFilter {
+    fn new(
+        event_type: Option<MoveStructTag>,
+        account: Option<Address>,
+        creation_number: Option<U64>,
+    ) -> anyhow::Result<Self> {
+        if creation_number.is_some() && account.is_none() {
+            bail!("'creation_number' can only be used together with 'account'");
+        }
+        let event_type = event_type
+            .map(|event_type| {
+                event_type.verify(0).context("'event_type' invalid")?;
+                StructTag::try_from(event_type)
+                    .map(|tag| TypeTag::Struct(Box::new(tag)))
+                    .context("'event_type' invalid")
+            })
+            .transpose()?;
+
+        Ok(Self {
+            event_type,
+            account: account.map(Into::into),
+            creation_number: creation_number.map(|number| number.0),
+        })
+    }
+
+    fn matches(&self, event: &ContractEvent) -> bool {
+        if let Some(event_type) = &self.event_type {
+            if event.type_tag() != event_type {
+                return false;
+            }
+        }
+        if self.account.is_none() && self.creation_number.is_none() {
+            return true;
+        }
+        match event.event_key() {
+            Some(key) => {
+                self.account
+                    .map_or(true, |account| key.get_creator_address() == account)
+                    && self
+                        .creation_number
+                        .map_or(true, |number| key.get_creation_number() == number)
+            },
+            // Module events aren't emitted to an event handle
+            None => false,
+        }
+    }
+}
+
+/// Keeps track of the number of active subscriptions. The count is released when the
+/// subscription is dropped, i.e. when the stream ends or the client disconnects.
+struct ActiveSubscriptionGuard {
+    active_connections: Arc<AtomicUsize>,
+}
+
+impl ActiveSubscriptionGuard {
+    fn acquire(
+        active_connections: Arc<AtomicUsize>,
+        max_active_connections: usize,
+    ) -> Option<Self> {
+        if active_connections.fetch_add(1, Ordering::Relaxed) >= max_active_connections {
+            active_connections.fetch_sub(1, Ordering::Relaxed);
+            return None;
+        }
+        metrics::EVENT_STREAM_SUBSCRIPTIONS.inc();
+        Some(Self { active_connections })
+    }
+}
+
+impl Drop for ActiveSubscriptionGuard {
+    fn drop(&mut self) {
+        self.active_connections.fetch_sub(1, Ordering::Relaxed);
+        metrics::EVENT_STREAM_SUBSCRIPTIONS.dec();
+    }
+}
+
+struct EventSubscription {
+    context: Arc<Context>,
+    filter: EventFilter,
+    cursor: EventCursor,
+    pending: VecDeque<StreamedEvent>,
+    _guard: ActiveSubscriptionGuard,
+}
+
+impl EventSubscription {
+    fn into_stream(self) -> BoxStream<'static, StreamedEvent> {
+        stream::unfold(self, |mut subscription| async move {
+            loop {
+                if let Some(event) = subscription.pending.pop_front() {
+                    metrics::EVENT_STREAM_EVENTS_SENT.inc();
+                    return Some((event, subscription));
+                }
+                if let Err(err) = subscription.fetch_next().await {
+                    warn!(
+                        "Closing event stream at version {}: {:#}",
+                        subscription.cursor.version, err
+                    );
+                    return None;
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Fetches the next batch of matching events, waiting for new transactions to be
+    /// committed if the subscription has caught up with the ledger.
+    async fn fetch_next(&mut self) -> anyhow::Result<()> {
+        let context = self.context.clone();
+        let filter = self.filter.clone();
+        let cursor = self.cursor;
+        let (events, next_cursor) =
+            tokio::task::spawn_blocking(move || fetch_events(&context, &filter, cursor)).await??;
+
+        if next_cursor == self.cursor {
+            tokio::time::sleep(Duration::from_millis(
+                self.context.node_config.api.event_stream_poll_interval_ms,
+            ))
+            .await;
+        }
+        self.cursor = next_cursor;
+        self.pending.extend(events);
+        Ok(())
+    }
+}
+
+/// Reads up to a page of transactions starting at `cursor` and returns the matching events,
+/// rendered as JSON, along with the cursor to continue from.
+fn fetch_events(
+    context: &Context,
+    filter: &EventFilter,
+    cursor: EventCursor,
+) -> anyhow::Result<(Vec<StreamedEvent>, EventCursor)> {
+    let latest_ledger_info = context.get_latest_ledger_info_wrapped()?;
+    let ledger_version = latest_ledger_info.version();
+    if cursor.version > ledger_version {
+        return Ok((vec![], cursor));
+    }
+    if cursor.version < latest_ledger_info.oldest_ledger_version.0 {
+        bail!("Version {} has been pruned", cursor.version);
+    }
+
+    let limit = std::cmp::min(
+        context.max_transactions_page_size() as u64,
+        ledger_version - cursor.version + 1,
+    );
+    let mut matched = vec![];
+    let mut event_indices = vec![];
+    for (offset, events) in context
+        .db
+        .get_events_iterator(cursor.version, limit)?
+        .enumerate()
+    {
+        let version = cursor.version + offset as u64;
+        let skip = if version == cursor.version {
+            cursor.event_index as usize
+        } else {
+            0
+        };
+        for (event_index, event) in events?.into_iter().enumerate().skip(skip) {
+            if filter.matches(&event) {
+                event_indices.push(event_index as u64);
+                matched.push(EventWithVersion::new(version, event));
+            }
+        }
+    }
+
+    let next_cursor = EventCursor {
+        version: cursor.version + limit,
+        event_index: 0,
+    };
+    if matched.is_empty() {
+        return Ok((vec![], next_cursor));
+    }
+
+    let events = context
+        .latest_state_view()?
+        .as_move_resolver()
+        .as_converter(context.db.clone(), context.table_info_reader.clone())
+        .try_into_versioned_events(&matched)
+        .context("Failed to convert events from storage into response")?
+        .into_iter()
+        .zip(event_indices)
+        .map(|(event, event_index)| StreamedEvent {
+            event_index: event_index.into(),
+            event,
+        })
+        .collect();
+    Ok((events, next_cursor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::event::EventKey;
+    use std::str::FromStr;
+
+    fn v1_event(address: AccountAddress, creation_number: u64, type_tag: &str) -> ContractEvent {
+        ContractEvent::new_v1(
+            EventKey::new(creation_number, address),
+            0,
+            TypeTag::from_str(type_tag).unwrap(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_event_filter_matches() {
+        let deposit = "0x1::coin::DepositEvent";
+        let withdraw = "0x1::coin::WithdrawEvent";
+        let alice = AccountAddress::from_hex_literal("0xa").unwrap();
+        let bob = AccountAddress::from_hex_literal("0xb").unwrap();
+        let module_event = ContractEvent::new_v2_with_type_tag_str(deposit, vec![]);
+
+        // An empty filter matches everything
+        let filter = EventFilter::default();
+        assert!(filter.matches(&v1_event(alice, 0, deposit)));
+        assert!(filter.matches(&module_event));
+
+        // Filter by event type
+        let filter = EventFilter::new(Some(deposit.parse().unwrap()), None, None).unwrap();
+        assert!(filter.matches(&v1_event(alice, 0, deposit)));
+        assert!(filter.matches(&module_event));
+        assert!(!filter.matches(&v1_event(alice, 0, withdraw)));
+
+        // Filter by account, which never matches module events
+        let filter = EventFilter::new(None, Some(alice.into()), None).unwrap();
+        assert!(filter.matches(&v1_event(alice, 3, withdraw)));
+        assert!(!filter.matches(&v1_event(bob, 3, withdraw)));
+        assert!(!filter.matches(&module_event));
+
+        // Filter by event handle
+        let filter = EventFilter::new(None, Some(alice.into()), Some(3.into())).unwrap();
+        assert!(filter.matches(&v1_event(alice, 3, withdraw)));
+        assert!(!filter.matches(&v1_event(alice, 4, withdraw)));
+    }
+
+    #[test]
+    fn test_event_filter_requires_account_for_creation_number() {
+        assert!(EventFilter::new(None, None, Some(3.into())).is_err());
+    }
+
+    #[test]
+    fn test_active_subscription_guard() {
+        let active_connections = Arc::new(AtomicUsize::new(0));
+        let first = ActiveSubscriptionGuard::acquire(active_connections.clone(), 1).unwrap();
+        assert!(ActiveSubscriptionGuard::acquire(active_connections.clone(), 1).is_none());
+        assert_eq!(active_connections.load(Ordering::Relaxed), 1);
+
+        drop(first);
+        assert_eq!(active_connections.load(Ordering::Relaxed), 0);
+        assert!(ActiveSubscriptionGuard::acquire(active_connections, 1).is_some());
+    }
+}
@@ -9,8 +9,8 @@ use crate::{
     failpoint::fail_point_poem,
     page::Page,
     response::{
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        InternalError,
+        api_disabled, version_pruned, BadRequestError, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResultWith404, InternalError,
     },
     ApiTags,
 };
@@ -19,8 +19,9 @@ use aptos_api_types::{
     verify_field_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo,
     MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
 };
-use aptos_types::event::EventKey;
+use aptos_types::{contract_event::EventWithVersion, event::EventKey};
 use aptos_vm::data_cache::AsMoveResolver;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use poem_openapi::{
     param::{Path, Query},
     OpenApi,
@@ -151,6 +152,106 @@ impl EventsApi {
         })
         .await
     }
+
+    /// Get events by event type
+    ///
+    /// This API returns events of the given Move type emitted by any account, including
+    /// module events which are not emitted to an event handle. Events are returned in the
+    /// order they were emitted. A page never ends in the middle of a transaction, so the next
+    /// page starts at the version following the last returned event.
+    ///
+    /// This API requires the node to index events by type. The index only covers the transactions
+    /// processed while indexing was enabled, so results for versions before that are incomplete.
+    /// Events pruned from the ledger are never returned, and a start version older than the oldest
+    /// ledger version is rejected as pruned.
+    #[oai(
+        path = "/events/by_type/:event_type",
+        method = "get",
+        operation_id = "get_events_by_type",
+        tag = "ApiTags::Events"
+    )]
+    async fn get_events_by_type(
+        &self,
+        accept_type: AcceptType,
+        /// Move struct type of the events e.g. `0x1::coin::DepositEvent`
+        event_type: Path<MoveStructTag>,
+        /// Starting version of events.
+        ///
+        /// If unspecified, by default will retrieve the most recent events
+        start: Query<Option<U64>>,
+        /// Last version (inclusive) of events to retrieve.
+        ///
+        /// If unspecified, defaults to the latest ledger version
+        end: Query<Option<U64>>,
+        /// Max number of events to retrieve.
+        ///
+        /// If unspecified, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        event_type
+            .0
+            .verify(0)
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_events_by_type")?;
+        self.context
+            .check_api_output_enabled("Get events by type", &accept_type)?;
+        if self.context.event_by_type_reader.is_none() {
+            return Err(api_disabled("Get events by type"));
+        }
+        let page = Page::new(
+            start.0.map(|v| v.0),
+            limit.0,
+            self.context.max_events_page_size(),
+        );
+
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            let latest_ledger_info = api.context.get_latest_ledger_info::<BasicErrorWith404>()?;
+            let event_type: StructTag = event_type
+                .0
+                .try_into()
+                .context("'event_type' invalid")
+                .map_err(|err| {
+                    BasicErrorWith404::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                        &latest_ledger_info,
+                    )
+                })?;
+            let oldest_version = latest_ledger_info.oldest_ledger_version.0;
+            if let Some(start) = page.start_option() {
+                if start < oldest_version {
+                    return Err(version_pruned(start, &latest_ledger_info));
+                }
+            }
+            let end_version = match end.0 {
+                Some(end) => std::cmp::min(end.0, latest_ledger_info.version()),
+                None => latest_ledger_info.version(),
+            };
+            let events = api
+                .context
+                .get_events_by_type(
+                    &TypeTag::Struct(Box::new(event_type.clone())),
+                    page.start_option(),
+                    page.limit(&latest_ledger_info)?,
+                    oldest_version,
+                    end_version,
+                )
+                .context(format!("Failed to find events by type {}", event_type))
+                .map_err(|err| {
+                    BasicErrorWith404::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &latest_ledger_info,
+                    )
+                })?;
+            api.render(latest_ledger_info, accept_type, events)
+        })
+        .await
+    }
 }
 
 impl EventsApi {
@@ -180,6 +281,16 @@ impl EventsApi {
                 )
             })?;
 
+        self.render(latest_ledger_info, accept_type, events)
+    }
+
+    /// Render events from storage in the requested output format
+    fn render(
+        &self,
+        latest_ledger_info: LedgerInfo,
+        accept_type: AcceptType,
+        events: Vec<EventWithVersion>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
         match accept_type {
             AcceptType::Json => {
                 let events = self
@@ -12,10 +12,12 @@ mod blocks;
 mod check_size;
 pub mod context;
 mod error_converter;
+mod event_stream;
 mod events;
 mod failpoint;
 mod index;
 mod log;
+mod mempool;
 pub mod metrics;
 mod page;
 mod response;
@@ -44,6 +46,9 @@ pub enum ApiTags {
     /// General information
     General,
 
+    /// Inspection of pending transactions in mempool
+    Mempool,
+
     /// Access to tables
     Tables,
 
@@ -0,0 +1,174 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    accept_type::AcceptType,
+    context::Context,
+    failpoint::fail_point_poem,
+    response::{
+        api_disabled, transaction_not_found_by_hash, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResultWith404,
+    },
+    ApiTags,
+};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    Address, AptosErrorCode, HashValue, MempoolAccount, MempoolTransaction, MempoolTransactionState,
+};
+use aptos_mempool::MempoolTransactionStatus;
+use poem_openapi::{param::Path, OpenApi};
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// API for inspecting the pending transactions in the mempool of the node.
+#[derive(Clone)]
+pub struct MempoolApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl MempoolApi {
+    /// Get mempool transaction by hash
+    ///
+    /// Look up a pending transaction in the mempool of this node, to find out why it is not
+    /// committed yet: whether it's ready to be included in a block or parked waiting for a
+    /// transaction with a lower sequence number, how it ranks against other transactions, how
+    /// long it has been in mempool and which peers it has been broadcast to.
+    ///
+    /// Mempool state is local to the node, other nodes may hold the transaction differently.
+    #[oai(
+        path = "/mempool/transactions/:txn_hash",
+        method = "get",
+        operation_id = "get_mempool_transaction",
+        tag = "ApiTags::Mempool"
+    )]
+    async fn get_mempool_transaction(
+        &self,
+        accept_type: AcceptType,
+        /// Hash of transaction to retrieve
+        txn_hash: Path<HashValue>,
+    ) -> BasicResultWith404<MempoolTransaction> {
+        fail_point_poem("endpoint_get_mempool_transaction")?;
+        self.context
+            .check_api_output_enabled("Get mempool transaction", &accept_type)?;
+        if !self.context.node_config.api.mempool_inspection_enabled {
+            return Err(api_disabled("Get mempool transaction"));
+        }
+
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let status = self
+            .context
+            .get_mempool_transaction_status(txn_hash.0.into())
+            .await
+            .context(format!(
+                "Failed to get mempool transaction by hash {}",
+                txn_hash.0
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?
+            .ok_or_else(|| transaction_not_found_by_hash(txn_hash.0, &ledger_info))?;
+
+        BasicResponse::try_from_rust_value((
+            to_mempool_transaction(status, SystemTime::now()),
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            &accept_type,
+        ))
+    }
+
+    /// Get mempool transactions of an account
+    ///
+    /// List the pending transactions of an account in the mempool of this node, in sequence
+    /// number order, along with the sequence number of the account as last seen by mempool.
+    /// A gap between that sequence number and the lowest pending one explains transactions
+    /// that are parked.
+    #[oai(
+        path = "/mempool/accounts/:address",
+        method = "get",
+        operation_id = "get_mempool_account",
+        tag = "ApiTags::Mempool"
+    )]
+    async fn get_mempool_account(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+    ) -> BasicResultWith404<MempoolAccount> {
+        fail_point_poem("endpoint_get_mempool_account")?;
+        self.context
+            .check_api_output_enabled("Get mempool account", &accept_type)?;
+        if !self.context.node_config.api.mempool_inspection_enabled {
+            return Err(api_disabled("Get mempool account"));
+        }
+
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let status = self
+            .context
+            .get_mempool_account_status(address.0.into())
+            .await
+            .context(format!(
+                "Failed to get mempool transactions of account {}",
+                address.0
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        let now = SystemTime::now();
+        let account = MempoolAccount {
+            address: address.0,
+            sequence_number: status.sequence_number.map(Into::into),
+            transactions: status
+                .transactions
+                .into_iter()
+                .map(|txn| to_mempool_transaction(txn, now))
+                .collect(),
+        };
+        BasicResponse::try_from_rust_value((
+            account,
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            &accept_type,
+        ))
+    }
+}
+
+fn to_mempool_transaction(status: MempoolTransactionStatus, now: SystemTime) -> MempoolTransaction {
+    let insertion_timestamp_usecs = status
+        .insertion_time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros() as u64;
+    let time_in_mempool_ms = now
+        .duration_since(status.insertion_time)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    MempoolTransaction {
+        hash: status.txn.committed_hash().into(),
+        sender: status.txn.sender().into(),
+        sequence_number: status.txn.sequence_number().into(),
+        state: match status.state {
+            aptos_mempool::MempoolTransactionState::Ready => MempoolTransactionState::Ready,
+            aptos_mempool::MempoolTransactionState::Parked => MempoolTransactionState::Parked,
+        },
+        ranking_score: status.ranking_score.into(),
+        insertion_timestamp_usecs: insertion_timestamp_usecs.into(),
+        time_in_mempool_ms: time_in_mempool_ms.into(),
+        broadcast_peers: status
+            .broadcast_peers
+            .iter()
+            .map(|peer| peer.to_string())
+            .collect(),
+    }
+}
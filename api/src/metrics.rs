@@ -4,8 +4,8 @@
 
 use aptos_global_constants::DEFAULT_BUCKETS;
 use aptos_metrics_core::{
-    exponential_buckets, register_histogram_vec, register_int_counter_vec, register_int_gauge,
-    HistogramVec, IntCounterVec, IntGauge,
+    exponential_buckets, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, HistogramVec, IntCounter, IntCounterVec, IntGauge,
 };
 use once_cell::sync::Lazy;
 
@@ -105,3 +105,19 @@ pub static WAIT_TRANSACTION_POLL_TIME: Lazy<HistogramVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static EVENT_STREAM_SUBSCRIPTIONS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_api_event_stream_subscriptions",
+        "Number of active event stream subscriptions"
+    )
+    .unwrap()
+});
+
+pub static EVENT_STREAM_EVENTS_SENT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_api_event_stream_events_sent",
+        "Number of events sent to event stream subscribers"
+    )
+    .unwrap()
+});
@@ -4,13 +4,15 @@
 
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
-    context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    context::Context, error_converter::convert_error, event_stream::EventStreamApi,
+    events::EventsApi, index::IndexApi, log::middleware_log, mempool::MempoolApi, set_failpoints,
+    state::StateApi, transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_config::config::{ApiConfig, NodeConfig};
-use aptos_db_indexer::table_info_reader::TableInfoReader;
+use aptos_db_indexer::{
+    event_by_type_reader::EventByTypeReader, table_info_reader::TableInfoReader,
+};
 use aptos_logger::info;
 use aptos_mempool::MempoolClientSender;
 use aptos_storage_interface::DbReader;
@@ -36,11 +38,19 @@ pub fn bootstrap(
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
     table_info_reader: Option<Arc<dyn TableInfoReader>>,
+    event_by_type_reader: Option<Arc<dyn EventByTypeReader>>,
 ) -> anyhow::Result<Runtime> {
     let max_runtime_workers = get_max_runtime_workers(&config.api);
     let runtime = aptos_runtimes::spawn_named_runtime("api".into(), Some(max_runtime_workers));
 
-    let context = Context::new(chain_id, db, mp_sender, config.clone(), table_info_reader);
+    let context = Context::new(
+        chain_id,
+        db,
+        mp_sender,
+        config.clone(),
+        table_info_reader,
+        event_by_type_reader,
+    );
 
     attach_poem_to_runtime(runtime.handle(), context.clone(), config, false)
         .context("Failed to attach poem to runtime")?;
@@ -104,7 +114,9 @@ pub fn get_api_service(
         BasicApi,
         BlocksApi,
         EventsApi,
+        EventStreamApi,
         IndexApi,
+        MempoolApi,
         StateApi,
         TransactionsApi,
         ViewFunctionApi,
@@ -125,9 +137,15 @@ pub fn get_api_service(
         EventsApi {
             context: context.clone(),
         },
+        EventStreamApi {
+            context: context.clone(),
+        },
         IndexApi {
             context: context.clone(),
         },
+        MempoolApi {
+            context: context.clone(),
+        },
         StateApi {
             context: context.clone(),
         },
@@ -0,0 +1,100 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context_with_config;
+use aptos_api_test_context::{current_function_name, ApiSpecificConfig, TestContext};
+use aptos_config::config::NodeConfig;
+use aptos_types::account_address::AccountAddress;
+use serde_json::Value;
+use std::{str::FromStr, time::Duration};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_events() {
+    let mut context = new_test_context_with_config(current_function_name!(), event_stream_config());
+
+    // Subscribe to the coin register events of an account that doesn't exist yet
+    let account = context.gen_account();
+    let mut response = open_event_stream(
+        &context,
+        &format!(
+            "event_type=0x1::account::CoinRegisterEvent&account={}",
+            account.address()
+        ),
+    )
+    .await;
+    assert_eq!(response.status(), 200);
+
+    // Create the account, which registers it for the coin
+    let subscription_version = context.get_latest_ledger_info().version();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&[txn]).await;
+
+    // Verify the event of the account arrives (and not the ones of other accounts)
+    let event = tokio::time::timeout(Duration::from_secs(30), next_event(&mut response))
+        .await
+        .expect("timed out waiting for the event");
+    assert_eq!(event["type"], "0x1::account::CoinRegisterEvent");
+    assert_eq!(
+        AccountAddress::from_str(event["guid"]["account_address"].as_str().unwrap()).unwrap(),
+        account.address()
+    );
+    let version: u64 = event["version"].as_str().unwrap().parse().unwrap();
+    assert!(version > subscription_version);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_events_subscription_limit() {
+    let mut node_config = event_stream_config();
+    node_config.api.event_stream_max_active_connections = 1;
+    let context = new_test_context_with_config(current_function_name!(), node_config);
+
+    // The first subscription is accepted
+    let response = open_event_stream(&context, "").await;
+    assert_eq!(response.status(), 200);
+
+    // Any further subscription is rejected while the first one is active
+    let rejected = open_event_stream(&context, "").await;
+    assert_eq!(rejected.status(), 503);
+    drop(response);
+}
+
+/// Returns a node config with the event stream enabled
+fn event_stream_config() -> NodeConfig {
+    let mut node_config = NodeConfig::default();
+    node_config.api.event_stream_enabled = true;
+    node_config.api.event_stream_poll_interval_ms = 10;
+    node_config
+}
+
+/// Opens an event stream with the given query directly against the API (as
+/// the test routes buffer the whole response)
+async fn open_event_stream(context: &TestContext, query: &str) -> reqwest::Response {
+    let ApiSpecificConfig::V1(address) = context.api_specific_config;
+    let url = format!(
+        "http://{}{}?{}",
+        address,
+        context.prepend_path("/events/stream"),
+        query
+    );
+    reqwest::get(url).await.unwrap()
+}
+
+/// Reads the next event (skipping keep alive comments) from an event stream
+async fn next_event(response: &mut reqwest::Response) -> Value {
+    let mut buffer = String::new();
+    loop {
+        if let Some(end) = buffer.find("\n\n") {
+            let message: String = buffer.drain(..end + 2).collect();
+            if let Some(data) = message.lines().find_map(|line| line.strip_prefix("data:")) {
+                return serde_json::from_str(data.trim()).unwrap();
+            }
+            continue;
+        }
+        let chunk = response
+            .chunk()
+            .await
+            .unwrap()
+            .expect("the event stream ended");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+}
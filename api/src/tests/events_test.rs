@@ -2,8 +2,9 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_config::config::{LedgerPrunerConfig, NodeConfig};
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use serde_json::json;
 use std::path::PathBuf;
@@ -189,6 +190,160 @@ async fn test_module_events() {
     assert_eq!(events.len(), 8);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type() {
+    let mut node_config = NodeConfig::default();
+    node_config.indexer_table_info.index_events_by_type = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    // Publish the event module and emit 3 events in one transaction and 2 in the next
+    let mut user = context.create_account().await;
+    let user_addr = user.address();
+    let named_addresses = vec![("event".to_string(), user_addr)];
+    let txn = futures::executor::block_on(async move {
+        let path = PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
+            .join("../aptos-move/move-examples/event");
+        TestContext::build_package(path, named_addresses)
+    });
+    context.publish_package(&mut user, txn).await;
+    for num_events in ["3", "2"] {
+        context
+            .api_execute_entry_function(
+                &mut user,
+                &format!("0x{}::event::emit", user_addr.to_hex()),
+                json!([]),
+                json!([num_events]),
+            )
+            .await;
+    }
+
+    // Returns the (version, seq) of every event in the response
+    let get_events = |resp: serde_json::Value| -> Vec<(u64, u64)> {
+        resp.as_array()
+            .unwrap()
+            .iter()
+            .map(|event| {
+                (
+                    event["version"].as_str().unwrap().parse().unwrap(),
+                    event["data"]["seq"].as_str().unwrap().parse().unwrap(),
+                )
+            })
+            .collect()
+    };
+    let path = format!("/events/by_type/0x{}::event::MyEvent", user_addr.to_hex());
+
+    // All events are returned in the order they were emitted
+    let events = get_events(context.get(&path).await);
+    assert_eq!(events.len(), 5);
+    let (first_version, second_version) = (events[0].0, events[3].0);
+    assert!(first_version < second_version);
+    assert_eq!(events, vec![
+        (first_version, 0),
+        (first_version, 1),
+        (first_version, 2),
+        (second_version, 0),
+        (second_version, 1),
+    ]);
+
+    // Without a start, the most recent events are returned
+    let events = get_events(context.get(&format!("{}?limit=2", path)).await);
+    assert_eq!(events, vec![(second_version, 0), (second_version, 1)]);
+
+    // A page never ends in the middle of a transaction
+    let events = get_events(
+        context
+            .get(&format!("{}?start={}&limit=1", path, first_version))
+            .await,
+    );
+    assert_eq!(events, vec![
+        (first_version, 0),
+        (first_version, 1),
+        (first_version, 2)
+    ]);
+
+    // The next page starts at the version following the last returned event
+    let events = get_events(
+        context
+            .get(&format!("{}?start={}", path, first_version + 1))
+            .await,
+    );
+    assert_eq!(events, vec![(second_version, 0), (second_version, 1)]);
+
+    // Events after the end version are not returned
+    let events = get_events(
+        context
+            .get(&format!("{}?start=0&end={}", path, first_version))
+            .await,
+    );
+    assert_eq!(events.len(), 3);
+
+    // Events of other types are not returned
+    let resp = context
+        .get(&format!(
+            "/events/by_type/0x{}::event::Field",
+            user_addr.to_hex()
+        ))
+        .await;
+    assert!(resp.as_array().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type_pruned() {
+    let mut node_config = NodeConfig::default();
+    node_config.indexer_table_info.index_events_by_type = true;
+    node_config
+        .storage
+        .storage_pruner_config
+        .ledger_pruner_config = LedgerPrunerConfig {
+        enable: true,
+        prune_window: 2,
+        batch_size: 1,
+        user_pruning_window_offset: 0,
+        ..LedgerPrunerConfig::default()
+    };
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    // Every block emits a new block event, the ledger pruner removes the older blocks
+    for _ in 0..3 {
+        context.create_account().await;
+    }
+    context.db.wait_for_ledger_pruner().unwrap();
+    let oldest_version: u64 = context.get_latest_ledger_info().oldest_ledger_version.0;
+    assert!(oldest_version > 0);
+    let path = "/events/by_type/0x1::block::NewBlockEvent";
+
+    // Without a start, the events of pruned versions are skipped
+    let resp = context.get(path).await;
+    let versions: Vec<u64> = resp
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| event["version"].as_str().unwrap().parse().unwrap())
+        .collect();
+    assert!(!versions.is_empty());
+    assert!(versions.iter().all(|version| *version >= oldest_version));
+
+    // Starting before the oldest ledger version is rejected as pruned
+    context
+        .expect_status_code(410)
+        .get(&format!("{}?start={}", path, oldest_version - 1))
+        .await;
+    context
+        .expect_status_code(200)
+        .get(&format!("{}?start={}", path, oldest_version))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type_disabled() {
+    let context = new_test_context(current_function_name!());
+
+    context
+        .expect_status_code(403)
+        .get("/events/by_type/0x1::coin::DepositEvent")
+        .await;
+}
+
 // until we have generics in the genesis
 #[ignore]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -0,0 +1,114 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_config::config::NodeConfig;
+use aptos_types::transaction::SignedTransaction;
+use serde_json::json;
+
+const UNKNOWN_HASH: &str = "0xdadfeddcca7cb6396c735e9094c76c6e4e9cb3e3ef814730693aed59bd87b31d";
+
+fn new_test_context_with_mempool_inspection(test_name: String) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.api.mempool_inspection_enabled = true;
+    new_test_context_with_config(test_name, node_config)
+}
+
+async fn submit(context: &TestContext, txn: &SignedTransaction) {
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", bcs::to_bytes(txn).unwrap())
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_mempool_inspection_disabled() {
+    let context = new_test_context(current_function_name!());
+
+    let resp = context
+        .expect_status_code(403)
+        .get(&format!("/mempool/transactions/{}", UNKNOWN_HASH))
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+    let resp = context
+        .expect_status_code(403)
+        .get("/mempool/accounts/0x1")
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_mempool_transaction_not_found() {
+    let context = new_test_context_with_mempool_inspection(current_function_name!());
+
+    let resp = context
+        .expect_status_code(404)
+        .get(&format!("/mempool/transactions/{}", UNKNOWN_HASH))
+        .await;
+    assert_eq!(resp["error_code"], "transaction_not_found");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_mempool_account() {
+    let context = new_test_context_with_mempool_inspection(current_function_name!());
+    let root = context.root_account().await;
+    let receiver = context.gen_account().address();
+    let sequence_number = root.sequence_number();
+    let factory = context.transaction_factory();
+
+    // The transaction after the gap in sequence numbers is parked.
+    let ready_txn = root
+        .sign_with_transaction_builder(factory.account_transfer(receiver, 1).gas_unit_price(100));
+    root.increment_sequence_number();
+    let parked_txn = root
+        .sign_with_transaction_builder(factory.account_transfer(receiver, 1).gas_unit_price(200));
+    submit(&context, &ready_txn).await;
+    submit(&context, &parked_txn).await;
+
+    let resp = context
+        .get(&format!("/mempool/accounts/{}", root.address()))
+        .await;
+    assert_eq!(resp["address"], root.address().to_hex_literal());
+    assert_eq!(resp["sequence_number"], sequence_number.to_string());
+    let summary: Vec<_> = resp["transactions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|txn| {
+            json!({
+                "hash": txn["hash"],
+                "sequence_number": txn["sequence_number"],
+                "state": txn["state"],
+                "ranking_score": txn["ranking_score"],
+            })
+        })
+        .collect();
+    // Transactions are ranked by gas unit price.
+    assert_eq!(summary, vec![
+        json!({
+            "hash": ready_txn.committed_hash().to_hex_literal(),
+            "sequence_number": sequence_number.to_string(),
+            "state": "ready",
+            "ranking_score": "100",
+        }),
+        json!({
+            "hash": parked_txn.committed_hash().to_hex_literal(),
+            "sequence_number": (sequence_number + 2).to_string(),
+            "state": "parked",
+            "ranking_score": "200",
+        }),
+    ]);
+
+    let resp = context
+        .get(&format!(
+            "/mempool/transactions/{}",
+            parked_txn.committed_hash().to_hex_literal()
+        ))
+        .await;
+    assert_eq!(resp["hash"], parked_txn.committed_hash().to_hex_literal());
+    assert_eq!(resp["sender"], root.address().to_hex_literal());
+    assert_eq!(resp["state"], "parked");
+    assert_eq!(resp["ranking_score"], "200");
+    assert_eq!(resp["broadcast_peers"], json!([]));
+}
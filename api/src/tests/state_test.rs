@@ -1,10 +1,15 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_config::config::{NodeConfig, StateMerklePrunerConfig};
 use aptos_sdk::{transaction_builder::aptos_stdlib::aptos_token_stdlib, types::LocalAccount};
 use aptos_storage_interface::DbReader;
+use aptos_types::{
+    account_config::{aptos_test_root_address, AccountResource},
+    state_store::state_key::StateKey,
+};
 use move_core_types::account_address::AccountAddress;
 use move_package::BuildConfig;
 use serde::Serialize;
@@ -20,6 +25,71 @@ async fn test_get_account_resource() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_after_state_merkle_pruning() {
+    let mut node_config = NodeConfig::default();
+    // Commits a snapshot with every block, so the state merkle pruner moves on with each block.
+    node_config.storage.buffered_state_target_items = 1;
+    node_config
+        .storage
+        .storage_pruner_config
+        .state_merkle_pruner_config = StateMerklePrunerConfig {
+        enable: true,
+        prune_window: 1,
+        batch_size: 1,
+        ..StateMerklePrunerConfig::default()
+    };
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let root = aptos_test_root_address();
+
+    context.create_account().await;
+    let old_version = context.get_latest_ledger_info().version();
+    let old_resource = context
+        .get(&get_account_resource_with_version(
+            &root.to_hex_literal(),
+            "0x1::account::Account",
+            old_version,
+        ))
+        .await;
+    context.create_account().await;
+    context.create_account().await;
+    let latest_version = context.get_latest_ledger_info().version();
+
+    // Commit the latest snapshot and wait for the trees before it to be pruned.
+    context
+        .db
+        .commit_state_and_wait_for_state_merkle_pruner()
+        .unwrap();
+    let state_key = StateKey::resource_typed::<AccountResource>(&root).unwrap();
+    assert!(context
+        .db
+        .get_state_value_with_proof_by_version(&state_key, latest_version)
+        .is_ok());
+    assert!(context
+        .db
+        .get_state_value_with_proof_by_version(&state_key, old_version)
+        .is_err());
+
+    let resp = context
+        .get(&get_account_resource_with_version(
+            &root.to_hex_literal(),
+            "0x1::account::Account",
+            old_version,
+        ))
+        .await;
+    assert_eq!(resp, old_resource);
+    let latest_resource = context
+        .get(&get_account_resource(
+            &root.to_hex_literal(),
+            "0x1::account::Account",
+        ))
+        .await;
+    assert_ne!(
+        resp["data"]["sequence_number"],
+        latest_resource["data"]["sequence_number"]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_resource_by_invalid_address() {
     let mut context = new_test_context(current_function_name!());
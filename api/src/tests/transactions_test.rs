@@ -5,7 +5,9 @@
 use super::new_test_context;
 use crate::tests::new_test_context_with_config;
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
-use aptos_config::config::{GasEstimationStaticOverride, NodeConfig};
+use aptos_config::config::{
+    GasEstimationStaticOverride, LedgerHistoryRetentionConfig, LedgerPrunerConfig, NodeConfig,
+};
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519Signature},
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
@@ -187,6 +189,48 @@ async fn test_post_transaction_rejected_by_mempool() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_rejected_by_submission_filter() {
+    let mut node_config = NodeConfig::default();
+
+    // Deny the transactions sent by the root account
+    node_config.api.transaction_submission_filter = node_config
+        .api
+        .transaction_submission_filter
+        .add_deny_sender(aptos_test_root_address());
+
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+
+    let resp = context
+        .expect_status_code(403)
+        .post_bcs_txn("/transactions", &bcs::to_bytes(&txn).unwrap())
+        .await;
+    assert_eq!(resp["error_code"], "rejected_by_filter");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_rejected_by_mempool_filter() {
+    let mut node_config = NodeConfig::default();
+
+    // Deny the transactions sent by the root account, in mempool only
+    node_config.mempool.transaction_filter = node_config
+        .mempool
+        .transaction_filter
+        .add_deny_sender(aptos_test_root_address());
+
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+
+    let resp = context
+        .expect_status_code(403)
+        .post_bcs_txn("/transactions", &bcs::to_bytes(&txn).unwrap())
+        .await;
+    assert_eq!(resp["error_code"], "rejected_by_filter");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_multi_agent_signed_transaction() {
     let mut context = new_test_context(current_function_name!());
@@ -489,6 +533,84 @@ async fn test_get_transaction_by_hash() {
     assert_json(resp, txns[0].clone());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_retained_transaction() {
+    let account = LocalAccount::generate(&mut thread_rng());
+    let mut node_config = NodeConfig::default();
+    node_config
+        .storage
+        .storage_pruner_config
+        .ledger_pruner_config = LedgerPrunerConfig {
+        enable: true,
+        prune_window: 2,
+        batch_size: 1,
+        user_pruning_window_offset: 0,
+        history_retention: LedgerHistoryRetentionConfig {
+            accounts: vec![account.address()],
+            modules: vec![],
+        },
+        ..LedgerPrunerConfig::default()
+    };
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    // Versions 1 to 3, the account creation writes to the account's resources.
+    let retained_txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![retained_txn.clone()]).await;
+    // Versions 4 to 6.
+    let other_account = context.gen_account();
+    let pruned_txn = context.create_user_account(&other_account).await;
+    context.commit_block(&vec![pruned_txn.clone()]).await;
+    // Moves the prune window past both blocks.
+    for _ in 0..3 {
+        context.commit_block(&[]).await;
+    }
+
+    // The retained transactions are recorded by the pruner in the background.
+    let mut retained = None;
+    for _ in 0..100 {
+        let resp = context
+            .reply(warp::test::request().path(&context.prepend_path("/transactions/by_version/2")))
+            .await;
+        if resp.status() == 200 {
+            retained = Some(serde_json::from_slice::<serde_json::Value>(resp.body()).unwrap());
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    let retained = retained.expect("The transaction should be retained.");
+    assert_eq!(
+        retained["hash"].as_str().unwrap(),
+        retained_txn.committed_hash().to_hex_literal()
+    );
+    let resp = context
+        .get(&format!(
+            "/transactions/by_hash/{}",
+            retained_txn.committed_hash().to_hex_literal()
+        ))
+        .await;
+    assert_json(resp, retained);
+
+    context
+        .expect_status_code(410)
+        .get("/transactions/by_version/5")
+        .await;
+    let mut pruned = false;
+    for _ in 0..100 {
+        let resp = context
+            .reply(warp::test::request().path(&context.prepend_path(&format!(
+                "/transactions/by_hash/{}",
+                pruned_txn.committed_hash().to_hex_literal()
+            ))))
+            .await;
+        if resp.status() == 404 {
+            pruned = true;
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    assert!(pruned, "The transaction should be pruned.");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_transaction_by_hash_not_found() {
     let mut context = new_test_context(current_function_name!());
@@ -1610,6 +1732,91 @@ async fn test_simulation_filter_allow_sender() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulation_at_ledger_version() {
+    let mut context = new_test_context(current_function_name!());
+    let mut account = context.create_account().await;
+    let receiver = context.gen_account();
+    let old_version = context.get_latest_ledger_info().version();
+
+    // Spend most of the balance of the account after the old version
+    let txn = context.account_transfer(&mut account, &receiver, 150_000_000);
+    context.commit_block(&vec![txn]).await;
+
+    // Transferring more than the remaining balance only succeeds against the old version
+    let payload = json!({
+        "type": "entry_function_payload",
+        "function": "0x1::aptos_account::transfer",
+        "type_arguments": [],
+        "arguments": [receiver.address().to_hex_literal(), "100000000"],
+    });
+    let resp = context
+        .simulate_transaction(&account, payload.clone(), 200)
+        .await;
+    assert!(!resp[0]["success"].as_bool().unwrap());
+
+    account.set_sequence_number(0);
+    let resp = context
+        .simulate_transaction_with_endpoint(
+            &account,
+            payload.clone(),
+            &format!("/transactions/simulate?ledger_version={}", old_version),
+            200,
+        )
+        .await;
+    assert_eq!(resp[0]["version"], old_version.to_string());
+    assert!(
+        resp[0]["success"].as_bool().unwrap(),
+        "{}",
+        resp[0]["vm_status"]
+    );
+
+    // Versions in the future cannot be simulated against
+    let latest_version = context.get_latest_ledger_info().version();
+    context
+        .simulate_transaction_with_endpoint(
+            &account,
+            payload,
+            &format!(
+                "/transactions/simulate?ledger_version={}",
+                latest_version + 100
+            ),
+            404,
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulation_with_gas_profile() {
+    let mut context = new_test_context(current_function_name!());
+    let admin0 = context.root_account().await;
+
+    let resp = context
+        .simulate_transaction_with_endpoint(
+            &admin0,
+            json!({
+                "type": "script_payload",
+                "code": {
+                    "bytecode": "a11ceb0b030000000105000100000000050601000000000000000600000000000000001a0102",
+                },
+                "type_arguments": [],
+                "arguments": [],
+            }),
+            "/transactions/simulate_with_gas_profile",
+            200,
+        )
+        .await;
+
+    assert!(!resp["transaction"]["success"].as_bool().unwrap());
+    assert!(resp["transaction"]["vm_status"]
+        .as_str()
+        .unwrap()
+        .contains("Division by zero"));
+    let gas_profile = &resp["gas_profile"];
+    assert_ne!(gas_profile["execution_and_io_internal_gas"], "0");
+    assert!(!gas_profile["operations"].as_array().unwrap().is_empty());
+}
+
 fn gen_string(len: u64) -> String {
     let mut rng = thread_rng();
     std::iter::repeat(())
@@ -221,3 +221,87 @@ async fn test_view_aggregator() {
         .await;
     context.check_golden_output_no_prune(resp);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_batch() {
+    let mut context = new_test_context(current_function_name!());
+    let creator = &mut context.gen_account();
+    let owner = &mut context.gen_account();
+    let txn1 = context.mint_user_account(creator).await;
+    let txn2 = context.account_transfer(creator, owner, 100_000);
+
+    context.commit_block(&vec![txn1, txn2]).await;
+
+    let resp = context
+        .expect_status_code(200)
+        .post(
+            "/view/batch",
+            json!([
+                build_coin_balance_request(&owner.address()),
+                json!({
+                    "function":"0x1::aptos_account::assert_account_exists",
+                    "arguments": vec![owner.address().to_string()],
+                    "type_arguments": [],
+                }),
+                build_coin_decimals_request(),
+            ]),
+        )
+        .await;
+
+    // A failing function doesn't fail the other functions in the batch
+    let results = resp.as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["values"], json!(["100000"]));
+    assert!(results[0]["error"].is_null());
+    assert!(results[1]["values"].is_null());
+    assert_eq!(results[1]["error"]["error_code"], "invalid_input");
+    assert_eq!(results[2]["values"], json!([8]));
+    assert!(results[2]["error"].is_null());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_batch_blocklist() {
+    let mut node_config = NodeConfig::default();
+
+    // Blocklist the balance function.
+    node_config.api.view_filter = ViewFilter::Blocklist(vec![ViewFunctionId {
+        address: AccountAddress::from_str("0x1").unwrap(),
+        module: "coin".to_string(),
+        function_name: "balance".to_string(),
+    }]);
+
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    let resp = context
+        .expect_status_code(200)
+        .post(
+            "/view/batch",
+            json!([
+                build_coin_balance_request(&AccountAddress::ONE),
+                build_coin_decimals_request(),
+            ]),
+        )
+        .await;
+
+    let results = resp.as_array().unwrap();
+    assert!(results[0]["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("is not allowed"));
+    assert_eq!(results[1]["values"], json!([8]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_batch_too_large() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.max_view_function_batch_size = 1;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    context
+        .expect_status_code(400)
+        .post(
+            "/view/batch",
+            json!([build_coin_decimals_request(), build_coin_decimals_request()]),
+        )
+        .await;
+}
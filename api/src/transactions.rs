@@ -22,19 +22,25 @@ use crate::{
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode,
-    AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue,
-    HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
-    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
+    AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, GasProfile,
+    GasProfileEntry, HashValue, HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction,
+    SimulatedTransactionWithGasProfile, SubmitTransactionRequest, Transaction, TransactionData,
+    TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
     TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion,
     MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
 use aptos_crypto::{hash::CryptoHash, signing_message};
+use aptos_gas_meter::{StandardGasAlgebra, StandardGasMeter};
+use aptos_gas_profiling::{GasProfiler, TransactionGasLog};
+use aptos_memory_usage_tracker::MemoryTrackedGasMeter;
+use aptos_storage_interface::state_view::DbStateView;
 use aptos_types::{
     account_address::AccountAddress,
     mempool_status::MempoolStatusCode,
     transaction::{
         EntryFunction, ExecutionStatus, MultisigTransactionPayload, RawTransaction,
-        RawTransactionWithData, SignedTransaction, TransactionPayload, TransactionStatus,
+        RawTransactionWithData, SignedTransaction, TransactionOutput, TransactionPayload,
+        TransactionStatus,
     },
     vm_status::StatusCode,
     APTOS_COIN_TYPE,
@@ -55,6 +61,7 @@ generate_error_response!(
     (400, BadRequest),
     (403, Forbidden),
     (404, NotFound),
+    (410, Gone),
     (413, PayloadTooLarge),
     (500, Internal),
     (503, ServiceUnavailable),
@@ -489,6 +496,11 @@ impl TransactionsApi {
         /// If set to true, the transaction will use a higher price than the original
         /// estimate.
         estimate_prioritized_gas_unit_price: Query<Option<bool>>,
+        /// Ledger version to simulate the transaction against
+        ///
+        /// If not provided, it will be the latest version.  The version must not have been
+        /// pruned by the node.
+        ledger_version: Query<Option<U64>>,
         data: SubmitTransactionPost,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         data.verify()
@@ -509,23 +521,11 @@ impl TransactionsApi {
         let api = self.clone();
         let context = self.context.clone();
         api_spawn_blocking(move || {
-            let ledger_info = context.get_latest_ledger_info()?;
+            let ledger_version = ledger_version.0.map(|version| version.0);
+            let (ledger_info, _) =
+                context.get_latest_ledger_info_and_verify_lookup_version(ledger_version)?;
             let mut signed_transaction = api.get_signed_transaction(&ledger_info, data)?;
-
-            // Confirm the simulation filter allows the transaction. We use HashValue::zero()
-            // here for the block ID because we don't allow filtering by block ID for the
-            // simulation filters. See the ConfigSanitizer for ApiConfig.
-            if !context.node_config.api.simulation_filter.allows(
-                aptos_crypto::HashValue::zero(),
-                ledger_info.timestamp(),
-                &signed_transaction,
-            ) {
-                return Err(SubmitTransactionError::forbidden_with_code(
-                    "Transaction not allowed by simulation filter",
-                    AptosErrorCode::InvalidInput,
-                    &ledger_info,
-                ));
-            }
+            api.check_simulation_filter(&ledger_info, &signed_transaction)?;
 
             let estimated_gas_unit_price = match (
                 estimate_gas_unit_price.0.unwrap_or_default(),
@@ -557,7 +557,7 @@ impl TransactionsApi {
                 // Retrieve account balance to determine max gas available, right now this is using
                 // a view function, but we may want to re-evaluate this based on performance
                 let (_, _, state_view) = context
-                    .state_view::<BasicErrorWith404>(Option::None)
+                    .state_view::<BasicErrorWith404>(ledger_version)
                     .map_err(|err| {
                         SubmitTransactionError::bad_request_with_code_no_info(
                             err,
@@ -617,7 +617,71 @@ impl TransactionsApi {
                 );
             }
 
-            api.simulate(&accept_type, ledger_info, signed_transaction)
+            api.simulate(
+                &accept_type,
+                ledger_info,
+                ledger_version,
+                signed_transaction,
+            )
+        })
+        .await
+    }
+
+    /// Simulate transaction with gas profile
+    ///
+    /// Simulates the transaction exactly as it was submitted (no gas estimation is applied),
+    /// and returns the simulated transaction, which includes its write set, along with a
+    /// profile of the gas it used.  Combined with `ledger_version`, this can be used to
+    /// reproduce the execution of a transaction against the state at a past version.
+    ///
+    /// The same rules as for the `/transactions/simulate` endpoint apply to the submitted
+    /// transaction, i.e. it must not have a valid signature.
+    #[oai(
+        path = "/transactions/simulate_with_gas_profile",
+        method = "post",
+        operation_id = "simulate_transaction_with_gas_profile",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn simulate_transaction_with_gas_profile(
+        &self,
+        accept_type: AcceptType,
+        /// Ledger version to simulate the transaction against
+        ///
+        /// If not provided, it will be the latest version.  The version must not have been
+        /// pruned by the node.
+        ledger_version: Query<Option<U64>>,
+        data: SubmitTransactionPost,
+    ) -> SimulateTransactionResult<SimulatedTransactionWithGasProfile> {
+        data.verify()
+            .context("Simulated transaction invalid")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code_no_info(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                )
+            })?;
+        fail_point_poem("endpoint_simulate_transaction_with_gas_profile")?;
+        if !self.context.node_config.api.transaction_simulation_enabled {
+            return Err(api_disabled("Simulate transaction with gas profile"));
+        }
+        if accept_type == AcceptType::Bcs {
+            return Err(SubmitTransactionError::bad_request_with_code_no_info(
+                "BCS is not supported for simulation with gas profile",
+                AptosErrorCode::BcsNotSupported,
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Simulate transaction with gas profile", &accept_type)?;
+
+        let api = self.clone();
+        let context = self.context.clone();
+        api_spawn_blocking(move || {
+            let ledger_version = ledger_version.0.map(|version| version.0);
+            let (ledger_info, _) =
+                context.get_latest_ledger_info_and_verify_lookup_version(ledger_version)?;
+            let signed_transaction = api.get_signed_transaction(&ledger_info, data)?;
+            api.check_simulation_filter(&ledger_info, &signed_transaction)?;
+            api.simulate_with_gas_profile(ledger_info, ledger_version, signed_transaction)
         })
         .await
     }
@@ -947,7 +1011,15 @@ impl TransactionsApi {
             return Ok(GetByVersionResponse::VersionTooNew);
         }
         if version < ledger_info.oldest_version() {
-            return Ok(GetByVersionResponse::VersionTooOld);
+            return Ok(
+                match self
+                    .context
+                    .get_retained_transaction_by_version(version, ledger_info.version())?
+                {
+                    Some(txn) => GetByVersionResponse::Found(txn.into()),
+                    None => GetByVersionResponse::VersionTooOld,
+                },
+            );
         }
         Ok(GetByVersionResponse::Found(
             self.context
@@ -1192,7 +1264,31 @@ impl TransactionsApi {
     }
 
     /// Submits a single transaction, and converts mempool codes to errors
-    async fn create_internal(&self, txn: SignedTransaction) -> Result<(), AptosError> {
+    async fn create_internal(
+        &self,
+        ledger_info: &LedgerInfo,
+        txn: SignedTransaction,
+    ) -> Result<(), AptosError> {
+        // Confirm the submission filter allows the transaction. We use HashValue::zero()
+        // here for the block ID because we don't allow filtering by block ID for the
+        // submission filters. See the ConfigSanitizer for ApiConfig.
+        if !self
+            .context
+            .node_config
+            .api
+            .transaction_submission_filter
+            .allows(
+                aptos_crypto::HashValue::zero(),
+                ledger_info.timestamp(),
+                &txn,
+            )
+        {
+            return Err(AptosError::new_with_error_code(
+                "Transaction not allowed by transaction submission filter",
+                AptosErrorCode::RejectedByFilter,
+            ));
+        }
+
         let (mempool_status, vm_status_opt) = self
             .context
             .submit_transaction(txn)
@@ -1236,6 +1332,10 @@ impl TransactionsApi {
                 mempool_status.message,
                 AptosErrorCode::InvalidTransactionUpdate,
             )),
+            MempoolStatusCode::RejectedByFilter => Err(AptosError::new_with_error_code(
+                "Transaction not allowed by mempool transaction filter",
+                AptosErrorCode::RejectedByFilter,
+            )),
             MempoolStatusCode::UnknownStatus => Err(AptosError::new_with_error_code(
                 format!("Transaction was rejected with status {}", mempool_status,),
                 AptosErrorCode::InternalError,
@@ -1250,7 +1350,7 @@ impl TransactionsApi {
         ledger_info: &LedgerInfo,
         txn: SignedTransaction,
     ) -> SubmitTransactionResult<PendingTransaction> {
-        match self.create_internal(txn.clone()).await {
+        match self.create_internal(ledger_info, txn.clone()).await {
             Ok(()) => match accept_type {
                 AcceptType::Json => {
                     let state_view = self
@@ -1306,6 +1406,9 @@ impl TransactionsApi {
                         ledger_info,
                     ),
                 ),
+                AptosErrorCode::RejectedByFilter => Err(
+                    SubmitTransactionError::forbidden_from_aptos_error(error, ledger_info),
+                ),
                 _ => Err(SubmitTransactionError::internal_from_aptos_error(
                     error,
                     ledger_info,
@@ -1324,7 +1427,7 @@ impl TransactionsApi {
         // Iterate through transactions keeping track of failures
         let mut txn_failures = Vec::new();
         for (idx, txn) in txns.iter().enumerate() {
-            if let Err(error) = self.create_internal(txn.clone()).await {
+            if let Err(error) = self.create_internal(ledger_info, txn.clone()).await {
                 txn_failures.push(TransactionsBatchSingleSubmissionFailure {
                     error,
                     transaction_index: idx,
@@ -1350,6 +1453,59 @@ impl TransactionsApi {
         ))
     }
 
+    /// Confirm the simulation filter allows the transaction. We use HashValue::zero()
+    /// here for the block ID because we don't allow filtering by block ID for the
+    /// simulation filters. See the ConfigSanitizer for ApiConfig.
+    fn check_simulation_filter(
+        &self,
+        ledger_info: &LedgerInfo,
+        txn: &SignedTransaction,
+    ) -> Result<(), SubmitTransactionError> {
+        if !self.context.node_config.api.simulation_filter.allows(
+            aptos_crypto::HashValue::zero(),
+            ledger_info.timestamp(),
+            txn,
+        ) {
+            return Err(SubmitTransactionError::forbidden_with_code(
+                "Transaction not allowed by simulation filter",
+                AptosErrorCode::InvalidInput,
+                ledger_info,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the state view to simulate against, i.e. the state at `ledger_version` if
+    /// provided, otherwise the latest state.
+    ///
+    /// Transactions with a valid signature are rejected, as otherwise a malicious actor
+    /// could execute the transaction without their knowledge
+    fn simulation_state_view(
+        &self,
+        ledger_info: &LedgerInfo,
+        ledger_version: Option<u64>,
+        txn: &SignedTransaction,
+    ) -> Result<DbStateView, SubmitTransactionError> {
+        if txn.verify_signature().is_ok() {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                "Simulated transactions must not have a valid signature",
+                AptosErrorCode::InvalidInput,
+                ledger_info,
+            ));
+        }
+
+        match ledger_version {
+            Some(version) => self.context.state_view_at_version(version).map_err(|err| {
+                SubmitTransactionError::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                )
+            }),
+            None => self.context.latest_state_view_poem(ledger_info),
+        }
+    }
+
     // TODO: This function leverages a lot of types from aptos_types, use the
     // local API types and just return those directly, instead of converting
     // from these types in render_transactions.
@@ -1361,24 +1517,111 @@ impl TransactionsApi {
         &self,
         accept_type: &AcceptType,
         ledger_info: LedgerInfo,
+        ledger_version: Option<u64>,
         txn: SignedTransaction,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
-        // The caller must ensure that the signature is not valid, as otherwise
-        // a malicious actor could execute the transaction without their knowledge
-        if txn.verify_signature().is_ok() {
-            return Err(SubmitTransactionError::bad_request_with_code(
-                "Simulated transactions must not have a valid signature",
-                AptosErrorCode::InvalidInput,
-                &ledger_info,
-            ));
-        }
-
         // Simulate transaction
-        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
+        let state_view = self.simulation_state_view(&ledger_info, ledger_version, &txn)?;
         let (vm_status, output) =
             AptosSimulationVM::create_vm_and_simulate_signed_transaction(&txn, &state_view);
-        let version = ledger_info.version();
+        let version = ledger_version.unwrap_or_else(|| ledger_info.version());
+        let simulated_txn = self.simulated_transaction(version, txn, &output);
+
+        match accept_type {
+            AcceptType::Json => {
+                let user_transaction =
+                    self.render_simulated_transaction(&ledger_info, simulated_txn, &vm_status)?;
+                BasicResponse::try_from_json((
+                    vec![user_transaction],
+                    &ledger_info,
+                    BasicResponseStatus::Ok,
+                ))
+            },
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((simulated_txn, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+
+    /// Simulate a transaction in the VM, profiling the gas used by it
+    pub fn simulate_with_gas_profile(
+        &self,
+        ledger_info: LedgerInfo,
+        ledger_version: Option<u64>,
+        txn: SignedTransaction,
+    ) -> SimulateTransactionResult<SimulatedTransactionWithGasProfile> {
+        let state_view = self.simulation_state_view(&ledger_info, ledger_version, &txn)?;
+        let (vm_status, output, gas_profiler) =
+            AptosSimulationVM::create_vm_and_simulate_signed_transaction_with_custom_gas_meter(
+                &txn,
+                &state_view,
+                |gas_feature_version, gas_params, storage_gas_params, balance| {
+                    let gas_meter =
+                        MemoryTrackedGasMeter::new(StandardGasMeter::new(StandardGasAlgebra::new(
+                            gas_feature_version,
+                            gas_params,
+                            storage_gas_params,
+                            balance,
+                        )));
+                    let entry_function = match txn.payload() {
+                        TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+                        TransactionPayload::Multisig(multisig) => {
+                            match &multisig.transaction_payload {
+                                Some(MultisigTransactionPayload::EntryFunction(entry_function)) => {
+                                    Some(entry_function)
+                                },
+                                None => None,
+                            }
+                        },
+                        TransactionPayload::Script(_) | TransactionPayload::ModuleBundle(_) => None,
+                    };
+                    Ok(match entry_function {
+                        Some(entry_function) => GasProfiler::new_function(
+                            gas_meter,
+                            entry_function.module().clone(),
+                            entry_function.function().to_owned(),
+                            entry_function.ty_args().to_vec(),
+                        ),
+                        None => GasProfiler::new_script(gas_meter),
+                    })
+                },
+            )
+            .map_err(|status| {
+                SubmitTransactionError::internal_with_code(
+                    format!(
+                        "Failed to simulate transaction with gas profiler: {}",
+                        status
+                    ),
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let gas_profile = render_gas_profile(&gas_profiler.finish());
+
+        let version = ledger_version.unwrap_or_else(|| ledger_info.version());
+        let simulated_txn = self.simulated_transaction(version, txn, &output);
+        let transaction =
+            self.render_simulated_transaction(&ledger_info, simulated_txn, &vm_status)?;
 
+        BasicResponse::try_from_json((
+            SimulatedTransactionWithGasProfile {
+                transaction,
+                gas_profile,
+            },
+            &ledger_info,
+            BasicResponseStatus::Ok,
+        ))
+    }
+
+    /// Build up a transaction from the outputs of a simulation
+    ///
+    /// All state hashes are invalid, and will be filled with 0s
+    fn simulated_transaction(
+        &self,
+        version: u64,
+        txn: SignedTransaction,
+        output: &TransactionOutput,
+    ) -> TransactionOnChainData {
         // Ensure that all known statuses return their values in the output (even if they aren't supposed to)
         let exe_status = match output.status().clone() {
             TransactionStatus::Keep(exec_status) => exec_status,
@@ -1414,8 +1657,6 @@ impl TransactionsApi {
             .simulate_txn_stats()
             .increment(stats_key, output.gas_used());
 
-        // Build up a transaction from the outputs
-        // All state hashes are invalid, and will be filled with 0s
         let txn = aptos_types::transaction::Transaction::UserTransaction(txn);
         let zero_hash = aptos_crypto::HashValue::zero();
         let info = aptos_types::transaction::TransactionInfo::new(
@@ -1426,61 +1667,52 @@ impl TransactionsApi {
             output.gas_used(),
             exe_status,
         );
-        let simulated_txn = TransactionOnChainData {
+        TransactionOnChainData {
             version,
             transaction: txn,
             info,
             events: output.events().to_vec(),
             accumulator_root_hash: zero_hash,
             changes: output.write_set().clone(),
-        };
+        }
+    }
 
-        match accept_type {
-            AcceptType::Json => {
-                let transactions = self
-                    .context
-                    .render_transactions_non_sequential(&ledger_info, vec![simulated_txn])?;
-
-                // Users can only make requests to simulate UserTransactions, so unpack
-                // the Vec<Transaction> into Vec<UserTransaction>.
-                let mut user_transactions = Vec::new();
-                for transaction in transactions.into_iter() {
-                    match transaction {
-                        Transaction::UserTransaction(user_txn) => {
-                            let mut txn = *user_txn;
-                            match &vm_status {
-                                VMStatus::Error {
-                                    message: Some(msg), ..
-                                }
-                                | VMStatus::ExecutionFailure {
-                                    message: Some(msg), ..
-                                } => {
-                                    txn.info.vm_status +=
-                                        format!("\nExecution failed with message: {}", msg)
-                                            .as_str();
-                                },
-                                _ => (),
-                            }
-                            user_transactions.push(txn);
-                        },
-                        _ => {
-                            return Err(SubmitTransactionError::internal_with_code(
-                                "Simulation transaction resulted in a non-UserTransaction",
-                                AptosErrorCode::InternalError,
-                                &ledger_info,
-                            ))
-                        },
+    /// Render a simulated transaction as JSON, appending the VM's error message (if any)
+    /// to its VM status
+    fn render_simulated_transaction(
+        &self,
+        ledger_info: &LedgerInfo,
+        simulated_txn: TransactionOnChainData,
+        vm_status: &VMStatus,
+    ) -> Result<UserTransaction, SubmitTransactionError> {
+        let transactions = self
+            .context
+            .render_transactions_non_sequential(ledger_info, vec![simulated_txn])?;
+
+        // Users can only make requests to simulate UserTransactions, so unpack
+        // the Vec<Transaction> into a UserTransaction.
+        match transactions.into_iter().next() {
+            Some(Transaction::UserTransaction(user_txn)) => {
+                let mut txn = *user_txn;
+                match vm_status {
+                    VMStatus::Error {
+                        message: Some(msg), ..
                     }
+                    | VMStatus::ExecutionFailure {
+                        message: Some(msg), ..
+                    } => {
+                        txn.info.vm_status +=
+                            format!("\nExecution failed with message: {}", msg).as_str();
+                    },
+                    _ => (),
                 }
-                BasicResponse::try_from_json((
-                    user_transactions,
-                    &ledger_info,
-                    BasicResponseStatus::Ok,
-                ))
-            },
-            AcceptType::Bcs => {
-                BasicResponse::try_from_bcs((simulated_txn, &ledger_info, BasicResponseStatus::Ok))
+                Ok(txn)
             },
+            _ => Err(SubmitTransactionError::internal_with_code(
+                "Simulation transaction resulted in a non-UserTransaction",
+                AptosErrorCode::InternalError,
+                ledger_info,
+            )),
         }
     }
 
@@ -1567,6 +1799,30 @@ fn override_gas_parameters(
     SignedTransaction::new_with_authenticator(raw_txn, signed_txn.authenticator())
 }
 
+/// Summarize the gas log of a simulated transaction into its API representation
+fn render_gas_profile(gas_log: &TransactionGasLog) -> GasProfile {
+    fn entries<T: Into<u64>>(items: Vec<(String, usize, T)>) -> Vec<GasProfileEntry> {
+        items
+            .into_iter()
+            .map(|(name, count, cost)| (name, count, cost.into()).into())
+            .collect()
+    }
+
+    let aggregated = gas_log.exec_io.aggregate_gas_events();
+
+    GasProfile {
+        gas_scaling_factor: u64::from(gas_log.exec_io.gas_scaling_factor).into(),
+        execution_and_io_internal_gas: u64::from(gas_log.exec_io.total).into(),
+        intrinsic_internal_gas: u64::from(gas_log.exec_io.intrinsic_cost).into(),
+        operations: entries(aggregated.ops),
+        storage_reads: entries(aggregated.storage_reads),
+        storage_writes: entries(aggregated.storage_writes),
+        event_writes: entries(aggregated.event_writes),
+        storage_fee_octas: u64::from(gas_log.storage.total).into(),
+        storage_refund_octas: u64::from(gas_log.storage.total_refund).into(),
+    }
+}
+
 enum GetByVersionResponse {
     VersionTooNew,
     VersionTooOld,
@@ -14,13 +14,13 @@ use crate::{
 };
 use anyhow::Context as anyhowContext;
 use aptos_api_types::{
-    AptosErrorCode, AsConverter, MoveValue, ViewFunction, ViewRequest, MAX_RECURSIVE_TYPES_ALLOWED,
-    U64,
+    AptosError, AptosErrorCode, AsConverter, MoveConverter, MoveValue, ViewFunction,
+    ViewFunctionResult, ViewRequest, MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
 use aptos_bcs_utils::serialize_uleb128;
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use itertools::Itertools;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::{language_storage::TypeTag, resolver::ModuleResolver};
 use poem_openapi::{param::Query, payload::Json, ApiRequest, OpenApi};
 use std::sync::Arc;
 
@@ -71,6 +71,83 @@ impl ViewFunctionApi {
         api_spawn_blocking(move || view_request(context, accept_type, request, ledger_version))
             .await
     }
+
+    /// Execute a batch of view functions
+    ///
+    /// Execute multiple Move view functions against the same ledger version, and return the
+    /// result of each of them, in the same order as the requests.  A function that fails
+    /// doesn't fail the whole batch, instead the error is returned as its result.
+    ///
+    /// This endpoint only supports JSON input and output.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window.
+    /// If the requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/view/batch",
+        method = "post",
+        operation_id = "view_batch",
+        tag = "ApiTags::View"
+    )]
+    async fn view_function_batch(
+        &self,
+        accept_type: AcceptType,
+        /// View function requests with type and position arguments
+        requests: Json<Vec<ViewRequest>>,
+        /// Ledger version to get state of accounts
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<ViewFunctionResult>> {
+        fail_point_poem("endpoint_view_function_batch")?;
+        if accept_type == AcceptType::Bcs {
+            return Err(BasicErrorWith404::bad_request_with_code_no_info(
+                "BCS is not supported for batch view functions",
+                AptosErrorCode::BcsNotSupported,
+            ));
+        }
+        self.context
+            .check_api_output_enabled("View function batch", &accept_type)?;
+
+        let context = self.context.clone();
+        api_spawn_blocking(move || view_request_batch(context, requests.0, ledger_version)).await
+    }
+}
+
+/// Returns whether the view function is allowed by the view filter.
+fn is_view_function_allowed(context: &Context, view_function: &ViewFunction) -> bool {
+    context.node_config.api.view_filter.allows(
+        view_function.module.address(),
+        view_function.module.name().as_str(),
+        view_function.function.as_str(),
+    )
+}
+
+fn view_function_not_allowed(view_function: &ViewFunction) -> String {
+    format!(
+        "Function {}::{} is not allowed",
+        view_function.module, view_function.function
+    )
+}
+
+/// Converts the BCS encoded return values of a view function into Move values.
+fn convert_return_values<R: ModuleResolver>(
+    converter: &MoveConverter<R>,
+    view_function: &ViewFunction,
+    values: Vec<Vec<u8>>,
+) -> anyhow::Result<Vec<MoveValue>> {
+    let return_types = converter
+        .function_return_types(view_function)
+        .and_then(|tys| {
+            tys.into_iter()
+                .map(TypeTag::try_from)
+                .collect::<anyhow::Result<Vec<_>>>()
+        })?;
+
+    values
+        .into_iter()
+        .zip(return_types.into_iter())
+        .map(|(v, ty)| converter.try_into_move_value(&ty, &v))
+        .collect::<anyhow::Result<Vec<_>>>()
 }
 
 fn view_request(
@@ -121,16 +198,9 @@ fn view_request(
     };
 
     // Reject the request if it's not allowed by the filter.
-    if !context.node_config.api.view_filter.allows(
-        view_function.module.address(),
-        view_function.module.name().as_str(),
-        view_function.function.as_str(),
-    ) {
+    if !is_view_function_allowed(&context, &view_function) {
         return Err(BasicErrorWith404::forbidden_with_code_no_info(
-            format!(
-                "Function {}::{} is not allowed",
-                view_function.module, view_function.function
-            ),
+            view_function_not_allowed(&view_function),
             AptosErrorCode::InvalidInput,
         ));
     }
@@ -170,32 +240,10 @@ fn view_request(
         },
         AcceptType::Json => {
             let resolver = state_view.as_move_resolver();
-            let return_types = resolver
-                .as_converter(context.db.clone(), context.table_info_reader.clone())
-                .function_return_types(&view_function)
-                .and_then(|tys| {
-                    tys.into_iter()
-                        .map(TypeTag::try_from)
-                        .collect::<anyhow::Result<Vec<_>>>()
-                })
-                .map_err(|err| {
-                    BasicErrorWith404::bad_request_with_code(
-                        err,
-                        AptosErrorCode::InternalError,
-                        &ledger_info,
-                    )
-                })?;
-
-            let move_vals = values
-                .into_iter()
-                .zip(return_types.into_iter())
-                .map(|(v, ty)| {
-                    resolver
-                        .as_converter(context.db.clone(), context.table_info_reader.clone())
-                        .try_into_move_value(&ty, &v)
-                })
-                .collect::<anyhow::Result<Vec<_>>>()
-                .map_err(|err| {
+            let converter =
+                resolver.as_converter(context.db.clone(), context.table_info_reader.clone());
+            let move_vals =
+                convert_return_values(&converter, &view_function, values).map_err(|err| {
                     BasicErrorWith404::bad_request_with_code(
                         err,
                         AptosErrorCode::InternalError,
@@ -212,3 +260,111 @@ fn view_request(
     );
     result
 }
+
+fn view_request_batch(
+    context: Arc<Context>,
+    requests: Vec<ViewRequest>,
+    ledger_version: Query<Option<U64>>,
+) -> BasicResultWith404<Vec<ViewFunctionResult>> {
+    // Retrieve the current state of the chain
+    let (ledger_info, requested_version) = context
+        .get_latest_ledger_info_and_verify_lookup_version(ledger_version.map(|inner| inner.0))?;
+
+    let max_batch_size = context.node_config.api.max_view_function_batch_size;
+    if requests.len() > max_batch_size {
+        return Err(BasicErrorWith404::bad_request_with_code(
+            format!(
+                "Requested too many view functions: {}, while limit is {}",
+                requests.len(),
+                max_batch_size,
+            ),
+            AptosErrorCode::InvalidInput,
+            &ledger_info,
+        ));
+    }
+
+    // All view functions are executed against the same state view
+    let state_view = context
+        .state_view_at_version(requested_version)
+        .map_err(|err| {
+            BasicErrorWith404::bad_request_with_code(
+                err,
+                AptosErrorCode::InternalError,
+                &ledger_info,
+            )
+        })?;
+    let resolver = state_view.as_move_resolver();
+    let converter = resolver.as_converter(context.db.clone(), context.table_info_reader.clone());
+
+    // Requests that can't be converted, or that aren't allowed by the filter, fail without
+    // being executed
+    let view_functions: Vec<Result<ViewFunction, AptosError>> = requests
+        .into_iter()
+        .map(|request| {
+            let view_function = converter.convert_view_function(request).map_err(|err| {
+                AptosError::new_with_error_code(err, AptosErrorCode::InvalidInput)
+            })?;
+            if !is_view_function_allowed(&context, &view_function) {
+                return Err(AptosError::new_with_error_code(
+                    view_function_not_allowed(&view_function),
+                    AptosErrorCode::InvalidInput,
+                ));
+            }
+            Ok(view_function)
+        })
+        .collect();
+
+    let outputs = AptosVM::execute_view_functions(
+        &state_view,
+        view_functions
+            .iter()
+            .flatten()
+            .map(|view_function| {
+                (
+                    view_function.module.clone(),
+                    view_function.function.clone(),
+                    view_function.ty_args.clone(),
+                    view_function.args.clone(),
+                )
+            })
+            .collect(),
+        context.node_config.api.max_gas_view_function,
+    );
+
+    let mut outputs = outputs.into_iter();
+    let results = view_functions
+        .into_iter()
+        .map(|view_function| {
+            let view_function = match view_function {
+                Ok(view_function) => view_function,
+                Err(err) => return ViewFunctionResult::failure(err),
+            };
+            let output = outputs
+                .next()
+                .expect("Every executed view function must have an output");
+            context.view_function_stats().increment(
+                FunctionStats::function_to_key(&view_function.module, &view_function.function),
+                output.gas_used,
+            );
+
+            let values = match output.values {
+                Ok(values) => values,
+                Err(err) => {
+                    return ViewFunctionResult::failure(AptosError::new_with_error_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                    ))
+                },
+            };
+            match convert_return_values(&converter, &view_function, values) {
+                Ok(move_vals) => ViewFunctionResult::success(move_vals),
+                Err(err) => ViewFunctionResult::failure(AptosError::new_with_error_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                )),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    BasicResponse::try_from_json((results, &ledger_info, BasicResponseStatus::Ok))
+}
@@ -10,13 +10,15 @@ use aptos_api_types::{
 use aptos_cached_packages::aptos_stdlib;
 use aptos_config::{
     config::{
-        NodeConfig, RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+        NodeConfig, RocksdbConfigs, StorageDirPaths, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
     },
     keys::ConfigKey,
 };
 use aptos_crypto::{ed25519::Ed25519PrivateKey, hash::HashValue, SigningKey};
 use aptos_db::AptosDB;
+use aptos_db_indexer::{
+    db_ops::open_db, db_v2::IndexerAsyncV2, event_by_type_reader::EventByTypeReader,
+};
 use aptos_executor::{block_executor::BlockExecutor, db_bootstrapper};
 use aptos_executor_types::BlockExecutorTrait;
 use aptos_framework::BuiltPackage;
@@ -39,6 +41,7 @@ use aptos_types::{
     block_info::BlockInfo,
     block_metadata::BlockMetadata,
     chain_id::ChainId,
+    contract_event::ContractEvent,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     transaction::{
         signature_verified_transaction::into_signature_verified_block, Transaction,
@@ -124,11 +127,11 @@ pub fn new_test_context(
         DbReaderWriter::wrap(
             AptosDB::open(
                 StorageDirPaths::from_path(&tmp_dir),
-                false,                       /* readonly */
-                NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+                false,                                             /* readonly */
+                node_config.storage.storage_pruner_config.clone(), /* pruner */
                 RocksdbConfigs::default(),
                 false, /* indexer */
-                BUFFERED_STATE_TARGET_ITEMS,
+                node_config.storage.buffered_state_target_items,
                 DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
             )
             .unwrap(),
@@ -138,7 +141,22 @@ pub fn new_test_context(
         db_bootstrapper::maybe_bootstrap::<AptosVM>(&db_rw, &genesis, genesis_waypoint).unwrap();
     assert!(ret.is_some());
 
-    let mempool = MockSharedMempool::new_in_runtime(&db_rw, VMValidator::new(db.clone()));
+    let mempool = MockSharedMempool::new_in_runtime_with_config(
+        node_config.mempool.clone(),
+        &db_rw,
+        VMValidator::new(db.clone()),
+    );
+
+    let event_by_type_indexer = if node_config.indexer_table_info.index_events_by_type {
+        let indexer_db = open_db(
+            tmp_dir.path().join("index_async_v2_db"),
+            &node_config.storage.rocksdb_configs.index_db_config,
+        )
+        .unwrap();
+        Some(Arc::new(IndexerAsyncV2::new(indexer_db).unwrap()))
+    } else {
+        None
+    };
 
     let context = Context::new(
         ChainId::test(),
@@ -146,6 +164,9 @@ pub fn new_test_context(
         mempool.ac_client.clone(),
         node_config.clone(),
         None, /* table info reader */
+        event_by_type_indexer
+            .clone()
+            .map(|indexer| indexer as Arc<dyn EventByTypeReader>),
     );
 
     // Configure the testing depending on which API version we're testing.
@@ -165,6 +186,7 @@ pub fn new_test_context(
         test_name,
         api_specific_config,
     )
+    .with_event_by_type_indexer(event_by_type_indexer)
 }
 
 #[derive(Clone)]
@@ -173,6 +195,7 @@ pub struct TestContext {
     pub validator_owner: AccountAddress,
     pub mempool: Arc<MockSharedMempool>,
     pub db: Arc<AptosDB>,
+    event_by_type_indexer: Option<Arc<IndexerAsyncV2>>,
     rng: rand::rngs::StdRng,
     root_key: ConfigKey<Ed25519PrivateKey>,
     executor: Arc<dyn BlockExecutorTrait>,
@@ -204,6 +227,7 @@ impl TestContext {
             mempool: Arc::new(mempool),
             expect_status_code: 200,
             db,
+            event_by_type_indexer: None,
             test_name,
             golden_output: None,
             fake_time_usecs: 0,
@@ -211,6 +235,15 @@ impl TestContext {
         }
     }
 
+    /// Indexes the events of every committed block by type with the given indexer
+    pub fn with_event_by_type_indexer(
+        mut self,
+        event_by_type_indexer: Option<Arc<IndexerAsyncV2>>,
+    ) -> Self {
+        self.event_by_type_indexer = event_by_type_indexer;
+        self
+    }
+
     pub fn set_fake_time_usecs(&mut self, fake_time_usecs: u64) {
         self.fake_time_usecs = fake_time_usecs;
     }
@@ -609,6 +642,7 @@ impl TestContext {
             .collect();
 
         // Check that txn execution was successful.
+        let first_version = self.get_latest_ledger_info().version() + 1;
         let parent_id = self.executor.committed_block_id();
         let result = self
             .executor
@@ -636,6 +670,7 @@ impl TestContext {
                 self.new_ledger_info(&metadata, result.root_hash(), txns.len() + 1),
             )
             .unwrap();
+        self.index_events_by_type(first_version, txns.len() as u64 + 1);
 
         self.mempool
             .mempool_notifier
@@ -644,6 +679,25 @@ impl TestContext {
             .unwrap();
     }
 
+    fn index_events_by_type(&self, first_version: u64, num_transactions: u64) {
+        if let Some(indexer) = &self.event_by_type_indexer {
+            let events = self
+                .context
+                .db
+                .get_events_iterator(first_version, num_transactions)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let events: Vec<&[ContractEvent]> = events.iter().map(Vec::as_slice).collect();
+            indexer
+                .index_events_by_type(first_version, &events)
+                .unwrap();
+            indexer
+                .update_next_version(first_version + num_transactions)
+                .unwrap();
+        }
+    }
+
     pub async fn get_sequence_number(&self, account: AccountAddress) -> u64 {
         let account_resource = self
             .gen_resource(&account, "0x1::account::Account")
@@ -874,6 +928,22 @@ impl TestContext {
         sender: &LocalAccount,
         payload: Value,
         status_code: u16,
+    ) -> Value {
+        self.simulate_transaction_with_endpoint(
+            sender,
+            payload,
+            "/transactions/simulate",
+            status_code,
+        )
+        .await
+    }
+
+    pub async fn simulate_transaction_with_endpoint(
+        &mut self,
+        sender: &LocalAccount,
+        payload: Value,
+        endpoint: &str,
+        status_code: u16,
     ) -> Value {
         let mut request = json!({
             "sender": sender.address(),
@@ -907,7 +977,7 @@ impl TestContext {
         });
 
         self.expect_status_code(status_code)
-            .post("/transactions/simulate", request)
+            .post(endpoint, request)
             .await
     }
 
@@ -0,0 +1,46 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Address, HashValue, U64};
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+
+/// Whether a transaction in mempool can be included in a block
+#[derive(Clone, Copy, Debug, Deserialize, Enum, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum MempoolTransactionState {
+    /// All transactions of the sender with lower sequence numbers are in mempool or committed
+    Ready,
+    /// Waiting for a transaction of the sender with a lower sequence number
+    Parked,
+}
+
+/// A pending transaction as held by the mempool of the node
+#[derive(Clone, Debug, Deserialize, Eq, Object, PartialEq, Serialize)]
+pub struct MempoolTransaction {
+    pub hash: HashValue,
+    pub sender: Address,
+    pub sequence_number: U64,
+    pub state: MempoolTransactionState,
+    /// Score the transaction is ordered by for inclusion in blocks, higher first
+    pub ranking_score: U64,
+    /// Timestamp in microseconds at which the transaction was added to mempool
+    pub insertion_timestamp_usecs: U64,
+    /// Time in milliseconds the transaction has spent in mempool
+    pub time_in_mempool_ms: U64,
+    /// Peers the transaction has been broadcast to
+    pub broadcast_peers: Vec<String>,
+}
+
+/// The pending transactions of an account as held by the mempool of the node
+#[derive(Clone, Debug, Deserialize, Eq, Object, PartialEq, Serialize)]
+pub struct MempoolAccount {
+    pub address: Address,
+    /// Sequence number of the account as last seen by mempool
+    ///
+    /// Transactions with a higher sequence number are parked until the gap is filled.
+    pub sequence_number: Option<U64>,
+    /// Transactions ordered by sequence number
+    pub transactions: Vec<MempoolTransaction>,
+}
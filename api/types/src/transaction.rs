@@ -15,6 +15,7 @@ use aptos_crypto::{
     secp256r1_ecdsa::PUBLIC_KEY_LENGTH,
     ValidCryptoMaterial,
 };
+use aptos_storage_interface::retained_transaction::RetainedTransaction;
 use aptos_types::{
     account_address::AccountAddress,
     block_metadata::BlockMetadata,
@@ -167,6 +168,19 @@ impl
     }
 }
 
+impl From<RetainedTransaction> for TransactionOnChainData {
+    fn from(txn: RetainedTransaction) -> Self {
+        Self {
+            version: txn.version,
+            transaction: txn.transaction,
+            info: txn.info,
+            events: txn.events,
+            accumulator_root_hash: txn.accumulator_root_hash,
+            changes: txn.write_set,
+        }
+    }
+}
+
 /// Enum of the different types of transactions in Aptos
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -639,6 +653,74 @@ impl From<(&EventWithVersion, serde_json::Value)> for VersionedEvent {
     }
 }
 
+/// An event delivered through an event stream subscription
+///
+/// The `version` and `event_index` together form the resumption cursor: to resume a
+/// subscription after a disconnect, reconnect with `start_version` set to `version` and
+/// `start_event_index` set to `event_index + 1`.
+#[derive(Clone, Debug, Deserialize, Eq, Object, PartialEq, Serialize)]
+pub struct StreamedEvent {
+    /// Index of the event within the events emitted by the transaction at `version`
+    pub event_index: U64,
+    #[serde(flatten)]
+    #[oai(flatten)]
+    pub event: VersionedEvent,
+}
+
+/// A simulated transaction along with a profile of the gas it used
+///
+/// The write set produced by the simulation is included in `transaction.changes`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize, Object)]
+pub struct SimulatedTransactionWithGasProfile {
+    pub transaction: UserTransaction,
+    pub gas_profile: GasProfile,
+}
+
+/// Breakdown of the gas charged while simulating a transaction
+///
+/// Execution and IO costs are reported in internal gas units, which can be converted to
+/// gas units by dividing by `gas_scaling_factor`.  Storage fees are reported in octas.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize, Object)]
+pub struct GasProfile {
+    pub gas_scaling_factor: U64,
+    /// Total execution and IO cost in internal gas units
+    pub execution_and_io_internal_gas: U64,
+    /// Intrinsic cost of the transaction in internal gas units
+    pub intrinsic_internal_gas: U64,
+    /// Execution cost of each instruction and native function, aggregated by name
+    pub operations: Vec<GasProfileEntry>,
+    /// Cost of each storage read, aggregated by state key
+    pub storage_reads: Vec<GasProfileEntry>,
+    /// IO cost of each storage write, aggregated by state key
+    pub storage_writes: Vec<GasProfileEntry>,
+    /// IO cost of each emitted event, aggregated by event type
+    pub event_writes: Vec<GasProfileEntry>,
+    /// Total storage fee in octas
+    pub storage_fee_octas: U64,
+    /// Total storage refund in octas
+    pub storage_refund_octas: U64,
+}
+
+/// A single aggregated entry of a [`GasProfile`]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize, Object)]
+pub struct GasProfileEntry {
+    pub name: String,
+    /// Number of times this entry was charged
+    pub count: U64,
+    /// Cost in internal gas units
+    pub internal_gas: U64,
+}
+
+impl From<(String, usize, u64)> for GasProfileEntry {
+    fn from((name, count, internal_gas): (String, usize, u64)) -> Self {
+        Self {
+            name,
+            count: (count as u64).into(),
+            internal_gas: internal_gas.into(),
+        }
+    }
+}
+
 /// The writeset payload of the Genesis transaction
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{EntryFunctionId, MoveType};
+use crate::{AptosError, EntryFunctionId, MoveType, MoveValue};
 use aptos_types::serde_helper::vec_bytes;
 use move_core_types::{
     identifier::Identifier,
@@ -20,6 +20,33 @@ pub struct ViewRequest {
     pub arguments: Vec<serde_json::Value>,
 }
 
+/// Result of a single view function in a batch of view requests
+///
+/// Exactly one of `values` and `error` is set.
+#[derive(Clone, Debug, Serialize, Deserialize, Object)]
+pub struct ViewFunctionResult {
+    /// Return values of the function, if it executed successfully
+    pub values: Option<Vec<MoveValue>>,
+    /// Reason the function failed, if it did not execute successfully
+    pub error: Option<AptosError>,
+}
+
+impl ViewFunctionResult {
+    pub fn success(values: Vec<MoveValue>) -> Self {
+        Self {
+            values: Some(values),
+            error: None,
+        }
+    }
+
+    pub fn failure(error: AptosError) -> Self {
+        Self {
+            values: None,
+            error: Some(error),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ViewFunction {
     pub module: ModuleId,
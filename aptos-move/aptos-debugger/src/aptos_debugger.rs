@@ -8,7 +8,10 @@ use aptos_memory_usage_tracker::MemoryTrackedGasMeter;
 use aptos_rest_client::Client;
 use aptos_types::{
     account_address::AccountAddress,
-    state_store::TStateView,
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        Result as StateViewResult, TStateView,
+    },
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, SignedTransaction,
         Transaction, TransactionInfo, TransactionOutput, TransactionPayload, Version,
@@ -21,7 +24,7 @@ use aptos_validator_interface::{
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM, VMExecutor};
 use aptos_vm_logging::log_schema::AdapterLogSchema;
 use aptos_vm_types::output::VMOutput;
-use std::{path::Path, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 pub struct AptosDebugger {
     debugger: Arc<dyn AptosValidatorInterface + Send>,
@@ -75,6 +78,24 @@ impl AptosDebugger {
         Ok(result)
     }
 
+    /// Executes the given transactions at the specified version, using the
+    /// overridden state values (e.g., locally modified framework modules or
+    /// on-chain configs) in place of the ones stored on-chain.
+    pub fn execute_transactions_at_version_with_overrides(
+        &self,
+        version: Version,
+        txns: Vec<Transaction>,
+        overrides: &HashMap<StateKey, StateValue>,
+    ) -> Result<Vec<TransactionOutput>> {
+        let sig_verified_txns: Vec<SignatureVerifiedTransaction> =
+            txns.into_iter().map(|x| x.into()).collect::<Vec<_>>();
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let state_view = OverriddenStateView::new(&state_view, overrides);
+
+        AptosVM::execute_block_no_limit(&sig_verified_txns, &state_view)
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
+    }
+
     pub fn execute_transaction_at_version_with_gas_profiler(
         &self,
         version: Version,
@@ -221,6 +242,14 @@ impl AptosDebugger {
         Ok(ret)
     }
 
+    pub async fn get_committed_transactions(
+        &self,
+        begin: Version,
+        limit: u64,
+    ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        self.debugger.get_committed_transactions(begin, limit).await
+    }
+
     pub async fn get_latest_version(&self) -> Result<Version> {
         self.debugger.get_latest_version().await
     }
@@ -261,3 +290,34 @@ fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
         .iter()
         .any(|event| event.event_key() == Some(&new_epoch_event_key))
 }
+
+/// A state view that serves the overridden state values (if any), and
+/// falls back to the base view for all other state keys.
+struct OverriddenStateView<'a, S> {
+    base_view: &'a S,
+    overrides: &'a HashMap<StateKey, StateValue>,
+}
+
+impl<'a, S: TStateView<Key = StateKey>> OverriddenStateView<'a, S> {
+    fn new(base_view: &'a S, overrides: &'a HashMap<StateKey, StateValue>) -> Self {
+        Self {
+            base_view,
+            overrides,
+        }
+    }
+}
+
+impl<'a, S: TStateView<Key = StateKey>> TStateView for OverriddenStateView<'a, S> {
+    type Key = StateKey;
+
+    fn get_state_value(&self, state_key: &StateKey) -> StateViewResult<Option<StateValue>> {
+        match self.overrides.get(state_key) {
+            Some(state_value) => Ok(Some(state_value.clone())),
+            None => self.base_view.get_state_value(state_key),
+        }
+    }
+
+    fn get_usage(&self) -> StateViewResult<StateStorageUsage> {
+        self.base_view.get_usage()
+    }
+}
@@ -22,7 +22,14 @@ use std::{collections::HashMap, path::Path, sync::Arc};
 pub struct DBDebuggerInterface(Arc<dyn DbReader>);
 
 impl DBDebuggerInterface {
+    /// Opens the AptosDB at the given path in read-only mode (so that it can
+    /// be used alongside a running node, or on a restored snapshot).
     pub fn open<P: AsRef<Path> + Clone>(db_root_path: P) -> Result<Self> {
+        ensure!(
+            db_root_path.as_ref().exists(),
+            "The DB path does not exist: {}",
+            db_root_path.as_ref().display()
+        );
         Ok(Self(Arc::new(
             AptosDB::open(
                 StorageDirPaths::from_path(db_root_path),
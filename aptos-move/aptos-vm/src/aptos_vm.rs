@@ -2154,15 +2154,66 @@ impl AptosVM {
             /*override_is_delayed_field_optimization_capable=*/ Some(false),
         );
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
-        let mut gas_meter = match vm.make_standard_gas_meter(max_gas_amount.into(), &log_context) {
+        vm.execute_view_function_with_resolver(
+            &resolver,
+            &log_context,
+            module_id,
+            func_name,
+            type_args,
+            arguments,
+            max_gas_amount,
+        )
+    }
+
+    /// Executes multiple view functions against the same state view. All functions share a
+    /// single VM, and hence its module cache, but each of them is executed in its own session
+    /// and with its own gas limit, so that a failing function does not affect the others.
+    pub fn execute_view_functions(
+        state_view: &impl StateView,
+        functions: Vec<(ModuleId, Identifier, Vec<TypeTag>, Vec<Vec<u8>>)>,
+        max_gas_amount: u64,
+    ) -> Vec<ViewFunctionOutput> {
+        let resolver = state_view.as_move_resolver();
+        let vm = AptosVM::new(
+            &resolver,
+            /*override_is_delayed_field_optimization_capable=*/ Some(false),
+        );
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        functions
+            .into_iter()
+            .map(|(module_id, func_name, type_args, arguments)| {
+                vm.execute_view_function_with_resolver(
+                    &resolver,
+                    &log_context,
+                    module_id,
+                    func_name,
+                    type_args,
+                    arguments,
+                    max_gas_amount,
+                )
+            })
+            .collect()
+    }
+
+    fn execute_view_function_with_resolver(
+        &self,
+        resolver: &impl AptosMoveResolver,
+        log_context: &AdapterLogSchema,
+        module_id: ModuleId,
+        func_name: Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        max_gas_amount: u64,
+    ) -> ViewFunctionOutput {
+        let mut gas_meter = match self.make_standard_gas_meter(max_gas_amount.into(), log_context) {
             Ok(gas_meter) => gas_meter,
             Err(e) => return ViewFunctionOutput::new(Err(anyhow::Error::msg(format!("{}", e))), 0),
         };
 
-        let mut session = vm.new_session(&resolver, SessionId::Void, None);
+        let mut session = self.new_session(resolver, SessionId::Void, None);
         let execution_result = Self::execute_view_function_in_vm(
             &mut session,
-            &vm,
+            self,
             module_id,
             func_name,
             type_args,
@@ -2657,6 +2708,44 @@ impl AptosSimulationVM {
         transaction: &SignedTransaction,
         state_view: &impl StateView,
     ) -> (VMStatus, TransactionOutput) {
+        let result = Self::create_vm_and_simulate_signed_transaction_with_custom_gas_meter(
+            transaction,
+            state_view,
+            |gas_feature_version, vm_gas_params, storage_gas_params, balance| {
+                Ok(MemoryTrackedGasMeter::new(StandardGasMeter::new(
+                    StandardGasAlgebra::new(
+                        gas_feature_version,
+                        vm_gas_params,
+                        storage_gas_params,
+                        balance,
+                    ),
+                )))
+            },
+        );
+        match result {
+            Ok((vm_status, txn_output, _gas_meter)) => (vm_status, txn_output),
+            Err(vm_status) => {
+                let txn_output = discarded_output(vm_status.status_code())
+                    .into_transaction_output()
+                    .expect("Discarded outputs have nothing to materialize");
+                (vm_status, txn_output)
+            },
+        }
+    }
+
+    /// Same as `create_vm_and_simulate_signed_transaction`, but charges gas using the gas
+    /// meter created by `make_gas_meter`, and returns it along with the output (e.g., so
+    /// that the gas usage of the simulation can be profiled).
+    /// *Precondition:* the transaction must **not** have a valid signature.
+    pub fn create_vm_and_simulate_signed_transaction_with_custom_gas_meter<G, F>(
+        transaction: &SignedTransaction,
+        state_view: &impl StateView,
+        make_gas_meter: F,
+    ) -> Result<(VMStatus, TransactionOutput, G), VMStatus>
+    where
+        G: AptosGasMeter,
+        F: FnOnce(u64, VMGasParameters, StorageGasParameters, Gas) -> Result<G, VMStatus>,
+    {
         assert_err!(
             transaction.verify_signature(),
             "Simulated transaction should not have a valid signature"
@@ -2666,12 +2755,27 @@ impl AptosSimulationVM {
         let vm = Self::new(&resolver);
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
 
-        let (vm_status, vm_output) =
-            vm.0.execute_user_transaction(&resolver, transaction, &log_context);
+        let mut gas_meter = make_gas_meter(
+            vm.0.gas_feature_version,
+            get_or_vm_startup_failure(&vm.0.gas_params, &log_context)?
+                .vm
+                .clone(),
+            get_or_vm_startup_failure(&vm.0.storage_gas_params, &log_context)?.clone(),
+            transaction.max_gas_amount().into(),
+        )?;
+        let traversal_storage = TraversalStorage::new();
+        let mut traversal_context = TraversalContext::new(&traversal_storage);
+        let (vm_status, vm_output) = vm.0.execute_user_transaction_impl(
+            &resolver,
+            transaction,
+            &log_context,
+            &mut gas_meter,
+            &mut traversal_context,
+        );
         let txn_output = vm_output
             .try_materialize_into_transaction_output(&resolver)
             .expect("Materializing aggregator V1 deltas should never fail");
-        (vm_status, txn_output)
+        Ok((vm_status, txn_output, gas_meter))
     }
 }
 
@@ -51,12 +51,35 @@ struct ApplicationNetworkHandle<T> {
     pub network_events: NetworkEvents<T>,
 }
 
-/// TODO: make this configurable (e.g., for compression)
+/// Returns the given protocols (in preferred order), preceded by the
+/// zstd compressed protocol if zstd compression is enabled. Otherwise,
+/// the zstd compressed protocol is not advertised to peers.
+fn get_supported_protocols(
+    protocols: &[ProtocolId],
+    zstd_protocol: ProtocolId,
+    enable_zstd_compression: bool,
+) -> Vec<ProtocolId> {
+    let mut supported_protocols = vec![];
+    if enable_zstd_compression {
+        supported_protocols.push(zstd_protocol);
+    }
+    supported_protocols.extend_from_slice(protocols);
+    supported_protocols
+}
+
 /// Returns the network application config for the consensus client and service
 pub fn consensus_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
-    let direct_send_protocols: Vec<ProtocolId> =
-        aptos_consensus::network_interface::DIRECT_SEND.into();
-    let rpc_protocols: Vec<ProtocolId> = aptos_consensus::network_interface::RPC.into();
+    let enable_zstd_compression = node_config.consensus.enable_zstd_compression;
+    let direct_send_protocols = get_supported_protocols(
+        aptos_consensus::network_interface::DIRECT_SEND,
+        aptos_consensus::network_interface::DIRECT_SEND_ZSTD,
+        enable_zstd_compression,
+    );
+    let rpc_protocols = get_supported_protocols(
+        aptos_consensus::network_interface::RPC,
+        aptos_consensus::network_interface::RPC_ZSTD,
+        enable_zstd_compression,
+    );
 
     let network_client_config =
         NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
@@ -71,9 +94,17 @@ pub fn consensus_network_configuration(node_config: &NodeConfig) -> NetworkAppli
 }
 
 pub fn dkg_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
-    let direct_send_protocols: Vec<ProtocolId> =
-        aptos_dkg_runtime::network_interface::DIRECT_SEND.into();
-    let rpc_protocols: Vec<ProtocolId> = aptos_dkg_runtime::network_interface::RPC.into();
+    let enable_zstd_compression = node_config.dkg.enable_zstd_compression;
+    let direct_send_protocols = get_supported_protocols(
+        aptos_dkg_runtime::network_interface::DIRECT_SEND,
+        aptos_dkg_runtime::network_interface::DIRECT_SEND_ZSTD,
+        enable_zstd_compression,
+    );
+    let rpc_protocols = get_supported_protocols(
+        aptos_dkg_runtime::network_interface::RPC,
+        aptos_dkg_runtime::network_interface::RPC_ZSTD,
+        enable_zstd_compression,
+    );
 
     let network_client_config =
         NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
@@ -87,9 +118,17 @@ pub fn dkg_network_configuration(node_config: &NodeConfig) -> NetworkApplication
 }
 
 pub fn jwk_consensus_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
-    let direct_send_protocols: Vec<ProtocolId> =
-        aptos_jwk_consensus::network_interface::DIRECT_SEND.into();
-    let rpc_protocols: Vec<ProtocolId> = aptos_jwk_consensus::network_interface::RPC.into();
+    let enable_zstd_compression = node_config.jwk_consensus.enable_zstd_compression;
+    let direct_send_protocols = get_supported_protocols(
+        aptos_jwk_consensus::network_interface::DIRECT_SEND,
+        aptos_jwk_consensus::network_interface::DIRECT_SEND_ZSTD,
+        enable_zstd_compression,
+    );
+    let rpc_protocols = get_supported_protocols(
+        aptos_jwk_consensus::network_interface::RPC,
+        aptos_jwk_consensus::network_interface::RPC_ZSTD,
+        enable_zstd_compression,
+    );
 
     let network_client_config =
         NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
@@ -104,7 +143,11 @@ pub fn jwk_consensus_network_configuration(node_config: &NodeConfig) -> NetworkA
 
 /// Returns the network application config for the mempool client and service
 pub fn mempool_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
-    let direct_send_protocols = vec![ProtocolId::MempoolDirectSend];
+    let direct_send_protocols = get_supported_protocols(
+        &[ProtocolId::MempoolDirectSend],
+        ProtocolId::MempoolDirectSendCompressedZstd,
+        node_config.mempool.enable_zstd_compression,
+    );
     let rpc_protocols = vec![]; // Mempool does not use RPC
 
     let network_client_config =
@@ -143,7 +186,14 @@ pub fn peer_monitoring_network_configuration(node_config: &NodeConfig) -> Networ
 /// Returns the network application config for the storage service client and server
 pub fn storage_service_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
     let direct_send_protocols = vec![]; // The storage service does not use direct send
-    let rpc_protocols = vec![ProtocolId::StorageServiceRpc];
+    let rpc_protocols = get_supported_protocols(
+        &[ProtocolId::StorageServiceRpc],
+        ProtocolId::StorageServiceRpcCompressedZstd,
+        node_config
+            .state_sync
+            .storage_service
+            .enable_zstd_compression,
+    );
     let max_network_channel_size = node_config
         .state_sync
         .storage_service
@@ -11,7 +11,9 @@ use aptos_consensus::{
 };
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::client::AptosDataClient;
-use aptos_db_indexer::table_info_reader::TableInfoReader;
+use aptos_db_indexer::{
+    event_by_type_reader::EventByTypeReader, table_info_reader::TableInfoReader,
+};
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_indexer_grpc_fullnode::runtime::bootstrap as bootstrap_indexer_grpc;
 use aptos_indexer_grpc_table_info::runtime::bootstrap as bootstrap_indexer_table_info;
@@ -67,6 +69,13 @@ pub fn bootstrap_api_and_indexer(
     };
 
     // Create the API runtime
+    let event_by_type_reader: Option<Arc<dyn EventByTypeReader>> = indexer_async_v2
+        .clone()
+        .filter(|_| node_config.indexer_table_info.index_events_by_type)
+        .map(|arc| {
+            let trait_object: Arc<dyn EventByTypeReader> = arc;
+            trait_object
+        });
     let table_info_reader: Option<Arc<dyn TableInfoReader>> = indexer_async_v2.map(|arc| {
         let trait_object: Arc<dyn TableInfoReader> = arc;
         trait_object
@@ -78,6 +87,7 @@ pub fn bootstrap_api_and_indexer(
             db_rw.reader.clone(),
             mempool_client_sender.clone(),
             table_info_reader.clone(),
+            event_by_type_reader,
         )?)
     } else {
         None
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::network::ApplicationNetworkInterfaces;
-use aptos_config::config::{NodeConfig, StateSyncConfig};
+use aptos_config::config::{NodeConfig, StateSyncConfig, StorageConfig};
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::{client::AptosDataClient, poller};
 use aptos_data_streaming_service::{
@@ -28,7 +28,8 @@ use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_notifications::StorageServiceNotificationListener;
 use aptos_storage_service_server::{
-    network::StorageServiceNetworkEvents, storage::StorageReader, StorageServiceServer,
+    archive::ArchiveReader, network::StorageServiceNetworkEvents, storage::StorageReader,
+    StorageServiceServer,
 };
 use aptos_storage_service_types::StorageServiceMessage;
 use aptos_time_service::TimeService;
@@ -160,6 +161,7 @@ pub fn start_state_sync_and_get_notification_handles(
     // Start the state sync storage service
     let storage_service_runtime = setup_state_sync_storage_service(
         state_sync_config,
+        &node_config.storage,
         peers_and_metadata,
         network_service_events,
         &db_rw,
@@ -251,6 +253,7 @@ fn setup_aptos_data_client(
 /// Sets up the state sync storage service runtime
 fn setup_state_sync_storage_service(
     config: StateSyncConfig,
+    storage_config: &StorageConfig,
     peers_and_metadata: Arc<PeersAndMetadata>,
     network_service_events: NetworkServiceEvents<StorageServiceMessage>,
     db_rw: &DbReaderWriter,
@@ -260,7 +263,17 @@ fn setup_state_sync_storage_service(
     let storage_service_runtime = aptos_runtimes::spawn_named_runtime("stor-server".into(), None);
 
     // Spawn the state sync storage service servers on the runtime
-    let storage_reader = StorageReader::new(config.storage_service, Arc::clone(&db_rw.reader));
+    let archive_reader = config.storage_service.enable_archive_from_backup.then(|| {
+        ArchiveReader::new(
+            storage_config.clone(),
+            storage_service_runtime.handle().clone(),
+        )
+    });
+    let storage_reader = StorageReader::new_with_archive(
+        config.storage_service,
+        Arc::clone(&db_rw.reader),
+        archive_reader,
+    );
     let service = StorageServiceServer::new(
         config,
         storage_service_runtime.handle().clone(),
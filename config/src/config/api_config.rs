@@ -2,7 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::transaction_filter_type::{Filter, Matcher};
+use super::transaction_filter_type::Filter;
 use crate::{
     config::{
         config_sanitizer::ConfigSanitizer, gas_estimation_config::GasEstimationConfig,
@@ -63,6 +63,8 @@ pub struct ApiConfig {
     ///
     /// This limits the execution length of a view function to the given gas used.
     pub max_gas_view_function: u64,
+    /// Maximum number of view functions that can be executed with the batch view API
+    pub max_view_function_batch_size: usize,
     /// Optional: Maximum number of worker threads for the API.
     ///
     /// If not set, `runtime_worker_multiplier` will multiply times the number of CPU cores on the machine
@@ -77,6 +79,8 @@ pub struct ApiConfig {
     pub periodic_gas_estimation_ms: Option<u64>,
     /// Configuration to filter simulation requests.
     pub simulation_filter: Filter,
+    /// Configuration to filter transaction submission requests.
+    pub transaction_submission_filter: Filter,
     /// Configuration to filter view function requests.
     pub view_filter: ViewFilter,
     /// Periodically log stats for view function and simulate transaction usage
@@ -87,6 +91,16 @@ pub struct ApiConfig {
     pub wait_by_hash_poll_interval_ms: u64,
     /// The number of active wait_by_hash requests that can be active at any given time.
     pub wait_by_hash_max_active_connections: usize,
+    /// Enables the event stream (server-sent events) subscription API
+    #[serde(default = "default_disabled")]
+    pub event_stream_enabled: bool,
+    /// The interval at which event streams will poll the storage for new events.
+    pub event_stream_poll_interval_ms: u64,
+    /// The number of event stream subscriptions that can be active at any given time.
+    pub event_stream_max_active_connections: usize,
+    /// Enables the APIs to inspect the pending transactions in mempool
+    #[serde(default = "default_disabled")]
+    pub mempool_inspection_enabled: bool,
 }
 
 const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -97,6 +111,7 @@ pub const DEFAULT_MAX_PAGE_SIZE: u16 = 100;
 const DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE: u16 = 9999;
 const DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE: u16 = 9999;
 const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000; // We keep this value the same as the max number of gas allowed for one single transaction defined in aptos-gas.
+pub const DEFAULT_MAX_VIEW_FUNCTION_BATCH_SIZE: usize = 100;
 
 fn default_enabled() -> bool {
     true
@@ -128,16 +143,22 @@ impl Default for ApiConfig {
             max_account_resources_page_size: DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE,
             max_account_modules_page_size: DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE,
             max_gas_view_function: DEFAULT_MAX_VIEW_GAS,
+            max_view_function_batch_size: DEFAULT_MAX_VIEW_FUNCTION_BATCH_SIZE,
             max_runtime_workers: None,
             runtime_worker_multiplier: 2,
             gas_estimation: GasEstimationConfig::default(),
             periodic_gas_estimation_ms: Some(30_000),
             simulation_filter: Filter::default(),
+            transaction_submission_filter: Filter::default(),
             view_filter: ViewFilter::default(),
             periodic_function_stats_sec: Some(60),
             wait_by_hash_timeout_ms: 1_000,
             wait_by_hash_poll_interval_ms: 20,
             wait_by_hash_max_active_connections: 100,
+            event_stream_enabled: default_disabled(),
+            event_stream_poll_interval_ms: 100,
+            event_stream_max_active_connections: 100,
+            mempool_inspection_enabled: default_disabled(),
         }
     }
 }
@@ -187,15 +208,23 @@ impl ConfigSanitizer for ApiConfig {
             ));
         }
 
-        // We don't support Block ID based simulation filters.
+        // We don't support Block ID based simulation or submission filters.
         for rule in api_config.simulation_filter.rules() {
-            if let Matcher::BlockId(_) = rule.matcher() {
+            if rule.matcher().matches_block_id() {
                 return Err(Error::ConfigSanitizerFailed(
                     sanitizer_name,
                     "Block ID based simulation filters are not supported!".into(),
                 ));
             }
         }
+        for rule in api_config.transaction_submission_filter.rules() {
+            if rule.matcher().matches_block_id() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "Block ID based transaction submission filters are not supported!".into(),
+                ));
+            }
+        }
 
         // Sanitize the gas estimation config
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
@@ -21,6 +21,8 @@ const MIN_BLOCK_BYTES_OVERRIDE: u64 = 1024 * 1024 + BATCH_PADDING_BYTES as u64;
 pub struct ConsensusConfig {
     // length of inbound queue of messages
     pub max_network_channel_size: usize,
+    // whether to prefer zstd (over LZ4) compressed protocols with peers that support them
+    pub enable_zstd_compression: bool,
     pub max_sending_block_txns: u64,
     pub max_sending_block_bytes: u64,
     pub max_sending_inline_txns: u64,
@@ -149,6 +151,7 @@ impl Default for ConsensusConfig {
     fn default() -> ConsensusConfig {
         ConsensusConfig {
             max_network_channel_size: 1024,
+            enable_zstd_compression: false,
             max_sending_block_txns: MAX_SENDING_BLOCK_TXNS,
             max_sending_block_bytes: 3 * 1024 * 1024, // 3MB
             max_receiving_block_txns: 10000.max(2 * MAX_SENDING_BLOCK_TXNS),
@@ -7,12 +7,15 @@ use serde::{Deserialize, Serialize};
 #[serde(default, deny_unknown_fields)]
 pub struct DKGConfig {
     pub max_network_channel_size: usize,
+    /// Whether or not to prefer zstd (over LZ4) compressed protocols with peers that support them
+    pub enable_zstd_compression: bool,
 }
 
 impl Default for DKGConfig {
     fn default() -> Self {
         Self {
             max_network_channel_size: 256,
+            enable_zstd_compression: false,
         }
     }
 }
@@ -20,6 +20,10 @@ pub struct IndexerTableInfoConfig {
     pub parser_batch_size: u16,
 
     pub enable_expensive_logging: bool,
+
+    /// Index events by their Move type, enabling event lookups by type across all accounts.
+    /// Only transactions processed while this is enabled are indexed.
+    pub index_events_by_type: bool,
 }
 
 // Reminder, #[serde(default)] on IndexerTableInfoConfig means that the default values for
@@ -32,6 +36,7 @@ impl Default for IndexerTableInfoConfig {
             parser_task_count: DEFAULT_PARSER_TASK_COUNT,
             parser_batch_size: DEFAULT_PARSER_BATCH_SIZE,
             enable_expensive_logging: false,
+            index_events_by_type: false,
         }
     }
 }
@@ -4,12 +4,14 @@
 
 use crate::config::{
     config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
-    node_config_loader::NodeType, Error, NodeConfig, MAX_APPLICATION_MESSAGE_SIZE,
+    node_config_loader::NodeType, transaction_filter_type::Filter, Error, NodeConfig,
+    MAX_APPLICATION_MESSAGE_SIZE,
 };
 use aptos_global_constants::DEFAULT_BUCKETS;
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -24,6 +26,8 @@ pub struct MempoolConfig {
     pub default_failovers: usize,
     /// Whether or not to enable intelligent peer prioritization
     pub enable_intelligent_peer_prioritization: bool,
+    /// Whether or not to prefer zstd (over LZ4) compressed broadcasts with peers that support them
+    pub enable_zstd_compression: bool,
     /// The maximum number of broadcasts sent to a single peer that are pending a response ACK at any point.
     pub max_broadcasts_per_peer: usize,
     /// Maximum number of inbound network messages to the Mempool application
@@ -58,6 +62,15 @@ pub struct MempoolConfig {
     pub broadcast_buckets: Vec<u64>,
     pub eager_expire_threshold_ms: Option<u64>,
     pub eager_expire_time_ms: u64,
+    /// Filter for transactions admitted into the Mempool, both from clients and from peers.
+    ///
+    /// Block ID based rules are not supported, and block timestamp based rules are evaluated
+    /// against the time the transaction is received.
+    pub transaction_filter: Filter,
+    /// Journal of pending transactions that is replayed into the Mempool on startup
+    pub persistence: MempoolPersistenceConfig,
+    /// Quotas on the transactions admitted from a single upstream peer or sender
+    pub fairness: MempoolFairnessConfig,
 }
 
 impl Default for MempoolConfig {
@@ -77,6 +90,7 @@ impl Default for MempoolConfig {
             capacity_per_user: 100,
             default_failovers: 1,
             enable_intelligent_peer_prioritization: true,
+            enable_zstd_compression: false,
             shared_mempool_peer_update_interval_ms: 1_000,
             shared_mempool_priority_update_interval_secs: 600, // 10 minutes (frequent reprioritization is expensive)
             system_transaction_timeout_secs: 600,
@@ -84,17 +98,148 @@ impl Default for MempoolConfig {
             broadcast_buckets: DEFAULT_BUCKETS.to_vec(),
             eager_expire_threshold_ms: Some(10_000),
             eager_expire_time_ms: 3_000,
+            transaction_filter: Filter::default(),
+            persistence: MempoolPersistenceConfig::default(),
+            fairness: MempoolFairnessConfig::default(),
+        }
+    }
+}
+
+impl MempoolConfig {
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.persistence.set_data_dir(data_dir);
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MempoolPersistenceConfig {
+    /// Whether or not to persist pending transactions across node restarts
+    pub enabled: bool,
+    /// File to persist pending transactions to, relative to the data directory unless absolute
+    pub path: PathBuf,
+    /// Interval to flush the pending transactions to disk.
+    ///
+    /// Transactions accepted since the last flush are lost if the node crashes.
+    pub flush_interval_ms: u64,
+    /// Maximum number of transactions to persist
+    pub max_transactions: usize,
+    /// Maximum number of bytes of transactions to persist
+    pub max_bytes: u64,
+    /// Directory a relative `path` is resolved against, set by `NodeConfig::set_data_dir`
+    #[serde(skip)]
+    data_dir: PathBuf,
+}
+
+impl Default for MempoolPersistenceConfig {
+    fn default() -> MempoolPersistenceConfig {
+        MempoolPersistenceConfig {
+            enabled: false,
+            path: PathBuf::from("mempool/pending_transactions.bcs"),
+            flush_interval_ms: 10_000,
+            max_transactions: 100_000,
+            max_bytes: 100 * 1024 * 1024,
+            data_dir: PathBuf::from("/opt/aptos/data"),
+        }
+    }
+}
+
+impl MempoolPersistenceConfig {
+    pub fn path(&self) -> PathBuf {
+        if self.path.is_relative() {
+            self.data_dir.join(&self.path)
+        } else {
+            self.path.clone()
+        }
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.data_dir = data_dir;
+    }
+}
+
+/// Token bucket quotas on the transactions admitted into the Mempool. Transactions over the quota
+/// of the upstream peer they were received from, or of their sender, are rejected, and parked
+/// transactions of senders that went over quota are evicted first when the Mempool is full.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MempoolFairnessConfig {
+    /// Whether or not to enforce the quotas
+    pub enabled: bool,
+    /// Number of transactions per second admitted from a single upstream peer.
+    ///
+    /// Peers on the validator network are not subject to this quota.
+    pub peer_txns_per_sec: usize,
+    /// Maximum burst of transactions admitted from a single upstream peer
+    pub peer_burst_txns: usize,
+    /// Number of transactions per second admitted from a single sender
+    pub sender_txns_per_sec: usize,
+    /// Maximum burst of transactions admitted from a single sender
+    pub sender_burst_txns: usize,
+}
+
+impl Default for MempoolFairnessConfig {
+    fn default() -> MempoolFairnessConfig {
+        MempoolFairnessConfig {
+            enabled: false,
+            peer_txns_per_sec: 1_000,
+            peer_burst_txns: 10_000,
+            sender_txns_per_sec: 10,
+            sender_burst_txns: 100,
         }
     }
 }
 
 impl ConfigSanitizer for MempoolConfig {
     fn sanitize(
-        _node_config: &NodeConfig,
+        node_config: &NodeConfig,
         _node_type: NodeType,
         _chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
-        Ok(()) // TODO: add reasonable verifications
+        let sanitizer_name = Self::get_sanitizer_name();
+
+        // Transactions don't belong to a block when they are admitted into the Mempool
+        for rule in node_config.mempool.transaction_filter.rules() {
+            if rule.matcher().matches_block_id() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "Block ID based mempool transaction filters are not supported!".into(),
+                ));
+            }
+        }
+
+        // The token buckets must refill, and hold at least a refill
+        let fairness_config = &node_config.mempool.fairness;
+        if fairness_config.enabled {
+            for (rate, burst) in [
+                (
+                    fairness_config.peer_txns_per_sec,
+                    fairness_config.peer_burst_txns,
+                ),
+                (
+                    fairness_config.sender_txns_per_sec,
+                    fairness_config.sender_burst_txns,
+                ),
+            ] {
+                if rate == 0 || burst < rate {
+                    return Err(Error::ConfigSanitizerFailed(
+                        sanitizer_name,
+                        "The mempool fairness quotas must have a positive rate, and a burst no smaller than the rate!".into(),
+                    ));
+                }
+            }
+        }
+
+        // The journal must be flushed periodically
+        let persistence_config = &node_config.mempool.persistence;
+        if persistence_config.enabled && persistence_config.flush_interval_ms == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The mempool persistence flush interval must be greater than zero!".into(),
+            ));
+        }
+
+        Ok(())
     }
 }
 
@@ -143,6 +288,7 @@ impl ConfigOptimizer for MempoolConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aptos_crypto::HashValue;
 
     #[test]
     fn test_optimize_vfn_configs() {
@@ -249,4 +395,67 @@ mod tests {
             local_max_broadcasts_per_peer
         );
     }
+
+    #[test]
+    fn test_sanitize_block_id_transaction_filter() {
+        // Create a node config with a block ID based transaction filter
+        let node_config = NodeConfig {
+            mempool: MempoolConfig {
+                transaction_filter: Filter::empty().add_deny_block_id(HashValue::random()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            MempoolConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_persistence_flush_interval() {
+        // Create a node config with persistence enabled and no flush interval
+        let node_config = NodeConfig {
+            mempool: MempoolConfig {
+                persistence: MempoolPersistenceConfig {
+                    enabled: true,
+                    flush_interval_ms: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            MempoolConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_fairness_quotas() {
+        // Create a node config with a sender burst smaller than the rate
+        let node_config = NodeConfig {
+            mempool: MempoolConfig {
+                fairness: MempoolFairnessConfig {
+                    enabled: true,
+                    sender_txns_per_sec: 10,
+                    sender_burst_txns: 5,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            MempoolConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
@@ -351,6 +351,7 @@ pub enum DiscoveryMethod {
     Onchain,
     File(FileDiscovery),
     Rest(RestDiscovery),
+    Dns(DnsDiscovery),
     None,
 }
 
@@ -368,6 +369,20 @@ pub struct RestDiscovery {
     pub interval_secs: u64,
 }
 
+/// Discovers peers by periodically resolving DNS names. Each name is resolved
+/// to a set of SRV records (one per peer endpoint), and the TXT records of each
+/// SRV target hold the peer's noise public key (and optionally its peer id).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DnsDiscovery {
+    /// The DNS names to resolve (e.g., `_aptos._tcp.fullnodes.example.com`)
+    pub names: Vec<String>,
+    /// The role to assign to all discovered peers
+    pub peer_role: PeerRole,
+    /// The interval (in seconds) at which the DNS names are resolved again
+    pub interval_secs: u64,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RateLimitConfig {
@@ -125,6 +125,7 @@ impl NodeConfig {
 
         // Set the data directory for each sub-module
         self.consensus.set_data_dir(data_dir.clone());
+        self.mempool.set_data_dir(data_dir.clone());
         self.storage.set_data_dir(data_dir);
     }
 
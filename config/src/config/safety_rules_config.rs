@@ -51,9 +51,7 @@ impl Default for SafetyRulesConfig {
 
 impl SafetyRulesConfig {
     pub fn set_data_dir(&mut self, data_dir: PathBuf) {
-        if let SecureBackend::OnDiskStorage(backend) = &mut self.backend {
-            backend.set_data_dir(data_dir);
-        }
+        self.backend.set_data_dir(data_dir);
     }
 
     #[cfg(test)]
@@ -83,6 +81,15 @@ impl ConfigSanitizer for SafetyRulesConfig {
             return Ok(());
         }
 
+        // Verify that the secure backend can hold the consensus key
+        if safety_rules_config.backend.is_pkcs11() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The secure backend should not be set to a PKCS#11 token, as the BLS12-381 consensus key can't be kept on it!"
+                    .to_string(),
+            ));
+        }
+
         if let Some(chain_id) = chain_id {
             // Verify that the secure backend is appropriate for mainnet validators
             if chain_id.is_mainnet()
@@ -229,7 +236,7 @@ impl SafetyRulesTestConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::ConsensusConfig;
+    use crate::config::{ConsensusConfig, Pkcs11Config, Token};
 
     #[test]
     fn test_sanitize_invalid_backend_for_mainnet() {
@@ -278,6 +285,34 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn test_sanitize_pkcs11_backend() {
+        // Create a node config with a PKCS#11 backend
+        let node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                safety_rules: SafetyRulesConfig {
+                    backend: SecureBackend::Pkcs11(Pkcs11Config {
+                        library_path: PathBuf::from("/usr/lib/softhsm/libsofthsm2.so"),
+                        token_label: "aptos".into(),
+                        pin: Token::FromConfig("1234".into()),
+                        namespace: None,
+                        data_backend: Box::new(SecureBackend::InMemoryStorage),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config sanitizer fails (on any chain)
+        for chain_id in [None, Some(ChainId::testnet()), Some(ChainId::mainnet())] {
+            let error = SafetyRulesConfig::sanitize(&node_config, NodeType::Validator, chain_id)
+                .unwrap_err();
+            assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+        }
+    }
+
     #[test]
     fn test_sanitize_invalid_service_for_mainnet() {
         // Create a node config with a non-local service
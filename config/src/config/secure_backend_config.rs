@@ -3,10 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::Error;
-use aptos_secure_storage::{InMemoryStorage, Namespaced, OnDiskStorage, Storage, VaultStorage};
+use aptos_secure_storage::{
+    EncryptedOnDiskStorage, InMemoryStorage, Namespaced, OnDiskStorage, Pkcs11Storage, Storage,
+    VaultStorage,
+};
 use serde::{Deserialize, Serialize};
 use std::{
-    fs::File,
+    fs::{self, File},
     io::Read,
     path::{Path, PathBuf},
 };
@@ -20,15 +23,20 @@ pub enum SecureBackend {
     InMemoryStorage,
     Vault(VaultConfig),
     OnDiskStorage(OnDiskStorageConfig),
+    EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig),
+    Pkcs11(Pkcs11Config),
 }
 
 impl SecureBackend {
     pub fn namespace(&self) -> Option<&str> {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
-                namespace.as_deref()
-            },
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            })
+            | SecureBackend::Pkcs11(Pkcs11Config { namespace, .. }) => namespace.as_deref(),
             SecureBackend::InMemoryStorage => None,
         }
     }
@@ -36,17 +44,37 @@ impl SecureBackend {
     pub fn clear_namespace(&mut self) {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            })
+            | SecureBackend::Pkcs11(Pkcs11Config { namespace, .. }) => {
                 *namespace = None;
             },
             SecureBackend::InMemoryStorage => {},
         }
     }
 
+    /// Sets the directory that relative storage paths are resolved against
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        match self {
+            SecureBackend::OnDiskStorage(config) => config.set_data_dir(data_dir),
+            SecureBackend::EncryptedOnDiskStorage(config) => config.set_data_dir(data_dir),
+            SecureBackend::Pkcs11(config) => config.data_backend.set_data_dir(data_dir),
+            SecureBackend::InMemoryStorage | SecureBackend::Vault(_) => {},
+        }
+    }
+
     /// Returns true iff the backend is in memory
     pub fn is_in_memory(&self) -> bool {
         matches!(self, SecureBackend::InMemoryStorage)
     }
+
+    /// Returns true iff the backend is a PKCS#11 token
+    pub fn is_pkcs11(&self) -> bool {
+        matches!(self, SecureBackend::Pkcs11(_))
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -97,6 +125,97 @@ pub struct OnDiskStorageConfig {
     data_dir: PathBuf,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedOnDiskStorageConfig {
+    // Required path for encrypted on disk storage
+    pub path: PathBuf,
+    /// A namespace is an optional portion of the path to a key stored within the storage. For
+    /// example, a key, S, without a namespace would be available in S, with a namespace, N, it
+    /// would be in N/S.
+    pub namespace: Option<String>,
+    /// The secret from which the encryption key is derived
+    pub secret: EncryptionSecret,
+    /// The secret the storage was previously encrypted with. If set and the storage cannot be
+    /// decrypted with `secret`, it is re-encrypted with `secret` on startup.
+    #[serde(default)]
+    pub previous_secret: Option<EncryptionSecret>,
+    #[serde(skip)]
+    data_dir: PathBuf,
+}
+
+impl Default for EncryptedOnDiskStorageConfig {
+    fn default() -> Self {
+        Self {
+            namespace: None,
+            path: PathBuf::from(SECURE_STORAGE_FILENAME),
+            secret: EncryptionSecret::FromDisk(PathBuf::from("/opt/aptos/secure_storage.key")),
+            previous_secret: None,
+            data_dir: PathBuf::from("/opt/aptos/data"),
+        }
+    }
+}
+
+impl EncryptedOnDiskStorageConfig {
+    pub fn path(&self) -> PathBuf {
+        if self.path.is_relative() {
+            self.data_dir.join(&self.path)
+        } else {
+            self.path.clone()
+        }
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.data_dir = data_dir;
+    }
+}
+
+/// Encryption secrets can either be passed via an environment variable (e.g., a passphrase) or
+/// stored in a key file on disk. They are never stored within the config itself.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EncryptionSecret {
+    /// The name of the environment variable holding the secret
+    FromEnv(String),
+    /// This is an absolute path and not relative to data_dir
+    FromDisk(PathBuf),
+}
+
+impl EncryptionSecret {
+    pub fn read_secret(&self) -> Result<Vec<u8>, Error> {
+        let secret = match self {
+            EncryptionSecret::FromEnv(name) => std::env::var(name)
+                .map_err(|e| Error::Unexpected(format!("Unable to read {}: {}", name, e)))?
+                .into_bytes(),
+            EncryptionSecret::FromDisk(path) => {
+                fs::read(path).map_err(|e| Error::IO(path.to_str().unwrap().to_string(), e))?
+            },
+        };
+        if secret.is_empty() {
+            return Err(Error::Missing("encryption secret"));
+        }
+        Ok(secret)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pkcs11Config {
+    /// Path to the PKCS#11 library of the token, e.g., /usr/lib/softhsm/libsofthsm2.so.
+    pub library_path: PathBuf,
+    /// The label of the token holding the keys.
+    pub token_label: String,
+    /// The user PIN for logging into the token.
+    pub pin: Token,
+    /// A namespace is an optional prefix of the names of keys and data. For example, a key, S,
+    /// without a namespace would be labeled S, with a namespace, N, it would be labeled N/S.
+    pub namespace: Option<String>,
+    /// The backend storing all non-key data. The BLS12-381 consensus key cannot be signed with
+    /// on a PKCS#11 token, so it is refused rather than stored here (and the token can't be used
+    /// as the safety rules backend).
+    pub data_backend: Box<SecureBackend>,
+}
+
 /// Tokens can either be directly within this config or stored somewhere on disk.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -172,6 +291,44 @@ impl From<&SecureBackend> for Storage {
                     storage
                 }
             },
+            SecureBackend::EncryptedOnDiskStorage(config) => {
+                let secret = config
+                    .secret
+                    .read_secret()
+                    .expect("Unable to read encryption secret");
+                let storage = match &config.previous_secret {
+                    Some(previous_secret) => EncryptedOnDiskStorage::new_with_key_rotation(
+                        config.path(),
+                        secret,
+                        previous_secret
+                            .read_secret()
+                            .expect("Unable to read previous encryption secret"),
+                    ),
+                    None => EncryptedOnDiskStorage::new(config.path(), secret),
+                };
+                let storage = Storage::from(storage);
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            },
+            SecureBackend::Pkcs11(config) => {
+                let storage = Storage::from(
+                    Pkcs11Storage::new(
+                        &config.library_path,
+                        &config.token_label,
+                        config.pin.read_token().expect("Unable to read PKCS#11 PIN"),
+                        config.data_backend.as_ref().into(),
+                    )
+                    .expect("Unable to open PKCS#11 token"),
+                );
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            },
             SecureBackend::Vault(config) => {
                 let storage = Storage::from(VaultStorage::new(
                     config.server.clone(),
@@ -290,6 +447,80 @@ vault:
         serde_yaml::to_string(&from_disk).unwrap();
     }
 
+    #[test]
+    fn test_encrypted_on_disk_storage_parsing() {
+        let text = r#"
+type: "encrypted_on_disk_storage"
+path: secure_storage.json
+secret:
+    from_env: "SECURE_STORAGE_PASSPHRASE"
+previous_secret:
+    from_disk: "/secrets/old.key"
+        "#;
+
+        let backend: SecureBackend = serde_yaml::from_str(text).unwrap();
+        let expected = SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+            path: PathBuf::from("secure_storage.json"),
+            namespace: None,
+            secret: EncryptionSecret::FromEnv("SECURE_STORAGE_PASSPHRASE".to_string()),
+            previous_secret: Some(EncryptionSecret::FromDisk(PathBuf::from(
+                "/secrets/old.key",
+            ))),
+            data_dir: PathBuf::new(),
+        });
+        assert_eq!(backend, expected);
+        serde_yaml::to_string(&backend).unwrap();
+    }
+
+    #[test]
+    fn test_pkcs11_parsing() {
+        let text = r#"
+type: "pkcs11"
+library_path: "/usr/lib/softhsm/libsofthsm2.so"
+token_label: "validator"
+pin:
+    from_disk: "/secrets/pin"
+namespace: "safety_rules"
+data_backend:
+    type: "on_disk_storage"
+    path: secure_storage.json
+        "#;
+
+        let mut backend: SecureBackend = serde_yaml::from_str(text).unwrap();
+        backend.set_data_dir(PathBuf::from("/opt/aptos/data"));
+        let data_backend = match &backend {
+            SecureBackend::Pkcs11(config) => {
+                assert_eq!(config.token_label, "validator");
+                assert_eq!(config.pin, Token::FromDisk(PathBuf::from("/secrets/pin")));
+                config.data_backend.as_ref()
+            },
+            _ => panic!("Unexpected backend: {:?}", backend),
+        };
+        match data_backend {
+            SecureBackend::OnDiskStorage(config) => assert_eq!(
+                config.path(),
+                PathBuf::from("/opt/aptos/data/secure_storage.json")
+            ),
+            _ => panic!("Unexpected data backend: {:?}", data_backend),
+        }
+        assert_eq!(backend.namespace(), Some("safety_rules"));
+        serde_yaml::to_string(&backend).unwrap();
+    }
+
+    #[test]
+    fn test_encryption_secret_reading() {
+        let temppath = aptos_temppath::TempPath::new();
+        temppath.create_as_file().unwrap();
+        let disk = EncryptionSecret::FromDisk(temppath.path().to_path_buf());
+        assert!(disk.read_secret().is_err());
+
+        File::create(temppath.path())
+            .unwrap()
+            .write_all(b"disk_secret")
+            .unwrap();
+        assert_eq!(b"disk_secret".to_vec(), disk.read_secret().unwrap());
+    }
+
     #[test]
     fn test_token_reading() {
         let temppath = aptos_temppath::TempPath::new();
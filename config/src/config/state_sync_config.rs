@@ -6,6 +6,9 @@ use crate::config::{
     config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
     node_config_loader::NodeType, Error, NodeConfig,
 };
+use aptos_compression::algorithm::{
+    DEFAULT_ZSTD_COMPRESSION_LEVEL, MAX_ZSTD_COMPRESSION_LEVEL, MIN_ZSTD_COMPRESSION_LEVEL,
+};
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
@@ -44,6 +47,11 @@ pub enum BootstrappingMode {
     ExecuteTransactionsFromGenesis,
     /// Executes transactions or applies outputs from genesis (whichever is faster)
     ExecuteOrApplyFromGenesis,
+    /// Restores the state keys and values (at the latest state snapshot) from the
+    /// backup storage configured in the storage config (see `BackupStorageConfig`).
+    /// The backed up transaction outputs after the snapshot are then applied, up
+    /// to the last epoch that ends in the backup. Newer data is synced from peers.
+    RestoreFromBackup,
 }
 
 impl BootstrappingMode {
@@ -57,12 +65,21 @@ impl BootstrappingMode {
                 "execute_transactions_from_genesis"
             },
             BootstrappingMode::ExecuteOrApplyFromGenesis => "execute_or_apply_from_genesis",
+            BootstrappingMode::RestoreFromBackup => "restore_from_backup",
         }
     }
 
     /// Returns true iff the bootstrapping mode is fast sync
     pub fn is_fast_sync(&self) -> bool {
-        *self == BootstrappingMode::DownloadLatestStates
+        matches!(
+            self,
+            BootstrappingMode::DownloadLatestStates | BootstrappingMode::RestoreFromBackup
+        )
+    }
+
+    /// Returns true iff the bootstrapping mode reads from a backup storage
+    pub fn is_restore_from_backup(&self) -> bool {
+        *self == BootstrappingMode::RestoreFromBackup
     }
 }
 
@@ -150,6 +167,12 @@ impl Default for StateSyncDriverConfig {
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StorageServiceConfig {
+    /// Whether to serve the transactions and outputs pruned from storage out
+    /// of the backup storage (see `BackupStorageConfig`)
+    pub enable_archive_from_backup: bool,
+    /// Whether to prefer zstd (over LZ4) compressed responses with peers
+    /// that support them. Only applies if the client requests compression.
+    pub enable_zstd_compression: bool,
     /// Maximum number of epoch ending ledger infos per chunk
     pub max_epoch_chunk_size: u64,
     /// Maximum number of invalid requests per peer
@@ -178,11 +201,15 @@ pub struct StorageServiceConfig {
     pub request_moderator_refresh_interval_ms: u64,
     /// The interval (ms) to refresh the storage summary
     pub storage_summary_refresh_interval_ms: u64,
+    /// The compression level to use for zstd compressed responses (1 to 22)
+    pub zstd_compression_level: i32,
 }
 
 impl Default for StorageServiceConfig {
     fn default() -> Self {
         Self {
+            enable_archive_from_backup: false,
+            enable_zstd_compression: false,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
             max_invalid_requests_per_peer: 500,
             max_lru_cache_size: 500, // At ~0.6MiB per chunk, this should take no more than 0.5GiB
@@ -197,6 +224,7 @@ impl Default for StorageServiceConfig {
             min_time_to_ignore_peers_secs: 300, // 5 minutes
             request_moderator_refresh_interval_ms: 1000, // 1 second
             storage_summary_refresh_interval_ms: 100, // Optimal for <= 10 blocks per second
+            zstd_compression_level: DEFAULT_ZSTD_COMPRESSION_LEVEL,
         }
     }
 }
@@ -462,7 +490,10 @@ impl ConfigSanitizer for StateSyncConfig {
         chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
         // Sanitize the state sync driver config
-        StateSyncDriverConfig::sanitize(node_config, node_type, chain_id)
+        StateSyncDriverConfig::sanitize(node_config, node_type, chain_id)?;
+
+        // Sanitize the storage service config
+        StorageServiceConfig::sanitize(node_config, node_type, chain_id)
     }
 }
 
@@ -486,6 +517,60 @@ impl ConfigSanitizer for StateSyncDriverConfig {
             ));
         }
 
+        // Verify that a single backup storage is configured for
+        // nodes that are restoring from a backup.
+        if state_sync_driver_config
+            .bootstrapping_mode
+            .is_restore_from_backup()
+        {
+            if !node_config.storage.backup_storage.has_single_storage() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "Exactly one backup storage must be configured for nodes that are restoring from a backup!"
+                        .to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigSanitizer for StorageServiceConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let storage_service_config = &node_config.state_sync.storage_service;
+
+        // Verify that a single backup storage is configured for
+        // nodes that serve pruned data from a backup.
+        if storage_service_config.enable_archive_from_backup
+            && !node_config.storage.backup_storage.has_single_storage()
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Exactly one backup storage must be configured for nodes that serve pruned data from a backup!"
+                    .to_string(),
+            ));
+        }
+
+        // Verify that the zstd compression level is supported
+        let zstd_compression_level = storage_service_config.zstd_compression_level;
+        if !(MIN_ZSTD_COMPRESSION_LEVEL..=MAX_ZSTD_COMPRESSION_LEVEL)
+            .contains(&zstd_compression_level)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The zstd compression level must be between {} and {}! Got: {}",
+                    MIN_ZSTD_COMPRESSION_LEVEL, MAX_ZSTD_COMPRESSION_LEVEL, zstd_compression_level
+                ),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -573,6 +658,7 @@ impl ConfigOptimizer for DataStreamingServiceConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
 
     #[test]
     fn test_optimize_bootstrapping_mode_devnet_vfn() {
@@ -791,6 +877,131 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_restore_from_backup_storage() {
+        // Create a node config that restores from a backup, without a backup storage
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    bootstrapping_mode: BootstrappingMode::RestoreFromBackup,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Configure both backup storages and verify that sanitization fails
+        node_config.storage.backup_storage.local_fs_dir = Some(PathBuf::from("/opt/aptos/backup"));
+        node_config.storage.backup_storage.command_adapter_config =
+            Some(PathBuf::from("/opt/aptos/etc/backup.yaml"));
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Configure a single backup storage and verify that sanitization passes
+        node_config.storage.backup_storage.command_adapter_config = None;
+        StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_archive_from_backup_storage() {
+        // Create a node config that serves pruned data from a backup, without a backup storage
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                storage_service: StorageServiceConfig {
+                    enable_archive_from_backup: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Configure a single backup storage and verify that sanitization passes
+        node_config.storage.backup_storage.command_adapter_config =
+            Some(PathBuf::from("/opt/aptos/etc/backup.yaml"));
+        StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_zstd_compression_level() {
+        for zstd_compression_level in [-1, 0, 23] {
+            // Create a node config with an unsupported zstd compression level
+            let node_config = NodeConfig {
+                state_sync: StateSyncConfig {
+                    storage_service: StorageServiceConfig {
+                        zstd_compression_level,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            // Verify that sanitization fails
+            let error = StateSyncConfig::sanitize(
+                &node_config,
+                NodeType::PublicFullnode,
+                Some(ChainId::testnet()),
+            )
+            .unwrap_err();
+            assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+        }
+
+        // Verify that sanitization passes for the supported levels
+        for zstd_compression_level in [1, 3, 22] {
+            let node_config = NodeConfig {
+                state_sync: StateSyncConfig {
+                    storage_service: StorageServiceConfig {
+                        zstd_compression_level,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            StateSyncConfig::sanitize(
+                &node_config,
+                NodeType::PublicFullnode,
+                Some(ChainId::testnet()),
+            )
+            .unwrap();
+        }
+    }
+
     /// Creates and returns a node config with the syncing modes set to execution
     fn create_execution_mode_config() -> NodeConfig {
         NodeConfig {
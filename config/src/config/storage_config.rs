@@ -6,9 +6,9 @@ use crate::{
     config::{config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig},
     utils,
 };
-use anyhow::{ensure, Result};
+use anyhow::{anyhow, ensure, Result};
 use aptos_logger::warn;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use arr_macro::arr;
 use number_range::NumberRangeOptions;
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,7 @@ use std::{
     collections::HashMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
 // Lru cache will consume about 2G RAM based on this default value.
@@ -31,6 +32,44 @@ pub struct DbPathConfig {
     pub state_merkle_db_path: Option<ShardedDbPathConfig>,
 }
 
+/// The backup storage a node bootstraps from when state sync is configured to
+/// restore from a backup (i.e., `BootstrappingMode::RestoreFromBackup`), and
+/// that the storage service serves pruned data from (if enabled).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackupStorageConfig {
+    /// Directory of a backup on the local file system
+    pub local_fs_dir: Option<PathBuf>,
+    /// Config file of a command adapter backup storage (e.g., for cloud storage)
+    pub command_adapter_config: Option<PathBuf>,
+    /// Directory to cache the backup metadata in. Relative to the data dir.
+    pub metadata_cache_dir: PathBuf,
+    /// Number of files downloaded from the backup storage concurrently
+    pub concurrent_downloads: usize,
+}
+
+impl BackupStorageConfig {
+    /// Returns true iff exactly one backup storage is configured
+    pub fn has_single_storage(&self) -> bool {
+        [&self.local_fs_dir, &self.command_adapter_config]
+            .iter()
+            .filter(|storage| storage.is_some())
+            .count()
+            == 1
+    }
+}
+
+impl Default for BackupStorageConfig {
+    fn default() -> Self {
+        Self {
+            local_fs_dir: None,
+            command_adapter_config: None,
+            metadata_cache_dir: PathBuf::from("backup_metadata_cache"),
+            concurrent_downloads: 8,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ShardedDbPathConfig {
@@ -175,18 +214,29 @@ pub struct StorageConfig {
     /// If not specificed, will use `dir` as default.
     /// Only allowed when sharding is enabled.
     pub db_path_overrides: Option<DbPathConfig>,
+    /// Backup storage to bootstrap from (if state sync restores from a backup)
+    /// and to serve pruned data from (if the storage service archive is enabled)
+    pub backup_storage: BackupStorageConfig,
 }
 
 pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
     ledger_pruner_config: LedgerPrunerConfig {
         enable: false,
         prune_window: 0,
+        prune_window_secs: None,
+        max_db_size_bytes: None,
         batch_size: 0,
         user_pruning_window_offset: 0,
+        history_retention: LedgerHistoryRetentionConfig {
+            accounts: Vec::new(),
+            modules: Vec::new(),
+        },
     },
     state_merkle_pruner_config: StateMerklePrunerConfig {
         enable: false,
         prune_window: 0,
+        prune_window_secs: None,
+        max_db_size_bytes: None,
         batch_size: 0,
     },
     epoch_snapshot_pruner_config: EpochSnapshotPrunerConfig {
@@ -196,7 +246,11 @@ pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
     },
 };
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+/// Size based prune windows never go below this many versions, to keep serving requests that need
+/// a consistent view of a recent version (see the comment in `StorageConfig::default()`).
+pub const MIN_SIZE_BASED_PRUNE_WINDOW: u64 = 1_000_000;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct LedgerPrunerConfig {
     /// Boolean to enable/disable the ledger pruner. The ledger pruner is responsible for pruning
@@ -206,12 +260,57 @@ pub struct LedgerPrunerConfig {
     /// being big in size, we might want to configure a smaller window for state store vs other
     /// store.
     pub prune_window: u64,
+    /// If set, keeps the transactions committed in this many seconds before the latest block
+    /// instead of `prune_window` versions. The window is mapped to versions by block timestamps.
+    pub prune_window_secs: Option<u64>,
+    /// If set, the window is shrunk whenever the ledger DB and the state K/V DB together take more
+    /// than this many bytes on disk, but never below `MIN_SIZE_BASED_PRUNE_WINDOW` versions.
+    pub max_db_size_bytes: Option<u64>,
     /// Batch size of the versions to be sent to the ledger pruner - this is to avoid slowdown due to
     /// issuing too many DB calls and batch prune instead. For ledger pruner, this means the number
     /// of versions to prune a time.
     pub batch_size: usize,
     /// The offset for user pruning window to adjust
     pub user_pruning_window_offset: u64,
+    /// Transactions kept by the ledger pruner beyond the prune window. Only applies to the ledger
+    /// pruner, the state K/V pruner ignores it.
+    pub history_retention: LedgerHistoryRetentionConfig,
+}
+
+/// Selects the transactions whose history is kept regardless of the ledger prune window, e.g. the
+/// ones involving a node operator's own contracts. The transactions, their infos, events and write
+/// sets are kept, but not the transaction accumulator, so they are served without proofs.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LedgerHistoryRetentionConfig {
+    /// Transactions sent by these accounts, writing to their resources or emitting events under
+    /// their event handles are kept.
+    pub accounts: Vec<AccountAddress>,
+    /// Transactions calling entry functions in these modules or emitting events of types defined
+    /// in them are kept. Modules are in the form of `<address>::<name>`, e.g. `0x1::coin`.
+    pub modules: Vec<String>,
+}
+
+impl LedgerHistoryRetentionConfig {
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.modules.is_empty()
+    }
+
+    /// Returns the address and the name of each module in `modules`.
+    pub fn parse_modules(&self) -> Result<Vec<(AccountAddress, String)>> {
+        self.modules
+            .iter()
+            .map(|module| {
+                let (address, name) = module.split_once("::").ok_or_else(|| {
+                    anyhow!("Module {module} is not in the form of <address>::<name>.")
+                })?;
+                ensure!(!name.is_empty(), "Module {module} has an empty name.");
+                let address = AccountAddress::from_str(address)
+                    .map_err(|err| anyhow!("Module {module} has a bad address: {err}"))?;
+                Ok((address, name.to_string()))
+            })
+            .collect()
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -222,6 +321,13 @@ pub struct StateMerklePrunerConfig {
     pub enable: bool,
     /// Window size in versions.
     pub prune_window: u64,
+    /// If set, keeps the state trees of the snapshots taken in this many seconds before the latest
+    /// one instead of `prune_window` versions. Falls back to `prune_window` while the ledger pruner
+    /// has pruned the block timestamps of versions this window would keep.
+    pub prune_window_secs: Option<u64>,
+    /// If set, the window is shrunk whenever the state merkle DB takes more than this many bytes
+    /// on disk, but never below `MIN_SIZE_BASED_PRUNE_WINDOW` versions.
+    pub max_db_size_bytes: Option<u64>,
     /// Number of stale nodes to prune a time.
     pub batch_size: usize,
 }
@@ -245,12 +351,14 @@ impl From<EpochSnapshotPrunerConfig> for StateMerklePrunerConfig {
         Self {
             enable: config.enable,
             prune_window: config.prune_window,
+            prune_window_secs: None,
+            max_db_size_bytes: None,
             batch_size: config.batch_size,
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct PrunerConfig {
     pub ledger_pruner_config: LedgerPrunerConfig,
@@ -265,8 +373,11 @@ impl Default for LedgerPrunerConfig {
             // This assumes we have 1T disk, minus the space needed by state merkle db and the
             // overhead in storage.
             prune_window: 150_000_000,
+            prune_window_secs: None,
+            max_db_size_bytes: None,
             batch_size: 5_000,
             user_pruning_window_offset: 200_000,
+            history_retention: LedgerHistoryRetentionConfig::default(),
         }
     }
 }
@@ -281,6 +392,8 @@ impl Default for StateMerklePrunerConfig {
             // happens due to this being too small, a node restart should recover it.
             // Still, defaulting to 1M to be super safe.
             prune_window: 1_000_000,
+            prune_window_secs: None,
+            max_db_size_bytes: None,
             // A 10k transaction block (touching 60k state values, in the case of the account
             // creation benchmark) on a 4B items DB (or 1.33B accounts) yields 300k JMT nodes
             batch_size: 1_000,
@@ -322,6 +435,7 @@ impl Default for StorageConfig {
             rocksdb_configs: RocksdbConfigs::default(),
             enable_indexer: false,
             db_path_overrides: None,
+            backup_storage: BackupStorageConfig::default(),
             buffered_state_target_items: BUFFERED_STATE_TARGET_ITEMS,
             max_num_nodes_per_lru_cache_shard: DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         }
@@ -337,6 +451,15 @@ impl StorageConfig {
         }
     }
 
+    pub fn backup_metadata_cache_dir(&self) -> PathBuf {
+        let metadata_cache_dir = &self.backup_storage.metadata_cache_dir;
+        if metadata_cache_dir.is_relative() {
+            self.data_dir.join(metadata_cache_dir)
+        } else {
+            metadata_cache_dir.clone()
+        }
+    }
+
     pub fn get_dir_paths(&self) -> StorageDirPaths {
         let default_dir = self.dir();
         let mut ledger_db_path = None;
@@ -517,6 +640,40 @@ impl ConfigSanitizer for StorageConfig {
             ));
         }
 
+        let ledger_pruner_config = &config.storage_pruner_config.ledger_pruner_config;
+        let state_merkle_pruner_config = &config.storage_pruner_config.state_merkle_pruner_config;
+        for (pruner_name, prune_window_secs, max_db_size_bytes) in [
+            (
+                "Ledger",
+                ledger_pruner_config.prune_window_secs,
+                ledger_pruner_config.max_db_size_bytes,
+            ),
+            (
+                "State Merkle",
+                state_merkle_pruner_config.prune_window_secs,
+                state_merkle_pruner_config.max_db_size_bytes,
+            ),
+        ] {
+            if prune_window_secs == Some(0) {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!("{pruner_name} prune_window_secs must be positive."),
+                ));
+            }
+            if max_db_size_bytes == Some(0) {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!("{pruner_name} max_db_size_bytes must be positive."),
+                ));
+            }
+        }
+        if let Err(err) = ledger_pruner_config.history_retention.parse_modules() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!("Bad module in the ledger history retention config: {err}"),
+            ));
+        }
+
         if let Some(db_path_overrides) = config.db_path_overrides.as_ref() {
             if !config.rocksdb_configs.enable_storage_sharding {
                 return Err(Error::ConfigSanitizerFailed(
@@ -574,7 +731,11 @@ impl ConfigSanitizer for StorageConfig {
 
 #[cfg(test)]
 mod test {
-    use crate::config::{PrunerConfig, ShardPathConfig, ShardedDbPathConfig};
+    use crate::config::{
+        config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+        PrunerConfig, ShardPathConfig, ShardedDbPathConfig, StorageConfig,
+    };
+    use aptos_types::account_address::AccountAddress;
 
     #[test]
     pub fn test_default_prune_window() {
@@ -586,6 +747,59 @@ mod test {
         assert!(config.epoch_snapshot_pruner_config.prune_window > 50_000_000);
     }
 
+    #[test]
+    pub fn test_sanitize_zero_time_or_size_prune_window() {
+        let mut node_config = NodeConfig::default();
+        node_config
+            .storage
+            .storage_pruner_config
+            .ledger_pruner_config
+            .prune_window_secs = Some(30 * 24 * 3600);
+        node_config
+            .storage
+            .storage_pruner_config
+            .state_merkle_pruner_config
+            .max_db_size_bytes = Some(1 << 40);
+        StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+
+        node_config
+            .storage
+            .storage_pruner_config
+            .state_merkle_pruner_config
+            .max_db_size_bytes = Some(0);
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    pub fn test_sanitize_history_retention_modules() {
+        let mut node_config = NodeConfig::default();
+        let history_retention = &mut node_config
+            .storage
+            .storage_pruner_config
+            .ledger_pruner_config
+            .history_retention;
+        history_retention.accounts = vec![AccountAddress::ONE];
+        history_retention.modules = vec!["0x1::coin".to_string()];
+        assert_eq!(history_retention.parse_modules().unwrap(), vec![(
+            AccountAddress::ONE,
+            "coin".to_string()
+        )]);
+        StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+
+        for bad_module in ["coin", "0x1::", "not_an_address::coin"] {
+            node_config
+                .storage
+                .storage_pruner_config
+                .ledger_pruner_config
+                .history_retention
+                .modules = vec![bad_module.to_string()];
+            let error =
+                StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+            assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+        }
+    }
+
     #[test]
     pub fn test_sharded_db_path_config() {
         let path_overrides = ShardedDbPathConfig {
@@ -4,12 +4,35 @@
 use aptos_crypto::HashValue;
 use aptos_types::{
     account_address::AccountAddress,
-    transaction::{SignedTransaction, TransactionPayload},
+    transaction::{
+        EntryFunction, MultisigTransactionPayload, SignedTransaction, TransactionPayload,
+    },
 };
 use serde::{Deserialize, Serialize};
 
+/// The type of a transaction payload
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PayloadType {
+    EntryFunction,
+    ModuleBundle,
+    Multisig,
+    Script,
+}
+
+impl PayloadType {
+    fn of(payload: &TransactionPayload) -> Self {
+        match payload {
+            TransactionPayload::EntryFunction(_) => PayloadType::EntryFunction,
+            TransactionPayload::ModuleBundle(_) => PayloadType::ModuleBundle,
+            TransactionPayload::Multisig(_) => PayloadType::Multisig,
+            TransactionPayload::Script(_) => PayloadType::Script,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Matcher {
+    /// Matches all transactions
     All,
     BlockId(HashValue),
     BlockTimeStampGreaterThan(u64),
@@ -18,9 +41,38 @@ pub enum Matcher {
     Sender(AccountAddress),
     ModuleAddress(AccountAddress),
     EntryFunction(AccountAddress, String, String),
+    PayloadType(PayloadType),
+    /// Matches entry function transactions, including multisig transactions executing an entry
+    /// function, whose argument at the given index is equal to the given BCS encoded bytes
+    EntryFunctionArgument(usize, Vec<u8>),
+    GasUnitPriceGreaterThan(u64),
+    GasUnitPriceLessThan(u64),
+    MaxGasAmountGreaterThan(u64),
+    MaxGasAmountLessThan(u64),
+    SecondarySigner(AccountAddress),
+    FeePayer(AccountAddress),
+    /// Matches if all of the inner matchers match. Named `AllOf`, as `All` already matches
+    /// all transactions.
+    AllOf(Vec<Matcher>),
+    /// Matches if any of the inner matchers match
+    AnyOf(Vec<Matcher>),
+    /// Matches if the inner matcher doesn't match
+    Not(Box<Matcher>),
 }
 
 impl Matcher {
+    /// Returns true if the matcher, or any of its inner matchers, matches on the block id
+    pub fn matches_block_id(&self) -> bool {
+        match self {
+            Matcher::BlockId(_) => true,
+            Matcher::AllOf(matchers) | Matcher::AnyOf(matchers) => {
+                matchers.iter().any(Matcher::matches_block_id)
+            },
+            Matcher::Not(matcher) => matcher.matches_block_id(),
+            _ => false,
+        }
+    }
+
     fn matches(&self, block_id: HashValue, timestamp: u64, txn: &SignedTransaction) -> bool {
         match self {
             Matcher::All => true,
@@ -43,10 +95,48 @@ impl Matcher {
                 },
                 _ => false,
             },
+            Matcher::PayloadType(payload_type) => PayloadType::of(txn.payload()) == *payload_type,
+            Matcher::EntryFunctionArgument(index, argument) => entry_function(txn.payload())
+                .map_or(false, |entry_function| {
+                    entry_function
+                        .args()
+                        .get(*index)
+                        .map_or(false, |arg| arg == argument)
+                }),
+            Matcher::GasUnitPriceGreaterThan(price) => txn.gas_unit_price() > *price,
+            Matcher::GasUnitPriceLessThan(price) => txn.gas_unit_price() < *price,
+            Matcher::MaxGasAmountGreaterThan(amount) => txn.max_gas_amount() > *amount,
+            Matcher::MaxGasAmountLessThan(amount) => txn.max_gas_amount() < *amount,
+            Matcher::SecondarySigner(address) => txn
+                .authenticator_ref()
+                .secondary_signer_addresses()
+                .contains(address),
+            Matcher::FeePayer(address) => {
+                txn.authenticator_ref().fee_payer_address() == Some(*address)
+            },
+            Matcher::AllOf(matchers) => matchers
+                .iter()
+                .all(|matcher| matcher.matches(block_id, timestamp, txn)),
+            Matcher::AnyOf(matchers) => matchers
+                .iter()
+                .any(|matcher| matcher.matches(block_id, timestamp, txn)),
+            Matcher::Not(matcher) => !matcher.matches(block_id, timestamp, txn),
         }
     }
 }
 
+/// Returns the entry function executed by the payload, if any
+fn entry_function(payload: &TransactionPayload) -> Option<&EntryFunction> {
+    match payload {
+        TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+        TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+            Some(MultisigTransactionPayload::EntryFunction(entry_function)) => Some(entry_function),
+            None => None,
+        },
+        TransactionPayload::Script(_) | TransactionPayload::ModuleBundle(_) => None,
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Rule {
     Allow(Matcher),
@@ -113,6 +203,14 @@ impl Rule {
 /// This filter allows transactions from the sender with address f8871acf2c827d40e23b71f6ff2b9accef8dbb17709b88bd9eb95e6bb748c25a or
 /// from the module with address 0000000000000000000000000000000000000000000000000000000000000001 or entry functions
 /// test::check and test::new from the module 0000000000000000000000000000000000000000000000000000000000000001. All other transactions are denied.
+///
+/// Matchers can also be combined using `AllOf`, `AnyOf` and `Not`. For example, the following rule
+/// denies all entry function transactions with a gas unit price below 100:
+///             rules:
+///                 - Deny:
+///                     AllOf:
+///                         - PayloadType: EntryFunction
+///                         - GasUnitPriceLessThan: 100
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Filter {
     rules: Vec<Rule>,
@@ -132,6 +132,11 @@ pub const DIRECT_SEND: &[ProtocolId] = &[
     ProtocolId::ConsensusDirectSendJson,
 ];
 
+/// The zstd compressed protocols. These are preferred over all other
+/// protocols, but only if zstd compression is enabled in the config.
+pub const RPC_ZSTD: ProtocolId = ProtocolId::ConsensusRpcCompressedZstd;
+pub const DIRECT_SEND_ZSTD: ProtocolId = ProtocolId::ConsensusDirectSendCompressedZstd;
+
 impl<NetworkClient: NetworkClientInterface<ConsensusMsg>> ConsensusNetworkClient<NetworkClient> {
     /// Returns a new consensus network client
     pub fn new(network_client: NetworkClient) -> Self {
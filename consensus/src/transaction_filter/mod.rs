@@ -38,26 +38,30 @@ mod test {
     use aptos_types::{
         chain_id::ChainId,
         move_utils::MemberId,
-        transaction::{EntryFunction, RawTransaction, SignedTransaction, TransactionPayload},
+        transaction::{
+            authenticator::AccountAuthenticator, EntryFunction, Multisig,
+            MultisigTransactionPayload, RawTransaction, SignedTransaction, TransactionPayload,
+        },
     };
     use move_core_types::account_address::AccountAddress;
 
     fn create_signed_transaction(function: MemberId) -> SignedTransaction {
-        let private_key = Ed25519PrivateKey::generate_for_testing();
-        let public_key = private_key.public_key();
-        let sender = AccountAddress::random();
-        let sequence_number = 0;
         let MemberId {
             module_id,
             member_id: function_id,
         } = function;
 
-        let payload = TransactionPayload::EntryFunction(EntryFunction::new(
-            module_id,
-            function_id,
-            vec![],
-            vec![],
-        ));
+        create_signed_transaction_with_payload(TransactionPayload::EntryFunction(
+            EntryFunction::new(module_id, function_id, vec![], vec![]),
+        ))
+    }
+
+    fn create_signed_transaction_with_payload(payload: TransactionPayload) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let sender = AccountAddress::random();
+        let sequence_number = 0;
+
         let raw_transaction =
             RawTransaction::new(sender, sequence_number, payload, 0, 0, 0, ChainId::new(10));
 
@@ -68,6 +72,46 @@ mod test {
         )
     }
 
+    fn create_account_authenticator(raw_transaction: &RawTransaction) -> AccountAuthenticator {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        AccountAuthenticator::ed25519(
+            private_key.public_key(),
+            private_key.sign(raw_transaction).unwrap(),
+        )
+    }
+
+    /// Creates a multi-agent transaction with the given secondary signers
+    fn create_multi_agent_transaction(
+        secondary_signer_addresses: Vec<AccountAddress>,
+    ) -> SignedTransaction {
+        let raw_transaction =
+            create_signed_transaction(str::parse("0x1::test::add").unwrap()).into_raw_transaction();
+        let secondary_signers = secondary_signer_addresses
+            .iter()
+            .map(|_| create_account_authenticator(&raw_transaction))
+            .collect();
+        SignedTransaction::new_multi_agent(
+            raw_transaction.clone(),
+            create_account_authenticator(&raw_transaction),
+            secondary_signer_addresses,
+            secondary_signers,
+        )
+    }
+
+    /// Creates a fee payer transaction (without secondary signers) with the given fee payer
+    fn create_fee_payer_transaction(fee_payer_address: AccountAddress) -> SignedTransaction {
+        let raw_transaction =
+            create_signed_transaction(str::parse("0x1::test::add").unwrap()).into_raw_transaction();
+        SignedTransaction::new_fee_payer(
+            raw_transaction.clone(),
+            create_account_authenticator(&raw_transaction),
+            vec![],
+            vec![],
+            fee_payer_address,
+            create_account_authenticator(&raw_transaction),
+        )
+    }
+
     fn get_transactions() -> Vec<SignedTransaction> {
         vec![
             create_signed_transaction(str::parse("0x1::test::add").unwrap()),
@@ -287,4 +331,188 @@ mod test {
         let filtered_txns = allow_list_filter.filter(block_id, 0, txns.clone());
         assert_eq!(filtered_txns, txns[4..].to_vec());
     }
+
+    #[test]
+    fn test_payload_type_filter() {
+        let txns = get_transactions();
+        let block_id = HashValue::random();
+
+        let deny_entry_function_filter = TransactionFilter::new(
+            serde_yaml::from_str::<Filter>("rules:\n  - Deny:\n      PayloadType: EntryFunction")
+                .unwrap(),
+        );
+        let filtered_txns = deny_entry_function_filter.filter(block_id, 0, txns.clone());
+        assert!(filtered_txns.is_empty());
+
+        let deny_script_filter = TransactionFilter::new(
+            serde_yaml::from_str::<Filter>("rules:\n  - Deny:\n      PayloadType: Script").unwrap(),
+        );
+        let filtered_txns = deny_script_filter.filter(block_id, 0, txns.clone());
+        assert_eq!(filtered_txns, txns);
+    }
+
+    #[test]
+    fn test_gas_filter() {
+        // All test transactions have a gas unit price and max gas amount of 0
+        let txns = get_transactions();
+        let block_id = HashValue::random();
+
+        let deny_low_gas_filter = TransactionFilter::new(
+            serde_yaml::from_str::<Filter>("rules:\n  - Deny:\n      MaxGasAmountLessThan: 1")
+                .unwrap(),
+        );
+        let filtered_txns = deny_low_gas_filter.filter(block_id, 0, txns.clone());
+        assert!(filtered_txns.is_empty());
+
+        let deny_high_gas_price_filter = TransactionFilter::new(
+            serde_yaml::from_str::<Filter>("rules:\n  - Deny:\n      GasUnitPriceGreaterThan: 0")
+                .unwrap(),
+        );
+        let filtered_txns = deny_high_gas_price_filter.filter(block_id, 0, txns.clone());
+        assert_eq!(filtered_txns, txns);
+    }
+
+    #[test]
+    fn test_combinator_filter() {
+        let txns = get_transactions();
+        let block_id = HashValue::random();
+        let filter = serde_yaml::from_str::<Filter>(r#"
+            rules:
+                - Allow:
+                    AllOf:
+                        - ModuleAddress: "0000000000000000000000000000000000000000000000000000000000000001"
+                        - Not:
+                            EntryFunction:
+                                - "0000000000000000000000000000000000000000000000000000000000000001"
+                                - test
+                                - add
+                - Allow:
+                    AnyOf:
+                        - ModuleAddress: "0000000000000000000000000000000000000000000000000000000000000003"
+                        - ModuleAddress: "0000000000000000000000000000000000000000000000000000000000000004"
+                - Deny: All
+              "#).unwrap();
+
+        let combinator_filter = TransactionFilter::new(filter);
+        let filtered_txns = combinator_filter.filter(block_id, 0, txns.clone());
+        let expected_txns = [&txns[1..4], &txns[5..]].concat();
+        assert_eq!(filtered_txns, expected_txns);
+    }
+
+    #[test]
+    fn test_entry_function_argument_filter() {
+        let MemberId {
+            module_id,
+            member_id: function_id,
+        } = str::parse("0x1::test::add").unwrap();
+        let entry_function = |argument: u64| {
+            EntryFunction::new(module_id.clone(), function_id.clone(), vec![], vec![
+                bcs::to_bytes(&argument).unwrap(),
+            ])
+        };
+        let multisig = |transaction_payload| {
+            TransactionPayload::Multisig(Multisig {
+                multisig_address: AccountAddress::random(),
+                transaction_payload,
+            })
+        };
+        let txns = vec![
+            create_signed_transaction_with_payload(TransactionPayload::EntryFunction(
+                entry_function(1),
+            )),
+            create_signed_transaction_with_payload(TransactionPayload::EntryFunction(
+                entry_function(2),
+            )),
+            create_signed_transaction_with_payload(multisig(Some(
+                MultisigTransactionPayload::EntryFunction(entry_function(1)),
+            ))),
+            create_signed_transaction_with_payload(multisig(Some(
+                MultisigTransactionPayload::EntryFunction(entry_function(2)),
+            ))),
+            create_signed_transaction_with_payload(multisig(None)),
+        ];
+        let block_id = HashValue::random();
+
+        // Deny the transactions whose first argument is 1, including multisig transactions
+        let filter = serde_yaml::from_str::<Filter>(
+            "rules:\n  - Deny:\n      EntryFunctionArgument: [0, [1, 0, 0, 0, 0, 0, 0, 0]]",
+        )
+        .unwrap();
+        let filtered_txns = TransactionFilter::new(filter).filter(block_id, 0, txns.clone());
+        assert_eq!(filtered_txns, vec![
+            txns[1].clone(),
+            txns[3].clone(),
+            txns[4].clone()
+        ]);
+    }
+
+    #[test]
+    fn test_secondary_signer_filter() {
+        let secondary_signer = AccountAddress::random();
+        let other_signer = AccountAddress::random();
+        let txns = vec![
+            create_signed_transaction(str::parse("0x1::test::add").unwrap()),
+            create_multi_agent_transaction(vec![other_signer, secondary_signer]),
+            create_multi_agent_transaction(vec![other_signer]),
+            create_fee_payer_transaction(secondary_signer),
+        ];
+        let block_id = HashValue::random();
+
+        // Only the multi-agent transaction signed by the secondary signer matches (the sender
+        // and the fee payer are not secondary signers)
+        let filter = serde_yaml::from_str::<Filter>(&format!(
+            "rules:\n  - Deny:\n      SecondarySigner: \"{}\"",
+            secondary_signer.to_hex()
+        ))
+        .unwrap();
+        let filtered_txns = TransactionFilter::new(filter).filter(block_id, 0, txns.clone());
+        assert_eq!(filtered_txns, vec![
+            txns[0].clone(),
+            txns[2].clone(),
+            txns[3].clone()
+        ]);
+
+        // An address that didn't sign any transaction matches none
+        let filter = serde_yaml::from_str::<Filter>(&format!(
+            "rules:\n  - Deny:\n      SecondarySigner: \"{}\"",
+            AccountAddress::random().to_hex()
+        ))
+        .unwrap();
+        let filtered_txns = TransactionFilter::new(filter).filter(block_id, 0, txns.clone());
+        assert_eq!(filtered_txns, txns);
+    }
+
+    #[test]
+    fn test_fee_payer_filter() {
+        let fee_payer = AccountAddress::random();
+        let txns = vec![
+            create_signed_transaction(str::parse("0x1::test::add").unwrap()),
+            create_multi_agent_transaction(vec![fee_payer]),
+            create_fee_payer_transaction(fee_payer),
+            create_fee_payer_transaction(AccountAddress::random()),
+        ];
+        let block_id = HashValue::random();
+
+        // Only the transaction paid for by the fee payer matches
+        let filter = serde_yaml::from_str::<Filter>(&format!(
+            "rules:\n  - Deny:\n      FeePayer: \"{}\"",
+            fee_payer.to_hex()
+        ))
+        .unwrap();
+        let filtered_txns = TransactionFilter::new(filter).filter(block_id, 0, txns.clone());
+        assert_eq!(filtered_txns, vec![
+            txns[0].clone(),
+            txns[1].clone(),
+            txns[3].clone()
+        ]);
+
+        // An address that didn't pay for any transaction matches none
+        let filter = serde_yaml::from_str::<Filter>(&format!(
+            "rules:\n  - Deny:\n      FeePayer: \"{}\"",
+            AccountAddress::random().to_hex()
+        ))
+        .unwrap();
+        let filtered_txns = TransactionFilter::new(filter).filter(block_id, 0, txns.clone());
+        assert_eq!(filtered_txns, txns);
+    }
 }
@@ -0,0 +1,38 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+/// The compression level to use for zstd (if not configured otherwise).
+/// Level 3 is the zstd default and offers a good tradeoff between
+/// compression ratio and CPU time.
+pub const DEFAULT_ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+/// The range of (regular) compression levels supported by zstd
+pub const MIN_ZSTD_COMPRESSION_LEVEL: i32 = 1;
+pub const MAX_ZSTD_COMPRESSION_LEVEL: i32 = 22;
+
+/// A simple enum for identifying the algorithm used to compress (and
+/// decompress) data. Both peers must agree on the algorithm (e.g., via
+/// the negotiated network protocol) for decompression to succeed.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum CompressionAlgorithm {
+    /// LZ4 in fast mode (the default, and the most CPU efficient)
+    #[default]
+    Lz4,
+    /// Zstd at the given compression level
+    Zstd(i32),
+}
+
+impl CompressionAlgorithm {
+    /// Returns zstd at the default compression level
+    pub fn zstd() -> Self {
+        Self::Zstd(DEFAULT_ZSTD_COMPRESSION_LEVEL)
+    }
+
+    /// Returns a summary label for the algorithm
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Self::Lz4 => "lz4",
+            Self::Zstd(_) => "zstd",
+        }
+    }
+}
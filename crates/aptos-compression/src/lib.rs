@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    algorithm::CompressionAlgorithm,
     client::CompressionClient,
     Error::{CompressionError, DecompressionError},
 };
@@ -13,12 +14,17 @@ use thiserror::Error;
 /// This crate provides a simple library interface for data compression.
 /// It is useful for compressing large data chunks that are
 /// sent across the network (e.g., by state sync and consensus).
-/// Internally, it uses LZ4 in fast mode to compress the data.
+/// By default, it uses LZ4 in fast mode to compress the data.
 /// See <https://github.com/10xGenomics/lz4-rs> for more information.
+/// Zstd is also supported for clients that prefer a better
+/// compression ratio over CPU time.
+/// See <https://github.com/gyscos/zstd-rs> for more information.
 ///
 /// Note: the crate also exposes some basic compression metrics
 /// that can be used to track the cumulative compression ratio
-/// and compression/decompression durations during the runtime.
+/// and compression/decompression durations (per algorithm) during
+/// the runtime.
+pub mod algorithm;
 pub mod client;
 mod metrics;
 #[cfg(test)]
@@ -40,11 +46,21 @@ pub enum Error {
     DecompressionError(String),
 }
 
-/// Compresses the raw data stream
+/// Compresses the raw data stream (using LZ4)
 pub fn compress(
     raw_data: Vec<u8>,
     client: CompressionClient,
     max_bytes: usize,
+) -> Result<CompressedData, Error> {
+    compress_with_algorithm(raw_data, client, &CompressionAlgorithm::Lz4, max_bytes)
+}
+
+/// Compresses the raw data stream using the given algorithm
+pub fn compress_with_algorithm(
+    raw_data: Vec<u8>,
+    client: CompressionClient,
+    algorithm: &CompressionAlgorithm,
+    max_bytes: usize,
 ) -> Result<CompressedData, Error> {
     // Start the compression timer
     let start_time = Instant::now();
@@ -56,16 +72,22 @@ pub fn compress(
             raw_data.len(),
             max_bytes
         );
-        return create_compression_error(&client, error_string);
+        return create_compression_error(&client, algorithm, error_string);
     }
 
     // Compress the data
-    let compression_mode = CompressionMode::FAST(ACCELERATION_PARAMETER);
-    let compressed_data = match lz4::block::compress(&raw_data, Some(compression_mode), true) {
+    let compression_result = match algorithm {
+        CompressionAlgorithm::Lz4 => {
+            let compression_mode = CompressionMode::FAST(ACCELERATION_PARAMETER);
+            lz4::block::compress(&raw_data, Some(compression_mode), true)
+        },
+        CompressionAlgorithm::Zstd(level) => zstd::bulk::compress(&raw_data, *level),
+    };
+    let compressed_data = match compression_result {
         Ok(compressed_data) => compressed_data,
         Err(error) => {
             let error_string = format!("Failed to compress the data: {}", error);
-            return create_compression_error(&client, error_string);
+            return create_compression_error(&client, algorithm, error_string);
         },
     };
 
@@ -78,44 +100,72 @@ pub fn compress(
             compressed_data.len(),
             max_bytes
         );
-        return create_compression_error(&client, error_string);
+        return create_compression_error(&client, algorithm, error_string);
     }
 
     // Stop the timer and update the metrics
-    metrics::observe_compression_operation_time(&client, start_time);
-    metrics::update_compression_metrics(&client, &raw_data, &compressed_data);
+    metrics::observe_compression_operation_time(&client, algorithm, start_time);
+    metrics::update_compression_metrics(&client, algorithm, &raw_data, &compressed_data);
 
     Ok(compressed_data)
 }
 
-/// Decompresses the compressed data stream
+/// Decompresses the compressed data stream (using LZ4)
 pub fn decompress(
     compressed_data: &CompressedData,
     client: CompressionClient,
     max_size: usize,
+) -> Result<Vec<u8>, Error> {
+    decompress_with_algorithm(
+        compressed_data,
+        client,
+        &CompressionAlgorithm::Lz4,
+        max_size,
+    )
+}
+
+/// Decompresses the compressed data stream using the given algorithm
+pub fn decompress_with_algorithm(
+    compressed_data: &CompressedData,
+    client: CompressionClient,
+    algorithm: &CompressionAlgorithm,
+    max_size: usize,
 ) -> Result<Vec<u8>, Error> {
     // Start the decompression timer
     let start_time = Instant::now();
 
-    // Check size of the data and initialize raw_data
-    let decompressed_size = match get_decompressed_size(compressed_data, max_size) {
+    // Check size of the data
+    let size_result = match algorithm {
+        CompressionAlgorithm::Lz4 => get_decompressed_size(compressed_data, max_size),
+        CompressionAlgorithm::Zstd(_) => get_zstd_decompressed_size(compressed_data, max_size),
+    };
+    let decompressed_size = match size_result {
         Ok(size) => size,
         Err(error) => {
             let error_string = format!("Failed to get decompressed size: {}", error);
-            return create_decompression_error(&client, error_string);
+            return create_decompression_error(&client, algorithm, error_string);
         },
     };
-    let mut raw_data = vec![0u8; decompressed_size];
 
     // Decompress the data
-    if let Err(error) = lz4::block::decompress_to_buffer(compressed_data, None, &mut raw_data) {
-        let error_string = format!("Failed to decompress the data: {}", error);
-        return create_decompression_error(&client, error_string);
+    let decompression_result = match algorithm {
+        CompressionAlgorithm::Lz4 => {
+            let mut raw_data = vec![0u8; decompressed_size];
+            lz4::block::decompress_to_buffer(compressed_data, None, &mut raw_data).map(|_| raw_data)
+        },
+        CompressionAlgorithm::Zstd(_) => zstd::bulk::decompress(compressed_data, decompressed_size),
+    };
+    let raw_data = match decompression_result {
+        Ok(raw_data) => raw_data,
+        Err(error) => {
+            let error_string = format!("Failed to decompress the data: {}", error);
+            return create_decompression_error(&client, algorithm, error_string);
+        },
     };
 
     // Stop the timer and update the metrics
-    metrics::observe_decompression_operation_time(&client, start_time);
-    metrics::update_decompression_metrics(&client, compressed_data, &raw_data);
+    metrics::observe_decompression_operation_time(&client, algorithm, start_time);
+    metrics::update_decompression_metrics(&client, algorithm, compressed_data, &raw_data);
 
     Ok(raw_data)
 }
@@ -123,10 +173,11 @@ pub fn decompress(
 /// A simple utility function that wraps the given error string in a compression error
 fn create_compression_error(
     client: &CompressionClient,
+    algorithm: &CompressionAlgorithm,
     error_string: String,
 ) -> Result<CompressedData, Error> {
     // Increment the compression error counter
-    metrics::increment_compression_error(client);
+    metrics::increment_compression_error(client, algorithm);
 
     // Create and return the error
     Err(CompressionError(error_string))
@@ -135,10 +186,11 @@ fn create_compression_error(
 /// A simple utility function that wraps the given error string in a decompression error
 fn create_decompression_error(
     client: &CompressionClient,
+    algorithm: &CompressionAlgorithm,
     error_string: String,
 ) -> Result<Vec<u8>, Error> {
     // Increment the decompression error counter
-    metrics::increment_decompression_error(client);
+    metrics::increment_decompression_error(client, algorithm);
 
     // Create and return the error
     Err(DecompressionError(error_string))
@@ -183,6 +235,39 @@ fn get_decompressed_size(
     Ok(size)
 }
 
+/// Parses the original data size from the zstd frame header. Note: the
+/// frames produced by the zstd bulk compressor always contain the size.
+fn get_zstd_decompressed_size(
+    compressed_data: &CompressedData,
+    max_size: usize,
+) -> Result<usize, Error> {
+    // Parse the frame content size
+    let size = match zstd::zstd_safe::get_frame_content_size(compressed_data) {
+        Ok(Some(size)) => size,
+        Ok(None) => {
+            return Err(DecompressionError(
+                "The zstd frame header does not contain the content size!".into(),
+            ));
+        },
+        Err(error) => {
+            return Err(DecompressionError(format!(
+                "Failed to parse the zstd frame header! Error: {:?}",
+                error
+            )));
+        },
+    };
+
+    // Ensure that the size is not greater than the max size limit
+    if size > max_size as u64 {
+        return Err(DecompressionError(format!(
+            "Parsed zstd frame content size is too big: {} > {}",
+            size, max_size
+        )));
+    }
+
+    Ok(size as usize)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -217,4 +302,37 @@ mod test {
         let result = get_decompressed_size(&compressed_data, max_compression_size);
         assert_eq!(result.unwrap(), raw_data.len());
     }
+
+    #[test]
+    fn test_get_zstd_decompressed_size() {
+        // Create some test data
+        let max_compression_size = 100;
+
+        // Verify that an error is returned when the compressed data is not a zstd frame
+        let result = get_zstd_decompressed_size(&vec![0u8; 10], max_compression_size);
+        assert!(result.is_err());
+
+        // Verify that an error is returned when the content size is too large
+        let compressed_data = compress_with_algorithm(
+            vec![0u8; max_compression_size + 1],
+            CompressionClient::StateSync,
+            &CompressionAlgorithm::zstd(),
+            max_compression_size + 1,
+        )
+        .unwrap();
+        let result = get_zstd_decompressed_size(&compressed_data, max_compression_size);
+        assert!(result.is_err());
+
+        // Verify that the correct decompressed size is returned
+        let raw_data = vec![0u8; max_compression_size];
+        let compressed_data = compress_with_algorithm(
+            raw_data.clone(),
+            CompressionClient::StateSync,
+            &CompressionAlgorithm::zstd(),
+            max_compression_size,
+        )
+        .unwrap();
+        let result = get_zstd_decompressed_size(&compressed_data, max_compression_size);
+        assert_eq!(result.unwrap(), raw_data.len());
+    }
 }
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::CompressionClient;
+use crate::{algorithm::CompressionAlgorithm, CompressionClient};
 use aptos_crypto::{ed25519::Ed25519PrivateKey, hash::HashValue, PrivateKey, SigningKey, Uniform};
 use aptos_types::{
     account_address::AccountAddress,
@@ -26,39 +26,99 @@ const MIB: usize = 1024 * 1024;
 
 #[test]
 fn test_basic_compression() {
-    // Test compress random bytes
-    let raw_bytes: Vec<_> = (0..MIB).map(|_| rand::thread_rng().gen::<u8>()).collect();
-    test_compress_and_decompress(raw_bytes);
+    for algorithm in [CompressionAlgorithm::Lz4, CompressionAlgorithm::zstd()] {
+        // Test compress random bytes
+        let raw_bytes: Vec<_> = (0..MIB).map(|_| rand::thread_rng().gen::<u8>()).collect();
+        test_compress_and_decompress(raw_bytes, &algorithm);
 
-    // Test epoch ending ledger infos
+        // Test epoch ending ledger infos
+        let epoch_ending_ledger_infos = create_epoch_ending_ledger_infos(0, 999);
+        test_compress_and_decompress(epoch_ending_ledger_infos, &algorithm);
+
+        // Test transaction outputs with proof
+        let outputs_with_proof = create_output_list_with_proof(13434, 17000, 19000);
+        test_compress_and_decompress(outputs_with_proof, &algorithm);
+
+        // Test transactions with proof
+        let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
+        test_compress_and_decompress(transactions_with_proof, &algorithm);
+    }
+}
+
+#[test]
+fn test_zstd_compression_levels() {
+    // Compress the same data at different zstd levels
     let epoch_ending_ledger_infos = create_epoch_ending_ledger_infos(0, 999);
-    test_compress_and_decompress(epoch_ending_ledger_infos);
+    let bcs_encoded_bytes = bcs::to_bytes(&epoch_ending_ledger_infos).unwrap();
+    let compressed_bytes: Vec<_> = [1, 19]
+        .into_iter()
+        .map(|level| {
+            crate::compress_with_algorithm(
+                bcs_encoded_bytes.clone(),
+                CompressionClient::StateSync,
+                &CompressionAlgorithm::Zstd(level),
+                MAX_COMPRESSION_SIZE,
+            )
+            .unwrap()
+        })
+        .collect();
 
-    // Test transaction outputs with proof
-    let outputs_with_proof = create_output_list_with_proof(13434, 17000, 19000);
-    test_compress_and_decompress(outputs_with_proof);
+    // Verify that the higher level offers a better compression ratio
+    assert!(compressed_bytes[1].len() < compressed_bytes[0].len());
 
-    // Test transactions with proof
-    let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
-    test_compress_and_decompress(transactions_with_proof);
+    // Verify that decompression doesn't depend on the level used for compression
+    for compressed_bytes in compressed_bytes {
+        let decompressed_bytes = crate::decompress_with_algorithm(
+            &compressed_bytes,
+            CompressionClient::StateSync,
+            &CompressionAlgorithm::Zstd(0),
+            MAX_COMPRESSION_SIZE,
+        )
+        .unwrap();
+        assert_eq!(decompressed_bytes, bcs_encoded_bytes);
+    }
 }
 
 #[test]
 fn test_compression_limits() {
-    // Create test data
-    let too_small_bytes = 1;
-    let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
+    for algorithm in [CompressionAlgorithm::Lz4, CompressionAlgorithm::zstd()] {
+        // Create test data
+        let too_small_bytes = 1;
+        let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
 
-    // Test compression limit
-    let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
-    let maybe_compressed_bytes = crate::compress(
-        bcs_encoded_bytes,
-        CompressionClient::StateSync,
-        too_small_bytes,
-    );
-    assert!(maybe_compressed_bytes.is_err());
+        // Test compression limit
+        let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
+        let maybe_compressed_bytes = crate::compress_with_algorithm(
+            bcs_encoded_bytes,
+            CompressionClient::StateSync,
+            &algorithm,
+            too_small_bytes,
+        );
+        assert!(maybe_compressed_bytes.is_err());
+
+        // Test decompression limit
+        let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
+        let compressed_bytes = crate::compress_with_algorithm(
+            bcs_encoded_bytes,
+            CompressionClient::StateSync,
+            &algorithm,
+            MAX_COMPRESSION_SIZE,
+        )
+        .unwrap();
+        let maybe_decompressed_bytes = crate::decompress_with_algorithm(
+            &compressed_bytes,
+            CompressionClient::StateSync,
+            &algorithm,
+            too_small_bytes,
+        );
+        assert!(maybe_decompressed_bytes.is_err());
+    }
+}
 
-    // Test decompression limit
+#[test]
+fn test_mismatched_algorithms() {
+    // Compress the data using LZ4
+    let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
     let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
     let compressed_bytes = crate::compress(
         bcs_encoded_bytes,
@@ -66,27 +126,35 @@ fn test_compression_limits() {
         MAX_COMPRESSION_SIZE,
     )
     .unwrap();
-    let maybe_decompressed_bytes = crate::decompress(
+
+    // Verify that decompression using zstd fails
+    let maybe_decompressed_bytes = crate::decompress_with_algorithm(
         &compressed_bytes,
         CompressionClient::StateSync,
-        too_small_bytes,
+        &CompressionAlgorithm::zstd(),
+        MAX_COMPRESSION_SIZE,
     );
     assert!(maybe_decompressed_bytes.is_err());
 }
 
 /// Ensures that the given object can be compressed and decompressed successfully
 /// when BCS encoded.
-fn test_compress_and_decompress<T: Debug + DeserializeOwned + PartialEq + Serialize>(object: T) {
+fn test_compress_and_decompress<T: Debug + DeserializeOwned + PartialEq + Serialize>(
+    object: T,
+    algorithm: &CompressionAlgorithm,
+) {
     let bcs_encoded_bytes = bcs::to_bytes(&object).unwrap();
-    let compressed_bytes = crate::compress(
+    let compressed_bytes = crate::compress_with_algorithm(
         bcs_encoded_bytes,
         CompressionClient::StateSync,
+        algorithm,
         MAX_COMPRESSION_SIZE,
     )
     .unwrap();
-    let decompressed_bytes = crate::decompress(
+    let decompressed_bytes = crate::decompress_with_algorithm(
         &compressed_bytes,
         CompressionClient::StateSync,
+        algorithm,
         MAX_COMPRESSION_SIZE,
     )
     .unwrap();
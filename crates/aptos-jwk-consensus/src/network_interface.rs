@@ -24,6 +24,11 @@ pub const RPC: &[ProtocolId] = &[
     ProtocolId::JWKConsensusRpcJson,
 ];
 
+/// The zstd compressed protocols. These are preferred over all other
+/// protocols, but only if zstd compression is enabled in the config.
+pub const DIRECT_SEND_ZSTD: ProtocolId = ProtocolId::JWKConsensusDirectSendCompressedZstd;
+pub const RPC_ZSTD: ProtocolId = ProtocolId::JWKConsensusRpcCompressedZstd;
+
 #[derive(Clone)]
 pub struct JWKConsensusNetworkClient<NetworkClient> {
     network_client: NetworkClient,
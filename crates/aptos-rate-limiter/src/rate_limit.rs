@@ -174,6 +174,14 @@ impl<Key: Eq + Hash + Clone + Debug> TokenBucketRateLimiter<Key> {
         }
         remove
     }
+
+    /// Garbage collects all buckets that are full and not in use.  These are only equivalent to
+    /// new buckets if new buckets start full, so this is meant for keys that come and go.
+    pub fn garbage_collect_full_buckets(&self) {
+        self.buckets
+            .write()
+            .retain(|_, bucket| Arc::strong_count(bucket) > 1 || !bucket.lock().is_full());
+    }
 }
 
 /// A token bucket object that keeps track of everything related to a key
@@ -378,6 +386,12 @@ impl Bucket {
         self.allowed_in_period = self.allowed_in_period.saturating_sub(new_tokens);
         self.add_tokens(new_tokens);
     }
+
+    /// Tells us if the bucket is full, after refilling it
+    pub fn is_full(&mut self) -> bool {
+        self.refill();
+        self.tokens >= self.size
+    }
 }
 
 #[cfg(test)]
@@ -549,4 +563,26 @@ mod tests {
         assert!(!rate_limiter.try_garbage_collect_key(&key_to_keep));
         assert_num_keys(&rate_limiter, 1);
     }
+
+    #[test]
+    fn test_garbage_collect_full_buckets() {
+        let rate_limiter = TokenBucketRateLimiter::test(1, 1);
+
+        // A full bucket that isn't in use is collected
+        {
+            let _bucket_arc = rate_limiter.bucket("full");
+        }
+        // A bucket that isn't full is kept
+        {
+            let bucket_arc = rate_limiter.bucket("empty");
+            assert_acquire(&mut bucket_arc.lock(), 1);
+        }
+        // A bucket in use is kept
+        let _bucket_arc = rate_limiter.bucket("in use");
+        assert_num_keys(&rate_limiter, 3);
+
+        rate_limiter.garbage_collect_full_buckets();
+        assert_num_keys(&rate_limiter, 2);
+        assert!(!rate_limiter.buckets.read().contains_key("full"));
+    }
 }
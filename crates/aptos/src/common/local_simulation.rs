@@ -3,13 +3,23 @@
 
 use crate::common::types::{CliError, CliTypedResult};
 use aptos_crypto::HashValue;
+#[cfg(feature = "move-debug")]
+use aptos_framework::{BuildOptions, BuiltPackage};
 use aptos_gas_profiling::FrameName;
 use aptos_move_debugger::aptos_debugger::AptosDebugger;
 use aptos_types::transaction::SignedTransaction;
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use aptos_vm_logging::log_schema::AdapterLogSchema;
 use aptos_vm_types::{output::VMOutput, resolver::StateStorageView};
+#[cfg(feature = "move-debug")]
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+#[cfg(feature = "move-debug")]
+use move_core_types::account_address::AccountAddress;
 use move_core_types::vm_status::VMStatus;
+#[cfg(feature = "move-debug")]
+use move_debug_adapter::{DebugSession, SourceIndex};
+#[cfg(feature = "move-debug")]
+use std::collections::BTreeMap;
 use std::{path::Path, time::Instant};
 
 pub fn run_transaction_using_debugger(
@@ -29,6 +39,56 @@ pub fn run_transaction_using_debugger(
     Ok((vm_status, vm_output))
 }
 
+/// Builds the package at `package_dir` to collect the sources and source maps of its modules and
+/// their dependencies, which are needed to debug them.
+#[cfg(feature = "move-debug")]
+pub fn build_debug_source_index(
+    package_dir: &Path,
+    named_addresses: BTreeMap<String, AccountAddress>,
+) -> CliTypedResult<SourceIndex> {
+    let package = BuiltPackage::build(package_dir.to_path_buf(), BuildOptions {
+        named_addresses,
+        ..BuildOptions::default()
+    })
+    .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+
+    let mut index = SourceIndex::default();
+    for unit in package.package.all_modules() {
+        if let CompiledUnit::Module(NamedCompiledModule {
+            module, source_map, ..
+        }) = &unit.unit
+        {
+            let contents = std::fs::read_to_string(&unit.source_path)
+                .map_err(|err| CliError::IO(unit.source_path.display().to_string(), err))?;
+            index.add_file(&unit.source_path, &contents);
+            index.add_module(module, source_map);
+        }
+    }
+    Ok(index)
+}
+
+/// Runs the transaction under the control of a Debug Adapter Protocol client connecting on the
+/// given port.
+#[cfg(feature = "move-debug")]
+pub fn debug_transaction_using_debugger(
+    port: u16,
+    index: SourceIndex,
+    debugger: &AptosDebugger,
+    version: u64,
+    transaction: SignedTransaction,
+    hash: HashValue,
+) -> CliTypedResult<(VMStatus, VMOutput)> {
+    let session = DebugSession::listen(port, index)
+        .map_err(|err| CliError::IO(format!("Debug adapter port {}", port), err))?;
+    session.install();
+    let result = run_transaction_using_debugger(debugger, version, transaction, hash);
+    session.finish(match &result {
+        Ok((VMStatus::Executed, _)) => 0,
+        _ => 1,
+    });
+    result
+}
+
 pub fn benchmark_transaction_using_debugger(
     debugger: &AptosDebugger,
     version: u64,
@@ -1522,6 +1522,26 @@ pub struct TransactionOptions {
     /// flamegraphs that reflect the gas usage.
     #[clap(long)]
     pub(crate) profile_gas: bool,
+
+    #[cfg(feature = "move-debug")]
+    /// If this option is set together with `--local`, wait for a Debug Adapter Protocol client
+    /// to connect on this port and simulate the transaction under its control.
+    #[clap(long, requires = "local")]
+    pub(crate) debug_adapter_port: Option<u16>,
+
+    #[cfg(feature = "move-debug")]
+    /// Path to the Move package whose sources are used for debugging.
+    ///
+    /// Defaults to the current directory.
+    #[clap(long, value_parser, requires = "debug_adapter_port")]
+    pub(crate) debug_package_dir: Option<PathBuf>,
+
+    #[cfg(feature = "move-debug")]
+    /// Named addresses needed to build the package used for debugging
+    ///
+    /// Example: alice=0x1234, bob=0x5678
+    #[clap(long, value_parser = crate::common::utils::parse_map::<String, AccountAddressWrapper>, default_value = "")]
+    pub(crate) debug_named_addresses: BTreeMap<String, AccountAddressWrapper>,
 }
 
 impl TransactionOptions {
@@ -1828,6 +1848,29 @@ impl TransactionOptions {
         println!();
         println!("Simulating transaction locally...");
 
+        #[cfg(feature = "move-debug")]
+        if let Some(port) = self.debug_adapter_port {
+            let package_dir = dir_default_to_current(self.debug_package_dir.clone())?;
+            let named_addresses = self
+                .debug_named_addresses
+                .iter()
+                .map(|(name, address)| (name.clone(), address.account_address))
+                .collect();
+            let index = local_simulation::build_debug_source_index(&package_dir, named_addresses)?;
+            return self
+                .simulate_using_debugger(payload, |debugger, version, transaction, hash| {
+                    local_simulation::debug_transaction_using_debugger(
+                        port,
+                        index,
+                        debugger,
+                        version,
+                        transaction,
+                        hash,
+                    )
+                })
+                .await;
+        }
+
         self.simulate_using_debugger(payload, local_simulation::run_transaction_using_debugger)
             .await
     }
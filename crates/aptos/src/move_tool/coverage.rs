@@ -1,17 +1,25 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::common::types::{CliCommand, CliError, CliResult, CliTypedResult, MovePackageDir};
+use crate::common::{
+    types::{CliCommand, CliError, CliResult, CliTypedResult, MovePackageDir},
+    utils::write_to_file,
+};
 use aptos_framework::extended_checks;
 use async_trait::async_trait;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
 use move_coverage::{
-    coverage_map::CoverageMap, format_csv_summary, format_human_summary,
+    cobertura::write_cobertura, coverage_map::CoverageMap, format_csv_summary,
+    format_human_summary, lcov::write_lcov, line_coverage::ModuleLineCoverage,
     source_coverage::SourceCoverageBuilder, summary::summarize_inst_cov,
 };
 use move_disassembler::disassembler::Disassembler;
 use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig, CompilerConfig};
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 /// Display a coverage summary for all modules in a package
 ///
@@ -142,6 +150,85 @@ impl CliCommand<()> for BytecodeCoverage {
     }
 }
 
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum CoverageFormat {
+    /// LCOV tracefile
+    Lcov,
+    /// Cobertura XML
+    Cobertura,
+}
+
+/// Export line, function and branch coverage of all modules in a package
+///
+/// The coverage is mapped from bytecode to source lines, for CI systems and editors to ingest.
+/// Branches are the outcomes of conditional jumps in the bytecode.
+#[derive(Debug, Parser)]
+pub struct ExportCoverage {
+    /// Format to export the coverage in
+    #[clap(long, value_enum, ignore_case = true, default_value_t = CoverageFormat::Lcov)]
+    pub format: CoverageFormat,
+    /// File to write the coverage to
+    #[clap(long, value_parser)]
+    pub output_file: PathBuf,
+    /// A filter string to determine which modules to export coverage of
+    #[clap(long, short)]
+    pub filter: Option<String>,
+    #[clap(flatten)]
+    pub move_options: MovePackageDir,
+}
+
+#[async_trait]
+impl CliCommand<()> for ExportCoverage {
+    fn command_name(&self) -> &'static str {
+        "ExportCoverage"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        let package_path = self.move_options.get_package_path()?;
+        let (coverage_map, package) = compile_coverage(self.move_options)?;
+        let coverage_map = coverage_map.to_unified_exec_map();
+        let modules = package
+            .root_modules()
+            .filter(|unit| match &self.filter {
+                Some(filter_str) => unit.unit.name().as_str().contains(filter_str.as_str()),
+                None => true,
+            })
+            .filter_map(|unit| match &unit.unit {
+                CompiledUnit::Module(NamedCompiledModule {
+                    module, source_map, ..
+                }) => Some(ModuleLineCoverage::new(
+                    module,
+                    &coverage_map,
+                    source_map,
+                    &unit.source_path,
+                )),
+                _ => None,
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to map coverage to source {}", err))
+            })?;
+
+        let mut bytes = Vec::new();
+        match self.format {
+            CoverageFormat::Lcov => write_lcov(&modules, &mut bytes),
+            CoverageFormat::Cobertura => write_cobertura(
+                &modules,
+                package.compiled_package_info.package_name.as_str(),
+                &package_path,
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                &mut bytes,
+            ),
+        }
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to export coverage {}", err)))?;
+
+        write_to_file(&self.output_file, "coverage", &bytes)
+    }
+}
+
 fn compile_coverage(
     move_options: MovePackageDir,
 ) -> CliTypedResult<(CoverageMap, CompiledPackage)> {
@@ -178,6 +265,7 @@ pub enum CoveragePackage {
     Summary(SummaryCoverage),
     Source(SourceCoverage),
     Bytecode(BytecodeCoverage),
+    Export(ExportCoverage),
 }
 
 impl CoveragePackage {
@@ -186,6 +274,7 @@ impl CoveragePackage {
             Self::Summary(tool) => tool.execute_serialized_success().await,
             Self::Source(tool) => tool.execute_serialized_success().await,
             Self::Bytecode(tool) => tool.execute_serialized_success().await,
+            Self::Export(tool) => tool.execute_serialized_success().await,
         }
     }
 }
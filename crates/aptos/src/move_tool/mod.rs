@@ -70,6 +70,7 @@ mod bytecode;
 pub mod coverage;
 mod manifest;
 pub mod package_hooks;
+mod replay_range;
 mod show;
 pub mod stored_package;
 
@@ -105,6 +106,7 @@ pub enum MoveTool {
     VerifyPackage(VerifyPackage),
     View(ViewFunction),
     Replay(Replay),
+    ReplayRange(replay_range::ReplayRange),
 }
 
 impl MoveTool {
@@ -137,6 +139,7 @@ impl MoveTool {
             MoveTool::VerifyPackage(tool) => tool.execute_serialized().await,
             MoveTool::View(tool) => tool.execute_serialized().await,
             MoveTool::Replay(tool) => tool.execute_serialized().await,
+            MoveTool::ReplayRange(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -462,6 +465,12 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
+
+    #[cfg(feature = "move-debug")]
+    /// Wait for a Debug Adapter Protocol client to connect on this port and run the tests under
+    /// its control
+    #[clap(long)]
+    pub debug_adapter_port: Option<u16>,
 }
 
 #[async_trait]
@@ -497,6 +506,8 @@ impl CliCommand<&'static str> for TestPackage {
                 report_stacktrace_on_abort: true,
                 report_storage_on_error: self.dump_state,
                 ignore_compile_warnings: self.ignore_compile_warnings,
+                #[cfg(feature = "move-debug")]
+                debug_adapter_port: self.debug_adapter_port,
                 ..UnitTestingConfig::default_with_bound(None)
             },
             // TODO(Gas): we may want to switch to non-zero costs in the future
@@ -1508,13 +1519,24 @@ pub enum ReplayNetworkSelection {
 
 /// Replay a comitted transaction using a local VM.
 #[derive(Parser, Debug)]
+#[clap(group(clap::ArgGroup::new("source")
+        .required(true)
+        .multiple(false)
+        .args(&["network", "db_path"]),
+))]
 pub struct Replay {
     /// The network to replay on.
     ///
     /// Possible values:
     ///     mainnet, testnet, <REST_ENDPOINT_URL>
     #[clap(long)]
-    pub(crate) network: ReplayNetworkSelection,
+    pub(crate) network: Option<ReplayNetworkSelection>,
+
+    /// Path to a local AptosDB (e.g., a restored snapshot) to replay from.
+    ///
+    /// The DB is opened read-only, and avoids the rate limits of REST endpoints.
+    #[clap(long, value_parser)]
+    pub(crate) db_path: Option<PathBuf>,
 
     /// The id of the transaction to replay. Also being referred to as "version" in some contexts.
     #[clap(long)]
@@ -1533,6 +1555,67 @@ pub struct Replay {
     pub(crate) skip_comparison: bool,
 }
 
+impl ReplayNetworkSelection {
+    /// Returns the REST endpoint of the selected network
+    pub fn rest_endpoint(&self) -> &str {
+        match self {
+            Self::Mainnet => "https://fullnode.mainnet.aptoslabs.com",
+            Self::Testnet => "https://fullnode.testnet.aptoslabs.com",
+            Self::Devnet => "https://fullnode.devnet.aptoslabs.com",
+            Self::RestEndpoint(url) => url,
+        }
+    }
+}
+
+/// The source of the on-chain state a transaction is replayed against
+#[derive(Debug, PartialEq)]
+pub(crate) enum ReplaySource<'a> {
+    Db(&'a Path),
+    Rest(Url),
+}
+
+impl<'a> ReplaySource<'a> {
+    /// Picks the local DB (if a path is given), or the REST endpoint of the
+    /// selected network.
+    pub(crate) fn new(
+        network: Option<&ReplayNetworkSelection>,
+        db_path: Option<&'a Path>,
+    ) -> CliTypedResult<Self> {
+        if let Some(db_path) = db_path {
+            if !db_path.is_dir() {
+                return Err(CliError::CommandArgumentError(format!(
+                    "The db path {} is not a directory",
+                    db_path.display()
+                )));
+            }
+            return Ok(Self::Db(db_path));
+        }
+
+        let rest_endpoint = network
+            .ok_or_else(|| {
+                CliError::CommandArgumentError(
+                    "Either the network or the db path must be provided".to_string(),
+                )
+            })?
+            .rest_endpoint();
+        Ok(Self::Rest(Url::parse(rest_endpoint).map_err(|_err| {
+            CliError::UnableToParse("url", rest_endpoint.to_string())
+        })?))
+    }
+}
+
+/// Creates a debugger that reads from the local DB (if a path is given),
+/// or from the REST endpoint of the selected network.
+pub(crate) fn create_replay_debugger(
+    network: Option<&ReplayNetworkSelection>,
+    db_path: Option<&Path>,
+) -> CliTypedResult<AptosDebugger> {
+    Ok(match ReplaySource::new(network, db_path)? {
+        ReplaySource::Db(db_path) => AptosDebugger::db(db_path)?,
+        ReplaySource::Rest(url) => AptosDebugger::rest_client(Client::new(url))?,
+    })
+}
+
 impl FromStr for ReplayNetworkSelection {
     type Err = CliError;
 
@@ -1553,25 +1636,13 @@ impl CliCommand<TransactionSummary> for Replay {
     }
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
-        use ReplayNetworkSelection::*;
-
         if self.profile_gas && self.benchmark {
             return Err(CliError::UnexpectedError(
                 "Cannot perform benchmarking and gas profiling at the same time.".to_string(),
             ));
         }
 
-        let rest_endpoint = match &self.network {
-            Mainnet => "https://fullnode.mainnet.aptoslabs.com",
-            Testnet => "https://fullnode.testnet.aptoslabs.com",
-            Devnet => "https://fullnode.devnet.aptoslabs.com",
-            RestEndpoint(url) => url,
-        };
-
-        let debugger = AptosDebugger::rest_client(Client::new(
-            Url::parse(rest_endpoint)
-                .map_err(|_err| CliError::UnableToParse("url", rest_endpoint.to_string()))?,
-        ))?;
+        let debugger = create_replay_debugger(self.network.as_ref(), self.db_path.as_deref())?;
 
         // Fetch the transaction to replay.
         let (txn, txn_info) = debugger
@@ -2119,3 +2190,68 @@ impl FromStr for MemberId {
         parse_member_id(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_temppath::TempPath;
+
+    #[test]
+    fn test_replay_source() {
+        // The REST endpoint of the selected network is used without a db path
+        assert_eq!(
+            ReplaySource::new(Some(&ReplayNetworkSelection::Mainnet), None).unwrap(),
+            ReplaySource::Rest(Url::parse("https://fullnode.mainnet.aptoslabs.com").unwrap())
+        );
+        let network = ReplayNetworkSelection::from_str("http://localhost:8080").unwrap();
+        assert_eq!(
+            ReplaySource::new(Some(&network), None).unwrap(),
+            ReplaySource::Rest(Url::parse("http://localhost:8080").unwrap())
+        );
+        assert!(create_replay_debugger(Some(&network), None).is_ok());
+
+        // The local DB is used if a db path is given
+        let db_path = TempPath::new();
+        db_path.create_as_dir().unwrap();
+        assert_eq!(
+            ReplaySource::new(None, Some(db_path.path())).unwrap(),
+            ReplaySource::Db(db_path.path())
+        );
+
+        // Invalid endpoints are rejected
+        let network = ReplayNetworkSelection::from_str("not a url").unwrap();
+        assert!(ReplaySource::new(Some(&network), None).is_err());
+
+        // A source must be given
+        assert!(create_replay_debugger(None, None).is_err());
+    }
+
+    #[test]
+    fn test_replay_missing_db_path() {
+        let db_path = TempPath::new();
+        assert!(matches!(
+            create_replay_debugger(None, Some(db_path.path())),
+            Err(CliError::CommandArgumentError(_))
+        ));
+    }
+
+    #[test]
+    fn test_replay_source_args() {
+        // Exactly one of the network and the db path must be given
+        assert!(
+            Replay::try_parse_from(["replay", "--txn-id", "1", "--network", "mainnet"]).is_ok()
+        );
+        assert!(Replay::try_parse_from(["replay", "--txn-id", "1", "--db-path", "db"]).is_ok());
+        assert!(Replay::try_parse_from(["replay", "--txn-id", "1"]).is_err());
+        assert!(Replay::try_parse_from([
+            "replay",
+            "--txn-id",
+            "1",
+            "--network",
+            "mainnet",
+            "--db-path",
+            "db",
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,497 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{CliError, CliTypedResult},
+        utils::write_to_file,
+    },
+    move_tool::{create_replay_debugger, ReplayNetworkSelection},
+    CliCommand,
+};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_framework::{BuildOptions, BuiltPackage};
+use aptos_move_debugger::aptos_debugger::AptosDebugger;
+use aptos_rest_client::aptos_api_types::MoveModuleId;
+use aptos_types::{
+    on_chain_config::GasScheduleV2,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{
+        ExecutionStatus, MultisigTransactionPayload, Transaction, TransactionInfo,
+        TransactionOutput, TransactionPayload, TransactionStatus, Version,
+    },
+    write_set::WriteSet,
+};
+use async_trait::async_trait;
+use clap::Parser;
+use move_binary_format::file_format::CompiledScript;
+use move_core_types::language_storage::ModuleId;
+use serde::Serialize;
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::PathBuf,
+};
+
+/// The number of transactions to fetch from the data source at once
+const FETCH_BATCH_SIZE: u64 = 100;
+
+/// Replay a range of committed transactions using a local VM, and report
+/// every transaction whose status, gas used or write set diverged from the
+/// on-chain results.
+///
+/// Each transaction is executed individually against the on-chain state at its
+/// version, so a divergence in one transaction does not affect the others.
+/// Locally-modified Move packages (e.g., the framework) and the gas schedule
+/// compiled into this CLI can be used in place of the on-chain ones.
+#[derive(Parser, Debug)]
+#[clap(group(clap::ArgGroup::new("source")
+        .required(true)
+        .multiple(false)
+        .args(&["network", "db_path"]),
+))]
+pub struct ReplayRange {
+    /// The network to replay on.
+    ///
+    /// Possible values:
+    ///     mainnet, testnet, <REST_ENDPOINT_URL>
+    #[clap(long)]
+    pub(crate) network: Option<ReplayNetworkSelection>,
+
+    /// Path to a local AptosDB to replay from (instead of a REST endpoint).
+    #[clap(long, value_parser)]
+    pub(crate) db_path: Option<PathBuf>,
+
+    /// The first version (inclusive) of the range to replay.
+    #[clap(long)]
+    pub(crate) start_version: u64,
+
+    /// The last version (exclusive) of the range to replay.
+    #[clap(long)]
+    pub(crate) end_version: u64,
+
+    /// If set, only replay the transactions that may touch the given module
+    /// (e.g., `0x1::coin`), i.e., the transactions calling one of its entry
+    /// functions (directly or via a multisig account) or running a script
+    /// that references it.
+    #[clap(long)]
+    pub(crate) module: Option<MoveModuleId>,
+
+    /// Paths to locally-modified Move packages whose modules should be used
+    /// instead of the on-chain ones (e.g., `aptos-move/framework/aptos-framework`).
+    ///
+    /// Note: only the module code is overridden, the on-chain package metadata is kept.
+    #[clap(long, value_parser)]
+    pub(crate) override_package_dir: Vec<PathBuf>,
+
+    /// If set, use the gas schedule compiled into this CLI instead of the on-chain one.
+    #[clap(long)]
+    pub(crate) use_local_gas_schedule: bool,
+
+    /// If set, write the divergence report (as JSON) to the given file.
+    #[clap(long, value_parser)]
+    pub(crate) output_file: Option<PathBuf>,
+}
+
+/// A summary of the replayed transactions, and all divergences found
+#[derive(Debug, Serialize)]
+pub struct ReplayReport {
+    pub start_version: Version,
+    pub end_version: Version,
+    pub replayed_transactions: u64,
+    pub skipped_transactions: u64,
+    pub divergences: Vec<TransactionDivergence>,
+}
+
+/// A single transaction whose replayed output diverged from the on-chain one
+#[derive(Debug, Serialize)]
+pub struct TransactionDivergence {
+    pub version: Version,
+    pub transaction_hash: HashValue,
+    pub expected_status: String,
+    pub actual_status: String,
+    pub expected_gas_used: u64,
+    pub actual_gas_used: u64,
+    pub write_set_diverged: bool,
+    /// The state keys written differently than when replaying with the on-chain
+    /// state. Only available if state values were overridden.
+    pub diverged_state_keys: Vec<String>,
+}
+
+#[async_trait]
+impl CliCommand<ReplayReport> for ReplayRange {
+    fn command_name(&self) -> &'static str {
+        "ReplayRange"
+    }
+
+    async fn execute(self) -> CliTypedResult<ReplayReport> {
+        if self.start_version >= self.end_version {
+            return Err(CliError::CommandArgumentError(format!(
+                "The start version ({}) must be smaller than the end version ({})",
+                self.start_version, self.end_version
+            )));
+        }
+
+        let debugger = create_replay_debugger(self.network.as_ref(), self.db_path.as_deref())?;
+        let module: Option<ModuleId> = self.module.clone().map(Into::into);
+        let overrides = self.build_state_overrides()?;
+
+        let mut report = ReplayReport {
+            start_version: self.start_version,
+            end_version: self.end_version,
+            replayed_transactions: 0,
+            skipped_transactions: 0,
+            divergences: vec![],
+        };
+
+        let mut begin = self.start_version;
+        while begin < self.end_version {
+            let limit = std::cmp::min(FETCH_BATCH_SIZE, self.end_version - begin);
+            let (txns, txn_infos) = debugger.get_committed_transactions(begin, limit).await?;
+            if txns.is_empty() {
+                break;
+            }
+
+            for (idx, (txn, txn_info)) in txns.into_iter().zip(txn_infos).enumerate() {
+                let version = begin + idx as u64;
+
+                // Genesis (and other write set transactions) can't be replayed
+                let should_replay = !matches!(txn, Transaction::GenesisTransaction(_))
+                    && match &module {
+                        Some(module) => touches_module(&txn, module),
+                        None => true,
+                    };
+                if !should_replay {
+                    report.skipped_transactions += 1;
+                    continue;
+                }
+
+                eprintln!("Replaying transaction at version {}...", version);
+                if let Some(divergence) =
+                    replay_transaction(&debugger, version, txn, &txn_info, &overrides)?
+                {
+                    report.divergences.push(divergence);
+                }
+                report.replayed_transactions += 1;
+            }
+            begin += limit;
+        }
+
+        if let Some(output_file) = &self.output_file {
+            let report_json = serde_json::to_string_pretty(&report).map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to serialize the report: {}", err))
+            })?;
+            write_to_file(
+                output_file.as_path(),
+                "Replay report",
+                report_json.as_bytes(),
+            )?;
+        }
+
+        Ok(report)
+    }
+}
+
+impl ReplayRange {
+    /// Builds the state values that should be used instead of the on-chain ones
+    fn build_state_overrides(&self) -> CliTypedResult<HashMap<StateKey, StateValue>> {
+        let mut overrides = HashMap::new();
+
+        for package_dir in &self.override_package_dir {
+            eprintln!("Building package at {}...", package_dir.display());
+            let package = BuiltPackage::build(package_dir.clone(), BuildOptions::default())
+                .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+            for (module, code) in package.modules().zip(package.extract_code()) {
+                overrides.insert(
+                    StateKey::module_id(&module.self_id()),
+                    StateValue::new_legacy(code.into()),
+                );
+            }
+        }
+
+        if self.use_local_gas_schedule {
+            let gas_schedule = aptos_vm_genesis::default_gas_schedule();
+            let state_key = StateKey::on_chain_config::<GasScheduleV2>().map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to create the state key: {}", err))
+            })?;
+            let bytes = bcs::to_bytes(&gas_schedule)?;
+            overrides.insert(state_key, StateValue::new_legacy(bytes.into()));
+        }
+
+        Ok(overrides)
+    }
+}
+
+/// Replays a single transaction at the given version, and returns the divergence
+/// from the on-chain transaction info (if any).
+fn replay_transaction(
+    debugger: &AptosDebugger,
+    version: Version,
+    txn: Transaction,
+    txn_info: &TransactionInfo,
+    overrides: &HashMap<StateKey, StateValue>,
+) -> CliTypedResult<Option<TransactionDivergence>> {
+    let output = execute_transaction(debugger, version, txn.clone(), overrides)?;
+
+    let expected_status: TransactionStatus = txn_info.status().clone().into();
+    let write_set_diverged = CryptoHash::hash(output.write_set()) != txn_info.state_change_hash();
+    if output.status() == &expected_status
+        && output.gas_used() == txn_info.gas_used()
+        && !write_set_diverged
+    {
+        return Ok(None);
+    }
+
+    // If the state was overridden, replay the transaction against the on-chain
+    // state to determine which state keys were written differently.
+    let diverged_state_keys = if write_set_diverged && !overrides.is_empty() {
+        let expected_output = execute_transaction(debugger, version, txn.clone(), &HashMap::new())?;
+        diff_write_sets(expected_output.write_set(), output.write_set())
+    } else {
+        vec![]
+    };
+
+    Ok(Some(TransactionDivergence {
+        version,
+        transaction_hash: txn.hash(),
+        expected_status: format_status(&expected_status),
+        actual_status: format_status(output.status()),
+        expected_gas_used: txn_info.gas_used(),
+        actual_gas_used: output.gas_used(),
+        write_set_diverged,
+        diverged_state_keys,
+    }))
+}
+
+/// Executes a single transaction at the given version (using the given overrides)
+fn execute_transaction(
+    debugger: &AptosDebugger,
+    version: Version,
+    txn: Transaction,
+    overrides: &HashMap<StateKey, StateValue>,
+) -> CliTypedResult<TransactionOutput> {
+    let mut outputs =
+        debugger.execute_transactions_at_version_with_overrides(version, vec![txn], overrides)?;
+    outputs.pop().ok_or_else(|| {
+        CliError::UnexpectedError(format!(
+            "No transaction output was produced at version {}",
+            version
+        ))
+    })
+}
+
+/// Returns the state keys that were written differently in the two write sets
+fn diff_write_sets(expected: &WriteSet, actual: &WriteSet) -> Vec<String> {
+    let expected: HashMap<_, _> = expected.iter().collect();
+    let actual: HashMap<_, _> = actual.iter().collect();
+    let state_keys: BTreeSet<String> = expected
+        .keys()
+        .chain(actual.keys())
+        .filter(|state_key| expected.get(*state_key) != actual.get(*state_key))
+        .map(|state_key| format!("{:?}", state_key))
+        .collect();
+    state_keys.into_iter().collect()
+}
+
+/// Returns true iff the transaction may touch the given module, i.e., it calls
+/// an entry function of the module (directly or via a multisig account), or it
+/// runs a script that references the module.
+fn touches_module(txn: &Transaction, module: &ModuleId) -> bool {
+    let signed_txn = match txn {
+        Transaction::UserTransaction(signed_txn) => signed_txn,
+        _ => return false,
+    };
+    match signed_txn.payload() {
+        TransactionPayload::EntryFunction(entry_function) => entry_function.module() == module,
+        TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+            Some(MultisigTransactionPayload::EntryFunction(entry_function)) => {
+                entry_function.module() == module
+            },
+            // The payload was stored on-chain when the multisig transaction was
+            // created (only its hash is known here), so replay it to be safe.
+            None => true,
+        },
+        TransactionPayload::Script(script) => script_references_module(script.code(), module),
+        TransactionPayload::ModuleBundle(_) => false,
+    }
+}
+
+/// Returns true iff the script references the given module. Scripts that
+/// can't be deserialized are assumed to reference it (so they are replayed).
+fn script_references_module(code: &[u8], module: &ModuleId) -> bool {
+    match CompiledScript::deserialize(code) {
+        Ok(script) => script
+            .module_handles
+            .iter()
+            .any(|handle| &script.module_id_for_handle(handle) == module),
+        Err(_) => true,
+    }
+}
+
+fn format_status(status: &TransactionStatus) -> String {
+    match status {
+        TransactionStatus::Keep(ExecutionStatus::Success) => "success".to_string(),
+        status => format!("{:?}", status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+    use aptos_types::{
+        chain_id::ChainId,
+        transaction::{EntryFunction, Multisig, RawTransaction, Script},
+        write_set::{WriteOp, WriteSetMut},
+    };
+    use move_binary_format::file_format::{
+        empty_script, AddressIdentifierIndex, IdentifierIndex, ModuleHandle,
+    };
+    use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+
+    #[test]
+    fn test_diff_write_sets() {
+        let unchanged_key = StateKey::raw(b"unchanged");
+        let modified_key = StateKey::raw(b"modified");
+        let expected_only_key = StateKey::raw(b"expected_only");
+        let actual_only_key = StateKey::raw(b"actual_only");
+
+        let expected = WriteSetMut::new(vec![
+            (
+                unchanged_key.clone(),
+                WriteOp::legacy_modification(vec![0u8].into()),
+            ),
+            (
+                modified_key.clone(),
+                WriteOp::legacy_modification(vec![1u8].into()),
+            ),
+            (expected_only_key.clone(), WriteOp::legacy_deletion()),
+        ])
+        .freeze()
+        .unwrap();
+        let actual = WriteSetMut::new(vec![
+            (
+                unchanged_key,
+                WriteOp::legacy_modification(vec![0u8].into()),
+            ),
+            (
+                modified_key.clone(),
+                WriteOp::legacy_modification(vec![2u8].into()),
+            ),
+            (
+                actual_only_key.clone(),
+                WriteOp::legacy_creation(vec![3u8].into()),
+            ),
+        ])
+        .freeze()
+        .unwrap();
+
+        // Only the keys written differently are reported (sorted and deduplicated)
+        let mut expected_keys: Vec<String> = [modified_key, expected_only_key, actual_only_key]
+            .iter()
+            .map(|state_key| format!("{:?}", state_key))
+            .collect();
+        expected_keys.sort();
+        assert_eq!(diff_write_sets(&expected, &actual), expected_keys);
+
+        // Identical write sets have no diverged keys
+        assert!(diff_write_sets(&expected, &expected).is_empty());
+    }
+
+    #[test]
+    fn test_touches_module() {
+        let coin = module_id("coin");
+        let account = module_id("account");
+
+        // Entry functions only touch their own module
+        let entry_function = create_user_txn(TransactionPayload::EntryFunction(
+            create_entry_function(&coin),
+        ));
+        assert!(touches_module(&entry_function, &coin));
+        assert!(!touches_module(&entry_function, &account));
+
+        // Multisig entry functions only touch their own module
+        let multisig = create_user_txn(TransactionPayload::Multisig(Multisig {
+            multisig_address: AccountAddress::random(),
+            transaction_payload: Some(MultisigTransactionPayload::EntryFunction(
+                create_entry_function(&coin),
+            )),
+        }));
+        assert!(touches_module(&multisig, &coin));
+        assert!(!touches_module(&multisig, &account));
+
+        // Multisig transactions with an on-chain payload are always replayed
+        let multisig_on_chain = create_user_txn(TransactionPayload::Multisig(Multisig {
+            multisig_address: AccountAddress::random(),
+            transaction_payload: None,
+        }));
+        assert!(touches_module(&multisig_on_chain, &account));
+
+        // Scripts touch the modules they reference
+        let script = create_user_txn(TransactionPayload::Script(Script::new(
+            create_script_code(&coin),
+            vec![],
+            vec![],
+        )));
+        assert!(touches_module(&script, &coin));
+        assert!(!touches_module(&script, &account));
+
+        // Scripts that can't be deserialized are always replayed
+        let invalid_script = create_user_txn(TransactionPayload::Script(Script::new(
+            vec![0xDE, 0xAD],
+            vec![],
+            vec![],
+        )));
+        assert!(touches_module(&invalid_script, &account));
+
+        // Non-user transactions never touch a module
+        assert!(!touches_module(
+            &Transaction::StateCheckpoint(HashValue::zero()),
+            &coin
+        ));
+    }
+
+    fn module_id(name: &str) -> ModuleId {
+        ModuleId::new(AccountAddress::ONE, Identifier::new(name).unwrap())
+    }
+
+    fn create_entry_function(module: &ModuleId) -> EntryFunction {
+        EntryFunction::new(
+            module.clone(),
+            Identifier::new("transfer").unwrap(),
+            vec![],
+            vec![],
+        )
+    }
+
+    fn create_script_code(module: &ModuleId) -> Vec<u8> {
+        let mut script = empty_script();
+        script.address_identifiers.push(*module.address());
+        script.identifiers.push(module.name().to_owned());
+        script.module_handles.push(ModuleHandle {
+            address: AddressIdentifierIndex(0),
+            name: IdentifierIndex(0),
+        });
+
+        let mut code = vec![];
+        script.serialize(&mut code).unwrap();
+        code
+    }
+
+    fn create_user_txn(payload: TransactionPayload) -> Transaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_txn = RawTransaction::new(
+            AccountAddress::random(),
+            0,
+            payload,
+            0,
+            0,
+            0,
+            ChainId::test(),
+        );
+        let signed_txn = raw_txn
+            .sign(&private_key, private_key.public_key())
+            .unwrap()
+            .into_inner();
+        Transaction::UserTransaction(signed_txn)
+    }
+}
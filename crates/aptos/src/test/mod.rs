@@ -861,6 +861,8 @@ impl CliTestFramework {
             ignore_compile_warnings: false,
             compute_coverage: false,
             dump_state: false,
+            #[cfg(feature = "move-debug")]
+            debug_adapter_port: None,
         }
         .execute()
         .await
@@ -96,6 +96,7 @@ pub fn bootstrap(
             mp_sender,
             node_config,
             None, /* table info reader */
+            None, /* event by type reader */
         ));
         run_forever(indexer_config, context).await;
     });
@@ -22,6 +22,9 @@ pub const DIRECT_SEND: &[ProtocolId] = &[
     ProtocolId::DKGDirectSendJson,
 ];
 
+pub const RPC_ZSTD: ProtocolId = ProtocolId::DKGRpcCompressedZstd;
+pub const DIRECT_SEND_ZSTD: ProtocolId = ProtocolId::DKGDirectSendCompressedZstd;
+
 #[derive(Clone)]
 pub struct DKGNetworkClient<NetworkClient> {
     network_client: NetworkClient,
@@ -0,0 +1,15 @@
+#!/bin/sh
+# Copyright © Aptos Foundation
+# SPDX-License-Identifier: Apache-2.0
+
+# This script is a utility script used by some rust tests such as aptos-core/secure/storage/src/tests/pkcs11.rs .
+# It initializes a SoftHSM token, the tests are then run with:
+#   APTOS_PKCS11_TEST_MODULE=/usr/lib/softhsm/libsofthsm2.so cargo test -p aptos-secure-storage pkcs11 -- --ignored
+# If this is not directly referenced anywhere in the codebase anymore you can delete it.
+
+set -ex
+
+LABEL="${APTOS_PKCS11_TEST_TOKEN:-aptos-test}"
+PIN="${APTOS_PKCS11_TEST_PIN:-1234}"
+
+softhsm2-util --init-token --free --label "$LABEL" --pin "$PIN" --so-pin "$PIN"
@@ -19,13 +19,17 @@ use aptos_indexer_grpc_utils::{
     },
     counters::{log_grpc_step, IndexerGrpcStep, NUM_MULTI_FETCH_OVERLAPPED_VERSIONS},
     file_store_operator::FileStoreOperator,
+    filter::{filter_and_chunk_transactions, Filterable},
     in_memory_cache::InMemoryCache,
     time_diff_since_pb_timestamp_in_secs,
     types::RedisUrl,
 };
 use aptos_moving_average::MovingAverage;
 use aptos_protos::{
-    indexer::v1::{raw_data_server::RawData, GetTransactionsRequest, TransactionsResponse},
+    indexer::v1::{
+        raw_data_server::RawData, BooleanTransactionFilter, GetTransactionsRequest,
+        TransactionsResponse,
+    },
     transaction::v1::{transaction::TxnData, Transaction},
 };
 use futures::Stream;
@@ -148,6 +152,15 @@ impl RawData for RawDataServerWrapper {
         let request = req.into_inner();
 
         let transactions_count = request.transactions_count;
+        let transaction_filter = request.transaction_filter;
+        if let Some(transaction_filter) = transaction_filter.as_ref() {
+            if let Err(e) = transaction_filter.validate() {
+                return Result::Err(Status::invalid_argument(format!(
+                    "Invalid transaction filter: {}",
+                    e
+                )));
+            }
+        }
 
         // Response channel to stream the data to the client.
         let (tx, rx) = channel(self.data_service_response_channel_size);
@@ -191,6 +204,7 @@ impl RawData for RawDataServerWrapper {
                     transactions_count,
                     tx,
                     sender_addresses_to_ignore,
+                    transaction_filter,
                     current_version,
                     in_memory_cache,
                 )
@@ -379,6 +393,7 @@ async fn data_fetcher_task(
     transactions_count: Option<u64>,
     tx: tokio::sync::mpsc::Sender<Result<TransactionsResponse, Status>>,
     sender_addresses_to_ignore: HashSet<String>,
+    transaction_filter: Option<BooleanTransactionFilter>,
     mut current_version: u64,
     in_memory_cache: Arc<InMemoryCache>,
 ) {
@@ -516,20 +531,20 @@ async fn data_fetcher_task(
         // 2. Push the data to the response channel, i.e. stream the data to the client.
         let current_batch_size = transaction_data.as_slice().len();
         let end_of_batch_version = transaction_data.as_slice().last().unwrap().version;
-        let resp_items = get_transactions_responses_builder(
-            transaction_data,
-            chain_id as u32,
-            &sender_addresses_to_ignore,
-        );
-        let data_latency_in_secs = resp_items
-            .last()
-            .unwrap()
-            .transactions
+        // The latency is based on the last transaction of the batch, even if it's filtered out.
+        let data_latency_in_secs = transaction_data
+            .as_slice()
             .last()
             .unwrap()
             .timestamp
             .as_ref()
             .map(time_diff_since_pb_timestamp_in_secs);
+        let resp_items = get_transactions_responses_builder(
+            transaction_data,
+            chain_id as u32,
+            &sender_addresses_to_ignore,
+            transaction_filter.as_ref(),
+        );
 
         match channel_send_multiple_with_timeout(resp_items, tx.clone(), request_metadata.clone())
             .await
@@ -681,21 +696,23 @@ fn ensure_sequential_transactions(mut batches: Vec<Vec<Transaction>>) -> Vec<Tra
 }
 
 /// Builds the response for the get transactions request. Partial batch is ok, i.e., a batch with transactions < 1000.
+/// With a transaction filter, only the matching transactions are returned, and a response can have no transactions
+/// at all; the processed range of the responses still covers the whole batch.
 fn get_transactions_responses_builder(
     transactions: Vec<Transaction>,
     chain_id: u32,
     sender_addresses_to_ignore: &HashSet<String>,
+    transaction_filter: Option<&BooleanTransactionFilter>,
 ) -> Vec<TransactionsResponse> {
-    let filtered_transactions =
+    // Wipe the ignored senders first, so the filter can't match on their payloads or events.
+    let transactions =
         filter_transactions_for_sender_addresses(transactions, sender_addresses_to_ignore);
-    let chunks = chunk_transactions(filtered_transactions, MESSAGE_SIZE_LIMIT);
-    chunks
-        .into_iter()
-        .map(|chunk| TransactionsResponse {
-            chain_id: Some(chain_id as u64),
-            transactions: chunk,
-        })
-        .collect()
+    filter_and_chunk_transactions(
+        transactions,
+        transaction_filter,
+        MESSAGE_SIZE_LIMIT,
+        chain_id as u64,
+    )
 }
 
 // This is a CPU bound operation, so we spawn_blocking
@@ -891,33 +908,47 @@ async fn channel_send_multiple_with_timeout(
         .iter()
         .map(|resp_item| resp_item.encoded_len())
         .sum::<usize>();
-    let overall_start_txn = resp_items.first().unwrap().transactions.first().unwrap();
-    let overall_end_txn = resp_items.last().unwrap().transactions.last().unwrap();
-    let overall_start_version = overall_start_txn.version;
-    let overall_end_version = overall_end_txn.version;
-    let overall_start_txn_timestamp = overall_start_txn.clone().timestamp;
-    let overall_end_txn_timestamp = overall_end_txn.clone().timestamp;
+    // Responses can be empty when a transaction filter is set, so the versions come from the
+    // processed ranges and the timestamps are only logged when available.
+    let overall_start_version = resp_items
+        .first()
+        .unwrap()
+        .processed_range
+        .as_ref()
+        .unwrap()
+        .first_version;
+    let overall_end_version = resp_items
+        .last()
+        .unwrap()
+        .processed_range
+        .as_ref()
+        .unwrap()
+        .last_version;
+    let overall_start_txn_timestamp = resp_items
+        .iter()
+        .find_map(|resp_item| resp_item.transactions.first())
+        .and_then(|txn| txn.timestamp.clone());
+    let overall_end_txn_timestamp = resp_items
+        .iter()
+        .rev()
+        .find_map(|resp_item| resp_item.transactions.last())
+        .and_then(|txn| txn.timestamp.clone());
 
     for resp_item in resp_items {
         let send_start_time = Instant::now();
         let response_size = resp_item.encoded_len();
         let num_of_transactions = resp_item.transactions.len();
-        let start_version = resp_item.transactions.first().unwrap().version;
-        let end_version = resp_item.transactions.last().unwrap().version;
+        let processed_range = resp_item.processed_range.clone().unwrap();
+        let start_version = processed_range.first_version;
+        let end_version = processed_range.last_version;
         let start_version_txn_timestamp = resp_item
             .transactions
             .first()
-            .unwrap()
-            .timestamp
-            .as_ref()
-            .unwrap();
+            .and_then(|txn| txn.timestamp.clone());
         let end_version_txn_timestamp = resp_item
             .transactions
             .last()
-            .unwrap()
-            .timestamp
-            .as_ref()
-            .unwrap();
+            .and_then(|txn| txn.timestamp.clone());
 
         tx.send_timeout(
             Result::<TransactionsResponse, Status>::Ok(resp_item.clone()),
@@ -930,8 +961,8 @@ async fn channel_send_multiple_with_timeout(
             IndexerGrpcStep::DataServiceChunkSent,
             Some(start_version as i64),
             Some(end_version as i64),
-            Some(start_version_txn_timestamp),
-            Some(end_version_txn_timestamp),
+            start_version_txn_timestamp.as_ref(),
+            end_version_txn_timestamp.as_ref(),
             Some(send_start_time.elapsed().as_secs_f64()),
             Some(response_size),
             Some(num_of_transactions as i64),
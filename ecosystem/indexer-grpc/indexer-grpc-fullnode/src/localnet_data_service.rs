@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{stream_coordinator::IndexerStreamCoordinator, ServiceContext};
+use aptos_indexer_grpc_utils::filter::{filter_and_chunk_transactions, Filterable};
 use aptos_logger::{error, info};
 use aptos_protos::{
     indexer::v1::{raw_data_server::RawData, GetTransactionsRequest, TransactionsResponse},
@@ -44,6 +45,15 @@ impl RawData for LocalnetDataService {
         let output_batch_size = self.service_context.output_batch_size;
         let ledger_chain_id = context.chain_id().id();
         let transactions_count = r.transactions_count;
+        let transaction_filter = r.transaction_filter;
+        if let Some(transaction_filter) = transaction_filter.as_ref() {
+            if let Err(e) = transaction_filter.validate() {
+                return Err(Status::invalid_argument(format!(
+                    "Invalid transaction filter: {}",
+                    e
+                )));
+            }
+        }
         // Creates a channel to send the stream to the client
         let (tx, mut rx) = mpsc::channel(TRANSACTION_CHANNEL_SIZE);
         let (external_service_tx, external_service_rx) = mpsc::channel(TRANSACTION_CHANNEL_SIZE);
@@ -90,9 +100,8 @@ impl RawData for LocalnetDataService {
                     }
                 }
 
-                let response = response.map(|t| TransactionsResponse {
-                    chain_id: Some(ledger_chain_id as u64),
-                    transactions: match t.response.expect("Response must be set") {
+                let response = match response {
+                    Ok(t) => match t.response.expect("Response must be set") {
                         transactions_from_node_response::Response::Data(transaction_output) => {
                             let mut transactions = transaction_output.transactions;
                             let current_transactions_count = transactions.len() as u64;
@@ -101,11 +110,25 @@ impl RawData for LocalnetDataService {
                                     transactions.into_iter().take(*count as usize).collect();
                                 *count = count.saturating_sub(current_transactions_count);
                             }
-                            transactions
+                            // No size limit, so there is at most one response, which always
+                            // carries the processed range. An empty batch covers no versions,
+                            // so there is nothing to send.
+                            match filter_and_chunk_transactions(
+                                transactions,
+                                transaction_filter.as_ref(),
+                                usize::MAX,
+                                ledger_chain_id as u64,
+                            )
+                            .pop()
+                            {
+                                Some(response) => Ok(response),
+                                None => continue,
+                            }
                         },
                         _ => panic!("Unexpected response type."),
                     },
-                });
+                    Err(e) => Err(e),
+                };
                 match external_service_tx.send(response).await {
                     Ok(_) => {},
                     Err(e) => {
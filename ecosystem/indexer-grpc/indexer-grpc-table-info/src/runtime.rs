@@ -50,6 +50,7 @@ pub fn bootstrap(
             mp_sender,
             node_config.clone(),
             None,
+            None,
         ));
 
         let mut parser = TableInfoService::new(
@@ -58,6 +59,7 @@ pub fn bootstrap(
             node_config.indexer_table_info.parser_task_count,
             node_config.indexer_table_info.parser_batch_size,
             node_config.indexer_table_info.enable_expensive_logging,
+            node_config.indexer_table_info.index_events_by_type,
             indexer_async_v2_clone,
         );
 
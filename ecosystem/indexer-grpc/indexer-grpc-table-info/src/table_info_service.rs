@@ -10,7 +10,7 @@ use aptos_indexer_grpc_fullnode::stream_coordinator::{
 };
 use aptos_indexer_grpc_utils::counters::{log_grpc_step, IndexerGrpcStep};
 use aptos_logger::{debug, error, info, sample, sample::SampleRate};
-use aptos_types::write_set::WriteSet;
+use aptos_types::{contract_event::ContractEvent, write_set::WriteSet};
 use std::{sync::Arc, time::Duration};
 use tonic::Status;
 
@@ -24,6 +24,7 @@ pub struct TableInfoService {
     pub parser_batch_size: u16,
     pub context: Arc<Context>,
     pub enable_expensive_logging: bool,
+    pub index_events_by_type: bool,
     pub indexer_async_v2: Arc<IndexerAsyncV2>,
 }
 
@@ -34,6 +35,7 @@ impl TableInfoService {
         parser_task_count: u16,
         parser_batch_size: u16,
         enable_expensive_logging: bool,
+        index_events_by_type: bool,
         indexer_async_v2: Arc<IndexerAsyncV2>,
     ) -> Self {
         Self {
@@ -42,6 +44,7 @@ impl TableInfoService {
             parser_batch_size,
             context,
             enable_expensive_logging,
+            index_events_by_type,
             indexer_async_v2,
         }
     }
@@ -49,7 +52,7 @@ impl TableInfoService {
     /// 1. fetch new transactions
     /// 2. break them down into batches in parser_batch_size and spawn up threads in parser_task_count
     /// 3. parse write sets from transactions with move annotater to get table handle -> key, value type
-    /// 4. write parsed table info to rocksdb
+    /// 4. write parsed table info to rocksdb, along with the events by type index if enabled
     /// 5. after all batches from the loop complete, if pending on items not empty, move on to 6, otherwise, start from 1 again
     /// 6. retry all the txns in the loop sequentially to clean up the pending on items
     pub async fn run(&mut self) {
@@ -102,6 +105,7 @@ impl TableInfoService {
                 ledger_version,
                 batch,
                 false, /* end_early_if_pending_on_empty */
+                self.index_events_by_type,
                 self.enable_expensive_logging,
             ));
             tasks.push(task);
@@ -127,6 +131,8 @@ impl TableInfoService {
                 //
                 // Risk of this sequential approach is that it could be slow when the txns to process contain extremely
                 // nested table items, but the risk is bounded by the configuration of the number of txns to process and number of threads
+                //
+                // Events have already been indexed by the parallel batches, so they are skipped here
                 if !self.indexer_async_v2.is_indexer_async_v2_pending_on_empty() {
                     let retry_batch = TransactionBatchInfo {
                         start_version: self.current_version,
@@ -139,7 +145,8 @@ impl TableInfoService {
                         indexer_async_v2.clone(),
                         ledger_version,
                         retry_batch,
-                        true, /* end_early_if_pending_on_empty */
+                        true,  /* end_early_if_pending_on_empty */
+                        false, /* index_events_by_type */
                         self.enable_expensive_logging,
                     )
                     .await
@@ -175,6 +182,7 @@ impl TableInfoService {
         ledger_version: u64,
         batch: TransactionBatchInfo,
         end_early_if_pending_on_empty: bool,
+        index_events_by_type: bool,
         _enable_verbose_logging: bool,
     ) -> Result<EndVersion, Status> {
         let start_time = std::time::Instant::now();
@@ -189,11 +197,16 @@ impl TableInfoService {
         Self::parse_table_info(
             context.clone(),
             raw_txns.clone(),
-            indexer_async_v2,
+            indexer_async_v2.clone(),
             end_early_if_pending_on_empty,
         )
         .expect("[Table Info] Failed to parse table info");
 
+        if index_events_by_type {
+            Self::index_events_by_type(&raw_txns, indexer_async_v2)
+                .expect("[Table Info] Failed to index events by type");
+        }
+
         log_grpc_step(
             SERVICE_TYPE,
             IndexerGrpcStep::TableInfoProcessedBatch,
@@ -294,6 +307,22 @@ impl TableInfoService {
         Ok(())
     }
 
+    /// Index the events emitted by the transactions by their type
+    fn index_events_by_type(
+        raw_txns: &[TransactionOnChainData],
+        indexer_async_v2: Arc<IndexerAsyncV2>,
+    ) -> Result<(), Error> {
+        if raw_txns.is_empty() {
+            return Ok(());
+        }
+
+        let first_version = raw_txns.first().map(|txn| txn.version).unwrap();
+        let events: Vec<&[ContractEvent]> =
+            raw_txns.iter().map(|txn| txn.events.as_slice()).collect();
+        indexer_async_v2.index_events_by_type(first_version, &events)?;
+        Ok(())
+    }
+
     /// TODO(jill): consolidate it with `ensure_highest_known_version`
     /// Will keep looping and checking the latest ledger info to see if there are new transactions
     /// If there are, it will update the ledger version version
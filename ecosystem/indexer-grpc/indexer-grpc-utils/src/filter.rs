@@ -0,0 +1,710 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Evaluation of the transaction filters of `GetTransactionsRequest`.
+
+use anyhow::{bail, ensure, Result};
+use aptos_protos::{
+    indexer::v1::{
+        api_filter, boolean_transaction_filter, ApiFilter, BooleanTransactionFilter,
+        EntryFunctionFilter, EventFilter, MoveStructTagFilter, ProcessedRange,
+        TransactionRootFilter, TransactionsResponse, UserTransactionFilter, WriteSetChangeFilter,
+    },
+    transaction::v1::{
+        move_type, multisig_transaction_payload,
+        transaction::{TransactionType, TxnData},
+        transaction_payload, write_set_change, EntryFunctionPayload, Event, MoveStructTag,
+        Transaction,
+    },
+};
+
+/// Filters nested deeper than this are rejected, so a request can't make the server recurse
+/// without bounds.
+pub const MAX_FILTER_DEPTH: usize = 16;
+
+/// A filter that can be validated once per request and then evaluated against every transaction.
+pub trait Filterable<T> {
+    /// Returns an error if the filter can never be evaluated meaningfully, e.g., if it is empty.
+    fn validate(&self) -> Result<()>;
+
+    fn matches(&self, item: &T) -> bool;
+}
+
+impl Filterable<Transaction> for BooleanTransactionFilter {
+    fn validate(&self) -> Result<()> {
+        validate_with_depth(self, 0)
+    }
+
+    fn matches(&self, txn: &Transaction) -> bool {
+        match self.filter.as_ref() {
+            Some(boolean_transaction_filter::Filter::ApiFilter(filter)) => filter.matches(txn),
+            Some(boolean_transaction_filter::Filter::LogicalAnd(and)) => {
+                and.filters.iter().all(|filter| filter.matches(txn))
+            },
+            Some(boolean_transaction_filter::Filter::LogicalOr(or)) => {
+                or.filters.iter().any(|filter| filter.matches(txn))
+            },
+            Some(boolean_transaction_filter::Filter::LogicalNot(not)) => !not.matches(txn),
+            // Rejected by validation.
+            None => false,
+        }
+    }
+}
+
+fn validate_with_depth(filter: &BooleanTransactionFilter, depth: usize) -> Result<()> {
+    ensure!(
+        depth < MAX_FILTER_DEPTH,
+        "Filter is nested deeper than {} levels",
+        MAX_FILTER_DEPTH
+    );
+    match filter.filter.as_ref() {
+        Some(boolean_transaction_filter::Filter::ApiFilter(filter)) => filter.validate(),
+        Some(boolean_transaction_filter::Filter::LogicalAnd(and)) => {
+            ensure!(!and.filters.is_empty(), "logical_and must not be empty");
+            and.filters
+                .iter()
+                .try_for_each(|filter| validate_with_depth(filter, depth + 1))
+        },
+        Some(boolean_transaction_filter::Filter::LogicalOr(or)) => {
+            ensure!(!or.filters.is_empty(), "logical_or must not be empty");
+            or.filters
+                .iter()
+                .try_for_each(|filter| validate_with_depth(filter, depth + 1))
+        },
+        Some(boolean_transaction_filter::Filter::LogicalNot(not)) => {
+            validate_with_depth(not, depth + 1)
+        },
+        None => bail!("BooleanTransactionFilter must have a filter set"),
+    }
+}
+
+impl Filterable<Transaction> for ApiFilter {
+    fn validate(&self) -> Result<()> {
+        match self.filter.as_ref() {
+            Some(api_filter::Filter::TransactionRootFilter(filter)) => filter.validate(),
+            Some(api_filter::Filter::UserTransactionFilter(filter)) => filter.validate(),
+            Some(api_filter::Filter::EventFilter(filter)) => filter.validate(),
+            Some(api_filter::Filter::WriteSetChangeFilter(filter)) => filter.validate(),
+            None => bail!("APIFilter must have a filter set"),
+        }
+    }
+
+    fn matches(&self, txn: &Transaction) -> bool {
+        match self.filter.as_ref() {
+            Some(api_filter::Filter::TransactionRootFilter(filter)) => filter.matches(txn),
+            Some(api_filter::Filter::UserTransactionFilter(filter)) => filter.matches(txn),
+            Some(api_filter::Filter::EventFilter(filter)) => filter.matches(txn),
+            Some(api_filter::Filter::WriteSetChangeFilter(filter)) => filter.matches(txn),
+            None => false,
+        }
+    }
+}
+
+impl Filterable<Transaction> for TransactionRootFilter {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.success.is_some() || self.transaction_type.is_some(),
+            "TransactionRootFilter must have at least one field set"
+        );
+        if let Some(transaction_type) = self.transaction_type {
+            match TransactionType::from_i32(transaction_type) {
+                Some(TransactionType::Unspecified) | None => {
+                    bail!("Invalid transaction type {}", transaction_type)
+                },
+                Some(_) => {},
+            }
+        }
+        Ok(())
+    }
+
+    fn matches(&self, txn: &Transaction) -> bool {
+        if let Some(success) = self.success {
+            if txn.info.as_ref().map(|info| info.success) != Some(success) {
+                return false;
+            }
+        }
+        if let Some(transaction_type) = self.transaction_type {
+            if txn.r#type != transaction_type {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Filterable<Transaction> for UserTransactionFilter {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.sender.is_some() || self.entry_function.is_some(),
+            "UserTransactionFilter must have at least one field set"
+        );
+        if let Some(sender) = self.sender.as_ref() {
+            validate_address(sender)?;
+        }
+        if let Some(entry_function) = self.entry_function.as_ref() {
+            entry_function.validate()?;
+        }
+        Ok(())
+    }
+
+    fn matches(&self, txn: &Transaction) -> bool {
+        let request = match txn.txn_data.as_ref() {
+            Some(TxnData::User(user_transaction)) => match user_transaction.request.as_ref() {
+                Some(request) => request,
+                None => return false,
+            },
+            _ => return false,
+        };
+        if let Some(sender) = self.sender.as_ref() {
+            if !addresses_equal(sender, &request.sender) {
+                return false;
+            }
+        }
+        if let Some(entry_function) = self.entry_function.as_ref() {
+            let payload = request
+                .payload
+                .as_ref()
+                .and_then(|payload| payload.payload.as_ref());
+            let entry_function_payload = match payload {
+                Some(transaction_payload::Payload::EntryFunctionPayload(payload)) => Some(payload),
+                Some(transaction_payload::Payload::MultisigPayload(multisig)) => multisig
+                    .transaction_payload
+                    .as_ref()
+                    .and_then(|payload| payload.payload.as_ref())
+                    .map(|payload| match payload {
+                        multisig_transaction_payload::Payload::EntryFunctionPayload(payload) => {
+                            payload
+                        },
+                    }),
+                _ => None,
+            };
+            if !entry_function_payload.map_or(false, |payload| entry_function.matches(payload)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Filterable<EntryFunctionPayload> for EntryFunctionFilter {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.address.is_some() || self.module_name.is_some() || self.function.is_some(),
+            "EntryFunctionFilter must have at least one field set"
+        );
+        if let Some(address) = self.address.as_ref() {
+            validate_address(address)?;
+        }
+        Ok(())
+    }
+
+    fn matches(&self, payload: &EntryFunctionPayload) -> bool {
+        let function = match payload.function.as_ref() {
+            Some(function) => function,
+            None => return false,
+        };
+        let module = match function.module.as_ref() {
+            Some(module) => module,
+            None => return false,
+        };
+        self.address
+            .as_ref()
+            .map_or(true, |address| addresses_equal(address, &module.address))
+            && self
+                .module_name
+                .as_ref()
+                .map_or(true, |module_name| module_name == &module.name)
+            && self
+                .function
+                .as_ref()
+                .map_or(true, |name| name == &function.name)
+    }
+}
+
+impl Filterable<Transaction> for EventFilter {
+    fn validate(&self) -> Result<()> {
+        match self.struct_type.as_ref() {
+            Some(struct_type) => struct_type.validate(),
+            None => bail!("EventFilter must have struct_type set"),
+        }
+    }
+
+    fn matches(&self, txn: &Transaction) -> bool {
+        let struct_type = match self.struct_type.as_ref() {
+            Some(struct_type) => struct_type,
+            None => return false,
+        };
+        events(txn).iter().any(|event| {
+            match event.r#type.as_ref().and_then(|t| t.content.as_ref()) {
+                Some(move_type::Content::Struct(struct_tag)) => struct_type.matches(struct_tag),
+                _ => false,
+            }
+        })
+    }
+}
+
+impl Filterable<Transaction> for WriteSetChangeFilter {
+    fn validate(&self) -> Result<()> {
+        match self.resource_type.as_ref() {
+            Some(resource_type) => resource_type.validate(),
+            None => bail!("WriteSetChangeFilter must have resource_type set"),
+        }
+    }
+
+    fn matches(&self, txn: &Transaction) -> bool {
+        let resource_type = match self.resource_type.as_ref() {
+            Some(resource_type) => resource_type,
+            None => return false,
+        };
+        let changes = match txn.info.as_ref() {
+            Some(info) => &info.changes,
+            None => return false,
+        };
+        changes.iter().any(|change| {
+            let struct_tag = match change.change.as_ref() {
+                Some(write_set_change::Change::WriteResource(resource)) => resource.r#type.as_ref(),
+                Some(write_set_change::Change::DeleteResource(resource)) => {
+                    resource.r#type.as_ref()
+                },
+                _ => None,
+            };
+            struct_tag.map_or(false, |struct_tag| resource_type.matches(struct_tag))
+        })
+    }
+}
+
+impl Filterable<MoveStructTag> for MoveStructTagFilter {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.address.is_some() || self.module.is_some() || self.name.is_some(),
+            "MoveStructTagFilter must have at least one field set"
+        );
+        if let Some(address) = self.address.as_ref() {
+            validate_address(address)?;
+        }
+        Ok(())
+    }
+
+    fn matches(&self, struct_tag: &MoveStructTag) -> bool {
+        self.address.as_ref().map_or(true, |address| {
+            addresses_equal(address, &struct_tag.address)
+        }) && self
+            .module
+            .as_ref()
+            .map_or(true, |module| module == &struct_tag.module)
+            && self
+                .name
+                .as_ref()
+                .map_or(true, |name| name == &struct_tag.name)
+    }
+}
+
+/// Returns the events emitted by the transaction, if its type can emit any.
+fn events(txn: &Transaction) -> &[Event] {
+    match txn.txn_data.as_ref() {
+        Some(TxnData::User(user_transaction)) => &user_transaction.events,
+        Some(TxnData::BlockMetadata(block_metadata)) => &block_metadata.events,
+        Some(TxnData::Genesis(genesis)) => &genesis.events,
+        _ => &[],
+    }
+}
+
+/// Strips the `0x` prefix and leading zeros, so "0x1" and "0x0000...01" compare equal.
+fn normalize_address(address: &str) -> String {
+    let address = address.strip_prefix("0x").unwrap_or(address);
+    let address = address.trim_start_matches('0');
+    address.to_lowercase()
+}
+
+fn addresses_equal(filter_address: &str, address: &str) -> bool {
+    normalize_address(filter_address) == normalize_address(address)
+}
+
+fn validate_address(address: &str) -> Result<()> {
+    let hex = address.strip_prefix("0x").unwrap_or(address);
+    ensure!(
+        !hex.is_empty() && hex.len() <= 64 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        "Invalid address {}",
+        address
+    );
+    Ok(())
+}
+
+/// Applies the filter to a batch of consecutive transactions and splits the matching ones into
+/// responses of at most `chunk_size` bytes. Every response carries the range of versions it
+/// covers, so together they cover the whole batch even if no transaction matches.
+pub fn filter_and_chunk_transactions(
+    transactions: Vec<Transaction>,
+    filter: Option<&BooleanTransactionFilter>,
+    chunk_size: usize,
+    chain_id: u64,
+) -> Vec<TransactionsResponse> {
+    let (first_version, last_version) = match (transactions.first(), transactions.last()) {
+        (Some(first), Some(last)) => (first.version, last.version),
+        _ => return vec![],
+    };
+    let transactions = match filter {
+        Some(filter) => transactions
+            .into_iter()
+            .filter(|txn| filter.matches(txn))
+            .collect(),
+        None => transactions,
+    };
+
+    let chunks = crate::chunk_transactions(transactions, chunk_size);
+    if chunks.is_empty() {
+        return vec![TransactionsResponse {
+            transactions: vec![],
+            chain_id: Some(chain_id),
+            processed_range: Some(ProcessedRange {
+                first_version,
+                last_version,
+            }),
+        }];
+    }
+
+    let num_chunks = chunks.len();
+    let mut next_version = first_version;
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(idx, chunk)| {
+            // The last chunk also covers the filtered out transactions at the end of the batch.
+            let chunk_last_version = if idx + 1 == num_chunks {
+                last_version
+            } else {
+                chunk.last().unwrap().version
+            };
+            let processed_range = ProcessedRange {
+                first_version: next_version,
+                last_version: chunk_last_version,
+            };
+            next_version = chunk_last_version + 1;
+            TransactionsResponse {
+                transactions: chunk,
+                chain_id: Some(chain_id),
+                processed_range: Some(processed_range),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::{
+        indexer::v1::{LogicalAndFilters, LogicalOrFilters},
+        transaction::v1::{
+            EntryFunctionId, MoveModuleId, MoveType, MultisigPayload, MultisigTransactionPayload,
+            TransactionInfo, TransactionPayload, UserTransaction, UserTransactionRequest,
+            WriteResource, WriteSetChange,
+        },
+    };
+
+    fn struct_tag(address: &str, module: &str, name: &str) -> MoveStructTag {
+        MoveStructTag {
+            address: address.to_string(),
+            module: module.to_string(),
+            name: name.to_string(),
+            ..MoveStructTag::default()
+        }
+    }
+
+    fn struct_tag_filter(
+        address: Option<&str>,
+        module: Option<&str>,
+        name: Option<&str>,
+    ) -> MoveStructTagFilter {
+        MoveStructTagFilter {
+            address: address.map(ToString::to_string),
+            module: module.map(ToString::to_string),
+            name: name.map(ToString::to_string),
+        }
+    }
+
+    fn entry_function_payload(address: &str, module: &str, function: &str) -> EntryFunctionPayload {
+        EntryFunctionPayload {
+            function: Some(EntryFunctionId {
+                module: Some(MoveModuleId {
+                    address: address.to_string(),
+                    name: module.to_string(),
+                }),
+                name: function.to_string(),
+            }),
+            ..EntryFunctionPayload::default()
+        }
+    }
+
+    fn user_transaction(
+        version: u64,
+        sender: &str,
+        payload: transaction_payload::Payload,
+    ) -> Transaction {
+        Transaction {
+            version,
+            r#type: TransactionType::User as i32,
+            info: Some(TransactionInfo {
+                success: true,
+                changes: vec![WriteSetChange {
+                    change: Some(write_set_change::Change::WriteResource(WriteResource {
+                        r#type: Some(struct_tag("0x1", "account", "Account")),
+                        ..WriteResource::default()
+                    })),
+                    ..WriteSetChange::default()
+                }],
+                ..TransactionInfo::default()
+            }),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: sender.to_string(),
+                    payload: Some(TransactionPayload {
+                        payload: Some(payload),
+                        ..TransactionPayload::default()
+                    }),
+                    ..UserTransactionRequest::default()
+                }),
+                events: vec![Event {
+                    r#type: Some(MoveType {
+                        content: Some(move_type::Content::Struct(struct_tag(
+                            "0x1",
+                            "coin",
+                            "DepositEvent",
+                        ))),
+                        ..MoveType::default()
+                    }),
+                    ..Event::default()
+                }],
+            })),
+            ..Transaction::default()
+        }
+    }
+
+    fn transfer(version: u64, sender: &str) -> Transaction {
+        user_transaction(
+            version,
+            sender,
+            transaction_payload::Payload::EntryFunctionPayload(entry_function_payload(
+                "0x1",
+                "aptos_account",
+                "transfer",
+            )),
+        )
+    }
+
+    fn block_metadata(version: u64) -> Transaction {
+        Transaction {
+            version,
+            r#type: TransactionType::BlockMetadata as i32,
+            info: Some(TransactionInfo {
+                success: true,
+                ..TransactionInfo::default()
+            }),
+            txn_data: Some(TxnData::BlockMetadata(Default::default())),
+            ..Transaction::default()
+        }
+    }
+
+    fn api_filter(filter: api_filter::Filter) -> BooleanTransactionFilter {
+        BooleanTransactionFilter {
+            filter: Some(boolean_transaction_filter::Filter::ApiFilter(ApiFilter {
+                filter: Some(filter),
+            })),
+        }
+    }
+
+    fn sender_filter(sender: &str) -> BooleanTransactionFilter {
+        api_filter(api_filter::Filter::UserTransactionFilter(
+            UserTransactionFilter {
+                sender: Some(sender.to_string()),
+                entry_function: None,
+            },
+        ))
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(BooleanTransactionFilter::default().validate().is_err());
+        assert!(api_filter(api_filter::Filter::TransactionRootFilter(
+            TransactionRootFilter::default()
+        ))
+        .validate()
+        .is_err());
+        assert!(api_filter(api_filter::Filter::TransactionRootFilter(
+            TransactionRootFilter {
+                success: None,
+                transaction_type: Some(TransactionType::Unspecified as i32),
+            }
+        ))
+        .validate()
+        .is_err());
+        assert!(sender_filter("0xzz").validate().is_err());
+        assert!(sender_filter("0x1").validate().is_ok());
+        assert!(BooleanTransactionFilter {
+            filter: Some(boolean_transaction_filter::Filter::LogicalAnd(Box::new(
+                LogicalAndFilters { filters: vec![] }
+            ))),
+        }
+        .validate()
+        .is_err());
+
+        let mut nested = sender_filter("0x1");
+        for _ in 0..MAX_FILTER_DEPTH {
+            nested = BooleanTransactionFilter {
+                filter: Some(boolean_transaction_filter::Filter::LogicalNot(Box::new(
+                    nested,
+                ))),
+            };
+        }
+        assert!(nested.validate().is_err());
+    }
+
+    #[test]
+    fn test_transaction_root_filter() {
+        let filter = TransactionRootFilter {
+            success: Some(true),
+            transaction_type: Some(TransactionType::User as i32),
+        };
+        assert!(filter.matches(&transfer(1, "0x1")));
+        assert!(!filter.matches(&block_metadata(2)));
+
+        let mut failed = transfer(3, "0x1");
+        failed.info.as_mut().unwrap().success = false;
+        assert!(!filter.matches(&failed));
+    }
+
+    #[test]
+    fn test_user_transaction_filter() {
+        let sender = "0x00000000000000000000000000000000000000000000000000000000000000ab";
+        assert!(sender_filter("0xAB").matches(&transfer(1, sender)));
+        assert!(!sender_filter("0xac").matches(&transfer(1, sender)));
+        assert!(!sender_filter("0xab").matches(&block_metadata(2)));
+
+        let filter = UserTransactionFilter {
+            sender: None,
+            entry_function: Some(EntryFunctionFilter {
+                address: Some("0x1".to_string()),
+                module_name: Some("aptos_account".to_string()),
+                function: Some("transfer".to_string()),
+            }),
+        };
+        assert!(filter.matches(&transfer(1, sender)));
+
+        let multisig = user_transaction(
+            2,
+            sender,
+            transaction_payload::Payload::MultisigPayload(MultisigPayload {
+                multisig_address: "0xcafe".to_string(),
+                transaction_payload: Some(MultisigTransactionPayload {
+                    payload: Some(multisig_transaction_payload::Payload::EntryFunctionPayload(
+                        entry_function_payload("0x1", "aptos_account", "transfer"),
+                    )),
+                    ..MultisigTransactionPayload::default()
+                }),
+            }),
+        );
+        assert!(filter.matches(&multisig));
+
+        let other = user_transaction(
+            3,
+            sender,
+            transaction_payload::Payload::EntryFunctionPayload(entry_function_payload(
+                "0x1", "coin", "transfer",
+            )),
+        );
+        assert!(!filter.matches(&other));
+    }
+
+    #[test]
+    fn test_event_and_write_set_change_filters() {
+        let txn = transfer(1, "0x1");
+        let event_filter = EventFilter {
+            struct_type: Some(struct_tag_filter(Some("0x01"), Some("coin"), None)),
+        };
+        assert!(event_filter.matches(&txn));
+        let event_filter = EventFilter {
+            struct_type: Some(struct_tag_filter(None, None, Some("WithdrawEvent"))),
+        };
+        assert!(!event_filter.matches(&txn));
+
+        let write_set_change_filter = WriteSetChangeFilter {
+            resource_type: Some(struct_tag_filter(None, Some("account"), Some("Account"))),
+        };
+        assert!(write_set_change_filter.matches(&txn));
+        assert!(!write_set_change_filter.matches(&block_metadata(2)));
+    }
+
+    #[test]
+    fn test_logical_filters() {
+        let txn = transfer(1, "0x1");
+        let or = BooleanTransactionFilter {
+            filter: Some(boolean_transaction_filter::Filter::LogicalOr(Box::new(
+                LogicalOrFilters {
+                    filters: vec![sender_filter("0x2"), sender_filter("0x1")],
+                },
+            ))),
+        };
+        assert!(or.matches(&txn));
+
+        let and = BooleanTransactionFilter {
+            filter: Some(boolean_transaction_filter::Filter::LogicalAnd(Box::new(
+                LogicalAndFilters {
+                    filters: vec![sender_filter("0x2"), sender_filter("0x1")],
+                },
+            ))),
+        };
+        assert!(!and.matches(&txn));
+
+        let not = BooleanTransactionFilter {
+            filter: Some(boolean_transaction_filter::Filter::LogicalNot(Box::new(
+                and,
+            ))),
+        };
+        assert!(not.matches(&txn));
+    }
+
+    #[test]
+    fn test_filter_and_chunk_transactions() {
+        let transactions = vec![
+            transfer(10, "0x1"),
+            transfer(11, "0x2"),
+            transfer(12, "0x1"),
+            transfer(13, "0x2"),
+        ];
+
+        // Each transaction is larger than the chunk size, so it gets a chunk by itself.
+        let responses =
+            filter_and_chunk_transactions(transactions.clone(), Some(&sender_filter("0x1")), 1, 4);
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].transactions[0].version, 10);
+        assert_eq!(
+            responses[0].processed_range,
+            Some(ProcessedRange {
+                first_version: 10,
+                last_version: 10
+            })
+        );
+        assert_eq!(responses[1].transactions[0].version, 12);
+        assert_eq!(
+            responses[1].processed_range,
+            Some(ProcessedRange {
+                first_version: 11,
+                last_version: 13
+            })
+        );
+
+        // Nothing matches, a single empty response still covers the whole batch.
+        let responses =
+            filter_and_chunk_transactions(transactions, Some(&sender_filter("0x3")), 1, 4);
+        assert_eq!(responses.len(), 1);
+        assert!(responses[0].transactions.is_empty());
+        assert_eq!(
+            responses[0].processed_range,
+            Some(ProcessedRange {
+                first_version: 10,
+                last_version: 13
+            })
+        );
+
+        assert!(filter_and_chunk_transactions(vec![], None, 1, 4).is_empty());
+    }
+}
@@ -7,6 +7,7 @@ pub mod config;
 pub mod constants;
 pub mod counters;
 pub mod file_store_operator;
+pub mod filter;
 pub mod in_memory_cache;
 pub mod types;
 
@@ -61,7 +61,7 @@ where
         AptosDB::open(
             config.storage.get_dir_paths(),
             false, /* readonly */
-            config.storage.storage_pruner_config,
+            config.storage.storage_pruner_config.clone(),
             config.storage.rocksdb_configs,
             false,
             config.storage.buffered_state_target_items,
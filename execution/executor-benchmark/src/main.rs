@@ -11,7 +11,8 @@ use aptos_block_partitioner::{
     v2::config::PartitionerV2Config,
 };
 use aptos_config::config::{
-    EpochSnapshotPrunerConfig, LedgerPrunerConfig, PrunerConfig, StateMerklePrunerConfig,
+    EpochSnapshotPrunerConfig, LedgerHistoryRetentionConfig, LedgerPrunerConfig, PrunerConfig,
+    StateMerklePrunerConfig,
 };
 use aptos_executor::block_executor::TransactionBlockExecutor;
 use aptos_executor_benchmark::{native_executor::NativeExecutor, pipeline::PipelineConfig};
@@ -77,6 +78,8 @@ impl PrunerOpt {
             state_merkle_pruner_config: StateMerklePrunerConfig {
                 enable: self.enable_state_pruner,
                 prune_window: self.state_prune_window,
+                prune_window_secs: None,
+                max_db_size_bytes: None,
                 batch_size: self.state_pruning_batch_size,
             },
             epoch_snapshot_pruner_config: EpochSnapshotPrunerConfig {
@@ -87,8 +90,11 @@ impl PrunerOpt {
             ledger_pruner_config: LedgerPrunerConfig {
                 enable: self.enable_ledger_pruner,
                 prune_window: self.ledger_prune_window,
+                prune_window_secs: None,
+                max_db_size_bytes: None,
                 batch_size: self.ledger_pruning_batch_size,
                 user_pruning_window_offset: 0,
+                history_retention: LedgerHistoryRetentionConfig::default(),
             },
         }
     }
@@ -9,6 +9,7 @@ use crate::{
     logging::{LogEntry, LogSchema},
     shared_mempool::types::MultiBucketTimelineIndexIds,
 };
+use aptos_config::network_id::PeerNetworkId;
 use aptos_consensus_types::common::TransactionSummary;
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
@@ -352,10 +353,7 @@ impl MultiBucketTimelineIndex {
 
     #[inline]
     fn get_timeline(&mut self, ranking_score: u64) -> &mut TimelineIndex {
-        let index = self
-            .bucket_mins
-            .binary_search(&ranking_score)
-            .unwrap_or_else(|i| i - 1);
+        let index = self.get_bucket_index(ranking_score);
         self.timelines.get_mut(index).unwrap()
     }
 
@@ -385,12 +383,17 @@ impl MultiBucketTimelineIndex {
 
     #[inline]
     pub(crate) fn get_bucket(&self, ranking_score: u64) -> &str {
-        let index = self
-            .bucket_mins
-            .binary_search(&ranking_score)
-            .unwrap_or_else(|i| i - 1);
+        let index = self.get_bucket_index(ranking_score);
         self.bucket_mins_to_string[index].as_str()
     }
+
+    /// Returns the index of the timeline that holds transactions with `ranking_score`.
+    #[inline]
+    pub(crate) fn get_bucket_index(&self, ranking_score: u64) -> usize {
+        self.bucket_mins
+            .binary_search(&ranking_score)
+            .unwrap_or_else(|i| i - 1)
+    }
 }
 
 /// ParkingLotIndex keeps track of "not_ready" transactions, e.g., transactions that
@@ -400,8 +403,13 @@ pub struct ParkingLotIndex {
     // DS invariants:
     // 1. for each entry (account, txns) in `data`, `txns` is never empty
     // 2. for all accounts, data.get(account_indices.get(`account`)) == (account, sequence numbers of account's txns)
+    // 3. `over_quota_accounts` only holds accounts in `data`
+    // 4. for each peer, `peer_accounts` counts the parked txns of each account received from it
     data: Vec<(AccountAddress, BTreeSet<(u64, HashValue)>)>,
     account_indices: HashMap<AccountAddress, usize>,
+    // accounts over their fairness quota, whose txns are evicted first
+    over_quota_accounts: BTreeSet<AccountAddress>,
+    peer_accounts: HashMap<PeerNetworkId, HashMap<AccountAddress, usize>>,
     size: usize,
 }
 
@@ -410,6 +418,8 @@ impl ParkingLotIndex {
         Self {
             data: vec![],
             account_indices: HashMap::new(),
+            over_quota_accounts: BTreeSet::new(),
+            peer_accounts: HashMap::new(),
             size: 0,
         }
     }
@@ -449,6 +459,14 @@ impl ParkingLotIndex {
         };
         if is_new_entry {
             self.size += 1;
+            if let Some(peer) = txn.upstream_peer {
+                *self
+                    .peer_accounts
+                    .entry(peer)
+                    .or_default()
+                    .entry(*sender)
+                    .or_default() += 1;
+            }
         }
     }
 
@@ -458,6 +476,9 @@ impl ParkingLotIndex {
             if let Some((_account, txns)) = self.data.get_mut(index) {
                 if txns.remove(&(txn.txn.sequence_number(), txn.get_committed_hash())) {
                     self.size -= 1;
+                    if let Some(peer) = &txn.upstream_peer {
+                        self.remove_peer_account(peer, sender);
+                    }
                 }
 
                 // maintain DS invariant
@@ -465,6 +486,7 @@ impl ParkingLotIndex {
                     // remove account with no more txns
                     self.data.swap_remove(index);
                     self.account_indices.remove(sender);
+                    self.over_quota_accounts.remove(sender);
 
                     // update DS for account that was swapped in `swap_remove`
                     if let Some((swapped_account, _)) = self.data.get(index) {
@@ -475,6 +497,34 @@ impl ParkingLotIndex {
         }
     }
 
+    fn remove_peer_account(&mut self, peer: &PeerNetworkId, account: &AccountAddress) {
+        if let Some(accounts) = self.peer_accounts.get_mut(peer) {
+            if let Some(count) = accounts.get_mut(account) {
+                *count -= 1;
+                if *count == 0 {
+                    accounts.remove(account);
+                }
+            }
+            if accounts.is_empty() {
+                self.peer_accounts.remove(peer);
+            }
+        }
+    }
+
+    /// Marks `account` as over its fairness quota, if it has "non-ready" transactions.
+    pub(crate) fn mark_over_quota(&mut self, account: &AccountAddress) {
+        if self.account_indices.contains_key(account) {
+            self.over_quota_accounts.insert(*account);
+        }
+    }
+
+    /// Returns the accounts with "non-ready" transactions received from `peer`.
+    pub(crate) fn accounts_from(&self, peer: &PeerNetworkId) -> Vec<AccountAddress> {
+        self.peer_accounts
+            .get(peer)
+            .map_or_else(Vec::new, |accounts| accounts.keys().cloned().collect())
+    }
+
     pub(crate) fn contains(&self, account: &AccountAddress, seq_num: u64, hash: HashValue) -> bool {
         self.account_indices
             .get(account)
@@ -494,6 +544,30 @@ impl ParkingLotIndex {
         })
     }
 
+    /// Returns the "non-ready" transaction with the highest sequence number of an account over its
+    /// fairness quota.
+    pub(crate) fn get_poppable_over_quota(&self) -> Option<TxnPointer> {
+        self.over_quota_accounts
+            .iter()
+            .next()
+            .and_then(|account| self.get_poppable_of(account))
+    }
+
+    /// Returns the "non-ready" transaction of `account` with the highest sequence number.
+    fn get_poppable_of(&self, account: &AccountAddress) -> Option<TxnPointer> {
+        self.account_indices.get(account).and_then(|idx| {
+            self.data[*idx]
+                .1
+                .iter()
+                .next_back()
+                .map(|(seq_num, hash)| TxnPointer {
+                    sender: *account,
+                    sequence_number: *seq_num,
+                    hash: *hash,
+                })
+        })
+    }
+
     pub(crate) fn size(&self) -> usize {
         self.size
     }
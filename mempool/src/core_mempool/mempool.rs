@@ -8,13 +8,13 @@ use crate::{
     core_mempool::{
         index::TxnPointer,
         transaction::{InsertionInfo, MempoolTransaction, TimelineState},
-        transaction_store::TransactionStore,
+        transaction_store::{TransactionStatusWithTimeline, TransactionStore},
     },
     counters,
     logging::{LogEntry, LogSchema, TxnsLog},
     shared_mempool::types::MultiBucketTimelineIndexIds,
 };
-use aptos_config::config::NodeConfig;
+use aptos_config::{config::NodeConfig, network_id::PeerNetworkId};
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
@@ -231,6 +231,7 @@ impl Mempool {
         db_sequence_number: u64,
         timeline_state: TimelineState,
         client_submitted: bool,
+        upstream_peer: Option<PeerNetworkId>,
     ) -> MempoolStatus {
         trace!(
             LogSchema::new(LogEntry::AddTxn)
@@ -259,6 +260,7 @@ impl Mempool {
             db_sequence_number,
             now,
             client_submitted,
+            upstream_peer,
         );
 
         let status = self.transactions.insert(txn_info);
@@ -471,6 +473,43 @@ impl Mempool {
         self.transactions.timeline_range(start_end_pairs)
     }
 
+    /// Returns the inspection status of the transaction with `hash`.
+    pub(crate) fn get_transaction_status(
+        &self,
+        hash: HashValue,
+    ) -> Option<TransactionStatusWithTimeline> {
+        self.transactions.get_transaction_status(hash)
+    }
+
+    /// Returns the last seen sequence number of the account and the inspection status of its
+    /// transactions.
+    pub(crate) fn get_account_status(
+        &self,
+        address: &AccountAddress,
+    ) -> (Option<u64>, Vec<TransactionStatusWithTimeline>) {
+        self.transactions.get_account_status(address)
+    }
+
+    /// Returns the transactions to persist across restarts, within the given limits.
+    pub(crate) fn get_transactions_to_persist(
+        &self,
+        max_txns: usize,
+        max_bytes: u64,
+    ) -> Vec<SignedTransaction> {
+        self.transactions
+            .get_transactions_to_persist(max_txns, max_bytes)
+    }
+
+    /// Marks `senders` as over their fairness quota, so their parked transactions are evicted
+    /// first when mempool is full.
+    pub(crate) fn mark_over_quota(&mut self, senders: &[AccountAddress]) {
+        self.transactions.mark_over_quota(senders);
+    }
+
+    pub(crate) fn mark_over_quota_peer(&mut self, peer: &PeerNetworkId) {
+        self.transactions.mark_over_quota_peer(peer);
+    }
+
     pub fn gen_snapshot(&self) -> TxnsLog {
         self.transactions.gen_snapshot()
     }
@@ -9,6 +9,7 @@ mod transaction_store;
 
 #[cfg(test)]
 pub use self::transaction::{MempoolTransaction, SubmittedBy};
+pub(crate) use self::transaction_store::TransactionStatusWithTimeline;
 pub use self::{
     mempool::Mempool as CoreMempool, transaction::TimelineState,
     transaction_store::TXN_INDEX_ESTIMATED_BYTES,
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{core_mempool::TXN_INDEX_ESTIMATED_BYTES, counters};
+use aptos_config::network_id::PeerNetworkId;
 use aptos_crypto::HashValue;
 use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
 use serde::{Deserialize, Serialize};
@@ -25,6 +26,8 @@ pub struct MempoolTransaction {
     pub sequence_info: SequenceInfo,
     pub insertion_info: InsertionInfo,
     pub was_parked: bool,
+    // The peer the transaction was received from, if it was broadcast to this node.
+    pub upstream_peer: Option<PeerNetworkId>,
 }
 
 impl MempoolTransaction {
@@ -36,6 +39,7 @@ impl MempoolTransaction {
         seqno: u64,
         insertion_time: SystemTime,
         client_submitted: bool,
+        upstream_peer: Option<PeerNetworkId>,
     ) -> Self {
         Self {
             sequence_info: SequenceInfo {
@@ -48,6 +52,7 @@ impl MempoolTransaction {
             timeline_state,
             insertion_info: InsertionInfo::new(insertion_time, client_submitted, timeline_state),
             was_parked: false,
+            upstream_peer,
         }
     }
 
@@ -178,6 +183,7 @@ mod test {
             0,
             SystemTime::now(),
             false,
+            None,
         )
     }
 
@@ -14,9 +14,11 @@ use crate::{
     counters,
     counters::{BROADCAST_BATCHED_LABEL, BROADCAST_READY_LABEL, CONSENSUS_READY_LABEL},
     logging::{LogEntry, LogEvent, LogSchema, TxnsLog},
-    shared_mempool::types::MultiBucketTimelineIndexIds,
+    shared_mempool::types::{
+        MempoolTransactionState, MempoolTransactionStatus, MultiBucketTimelineIndexIds,
+    },
 };
-use aptos_config::config::MempoolConfig;
+use aptos_config::{config::MempoolConfig, network_id::PeerNetworkId};
 use aptos_crypto::HashValue;
 use aptos_logger::{prelude::*, Level};
 use aptos_types::{
@@ -26,7 +28,7 @@ use aptos_types::{
 };
 use std::{
     cmp::max,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     mem::size_of,
     ops::Bound,
     time::{Duration, SystemTime},
@@ -38,6 +40,10 @@ pub const TXN_INDEX_ESTIMATED_BYTES: usize = size_of::<crate::core_mempool::inde
     + (size_of::<u64>() * 3 + size_of::<AccountAddress>()) // timeline_index
     + (size_of::<HashValue>() + size_of::<u64>() + size_of::<AccountAddress>()); // hash_index
 
+/// The inspection status of a transaction, with the timeline bucket and ID it is broadcast from
+/// if it's ready for broadcast.
+pub(crate) type TransactionStatusWithTimeline = (MempoolTransactionStatus, Option<(usize, u64)>);
+
 /// TransactionStore is in-memory storage for all transactions in mempool.
 pub struct TransactionStore {
     // main DS
@@ -64,6 +70,9 @@ pub struct TransactionStore {
     // Using transaction commited hash because from end user's point view, a transaction should only have
     // one valid hash.
     hash_index: HashMap<HashValue, (AccountAddress, u64)>,
+    // Senders with transactions that went over their fairness quota, their parked transactions
+    // are evicted first when mempool is full.
+    over_quota_senders: HashSet<AccountAddress>,
     // estimated size in bytes
     size_bytes: usize,
 
@@ -95,6 +104,7 @@ impl TransactionStore {
                 .unwrap(),
             parking_lot_index: ParkingLotIndex::new(),
             hash_index: HashMap::new(),
+            over_quota_senders: HashSet::new(),
             // estimated size in bytes
             size_bytes: 0,
 
@@ -152,6 +162,62 @@ impl TransactionStore {
         }
     }
 
+    /// Returns the inspection status of the transaction with `hash`. Broadcast peers are left for
+    /// the caller to fill in.
+    pub(crate) fn get_transaction_status(
+        &self,
+        hash: HashValue,
+    ) -> Option<TransactionStatusWithTimeline> {
+        let (address, sequence_number) = self.hash_index.get(&hash)?;
+        self.get_mempool_txn(address, *sequence_number)
+            .map(|txn| self.transaction_status(txn))
+    }
+
+    /// Returns the last seen sequence number of the account and the inspection status of its
+    /// transactions, in sequence number order. Broadcast peers are left for the caller to fill in.
+    pub(crate) fn get_account_status(
+        &self,
+        address: &AccountAddress,
+    ) -> (Option<u64>, Vec<TransactionStatusWithTimeline>) {
+        let txns = self
+            .transactions
+            .get(address)
+            .map(|txns| {
+                txns.values()
+                    .map(|txn| self.transaction_status(txn))
+                    .collect()
+            })
+            .unwrap_or_default();
+        (self.sequence_numbers.get(address).copied(), txns)
+    }
+
+    fn transaction_status(&self, txn: &MempoolTransaction) -> TransactionStatusWithTimeline {
+        let state = if self.parking_lot_index.contains(
+            &txn.get_sender(),
+            txn.sequence_info.transaction_sequence_number,
+            txn.get_committed_hash(),
+        ) {
+            MempoolTransactionState::Parked
+        } else {
+            MempoolTransactionState::Ready
+        };
+        let timeline_position = match txn.timeline_state {
+            TimelineState::Ready(timeline_id) => Some((
+                self.timeline_index.get_bucket_index(txn.ranking_score),
+                timeline_id,
+            )),
+            TimelineState::NotReady | TimelineState::NonQualified => None,
+        };
+        let status = MempoolTransactionStatus {
+            txn: txn.txn.clone(),
+            state,
+            ranking_score: txn.ranking_score,
+            insertion_time: txn.insertion_info.insertion_time,
+            broadcast_peers: vec![],
+        };
+        (status, timeline_position)
+    }
+
     pub(crate) fn get_insertion_info_and_bucket(
         &self,
         address: &AccountAddress,
@@ -307,8 +373,13 @@ impl TransactionStore {
         curr_sequence_number: u64,
     ) -> bool {
         if self.is_full() && self.check_txn_ready(txn, curr_sequence_number) {
-            // try to free some space in Mempool from ParkingLot by evicting a non-ready txn
-            if let Some(txn_pointer) = self.parking_lot_index.get_poppable() {
+            // try to free some space in Mempool from ParkingLot by evicting a non-ready txn,
+            // preferably of a sender over quota
+            let txn_pointer = self
+                .parking_lot_index
+                .get_poppable_over_quota()
+                .or_else(|| self.parking_lot_index.get_poppable());
+            if let Some(txn_pointer) = txn_pointer {
                 if let Some(txn) = self
                     .transactions
                     .get_mut(&txn_pointer.sender)
@@ -327,6 +398,22 @@ impl TransactionStore {
         self.is_full()
     }
 
+    /// Marks `senders` as over their fairness quota, until they have no transactions left.
+    pub(crate) fn mark_over_quota(&mut self, senders: &[AccountAddress]) {
+        for sender in senders {
+            if self.transactions.contains_key(sender) {
+                self.over_quota_senders.insert(*sender);
+                self.parking_lot_index.mark_over_quota(sender);
+            }
+        }
+    }
+
+    /// Marks the senders of the transactions parked from `peer` as over their fairness quota.
+    pub(crate) fn mark_over_quota_peer(&mut self, peer: &PeerNetworkId) {
+        let senders = self.parking_lot_index.accounts_from(peer);
+        self.mark_over_quota(&senders);
+    }
+
     fn is_full(&self) -> bool {
         self.system_ttl_index.size() >= self.capacity || self.size_bytes >= self.capacity_bytes
     }
@@ -448,6 +535,9 @@ impl TransactionStore {
                     },
                 }
             }
+            if parking_lot_txns > 0 && self.over_quota_senders.contains(address) {
+                self.parking_lot_index.mark_over_quota(address);
+            }
 
             trace!(
                 LogSchema::new(LogEntry::ProcessReadyTxns).account(*address),
@@ -547,6 +637,7 @@ impl TransactionStore {
             if txns.is_empty() {
                 self.transactions.remove(address);
                 self.sequence_numbers.remove(address);
+                self.over_quota_senders.remove(address);
             }
         }
 
@@ -706,6 +797,9 @@ impl TransactionStore {
                         t.timeline_state = TimelineState::NotReady;
                     }
                 }
+                if self.over_quota_senders.contains(&key.address) {
+                    self.parking_lot_index.mark_over_quota(&key.address);
+                }
                 if let Some(txn) = txns.remove(&key.sequence_number) {
                     let is_active = self.priority_index.contains(&txn);
                     let status = if is_active {
@@ -742,6 +836,29 @@ impl TransactionStore {
         self.priority_index.iter()
     }
 
+    /// Returns the transactions to persist across restarts, stopping at the first transaction
+    /// that exceeds `max_txns` or `max_bytes`. Transactions of an account are in sequence number
+    /// order.
+    pub(crate) fn get_transactions_to_persist(
+        &self,
+        max_txns: usize,
+        max_bytes: u64,
+    ) -> Vec<SignedTransaction> {
+        let mut txns = vec![];
+        let mut total_bytes = 0;
+        for account_txns in self.transactions.values() {
+            for txn in account_txns.values() {
+                let txn_bytes = txn.txn.txn_bytes_len() as u64;
+                if txns.len() >= max_txns || total_bytes + txn_bytes > max_bytes {
+                    return txns;
+                }
+                total_bytes += txn_bytes;
+                txns.push(txn.txn.clone());
+            }
+        }
+        txns
+    }
+
     pub(crate) fn gen_snapshot(&self) -> TxnsLog {
         let mut txns_log = TxnsLog::new();
         for (account, txns) in self.transactions.iter() {
@@ -66,6 +66,7 @@ pub const SUCCESS_LABEL: &str = "success";
 // Bounded executor task labels
 pub const CLIENT_EVENT_LABEL: &str = "client_event";
 pub const CLIENT_EVENT_GET_TXN_LABEL: &str = "client_event_get_txn";
+pub const CLIENT_EVENT_INSPECT_LABEL: &str = "client_event_inspect";
 pub const RECONFIG_EVENT_LABEL: &str = "reconfig";
 pub const PEER_BROADCAST_EVENT_LABEL: &str = "peer_broadcast";
 
@@ -443,6 +444,39 @@ pub fn shared_mempool_pending_broadcasts(peer: &PeerNetworkId) -> IntGauge {
     ])
 }
 
+// Fairness quota labels
+pub const PEER_QUOTA_LABEL: &str = "peer";
+pub const SENDER_QUOTA_LABEL: &str = "sender";
+// Network label for transactions submitted by clients
+pub const CLIENT_NETWORK_LABEL: &str = "client";
+
+/// Counter tracking the number of transactions rejected for going over a fairness quota, by the
+/// peer they were received from
+static SHARED_MEMPOOL_QUOTA_THROTTLED_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_shared_mempool_quota_throttled_count",
+        "Number of transactions rejected for going over a fairness quota",
+        &["network", "peer", "quota"]
+    )
+    .unwrap()
+});
+
+pub fn shared_mempool_quota_throttled_inc(peer: Option<&PeerNetworkId>, quota: &str) {
+    match peer {
+        Some(peer) => SHARED_MEMPOOL_QUOTA_THROTTLED_COUNT.with_label_values(&[
+            peer.network_id().as_str(),
+            peer.peer_id().short_str().as_str(),
+            quota,
+        ]),
+        None => SHARED_MEMPOOL_QUOTA_THROTTLED_COUNT.with_label_values(&[
+            CLIENT_NETWORK_LABEL,
+            CLIENT_NETWORK_LABEL,
+            quota,
+        ]),
+    }
+    .inc();
+}
+
 /// Counter tracking the number of peers that changed priority in shared mempool
 pub static SHARED_MEMPOOL_PRIORITY_CHANGE_COUNT: Lazy<IntGauge> = Lazy::new(|| {
     register_int_gauge!(
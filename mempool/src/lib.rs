@@ -62,8 +62,9 @@ pub use shared_mempool::{
     bootstrap, network,
     network::MempoolSyncMsg,
     types::{
-        MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver, QuorumStoreRequest,
-        QuorumStoreResponse, SubmissionStatus,
+        MempoolAccountStatus, MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver,
+        MempoolTransactionState, MempoolTransactionStatus, QuorumStoreRequest, QuorumStoreResponse,
+        SubmissionStatus,
     },
 };
 #[cfg(any(test, feature = "fuzzing"))]
@@ -173,6 +173,7 @@ pub enum LogEntry {
     DBError,
     UnexpectedNetworkMsg,
     MempoolSnapshot,
+    Persistence,
 }
 
 #[derive(Clone, Copy, Serialize)]
@@ -195,5 +196,9 @@ pub enum LogEvent {
     SystemTTLExpiration,
     ClientExpiration,
 
+    // persistence events
+    Replay,
+    FlushFail,
+
     Success,
 }
@@ -10,7 +10,8 @@ use crate::{
     logging::{LogEntry, LogEvent, LogSchema},
     network::MempoolSyncMsg,
     shared_mempool::{
-        tasks,
+        fairness::FairnessQuotas,
+        persistence, tasks,
         tasks::process_committed_transactions,
         types::{notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolNotification},
     },
@@ -94,6 +95,28 @@ pub(crate) async fn coordinator<NetworkClient, TransactionValidator, ConfigProvi
     )
     .await;
 
+    // Replay the journal on the blocking thread pool (validation is CPU-bound and
+    // synchronous), and only start flushing once the replay is done, so that the
+    // first flush doesn't overwrite the journal.
+    if smp.config.persistence.enabled {
+        let smp = smp.clone();
+        executor.spawn(async move {
+            let mempool = smp.mempool.clone();
+            let persistence_config = smp.config.persistence.clone();
+            let result =
+                tokio::task::spawn_blocking(move || persistence::replay_transactions(&smp))
+                    .await
+                    .map_err(anyhow::Error::from);
+            if let Err(e) = result {
+                error!(
+                    LogSchema::event_log(LogEntry::Persistence, LogEvent::Replay).error(&e),
+                    "Failed to replay the mempool journal"
+                );
+            }
+            persistence::persistence_job(mempool, persistence_config).await;
+        });
+    }
+
     loop {
         let _timer = counters::MAIN_LOOP.start_timer();
         ::futures::select! {
@@ -194,6 +217,48 @@ async fn handle_client_request<NetworkClient, TransactionValidator>(
                 ))
                 .await;
         },
+        MempoolClientRequest::GetTransactionStatus(hash, callback) => {
+            // This timer measures how long it took for the bounded executor to *schedule* the
+            // task.
+            let _timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_INSPECT_LABEL,
+                counters::SPAWN_LABEL,
+            );
+            // This timer measures how long it took for the task to go from scheduled to started.
+            let task_start_timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_INSPECT_LABEL,
+                counters::START_LABEL,
+            );
+            bounded_executor
+                .spawn(tasks::process_client_get_transaction_status(
+                    smp.clone(),
+                    hash,
+                    callback,
+                    task_start_timer,
+                ))
+                .await;
+        },
+        MempoolClientRequest::GetAccountStatus(address, callback) => {
+            // This timer measures how long it took for the bounded executor to *schedule* the
+            // task.
+            let _timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_INSPECT_LABEL,
+                counters::SPAWN_LABEL,
+            );
+            // This timer measures how long it took for the task to go from scheduled to started.
+            let task_start_timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_INSPECT_LABEL,
+                counters::START_LABEL,
+            );
+            bounded_executor
+                .spawn(tasks::process_client_get_account_status(
+                    smp.clone(),
+                    address,
+                    callback,
+                    task_start_timer,
+                ))
+                .await;
+        },
     }
 }
 
@@ -360,8 +425,13 @@ async fn handle_update_peers<NetworkClient, TransactionValidator>(
     }
 }
 
-/// Garbage collect all expired transactions by SystemTTL.
-pub(crate) async fn gc_coordinator(mempool: Arc<Mutex<CoreMempool>>, gc_interval_ms: u64) {
+/// Garbage collect all expired transactions by SystemTTL, and the fairness quotas of idle peers
+/// and senders.
+pub(crate) async fn gc_coordinator(
+    mempool: Arc<Mutex<CoreMempool>>,
+    fairness_quotas: Arc<FairnessQuotas>,
+    gc_interval_ms: u64,
+) {
     debug!(LogSchema::event_log(LogEntry::GCRuntime, LogEvent::Start));
     let mut interval = IntervalStream::new(interval(Duration::from_millis(gc_interval_ms)));
     while let Some(_interval) = interval.next().await {
@@ -370,6 +440,7 @@ pub(crate) async fn gc_coordinator(mempool: Arc<Mutex<CoreMempool>>, gc_interval
             debug!(LogSchema::event_log(LogEntry::GCRuntime, LogEvent::Live))
         );
         mempool.lock().gc();
+        fairness_quotas.garbage_collect();
     }
 
     error!(LogSchema::event_log(
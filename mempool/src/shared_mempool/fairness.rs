@@ -0,0 +1,106 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Token bucket quotas on the transactions admitted into mempool, so that a single upstream peer
+//! or sender can't take up all of mempool.
+
+use crate::counters;
+use aptos_config::{config::MempoolFairnessConfig, network_id::PeerNetworkId};
+use aptos_rate_limiter::rate_limit::TokenBucketRateLimiter;
+use aptos_types::account_address::AccountAddress;
+
+const PEER_RATE_LIMITER_LABEL: &str = "mempool_peer_quota";
+const SENDER_RATE_LIMITER_LABEL: &str = "mempool_sender_quota";
+
+/// Reason a transaction was throttled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum QuotaExceeded {
+    Peer,
+    Sender,
+}
+
+pub(crate) struct FairnessQuotas {
+    enabled: bool,
+    peer_quotas: TokenBucketRateLimiter<PeerNetworkId>,
+    sender_quotas: TokenBucketRateLimiter<AccountAddress>,
+}
+
+impl FairnessQuotas {
+    pub(crate) fn new(config: &MempoolFairnessConfig) -> Self {
+        if !config.enabled {
+            return Self {
+                enabled: false,
+                peer_quotas: TokenBucketRateLimiter::open(PEER_RATE_LIMITER_LABEL),
+                sender_quotas: TokenBucketRateLimiter::open(SENDER_RATE_LIMITER_LABEL),
+            };
+        }
+        // New buckets start full, so that idle buckets can be garbage collected
+        Self {
+            enabled: true,
+            peer_quotas: TokenBucketRateLimiter::new(
+                PEER_RATE_LIMITER_LABEL,
+                String::new(),
+                100,
+                config.peer_burst_txns,
+                config.peer_txns_per_sec,
+                None,
+            ),
+            sender_quotas: TokenBucketRateLimiter::new(
+                SENDER_RATE_LIMITER_LABEL,
+                String::new(),
+                100,
+                config.sender_burst_txns,
+                config.sender_txns_per_sec,
+                None,
+            ),
+        }
+    }
+
+    /// Takes a token for a transaction of `sender`, received from `peer` or submitted by a client.
+    /// Peers on the validator network are only subject to the sender quota. A transaction rejected
+    /// by the sender quota doesn't use up a token of the peer.
+    pub(crate) fn try_admit(
+        &self,
+        peer: Option<&PeerNetworkId>,
+        sender: AccountAddress,
+    ) -> Result<(), QuotaExceeded> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let peer_bucket = match peer.filter(|peer| !peer.network_id().is_validator_network()) {
+            Some(peer) => {
+                let bucket = self.peer_quotas.bucket(*peer);
+                if bucket.lock().acquire_all_tokens(1).is_err() {
+                    counters::shared_mempool_quota_throttled_inc(
+                        Some(peer),
+                        counters::PEER_QUOTA_LABEL,
+                    );
+                    return Err(QuotaExceeded::Peer);
+                }
+                Some(bucket)
+            },
+            None => None,
+        };
+        if self
+            .sender_quotas
+            .bucket(sender)
+            .lock()
+            .acquire_all_tokens(1)
+            .is_err()
+        {
+            if let Some(bucket) = peer_bucket {
+                bucket.lock().return_tokens(1);
+            }
+            counters::shared_mempool_quota_throttled_inc(peer, counters::SENDER_QUOTA_LABEL);
+            return Err(QuotaExceeded::Sender);
+        }
+        Ok(())
+    }
+
+    /// Drops the quotas of peers and senders that have been idle long enough to be back to a full
+    /// quota.
+    pub(crate) fn garbage_collect(&self) {
+        self.peer_quotas.garbage_collect_full_buckets();
+        self.sender_quotas.garbage_collect_full_buckets();
+    }
+}
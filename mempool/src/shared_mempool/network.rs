@@ -521,4 +521,74 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
     pub fn sync_states_exists(&self, peer: &PeerNetworkId) -> bool {
         self.sync_states.read().get(peer).is_some()
     }
+
+    /// Returns the peers that the transaction at `timeline_id` in the timeline of `bucket_index`
+    /// has been sent to, i.e. the peers whose broadcasts of the bucket have reached `timeline_id`.
+    /// A peer counts as soon as a batch holding the transaction is sent, whether or not the peer
+    /// has acked it.
+    pub fn get_broadcast_peers(&self, bucket_index: usize, timeline_id: u64) -> Vec<PeerNetworkId> {
+        self.sync_states
+            .read()
+            .iter()
+            .filter(|(_, state)| {
+                state
+                    .timeline_id
+                    .id_per_bucket
+                    .get(bucket_index)
+                    .map_or(false, |broadcast_id| *broadcast_id >= timeline_id)
+            })
+            .map(|(peer, _)| *peer)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_config::network_id::NetworkId;
+    use aptos_network::application::{interface::NetworkClient, storage::PeersAndMetadata};
+    use aptos_types::PeerId;
+
+    #[test]
+    fn test_get_broadcast_peers() {
+        let network_client: NetworkClient<MempoolSyncMsg> = NetworkClient::new(
+            vec![],
+            vec![],
+            HashMap::new(),
+            PeersAndMetadata::new(&[NetworkId::Public]),
+        );
+        let network_interface = MempoolNetworkInterface::new(
+            network_client,
+            RoleType::FullNode,
+            MempoolConfig::default(),
+        );
+        let num_buckets = MempoolConfig::default().broadcast_buckets.len();
+        let peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let other_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+
+        // A batch up to timeline id 5 of the first bucket is sent to `peer` and not acked yet,
+        // nothing is sent to `other_peer`.
+        {
+            let mut sync_states = network_interface.sync_states.write();
+            let mut state = PeerSyncState::new(num_buckets);
+            let mut batch = vec![(0, 0); num_buckets];
+            batch[0] = (0, 5);
+            let batch_id = MultiBatchId(batch);
+            state.timeline_id.update(&batch_id);
+            state
+                .broadcast_info
+                .sent_batches
+                .insert(batch_id, SystemTime::now());
+            sync_states.insert(peer, state);
+            sync_states.insert(other_peer, PeerSyncState::new(num_buckets));
+        }
+
+        assert_eq!(network_interface.get_broadcast_peers(0, 1), vec![peer]);
+        assert_eq!(network_interface.get_broadcast_peers(0, 5), vec![peer]);
+        assert!(network_interface.get_broadcast_peers(0, 6).is_empty());
+        assert!(network_interface.get_broadcast_peers(1, 1).is_empty());
+        assert!(network_interface
+            .get_broadcast_peers(num_buckets, 1)
+            .is_empty());
+    }
 }
@@ -0,0 +1,142 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Journal of pending transactions, so that they survive node restarts. The journal is
+//! periodically rewritten with the transactions in core mempool, and replayed through the
+//! regular validation path on startup.
+
+use crate::{
+    core_mempool::{CoreMempool, TimelineState},
+    logging::{LogEntry, LogEvent, LogSchema},
+    network::MempoolSyncMsg,
+    shared_mempool::{tasks, types::SharedMempool},
+};
+use anyhow::Result;
+use aptos_config::config::MempoolPersistenceConfig;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_network::application::interface::NetworkClientInterface;
+use aptos_types::{mempool_status::MempoolStatusCode, transaction::SignedTransaction};
+use aptos_vm_validator::vm_validator::TransactionValidation;
+use futures::StreamExt;
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::time::interval;
+use tokio_stream::wrappers::IntervalStream;
+
+/// Reads the journal at `path`. A missing journal has no transactions.
+pub(crate) fn load_transactions(path: &Path) -> Result<Vec<SignedTransaction>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    Ok(bcs::from_bytes(&fs::read(path)?)?)
+}
+
+/// Replaces the journal at `path` with `txns`. The journal is written and synced to a temporary
+/// file first, so a crash mid-write leaves the previous journal intact.
+pub(crate) fn write_transactions(path: &Path, txns: &[SignedTransaction]) -> Result<()> {
+    let parent = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+    if let Some(parent) = parent {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(&bcs::to_bytes(txns)?)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    // Persist the rename as well.
+    if let Some(parent) = parent {
+        File::open(parent)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Re-submits the journaled transactions into core mempool. They are validated like transactions
+/// received from peers, so transactions that were committed or became invalid while the node was
+/// down are dropped. Expired transactions are dropped before validation.
+pub(crate) fn replay_transactions<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    let path = smp.config.persistence.path();
+    let txns = match load_transactions(&path) {
+        Ok(txns) => txns,
+        Err(e) => {
+            error!(
+                LogSchema::event_log(LogEntry::Persistence, LogEvent::Replay).error(&e),
+                "Failed to load the mempool journal at {}",
+                path.display()
+            );
+            return;
+        },
+    };
+    let num_journaled = txns.len();
+
+    let now_secs = aptos_infallible::duration_since_epoch().as_secs();
+    let txns: Vec<_> = txns
+        .into_iter()
+        .filter(|txn| txn.expiration_timestamp_secs() > now_secs)
+        .collect();
+
+    // Same as for client submissions, as the journal holds transactions from both clients and
+    // peers.
+    let ineligible_for_broadcast =
+        smp.network_interface.is_validator() && !smp.broadcast_within_validator_network();
+    let timeline_state = if ineligible_for_broadcast {
+        TimelineState::NonQualified
+    } else {
+        TimelineState::NotReady
+    };
+    let num_accepted = if txns.is_empty() {
+        0
+    } else {
+        tasks::process_incoming_transactions(smp, txns, timeline_state, false, None)
+            .iter()
+            .filter(|(_, (status, _))| status.code == MempoolStatusCode::Accepted)
+            .count()
+    };
+
+    info!(
+        LogSchema::event_log(LogEntry::Persistence, LogEvent::Replay).num_txns(num_accepted),
+        "Replayed {} of {} journaled transactions", num_accepted, num_journaled
+    );
+}
+
+/// Periodically rewrites the journal with the transactions in core mempool.
+pub(crate) async fn persistence_job(
+    mempool: Arc<Mutex<CoreMempool>>,
+    config: MempoolPersistenceConfig,
+) {
+    debug!(LogSchema::event_log(LogEntry::Persistence, LogEvent::Start));
+    let path = config.path();
+    let mut interval =
+        IntervalStream::new(interval(Duration::from_millis(config.flush_interval_ms)));
+    while let Some(_interval) = interval.next().await {
+        let txns = mempool
+            .lock()
+            .get_transactions_to_persist(config.max_transactions, config.max_bytes);
+        let path = path.clone();
+        let result = tokio::task::spawn_blocking(move || write_transactions(&path, &txns))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result);
+        if let Err(e) = result {
+            sample!(
+                SampleRate::Duration(Duration::from_secs(60)),
+                error!(LogSchema::event_log(LogEntry::Persistence, LogEvent::FlushFail).error(&e))
+            );
+        }
+    }
+
+    error!(LogSchema::event_log(
+        LogEntry::Persistence,
+        LogEvent::Terminated
+    ));
+}
@@ -31,7 +31,9 @@ use tokio::runtime::{Handle, Runtime};
 /// Creates a separate Tokio Runtime that runs the following routines:
 ///   - outbound_sync_task (task that periodically broadcasts transactions to peers).
 ///   - inbound_network_task (task that handles inbound mempool messages and network events).
-///   - gc_task (task that performs GC of all expired transactions by SystemTTL).
+///   - gc_task (task that performs GC of all expired transactions by SystemTTL, and of idle
+///     fairness quotas).
+///   - persistence_task (task that periodically journals pending transactions, if enabled).
 pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
     executor: &Handle,
     config: &NodeConfig,
@@ -61,6 +63,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
             config.base.role,
         );
 
+    let fairness_quotas = smp.fairness_quotas.clone();
     executor.spawn(coordinator(
         smp,
         executor.clone(),
@@ -75,6 +78,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
 
     executor.spawn(gc_coordinator(
         mempool.clone(),
+        fairness_quotas,
         config.mempool.system_transaction_gc_interval_ms,
     ));
 
@@ -4,13 +4,16 @@
 
 //! Tasks that are executed by coordinators (short-lived compared to coordinators)
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{CoreMempool, TimelineState, TransactionStatusWithTimeline},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
-    network::{BroadcastError, MempoolSyncMsg},
-    shared_mempool::types::{
-        notify_subscribers, MultiBatchId, ScheduledBroadcast, SharedMempool,
-        SharedMempoolNotification, SubmissionStatusBundle,
+    network::{BroadcastError, MempoolNetworkInterface, MempoolSyncMsg},
+    shared_mempool::{
+        fairness::QuotaExceeded,
+        types::{
+            notify_subscribers, MempoolAccountStatus, MempoolTransactionStatus, MultiBatchId,
+            ScheduledBroadcast, SharedMempool, SharedMempoolNotification, SubmissionStatusBundle,
+        },
     },
     thread_pool::IO_POOL,
     QuorumStoreRequest, QuorumStoreResponse, SubmissionStatus,
@@ -27,6 +30,7 @@ use aptos_metrics_core::HistogramTimer;
 use aptos_network::application::interface::NetworkClientInterface;
 use aptos_storage_interface::state_view::LatestDbStateCheckpointView;
 use aptos_types::{
+    account_address::AccountAddress,
     mempool_status::{MempoolStatus, MempoolStatusCode},
     on_chain_config::{OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig},
     transaction::SignedTransaction,
@@ -128,7 +132,17 @@ pub(crate) async fn process_client_transaction_submission<NetworkClient, Transac
     } else {
         TimelineState::NotReady
     };
-    let statuses = process_incoming_transactions(&smp, vec![transaction], timeline_state, true);
+    let mut statuses = vec![];
+    let transactions = apply_fairness_quotas(&smp, None, vec![transaction], &mut statuses);
+    if !transactions.is_empty() {
+        statuses.extend(process_incoming_transactions(
+            &smp,
+            transactions,
+            timeline_state,
+            true,
+            None,
+        ));
+    }
     log_txn_process_results(&statuses, None);
 
     if let Some(status) = statuses.first() {
@@ -165,6 +179,76 @@ pub(crate) async fn process_client_get_transaction<NetworkClient, TransactionVal
     }
 }
 
+/// Processes request by client for the inspection status of a transaction.
+pub(crate) async fn process_client_get_transaction_status<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    hash: HashValue,
+    callback: oneshot::Sender<Option<MempoolTransactionStatus>>,
+    timer: HistogramTimer,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    timer.stop_and_record();
+    let status = smp
+        .mempool
+        .lock()
+        .get_transaction_status(hash)
+        .map(|status| with_broadcast_peers(&smp.network_interface, status));
+
+    if callback.send(status).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::GetTransaction,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
+/// Processes request by client for the inspection status of the transactions of an account.
+pub(crate) async fn process_client_get_account_status<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    address: AccountAddress,
+    callback: oneshot::Sender<MempoolAccountStatus>,
+    timer: HistogramTimer,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    timer.stop_and_record();
+    let (sequence_number, statuses) = smp.mempool.lock().get_account_status(&address);
+    let status = MempoolAccountStatus {
+        sequence_number,
+        transactions: statuses
+            .into_iter()
+            .map(|status| with_broadcast_peers(&smp.network_interface, status))
+            .collect(),
+    };
+
+    if callback.send(status).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::GetTransaction,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
+/// Fills in the peers a transaction has been broadcast to, from the timeline position it was
+/// broadcast from.
+fn with_broadcast_peers<NetworkClient>(
+    network_interface: &MempoolNetworkInterface<NetworkClient>,
+    (mut status, timeline_position): TransactionStatusWithTimeline,
+) -> MempoolTransactionStatus
+where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+{
+    if let Some((bucket_index, timeline_id)) = timeline_position {
+        status.broadcast_peers = network_interface.get_broadcast_peers(bucket_index, timeline_id);
+    }
+    status
+}
+
 /// Processes transactions from other nodes.
 pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionValidator>(
     smp: SharedMempool<NetworkClient, TransactionValidator>,
@@ -179,7 +263,17 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
 {
     timer.stop_and_record();
     let _timer = counters::process_txn_submit_latency_timer(peer.network_id());
-    let results = process_incoming_transactions(&smp, transactions, timeline_state, false);
+    let mut results = vec![];
+    let transactions = apply_fairness_quotas(&smp, Some(&peer), transactions, &mut results);
+    if !transactions.is_empty() {
+        results.extend(process_incoming_transactions(
+            &smp,
+            transactions,
+            timeline_state,
+            false,
+            Some(peer),
+        ));
+    }
     log_txn_process_results(&results, Some(peer));
 
     let ack_response = gen_ack_response(request_id, results, &peer);
@@ -251,6 +345,58 @@ pub(crate) fn update_ack_counter(
     }
 }
 
+/// Rejects the transactions over the quota of the upstream `peer` or of their sender, and returns
+/// the admitted ones. Exceeding the peer quota is reported as `MempoolIsFull`, so the peer backs
+/// off. Senders over quota, and the senders of the transactions parked from a peer over quota, have
+/// their parked transactions evicted first when mempool is full.
+fn apply_fairness_quotas<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+    peer: Option<&PeerNetworkId>,
+    transactions: Vec<SignedTransaction>,
+    statuses: &mut Vec<SubmissionStatusBundle>,
+) -> Vec<SignedTransaction> {
+    let mut over_quota_senders = vec![];
+    let mut peer_over_quota = false;
+    let admitted = transactions
+        .into_iter()
+        .filter_map(|t| match smp.fairness_quotas.try_admit(peer, t.sender()) {
+            Ok(()) => Some(t),
+            Err(QuotaExceeded::Peer) => {
+                peer_over_quota = true;
+                statuses.push((
+                    t,
+                    (
+                        MempoolStatus::new(MempoolStatusCode::MempoolIsFull)
+                            .with_message("Peer transaction quota exceeded".to_string()),
+                        None,
+                    ),
+                ));
+                None
+            },
+            Err(QuotaExceeded::Sender) => {
+                over_quota_senders.push(t.sender());
+                statuses.push((
+                    t,
+                    (
+                        MempoolStatus::new(MempoolStatusCode::TooManyTransactions)
+                            .with_message("Sender transaction quota exceeded".to_string()),
+                        None,
+                    ),
+                ));
+                None
+            },
+        })
+        .collect();
+    if !over_quota_senders.is_empty() || peer_over_quota {
+        let mut mempool = smp.mempool.lock();
+        mempool.mark_over_quota(&over_quota_senders);
+        if let Some(peer) = peer.filter(|_| peer_over_quota) {
+            mempool.mark_over_quota_peer(peer);
+        }
+    }
+    admitted
+}
+
 /// Submits a list of SignedTransaction to the local mempool
 /// and returns a vector containing [SubmissionStatusBundle].
 pub(crate) fn process_incoming_transactions<NetworkClient, TransactionValidator>(
@@ -258,6 +404,7 @@ pub(crate) fn process_incoming_transactions<NetworkClient, TransactionValidator>
     transactions: Vec<SignedTransaction>,
     timeline_state: TimelineState,
     client_submitted: bool,
+    upstream_peer: Option<PeerNetworkId>,
 ) -> Vec<SubmissionStatusBundle>
 where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
@@ -265,6 +412,38 @@ where
 {
     let mut statuses = vec![];
 
+    // Reject the transactions that aren't allowed by the transaction filter. There is no
+    // block yet, so the current time is used as the block timestamp.
+    let transactions = if smp.config.transaction_filter.is_empty() {
+        transactions
+    } else {
+        let timestamp_usecs = aptos_infallible::duration_since_epoch().as_micros() as u64;
+        transactions
+            .into_iter()
+            .filter_map(|t| {
+                if smp
+                    .config
+                    .transaction_filter
+                    .allows(HashValue::zero(), timestamp_usecs, &t)
+                {
+                    Some(t)
+                } else {
+                    statuses.push((
+                        t,
+                        (
+                            MempoolStatus::new(MempoolStatusCode::RejectedByFilter),
+                            None,
+                        ),
+                    ));
+                    None
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    if transactions.is_empty() {
+        return statuses;
+    }
+
     let start_storage_read = Instant::now();
     let state_view = smp
         .db
@@ -326,6 +505,7 @@ where
         timeline_state,
         &mut statuses,
         client_submitted,
+        upstream_peer,
     );
     notify_subscribers(SharedMempoolNotification::NewTransactions, &smp.subscribers);
     statuses
@@ -340,6 +520,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
     timeline_state: TimelineState,
     statuses: &mut Vec<(SignedTransaction, (MempoolStatus, Option<StatusCode>))>,
     client_submitted: bool,
+    upstream_peer: Option<PeerNetworkId>,
 ) where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
@@ -367,6 +548,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
                             sequence_info,
                             timeline_state,
                             client_submitted,
+                            upstream_peer,
                         );
                         statuses.push((transaction, (mempool_status, None)));
                     },
@@ -407,6 +589,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
     timeline_state: TimelineState,
     statuses: &mut Vec<(SignedTransaction, (MempoolStatus, Option<StatusCode>))>,
     client_submitted: bool,
+    upstream_peer: Option<PeerNetworkId>,
 ) where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
@@ -419,6 +602,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
             sequence_info,
             timeline_state,
             client_submitted,
+            upstream_peer,
         );
         statuses.push((transaction, (mempool_status, None)));
     }
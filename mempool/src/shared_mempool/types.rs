@@ -6,6 +6,7 @@
 use crate::{
     core_mempool::CoreMempool,
     network::{MempoolNetworkInterface, MempoolSyncMsg},
+    shared_mempool::fairness::FairnessQuotas,
 };
 use anyhow::Result;
 use aptos_config::{
@@ -20,7 +21,8 @@ use aptos_infallible::{Mutex, RwLock};
 use aptos_network::application::interface::NetworkClientInterface;
 use aptos_storage_interface::DbReader;
 use aptos_types::{
-    mempool_status::MempoolStatus, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    account_address::AccountAddress, mempool_status::MempoolStatus, transaction::SignedTransaction,
+    vm_status::DiscardedVMStatus,
 };
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
@@ -50,6 +52,7 @@ pub(crate) struct SharedMempool<NetworkClient, TransactionValidator> {
     pub validator: Arc<RwLock<TransactionValidator>>,
     pub subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     pub broadcast_within_validator_network: Arc<RwLock<bool>>,
+    pub fairness_quotas: Arc<FairnessQuotas>,
 }
 
 impl<
@@ -67,6 +70,7 @@ impl<
         role: RoleType,
     ) -> Self {
         let network_interface = MempoolNetworkInterface::new(network_client, role, config.clone());
+        let fairness_quotas = Arc::new(FairnessQuotas::new(&config.fairness));
         SharedMempool {
             mempool,
             config,
@@ -75,6 +79,7 @@ impl<
             validator,
             subscribers,
             broadcast_within_validator_network: Arc::new(RwLock::new(true)),
+            fairness_quotas,
         }
     }
 
@@ -226,6 +231,37 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    GetTransactionStatus(HashValue, oneshot::Sender<Option<MempoolTransactionStatus>>),
+    GetAccountStatus(AccountAddress, oneshot::Sender<MempoolAccountStatus>),
+}
+
+/// Whether a transaction in mempool can be pulled into a block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MempoolTransactionState {
+    /// All transactions of the account with lower sequence numbers are in mempool or committed.
+    Ready,
+    /// Waiting for a transaction of the account with a lower sequence number.
+    Parked,
+}
+
+/// The state of a transaction in mempool, for clients to inspect.
+#[derive(Clone, Debug)]
+pub struct MempoolTransactionStatus {
+    pub txn: SignedTransaction,
+    pub state: MempoolTransactionState,
+    pub ranking_score: u64,
+    pub insertion_time: SystemTime,
+    /// Peers the transaction has been broadcast to.
+    pub broadcast_peers: Vec<PeerNetworkId>,
+}
+
+/// The transactions of an account in mempool, for clients to inspect.
+#[derive(Clone, Debug)]
+pub struct MempoolAccountStatus {
+    /// Sequence number of the account last seen by mempool, if it holds any of its transactions.
+    pub sequence_number: Option<u64>,
+    /// Transactions ordered by sequence number.
+    pub transactions: Vec<MempoolTransactionStatus>,
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
@@ -123,6 +123,7 @@ pub(crate) fn add_txns_to_mempool(
             transaction.account_seqno,
             TimelineState::NotReady,
             false,
+            None,
         );
         transactions.push(txn);
     }
@@ -151,6 +152,7 @@ pub(crate) fn add_signed_txn(pool: &mut CoreMempool, transaction: SignedTransact
             0,
             TimelineState::NotReady,
             false,
+            None,
         )
         .code
     {
@@ -4,16 +4,22 @@
 
 use crate::{
     core_mempool::{CoreMempool, MempoolTransaction, SubmittedBy, TimelineState},
+    shared_mempool::persistence::{load_transactions, write_transactions},
     tests::common::{
         add_signed_txn, add_txn, add_txns_to_mempool, setup_mempool,
         setup_mempool_with_broadcast_buckets, txn_bytes_len, TestTransaction,
     },
+    MempoolTransactionState,
+};
+use aptos_config::{
+    config::NodeConfig,
+    network_id::{NetworkId, PeerNetworkId},
 };
-use aptos_config::config::NodeConfig;
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use aptos_crypto::HashValue;
 use aptos_types::{
-    mempool_status::MempoolStatusCode, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    mempool_status::MempoolStatusCode, transaction::SignedTransaction,
+    vm_status::DiscardedVMStatus, PeerId,
 };
 use itertools::Itertools;
 use maplit::btreemap;
@@ -75,6 +81,7 @@ fn test_transaction_metrics() {
         0,
         TimelineState::NotReady,
         false,
+        None,
     );
     let txn = TestTransaction::new(1, 0, 1).make_signed_transaction();
     mempool.add_txn(
@@ -83,6 +90,7 @@ fn test_transaction_metrics() {
         0,
         TimelineState::NonQualified,
         false,
+        None,
     );
     let txn = TestTransaction::new(2, 0, 1).make_signed_transaction();
     mempool.add_txn(
@@ -91,6 +99,7 @@ fn test_transaction_metrics() {
         0,
         TimelineState::NotReady,
         true,
+        None,
     );
 
     // Check timestamp returned as end-to-end for broadcast-able transaction
@@ -566,6 +575,7 @@ fn test_capacity_bytes() {
                 txn.sequence_info.account_sequence_number,
                 txn.timeline_state,
                 false,
+                None,
             );
             assert_eq!(status.code, MempoolStatusCode::Accepted);
         });
@@ -577,6 +587,7 @@ fn test_capacity_bytes() {
                 txn.sequence_info.account_sequence_number,
                 txn.timeline_state,
                 false,
+                None,
             );
             assert_eq!(status.code, MempoolStatusCode::MempoolIsFull);
         }
@@ -595,6 +606,7 @@ fn new_test_mempool_transaction(address: usize, sequence_number: u64) -> Mempool
         0,
         SystemTime::now(),
         false,
+        None,
     )
 }
 
@@ -624,6 +636,58 @@ fn test_parking_lot_eviction() {
     assert!(add_txn(&mut pool, TestTransaction::new(0, 2, 1)).is_err());
 }
 
+#[test]
+fn test_parking_lot_eviction_prefers_over_quota_senders() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 4;
+    let mut pool = CoreMempool::new(&config);
+    // Fill Mempool with parked transactions of two accounts.
+    for address in [1, 2] {
+        for seq in [5, 6] {
+            add_txn(&mut pool, TestTransaction::new(address, seq, 1)).unwrap();
+        }
+    }
+    pool.mark_over_quota(&[TestTransaction::get_address(2)]);
+
+    // Ready transactions evict the parked transactions of the sender over quota first.
+    for seq in [0, 1] {
+        add_txn(&mut pool, TestTransaction::new(0, seq, 1)).unwrap();
+    }
+    let (_, statuses) = pool.get_account_status(&TestTransaction::get_address(1));
+    assert_eq!(statuses.len(), 2);
+    let (_, statuses) = pool.get_account_status(&TestTransaction::get_address(2));
+    assert!(statuses.is_empty());
+}
+
+#[test]
+fn test_parking_lot_eviction_prefers_over_quota_peers() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 4;
+    let mut pool = CoreMempool::new(&config);
+    // Fill Mempool with parked transactions of two accounts, received from two peers.
+    let peers = [
+        PeerNetworkId::new(NetworkId::Public, PeerId::random()),
+        PeerNetworkId::new(NetworkId::Public, PeerId::random()),
+    ];
+    for (address, peer) in [1, 2].into_iter().zip(peers) {
+        for seq in [5, 6] {
+            let txn = TestTransaction::new(address, seq, 1).make_signed_transaction();
+            let status = pool.add_txn(txn, 1, 0, TimelineState::NotReady, false, Some(peer));
+            assert_eq!(status.code, MempoolStatusCode::Accepted);
+        }
+    }
+    pool.mark_over_quota_peer(&peers[1]);
+
+    // Ready transactions evict the transactions parked from the peer over quota first.
+    for seq in [0, 1] {
+        add_txn(&mut pool, TestTransaction::new(0, seq, 1)).unwrap();
+    }
+    let (_, statuses) = pool.get_account_status(&TestTransaction::get_address(1));
+    assert_eq!(statuses.len(), 2);
+    let (_, statuses) = pool.get_account_status(&TestTransaction::get_address(2));
+    assert!(statuses.is_empty());
+}
+
 #[test]
 fn test_parking_lot_evict_only_for_ready_txn_insertion() {
     let mut config = NodeConfig::generate_random_config();
@@ -663,7 +727,7 @@ fn test_gc_ready_transaction() {
 
     // Insert in the middle transaction that's going to be expired.
     let txn = TestTransaction::new(1, 1, 1).make_signed_transaction_with_expiration_time(0);
-    pool.add_txn(txn, 1, 0, TimelineState::NotReady, false);
+    pool.add_txn(txn, 1, 0, TimelineState::NotReady, false, None);
 
     // Insert few transactions after it.
     // They are supposed to be ready because there's a sequential path from 0 to them.
@@ -702,7 +766,14 @@ fn test_clean_stuck_transactions() {
     }
     let db_sequence_number = 10;
     let txn = TestTransaction::new(0, db_sequence_number, 1).make_signed_transaction();
-    pool.add_txn(txn, 1, db_sequence_number, TimelineState::NotReady, false);
+    pool.add_txn(
+        txn,
+        1,
+        db_sequence_number,
+        TimelineState::NotReady,
+        false,
+        None,
+    );
     let block = pool.get_batch(1, 1024, true, btreemap![]);
     assert_eq!(block.len(), 1);
     assert_eq!(block[0].sequence_number(), 10);
@@ -719,6 +790,7 @@ fn test_get_transaction_by_hash() {
         db_sequence_number,
         TimelineState::NotReady,
         false,
+        None,
     );
     let hash = txn.committed_hash();
     let ret = pool.get_by_hash(hash);
@@ -739,6 +811,7 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
         db_sequence_number,
         TimelineState::NotReady,
         false,
+        None,
     );
     let hash = txn.committed_hash();
 
@@ -750,6 +823,7 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
         db_sequence_number,
         TimelineState::NotReady,
         false,
+        None,
     );
     let new_txn_hash = new_txn.committed_hash();
 
@@ -948,3 +1022,78 @@ fn test_include_gas_upgraded() {
     });
     assert_eq!(batch.len(), 0);
 }
+
+#[test]
+fn test_get_transactions_to_persist() {
+    let (mut pool, _) = setup_mempool();
+    let txns = add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(0, 1, 1),
+        TestTransaction::new(0, 2, 1),
+    ]);
+
+    // Transactions of an account are persisted in sequence number order
+    assert_eq!(pool.get_transactions_to_persist(100, u64::MAX), txns);
+
+    // The limits are respected
+    assert_eq!(pool.get_transactions_to_persist(2, u64::MAX), txns[..2]);
+    let max_bytes = txn_bytes_len(TestTransaction::new(0, 0, 1));
+    assert_eq!(pool.get_transactions_to_persist(100, max_bytes), txns[..1]);
+    assert!(pool.get_transactions_to_persist(0, u64::MAX).is_empty());
+}
+
+#[test]
+fn test_persisted_transactions_roundtrip() {
+    let dir = aptos_temppath::TempPath::new();
+    let path = dir.path().join("mempool").join("pending_transactions.bcs");
+
+    // A missing journal has no transactions
+    assert!(load_transactions(&path).unwrap().is_empty());
+
+    let txns = vec![
+        TestTransaction::new(0, 0, 1).make_signed_transaction(),
+        TestTransaction::new(1, 0, 1).make_signed_transaction(),
+    ];
+    write_transactions(&path, &txns).unwrap();
+    assert_eq!(load_transactions(&path).unwrap(), txns);
+
+    // Writing replaces the previous journal
+    write_transactions(&path, &txns[1..]).unwrap();
+    assert_eq!(load_transactions(&path).unwrap(), txns[1..]);
+}
+
+#[test]
+fn test_get_account_status() {
+    let (mut pool, _) = setup_mempool();
+    let txns = add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(1, 0, 1),
+        TestTransaction::new(1, 1, 1),
+        TestTransaction::new(1, 3, 1),
+    ]);
+
+    // Transactions are in sequence number order, and the one after the gap is parked
+    let (sequence_number, statuses) = pool.get_account_status(&TestTransaction::get_address(1));
+    assert_eq!(sequence_number, Some(0));
+    let states: Vec<_> = statuses
+        .iter()
+        .map(|(status, _)| (status.txn.clone(), status.state))
+        .collect();
+    assert_eq!(states, vec![
+        (txns[0].clone(), MempoolTransactionState::Ready),
+        (txns[1].clone(), MempoolTransactionState::Ready),
+        (txns[2].clone(), MempoolTransactionState::Parked),
+    ]);
+
+    // Lookup by hash returns the same status
+    let (status, _) = pool
+        .get_transaction_status(txns[2].committed_hash())
+        .unwrap();
+    assert_eq!(status.txn, txns[2]);
+    assert_eq!(status.state, MempoolTransactionState::Parked);
+    assert!(pool.get_transaction_status(HashValue::random()).is_none());
+
+    // Unknown accounts have no transactions
+    let (sequence_number, statuses) = pool.get_account_status(&TestTransaction::get_address(2));
+    assert_eq!(sequence_number, None);
+    assert!(statuses.is_empty());
+}
@@ -10,7 +10,7 @@ use crate::{
 use anyhow::{format_err, Result};
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
 use aptos_config::{
-    config::{NetworkConfig, NodeConfig},
+    config::{MempoolConfig, NetworkConfig, NodeConfig},
     network_id::NetworkId,
 };
 use aptos_event_notifications::{ReconfigNotification, ReconfigNotificationListener};
@@ -62,6 +62,7 @@ impl MockSharedMempool {
         // Create the shared mempool
         let (ac_client, mempool, quorum_store_sender, mempool_notifier) = Self::start(
             &Handle::current(),
+            MempoolConfig::default(),
             &DbReaderWriter::new(MockDbReaderWriter),
             MockVMValidator,
         );
@@ -90,10 +91,20 @@ impl MockSharedMempool {
     pub fn new_in_runtime<V: TransactionValidation + 'static>(
         db: &DbReaderWriter,
         validator: V,
+    ) -> Self {
+        Self::new_in_runtime_with_config(MempoolConfig::default(), db, validator)
+    }
+
+    /// Creates a mock of a running instance of shared mempool inside a tokio runtime,
+    /// using the given mempool config.
+    pub fn new_in_runtime_with_config<V: TransactionValidation + 'static>(
+        mempool_config: MempoolConfig,
+        db: &DbReaderWriter,
+        validator: V,
     ) -> Self {
         let handle = Handle::current();
         let (ac_client, mempool, quorum_store_sender, mempool_notifier) =
-            Self::start(&handle, db, validator);
+            Self::start(&handle, mempool_config, db, validator);
         Self {
             _runtime: None,
             _handle: Some(handle),
@@ -106,6 +117,7 @@ impl MockSharedMempool {
 
     pub fn start<V: TransactionValidation + 'static>(
         handle: &Handle,
+        mempool_config: MempoolConfig,
         db: &DbReaderWriter,
         validator: V,
     ) -> (
@@ -115,6 +127,7 @@ impl MockSharedMempool {
         MempoolNotifier,
     ) {
         let mut config = NodeConfig::generate_random_config();
+        config.mempool = mempool_config;
         config.validator_network = Some(NetworkConfig::network_with_id(NetworkId::Validator));
 
         let mempool = Arc::new(Mutex::new(CoreMempool::new(&config)));
@@ -184,6 +197,7 @@ impl MockSharedMempool {
                         0,
                         TimelineState::NotReady,
                         false,
+                        None,
                     )
                     .code
                     != MempoolStatusCode::Accepted
@@ -383,6 +383,7 @@ impl Node {
                 0,
                 TimelineState::NotReady,
                 false,
+                None,
             );
         }
     }
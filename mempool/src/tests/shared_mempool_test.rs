@@ -4,14 +4,32 @@
 
 use crate::{
     mocks::MockSharedMempool,
+    shared_mempool::{
+        fairness::{FairnessQuotas, QuotaExceeded},
+        persistence::write_transactions,
+    },
     tests::common::{batch_add_signed_txn, TestTransaction},
-    QuorumStoreRequest,
+    MempoolClientRequest, QuorumStoreRequest,
+};
+use aptos_config::{
+    config::{transaction_filter_type::Filter, MempoolConfig, MempoolFairnessConfig},
+    network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_consensus_types::common::RejectedTransactionSummary;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
 use aptos_mempool_notifications::MempoolNotificationSender;
-use aptos_types::{transaction::Transaction, vm_status::DiscardedVMStatus};
+use aptos_storage_interface::{mock::MockDbReaderWriter, DbReaderWriter};
+use aptos_types::{
+    chain_id::ChainId,
+    mempool_status::MempoolStatusCode,
+    transaction::{RawTransaction, Script, Transaction},
+    vm_status::DiscardedVMStatus,
+    PeerId,
+};
+use aptos_vm_validator::mocks::mock_vm_validator::{MockVMValidator, ACCOUNT_DNE_TEST_ADD};
 use futures::{channel::oneshot, sink::SinkExt};
-use tokio::time::timeout;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
 
 #[tokio::test]
 async fn test_consensus_events_rejected_txns() {
@@ -105,3 +123,124 @@ async fn test_mempool_notify_committed_txns() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_transaction_filter_rejects_txns() {
+    // Create a shared mempool that denies the transactions of one sender
+    let mempool_config = MempoolConfig {
+        transaction_filter: Filter::empty().add_deny_sender(TestTransaction::get_address(0)),
+        ..MempoolConfig::default()
+    };
+    let smp = MockSharedMempool::new_in_runtime_with_config(
+        mempool_config,
+        &DbReaderWriter::new(MockDbReaderWriter),
+        MockVMValidator,
+    );
+
+    // Submit a transaction from the denied sender and one from another sender
+    let denied_txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let allowed_txn = TestTransaction::new(1, 0, 1).make_signed_transaction();
+    for (txn, expected_code) in [
+        (denied_txn, MempoolStatusCode::RejectedByFilter),
+        (allowed_txn.clone(), MempoolStatusCode::Accepted),
+    ] {
+        let (callback, callback_rcv) = oneshot::channel();
+        let mut client_sender = smp.ac_client.clone();
+        client_sender
+            .send(MempoolClientRequest::SubmitTransaction(txn, callback))
+            .await
+            .unwrap();
+        let (mempool_status, _) = callback_rcv.await.unwrap().unwrap();
+        assert_eq!(mempool_status.code, expected_code);
+    }
+
+    // Verify only the allowed transaction is in mempool
+    assert_eq!(smp.get_txns(10), vec![allowed_txn]);
+}
+
+#[tokio::test]
+async fn test_replay_persisted_transactions() {
+    let dir = aptos_temppath::TempPath::new();
+    let path = dir.path().join("pending_transactions.bcs");
+    let valid_txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let expired_txn = TestTransaction::new(1, 0, 1).make_signed_transaction_with_expiration_time(0);
+    // Rejected by the validator, as its sender doesn't exist
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let invalid_txn = RawTransaction::new_script(
+        ACCOUNT_DNE_TEST_ADD,
+        0,
+        Script::new(vec![], vec![], vec![]),
+        100,
+        1,
+        u64::MAX,
+        ChainId::test(),
+    )
+    .sign(&private_key, private_key.public_key())
+    .unwrap()
+    .into_inner();
+    write_transactions(&path, &[expired_txn, invalid_txn, valid_txn.clone()]).unwrap();
+
+    let mut config = MempoolConfig::default();
+    config.persistence.enabled = true;
+    config.persistence.path = path;
+    let smp = MockSharedMempool::new_in_runtime_with_config(
+        config,
+        &DbReaderWriter::new(MockDbReaderWriter),
+        MockVMValidator,
+    );
+
+    // The journal is replayed once the coordinator starts
+    let mut txns = vec![];
+    for _ in 0..100 {
+        txns = smp.get_txns(10);
+        if !txns.is_empty() {
+            break;
+        }
+        sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(txns, vec![valid_txn]);
+}
+
+#[test]
+fn test_fairness_quotas() {
+    let quotas = FairnessQuotas::new(&MempoolFairnessConfig {
+        enabled: true,
+        peer_txns_per_sec: 1,
+        peer_burst_txns: 3,
+        sender_txns_per_sec: 1,
+        sender_burst_txns: 2,
+    });
+    let peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    let validator = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    let sender = TestTransaction::get_address(0);
+    let other_sender = TestTransaction::get_address(1);
+    let third_sender = TestTransaction::get_address(2);
+
+    // Each sender is limited to its burst, whoever forwards its transactions
+    assert_eq!(quotas.try_admit(Some(&peer), sender), Ok(()));
+    assert_eq!(quotas.try_admit(None, sender), Ok(()));
+    assert_eq!(
+        quotas.try_admit(Some(&validator), sender),
+        Err(QuotaExceeded::Sender)
+    );
+    // A transaction over the sender quota doesn't use up a token of the peer
+    assert_eq!(
+        quotas.try_admit(Some(&peer), sender),
+        Err(QuotaExceeded::Sender)
+    );
+
+    // The peer uses up its burst, while validators aren't subject to a peer quota
+    assert_eq!(quotas.try_admit(Some(&peer), other_sender), Ok(()));
+    assert_eq!(quotas.try_admit(Some(&peer), other_sender), Ok(()));
+    assert_eq!(
+        quotas.try_admit(Some(&peer), third_sender),
+        Err(QuotaExceeded::Peer)
+    );
+    assert_eq!(quotas.try_admit(Some(&validator), third_sender), Ok(()));
+
+    // Disabled quotas admit everything
+    let quotas = FairnessQuotas::new(&MempoolFairnessConfig::default());
+    for _ in 0..1_000 {
+        assert_eq!(quotas.try_admit(Some(&peer), sender), Ok(()));
+    }
+}
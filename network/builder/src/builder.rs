@@ -39,7 +39,7 @@ use aptos_network::{
         },
     },
 };
-use aptos_network_discovery::DiscoveryChangeListener;
+use aptos_network_discovery::{DiscoveryChangeListener, SystemDnsResolver};
 use aptos_time_service::TimeService;
 use aptos_types::{chain_id::ChainId, network_address::NetworkAddress};
 use std::{clone::Clone, collections::HashSet, sync::Arc, time::Duration};
@@ -389,6 +389,19 @@ impl NetworkBuilder {
                     Duration::from_secs(rest_discovery.interval_secs),
                     self.time_service.clone(),
                 ),
+                DiscoveryMethod::Dns(dns_discovery) => {
+                    let resolver = SystemDnsResolver::new()
+                        .expect("DNS discovery is unable to create the system DNS resolver!");
+                    DiscoveryChangeListener::dns(
+                        self.network_context,
+                        conn_mgr_reqs_tx.clone(),
+                        dns_discovery.names.clone(),
+                        dns_discovery.peer_role,
+                        Arc::new(resolver),
+                        Duration::from_secs(dns_discovery.interval_secs),
+                        self.time_service.clone(),
+                    )
+                },
                 DiscoveryMethod::None => {
                     continue;
                 },
@@ -0,0 +1,467 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiscoveryError;
+use aptos_config::config::{Peer, PeerRole, PeerSet, HANDSHAKE_VERSION};
+use aptos_crypto::{x25519, ValidCryptoMaterialStringExt};
+use aptos_logger::warn;
+use aptos_time_service::{Interval, TimeService, TimeServiceTrait};
+use aptos_types::{
+    account_address::from_identity_public_key,
+    network_address::{DnsName, NetworkAddress, Protocol},
+    PeerId,
+};
+use async_trait::async_trait;
+use futures::{future::BoxFuture, Future, Stream};
+use hickory_resolver::TokioAsyncResolver;
+use std::{
+    collections::{hash_map::Entry, HashSet},
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// The TXT record key holding the hex encoded noise public key of the peer
+pub const NOISE_KEY_TXT_KEY: &str = "noise_key";
+/// The (optional) TXT record key holding the hex encoded peer id. If it is
+/// missing, the peer id is derived from the noise public key.
+pub const PEER_ID_TXT_KEY: &str = "peer_id";
+
+/// A single resolved SRV record (i.e., a peer endpoint)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SrvRecord {
+    pub target: String,
+    pub port: u16,
+}
+
+/// A simple interface for the DNS lookups required by discovery. This
+/// allows tests to use a local stub resolver instead of the system one.
+#[async_trait]
+pub trait DnsResolver: Send + Sync {
+    /// Returns the SRV records for the given name
+    async fn lookup_srv(&self, name: &str) -> Result<Vec<SrvRecord>, DiscoveryError>;
+
+    /// Returns the TXT records (each joined into a single string) for the given name
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, DiscoveryError>;
+}
+
+/// A DNS resolver that uses the system resolver configuration (e.g., `/etc/resolv.conf`)
+pub struct SystemDnsResolver {
+    resolver: TokioAsyncResolver,
+}
+
+impl SystemDnsResolver {
+    pub fn new() -> Result<Self, DiscoveryError> {
+        let resolver = TokioAsyncResolver::tokio_from_system_conf()
+            .map_err(|error| DiscoveryError::Dns(error.to_string()))?;
+        Ok(Self { resolver })
+    }
+}
+
+#[async_trait]
+impl DnsResolver for SystemDnsResolver {
+    async fn lookup_srv(&self, name: &str) -> Result<Vec<SrvRecord>, DiscoveryError> {
+        let lookup = self
+            .resolver
+            .srv_lookup(name)
+            .await
+            .map_err(|error| DiscoveryError::Dns(error.to_string()))?;
+        Ok(lookup
+            .iter()
+            .map(|srv| SrvRecord {
+                target: srv.target().to_utf8(),
+                port: srv.port(),
+            })
+            .collect())
+    }
+
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, DiscoveryError> {
+        let lookup = self
+            .resolver
+            .txt_lookup(name)
+            .await
+            .map_err(|error| DiscoveryError::Dns(error.to_string()))?;
+        Ok(lookup
+            .iter()
+            .map(|txt| {
+                txt.txt_data()
+                    .iter()
+                    .map(|data| String::from_utf8_lossy(data).into_owned())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect())
+    }
+}
+
+/// A discovery stream that periodically resolves the configured DNS names
+/// into a peer set. Each name is resolved to a set of SRV records, and each
+/// SRV target is expected to publish a TXT record of the form:
+/// `noise_key=<hex encoded x25519 key> [peer_id=<hex encoded peer id>]`.
+pub struct DnsStream {
+    names: Vec<String>,
+    peer_role: PeerRole,
+    resolver: Arc<dyn DnsResolver>,
+    interval: Pin<Box<Interval>>,
+    pending_lookup: Option<BoxFuture<'static, Result<PeerSet, DiscoveryError>>>,
+}
+
+impl DnsStream {
+    pub(crate) fn new(
+        names: Vec<String>,
+        peer_role: PeerRole,
+        resolver: Arc<dyn DnsResolver>,
+        interval_duration: Duration,
+        time_service: TimeService,
+    ) -> Self {
+        DnsStream {
+            names,
+            peer_role,
+            resolver,
+            interval: Box::pin(time_service.interval(interval_duration)),
+            pending_lookup: None,
+        }
+    }
+}
+
+impl Stream for DnsStream {
+    type Item = Result<PeerSet, DiscoveryError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Wait for delay, and start a new lookup for the next call
+        if self.pending_lookup.is_none() {
+            futures::ready!(self.interval.as_mut().poll_next(cx));
+            let lookup =
+                resolve_peer_set(self.resolver.clone(), self.names.clone(), self.peer_role);
+            self.pending_lookup = Some(Box::pin(lookup));
+        }
+
+        // Wait for the lookup to complete
+        let result = futures::ready!(self
+            .pending_lookup
+            .as_mut()
+            .expect("The pending lookup must exist!")
+            .as_mut()
+            .poll(cx));
+        self.pending_lookup = None;
+
+        Poll::Ready(Some(result))
+    }
+}
+
+/// Resolves all the given DNS names into a single peer set. If any SRV
+/// lookup fails, an error is returned (so that the previously discovered
+/// peers are not dropped). Endpoints with failed TXT lookups are skipped.
+async fn resolve_peer_set(
+    resolver: Arc<dyn DnsResolver>,
+    names: Vec<String>,
+    peer_role: PeerRole,
+) -> Result<PeerSet, DiscoveryError> {
+    let mut peer_set = PeerSet::new();
+    for name in names {
+        for srv_record in resolver.lookup_srv(&name).await? {
+            let txt_records = match resolver.lookup_txt(&srv_record.target).await {
+                Ok(txt_records) => txt_records,
+                Err(error) => {
+                    warn!(
+                        "Ignoring DNS peer record for {} ({:?}), the TXT lookup failed: {:?}",
+                        name, srv_record, error
+                    );
+                    continue;
+                },
+            };
+            let (peer_id, address) = match parse_peer_endpoint(&srv_record, &txt_records) {
+                Ok(peer_endpoint) => peer_endpoint,
+                Err(error) => {
+                    warn!(
+                        "Ignoring invalid DNS peer record for {} ({:?}): {:?}",
+                        name, srv_record, error
+                    );
+                    continue;
+                },
+            };
+
+            // Merge multiple endpoints of the same peer
+            match peer_set.entry(peer_id) {
+                Entry::Occupied(mut entry) => {
+                    let peer = entry.get_mut();
+                    peer.keys.extend(address.find_noise_proto());
+                    peer.addresses.push(address);
+                },
+                Entry::Vacant(entry) => {
+                    entry.insert(Peer::new(vec![address], HashSet::new(), peer_role));
+                },
+            }
+        }
+    }
+    Ok(peer_set)
+}
+
+/// Parses the peer id and network address from the given SRV record
+/// and the TXT records of the SRV target.
+fn parse_peer_endpoint(
+    srv_record: &SrvRecord,
+    txt_records: &[String],
+) -> Result<(PeerId, NetworkAddress), DiscoveryError> {
+    // Parse the noise key and peer id from the TXT records
+    let mut noise_key = None;
+    let mut peer_id = None;
+    for (key, value) in txt_records
+        .iter()
+        .flat_map(|record| record.split_whitespace())
+        .filter_map(|entry| entry.split_once('='))
+    {
+        match key {
+            NOISE_KEY_TXT_KEY => {
+                let public_key = x25519::PublicKey::from_encoded_string(value)
+                    .map_err(|error| DiscoveryError::Parsing(error.to_string()))?;
+                noise_key = Some(public_key);
+            },
+            PEER_ID_TXT_KEY => {
+                let parsed_peer_id = PeerId::from_str(value)
+                    .map_err(|error| DiscoveryError::Parsing(error.to_string()))?;
+                peer_id = Some(parsed_peer_id);
+            },
+            _ => { /* Ignore unknown keys */ },
+        }
+    }
+    let noise_key = noise_key.ok_or_else(|| {
+        DiscoveryError::Parsing(format!(
+            "Missing {} in the TXT records of {}",
+            NOISE_KEY_TXT_KEY, srv_record.target
+        ))
+    })?;
+    let peer_id = peer_id.unwrap_or_else(|| from_identity_public_key(noise_key));
+
+    // Build the network address (the SRV target is fully qualified, so
+    // we strip the trailing dot).
+    let dns_name = DnsName::from_str(srv_record.target.trim_end_matches('.'))
+        .map_err(|error| DiscoveryError::Parsing(error.to_string()))?;
+    let address = NetworkAddress::from_protocols(vec![
+        Protocol::Dns(dns_name),
+        Protocol::Tcp(srv_record.port),
+    ])
+    .map_err(|error| DiscoveryError::Parsing(error.to_string()))?
+    .append_prod_protos(noise_key, HANDSHAKE_VERSION);
+
+    Ok((peer_id, address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiscoveryChangeListener;
+    use aptos_channels::Receiver;
+    use aptos_config::network_id::NetworkContext;
+    use aptos_crypto::Uniform;
+    use aptos_event_notifications::DbBackedOnChainConfig;
+    use aptos_logger::spawn_named;
+    use aptos_network::connectivity_manager::{ConnectivityRequest, DiscoverySource};
+    use futures::StreamExt;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashMap;
+
+    /// A local stub resolver that serves records from memory
+    #[derive(Default)]
+    struct StubDnsResolver {
+        srv_records: HashMap<String, Vec<SrvRecord>>,
+        txt_records: HashMap<String, Vec<String>>,
+    }
+
+    impl StubDnsResolver {
+        fn add_srv_record(&mut self, name: &str, target: &str, port: u16) {
+            self.srv_records
+                .entry(name.into())
+                .or_default()
+                .push(SrvRecord {
+                    target: target.into(),
+                    port,
+                });
+        }
+
+        fn add_txt_record(&mut self, name: &str, record: String) {
+            self.txt_records
+                .entry(name.into())
+                .or_default()
+                .push(record);
+        }
+    }
+
+    #[async_trait]
+    impl DnsResolver for StubDnsResolver {
+        async fn lookup_srv(&self, name: &str) -> Result<Vec<SrvRecord>, DiscoveryError> {
+            self.srv_records
+                .get(name)
+                .cloned()
+                .ok_or_else(|| DiscoveryError::Dns(format!("No SRV records for {}", name)))
+        }
+
+        async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, DiscoveryError> {
+            self.txt_records
+                .get(name)
+                .cloned()
+                .ok_or_else(|| DiscoveryError::Dns(format!("No TXT records for {}", name)))
+        }
+    }
+
+    fn create_noise_key(seed: u8) -> x25519::PublicKey {
+        let mut rng = StdRng::from_seed([seed; 32]);
+        x25519::PrivateKey::generate(&mut rng).public_key()
+    }
+
+    fn create_listener(
+        resolver: StubDnsResolver,
+        names: Vec<String>,
+    ) -> Receiver<ConnectivityRequest> {
+        let (conn_mgr_reqs_tx, conn_mgr_reqs_rx) = aptos_channels::new(
+            1,
+            &aptos_network::counters::PENDING_CONNECTIVITY_MANAGER_REQUESTS,
+        );
+        let listener_task = async move {
+            let listener = DiscoveryChangeListener::<DbBackedOnChainConfig>::dns(
+                NetworkContext::mock(),
+                conn_mgr_reqs_tx,
+                names,
+                PeerRole::Upstream,
+                Arc::new(resolver),
+                Duration::from_millis(5),
+                TimeService::real(),
+            );
+            Box::pin(listener).run().await
+        };
+
+        spawn_named!("[Network] Listener Task", listener_task);
+        conn_mgr_reqs_rx
+    }
+
+    #[tokio::test]
+    async fn test_resolve_peer_set() {
+        // Create a resolver with two peers (the first with two endpoints)
+        let noise_key_1 = create_noise_key(1);
+        let noise_key_2 = create_noise_key(2);
+        let peer_id_2 = PeerId::random();
+        let mut resolver = StubDnsResolver::default();
+        resolver.add_srv_record(
+            "_aptos._tcp.fullnodes.test",
+            "node-1a.fullnodes.test.",
+            6182,
+        );
+        resolver.add_srv_record(
+            "_aptos._tcp.fullnodes.test",
+            "node-1b.fullnodes.test.",
+            6182,
+        );
+        resolver.add_srv_record("_aptos._tcp.fullnodes.test", "node-2.fullnodes.test.", 6183);
+        for target in ["node-1a.fullnodes.test.", "node-1b.fullnodes.test."] {
+            resolver.add_txt_record(target, format!("noise_key={}", noise_key_1));
+        }
+        resolver.add_txt_record(
+            "node-2.fullnodes.test.",
+            format!("noise_key={} peer_id={}", noise_key_2, peer_id_2),
+        );
+
+        // Resolve the peer set
+        let peer_set = resolve_peer_set(
+            Arc::new(resolver),
+            vec!["_aptos._tcp.fullnodes.test".into()],
+            PeerRole::Upstream,
+        )
+        .await
+        .unwrap();
+
+        // Verify the first peer (with a peer id derived from the noise key)
+        assert_eq!(peer_set.len(), 2);
+        let peer_1 = peer_set
+            .get(&from_identity_public_key(noise_key_1))
+            .unwrap();
+        assert_eq!(peer_1.role, PeerRole::Upstream);
+        assert_eq!(peer_1.keys, HashSet::from([noise_key_1]));
+        assert_eq!(peer_1.addresses, vec![
+            NetworkAddress::from_str(&format!(
+                "/dns/node-1a.fullnodes.test/tcp/6182/noise-ik/{}/handshake/0",
+                noise_key_1
+            ))
+            .unwrap(),
+            NetworkAddress::from_str(&format!(
+                "/dns/node-1b.fullnodes.test/tcp/6182/noise-ik/{}/handshake/0",
+                noise_key_1
+            ))
+            .unwrap(),
+        ]);
+
+        // Verify the second peer (with an explicit peer id)
+        let peer_2 = peer_set.get(&peer_id_2).unwrap();
+        assert_eq!(peer_2.keys, HashSet::from([noise_key_2]));
+        assert_eq!(peer_2.addresses, vec![NetworkAddress::from_str(&format!(
+            "/dns/node-2.fullnodes.test/tcp/6183/noise-ik/{}/handshake/0",
+            noise_key_2
+        ))
+        .unwrap()]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_invalid_records() {
+        // Create a resolver with one valid and three invalid peers
+        let noise_key = create_noise_key(1);
+        let mut resolver = StubDnsResolver::default();
+        resolver.add_srv_record("_aptos._tcp.fullnodes.test", "valid.fullnodes.test.", 6182);
+        resolver.add_srv_record("_aptos._tcp.fullnodes.test", "no-txt.fullnodes.test.", 6182);
+        resolver.add_srv_record("_aptos._tcp.fullnodes.test", "no-key.fullnodes.test.", 6182);
+        resolver.add_srv_record(
+            "_aptos._tcp.fullnodes.test",
+            "bad-key.fullnodes.test.",
+            6182,
+        );
+        resolver.add_txt_record("valid.fullnodes.test.", format!("noise_key={}", noise_key));
+        resolver.add_txt_record("no-key.fullnodes.test.", "peer_id=0x1".into());
+        resolver.add_txt_record("bad-key.fullnodes.test.", "noise_key=1234".into());
+        let resolver = Arc::new(resolver);
+
+        // Verify that the invalid peers are ignored
+        let peer_set = resolve_peer_set(
+            resolver.clone(),
+            vec!["_aptos._tcp.fullnodes.test".into()],
+            PeerRole::Upstream,
+        )
+        .await
+        .unwrap();
+        assert_eq!(peer_set.len(), 1);
+        assert!(peer_set.contains_key(&from_identity_public_key(noise_key)));
+
+        // Verify that a failed lookup fails the entire update
+        let result = resolve_peer_set(
+            resolver,
+            vec![
+                "_aptos._tcp.fullnodes.test".into(),
+                "_aptos._tcp.missing.test".into(),
+            ],
+            PeerRole::Upstream,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dns_listener() {
+        // Create a resolver with a single peer
+        let noise_key = create_noise_key(1);
+        let mut resolver = StubDnsResolver::default();
+        resolver.add_srv_record("_aptos._tcp.fullnodes.test", "node.fullnodes.test.", 6182);
+        resolver.add_txt_record("node.fullnodes.test.", format!("noise_key={}", noise_key));
+
+        // Verify that the discovered peers are sent to the connectivity manager
+        let mut conn_mgr_reqs_rx =
+            create_listener(resolver, vec!["_aptos._tcp.fullnodes.test".into()]);
+        if let Some(ConnectivityRequest::UpdateDiscoveredPeers(DiscoverySource::Dns, peers)) =
+            conn_mgr_reqs_rx.next().await
+        {
+            assert_eq!(peers.len(), 1);
+            let peer = peers.get(&from_identity_public_key(noise_key)).unwrap();
+            assert_eq!(peer.keys, HashSet::from([noise_key]));
+        } else {
+            panic!("No message sent by discovery")
+        }
+    }
+}
@@ -140,6 +140,7 @@ pub enum DiscoverySource {
     OnChainValidatorSet,
     File,
     Rest,
+    Dns,
     Config,
 }
 
@@ -156,6 +157,7 @@ impl fmt::Display for DiscoverySource {
             DiscoverySource::File => "File",
             DiscoverySource::Config => "Config",
             DiscoverySource::Rest => "Rest",
+            DiscoverySource::Dns => "Dns",
         })
     }
 }
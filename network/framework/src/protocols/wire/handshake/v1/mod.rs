@@ -15,7 +15,7 @@
 
 use crate::counters::{start_serialization_timer, DESERIALIZATION_LABEL, SERIALIZATION_LABEL};
 use anyhow::anyhow;
-use aptos_compression::client::CompressionClient;
+use aptos_compression::{algorithm::CompressionAlgorithm, client::CompressionClient};
 use aptos_config::{config::MAX_APPLICATION_MESSAGE_SIZE, network_id::NetworkId};
 use aptos_types::chain_id::ChainId;
 #[cfg(any(test, feature = "fuzzing"))]
@@ -72,6 +72,14 @@ pub enum ProtocolId {
     JWKConsensusRpcBcs = 25,
     JWKConsensusRpcJson = 26,
     ConsensusObserver = 27,
+    ConsensusRpcCompressedZstd = 28,
+    ConsensusDirectSendCompressedZstd = 29,
+    MempoolDirectSendCompressedZstd = 30,
+    DKGDirectSendCompressedZstd = 31,
+    DKGRpcCompressedZstd = 32,
+    JWKConsensusDirectSendCompressedZstd = 33,
+    JWKConsensusRpcCompressedZstd = 34,
+    StorageServiceRpcCompressedZstd = 35, // Responses are compressed by the storage service (using zstd)
 }
 
 /// The encoding types for Protocols
@@ -113,6 +121,14 @@ impl ProtocolId {
             JWKConsensusRpcBcs => "JWKConsensusRpcBcs",
             JWKConsensusRpcJson => "JWKConsensusRpcJson",
             ConsensusObserver => "ConsensusObserver",
+            ConsensusRpcCompressedZstd => "ConsensusRpcCompressedZstd",
+            ConsensusDirectSendCompressedZstd => "ConsensusDirectSendCompressedZstd",
+            MempoolDirectSendCompressedZstd => "MempoolDirectSendCompressedZstd",
+            DKGDirectSendCompressedZstd => "DKGDirectSendCompressedZstd",
+            DKGRpcCompressedZstd => "DKGRpcCompressedZstd",
+            JWKConsensusDirectSendCompressedZstd => "JWKConsensusDirectSendCompressedZstd",
+            JWKConsensusRpcCompressedZstd => "JWKConsensusRpcCompressedZstd",
+            StorageServiceRpcCompressedZstd => "StorageServiceRpcCompressedZstd",
         }
     }
 
@@ -147,6 +163,14 @@ impl ProtocolId {
             ProtocolId::JWKConsensusRpcBcs,
             ProtocolId::JWKConsensusRpcJson,
             ProtocolId::ConsensusObserver,
+            ProtocolId::ConsensusRpcCompressedZstd,
+            ProtocolId::ConsensusDirectSendCompressedZstd,
+            ProtocolId::MempoolDirectSendCompressedZstd,
+            ProtocolId::DKGDirectSendCompressedZstd,
+            ProtocolId::DKGRpcCompressedZstd,
+            ProtocolId::JWKConsensusDirectSendCompressedZstd,
+            ProtocolId::JWKConsensusRpcCompressedZstd,
+            ProtocolId::StorageServiceRpcCompressedZstd,
         ]
     }
 
@@ -162,7 +186,15 @@ impl ProtocolId {
             },
             ProtocolId::JWKConsensusDirectSendCompressed
             | ProtocolId::JWKConsensusRpcCompressed => Encoding::CompressedBcs(RECURSION_LIMIT),
-            ProtocolId::MempoolDirectSend => Encoding::CompressedBcs(USER_INPUT_RECURSION_LIMIT),
+            ProtocolId::ConsensusDirectSendCompressedZstd
+            | ProtocolId::ConsensusRpcCompressedZstd
+            | ProtocolId::DKGDirectSendCompressedZstd
+            | ProtocolId::DKGRpcCompressedZstd
+            | ProtocolId::JWKConsensusDirectSendCompressedZstd
+            | ProtocolId::JWKConsensusRpcCompressedZstd => Encoding::CompressedBcs(RECURSION_LIMIT),
+            ProtocolId::MempoolDirectSend | ProtocolId::MempoolDirectSendCompressedZstd => {
+                Encoding::CompressedBcs(USER_INPUT_RECURSION_LIMIT)
+            },
             ProtocolId::MempoolRpc => Encoding::Bcs(USER_INPUT_RECURSION_LIMIT),
             _ => Encoding::Bcs(RECURSION_LIMIT),
         }
@@ -171,15 +203,21 @@ impl ProtocolId {
     /// Returns the compression client label based on the current protocol id
     fn get_compression_client(self) -> CompressionClient {
         match self {
-            ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
-                CompressionClient::Consensus
-            },
-            ProtocolId::MempoolDirectSend => CompressionClient::Mempool,
-            ProtocolId::DKGDirectSendCompressed | ProtocolId::DKGRpcCompressed => {
-                CompressionClient::DKG
+            ProtocolId::ConsensusDirectSendCompressed
+            | ProtocolId::ConsensusRpcCompressed
+            | ProtocolId::ConsensusDirectSendCompressedZstd
+            | ProtocolId::ConsensusRpcCompressedZstd => CompressionClient::Consensus,
+            ProtocolId::MempoolDirectSend | ProtocolId::MempoolDirectSendCompressedZstd => {
+                CompressionClient::Mempool
             },
+            ProtocolId::DKGDirectSendCompressed
+            | ProtocolId::DKGRpcCompressed
+            | ProtocolId::DKGDirectSendCompressedZstd
+            | ProtocolId::DKGRpcCompressedZstd => CompressionClient::DKG,
             ProtocolId::JWKConsensusDirectSendCompressed
-            | ProtocolId::JWKConsensusRpcCompressed => CompressionClient::JWKConsensus,
+            | ProtocolId::JWKConsensusRpcCompressed
+            | ProtocolId::JWKConsensusDirectSendCompressedZstd
+            | ProtocolId::JWKConsensusRpcCompressedZstd => CompressionClient::JWKConsensus,
             protocol_id => unreachable!(
                 "The given protocol ({:?}) should not be using compression!",
                 protocol_id
@@ -187,6 +225,23 @@ impl ProtocolId {
         }
     }
 
+    /// Returns the compression algorithm based on the current protocol id. Zstd
+    /// always uses the default compression level (the level is not negotiated,
+    /// as it isn't required for decompression).
+    /// Note: this should only be called for protocols that use compression.
+    fn get_compression_algorithm(self) -> CompressionAlgorithm {
+        match self {
+            ProtocolId::ConsensusDirectSendCompressedZstd
+            | ProtocolId::ConsensusRpcCompressedZstd
+            | ProtocolId::MempoolDirectSendCompressedZstd
+            | ProtocolId::DKGDirectSendCompressedZstd
+            | ProtocolId::DKGRpcCompressedZstd
+            | ProtocolId::JWKConsensusDirectSendCompressedZstd
+            | ProtocolId::JWKConsensusRpcCompressedZstd => CompressionAlgorithm::zstd(),
+            _ => CompressionAlgorithm::Lz4,
+        }
+    }
+
     /// Serializes the given message into bytes (based on the protocol ID
     /// and encoding to use).
     pub fn to_bytes<T: Serialize>(&self, value: &T) -> anyhow::Result<Vec<u8>> {
@@ -198,10 +253,12 @@ impl ProtocolId {
             Encoding::Bcs(limit) => self.bcs_encode(value, limit),
             Encoding::CompressedBcs(limit) => {
                 let compression_client = self.get_compression_client();
+                let compression_algorithm = self.get_compression_algorithm();
                 let bcs_bytes = self.bcs_encode(value, limit)?;
-                aptos_compression::compress(
+                aptos_compression::compress_with_algorithm(
                     bcs_bytes,
                     compression_client,
+                    &compression_algorithm,
                     MAX_APPLICATION_MESSAGE_SIZE,
                 )
                 .map_err(|e| anyhow!("{:?}", e))
@@ -228,9 +285,11 @@ impl ProtocolId {
             Encoding::Bcs(limit) => self.bcs_decode(bytes, limit),
             Encoding::CompressedBcs(limit) => {
                 let compression_client = self.get_compression_client();
-                let raw_bytes = aptos_compression::decompress(
+                let compression_algorithm = self.get_compression_algorithm();
+                let raw_bytes = aptos_compression::decompress_with_algorithm(
                     &bytes.to_vec(),
                     compression_client,
+                    &compression_algorithm,
                     MAX_APPLICATION_MESSAGE_SIZE,
                 )
                 .map_err(|e| anyhow! {"{:?}", e})?;
@@ -183,3 +183,56 @@ fn ignore_unknown_protocols() {
         ProtocolIdSet::empty(),
     );
 }
+
+#[test]
+fn compressed_protocols_round_trip() {
+    // Create a test message
+    let message: Vec<u64> = (0..1000).collect();
+
+    // Verify that the message can be encoded and decoded by each compressed protocol
+    for protocol_id in [
+        ProtocolId::ConsensusRpcCompressed,
+        ProtocolId::ConsensusRpcCompressedZstd,
+        ProtocolId::MempoolDirectSend,
+        ProtocolId::MempoolDirectSendCompressedZstd,
+    ] {
+        let bytes = protocol_id.to_bytes(&message).unwrap();
+        let decoded_message: Vec<u64> = protocol_id.from_bytes(&bytes).unwrap();
+        assert_eq!(decoded_message, message);
+    }
+
+    // Verify that zstd encoded messages can't be decoded using LZ4 (and vice versa)
+    let bytes = ProtocolId::ConsensusRpcCompressedZstd
+        .to_bytes(&message)
+        .unwrap();
+    assert!(ProtocolId::ConsensusRpcCompressed
+        .from_bytes::<Vec<u64>>(&bytes)
+        .is_err());
+    let bytes = ProtocolId::ConsensusRpcCompressed
+        .to_bytes(&message)
+        .unwrap();
+    assert!(ProtocolId::ConsensusRpcCompressedZstd
+        .from_bytes::<Vec<u64>>(&bytes)
+        .is_err());
+}
+
+// Ensure peers without zstd support negotiate down to the LZ4 protocols.
+#[test]
+fn negotiate_compression_protocols() {
+    let zstd_protos = ProtocolIdSet::from_iter([
+        ProtocolId::ConsensusRpcCompressedZstd,
+        ProtocolId::ConsensusRpcCompressed,
+    ]);
+    let zstd_hs = HandshakeMsg::from_supported(zstd_protos.clone());
+
+    let lz4_protos = ProtocolIdSet::from_iter([ProtocolId::ConsensusRpcCompressed]);
+    let lz4_hs = HandshakeMsg::from_supported(lz4_protos.clone());
+
+    // Case 1: both peers support zstd
+    let (_, common_protos) = zstd_hs.perform_handshake(&zstd_hs).unwrap();
+    assert_eq!(common_protos, zstd_protos);
+
+    // Case 2: only one peer supports zstd
+    let (_, common_protos) = zstd_hs.perform_handshake(&lz4_hs).unwrap();
+    assert_eq!(common_protos, lz4_protos);
+}
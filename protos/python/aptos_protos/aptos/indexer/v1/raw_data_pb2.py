@@ -17,7 +17,7 @@ from aptos.transaction.v1 import (
 )
 
 DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(
-    b'\n\x1f\x61ptos/indexer/v1/raw_data.proto\x12\x10\x61ptos.indexer.v1\x1a&aptos/transaction/v1/transaction.proto"\x84\x01\n\x15TransactionsInStorage\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x1d\n\x10starting_version\x18\x02 \x01(\x04H\x00\x88\x01\x01\x42\x13\n\x11_starting_version"\xb4\x01\n\x16GetTransactionsRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x12\x17\n\nbatch_size\x18\x03 \x01(\x04H\x02\x88\x01\x01\x42\x13\n\x11_starting_versionB\x15\n\x13_transactions_countB\r\n\x0b_batch_size"w\n\x14TransactionsResponse\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x19\n\x08\x63hain_id\x18\x02 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x42\x0b\n\t_chain_id2p\n\x07RawData\x12\x65\n\x0fGetTransactions\x12(.aptos.indexer.v1.GetTransactionsRequest\x1a&.aptos.indexer.v1.TransactionsResponse0\x01\x62\x06proto3'
+    b'\n\x1f\x61ptos/indexer/v1/raw_data.proto\x12\x10\x61ptos.indexer.v1\x1a&aptos/transaction/v1/transaction.proto"\x84\x01\n\x15TransactionsInStorage\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x1d\n\x10starting_version\x18\x02 \x01(\x04H\x00\x88\x01\x01\x42\x13\n\x11_starting_version"\x98\x02\n\x16GetTransactionsRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x12\x17\n\nbatch_size\x18\x03 \x01(\x04H\x02\x88\x01\x01\x12K\n\x12transaction_filter\x18\x04 \x01(\x0b\x32*.aptos.indexer.v1.BooleanTransactionFilterH\x03\x88\x01\x01\x42\x13\n\x11_starting_versionB\x15\n\x13_transactions_countB\r\n\x0b_batch_sizeB\x15\n\x13_transaction_filter"\x90\x02\n\x18\x42ooleanTransactionFilter\x12\x31\n\napi_filter\x18\x01 \x01(\x0b\x32\x1b.aptos.indexer.v1.APIFilterH\x00\x12:\n\x0blogical_and\x18\x02 \x01(\x0b\x32#.aptos.indexer.v1.LogicalAndFiltersH\x00\x12\x38\n\nlogical_or\x18\x03 \x01(\x0b\x32".aptos.indexer.v1.LogicalOrFiltersH\x00\x12\x41\n\x0blogical_not\x18\x04 \x01(\x0b\x32*.aptos.indexer.v1.BooleanTransactionFilterH\x00\x42\x08\n\x06\x66ilter"P\n\x11LogicalAndFilters\x12;\n\x07\x66ilters\x18\x01 \x03(\x0b\x32*.aptos.indexer.v1.BooleanTransactionFilter"O\n\x10LogicalOrFilters\x12;\n\x07\x66ilters\x18\x01 \x03(\x0b\x32*.aptos.indexer.v1.BooleanTransactionFilter"\xaf\x02\n\tAPIFilter\x12J\n\x17transaction_root_filter\x18\x01 \x01(\x0b\x32\'.aptos.indexer.v1.TransactionRootFilterH\x00\x12J\n\x17user_transaction_filter\x18\x02 \x01(\x0b\x32\'.aptos.indexer.v1.UserTransactionFilterH\x00\x12\x35\n\x0c\x65vent_filter\x18\x03 \x01(\x0b\x32\x1d.aptos.indexer.v1.EventFilterH\x00\x12I\n\x17write_set_change_filter\x18\x04 \x01(\x0b\x32&.aptos.indexer.v1.WriteSetChangeFilterH\x00\x42\x08\n\x06\x66ilter"\xa0\x01\n\x15TransactionRootFilter\x12\x14\n\x07success\x18\x01 \x01(\x08H\x00\x88\x01\x01\x12P\n\x10transaction_type\x18\x02 \x01(\x0e\x32\x31.aptos.transaction.v1.Transaction.TransactionTypeH\x01\x88\x01\x01\x42\n\n\x08_successB\x13\n\x11_transaction_type"\x8e\x01\n\x15UserTransactionFilter\x12\x13\n\x06sender\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x42\n\x0e\x65ntry_function\x18\x02 \x01(\x0b\x32%.aptos.indexer.v1.EntryFunctionFilterH\x01\x88\x01\x01\x42\t\n\x07_senderB\x11\n\x0f_entry_function"\x85\x01\n\x13\x45ntryFunctionFilter\x12\x14\n\x07\x61\x64\x64ress\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x18\n\x0bmodule_name\x18\x02 \x01(\tH\x01\x88\x01\x01\x12\x15\n\x08\x66unction\x18\x03 \x01(\tH\x02\x88\x01\x01\x42\n\n\x08_addressB\x0e\n\x0c_module_nameB\x0b\n\t_function"^\n\x0b\x45ventFilter\x12?\n\x0bstruct_type\x18\x01 \x01(\x0b\x32%.aptos.indexer.v1.MoveStructTagFilterH\x00\x88\x01\x01\x42\x0e\n\x0c_struct_type"k\n\x14WriteSetChangeFilter\x12\x41\n\rresource_type\x18\x01 \x01(\x0b\x32%.aptos.indexer.v1.MoveStructTagFilterH\x00\x88\x01\x01\x42\x10\n\x0e_resource_type"s\n\x13MoveStructTagFilter\x12\x14\n\x07\x61\x64\x64ress\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x13\n\x06module\x18\x02 \x01(\tH\x01\x88\x01\x01\x12\x11\n\x04name\x18\x03 \x01(\tH\x02\x88\x01\x01\x42\n\n\x08_addressB\t\n\x07_moduleB\x07\n\x05_name"E\n\x0eProcessedRange\x12\x19\n\rfirst_version\x18\x01 \x01(\x04\x42\x02\x30\x01\x12\x18\n\x0clast_version\x18\x02 \x01(\x04\x42\x02\x30\x01"\xcb\x01\n\x14TransactionsResponse\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x19\n\x08\x63hain_id\x18\x02 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12>\n\x0fprocessed_range\x18\x03 \x01(\x0b\x32 .aptos.indexer.v1.ProcessedRangeH\x01\x88\x01\x01\x42\x0b\n\t_chain_idB\x12\n\x10_processed_range2p\n\x07RawData\x12\x65\n\x0fGetTransactions\x12(.aptos.indexer.v1.GetTransactionsRequest\x1a&.aptos.indexer.v1.TransactionsResponse0\x01\x62\x06proto3'
 )
 
 _globals = globals()
@@ -35,14 +35,40 @@ if _descriptor._USE_C_DESCRIPTORS == False:
     _GETTRANSACTIONSREQUEST.fields_by_name[
         "transactions_count"
     ]._serialized_options = b"0\001"
+    _PROCESSEDRANGE.fields_by_name["first_version"]._options = None
+    _PROCESSEDRANGE.fields_by_name["first_version"]._serialized_options = b"0\001"
+    _PROCESSEDRANGE.fields_by_name["last_version"]._options = None
+    _PROCESSEDRANGE.fields_by_name["last_version"]._serialized_options = b"0\001"
     _TRANSACTIONSRESPONSE.fields_by_name["chain_id"]._options = None
     _TRANSACTIONSRESPONSE.fields_by_name["chain_id"]._serialized_options = b"0\001"
     _globals["_TRANSACTIONSINSTORAGE"]._serialized_start = 94
     _globals["_TRANSACTIONSINSTORAGE"]._serialized_end = 226
     _globals["_GETTRANSACTIONSREQUEST"]._serialized_start = 229
-    _globals["_GETTRANSACTIONSREQUEST"]._serialized_end = 409
-    _globals["_TRANSACTIONSRESPONSE"]._serialized_start = 411
-    _globals["_TRANSACTIONSRESPONSE"]._serialized_end = 530
-    _globals["_RAWDATA"]._serialized_start = 532
-    _globals["_RAWDATA"]._serialized_end = 644
+    _globals["_GETTRANSACTIONSREQUEST"]._serialized_end = 509
+    _globals["_BOOLEANTRANSACTIONFILTER"]._serialized_start = 512
+    _globals["_BOOLEANTRANSACTIONFILTER"]._serialized_end = 784
+    _globals["_LOGICALANDFILTERS"]._serialized_start = 786
+    _globals["_LOGICALANDFILTERS"]._serialized_end = 866
+    _globals["_LOGICALORFILTERS"]._serialized_start = 868
+    _globals["_LOGICALORFILTERS"]._serialized_end = 947
+    _globals["_APIFILTER"]._serialized_start = 950
+    _globals["_APIFILTER"]._serialized_end = 1253
+    _globals["_TRANSACTIONROOTFILTER"]._serialized_start = 1256
+    _globals["_TRANSACTIONROOTFILTER"]._serialized_end = 1416
+    _globals["_USERTRANSACTIONFILTER"]._serialized_start = 1419
+    _globals["_USERTRANSACTIONFILTER"]._serialized_end = 1561
+    _globals["_ENTRYFUNCTIONFILTER"]._serialized_start = 1564
+    _globals["_ENTRYFUNCTIONFILTER"]._serialized_end = 1697
+    _globals["_EVENTFILTER"]._serialized_start = 1699
+    _globals["_EVENTFILTER"]._serialized_end = 1793
+    _globals["_WRITESETCHANGEFILTER"]._serialized_start = 1795
+    _globals["_WRITESETCHANGEFILTER"]._serialized_end = 1902
+    _globals["_MOVESTRUCTTAGFILTER"]._serialized_start = 1904
+    _globals["_MOVESTRUCTTAGFILTER"]._serialized_end = 2019
+    _globals["_PROCESSEDRANGE"]._serialized_start = 2021
+    _globals["_PROCESSEDRANGE"]._serialized_end = 2090
+    _globals["_TRANSACTIONSRESPONSE"]._serialized_start = 2093
+    _globals["_TRANSACTIONSRESPONSE"]._serialized_end = 2296
+    _globals["_RAWDATA"]._serialized_start = 2298
+    _globals["_RAWDATA"]._serialized_end = 2410
 # @@protoc_insertion_point(module_scope)
@@ -28,32 +28,190 @@ class TransactionsInStorage(_message.Message):
     ) -> None: ...
 
 class GetTransactionsRequest(_message.Message):
-    __slots__ = ["starting_version", "transactions_count", "batch_size"]
+    __slots__ = [
+        "starting_version",
+        "transactions_count",
+        "batch_size",
+        "transaction_filter",
+    ]
     STARTING_VERSION_FIELD_NUMBER: _ClassVar[int]
     TRANSACTIONS_COUNT_FIELD_NUMBER: _ClassVar[int]
     BATCH_SIZE_FIELD_NUMBER: _ClassVar[int]
+    TRANSACTION_FILTER_FIELD_NUMBER: _ClassVar[int]
     starting_version: int
     transactions_count: int
     batch_size: int
+    transaction_filter: BooleanTransactionFilter
     def __init__(
         self,
         starting_version: _Optional[int] = ...,
         transactions_count: _Optional[int] = ...,
         batch_size: _Optional[int] = ...,
+        transaction_filter: _Optional[_Union[BooleanTransactionFilter, _Mapping]] = ...,
+    ) -> None: ...
+
+class BooleanTransactionFilter(_message.Message):
+    __slots__ = ["api_filter", "logical_and", "logical_or", "logical_not"]
+    API_FILTER_FIELD_NUMBER: _ClassVar[int]
+    LOGICAL_AND_FIELD_NUMBER: _ClassVar[int]
+    LOGICAL_OR_FIELD_NUMBER: _ClassVar[int]
+    LOGICAL_NOT_FIELD_NUMBER: _ClassVar[int]
+    api_filter: APIFilter
+    logical_and: LogicalAndFilters
+    logical_or: LogicalOrFilters
+    logical_not: BooleanTransactionFilter
+    def __init__(
+        self,
+        api_filter: _Optional[_Union[APIFilter, _Mapping]] = ...,
+        logical_and: _Optional[_Union[LogicalAndFilters, _Mapping]] = ...,
+        logical_or: _Optional[_Union[LogicalOrFilters, _Mapping]] = ...,
+        logical_not: _Optional[_Union[BooleanTransactionFilter, _Mapping]] = ...,
+    ) -> None: ...
+
+class LogicalAndFilters(_message.Message):
+    __slots__ = ["filters"]
+    FILTERS_FIELD_NUMBER: _ClassVar[int]
+    filters: _containers.RepeatedCompositeFieldContainer[BooleanTransactionFilter]
+    def __init__(
+        self,
+        filters: _Optional[_Iterable[_Union[BooleanTransactionFilter, _Mapping]]] = ...,
+    ) -> None: ...
+
+class LogicalOrFilters(_message.Message):
+    __slots__ = ["filters"]
+    FILTERS_FIELD_NUMBER: _ClassVar[int]
+    filters: _containers.RepeatedCompositeFieldContainer[BooleanTransactionFilter]
+    def __init__(
+        self,
+        filters: _Optional[_Iterable[_Union[BooleanTransactionFilter, _Mapping]]] = ...,
+    ) -> None: ...
+
+class APIFilter(_message.Message):
+    __slots__ = [
+        "transaction_root_filter",
+        "user_transaction_filter",
+        "event_filter",
+        "write_set_change_filter",
+    ]
+    TRANSACTION_ROOT_FILTER_FIELD_NUMBER: _ClassVar[int]
+    USER_TRANSACTION_FILTER_FIELD_NUMBER: _ClassVar[int]
+    EVENT_FILTER_FIELD_NUMBER: _ClassVar[int]
+    WRITE_SET_CHANGE_FILTER_FIELD_NUMBER: _ClassVar[int]
+    transaction_root_filter: TransactionRootFilter
+    user_transaction_filter: UserTransactionFilter
+    event_filter: EventFilter
+    write_set_change_filter: WriteSetChangeFilter
+    def __init__(
+        self,
+        transaction_root_filter: _Optional[
+            _Union[TransactionRootFilter, _Mapping]
+        ] = ...,
+        user_transaction_filter: _Optional[
+            _Union[UserTransactionFilter, _Mapping]
+        ] = ...,
+        event_filter: _Optional[_Union[EventFilter, _Mapping]] = ...,
+        write_set_change_filter: _Optional[
+            _Union[WriteSetChangeFilter, _Mapping]
+        ] = ...,
+    ) -> None: ...
+
+class TransactionRootFilter(_message.Message):
+    __slots__ = ["success", "transaction_type"]
+    SUCCESS_FIELD_NUMBER: _ClassVar[int]
+    TRANSACTION_TYPE_FIELD_NUMBER: _ClassVar[int]
+    success: bool
+    transaction_type: _transaction_pb2.Transaction.TransactionType
+    def __init__(
+        self,
+        success: _Optional[bool] = ...,
+        transaction_type: _Optional[
+            _Union[_transaction_pb2.Transaction.TransactionType, str]
+        ] = ...,
+    ) -> None: ...
+
+class UserTransactionFilter(_message.Message):
+    __slots__ = ["sender", "entry_function"]
+    SENDER_FIELD_NUMBER: _ClassVar[int]
+    ENTRY_FUNCTION_FIELD_NUMBER: _ClassVar[int]
+    sender: str
+    entry_function: EntryFunctionFilter
+    def __init__(
+        self,
+        sender: _Optional[str] = ...,
+        entry_function: _Optional[_Union[EntryFunctionFilter, _Mapping]] = ...,
+    ) -> None: ...
+
+class EntryFunctionFilter(_message.Message):
+    __slots__ = ["address", "module_name", "function"]
+    ADDRESS_FIELD_NUMBER: _ClassVar[int]
+    MODULE_NAME_FIELD_NUMBER: _ClassVar[int]
+    FUNCTION_FIELD_NUMBER: _ClassVar[int]
+    address: str
+    module_name: str
+    function: str
+    def __init__(
+        self,
+        address: _Optional[str] = ...,
+        module_name: _Optional[str] = ...,
+        function: _Optional[str] = ...,
+    ) -> None: ...
+
+class EventFilter(_message.Message):
+    __slots__ = ["struct_type"]
+    STRUCT_TYPE_FIELD_NUMBER: _ClassVar[int]
+    struct_type: MoveStructTagFilter
+    def __init__(
+        self, struct_type: _Optional[_Union[MoveStructTagFilter, _Mapping]] = ...
+    ) -> None: ...
+
+class WriteSetChangeFilter(_message.Message):
+    __slots__ = ["resource_type"]
+    RESOURCE_TYPE_FIELD_NUMBER: _ClassVar[int]
+    resource_type: MoveStructTagFilter
+    def __init__(
+        self, resource_type: _Optional[_Union[MoveStructTagFilter, _Mapping]] = ...
+    ) -> None: ...
+
+class MoveStructTagFilter(_message.Message):
+    __slots__ = ["address", "module", "name"]
+    ADDRESS_FIELD_NUMBER: _ClassVar[int]
+    MODULE_FIELD_NUMBER: _ClassVar[int]
+    NAME_FIELD_NUMBER: _ClassVar[int]
+    address: str
+    module: str
+    name: str
+    def __init__(
+        self,
+        address: _Optional[str] = ...,
+        module: _Optional[str] = ...,
+        name: _Optional[str] = ...,
+    ) -> None: ...
+
+class ProcessedRange(_message.Message):
+    __slots__ = ["first_version", "last_version"]
+    FIRST_VERSION_FIELD_NUMBER: _ClassVar[int]
+    LAST_VERSION_FIELD_NUMBER: _ClassVar[int]
+    first_version: int
+    last_version: int
+    def __init__(
+        self, first_version: _Optional[int] = ..., last_version: _Optional[int] = ...
     ) -> None: ...
 
 class TransactionsResponse(_message.Message):
-    __slots__ = ["transactions", "chain_id"]
+    __slots__ = ["transactions", "chain_id", "processed_range"]
     TRANSACTIONS_FIELD_NUMBER: _ClassVar[int]
     CHAIN_ID_FIELD_NUMBER: _ClassVar[int]
+    PROCESSED_RANGE_FIELD_NUMBER: _ClassVar[int]
     transactions: _containers.RepeatedCompositeFieldContainer[
         _transaction_pb2.Transaction
     ]
     chain_id: int
+    processed_range: ProcessedRange
     def __init__(
         self,
         transactions: _Optional[
             _Iterable[_Union[_transaction_pb2.Transaction, _Mapping]]
         ] = ...,
         chain_id: _Optional[int] = ...,
+        processed_range: _Optional[_Union[ProcessedRange, _Mapping]] = ...,
     ) -> None: ...
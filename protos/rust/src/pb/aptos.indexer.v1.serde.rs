@@ -2,6 +2,497 @@
 // SPDX-License-Identifier: Apache-2.0
 
 // @generated
+impl serde::Serialize for ApiFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.filter.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.APIFilter", len)?;
+        if let Some(v) = self.filter.as_ref() {
+            match v {
+                api_filter::Filter::TransactionRootFilter(v) => {
+                    struct_ser.serialize_field("transactionRootFilter", v)?;
+                }
+                api_filter::Filter::UserTransactionFilter(v) => {
+                    struct_ser.serialize_field("userTransactionFilter", v)?;
+                }
+                api_filter::Filter::EventFilter(v) => {
+                    struct_ser.serialize_field("eventFilter", v)?;
+                }
+                api_filter::Filter::WriteSetChangeFilter(v) => {
+                    struct_ser.serialize_field("writeSetChangeFilter", v)?;
+                }
+            }
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ApiFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "transaction_root_filter",
+            "transactionRootFilter",
+            "user_transaction_filter",
+            "userTransactionFilter",
+            "event_filter",
+            "eventFilter",
+            "write_set_change_filter",
+            "writeSetChangeFilter",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            TransactionRootFilter,
+            UserTransactionFilter,
+            EventFilter,
+            WriteSetChangeFilter,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "transactionRootFilter" | "transaction_root_filter" => Ok(GeneratedField::TransactionRootFilter),
+                            "userTransactionFilter" | "user_transaction_filter" => Ok(GeneratedField::UserTransactionFilter),
+                            "eventFilter" | "event_filter" => Ok(GeneratedField::EventFilter),
+                            "writeSetChangeFilter" | "write_set_change_filter" => Ok(GeneratedField::WriteSetChangeFilter),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ApiFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.APIFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ApiFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut filter__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::TransactionRootFilter => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactionRootFilter"));
+                            }
+                            filter__ = map.next_value::<::std::option::Option<_>>()?.map(api_filter::Filter::TransactionRootFilter);
+                        }
+                        GeneratedField::UserTransactionFilter => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("userTransactionFilter"));
+                            }
+                            filter__ = map.next_value::<::std::option::Option<_>>()?.map(api_filter::Filter::UserTransactionFilter);
+                        }
+                        GeneratedField::EventFilter => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("eventFilter"));
+                            }
+                            filter__ = map.next_value::<::std::option::Option<_>>()?.map(api_filter::Filter::EventFilter);
+                        }
+                        GeneratedField::WriteSetChangeFilter => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("writeSetChangeFilter"));
+                            }
+                            filter__ = map.next_value::<::std::option::Option<_>>()?.map(api_filter::Filter::WriteSetChangeFilter);
+                        }
+                    }
+                }
+                Ok(ApiFilter {
+                    filter: filter__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.APIFilter", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for BooleanTransactionFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.filter.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.BooleanTransactionFilter", len)?;
+        if let Some(v) = self.filter.as_ref() {
+            match v {
+                boolean_transaction_filter::Filter::ApiFilter(v) => {
+                    struct_ser.serialize_field("apiFilter", v)?;
+                }
+                boolean_transaction_filter::Filter::LogicalAnd(v) => {
+                    struct_ser.serialize_field("logicalAnd", v)?;
+                }
+                boolean_transaction_filter::Filter::LogicalOr(v) => {
+                    struct_ser.serialize_field("logicalOr", v)?;
+                }
+                boolean_transaction_filter::Filter::LogicalNot(v) => {
+                    struct_ser.serialize_field("logicalNot", v)?;
+                }
+            }
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for BooleanTransactionFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "api_filter",
+            "apiFilter",
+            "logical_and",
+            "logicalAnd",
+            "logical_or",
+            "logicalOr",
+            "logical_not",
+            "logicalNot",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            ApiFilter,
+            LogicalAnd,
+            LogicalOr,
+            LogicalNot,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "apiFilter" | "api_filter" => Ok(GeneratedField::ApiFilter),
+                            "logicalAnd" | "logical_and" => Ok(GeneratedField::LogicalAnd),
+                            "logicalOr" | "logical_or" => Ok(GeneratedField::LogicalOr),
+                            "logicalNot" | "logical_not" => Ok(GeneratedField::LogicalNot),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = BooleanTransactionFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.BooleanTransactionFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<BooleanTransactionFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut filter__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::ApiFilter => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("apiFilter"));
+                            }
+                            filter__ = map.next_value::<::std::option::Option<_>>()?.map(boolean_transaction_filter::Filter::ApiFilter);
+                        }
+                        GeneratedField::LogicalAnd => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("logicalAnd"));
+                            }
+                            filter__ = map.next_value::<::std::option::Option<_>>()?.map(boolean_transaction_filter::Filter::LogicalAnd)
+;
+                        }
+                        GeneratedField::LogicalOr => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("logicalOr"));
+                            }
+                            filter__ = map.next_value::<::std::option::Option<_>>()?.map(boolean_transaction_filter::Filter::LogicalOr)
+;
+                        }
+                        GeneratedField::LogicalNot => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("logicalNot"));
+                            }
+                            filter__ = map.next_value::<::std::option::Option<_>>()?.map(boolean_transaction_filter::Filter::LogicalNot)
+;
+                        }
+                    }
+                }
+                Ok(BooleanTransactionFilter {
+                    filter: filter__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.BooleanTransactionFilter", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for EntryFunctionFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.address.is_some() {
+            len += 1;
+        }
+        if self.module_name.is_some() {
+            len += 1;
+        }
+        if self.function.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.EntryFunctionFilter", len)?;
+        if let Some(v) = self.address.as_ref() {
+            struct_ser.serialize_field("address", v)?;
+        }
+        if let Some(v) = self.module_name.as_ref() {
+            struct_ser.serialize_field("moduleName", v)?;
+        }
+        if let Some(v) = self.function.as_ref() {
+            struct_ser.serialize_field("function", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for EntryFunctionFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "address",
+            "module_name",
+            "moduleName",
+            "function",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Address,
+            ModuleName,
+            Function,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "address" => Ok(GeneratedField::Address),
+                            "moduleName" | "module_name" => Ok(GeneratedField::ModuleName),
+                            "function" => Ok(GeneratedField::Function),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = EntryFunctionFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.EntryFunctionFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<EntryFunctionFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut address__ = None;
+                let mut module_name__ = None;
+                let mut function__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Address => {
+                            if address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("address"));
+                            }
+                            address__ = map.next_value()?;
+                        }
+                        GeneratedField::ModuleName => {
+                            if module_name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("moduleName"));
+                            }
+                            module_name__ = map.next_value()?;
+                        }
+                        GeneratedField::Function => {
+                            if function__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("function"));
+                            }
+                            function__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(EntryFunctionFilter {
+                    address: address__,
+                    module_name: module_name__,
+                    function: function__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.EntryFunctionFilter", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for EventFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.struct_type.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.EventFilter", len)?;
+        if let Some(v) = self.struct_type.as_ref() {
+            struct_ser.serialize_field("structType", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for EventFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "struct_type",
+            "structType",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            StructType,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "structType" | "struct_type" => Ok(GeneratedField::StructType),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = EventFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.EventFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<EventFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut struct_type__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::StructType => {
+                            if struct_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("structType"));
+                            }
+                            struct_type__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(EventFilter {
+                    struct_type: struct_type__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.EventFilter", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for GetTransactionsRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -10,48 +501,613 @@ impl serde::Serialize for GetTransactionsRequest {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if self.starting_version.is_some() {
+        if self.starting_version.is_some() {
+            len += 1;
+        }
+        if self.transactions_count.is_some() {
+            len += 1;
+        }
+        if self.batch_size.is_some() {
+            len += 1;
+        }
+        if self.transaction_filter.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.GetTransactionsRequest", len)?;
+        if let Some(v) = self.starting_version.as_ref() {
+            struct_ser.serialize_field("startingVersion", ToString::to_string(&v).as_str())?;
+        }
+        if let Some(v) = self.transactions_count.as_ref() {
+            struct_ser.serialize_field("transactionsCount", ToString::to_string(&v).as_str())?;
+        }
+        if let Some(v) = self.batch_size.as_ref() {
+            struct_ser.serialize_field("batchSize", ToString::to_string(&v).as_str())?;
+        }
+        if let Some(v) = self.transaction_filter.as_ref() {
+            struct_ser.serialize_field("transactionFilter", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetTransactionsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "starting_version",
+            "startingVersion",
+            "transactions_count",
+            "transactionsCount",
+            "batch_size",
+            "batchSize",
+            "transaction_filter",
+            "transactionFilter",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            StartingVersion,
+            TransactionsCount,
+            BatchSize,
+            TransactionFilter,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "startingVersion" | "starting_version" => Ok(GeneratedField::StartingVersion),
+                            "transactionsCount" | "transactions_count" => Ok(GeneratedField::TransactionsCount),
+                            "batchSize" | "batch_size" => Ok(GeneratedField::BatchSize),
+                            "transactionFilter" | "transaction_filter" => Ok(GeneratedField::TransactionFilter),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetTransactionsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.GetTransactionsRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetTransactionsRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut starting_version__ = None;
+                let mut transactions_count__ = None;
+                let mut batch_size__ = None;
+                let mut transaction_filter__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::StartingVersion => {
+                            if starting_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("startingVersion"));
+                            }
+                            starting_version__ =
+                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::TransactionsCount => {
+                            if transactions_count__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactionsCount"));
+                            }
+                            transactions_count__ =
+                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::BatchSize => {
+                            if batch_size__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("batchSize"));
+                            }
+                            batch_size__ =
+                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::TransactionFilter => {
+                            if transaction_filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactionFilter"));
+                            }
+                            transaction_filter__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(GetTransactionsRequest {
+                    starting_version: starting_version__,
+                    transactions_count: transactions_count__,
+                    batch_size: batch_size__,
+                    transaction_filter: transaction_filter__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.GetTransactionsRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for LogicalAndFilters {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.filters.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.LogicalAndFilters", len)?;
+        if !self.filters.is_empty() {
+            struct_ser.serialize_field("filters", &self.filters)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for LogicalAndFilters {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "filters",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Filters,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "filters" => Ok(GeneratedField::Filters),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = LogicalAndFilters;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.LogicalAndFilters")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<LogicalAndFilters, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut filters__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Filters => {
+                            if filters__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("filters"));
+                            }
+                            filters__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(LogicalAndFilters {
+                    filters: filters__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.LogicalAndFilters", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for LogicalOrFilters {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.filters.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.LogicalOrFilters", len)?;
+        if !self.filters.is_empty() {
+            struct_ser.serialize_field("filters", &self.filters)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for LogicalOrFilters {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "filters",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Filters,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "filters" => Ok(GeneratedField::Filters),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = LogicalOrFilters;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.LogicalOrFilters")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<LogicalOrFilters, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut filters__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Filters => {
+                            if filters__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("filters"));
+                            }
+                            filters__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(LogicalOrFilters {
+                    filters: filters__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.LogicalOrFilters", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for MoveStructTagFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.address.is_some() {
+            len += 1;
+        }
+        if self.module.is_some() {
+            len += 1;
+        }
+        if self.name.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.MoveStructTagFilter", len)?;
+        if let Some(v) = self.address.as_ref() {
+            struct_ser.serialize_field("address", v)?;
+        }
+        if let Some(v) = self.module.as_ref() {
+            struct_ser.serialize_field("module", v)?;
+        }
+        if let Some(v) = self.name.as_ref() {
+            struct_ser.serialize_field("name", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for MoveStructTagFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "address",
+            "module",
+            "name",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Address,
+            Module,
+            Name,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "address" => Ok(GeneratedField::Address),
+                            "module" => Ok(GeneratedField::Module),
+                            "name" => Ok(GeneratedField::Name),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = MoveStructTagFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.MoveStructTagFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<MoveStructTagFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut address__ = None;
+                let mut module__ = None;
+                let mut name__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Address => {
+                            if address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("address"));
+                            }
+                            address__ = map.next_value()?;
+                        }
+                        GeneratedField::Module => {
+                            if module__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("module"));
+                            }
+                            module__ = map.next_value()?;
+                        }
+                        GeneratedField::Name => {
+                            if name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(MoveStructTagFilter {
+                    address: address__,
+                    module: module__,
+                    name: name__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.MoveStructTagFilter", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ProcessedRange {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.first_version != 0 {
+            len += 1;
+        }
+        if self.last_version != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.ProcessedRange", len)?;
+        if self.first_version != 0 {
+            struct_ser.serialize_field("firstVersion", ToString::to_string(&self.first_version).as_str())?;
+        }
+        if self.last_version != 0 {
+            struct_ser.serialize_field("lastVersion", ToString::to_string(&self.last_version).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ProcessedRange {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "first_version",
+            "firstVersion",
+            "last_version",
+            "lastVersion",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            FirstVersion,
+            LastVersion,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "firstVersion" | "first_version" => Ok(GeneratedField::FirstVersion),
+                            "lastVersion" | "last_version" => Ok(GeneratedField::LastVersion),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ProcessedRange;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.ProcessedRange")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ProcessedRange, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut first_version__ = None;
+                let mut last_version__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::FirstVersion => {
+                            if first_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("firstVersion"));
+                            }
+                            first_version__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::LastVersion => {
+                            if last_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("lastVersion"));
+                            }
+                            last_version__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(ProcessedRange {
+                    first_version: first_version__.unwrap_or_default(),
+                    last_version: last_version__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.ProcessedRange", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for TransactionRootFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.success.is_some() {
             len += 1;
         }
-        if self.transactions_count.is_some() {
-            len += 1;
-        }
-        if self.batch_size.is_some() {
+        if self.transaction_type.is_some() {
             len += 1;
         }
-        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.GetTransactionsRequest", len)?;
-        if let Some(v) = self.starting_version.as_ref() {
-            struct_ser.serialize_field("startingVersion", ToString::to_string(&v).as_str())?;
-        }
-        if let Some(v) = self.transactions_count.as_ref() {
-            struct_ser.serialize_field("transactionsCount", ToString::to_string(&v).as_str())?;
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.TransactionRootFilter", len)?;
+        if let Some(v) = self.success.as_ref() {
+            struct_ser.serialize_field("success", v)?;
         }
-        if let Some(v) = self.batch_size.as_ref() {
-            struct_ser.serialize_field("batchSize", ToString::to_string(&v).as_str())?;
+        if let Some(v) = self.transaction_type.as_ref() {
+            let v = super::super::transaction::v1::transaction::TransactionType::from_i32(*v)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", *v)))?;
+            struct_ser.serialize_field("transactionType", &v)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for GetTransactionsRequest {
+impl<'de> serde::Deserialize<'de> for TransactionRootFilter {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         const FIELDS: &[&str] = &[
-            "starting_version",
-            "startingVersion",
-            "transactions_count",
-            "transactionsCount",
-            "batch_size",
-            "batchSize",
+            "success",
+            "transaction_type",
+            "transactionType",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            StartingVersion,
-            TransactionsCount,
-            BatchSize,
+            Success,
+            TransactionType,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -73,9 +1129,8 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsRequest {
                         E: serde::de::Error,
                     {
                         match value {
-                            "startingVersion" | "starting_version" => Ok(GeneratedField::StartingVersion),
-                            "transactionsCount" | "transactions_count" => Ok(GeneratedField::TransactionsCount),
-                            "batchSize" | "batch_size" => Ok(GeneratedField::BatchSize),
+                            "success" => Ok(GeneratedField::Success),
+                            "transactionType" | "transaction_type" => Ok(GeneratedField::TransactionType),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -85,55 +1140,41 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsRequest {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = GetTransactionsRequest;
+            type Value = TransactionRootFilter;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct aptos.indexer.v1.GetTransactionsRequest")
+                formatter.write_str("struct aptos.indexer.v1.TransactionRootFilter")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetTransactionsRequest, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<TransactionRootFilter, V::Error>
                 where
                     V: serde::de::MapAccess<'de>,
             {
-                let mut starting_version__ = None;
-                let mut transactions_count__ = None;
-                let mut batch_size__ = None;
+                let mut success__ = None;
+                let mut transaction_type__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::StartingVersion => {
-                            if starting_version__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("startingVersion"));
-                            }
-                            starting_version__ =
-                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
-                            ;
-                        }
-                        GeneratedField::TransactionsCount => {
-                            if transactions_count__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("transactionsCount"));
+                        GeneratedField::Success => {
+                            if success__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("success"));
                             }
-                            transactions_count__ =
-                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
-                            ;
+                            success__ = map.next_value()?;
                         }
-                        GeneratedField::BatchSize => {
-                            if batch_size__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("batchSize"));
+                        GeneratedField::TransactionType => {
+                            if transaction_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactionType"));
                             }
-                            batch_size__ =
-                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
-                            ;
+                            transaction_type__ = map.next_value::<::std::option::Option<super::super::transaction::v1::transaction::TransactionType>>()?.map(|x| x as i32);
                         }
                     }
                 }
-                Ok(GetTransactionsRequest {
-                    starting_version: starting_version__,
-                    transactions_count: transactions_count__,
-                    batch_size: batch_size__,
+                Ok(TransactionRootFilter {
+                    success: success__,
+                    transaction_type: transaction_type__,
                 })
             }
         }
-        deserializer.deserialize_struct("aptos.indexer.v1.GetTransactionsRequest", FIELDS, GeneratedVisitor)
+        deserializer.deserialize_struct("aptos.indexer.v1.TransactionRootFilter", FIELDS, GeneratedVisitor)
     }
 }
 impl serde::Serialize for TransactionsInStorage {
@@ -261,6 +1302,9 @@ impl serde::Serialize for TransactionsResponse {
         if self.chain_id.is_some() {
             len += 1;
         }
+        if self.processed_range.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.TransactionsResponse", len)?;
         if !self.transactions.is_empty() {
             struct_ser.serialize_field("transactions", &self.transactions)?;
@@ -268,6 +1312,9 @@ impl serde::Serialize for TransactionsResponse {
         if let Some(v) = self.chain_id.as_ref() {
             struct_ser.serialize_field("chainId", ToString::to_string(&v).as_str())?;
         }
+        if let Some(v) = self.processed_range.as_ref() {
+            struct_ser.serialize_field("processedRange", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -281,12 +1328,15 @@ impl<'de> serde::Deserialize<'de> for TransactionsResponse {
             "transactions",
             "chain_id",
             "chainId",
+            "processed_range",
+            "processedRange",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Transactions,
             ChainId,
+            ProcessedRange,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -310,6 +1360,7 @@ impl<'de> serde::Deserialize<'de> for TransactionsResponse {
                         match value {
                             "transactions" => Ok(GeneratedField::Transactions),
                             "chainId" | "chain_id" => Ok(GeneratedField::ChainId),
+                            "processedRange" | "processed_range" => Ok(GeneratedField::ProcessedRange),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -331,6 +1382,7 @@ impl<'de> serde::Deserialize<'de> for TransactionsResponse {
             {
                 let mut transactions__ = None;
                 let mut chain_id__ = None;
+                let mut processed_range__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
                         GeneratedField::Transactions => {
@@ -347,14 +1399,222 @@ impl<'de> serde::Deserialize<'de> for TransactionsResponse {
                                 map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
                             ;
                         }
+                        GeneratedField::ProcessedRange => {
+                            if processed_range__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("processedRange"));
+                            }
+                            processed_range__ = map.next_value()?;
+                        }
                     }
                 }
                 Ok(TransactionsResponse {
                     transactions: transactions__.unwrap_or_default(),
                     chain_id: chain_id__,
+                    processed_range: processed_range__,
                 })
             }
         }
         deserializer.deserialize_struct("aptos.indexer.v1.TransactionsResponse", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for UserTransactionFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.sender.is_some() {
+            len += 1;
+        }
+        if self.entry_function.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.UserTransactionFilter", len)?;
+        if let Some(v) = self.sender.as_ref() {
+            struct_ser.serialize_field("sender", v)?;
+        }
+        if let Some(v) = self.entry_function.as_ref() {
+            struct_ser.serialize_field("entryFunction", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for UserTransactionFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "sender",
+            "entry_function",
+            "entryFunction",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Sender,
+            EntryFunction,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "sender" => Ok(GeneratedField::Sender),
+                            "entryFunction" | "entry_function" => Ok(GeneratedField::EntryFunction),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = UserTransactionFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.UserTransactionFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<UserTransactionFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut sender__ = None;
+                let mut entry_function__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Sender => {
+                            if sender__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sender"));
+                            }
+                            sender__ = map.next_value()?;
+                        }
+                        GeneratedField::EntryFunction => {
+                            if entry_function__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("entryFunction"));
+                            }
+                            entry_function__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(UserTransactionFilter {
+                    sender: sender__,
+                    entry_function: entry_function__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.UserTransactionFilter", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for WriteSetChangeFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.resource_type.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.WriteSetChangeFilter", len)?;
+        if let Some(v) = self.resource_type.as_ref() {
+            struct_ser.serialize_field("resourceType", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for WriteSetChangeFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "resource_type",
+            "resourceType",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            ResourceType,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "resourceType" | "resource_type" => Ok(GeneratedField::ResourceType),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = WriteSetChangeFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.indexer.v1.WriteSetChangeFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<WriteSetChangeFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut resource_type__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::ResourceType => {
+                            if resource_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resourceType"));
+                            }
+                            resource_type__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(WriteSetChangeFilter {
+                    resource_type: resource_type__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.indexer.v1.WriteSetChangeFilter", FIELDS, GeneratedVisitor)
+    }
+}
@@ -10,7 +10,12 @@ import {
 import type { CallOptions, ClientOptions, UntypedServiceImplementation } from "@grpc/grpc-js";
 import Long from "long";
 import _m0 from "protobufjs/minimal";
-import { Transaction } from "../../transaction/v1/transaction";
+import {
+  Transaction,
+  Transaction_TransactionType,
+  transaction_TransactionTypeFromJSON,
+  transaction_TransactionTypeToJSON,
+} from "../../transaction/v1/transaction";
 
 /** This is for storage only. */
 export interface TransactionsInStorage {
@@ -38,7 +43,81 @@ export interface GetTransactionsRequest {
    * Optional; number of transactions in each `TransactionsResponse` for current stream.
    * If not present, default to 1000. If larger than 1000, request will be rejected.
    */
-  batchSize?: bigint | undefined;
+  batchSize?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; if present, only transactions matching the filter are returned. Note that
+   * `transactions_count` still counts all transactions, including the filtered out ones.
+   */
+  transactionFilter?: BooleanTransactionFilter | undefined;
+}
+
+/** A filter composed of other filters with boolean logic. */
+export interface BooleanTransactionFilter {
+  apiFilter?: APIFilter | undefined;
+  logicalAnd?: LogicalAndFilters | undefined;
+  logicalOr?: LogicalOrFilters | undefined;
+  logicalNot?: BooleanTransactionFilter | undefined;
+}
+
+/** Matches if all of the filters match. */
+export interface LogicalAndFilters {
+  filters?: BooleanTransactionFilter[] | undefined;
+}
+
+/** Matches if any of the filters matches. */
+export interface LogicalOrFilters {
+  filters?: BooleanTransactionFilter[] | undefined;
+}
+
+export interface APIFilter {
+  transactionRootFilter?: TransactionRootFilter | undefined;
+  userTransactionFilter?: UserTransactionFilter | undefined;
+  eventFilter?: EventFilter | undefined;
+  writeSetChangeFilter?: WriteSetChangeFilter | undefined;
+}
+
+/** Filters on the transaction itself. Unset fields match everything. */
+export interface TransactionRootFilter {
+  success?: boolean | undefined;
+  transactionType?: Transaction_TransactionType | undefined;
+}
+
+/** Filters on user transactions, never matches other transactions. Unset fields match everything. */
+export interface UserTransactionFilter {
+  sender?: string | undefined;
+  entryFunction?: EntryFunctionFilter | undefined;
+}
+
+/** Matches the entry function called, directly or through a multisig account. */
+export interface EntryFunctionFilter {
+  address?: string | undefined;
+  moduleName?: string | undefined;
+  function?: string | undefined;
+}
+
+/** Matches if any event emitted by the transaction matches. */
+export interface EventFilter {
+  structType?: MoveStructTagFilter | undefined;
+}
+
+/** Matches if any resource written or deleted by the transaction matches. */
+export interface WriteSetChangeFilter {
+  resourceType?: MoveStructTagFilter | undefined;
+}
+
+/** Matches a struct type, ignoring generic type parameters. Unset fields match everything. */
+export interface MoveStructTagFilter {
+  address?: string | undefined;
+  module?: string | undefined;
+  name?: string | undefined;
+}
+
+/** The range of versions a response covers, including the filtered out transactions. */
+export interface ProcessedRange {
+  firstVersion?: bigint | undefined;
+  lastVersion?: bigint | undefined;
 }
 
 /** TransactionsResponse is a batch of transactions. */
@@ -48,7 +127,14 @@ export interface TransactionsResponse {
     | Transaction[]
     | undefined;
   /** Required; chain id. */
-  chainId?: bigint | undefined;
+  chainId?:
+    | bigint
+    | undefined;
+  /**
+   * Required; the range of versions covered by this response. With a transaction filter,
+   * this can be set while `transactions` is empty, so clients can keep track of progress.
+   */
+  processedRange?: ProcessedRange | undefined;
 }
 
 function createBaseTransactionsInStorage(): TransactionsInStorage {
@@ -167,7 +253,12 @@ export const TransactionsInStorage = {
 };
 
 function createBaseGetTransactionsRequest(): GetTransactionsRequest {
-  return { startingVersion: undefined, transactionsCount: undefined, batchSize: undefined };
+  return {
+    startingVersion: undefined,
+    transactionsCount: undefined,
+    batchSize: undefined,
+    transactionFilter: undefined,
+  };
 }
 
 export const GetTransactionsRequest = {
@@ -190,6 +281,9 @@ export const GetTransactionsRequest = {
       }
       writer.uint32(24).uint64(message.batchSize.toString());
     }
+    if (message.transactionFilter !== undefined) {
+      BooleanTransactionFilter.encode(message.transactionFilter, writer.uint32(34).fork()).ldelim();
+    }
     return writer;
   },
 
@@ -221,6 +315,13 @@ export const GetTransactionsRequest = {
 
           message.batchSize = longToBigint(reader.uint64() as Long);
           continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.transactionFilter = BooleanTransactionFilter.decode(reader, reader.uint32());
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -269,6 +370,9 @@ export const GetTransactionsRequest = {
       startingVersion: isSet(object.startingVersion) ? BigInt(object.startingVersion) : undefined,
       transactionsCount: isSet(object.transactionsCount) ? BigInt(object.transactionsCount) : undefined,
       batchSize: isSet(object.batchSize) ? BigInt(object.batchSize) : undefined,
+      transactionFilter: isSet(object.transactionFilter)
+        ? BooleanTransactionFilter.fromJSON(object.transactionFilter)
+        : undefined,
     };
   },
 
@@ -283,6 +387,9 @@ export const GetTransactionsRequest = {
     if (message.batchSize !== undefined) {
       obj.batchSize = message.batchSize.toString();
     }
+    if (message.transactionFilter !== undefined) {
+      obj.transactionFilter = BooleanTransactionFilter.toJSON(message.transactionFilter);
+    }
     return obj;
   },
 
@@ -294,34 +401,38 @@ export const GetTransactionsRequest = {
     message.startingVersion = object.startingVersion ?? undefined;
     message.transactionsCount = object.transactionsCount ?? undefined;
     message.batchSize = object.batchSize ?? undefined;
+    message.transactionFilter = (object.transactionFilter !== undefined && object.transactionFilter !== null)
+      ? BooleanTransactionFilter.fromPartial(object.transactionFilter)
+      : undefined;
     return message;
   },
 };
 
-function createBaseTransactionsResponse(): TransactionsResponse {
-  return { transactions: [], chainId: undefined };
+function createBaseBooleanTransactionFilter(): BooleanTransactionFilter {
+  return { apiFilter: undefined, logicalAnd: undefined, logicalOr: undefined, logicalNot: undefined };
 }
 
-export const TransactionsResponse = {
-  encode(message: TransactionsResponse, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
-    if (message.transactions !== undefined && message.transactions.length !== 0) {
-      for (const v of message.transactions) {
-        Transaction.encode(v!, writer.uint32(10).fork()).ldelim();
-      }
+export const BooleanTransactionFilter = {
+  encode(message: BooleanTransactionFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.apiFilter !== undefined) {
+      APIFilter.encode(message.apiFilter, writer.uint32(10).fork()).ldelim();
     }
-    if (message.chainId !== undefined) {
-      if (BigInt.asUintN(64, message.chainId) !== message.chainId) {
-        throw new globalThis.Error("value provided for field message.chainId of type uint64 too large");
-      }
-      writer.uint32(16).uint64(message.chainId.toString());
+    if (message.logicalAnd !== undefined) {
+      LogicalAndFilters.encode(message.logicalAnd, writer.uint32(18).fork()).ldelim();
+    }
+    if (message.logicalOr !== undefined) {
+      LogicalOrFilters.encode(message.logicalOr, writer.uint32(26).fork()).ldelim();
+    }
+    if (message.logicalNot !== undefined) {
+      BooleanTransactionFilter.encode(message.logicalNot, writer.uint32(34).fork()).ldelim();
     }
     return writer;
   },
 
-  decode(input: _m0.Reader | Uint8Array, length?: number): TransactionsResponse {
+  decode(input: _m0.Reader | Uint8Array, length?: number): BooleanTransactionFilter {
     const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
     let end = length === undefined ? reader.len : reader.pos + length;
-    const message = createBaseTransactionsResponse();
+    const message = createBaseBooleanTransactionFilter();
     while (reader.pos < end) {
       const tag = reader.uint32();
       switch (tag >>> 3) {
@@ -330,14 +441,28 @@ export const TransactionsResponse = {
             break;
           }
 
-          message.transactions!.push(Transaction.decode(reader, reader.uint32()));
+          message.apiFilter = APIFilter.decode(reader, reader.uint32());
           continue;
         case 2:
-          if (tag !== 16) {
+          if (tag !== 18) {
             break;
           }
 
-          message.chainId = longToBigint(reader.uint64() as Long);
+          message.logicalAnd = LogicalAndFilters.decode(reader, reader.uint32());
+          continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.logicalOr = LogicalOrFilters.decode(reader, reader.uint32());
+          continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.logicalNot = BooleanTransactionFilter.decode(reader, reader.uint32());
           continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
@@ -349,91 +474,1361 @@ export const TransactionsResponse = {
   },
 
   // encodeTransform encodes a source of message objects.
-  // Transform<TransactionsResponse, Uint8Array>
+  // Transform<BooleanTransactionFilter, Uint8Array>
   async *encodeTransform(
     source:
-      | AsyncIterable<TransactionsResponse | TransactionsResponse[]>
-      | Iterable<TransactionsResponse | TransactionsResponse[]>,
+      | AsyncIterable<BooleanTransactionFilter | BooleanTransactionFilter[]>
+      | Iterable<BooleanTransactionFilter | BooleanTransactionFilter[]>,
   ): AsyncIterable<Uint8Array> {
     for await (const pkt of source) {
       if (globalThis.Array.isArray(pkt)) {
         for (const p of (pkt as any)) {
-          yield* [TransactionsResponse.encode(p).finish()];
+          yield* [BooleanTransactionFilter.encode(p).finish()];
         }
       } else {
-        yield* [TransactionsResponse.encode(pkt as any).finish()];
+        yield* [BooleanTransactionFilter.encode(pkt as any).finish()];
       }
     }
   },
 
   // decodeTransform decodes a source of encoded messages.
-  // Transform<Uint8Array, TransactionsResponse>
+  // Transform<Uint8Array, BooleanTransactionFilter>
   async *decodeTransform(
     source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
-  ): AsyncIterable<TransactionsResponse> {
+  ): AsyncIterable<BooleanTransactionFilter> {
     for await (const pkt of source) {
       if (globalThis.Array.isArray(pkt)) {
         for (const p of (pkt as any)) {
-          yield* [TransactionsResponse.decode(p)];
+          yield* [BooleanTransactionFilter.decode(p)];
         }
       } else {
-        yield* [TransactionsResponse.decode(pkt as any)];
+        yield* [BooleanTransactionFilter.decode(pkt as any)];
       }
     }
   },
 
-  fromJSON(object: any): TransactionsResponse {
+  fromJSON(object: any): BooleanTransactionFilter {
     return {
-      transactions: globalThis.Array.isArray(object?.transactions)
-        ? object.transactions.map((e: any) => Transaction.fromJSON(e))
-        : [],
-      chainId: isSet(object.chainId) ? BigInt(object.chainId) : undefined,
+      apiFilter: isSet(object.apiFilter) ? APIFilter.fromJSON(object.apiFilter) : undefined,
+      logicalAnd: isSet(object.logicalAnd) ? LogicalAndFilters.fromJSON(object.logicalAnd) : undefined,
+      logicalOr: isSet(object.logicalOr) ? LogicalOrFilters.fromJSON(object.logicalOr) : undefined,
+      logicalNot: isSet(object.logicalNot) ? BooleanTransactionFilter.fromJSON(object.logicalNot) : undefined,
     };
   },
 
-  toJSON(message: TransactionsResponse): unknown {
+  toJSON(message: BooleanTransactionFilter): unknown {
     const obj: any = {};
-    if (message.transactions?.length) {
-      obj.transactions = message.transactions.map((e) => Transaction.toJSON(e));
+    if (message.apiFilter !== undefined) {
+      obj.apiFilter = APIFilter.toJSON(message.apiFilter);
     }
-    if (message.chainId !== undefined) {
-      obj.chainId = message.chainId.toString();
+    if (message.logicalAnd !== undefined) {
+      obj.logicalAnd = LogicalAndFilters.toJSON(message.logicalAnd);
+    }
+    if (message.logicalOr !== undefined) {
+      obj.logicalOr = LogicalOrFilters.toJSON(message.logicalOr);
+    }
+    if (message.logicalNot !== undefined) {
+      obj.logicalNot = BooleanTransactionFilter.toJSON(message.logicalNot);
     }
     return obj;
   },
 
-  create(base?: DeepPartial<TransactionsResponse>): TransactionsResponse {
-    return TransactionsResponse.fromPartial(base ?? {});
+  create(base?: DeepPartial<BooleanTransactionFilter>): BooleanTransactionFilter {
+    return BooleanTransactionFilter.fromPartial(base ?? {});
   },
-  fromPartial(object: DeepPartial<TransactionsResponse>): TransactionsResponse {
-    const message = createBaseTransactionsResponse();
-    message.transactions = object.transactions?.map((e) => Transaction.fromPartial(e)) || [];
-    message.chainId = object.chainId ?? undefined;
+  fromPartial(object: DeepPartial<BooleanTransactionFilter>): BooleanTransactionFilter {
+    const message = createBaseBooleanTransactionFilter();
+    message.apiFilter = (object.apiFilter !== undefined && object.apiFilter !== null)
+      ? APIFilter.fromPartial(object.apiFilter)
+      : undefined;
+    message.logicalAnd = (object.logicalAnd !== undefined && object.logicalAnd !== null)
+      ? LogicalAndFilters.fromPartial(object.logicalAnd)
+      : undefined;
+    message.logicalOr = (object.logicalOr !== undefined && object.logicalOr !== null)
+      ? LogicalOrFilters.fromPartial(object.logicalOr)
+      : undefined;
+    message.logicalNot = (object.logicalNot !== undefined && object.logicalNot !== null)
+      ? BooleanTransactionFilter.fromPartial(object.logicalNot)
+      : undefined;
     return message;
   },
 };
 
-export type RawDataService = typeof RawDataService;
-export const RawDataService = {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
-  getTransactions: {
-    path: "/aptos.indexer.v1.RawData/GetTransactions",
-    requestStream: false,
-    responseStream: true,
-    requestSerialize: (value: GetTransactionsRequest) => Buffer.from(GetTransactionsRequest.encode(value).finish()),
-    requestDeserialize: (value: Buffer) => GetTransactionsRequest.decode(value),
-    responseSerialize: (value: TransactionsResponse) => Buffer.from(TransactionsResponse.encode(value).finish()),
-    responseDeserialize: (value: Buffer) => TransactionsResponse.decode(value),
+function createBaseLogicalAndFilters(): LogicalAndFilters {
+  return { filters: [] };
+}
+
+export const LogicalAndFilters = {
+  encode(message: LogicalAndFilters, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.filters !== undefined && message.filters.length !== 0) {
+      for (const v of message.filters) {
+        BooleanTransactionFilter.encode(v!, writer.uint32(10).fork()).ldelim();
+      }
+    }
+    return writer;
   },
-} as const;
 
-export interface RawDataServer extends UntypedServiceImplementation {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
-  getTransactions: handleServerStreamingCall<GetTransactionsRequest, TransactionsResponse>;
+  decode(input: _m0.Reader | Uint8Array, length?: number): LogicalAndFilters {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseLogicalAndFilters();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.filters!.push(BooleanTransactionFilter.decode(reader, reader.uint32()));
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<LogicalAndFilters, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<LogicalAndFilters | LogicalAndFilters[]> | Iterable<LogicalAndFilters | LogicalAndFilters[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [LogicalAndFilters.encode(p).finish()];
+        }
+      } else {
+        yield* [LogicalAndFilters.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, LogicalAndFilters>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<LogicalAndFilters> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [LogicalAndFilters.decode(p)];
+        }
+      } else {
+        yield* [LogicalAndFilters.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): LogicalAndFilters {
+    return {
+      filters: globalThis.Array.isArray(object?.filters)
+        ? object.filters.map((e: any) => BooleanTransactionFilter.fromJSON(e))
+        : [],
+    };
+  },
+
+  toJSON(message: LogicalAndFilters): unknown {
+    const obj: any = {};
+    if (message.filters?.length) {
+      obj.filters = message.filters.map((e) => BooleanTransactionFilter.toJSON(e));
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<LogicalAndFilters>): LogicalAndFilters {
+    return LogicalAndFilters.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<LogicalAndFilters>): LogicalAndFilters {
+    const message = createBaseLogicalAndFilters();
+    message.filters = object.filters?.map((e) => BooleanTransactionFilter.fromPartial(e)) || [];
+    return message;
+  },
+};
+
+function createBaseLogicalOrFilters(): LogicalOrFilters {
+  return { filters: [] };
 }
 
-export interface RawDataClient extends Client {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+export const LogicalOrFilters = {
+  encode(message: LogicalOrFilters, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.filters !== undefined && message.filters.length !== 0) {
+      for (const v of message.filters) {
+        BooleanTransactionFilter.encode(v!, writer.uint32(10).fork()).ldelim();
+      }
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): LogicalOrFilters {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseLogicalOrFilters();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.filters!.push(BooleanTransactionFilter.decode(reader, reader.uint32()));
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<LogicalOrFilters, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<LogicalOrFilters | LogicalOrFilters[]> | Iterable<LogicalOrFilters | LogicalOrFilters[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [LogicalOrFilters.encode(p).finish()];
+        }
+      } else {
+        yield* [LogicalOrFilters.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, LogicalOrFilters>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<LogicalOrFilters> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [LogicalOrFilters.decode(p)];
+        }
+      } else {
+        yield* [LogicalOrFilters.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): LogicalOrFilters {
+    return {
+      filters: globalThis.Array.isArray(object?.filters)
+        ? object.filters.map((e: any) => BooleanTransactionFilter.fromJSON(e))
+        : [],
+    };
+  },
+
+  toJSON(message: LogicalOrFilters): unknown {
+    const obj: any = {};
+    if (message.filters?.length) {
+      obj.filters = message.filters.map((e) => BooleanTransactionFilter.toJSON(e));
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<LogicalOrFilters>): LogicalOrFilters {
+    return LogicalOrFilters.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<LogicalOrFilters>): LogicalOrFilters {
+    const message = createBaseLogicalOrFilters();
+    message.filters = object.filters?.map((e) => BooleanTransactionFilter.fromPartial(e)) || [];
+    return message;
+  },
+};
+
+function createBaseAPIFilter(): APIFilter {
+  return {
+    transactionRootFilter: undefined,
+    userTransactionFilter: undefined,
+    eventFilter: undefined,
+    writeSetChangeFilter: undefined,
+  };
+}
+
+export const APIFilter = {
+  encode(message: APIFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.transactionRootFilter !== undefined) {
+      TransactionRootFilter.encode(message.transactionRootFilter, writer.uint32(10).fork()).ldelim();
+    }
+    if (message.userTransactionFilter !== undefined) {
+      UserTransactionFilter.encode(message.userTransactionFilter, writer.uint32(18).fork()).ldelim();
+    }
+    if (message.eventFilter !== undefined) {
+      EventFilter.encode(message.eventFilter, writer.uint32(26).fork()).ldelim();
+    }
+    if (message.writeSetChangeFilter !== undefined) {
+      WriteSetChangeFilter.encode(message.writeSetChangeFilter, writer.uint32(34).fork()).ldelim();
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): APIFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseAPIFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.transactionRootFilter = TransactionRootFilter.decode(reader, reader.uint32());
+          continue;
+        case 2:
+          if (tag !== 18) {
+            break;
+          }
+
+          message.userTransactionFilter = UserTransactionFilter.decode(reader, reader.uint32());
+          continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.eventFilter = EventFilter.decode(reader, reader.uint32());
+          continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.writeSetChangeFilter = WriteSetChangeFilter.decode(reader, reader.uint32());
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<APIFilter, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<APIFilter | APIFilter[]> | Iterable<APIFilter | APIFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [APIFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [APIFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, APIFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<APIFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [APIFilter.decode(p)];
+        }
+      } else {
+        yield* [APIFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): APIFilter {
+    return {
+      transactionRootFilter: isSet(object.transactionRootFilter)
+        ? TransactionRootFilter.fromJSON(object.transactionRootFilter)
+        : undefined,
+      userTransactionFilter: isSet(object.userTransactionFilter)
+        ? UserTransactionFilter.fromJSON(object.userTransactionFilter)
+        : undefined,
+      eventFilter: isSet(object.eventFilter) ? EventFilter.fromJSON(object.eventFilter) : undefined,
+      writeSetChangeFilter: isSet(object.writeSetChangeFilter)
+        ? WriteSetChangeFilter.fromJSON(object.writeSetChangeFilter)
+        : undefined,
+    };
+  },
+
+  toJSON(message: APIFilter): unknown {
+    const obj: any = {};
+    if (message.transactionRootFilter !== undefined) {
+      obj.transactionRootFilter = TransactionRootFilter.toJSON(message.transactionRootFilter);
+    }
+    if (message.userTransactionFilter !== undefined) {
+      obj.userTransactionFilter = UserTransactionFilter.toJSON(message.userTransactionFilter);
+    }
+    if (message.eventFilter !== undefined) {
+      obj.eventFilter = EventFilter.toJSON(message.eventFilter);
+    }
+    if (message.writeSetChangeFilter !== undefined) {
+      obj.writeSetChangeFilter = WriteSetChangeFilter.toJSON(message.writeSetChangeFilter);
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<APIFilter>): APIFilter {
+    return APIFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<APIFilter>): APIFilter {
+    const message = createBaseAPIFilter();
+    message.transactionRootFilter =
+      (object.transactionRootFilter !== undefined && object.transactionRootFilter !== null)
+        ? TransactionRootFilter.fromPartial(object.transactionRootFilter)
+        : undefined;
+    message.userTransactionFilter =
+      (object.userTransactionFilter !== undefined && object.userTransactionFilter !== null)
+        ? UserTransactionFilter.fromPartial(object.userTransactionFilter)
+        : undefined;
+    message.eventFilter = (object.eventFilter !== undefined && object.eventFilter !== null)
+      ? EventFilter.fromPartial(object.eventFilter)
+      : undefined;
+    message.writeSetChangeFilter = (object.writeSetChangeFilter !== undefined && object.writeSetChangeFilter !== null)
+      ? WriteSetChangeFilter.fromPartial(object.writeSetChangeFilter)
+      : undefined;
+    return message;
+  },
+};
+
+function createBaseTransactionRootFilter(): TransactionRootFilter {
+  return { success: undefined, transactionType: undefined };
+}
+
+export const TransactionRootFilter = {
+  encode(message: TransactionRootFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.success !== undefined) {
+      writer.uint32(8).bool(message.success);
+    }
+    if (message.transactionType !== undefined) {
+      writer.uint32(16).int32(message.transactionType);
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): TransactionRootFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseTransactionRootFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 8) {
+            break;
+          }
+
+          message.success = reader.bool();
+          continue;
+        case 2:
+          if (tag !== 16) {
+            break;
+          }
+
+          message.transactionType = reader.int32() as any;
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<TransactionRootFilter, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<TransactionRootFilter | TransactionRootFilter[]>
+      | Iterable<TransactionRootFilter | TransactionRootFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionRootFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [TransactionRootFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, TransactionRootFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<TransactionRootFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionRootFilter.decode(p)];
+        }
+      } else {
+        yield* [TransactionRootFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): TransactionRootFilter {
+    return {
+      success: isSet(object.success) ? globalThis.Boolean(object.success) : undefined,
+      transactionType: isSet(object.transactionType)
+        ? transaction_TransactionTypeFromJSON(object.transactionType)
+        : undefined,
+    };
+  },
+
+  toJSON(message: TransactionRootFilter): unknown {
+    const obj: any = {};
+    if (message.success !== undefined) {
+      obj.success = message.success;
+    }
+    if (message.transactionType !== undefined) {
+      obj.transactionType = transaction_TransactionTypeToJSON(message.transactionType);
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<TransactionRootFilter>): TransactionRootFilter {
+    return TransactionRootFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<TransactionRootFilter>): TransactionRootFilter {
+    const message = createBaseTransactionRootFilter();
+    message.success = object.success ?? undefined;
+    message.transactionType = object.transactionType ?? undefined;
+    return message;
+  },
+};
+
+function createBaseUserTransactionFilter(): UserTransactionFilter {
+  return { sender: undefined, entryFunction: undefined };
+}
+
+export const UserTransactionFilter = {
+  encode(message: UserTransactionFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.sender !== undefined) {
+      writer.uint32(8).string(message.sender);
+    }
+    if (message.entryFunction !== undefined) {
+      EntryFunctionFilter.encode(message.entryFunction, writer.uint32(18).fork()).ldelim();
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): UserTransactionFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseUserTransactionFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 8) {
+            break;
+          }
+
+          message.sender = reader.string();
+          continue;
+        case 2:
+          if (tag !== 18) {
+            break;
+          }
+
+          message.entryFunction = EntryFunctionFilter.decode(reader, reader.uint32());
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<UserTransactionFilter, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<UserTransactionFilter | UserTransactionFilter[]>
+      | Iterable<UserTransactionFilter | UserTransactionFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [UserTransactionFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [UserTransactionFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, UserTransactionFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<UserTransactionFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [UserTransactionFilter.decode(p)];
+        }
+      } else {
+        yield* [UserTransactionFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): UserTransactionFilter {
+    return {
+      sender: isSet(object.sender) ? globalThis.String(object.sender) : undefined,
+      entryFunction: isSet(object.entryFunction) ? EntryFunctionFilter.fromJSON(object.entryFunction) : undefined,
+    };
+  },
+
+  toJSON(message: UserTransactionFilter): unknown {
+    const obj: any = {};
+    if (message.sender !== undefined) {
+      obj.sender = message.sender;
+    }
+    if (message.entryFunction !== undefined) {
+      obj.entryFunction = EntryFunctionFilter.toJSON(message.entryFunction);
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<UserTransactionFilter>): UserTransactionFilter {
+    return UserTransactionFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<UserTransactionFilter>): UserTransactionFilter {
+    const message = createBaseUserTransactionFilter();
+    message.sender = object.sender ?? undefined;
+    message.entryFunction = (object.entryFunction !== undefined && object.entryFunction !== null)
+      ? EntryFunctionFilter.fromPartial(object.entryFunction)
+      : undefined;
+    return message;
+  },
+};
+
+function createBaseEntryFunctionFilter(): EntryFunctionFilter {
+  return { address: undefined, moduleName: undefined, function: undefined };
+}
+
+export const EntryFunctionFilter = {
+  encode(message: EntryFunctionFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.address !== undefined) {
+      writer.uint32(8).string(message.address);
+    }
+    if (message.moduleName !== undefined) {
+      writer.uint32(16).string(message.moduleName);
+    }
+    if (message.function !== undefined) {
+      writer.uint32(24).string(message.function);
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): EntryFunctionFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseEntryFunctionFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 8) {
+            break;
+          }
+
+          message.address = reader.string();
+          continue;
+        case 2:
+          if (tag !== 16) {
+            break;
+          }
+
+          message.moduleName = reader.string();
+          continue;
+        case 3:
+          if (tag !== 24) {
+            break;
+          }
+
+          message.function = reader.string();
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<EntryFunctionFilter, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<EntryFunctionFilter | EntryFunctionFilter[]>
+      | Iterable<EntryFunctionFilter | EntryFunctionFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [EntryFunctionFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [EntryFunctionFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, EntryFunctionFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<EntryFunctionFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [EntryFunctionFilter.decode(p)];
+        }
+      } else {
+        yield* [EntryFunctionFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): EntryFunctionFilter {
+    return {
+      address: isSet(object.address) ? globalThis.String(object.address) : undefined,
+      moduleName: isSet(object.moduleName) ? globalThis.String(object.moduleName) : undefined,
+      function: isSet(object.function) ? globalThis.String(object.function) : undefined,
+    };
+  },
+
+  toJSON(message: EntryFunctionFilter): unknown {
+    const obj: any = {};
+    if (message.address !== undefined) {
+      obj.address = message.address;
+    }
+    if (message.moduleName !== undefined) {
+      obj.moduleName = message.moduleName;
+    }
+    if (message.function !== undefined) {
+      obj.function = message.function;
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<EntryFunctionFilter>): EntryFunctionFilter {
+    return EntryFunctionFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<EntryFunctionFilter>): EntryFunctionFilter {
+    const message = createBaseEntryFunctionFilter();
+    message.address = object.address ?? undefined;
+    message.moduleName = object.moduleName ?? undefined;
+    message.function = object.function ?? undefined;
+    return message;
+  },
+};
+
+function createBaseEventFilter(): EventFilter {
+  return { structType: undefined };
+}
+
+export const EventFilter = {
+  encode(message: EventFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.structType !== undefined) {
+      MoveStructTagFilter.encode(message.structType, writer.uint32(10).fork()).ldelim();
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): EventFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseEventFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.structType = MoveStructTagFilter.decode(reader, reader.uint32());
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<EventFilter, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<EventFilter | EventFilter[]> | Iterable<EventFilter | EventFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [EventFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [EventFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, EventFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<EventFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [EventFilter.decode(p)];
+        }
+      } else {
+        yield* [EventFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): EventFilter {
+    return {
+      structType: isSet(object.structType) ? MoveStructTagFilter.fromJSON(object.structType) : undefined,
+    };
+  },
+
+  toJSON(message: EventFilter): unknown {
+    const obj: any = {};
+    if (message.structType !== undefined) {
+      obj.structType = MoveStructTagFilter.toJSON(message.structType);
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<EventFilter>): EventFilter {
+    return EventFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<EventFilter>): EventFilter {
+    const message = createBaseEventFilter();
+    message.structType = (object.structType !== undefined && object.structType !== null)
+      ? MoveStructTagFilter.fromPartial(object.structType)
+      : undefined;
+    return message;
+  },
+};
+
+function createBaseWriteSetChangeFilter(): WriteSetChangeFilter {
+  return { resourceType: undefined };
+}
+
+export const WriteSetChangeFilter = {
+  encode(message: WriteSetChangeFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.resourceType !== undefined) {
+      MoveStructTagFilter.encode(message.resourceType, writer.uint32(10).fork()).ldelim();
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): WriteSetChangeFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseWriteSetChangeFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.resourceType = MoveStructTagFilter.decode(reader, reader.uint32());
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<WriteSetChangeFilter, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<WriteSetChangeFilter | WriteSetChangeFilter[]>
+      | Iterable<WriteSetChangeFilter | WriteSetChangeFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [WriteSetChangeFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [WriteSetChangeFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, WriteSetChangeFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<WriteSetChangeFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [WriteSetChangeFilter.decode(p)];
+        }
+      } else {
+        yield* [WriteSetChangeFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): WriteSetChangeFilter {
+    return {
+      resourceType: isSet(object.resourceType) ? MoveStructTagFilter.fromJSON(object.resourceType) : undefined,
+    };
+  },
+
+  toJSON(message: WriteSetChangeFilter): unknown {
+    const obj: any = {};
+    if (message.resourceType !== undefined) {
+      obj.resourceType = MoveStructTagFilter.toJSON(message.resourceType);
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<WriteSetChangeFilter>): WriteSetChangeFilter {
+    return WriteSetChangeFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<WriteSetChangeFilter>): WriteSetChangeFilter {
+    const message = createBaseWriteSetChangeFilter();
+    message.resourceType = (object.resourceType !== undefined && object.resourceType !== null)
+      ? MoveStructTagFilter.fromPartial(object.resourceType)
+      : undefined;
+    return message;
+  },
+};
+
+function createBaseMoveStructTagFilter(): MoveStructTagFilter {
+  return { address: undefined, module: undefined, name: undefined };
+}
+
+export const MoveStructTagFilter = {
+  encode(message: MoveStructTagFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.address !== undefined) {
+      writer.uint32(8).string(message.address);
+    }
+    if (message.module !== undefined) {
+      writer.uint32(16).string(message.module);
+    }
+    if (message.name !== undefined) {
+      writer.uint32(24).string(message.name);
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): MoveStructTagFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseMoveStructTagFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 8) {
+            break;
+          }
+
+          message.address = reader.string();
+          continue;
+        case 2:
+          if (tag !== 16) {
+            break;
+          }
+
+          message.module = reader.string();
+          continue;
+        case 3:
+          if (tag !== 24) {
+            break;
+          }
+
+          message.name = reader.string();
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<MoveStructTagFilter, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<MoveStructTagFilter | MoveStructTagFilter[]>
+      | Iterable<MoveStructTagFilter | MoveStructTagFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [MoveStructTagFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [MoveStructTagFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, MoveStructTagFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<MoveStructTagFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [MoveStructTagFilter.decode(p)];
+        }
+      } else {
+        yield* [MoveStructTagFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): MoveStructTagFilter {
+    return {
+      address: isSet(object.address) ? globalThis.String(object.address) : undefined,
+      module: isSet(object.module) ? globalThis.String(object.module) : undefined,
+      name: isSet(object.name) ? globalThis.String(object.name) : undefined,
+    };
+  },
+
+  toJSON(message: MoveStructTagFilter): unknown {
+    const obj: any = {};
+    if (message.address !== undefined) {
+      obj.address = message.address;
+    }
+    if (message.module !== undefined) {
+      obj.module = message.module;
+    }
+    if (message.name !== undefined) {
+      obj.name = message.name;
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<MoveStructTagFilter>): MoveStructTagFilter {
+    return MoveStructTagFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<MoveStructTagFilter>): MoveStructTagFilter {
+    const message = createBaseMoveStructTagFilter();
+    message.address = object.address ?? undefined;
+    message.module = object.module ?? undefined;
+    message.name = object.name ?? undefined;
+    return message;
+  },
+};
+
+function createBaseProcessedRange(): ProcessedRange {
+  return { firstVersion: BigInt("0"), lastVersion: BigInt("0") };
+}
+
+export const ProcessedRange = {
+  encode(message: ProcessedRange, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.firstVersion !== undefined && message.firstVersion !== BigInt("0")) {
+      if (BigInt.asUintN(64, message.firstVersion) !== message.firstVersion) {
+        throw new globalThis.Error("value provided for field message.firstVersion of type uint64 too large");
+      }
+      writer.uint32(8).uint64(message.firstVersion.toString());
+    }
+    if (message.lastVersion !== undefined && message.lastVersion !== BigInt("0")) {
+      if (BigInt.asUintN(64, message.lastVersion) !== message.lastVersion) {
+        throw new globalThis.Error("value provided for field message.lastVersion of type uint64 too large");
+      }
+      writer.uint32(16).uint64(message.lastVersion.toString());
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): ProcessedRange {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseProcessedRange();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 8) {
+            break;
+          }
+
+          message.firstVersion = longToBigint(reader.uint64() as Long);
+          continue;
+        case 2:
+          if (tag !== 16) {
+            break;
+          }
+
+          message.lastVersion = longToBigint(reader.uint64() as Long);
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<ProcessedRange, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<ProcessedRange | ProcessedRange[]> | Iterable<ProcessedRange | ProcessedRange[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [ProcessedRange.encode(p).finish()];
+        }
+      } else {
+        yield* [ProcessedRange.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, ProcessedRange>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<ProcessedRange> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [ProcessedRange.decode(p)];
+        }
+      } else {
+        yield* [ProcessedRange.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): ProcessedRange {
+    return {
+      firstVersion: isSet(object.firstVersion) ? BigInt(object.firstVersion) : BigInt("0"),
+      lastVersion: isSet(object.lastVersion) ? BigInt(object.lastVersion) : BigInt("0"),
+    };
+  },
+
+  toJSON(message: ProcessedRange): unknown {
+    const obj: any = {};
+    if (message.firstVersion !== undefined && message.firstVersion !== BigInt("0")) {
+      obj.firstVersion = message.firstVersion.toString();
+    }
+    if (message.lastVersion !== undefined && message.lastVersion !== BigInt("0")) {
+      obj.lastVersion = message.lastVersion.toString();
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<ProcessedRange>): ProcessedRange {
+    return ProcessedRange.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<ProcessedRange>): ProcessedRange {
+    const message = createBaseProcessedRange();
+    message.firstVersion = object.firstVersion ?? BigInt("0");
+    message.lastVersion = object.lastVersion ?? BigInt("0");
+    return message;
+  },
+};
+
+function createBaseTransactionsResponse(): TransactionsResponse {
+  return { transactions: [], chainId: undefined, processedRange: undefined };
+}
+
+export const TransactionsResponse = {
+  encode(message: TransactionsResponse, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.transactions !== undefined && message.transactions.length !== 0) {
+      for (const v of message.transactions) {
+        Transaction.encode(v!, writer.uint32(10).fork()).ldelim();
+      }
+    }
+    if (message.chainId !== undefined) {
+      if (BigInt.asUintN(64, message.chainId) !== message.chainId) {
+        throw new globalThis.Error("value provided for field message.chainId of type uint64 too large");
+      }
+      writer.uint32(16).uint64(message.chainId.toString());
+    }
+    if (message.processedRange !== undefined) {
+      ProcessedRange.encode(message.processedRange, writer.uint32(26).fork()).ldelim();
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): TransactionsResponse {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseTransactionsResponse();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.transactions!.push(Transaction.decode(reader, reader.uint32()));
+          continue;
+        case 2:
+          if (tag !== 16) {
+            break;
+          }
+
+          message.chainId = longToBigint(reader.uint64() as Long);
+          continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.processedRange = ProcessedRange.decode(reader, reader.uint32());
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<TransactionsResponse, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<TransactionsResponse | TransactionsResponse[]>
+      | Iterable<TransactionsResponse | TransactionsResponse[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionsResponse.encode(p).finish()];
+        }
+      } else {
+        yield* [TransactionsResponse.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, TransactionsResponse>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<TransactionsResponse> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionsResponse.decode(p)];
+        }
+      } else {
+        yield* [TransactionsResponse.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): TransactionsResponse {
+    return {
+      transactions: globalThis.Array.isArray(object?.transactions)
+        ? object.transactions.map((e: any) => Transaction.fromJSON(e))
+        : [],
+      chainId: isSet(object.chainId) ? BigInt(object.chainId) : undefined,
+      processedRange: isSet(object.processedRange) ? ProcessedRange.fromJSON(object.processedRange) : undefined,
+    };
+  },
+
+  toJSON(message: TransactionsResponse): unknown {
+    const obj: any = {};
+    if (message.transactions?.length) {
+      obj.transactions = message.transactions.map((e) => Transaction.toJSON(e));
+    }
+    if (message.chainId !== undefined) {
+      obj.chainId = message.chainId.toString();
+    }
+    if (message.processedRange !== undefined) {
+      obj.processedRange = ProcessedRange.toJSON(message.processedRange);
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<TransactionsResponse>): TransactionsResponse {
+    return TransactionsResponse.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<TransactionsResponse>): TransactionsResponse {
+    const message = createBaseTransactionsResponse();
+    message.transactions = object.transactions?.map((e) => Transaction.fromPartial(e)) || [];
+    message.chainId = object.chainId ?? undefined;
+    message.processedRange = (object.processedRange !== undefined && object.processedRange !== null)
+      ? ProcessedRange.fromPartial(object.processedRange)
+      : undefined;
+    return message;
+  },
+};
+
+export type RawDataService = typeof RawDataService;
+export const RawDataService = {
+  /**
+   * Get transactions batch from starting version and end if transaction count is present.
+   * Transactions can be filtered on the server side with `transaction_filter`.
+   */
+  getTransactions: {
+    path: "/aptos.indexer.v1.RawData/GetTransactions",
+    requestStream: false,
+    responseStream: true,
+    requestSerialize: (value: GetTransactionsRequest) => Buffer.from(GetTransactionsRequest.encode(value).finish()),
+    requestDeserialize: (value: Buffer) => GetTransactionsRequest.decode(value),
+    responseSerialize: (value: TransactionsResponse) => Buffer.from(TransactionsResponse.encode(value).finish()),
+    responseDeserialize: (value: Buffer) => TransactionsResponse.decode(value),
+  },
+} as const;
+
+export interface RawDataServer extends UntypedServiceImplementation {
+  /**
+   * Get transactions batch from starting version and end if transaction count is present.
+   * Transactions can be filtered on the server side with `transaction_filter`.
+   */
+  getTransactions: handleServerStreamingCall<GetTransactionsRequest, TransactionsResponse>;
+}
+
+export interface RawDataClient extends Client {
+  /**
+   * Get transactions batch from starting version and end if transaction count is present.
+   * Transactions can be filtered on the server side with `transaction_filter`.
+   */
   getTransactions(
     request: GetTransactionsRequest,
     options?: Partial<CallOptions>,
@@ -0,0 +1,339 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{from_base64, to_base64, CryptoKVStorage, Error, GetResponse, KVStorage};
+use aptos_temppath::TempPath;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use ring::{
+    aead::{self, Aad, LessSafeKey, Nonce, UnboundKey},
+    pbkdf2,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::Write,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
+
+/// The version of the file format, bumped on incompatible changes.
+const FORMAT_VERSION: u32 = 1;
+/// The default number of PBKDF2 iterations, following the OWASP recommendation for
+/// PBKDF2-HMAC-SHA256.
+pub const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+const SALT_LENGTH: usize = 16;
+
+/// The on-disk representation: a plaintext header describing how the key is derived, followed by
+/// the AES-256-GCM encrypted JSON map of all values. The header is authenticated as well.
+#[derive(Deserialize, Serialize)]
+struct EncryptedFile {
+    version: u32,
+    kdf_iterations: u32,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    salt: Vec<u8>,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    nonce: Vec<u8>,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedFile {
+    fn aad(version: u32, kdf_iterations: u32, salt: &[u8]) -> Vec<u8> {
+        let mut aad = b"aptos-secure-storage".to_vec();
+        aad.extend_from_slice(&version.to_le_bytes());
+        aad.extend_from_slice(&kdf_iterations.to_le_bytes());
+        aad.extend_from_slice(salt);
+        aad
+    }
+}
+
+/// An encryption key derived from a secret with PBKDF2-HMAC-SHA256.
+struct DerivedKey {
+    salt: Vec<u8>,
+    kdf_iterations: u32,
+    key: LessSafeKey,
+}
+
+impl DerivedKey {
+    fn derive(secret: &[u8], salt: Vec<u8>, kdf_iterations: u32) -> Result<Self, Error> {
+        let iterations = NonZeroU32::new(kdf_iterations).ok_or_else(|| {
+            Error::InternalError("The number of KDF iterations must be non-zero".into())
+        })?;
+        let mut key_bytes = [0u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            iterations,
+            &salt,
+            secret,
+            &mut key_bytes,
+        );
+        let key = UnboundKey::new(&aead::AES_256_GCM, &key_bytes)
+            .map_err(|_| Error::InternalError("Invalid AES-256-GCM key length".into()))?;
+        Ok(Self {
+            salt,
+            kdf_iterations,
+            key: LessSafeKey::new(key),
+        })
+    }
+
+    /// Derives a key using a fresh random salt.
+    fn generate(secret: &[u8], kdf_iterations: u32) -> Result<Self, Error> {
+        let mut salt = vec![0u8; SALT_LENGTH];
+        fill_random(&mut salt)?;
+        Self::derive(secret, salt, kdf_iterations)
+    }
+}
+
+fn fill_random(buf: &mut [u8]) -> Result<(), Error> {
+    SystemRandom::new()
+        .fill(buf)
+        .map_err(|_| Error::EntropyError("Unable to generate random bytes".into()))
+}
+
+/// EncryptedOnDiskStorage is a key value store persisted to a single file on the local filesystem,
+/// like `OnDiskStorage`, except that the contents of the file are encrypted at rest with
+/// AES-256-GCM. The encryption key is derived from a secret (e.g., a passphrase or the contents of
+/// a key file) using PBKDF2. Writes go to a temporary file which atomically replaces the storage
+/// file. It is intended for single threads (or must be wrapped by a Arc<RwLock<>>).
+pub struct EncryptedOnDiskStorage {
+    file_path: PathBuf,
+    temp_path: TempPath,
+    secret: Vec<u8>,
+    key: DerivedKey,
+    time_service: TimeService,
+}
+
+impl EncryptedOnDiskStorage {
+    pub fn new(file_path: PathBuf, secret: Vec<u8>) -> Self {
+        Self::new_with_kdf_iterations(file_path, secret, DEFAULT_KDF_ITERATIONS)
+    }
+
+    pub fn new_with_kdf_iterations(
+        file_path: PathBuf,
+        secret: Vec<u8>,
+        kdf_iterations: u32,
+    ) -> Self {
+        Self::new_with_time_service(file_path, secret, kdf_iterations, TimeService::real())
+    }
+
+    /// Opens the storage, which may still be encrypted with a key derived from `previous_secret`.
+    /// In that case it is re-encrypted with a key derived from `secret`, which allows operators to
+    /// rotate the secret by updating the config and restarting.
+    pub fn new_with_key_rotation(
+        file_path: PathBuf,
+        secret: Vec<u8>,
+        previous_secret: Vec<u8>,
+    ) -> Self {
+        Self::new_with_key_rotation_and_kdf_iterations(
+            file_path,
+            secret,
+            previous_secret,
+            DEFAULT_KDF_ITERATIONS,
+        )
+    }
+
+    pub(crate) fn new_with_key_rotation_and_kdf_iterations(
+        file_path: PathBuf,
+        secret: Vec<u8>,
+        previous_secret: Vec<u8>,
+        kdf_iterations: u32,
+    ) -> Self {
+        let storage =
+            Self::new_with_kdf_iterations(file_path.clone(), secret.clone(), kdf_iterations);
+        match storage.read() {
+            Err(Error::DecryptionError(_)) => {
+                let mut previous = Self::new_with_kdf_iterations(
+                    file_path.clone(),
+                    previous_secret,
+                    kdf_iterations,
+                );
+                previous
+                    .rotate_encryption_key(secret.clone())
+                    .unwrap_or_else(|error| {
+                        panic!(
+                            "Unable to rotate the encryption key of storage at path {:?}: {}",
+                            file_path, error
+                        )
+                    });
+                Self::new_with_kdf_iterations(file_path, secret, kdf_iterations)
+            },
+            _ => storage,
+        }
+    }
+
+    fn new_with_time_service(
+        file_path: PathBuf,
+        secret: Vec<u8>,
+        kdf_iterations: u32,
+        time_service: TimeService,
+    ) -> Self {
+        // Reuse the salt of an existing file, so that the key is only derived once.
+        let existing_file = Self::read_file(&file_path).unwrap_or_else(|error| {
+            panic!("Unable to read storage at path {:?}: {}", file_path, error)
+        });
+        let key = match &existing_file {
+            Some(file) if file.kdf_iterations == kdf_iterations => {
+                DerivedKey::derive(&secret, file.salt.clone(), kdf_iterations)
+            },
+            _ => DerivedKey::generate(&secret, kdf_iterations),
+        }
+        .unwrap_or_else(|error| panic!("Unable to derive the encryption key: {}", error));
+
+        // The parent will be one when only a filename is supplied. Therefore use the current
+        // working directory provided by PathBuf::new().
+        let file_dir = file_path
+            .parent()
+            .map_or(PathBuf::new(), |p| p.to_path_buf());
+
+        let storage = Self {
+            file_path,
+            temp_path: TempPath::new_with_temp_dir(file_dir),
+            secret,
+            key,
+            time_service,
+        };
+        if existing_file.is_none() {
+            storage.write(&HashMap::new()).unwrap_or_else(|error| {
+                panic!(
+                    "Unable to create storage at path {:?}: {}",
+                    storage.file_path, error
+                )
+            });
+        }
+        storage
+    }
+
+    /// Re-encrypts all data with a key derived from `new_secret` and a fresh salt.
+    pub fn rotate_encryption_key(&mut self, new_secret: Vec<u8>) -> Result<(), Error> {
+        let data = self.read()?;
+        self.key = DerivedKey::generate(&new_secret, self.key.kdf_iterations)?;
+        self.secret = new_secret;
+        self.write(&data)
+    }
+
+    /// Returns `None` if the file does not exist or is empty.
+    fn read_file(file_path: &Path) -> Result<Option<EncryptedFile>, Error> {
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read(file_path)?;
+        if contents.is_empty() {
+            return Ok(None);
+        }
+        let file: EncryptedFile = serde_json::from_slice(&contents)?;
+        if file.version != FORMAT_VERSION {
+            return Err(Error::SerializationError(format!(
+                "Unsupported storage format version: {}",
+                file.version
+            )));
+        }
+        Ok(Some(file))
+    }
+
+    fn read(&self) -> Result<HashMap<String, Value>, Error> {
+        let file = match Self::read_file(&self.file_path)? {
+            Some(file) => file,
+            None => return Ok(HashMap::new()),
+        };
+
+        // The file may have been written with a different salt, e.g., by another instance.
+        let derived_key;
+        let key = if file.salt == self.key.salt && file.kdf_iterations == self.key.kdf_iterations {
+            &self.key
+        } else {
+            derived_key = DerivedKey::derive(&self.secret, file.salt.clone(), file.kdf_iterations)?;
+            &derived_key
+        };
+
+        let nonce = Nonce::try_assume_unique_for_key(&file.nonce)
+            .map_err(|_| Error::SerializationError("Invalid nonce length".into()))?;
+        let aad = EncryptedFile::aad(file.version, file.kdf_iterations, &file.salt);
+        let mut in_out = file.ciphertext;
+        let plaintext = key
+            .key
+            .open_in_place(nonce, Aad::from(aad), &mut in_out)
+            .map_err(|_| {
+                Error::DecryptionError(format!(
+                    "Unable to decrypt storage at path {:?}, the secret is wrong or the file is corrupted",
+                    self.file_path
+                ))
+            })?;
+        Ok(serde_json::from_slice(plaintext)?)
+    }
+
+    fn write(&self, data: &HashMap<String, Value>) -> Result<(), Error> {
+        let mut nonce = [0u8; aead::NONCE_LEN];
+        fill_random(&mut nonce)?;
+        let aad = EncryptedFile::aad(FORMAT_VERSION, self.key.kdf_iterations, &self.key.salt);
+        let mut in_out = serde_json::to_vec(data)?;
+        self.key
+            .key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(aad),
+                &mut in_out,
+            )
+            .map_err(|_| Error::InternalError("Unable to encrypt storage".into()))?;
+        let contents = serde_json::to_vec(&EncryptedFile {
+            version: FORMAT_VERSION,
+            kdf_iterations: self.key.kdf_iterations,
+            salt: self.key.salt.clone(),
+            nonce: nonce.to_vec(),
+            ciphertext: in_out,
+        })?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(self.temp_path.path())?;
+        file.write_all(&contents)?;
+        file.sync_all()?;
+        fs::rename(&self.temp_path, &self.file_path)?;
+        // Persist the rename as well.
+        if let Some(dir) = self
+            .file_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+        {
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl KVStorage for EncryptedOnDiskStorage {
+    fn available(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn get<V: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<V>, Error> {
+        let mut data = self.read()?;
+        data.remove(key)
+            .ok_or_else(|| Error::KeyNotSet(key.to_string()))
+            .and_then(|value| serde_json::from_value(value).map_err(|e| e.into()))
+    }
+
+    fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        let now = self.time_service.now_secs();
+        let mut data = self.read()?;
+        data.insert(
+            key.to_string(),
+            serde_json::to_value(&GetResponse::new(value, now))?,
+        );
+        self.write(&data)
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.write(&HashMap::new())
+    }
+}
+
+impl CryptoKVStorage for EncryptedOnDiskStorage {}
@@ -8,6 +8,8 @@ use thiserror::Error;
 
 #[derive(Debug, Deserialize, Error, PartialEq, Eq, Serialize)]
 pub enum Error {
+    #[error("Decryption error: {0}")]
+    DecryptionError(String),
     #[error("Entropy error: {0}")]
     EntropyError(String),
     #[error("Internal error: {0}")]
@@ -54,6 +56,12 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<cryptoki::error::Error> for Error {
+    fn from(error: cryptoki::error::Error) -> Self {
+        Self::InternalError(format!("PKCS#11 error: {}", error))
+    }
+}
+
 impl From<aptos_vault_client::Error> for Error {
     fn from(error: aptos_vault_client::Error) -> Self {
         match error {
@@ -0,0 +1,454 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    namespaced::NAMESPACE_SEPARATOR, CryptoStorage, Error, GetResponse, KVStorage,
+    PublicKeyResponse, Storage,
+};
+use aptos_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    hash::CryptoHash,
+    signing_message, PrivateKey,
+};
+use aptos_global_constants::CONSENSUS_KEY;
+use aptos_infallible::Mutex;
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    error::RvError,
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    types::AuthPin,
+};
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    path::{Path, PathBuf},
+};
+
+/// The DER encoded object identifier of Ed25519 (1.3.101.112), used as CKA_EC_PARAMS.
+const ED25519_EC_PARAMS: &[u8] = &[0x06, 0x03, 0x2B, 0x65, 0x70];
+
+/// The DER tag of an OCTET STRING, which wraps CKA_EC_POINT for Edwards curves.
+const DER_OCTET_STRING_TAG: u8 = 0x04;
+
+/// A PKCS#11 library may only be initialized once per process and is finalized when the last
+/// handle to it is dropped, so contexts are shared between all storages using the same library.
+static CONTEXTS: Lazy<Mutex<HashMap<PathBuf, Pkcs11>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn context(library_path: &Path) -> Result<Pkcs11, Error> {
+    let mut contexts = CONTEXTS.lock();
+    if let Some(context) = contexts.get(library_path) {
+        return Ok(context.clone());
+    }
+    let context = Pkcs11::new(library_path)?;
+    match context.initialize(CInitializeArgs::OsThreads) {
+        Ok(()) | Err(cryptoki::error::Error::Pkcs11(RvError::CryptokiAlreadyInitialized, ..)) => {},
+        Err(error) => return Err(error.into()),
+    }
+    contexts.insert(library_path.to_path_buf(), context.clone());
+    Ok(context)
+}
+
+/// Pkcs11Storage offers a CryptoStorage implementation backed by a PKCS#11 token (e.g., an HSM).
+/// Ed25519 key pairs are generated on the token as sensitive, non-extractable objects and all
+/// signing operations are performed by the token, so private keys never leave it. As a result,
+/// `export_private_key` and `export_private_key_for_version` always fail. Importing keys is
+/// supported for test environments.
+///
+/// PKCS#11 tokens are not suited for arbitrary data, so key/value operations are delegated to
+/// another storage. The exception is the BLS12-381 consensus key used by safety rules: PKCS#11
+/// does not define a mechanism for BLS12-381 signatures, so safety rules would have to read the
+/// private key back into memory to sign with it. Rather than silently weakening the guarantee
+/// that private keys never leave the token, storing or reading `CONSENSUS_KEY` is refused.
+///
+/// Keys are identified by their CKA_LABEL. Like `CryptoKVStorage`, the previous version of a
+/// rotated key is retained with the label suffixed by `_previous`.
+pub struct Pkcs11Storage {
+    session: Mutex<Session>,
+    data_storage: Box<Storage>,
+}
+
+impl Pkcs11Storage {
+    /// Opens a session on the token labeled `token_label` using the PKCS#11 library at
+    /// `library_path`, and logs in with the user `pin`.
+    pub fn new(
+        library_path: &Path,
+        token_label: &str,
+        pin: String,
+        data_storage: Storage,
+    ) -> Result<Self, Error> {
+        let context = context(library_path)?;
+        let slot = context
+            .get_slots_with_token()?
+            .into_iter()
+            .find(|slot| {
+                context
+                    .get_token_info(*slot)
+                    .map(|info| info.label().trim() == token_label)
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| {
+                Error::InternalError(format!("PKCS#11 token not found: {}", token_label))
+            })?;
+
+        let session = context.open_rw_session(slot)?;
+        // The login state is shared by all sessions of an application with the token.
+        match session.login(UserType::User, Some(&AuthPin::new(pin))) {
+            Ok(()) | Err(cryptoki::error::Error::Pkcs11(RvError::UserAlreadyLoggedIn, ..)) => {},
+            Err(cryptoki::error::Error::Pkcs11(RvError::PinIncorrect, ..)) => {
+                return Err(Error::PermissionDenied)
+            },
+            Err(error) => return Err(error.into()),
+        }
+
+        Ok(Self {
+            session: Mutex::new(session),
+            data_storage: Box::new(data_storage),
+        })
+    }
+
+    fn find_key(
+        session: &Session,
+        class: ObjectClass,
+        label: &str,
+    ) -> Result<Option<ObjectHandle>, Error> {
+        let mut handles = session.find_objects(&[
+            Attribute::Class(class),
+            Attribute::KeyType(KeyType::EC_EDWARDS),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])?;
+        match handles.len() {
+            0 => Ok(None),
+            1 => Ok(handles.pop()),
+            _ => Err(Error::InternalError(format!(
+                "Found multiple PKCS#11 keys labeled {}",
+                label
+            ))),
+        }
+    }
+
+    fn private_key_handle(session: &Session, label: &str) -> Result<ObjectHandle, Error> {
+        Self::find_key(session, ObjectClass::PRIVATE_KEY, label)?
+            .ok_or_else(|| Error::KeyNotSet(label.to_string()))
+    }
+
+    fn public_key(session: &Session, label: &str) -> Result<Ed25519PublicKey, Error> {
+        let handle = Self::find_key(session, ObjectClass::PUBLIC_KEY, label)?
+            .ok_or_else(|| Error::KeyNotSet(label.to_string()))?;
+        let ec_point = session
+            .get_attributes(handle, &[AttributeType::EcPoint])?
+            .into_iter()
+            .find_map(|attribute| match attribute {
+                Attribute::EcPoint(ec_point) => Some(ec_point),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                Error::InternalError(format!("PKCS#11 key {} has no CKA_EC_POINT", label))
+            })?;
+        Ed25519PublicKey::try_from(decode_ec_point(&ec_point))
+            .map_err(|e| Error::SerializationError(e.to_string()))
+    }
+
+    /// Removes both the private and public key objects labeled `label`, if present.
+    fn destroy_key_pair(session: &Session, label: &str) -> Result<(), Error> {
+        for class in [ObjectClass::PRIVATE_KEY, ObjectClass::PUBLIC_KEY] {
+            if let Some(handle) = Self::find_key(session, class, label)? {
+                session.destroy_object(handle)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn relabel_key_pair(session: &Session, label: &str, new_label: &str) -> Result<(), Error> {
+        for class in [ObjectClass::PRIVATE_KEY, ObjectClass::PUBLIC_KEY] {
+            let handle = Self::find_key(session, class, label)?
+                .ok_or_else(|| Error::KeyNotSet(label.to_string()))?;
+            session
+                .update_attributes(handle, &[Attribute::Label(new_label.as_bytes().to_vec())])?;
+        }
+        Ok(())
+    }
+
+    fn public_key_template(label: &str) -> Vec<Attribute> {
+        vec![
+            Attribute::Token(true),
+            Attribute::Private(false),
+            Attribute::Verify(true),
+            Attribute::EcParams(ED25519_EC_PARAMS.to_vec()),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ]
+    }
+
+    fn private_key_template(label: &str) -> Vec<Attribute> {
+        vec![
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Sensitive(true),
+            Attribute::Extractable(false),
+            Attribute::Sign(true),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ]
+    }
+
+    fn generate_key_pair(session: &Session, label: &str) -> Result<Ed25519PublicKey, Error> {
+        session.generate_key_pair(
+            &Mechanism::EccEdwardsKeyPairGen,
+            &Self::public_key_template(label),
+            &Self::private_key_template(label),
+        )?;
+        Self::public_key(session, label)
+    }
+
+    /// Records when the key labeled `name` was last updated, as the token does not track it.
+    fn set_last_update(&mut self, name: &str, public_key: &Ed25519PublicKey) -> Result<(), Error> {
+        self.data_storage
+            .set(&get_last_update_name(name), public_key)
+    }
+
+    fn sign_with_label<T: CryptoHash + Serialize>(
+        session: &Session,
+        label: &str,
+        message: &T,
+    ) -> Result<Ed25519Signature, Error> {
+        let handle = Self::private_key_handle(session, label)?;
+        let message =
+            signing_message(message).map_err(|e| Error::SerializationError(e.to_string()))?;
+        let signature = session.sign(&Mechanism::Eddsa, handle, &message)?;
+        Ed25519Signature::try_from(signature.as_slice())
+            .map_err(|e| Error::SerializationError(e.to_string()))
+    }
+
+    /// Returns the label of the key pair identified by `version`.
+    fn label_for_version(
+        session: &Session,
+        name: &str,
+        version: &Ed25519PublicKey,
+    ) -> Result<String, Error> {
+        if &Self::public_key(session, name)? == version {
+            return Ok(name.to_string());
+        }
+        let previous_name = get_previous_version_name(name);
+        match Self::public_key(session, &previous_name) {
+            Ok(previous_public_key) if &previous_public_key == version => Ok(previous_name),
+            Ok(_) | Err(Error::KeyNotSet(_)) => {
+                Err(Error::KeyVersionNotFound(name.into(), version.to_string()))
+            },
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl KVStorage for Pkcs11Storage {
+    fn available(&self) -> Result<(), Error> {
+        self.session.lock().get_session_info()?;
+        self.data_storage.available()
+    }
+
+    fn get<V: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<V>, Error> {
+        ensure_not_consensus_key(key)?;
+        self.data_storage.get(key)
+    }
+
+    fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        ensure_not_consensus_key(key)?;
+        self.data_storage.set(key, value)
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        {
+            let session = self.session.lock();
+            for class in [ObjectClass::PRIVATE_KEY, ObjectClass::PUBLIC_KEY] {
+                for handle in session.find_objects(&[
+                    Attribute::Class(class),
+                    Attribute::KeyType(KeyType::EC_EDWARDS),
+                ])? {
+                    session.destroy_object(handle)?;
+                }
+            }
+        }
+        self.data_storage.reset_and_clear()
+    }
+}
+
+impl CryptoStorage for Pkcs11Storage {
+    fn create_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let public_key = {
+            let session = self.session.lock();
+            Self::destroy_key_pair(&session, name)?;
+            Self::generate_key_pair(&session, name)?
+        };
+        self.set_last_update(name, &public_key)?;
+        Ok(public_key)
+    }
+
+    fn export_private_key(&self, _name: &str) -> Result<Ed25519PrivateKey, Error> {
+        Err(Error::PermissionDenied)
+    }
+
+    fn import_private_key(&mut self, name: &str, key: Ed25519PrivateKey) -> Result<(), Error> {
+        let public_key = key.public_key();
+        {
+            let session = self.session.lock();
+            Self::destroy_key_pair(&session, name)?;
+
+            let mut public_key_template = Self::public_key_template(name);
+            public_key_template.extend([
+                Attribute::Class(ObjectClass::PUBLIC_KEY),
+                Attribute::KeyType(KeyType::EC_EDWARDS),
+                Attribute::EcPoint(encode_ec_point(&public_key.to_bytes())),
+            ]);
+            session.create_object(&public_key_template)?;
+
+            let mut private_key_template = Self::private_key_template(name);
+            private_key_template.extend([
+                Attribute::Class(ObjectClass::PRIVATE_KEY),
+                Attribute::KeyType(KeyType::EC_EDWARDS),
+                Attribute::EcParams(ED25519_EC_PARAMS.to_vec()),
+                Attribute::Value(key.to_bytes().to_vec()),
+            ]);
+            session.create_object(&private_key_template)?;
+        }
+        self.set_last_update(name, &public_key)
+    }
+
+    fn export_private_key_for_version(
+        &self,
+        _name: &str,
+        _version: Ed25519PublicKey,
+    ) -> Result<Ed25519PrivateKey, Error> {
+        Err(Error::PermissionDenied)
+    }
+
+    fn get_public_key(&self, name: &str) -> Result<PublicKeyResponse, Error> {
+        let public_key = Self::public_key(&self.session.lock(), name)?;
+        let last_update = match self
+            .data_storage
+            .get::<Ed25519PublicKey>(&get_last_update_name(name))
+        {
+            Ok(response) if response.value == public_key => response.last_update,
+            Ok(_) | Err(Error::KeyNotSet(_)) => 0,
+            Err(e) => return Err(e),
+        };
+        Ok(PublicKeyResponse {
+            last_update,
+            public_key,
+        })
+    }
+
+    fn get_public_key_previous_version(&self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        match Self::public_key(&self.session.lock(), &get_previous_version_name(name)) {
+            Err(Error::KeyNotSet(_)) => Err(Error::KeyVersionNotFound(
+                name.into(),
+                "previous version".into(),
+            )),
+            result => result,
+        }
+    }
+
+    fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let public_key = {
+            let session = self.session.lock();
+            // Ensure the key exists before touching the previous version
+            Self::private_key_handle(&session, name)?;
+            let previous_name = get_previous_version_name(name);
+            Self::destroy_key_pair(&session, &previous_name)?;
+            Self::relabel_key_pair(&session, name, &previous_name)?;
+            Self::generate_key_pair(&session, name)?
+        };
+        self.set_last_update(name, &public_key)?;
+        Ok(public_key)
+    }
+
+    fn sign<T: CryptoHash + Serialize>(
+        &self,
+        name: &str,
+        message: &T,
+    ) -> Result<Ed25519Signature, Error> {
+        Self::sign_with_label(&self.session.lock(), name, message)
+    }
+
+    fn sign_using_version<T: CryptoHash + Serialize>(
+        &self,
+        name: &str,
+        version: Ed25519PublicKey,
+        message: &T,
+    ) -> Result<Ed25519Signature, Error> {
+        let session = self.session.lock();
+        let label = Self::label_for_version(&session, name, &version)?;
+        Self::sign_with_label(&session, &label, message)
+    }
+}
+
+/// CKA_EC_POINT of Edwards curve keys is a DER encoded OCTET STRING, though some tokens return
+/// the raw point.
+fn decode_ec_point(ec_point: &[u8]) -> &[u8] {
+    match ec_point {
+        [DER_OCTET_STRING_TAG, len, point @ ..] if *len as usize == point.len() => point,
+        _ => ec_point,
+    }
+}
+
+fn encode_ec_point(point: &[u8]) -> Vec<u8> {
+    let mut ec_point = vec![DER_OCTET_STRING_TAG, point.len() as u8];
+    ec_point.extend_from_slice(point);
+    ec_point
+}
+
+/// Returns true iff `key` is the (possibly namespaced) BLS12-381 consensus key.
+fn is_consensus_key(key: &str) -> bool {
+    key.rsplit(NAMESPACE_SEPARATOR).next().unwrap_or(key) == CONSENSUS_KEY
+}
+
+/// Fails if `key` is the consensus key, which cannot be kept on the token (see `Pkcs11Storage`).
+fn ensure_not_consensus_key(key: &str) -> Result<(), Error> {
+    if is_consensus_key(key) {
+        return Err(Error::InternalError(format!(
+            "{} cannot be stored in PKCS#11 storage: BLS12-381 signing is not supported by the \
+             token, so the private key would have to leave it",
+            key
+        )));
+    }
+    Ok(())
+}
+
+/// Private helper method to get the name of the previous version of the given key pair, matching
+/// `CryptoKVStorage`.
+fn get_previous_version_name(name: &str) -> String {
+    format!("{}_previous", name)
+}
+
+/// Private helper method to get the name of the data storage entry recording when the given key
+/// pair was last updated.
+fn get_last_update_name(name: &str) -> String {
+    format!("{}_pkcs11_public_key", name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ec_point_encoding() {
+        let point = [7u8; 32];
+        let encoded = encode_ec_point(&point);
+        assert_eq!(encoded.len(), 34);
+        assert_eq!(decode_ec_point(&encoded), &point);
+        // Raw points are passed through
+        assert_eq!(decode_ec_point(&point), &point);
+    }
+
+    #[test]
+    fn test_consensus_key() {
+        assert!(is_consensus_key(CONSENSUS_KEY));
+        assert!(is_consensus_key(&format!(
+            "safety_rules{}{}",
+            NAMESPACE_SEPARATOR, CONSENSUS_KEY
+        )));
+        assert!(!is_consensus_key("safety_data"));
+        assert!(!is_consensus_key("consensus_previous"));
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{tests::suite, EncryptedOnDiskStorage, Error, KVStorage, Storage};
+use aptos_temppath::TempPath;
+use std::fs;
+
+/// Keeps the tests fast, the number of iterations does not affect correctness.
+const KDF_ITERATIONS: u32 = 1_000;
+const SECRET: &[u8] = b"correct horse battery staple";
+const OTHER_SECRET: &[u8] = b"hunter2";
+const KEY: &str = "consensus_key";
+
+fn storage(temp_path: &TempPath, secret: &[u8]) -> EncryptedOnDiskStorage {
+    EncryptedOnDiskStorage::new_with_kdf_iterations(
+        temp_path.path().to_path_buf(),
+        secret.to_vec(),
+        KDF_ITERATIONS,
+    )
+}
+
+#[test]
+fn encrypted_on_disk() {
+    let temp_path = TempPath::new();
+    let mut storage = Storage::from(storage(&temp_path, SECRET));
+    suite::execute_all_storage_tests(&mut storage);
+}
+
+#[test]
+fn test_contents_are_encrypted() {
+    let temp_path = TempPath::new();
+    storage(&temp_path, SECRET)
+        .set(KEY, "a_very_recognizable_value")
+        .unwrap();
+
+    let contents = fs::read_to_string(temp_path.path()).unwrap();
+    assert!(!contents.contains(KEY));
+    assert!(!contents.contains("a_very_recognizable_value"));
+}
+
+#[test]
+fn test_reopen() {
+    let temp_path = TempPath::new();
+    storage(&temp_path, SECRET).set(KEY, 5u64).unwrap();
+    assert_eq!(
+        storage(&temp_path, SECRET).get::<u64>(KEY).unwrap().value,
+        5
+    );
+}
+
+#[test]
+fn test_wrong_secret() {
+    let temp_path = TempPath::new();
+    storage(&temp_path, SECRET).set(KEY, 5u64).unwrap();
+
+    let mut storage = storage(&temp_path, OTHER_SECRET);
+    assert!(matches!(
+        storage.get::<u64>(KEY),
+        Err(Error::DecryptionError(_))
+    ));
+    assert!(matches!(
+        storage.set(KEY, 6u64),
+        Err(Error::DecryptionError(_))
+    ));
+}
+
+#[test]
+fn test_tampered_file() {
+    let temp_path = TempPath::new();
+    storage(&temp_path, SECRET).set(KEY, 5u64).unwrap();
+
+    // Changing the KDF parameters in the header must be detected.
+    let contents = fs::read_to_string(temp_path.path()).unwrap().replace(
+        &format!("\"kdf_iterations\":{}", KDF_ITERATIONS),
+        &format!("\"kdf_iterations\":{}", KDF_ITERATIONS + 1),
+    );
+    fs::write(temp_path.path(), contents).unwrap();
+    assert!(matches!(
+        storage(&temp_path, SECRET).get::<u64>(KEY),
+        Err(Error::DecryptionError(_))
+    ));
+}
+
+#[test]
+fn test_rotate_encryption_key() {
+    let temp_path = TempPath::new();
+    let mut storage = storage(&temp_path, SECRET);
+    storage.set(KEY, 5u64).unwrap();
+    storage
+        .rotate_encryption_key(OTHER_SECRET.to_vec())
+        .unwrap();
+    assert_eq!(storage.get::<u64>(KEY).unwrap().value, 5);
+
+    assert!(matches!(
+        self::storage(&temp_path, SECRET).get::<u64>(KEY),
+        Err(Error::DecryptionError(_))
+    ));
+    assert_eq!(
+        self::storage(&temp_path, OTHER_SECRET)
+            .get::<u64>(KEY)
+            .unwrap()
+            .value,
+        5
+    );
+}
+
+#[test]
+fn test_new_with_key_rotation() {
+    let temp_path = TempPath::new();
+    storage(&temp_path, OTHER_SECRET).set(KEY, 5u64).unwrap();
+
+    // The first open re-encrypts the file, subsequent ones find it already rotated.
+    for _ in 0..2 {
+        let storage = EncryptedOnDiskStorage::new_with_key_rotation_and_kdf_iterations(
+            temp_path.path().to_path_buf(),
+            SECRET.to_vec(),
+            OTHER_SECRET.to_vec(),
+            KDF_ITERATIONS,
+        );
+        assert_eq!(storage.get::<u64>(KEY).unwrap().value, 5);
+    }
+    assert!(matches!(
+        storage(&temp_path, OTHER_SECRET).get::<u64>(KEY),
+        Err(Error::DecryptionError(_))
+    ));
+}
@@ -2,7 +2,9 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod encrypted_on_disk;
 mod in_memory;
 mod on_disk;
+mod pkcs11;
 mod suite;
 mod vault;
@@ -0,0 +1,183 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CryptoStorage, Error, InMemoryStorage, KVStorage, Namespaced, Pkcs11Storage, Storage};
+use aptos_crypto::{
+    bls12381, ed25519::Ed25519PrivateKey, test_utils::TestAptosCrypto, PrivateKey, Signature,
+    SigningKey, Uniform,
+};
+use aptos_global_constants::CONSENSUS_KEY;
+use std::{env, path::PathBuf};
+
+/// The path to the PKCS#11 library of a SoftHSM installation, e.g. /usr/lib/softhsm/libsofthsm2.so.
+const MODULE_ENV: &str = "APTOS_PKCS11_TEST_MODULE";
+const TOKEN_ENV: &str = "APTOS_PKCS11_TEST_TOKEN";
+const PIN_ENV: &str = "APTOS_PKCS11_TEST_PIN";
+
+/// Default token label and PIN, as set up by `docker/testutils/init_softhsm_token.sh`.
+const DEFAULT_TOKEN: &str = "aptos-test";
+const DEFAULT_PIN: &str = "1234";
+
+const CRYPTO_NAME: &str = "Test_Key_Name";
+const U64_KEY: &str = "U64_Key";
+const NAMESPACE: &str = "Test_Namespace";
+
+/// Tests share the same token and clear it, so they run sequentially.
+const PKCS11_TESTS: &[fn(&mut Storage)] = &[
+    test_consensus_key_is_refused,
+    test_create_sign_verify,
+    test_export_is_denied,
+    test_import_key,
+    test_key_value_delegation,
+    test_rotate_key,
+    test_sign_using_unknown_version,
+];
+
+/// A test for verifying Pkcs11Storage against SoftHSM. Initialize a token with
+/// `docker/testutils/init_softhsm_token.sh` and set `APTOS_PKCS11_TEST_MODULE` to run it with
+/// `cargo test -- --ignored`.
+#[test]
+#[ignore]
+fn execute_storage_tests_pkcs11() {
+    let module = PathBuf::from(
+        env::var(MODULE_ENV).unwrap_or_else(|_| panic!("{} must be set", MODULE_ENV)),
+    );
+    let token = env::var(TOKEN_ENV).unwrap_or_else(|_| DEFAULT_TOKEN.into());
+    let pin = env::var(PIN_ENV).unwrap_or_else(|_| DEFAULT_PIN.into());
+    let mut storage = Storage::from(
+        Pkcs11Storage::new(&module, &token, pin, Storage::from(InMemoryStorage::new())).unwrap(),
+    );
+    storage.available().unwrap();
+    storage.reset_and_clear().unwrap();
+
+    for test in PKCS11_TESTS.iter() {
+        test(&mut storage);
+        storage.reset_and_clear().unwrap();
+    }
+}
+
+fn test_consensus_key_is_refused(storage: &mut Storage) {
+    let consensus_key = bls12381::PrivateKey::generate_for_testing();
+    assert!(matches!(
+        storage.set(CONSENSUS_KEY, consensus_key.clone()),
+        Err(Error::InternalError(_))
+    ));
+    assert!(matches!(
+        storage.get::<bls12381::PrivateKey>(CONSENSUS_KEY),
+        Err(Error::InternalError(_))
+    ));
+
+    // Namespaced consensus keys are refused as well
+    let mut namespaced = Namespaced::new(NAMESPACE, &mut *storage);
+    assert!(matches!(
+        namespaced.set(CONSENSUS_KEY, consensus_key),
+        Err(Error::InternalError(_))
+    ));
+}
+
+fn test_create_sign_verify(storage: &mut Storage) {
+    let public_key = storage.create_key(CRYPTO_NAME).unwrap();
+    assert_eq!(
+        storage.get_public_key(CRYPTO_NAME).unwrap().public_key,
+        public_key
+    );
+
+    let message = TestAptosCrypto("Hello, World".to_string());
+    let signature = storage.sign(CRYPTO_NAME, &message).unwrap();
+    signature.verify(&message, &public_key).unwrap();
+}
+
+fn test_export_is_denied(storage: &mut Storage) {
+    let public_key = storage.create_key(CRYPTO_NAME).unwrap();
+    assert_eq!(
+        storage.export_private_key(CRYPTO_NAME).unwrap_err(),
+        Error::PermissionDenied
+    );
+    assert_eq!(
+        storage
+            .export_private_key_for_version(CRYPTO_NAME, public_key)
+            .unwrap_err(),
+        Error::PermissionDenied
+    );
+}
+
+fn test_import_key(storage: &mut Storage) {
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let public_key = private_key.public_key();
+    storage
+        .import_private_key(CRYPTO_NAME, private_key.clone())
+        .unwrap();
+    assert_eq!(
+        storage.get_public_key(CRYPTO_NAME).unwrap().public_key,
+        public_key
+    );
+
+    // The token must produce the same (deterministic) signature
+    let message = TestAptosCrypto("Hello, World".to_string());
+    assert_eq!(
+        storage.sign(CRYPTO_NAME, &message).unwrap(),
+        private_key.sign(&message).unwrap()
+    );
+}
+
+fn test_key_value_delegation(storage: &mut Storage) {
+    assert_eq!(
+        storage.get::<u64>(U64_KEY).unwrap_err(),
+        Error::KeyNotSet(U64_KEY.to_string())
+    );
+    storage.set(U64_KEY, 10u64).unwrap();
+    assert_eq!(storage.get::<u64>(U64_KEY).unwrap().value, 10);
+}
+
+fn test_rotate_key(storage: &mut Storage) {
+    assert!(storage
+        .get_public_key_previous_version(CRYPTO_NAME)
+        .is_err());
+    let first_key = storage.create_key(CRYPTO_NAME).unwrap();
+    let second_key = storage.rotate_key(CRYPTO_NAME).unwrap();
+    assert_ne!(first_key, second_key);
+    assert_eq!(
+        storage.get_public_key(CRYPTO_NAME).unwrap().public_key,
+        second_key
+    );
+    assert_eq!(
+        storage
+            .get_public_key_previous_version(CRYPTO_NAME)
+            .unwrap(),
+        first_key
+    );
+
+    let message = TestAptosCrypto("Hello, World".to_string());
+    for key in [&first_key, &second_key] {
+        storage
+            .sign_using_version(CRYPTO_NAME, key.clone(), &message)
+            .unwrap()
+            .verify(&message, key)
+            .unwrap();
+    }
+
+    // Only two versions are retained
+    let third_key = storage.rotate_key(CRYPTO_NAME).unwrap();
+    assert_eq!(
+        storage
+            .get_public_key_previous_version(CRYPTO_NAME)
+            .unwrap(),
+        second_key
+    );
+    assert_eq!(
+        storage.get_public_key(CRYPTO_NAME).unwrap().public_key,
+        third_key
+    );
+}
+
+fn test_sign_using_unknown_version(storage: &mut Storage) {
+    storage.create_key(CRYPTO_NAME).unwrap();
+    let unknown_key = Ed25519PrivateKey::generate_for_testing().public_key();
+    let message = TestAptosCrypto("Hello, World".to_string());
+    assert_eq!(
+        storage
+            .sign_using_version(CRYPTO_NAME, unknown_key.clone(), &message)
+            .unwrap_err(),
+        Error::KeyVersionNotFound(CRYPTO_NAME.into(), unknown_key.to_string())
+    );
+}
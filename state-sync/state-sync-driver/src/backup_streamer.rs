@@ -0,0 +1,716 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+};
+use anyhow::{anyhow, ensure};
+use aptos_backup_storage::{
+    manifest::{
+        epoch_ending::EpochEndingBackup, state_snapshot::StateSnapshotBackup,
+        transaction::TransactionBackup,
+    },
+    metadata::{
+        cache::{sync_and_load, MetadataCacheOpt},
+        view::MetadataView,
+        StateSnapshotBackupMeta,
+    },
+    storage::{
+        command_adapter::{config::CommandAdapterConfig, CommandAdapter},
+        local_fs::LocalFs,
+        BackupStorage, FileHandle,
+    },
+    utils::{read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt},
+};
+use aptos_config::config::StorageConfig;
+use aptos_crypto::{
+    hash::{CryptoHash, ACCUMULATOR_PLACEHOLDER_HASH},
+    HashValue,
+};
+use aptos_data_streaming_service::{
+    data_notification::{DataNotification, DataPayload, NotificationId},
+    data_stream::{DataStreamId, DataStreamListener},
+};
+use aptos_logger::prelude::*;
+use aptos_types::{
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{
+        accumulator::InMemoryTransactionAccumulator, SparseMerkleRangeProof,
+        TransactionAccumulatorInternalNode, TransactionAccumulatorProof,
+        TransactionAccumulatorRangeProof, TransactionInfoWithProof,
+    },
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
+    transaction::{
+        Transaction, TransactionAuxiliaryData, TransactionInfo, TransactionInfoListWithProof,
+        TransactionOutput, TransactionOutputListWithProof, TransactionStatus, Version,
+    },
+    write_set::WriteSet,
+};
+use futures::{channel::mpsc, SinkExt};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::io::BufReader;
+
+// The maximum number of pending notifications on a backup data stream
+const MAX_BACKUP_STREAM_CHANNEL_SIZE: usize = 10;
+
+/// Streams the data required to bootstrap the node from a backup storage.
+/// Data is sent as notifications on data streams, like the data streaming
+/// service does, so that the bootstrapper verifies (e.g., against the
+/// waypoint) and stores it exactly like data fetched from the network.
+///
+/// The latest state snapshot in the backup is restored, together with the
+/// epoch ending ledger infos and the transaction output required to verify
+/// it. The transaction outputs after the snapshot are then applied up to the
+/// last epoch that ends in the backed up transactions. Anything newer is
+/// synced from the network once the node has bootstrapped.
+pub struct BackupStreamer {
+    // The backup storage and its metadata (loaded on first use)
+    backup: Option<LoadedBackup>,
+
+    // The storage config (holding the backup storage config)
+    storage_config: StorageConfig,
+
+    // The id of the next data stream to create
+    next_stream_id: DataStreamId,
+}
+
+/// The backup storage and its metadata
+struct LoadedBackup {
+    storage: Arc<dyn BackupStorage>,
+    metadata_view: MetadataView,
+    latest_state_snapshot: StateSnapshotBackupMeta,
+
+    // The highest version of the continuous transaction backups
+    max_transaction_version: Version,
+
+    // The highest epoch that ends at or before the max transaction version
+    highest_epoch_end: u64,
+}
+
+impl LoadedBackup {
+    /// Returns the state snapshot at the given version. This is the latest
+    /// snapshot, unless a restore of an older snapshot is being resumed.
+    fn get_state_snapshot(&self, version: Version) -> Result<StateSnapshotBackupMeta, Error> {
+        self.metadata_view
+            .expect_state_snapshot(version)
+            .map_err(|error| Error::BackupStorageError(format!("{:?}", error)))
+    }
+}
+
+impl BackupStreamer {
+    pub fn new(storage_config: StorageConfig) -> Self {
+        Self {
+            backup: None,
+            storage_config,
+            next_stream_id: 0,
+        }
+    }
+
+    /// Returns the version of the latest state snapshot in the backup. The
+    /// snapshot is taken at the version that ends its epoch.
+    pub async fn get_state_snapshot_version(&mut self) -> Result<Version, Error> {
+        Ok(self.load_backup().await?.latest_state_snapshot.version)
+    }
+
+    /// Returns the highest epoch that ends in the backed up transactions
+    pub async fn get_highest_epoch_end(&mut self) -> Result<u64, Error> {
+        Ok(self.load_backup().await?.highest_epoch_end)
+    }
+
+    /// Streams all epoch ending ledger infos from `start_epoch` up to (and
+    /// including) the highest epoch that ends in the backed up transactions.
+    pub async fn get_all_epoch_ending_ledger_infos(
+        &mut self,
+        start_epoch: u64,
+    ) -> Result<DataStreamListener, Error> {
+        let backup = self.load_backup().await?;
+        let end_epoch = backup.highest_epoch_end;
+        let epoch_ending_backups = backup
+            .metadata_view
+            .select_epoch_ending_backups(backup.max_transaction_version)
+            .map_err(|error| {
+                Error::BackupStorageError(format!(
+                    "Failed to select the epoch ending backups! Error: {:?}",
+                    error
+                ))
+            })?;
+        let manifests = epoch_ending_backups
+            .into_iter()
+            .filter(|backup| backup.last_epoch >= start_epoch && backup.first_epoch <= end_epoch)
+            .map(|backup| backup.manifest)
+            .collect::<Vec<_>>();
+
+        let storage = backup.storage.clone();
+        Ok(self.spawn_stream(move |sender| async move {
+            stream_epoch_ending_ledger_infos(storage, manifests, start_epoch, end_epoch, sender)
+                .await
+        }))
+    }
+
+    /// Streams the transaction output (with proof) at the version of a state
+    /// snapshot in the backup.
+    pub async fn get_transaction_output(
+        &mut self,
+        version: Version,
+    ) -> Result<DataStreamListener, Error> {
+        let backup = self.load_backup().await?;
+        let state_snapshot = backup.get_state_snapshot(version)?;
+        let transaction_backups = backup
+            .metadata_view
+            .select_transaction_backups(version, version)
+            .map_err(|error| {
+                Error::BackupStorageError(format!(
+                    "Failed to select the transaction backups! Error: {:?}",
+                    error
+                ))
+            })?;
+        let transaction_manifest = transaction_backups
+            .into_iter()
+            .find(|backup| backup.first_version <= version && version <= backup.last_version)
+            .map(|backup| backup.manifest)
+            .ok_or_else(|| {
+                Error::BackupStorageError(format!(
+                    "No transaction backup found for the state snapshot version: {:?}",
+                    version
+                ))
+            })?;
+
+        let storage = backup.storage.clone();
+        let snapshot_manifest = state_snapshot.manifest;
+        Ok(self.spawn_stream(move |sender| async move {
+            stream_transaction_output(
+                storage,
+                transaction_manifest,
+                snapshot_manifest,
+                version,
+                sender,
+            )
+            .await
+        }))
+    }
+
+    /// Streams all transaction outputs (with proofs) in [`start_version`,
+    /// `end_version`]. The end version must end an epoch, as all outputs
+    /// are proven against the ledger info at the end version.
+    pub async fn get_all_transaction_outputs(
+        &mut self,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<DataStreamListener, Error> {
+        let backup = self.load_backup().await?;
+        if end_version > backup.max_transaction_version {
+            return Err(Error::BackupStorageError(format!(
+                "The end version {:?} is higher than the max transaction version {:?} in the backup!",
+                end_version, backup.max_transaction_version
+            )));
+        }
+        let transaction_backups = backup
+            .metadata_view
+            .select_transaction_backups(start_version, end_version)
+            .map_err(|error| {
+                Error::BackupStorageError(format!(
+                    "Failed to select the transaction backups! Error: {:?}",
+                    error
+                ))
+            })?;
+        let manifests = transaction_backups
+            .into_iter()
+            .map(|backup| backup.manifest)
+            .collect::<Vec<_>>();
+
+        let storage = backup.storage.clone();
+        Ok(self.spawn_stream(move |sender| async move {
+            stream_transaction_outputs(storage, manifests, start_version, end_version, sender).await
+        }))
+    }
+
+    /// Streams all state values of the state snapshot at the given version,
+    /// starting at `start_index`.
+    pub async fn get_all_state_values(
+        &mut self,
+        version: Version,
+        start_index: u64,
+    ) -> Result<DataStreamListener, Error> {
+        let backup = self.load_backup().await?;
+        let state_snapshot = backup.get_state_snapshot(version)?;
+
+        let storage = backup.storage.clone();
+        let snapshot_manifest = state_snapshot.manifest;
+        Ok(self.spawn_stream(move |sender| async move {
+            stream_state_values(storage, snapshot_manifest, start_index, sender).await
+        }))
+    }
+
+    /// Loads the backup storage and its metadata (if not already done)
+    async fn load_backup(&mut self) -> Result<&LoadedBackup, Error> {
+        if self.backup.is_none() {
+            let backup = self.load_backup_from_storage().await.map_err(|error| {
+                Error::BackupStorageError(format!(
+                    "Failed to load the backup storage! Error: {:?}",
+                    error
+                ))
+            })?;
+            info!(LogSchema::new(LogEntry::BackupStreamer).message(&format!(
+                "Loaded the backup storage! Latest state snapshot version: {:?}, epoch: {:?}. \
+                Max transaction version: {:?}, highest epoch end: {:?}",
+                backup.latest_state_snapshot.version,
+                backup.latest_state_snapshot.epoch,
+                backup.max_transaction_version,
+                backup.highest_epoch_end
+            )));
+            self.backup = Some(backup);
+        }
+        Ok(self.backup.as_ref().expect("The backup was loaded above!"))
+    }
+
+    async fn load_backup_from_storage(&self) -> anyhow::Result<LoadedBackup> {
+        let backup_storage_config = &self.storage_config.backup_storage;
+        let storage: Arc<dyn BackupStorage> = match (
+            &backup_storage_config.local_fs_dir,
+            &backup_storage_config.command_adapter_config,
+        ) {
+            (Some(local_fs_dir), None) => Arc::new(LocalFs::new(local_fs_dir.clone())),
+            (None, Some(command_adapter_config)) => Arc::new(CommandAdapter::new(
+                CommandAdapterConfig::load_from_file(command_adapter_config).await?,
+            )),
+            _ => return Err(anyhow!("Exactly one backup storage must be configured!")),
+        };
+
+        let metadata_view = sync_and_load(
+            &MetadataCacheOpt::new(Some(self.storage_config.backup_metadata_cache_dir())),
+            storage.clone(),
+            backup_storage_config.concurrent_downloads,
+        )
+        .await?;
+        // Transactions can only be restored up to the first gap in the backups
+        let max_transaction_version = metadata_view
+            .max_continuous_transaction_version()
+            .ok_or_else(|| anyhow!("No transactions found in the backup storage!"))?;
+        let latest_state_snapshot = metadata_view
+            .select_state_snapshot(max_transaction_version)?
+            .ok_or_else(|| anyhow!("No state snapshot found in the backup storage!"))?;
+
+        // Epochs that end after the backed up transactions are synced from the network
+        let highest_epoch_end = metadata_view
+            .select_epoch_ending_backups(max_transaction_version)?
+            .into_iter()
+            .filter(|backup| backup.last_version <= max_transaction_version)
+            .map(|backup| backup.last_epoch)
+            .fold(latest_state_snapshot.epoch, u64::max);
+
+        Ok(LoadedBackup {
+            storage,
+            metadata_view,
+            latest_state_snapshot,
+            max_transaction_version,
+            highest_epoch_end,
+        })
+    }
+
+    /// Creates a new data stream and spawns a task that sends the notifications
+    /// produced by `stream_data`. The stream always ends with an end of stream
+    /// notification (even if reading the backup fails), so that the bootstrapper
+    /// re-initializes the stream from its progress.
+    fn spawn_stream<F, Fut>(&mut self, stream_data: F) -> DataStreamListener
+    where
+        F: FnOnce(BackupStreamSender) -> Fut,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        let data_stream_id = self.next_stream_id;
+        self.next_stream_id += 1;
+
+        let (notification_sender, notification_receiver) =
+            mpsc::channel(MAX_BACKUP_STREAM_CHANNEL_SIZE);
+        let mut sender = BackupStreamSender::new(notification_sender);
+        let stream_data = stream_data(sender.clone());
+        tokio::spawn(async move {
+            if let Err(error) = stream_data.await {
+                error!(LogSchema::new(LogEntry::BackupStreamer).message(&format!(
+                    "Failed to stream data from the backup storage! Stream ID: {:?}, error: {:?}",
+                    data_stream_id, error
+                )));
+            }
+            // The stream may have already been dropped (e.g., if it was reset)
+            let _ = sender.send(DataPayload::EndOfStream).await;
+        });
+
+        DataStreamListener::new(data_stream_id, notification_receiver)
+    }
+}
+
+/// Sends data notifications (with unique notification IDs) on a backup stream
+#[derive(Clone)]
+struct BackupStreamSender {
+    next_notification_id: Arc<AtomicU64>,
+    notification_sender: mpsc::Sender<DataNotification>,
+}
+
+impl BackupStreamSender {
+    fn new(notification_sender: mpsc::Sender<DataNotification>) -> Self {
+        Self {
+            next_notification_id: Arc::new(AtomicU64::new(0)),
+            notification_sender,
+        }
+    }
+
+    async fn send(&mut self, data_payload: DataPayload) -> anyhow::Result<()> {
+        let notification_id = self.next_notification_id.fetch_add(1, Ordering::Relaxed);
+        self.notification_sender
+            .send(DataNotification::new(notification_id, data_payload))
+            .await
+            .map_err(|error| anyhow!("The backup stream was dropped! Error: {:?}", error))
+    }
+}
+
+/// Sends the epoch ending ledger infos in [`start_epoch`, `end_epoch`], one
+/// notification per chunk of the backups.
+async fn stream_epoch_ending_ledger_infos(
+    storage: Arc<dyn BackupStorage>,
+    manifests: Vec<FileHandle>,
+    start_epoch: u64,
+    end_epoch: u64,
+    mut sender: BackupStreamSender,
+) -> anyhow::Result<()> {
+    for manifest in manifests {
+        let manifest: EpochEndingBackup = storage.load_json_file(&manifest).await?;
+        for chunk in manifest.chunks {
+            if chunk.last_epoch < start_epoch || chunk.first_epoch > end_epoch {
+                continue;
+            }
+            let ledger_infos: Vec<LedgerInfoWithSignatures> =
+                read_records(&storage, &chunk.ledger_infos).await?;
+            let ledger_infos = ledger_infos
+                .into_iter()
+                .filter(|ledger_info| {
+                    let epoch = ledger_info.ledger_info().epoch();
+                    start_epoch <= epoch && epoch <= end_epoch
+                })
+                .collect::<Vec<_>>();
+            sender
+                .send(DataPayload::EpochEndingLedgerInfos(ledger_infos))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Sends the transaction output at `version`, proven by the state snapshot
+/// proof (i.e., against the ledger info that ends the snapshot epoch).
+async fn stream_transaction_output(
+    storage: Arc<dyn BackupStorage>,
+    transaction_manifest: FileHandle,
+    snapshot_manifest: FileHandle,
+    version: Version,
+    mut sender: BackupStreamSender,
+) -> anyhow::Result<()> {
+    // Read the transaction, events and write set at the version
+    let transaction_manifest: TransactionBackup =
+        storage.load_json_file(&transaction_manifest).await?;
+    let chunk = transaction_manifest
+        .chunks
+        .into_iter()
+        .find(|chunk| chunk.first_version <= version && version <= chunk.last_version)
+        .ok_or_else(|| anyhow!("No transaction chunk found for version: {}", version))?;
+    let records: Vec<(Transaction, TransactionInfo, Vec<ContractEvent>, WriteSet)> =
+        read_records(&storage, &chunk.transactions).await?;
+    let (transaction, transaction_info, events, write_set) = records
+        .into_iter()
+        .nth((version - chunk.first_version) as usize)
+        .ok_or_else(|| anyhow!("Transaction chunk is missing version: {}", version))?;
+
+    // Use the state snapshot proof for the transaction info
+    let snapshot_manifest: StateSnapshotBackup = storage.load_json_file(&snapshot_manifest).await?;
+    let (transaction_info_with_proof, _): (TransactionInfoWithProof, LedgerInfoWithSignatures) =
+        storage.load_bcs_file(&snapshot_manifest.proof).await?;
+    ensure!(
+        transaction_info_with_proof.transaction_info() == &transaction_info,
+        "The transaction info in the transaction backup doesn't match the state snapshot proof!"
+    );
+
+    let transaction_output = create_transaction_output(events, write_set, &transaction_info);
+    let transaction_output_list_with_proof = TransactionOutputListWithProof::new(
+        vec![(transaction, transaction_output)],
+        Some(version),
+        TransactionInfoListWithProof::new(
+            single_leaf_range_proof(
+                version,
+                transaction_info_with_proof.ledger_info_to_transaction_info_proof(),
+            ),
+            vec![transaction_info],
+        ),
+    );
+    sender
+        .send(DataPayload::TransactionOutputsWithProof(
+            transaction_output_list_with_proof,
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Sends the transaction outputs in [`start_version`, `end_version`], one
+/// notification per chunk of the backups. The chunk proofs are rebuilt to
+/// prove all outputs against the ledger info at `end_version` (which ends
+/// an epoch), as backup chunks may span epochs and their proofs may be
+/// against other ledger infos.
+async fn stream_transaction_outputs(
+    storage: Arc<dyn BackupStorage>,
+    manifests: Vec<FileHandle>,
+    start_version: Version,
+    end_version: Version,
+    mut sender: BackupStreamSender,
+) -> anyhow::Result<()> {
+    // Identify the chunks that hold the versions to stream
+    let mut chunks = vec![];
+    for manifest in manifests {
+        let manifest: TransactionBackup = storage.load_json_file(&manifest).await?;
+        chunks.extend(manifest.chunks.into_iter().filter(|chunk| {
+            chunk.last_version >= start_version && chunk.first_version <= end_version
+        }));
+    }
+    let first_chunk = chunks
+        .first()
+        .ok_or_else(|| anyhow!("No transaction chunk found for version: {}", start_version))?;
+    ensure!(
+        first_chunk.first_version <= start_version,
+        "The transaction chunks start after version: {}",
+        start_version
+    );
+
+    // Collect the transaction info hashes (i.e., the accumulator leaves) up to
+    // the end version, as the proofs require all leaves after each chunk.
+    let first_leaf_index = first_chunk.first_version;
+    let mut leaf_hashes = vec![];
+    for chunk in &chunks {
+        ensure!(
+            chunk.first_version == first_leaf_index + leaf_hashes.len() as u64,
+            "The transaction chunks are not continuous at version: {}",
+            chunk.first_version
+        );
+        let records: Vec<(Transaction, TransactionInfo, Vec<ContractEvent>, WriteSet)> =
+            read_records(&storage, &chunk.transactions).await?;
+        ensure!(
+            records.len() as u64 == chunk.last_version - chunk.first_version + 1,
+            "The transaction chunk at version {} has an unexpected number of records!",
+            chunk.first_version
+        );
+        leaf_hashes.extend(
+            records
+                .iter()
+                .map(|(_, transaction_info, _, _)| transaction_info.hash()),
+        );
+    }
+    ensure!(
+        leaf_hashes.len() as u64 > end_version - first_leaf_index,
+        "The transaction chunks end before version: {}",
+        end_version
+    );
+    leaf_hashes.truncate((end_version - first_leaf_index + 1) as usize);
+
+    // The left siblings of the first chunk proof are the frozen subtrees of
+    // the accumulator before the chunk (ordered from the leaf to the root).
+    let (first_chunk_proof, _): (TransactionAccumulatorRangeProof, LedgerInfoWithSignatures) =
+        storage.load_bcs_file(&first_chunk.proof).await?;
+    let mut frozen_subtree_roots = first_chunk_proof.left_siblings().clone();
+    frozen_subtree_roots.reverse();
+    let mut accumulator =
+        InMemoryTransactionAccumulator::new(frozen_subtree_roots, first_leaf_index)?
+            .append(&leaf_hashes[..(start_version - first_leaf_index) as usize]);
+
+    for chunk in chunks {
+        let first_version = chunk.first_version.max(start_version);
+        let last_version = chunk.last_version.min(end_version);
+        let records: Vec<(Transaction, TransactionInfo, Vec<ContractEvent>, WriteSet)> =
+            read_records(&storage, &chunk.transactions).await?;
+        let (transactions_and_outputs, transaction_infos): (Vec<_>, Vec<_>) = records
+            .into_iter()
+            .skip((first_version - chunk.first_version) as usize)
+            .take((last_version - first_version + 1) as usize)
+            .map(|(transaction, transaction_info, events, write_set)| {
+                let transaction_output =
+                    create_transaction_output(events, write_set, &transaction_info);
+                ((transaction, transaction_output), transaction_info)
+            })
+            .unzip();
+
+        let proof = range_proof(
+            &accumulator,
+            last_version,
+            &leaf_hashes[(last_version - first_leaf_index + 1) as usize..],
+        );
+        accumulator = accumulator.append(
+            &leaf_hashes[(first_version - first_leaf_index) as usize
+                ..=(last_version - first_leaf_index) as usize],
+        );
+
+        let transaction_output_list_with_proof = TransactionOutputListWithProof::new(
+            transactions_and_outputs,
+            Some(first_version),
+            TransactionInfoListWithProof::new(proof, transaction_infos),
+        );
+        sender
+            .send(DataPayload::TransactionOutputsWithProof(
+                transaction_output_list_with_proof,
+            ))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Sends the state values of the state snapshot, one notification per chunk
+/// of the backup. The first chunk is truncated to start at `start_index`.
+async fn stream_state_values(
+    storage: Arc<dyn BackupStorage>,
+    snapshot_manifest: FileHandle,
+    start_index: u64,
+    mut sender: BackupStreamSender,
+) -> anyhow::Result<()> {
+    let snapshot_manifest: StateSnapshotBackup = storage.load_json_file(&snapshot_manifest).await?;
+    for chunk in snapshot_manifest.chunks {
+        let (first_index, last_index) = (chunk.first_idx as u64, chunk.last_idx as u64);
+        if last_index < start_index {
+            continue;
+        }
+
+        // Drop the state values that have already been processed. The proof
+        // only covers the right side of the chunk, so it still holds.
+        let raw_values: Vec<(StateKey, StateValue)> = read_records(&storage, &chunk.blobs).await?;
+        let num_to_skip = start_index.saturating_sub(first_index);
+        let raw_values = raw_values
+            .into_iter()
+            .skip(num_to_skip as usize)
+            .collect::<Vec<_>>();
+        let first_key = raw_values
+            .first()
+            .map(|(state_key, _)| state_key.hash())
+            .ok_or_else(|| anyhow!("Empty state snapshot chunk at index: {}", first_index))?;
+        let proof: SparseMerkleRangeProof = storage.load_bcs_file(&chunk.proof).await?;
+
+        let state_value_chunk_with_proof = StateValueChunkWithProof {
+            first_index: first_index + num_to_skip,
+            last_index,
+            first_key,
+            last_key: chunk.last_key,
+            raw_values,
+            proof,
+            root_hash: snapshot_manifest.root_hash,
+        };
+        sender
+            .send(DataPayload::StateValuesWithProof(
+                state_value_chunk_with_proof,
+            ))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Reads all BCS serialized records in the given file
+async fn read_records<T: serde::de::DeserializeOwned>(
+    storage: &Arc<dyn BackupStorage>,
+    file_handle: &FileHandle,
+) -> anyhow::Result<Vec<T>> {
+    let mut file = BufReader::new(storage.open_for_read(file_handle).await?);
+    let mut records = vec![];
+    while let Some(record_bytes) = file.read_record_bytes().await? {
+        records.push(bcs::from_bytes(&record_bytes)?);
+    }
+    Ok(records)
+}
+
+/// Creates the transaction output of a backed up transaction
+fn create_transaction_output(
+    events: Vec<ContractEvent>,
+    write_set: WriteSet,
+    transaction_info: &TransactionInfo,
+) -> TransactionOutput {
+    TransactionOutput::new(
+        write_set,
+        events,
+        transaction_info.gas_used(),
+        TransactionStatus::Keep(transaction_info.status().clone()),
+        TransactionAuxiliaryData::default(),
+    )
+}
+
+/// Returns the range proof for the leaves from `accumulator.num_leaves()` to
+/// `last_leaf_index` (inclusive). The proof is for the accumulator holding
+/// the leaves of `accumulator`, the range and then `leaf_hashes_after` (i.e.,
+/// all leaves after the range).
+pub(crate) fn range_proof(
+    accumulator: &InMemoryTransactionAccumulator,
+    last_leaf_index: u64,
+    leaf_hashes_after: &[HashValue],
+) -> TransactionAccumulatorRangeProof {
+    // The left siblings are the frozen subtrees before the range
+    let left_siblings = accumulator
+        .frozen_subtree_roots()
+        .iter()
+        .rev()
+        .cloned()
+        .collect();
+
+    // The right siblings are the subtrees after the range, up to the root
+    let num_leaves = last_leaf_index + 1 + leaf_hashes_after.len() as u64;
+    let root_level = num_leaves.next_power_of_two().trailing_zeros();
+    let mut right_siblings = vec![];
+    for level in 0..root_level {
+        let node_index = last_leaf_index >> level;
+        if node_index & 1 == 0 {
+            let first_leaf = (((node_index + 1) << level) - last_leaf_index - 1) as usize;
+            let last_leaf = (first_leaf + (1 << level)).min(leaf_hashes_after.len());
+            right_siblings.push(subtree_hash(
+                level,
+                &leaf_hashes_after[first_leaf.min(last_leaf)..last_leaf],
+            ));
+        }
+    }
+
+    TransactionAccumulatorRangeProof::new(left_siblings, right_siblings)
+}
+
+/// Returns the hash of the subtree at the given level that holds the given
+/// leaves (from the left). Missing leaves are placeholders.
+fn subtree_hash(level: u32, leaf_hashes: &[HashValue]) -> HashValue {
+    if leaf_hashes.is_empty() {
+        *ACCUMULATOR_PLACEHOLDER_HASH
+    } else if level == 0 {
+        leaf_hashes[0]
+    } else {
+        let (left, right) = leaf_hashes.split_at(leaf_hashes.len().min(1 << (level - 1)));
+        TransactionAccumulatorInternalNode::new(
+            subtree_hash(level - 1, left),
+            subtree_hash(level - 1, right),
+        )
+        .hash()
+    }
+}
+
+/// Converts the accumulator proof of a single leaf into a range proof (for
+/// a range holding only that leaf). The accumulator proof siblings are ordered
+/// from the leaf to the root, and each one is on the left of the path iff the
+/// corresponding bit of the leaf index is set.
+pub(crate) fn single_leaf_range_proof(
+    leaf_index: u64,
+    accumulator_proof: &TransactionAccumulatorProof,
+) -> TransactionAccumulatorRangeProof {
+    let mut left_siblings = vec![];
+    let mut right_siblings = vec![];
+    for (level, sibling) in accumulator_proof.siblings().iter().enumerate() {
+        if (leaf_index >> level) & 1 == 1 {
+            left_siblings.push(*sibling);
+        } else {
+            right_siblings.push(*sibling);
+        }
+    }
+    TransactionAccumulatorRangeProof::new(left_siblings, right_siblings)
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backup_streamer::BackupStreamer,
     driver::DriverConfiguration,
     error::Error,
     logging::{LogEntry, LogSchema},
@@ -288,9 +289,13 @@ impl StateValueSyncer {
 
 /// A simple component that manages the bootstrapping of the node
 pub struct Bootstrapper<MetadataStorage, StorageSyncer, StreamingClient> {
-    // The currently active data stream (provided by the data streaming service)
+    // The currently active data stream (provided by the data streaming service
+    // or the backup streamer)
     active_data_stream: Option<DataStreamListener>,
 
+    // The streamer used to read from the backup storage (if restoring from a backup)
+    backup_streamer: Option<BackupStreamer>,
+
     // The channel used to notify a listener of successful bootstrapping
     bootstrap_notifier_channel: Option<oneshot::Sender<Result<(), Error>>>,
 
@@ -338,6 +343,7 @@ impl<
         streaming_client: StreamingClient,
         storage: Arc<dyn DbReader>,
         storage_synchronizer: StorageSyncer,
+        backup_streamer: Option<BackupStreamer>,
     ) -> Self {
         // Load the latest epoch state from storage
         let latest_epoch_state = utils::fetch_latest_epoch_state(storage.clone())
@@ -347,6 +353,7 @@ impl<
         Self {
             state_value_syncer: StateValueSyncer::new(),
             active_data_stream: None,
+            backup_streamer,
             bootstrap_notifier_channel: None,
             bootstrapped: false,
             driver_configuration,
@@ -450,6 +457,13 @@ impl<
             || !self.verified_epoch_states.verified_waypoint()
     }
 
+    /// Returns true iff the node is fast syncing a state snapshot (and not
+    /// applying the transaction outputs after a state snapshot restored from
+    /// a backup).
+    fn is_syncing_state_snapshot(&self) -> bool {
+        self.get_bootstrapping_mode().is_fast_sync() && self.speculative_stream_state.is_none()
+    }
+
     /// Initializes an active data stream so that we can begin to process notifications
     async fn initialize_active_data_stream(
         &mut self,
@@ -460,9 +474,12 @@ impl<
 
         // Always fetch the new epoch ending ledger infos first
         if self.should_fetch_epoch_ending_ledger_infos() {
-            return self
-                .fetch_epoch_ending_ledger_infos(global_data_summary)
-                .await;
+            return if self.backup_streamer.is_some() {
+                self.fetch_epoch_ending_ledger_infos_from_backup().await
+            } else {
+                self.fetch_epoch_ending_ledger_infos(global_data_summary)
+                    .await
+            };
         }
 
         // Get the highest synced and known ledger info versions
@@ -497,8 +514,11 @@ impl<
             );
         );
 
-        // Bootstrap according to the mode
-        if self.get_bootstrapping_mode().is_fast_sync() {
+        // Bootstrap according to the mode. When restoring from a backup, the
+        // backed up transaction outputs are applied after the state snapshot.
+        let restored_state_snapshot = self.get_bootstrapping_mode().is_restore_from_backup()
+            && highest_synced_version != GENESIS_TRANSACTION_VERSION;
+        if self.get_bootstrapping_mode().is_fast_sync() && !restored_state_snapshot {
             // We're fast syncing
             self.fetch_missing_state_snapshot_data(
                 highest_synced_version,
@@ -506,7 +526,7 @@ impl<
             )
             .await
         } else {
-            // We're transaction and/or output syncing
+            // We're transaction and/or output syncing (or applying backed up outputs)
             self.fetch_missing_transaction_data(highest_synced_version, highest_known_ledger_info)
                 .await
         }
@@ -542,6 +562,11 @@ impl<
                     // Continue snapshot syncing to the target
                     self.fetch_missing_state_values(target, true).await
                 }
+            } else if self.backup_streamer.is_some() {
+                // No snapshot sync has started. Start a new sync for the state snapshot in the backup.
+                let target_ledger_info = self.get_backup_state_snapshot_ledger_info().await?;
+                self.fetch_missing_state_values(target_ledger_info, false)
+                    .await
             } else {
                 // No snapshot sync has started. Start a new sync for the highest known ledger info.
                 self.fetch_missing_state_values(highest_known_ledger_info, false)
@@ -685,13 +710,19 @@ impl<
         let target_ledger_info_version = target_ledger_info.ledger_info().version();
         let data_stream = if self.state_value_syncer.transaction_output_to_sync.is_none() {
             // Fetch the transaction info first, before the states
-            self.streaming_client
-                .get_all_transaction_outputs(
-                    target_ledger_info_version,
-                    target_ledger_info_version,
-                    target_ledger_info_version,
-                )
-                .await?
+            if let Some(backup_streamer) = self.backup_streamer.as_mut() {
+                backup_streamer
+                    .get_transaction_output(target_ledger_info_version)
+                    .await?
+            } else {
+                self.streaming_client
+                    .get_all_transaction_outputs(
+                        target_ledger_info_version,
+                        target_ledger_info_version,
+                        target_ledger_info_version,
+                    )
+                    .await?
+            }
         } else {
             // Identify the next state index to fetch
             let next_state_index_to_process = if existing_snapshot_progress {
@@ -715,12 +746,18 @@ impl<
             // Fetch the missing state values
             self.state_value_syncer
                 .update_next_state_index_to_process(next_state_index_to_process);
-            self.streaming_client
-                .get_all_state_values(
-                    target_ledger_info_version,
-                    Some(next_state_index_to_process),
-                )
-                .await?
+            if let Some(backup_streamer) = self.backup_streamer.as_mut() {
+                backup_streamer
+                    .get_all_state_values(target_ledger_info_version, next_state_index_to_process)
+                    .await?
+            } else {
+                self.streaming_client
+                    .get_all_state_values(
+                        target_ledger_info_version,
+                        Some(next_state_index_to_process),
+                    )
+                    .await?
+            }
         };
         self.active_data_stream = Some(data_stream);
 
@@ -743,6 +780,7 @@ impl<
             .ok_or_else(|| {
                 Error::UnexpectedError("No higher epoch ending version known!".into())
             })?;
+        let mut proof_ledger_info = highest_known_ledger_info;
         let data_stream = match self.get_bootstrapping_mode() {
             BootstrappingMode::ApplyTransactionOutputsFromGenesis => {
                 self.streaming_client
@@ -793,13 +831,28 @@ impl<
                         .await?
                 }
             },
+            BootstrappingMode::RestoreFromBackup => {
+                // The backed up outputs are proven against the ledger info that ends the epoch
+                proof_ledger_info = self
+                    .verified_epoch_states
+                    .get_epoch_ending_ledger_info(end_version)
+                    .ok_or_else(|| {
+                        Error::UnexpectedError(format!(
+                            "The epoch ending ledger info for version {:?} is missing!",
+                            end_version
+                        ))
+                    })?;
+                self.get_backup_streamer()?
+                    .get_all_transaction_outputs(next_version, end_version)
+                    .await?
+            },
             bootstrapping_mode => {
                 unreachable!("Bootstrapping mode not supported: {:?}", bootstrapping_mode)
             },
         };
         self.speculative_stream_state = Some(SpeculativeStreamState::new(
             utils::fetch_latest_epoch_state(self.storage.clone())?,
-            Some(highest_known_ledger_info),
+            Some(proof_ledger_info),
             highest_synced_version,
         ));
         self.active_data_stream = Some(data_stream);
@@ -827,20 +880,7 @@ impl<
             })?;
 
         // Fetch the highest epoch end known locally
-        let highest_known_ledger_info = self.get_highest_known_ledger_info()?;
-        let highest_known_ledger_info = highest_known_ledger_info.ledger_info();
-        let highest_local_epoch_end = if highest_known_ledger_info.ends_epoch() {
-            highest_known_ledger_info.epoch()
-        } else if highest_known_ledger_info.epoch() > 0 {
-            highest_known_ledger_info
-                .epoch()
-                .checked_sub(1)
-                .ok_or_else(|| {
-                    Error::IntegerOverflow("The highest local epoch end has overflown!".into())
-                })?
-        } else {
-            unreachable!("Genesis should always end the first epoch!");
-        };
+        let highest_local_epoch_end = self.get_highest_local_epoch_end()?;
 
         // Compare the highest local epoch end to the highest advertised epoch end
         if highest_local_epoch_end < highest_advertised_epoch_end {
@@ -873,6 +913,81 @@ impl<
         Ok(())
     }
 
+    /// Fetches all epoch ending ledger infos (from the current epoch to the
+    /// highest epoch that ends in the backed up transactions) from the backup
+    /// storage.
+    async fn fetch_epoch_ending_ledger_infos_from_backup(&mut self) -> Result<(), Error> {
+        // If our storage has already synced beyond our waypoint, it's verified
+        self.verify_waypoint_is_synced()?;
+
+        // Get the highest epoch end in the backup
+        let highest_backup_epoch_end = self.get_backup_streamer()?.get_highest_epoch_end().await?;
+
+        // Compare the highest local epoch end to the highest epoch end in the backup
+        let highest_local_epoch_end = self.get_highest_local_epoch_end()?;
+        if highest_local_epoch_end < highest_backup_epoch_end {
+            info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                "Found higher epoch ending ledger infos in the backup! Local: {:?}, backup: {:?}",
+                highest_local_epoch_end, highest_backup_epoch_end
+            )));
+            let next_epoch_end = highest_local_epoch_end.checked_add(1).ok_or_else(|| {
+                Error::IntegerOverflow("The next epoch end has overflown!".into())
+            })?;
+            let epoch_ending_stream = self
+                .get_backup_streamer()?
+                .get_all_epoch_ending_ledger_infos(next_epoch_end)
+                .await?;
+            self.active_data_stream = Some(epoch_ending_stream);
+        } else if self.verified_epoch_states.verified_waypoint() {
+            info!(LogSchema::new(LogEntry::Bootstrapper).message(
+                "No new epoch ending ledger infos to fetch! Reached the highest epoch end in the backup!"
+            ));
+            self.verified_epoch_states
+                .set_fetched_epoch_ending_ledger_infos();
+        } else {
+            return Err(Error::UnsatisfiableWaypoint(format!(
+                "Our waypoint is unverified, but the backup has no higher epoch ending ledger infos! \
+                Highest local epoch end: {:?}, highest backup epoch end: {:?}, waypoint version: {:?}",
+                highest_local_epoch_end,
+                highest_backup_epoch_end,
+                self.driver_configuration.waypoint.version()
+            )));
+        };
+
+        Ok(())
+    }
+
+    /// Returns the highest epoch end known locally (including the newly fetched ones)
+    fn get_highest_local_epoch_end(&self) -> Result<u64, Error> {
+        let highest_known_ledger_info = self.get_highest_known_ledger_info()?;
+        let highest_known_ledger_info = highest_known_ledger_info.ledger_info();
+        if highest_known_ledger_info.ends_epoch() {
+            Ok(highest_known_ledger_info.epoch())
+        } else if highest_known_ledger_info.epoch() > 0 {
+            highest_known_ledger_info
+                .epoch()
+                .checked_sub(1)
+                .ok_or_else(|| {
+                    Error::IntegerOverflow("The highest local epoch end has overflown!".into())
+                })
+        } else {
+            unreachable!("Genesis should always end the first epoch!");
+        }
+    }
+
+    /// Marks our waypoint as verified if our storage has already synced beyond
+    /// it. Returns true iff the waypoint was marked as verified.
+    fn verify_waypoint_is_synced(&mut self) -> Result<bool, Error> {
+        let latest_ledger_info = utils::fetch_latest_synced_ledger_info(self.storage.clone())?;
+        let waypoint_version = self.driver_configuration.waypoint.version();
+        if latest_ledger_info.ledger_info().version() >= waypoint_version {
+            self.verified_epoch_states
+                .set_verified_waypoint(waypoint_version);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
     /// Verifies that connected peers have advertised data beyond our waypoint
     /// or that our waypoint is trivially satisfiable.
     fn verify_waypoint_is_satisfiable(
@@ -880,13 +995,10 @@ impl<
         global_data_summary: &GlobalDataSummary,
     ) -> Result<(), Error> {
         // If our storage has already synced beyond our waypoint, nothing needs to be checked
-        let latest_ledger_info = utils::fetch_latest_synced_ledger_info(self.storage.clone())?;
-        let waypoint_version = self.driver_configuration.waypoint.version();
-        if latest_ledger_info.ledger_info().version() >= waypoint_version {
-            self.verified_epoch_states
-                .set_verified_waypoint(waypoint_version);
+        if self.verify_waypoint_is_synced()? {
             return Ok(());
         }
+        let waypoint_version = self.driver_configuration.waypoint.version();
 
         // Get the highest advertised synced ledger info version
         let highest_advertised_ledger_info = global_data_summary
@@ -963,8 +1075,7 @@ impl<
         state_value_chunk_with_proof: StateValueChunkWithProof,
     ) -> Result<(), Error> {
         // Verify that we're expecting state value payloads
-        let bootstrapping_mode = self.get_bootstrapping_mode();
-        if self.should_fetch_epoch_ending_ledger_infos() || !bootstrapping_mode.is_fast_sync() {
+        if self.should_fetch_epoch_ending_ledger_infos() || !self.is_syncing_state_snapshot() {
             self.reset_active_stream(Some(NotificationAndFeedback::new(
                 notification_id,
                 NotificationFeedback::InvalidPayloadData,
@@ -1119,7 +1230,7 @@ impl<
         // Verify that we're expecting transaction or output payloads
         let bootstrapping_mode = self.get_bootstrapping_mode();
         if self.should_fetch_epoch_ending_ledger_infos()
-            || (bootstrapping_mode.is_fast_sync()
+            || (self.is_syncing_state_snapshot()
                 && self.state_value_syncer.transaction_output_to_sync.is_some())
         {
             self.reset_active_stream(Some(NotificationAndFeedback::new(
@@ -1133,7 +1244,7 @@ impl<
         }
 
         // If we're fast syncing, we expect a single transaction info
-        if bootstrapping_mode.is_fast_sync() {
+        if self.is_syncing_state_snapshot() {
             return self
                 .verify_transaction_info_to_sync(
                     notification_metadata.notification_id,
@@ -1172,7 +1283,8 @@ impl<
 
         // Execute/apply and commit the transactions/outputs
         let num_transactions_or_outputs = match bootstrapping_mode {
-            BootstrappingMode::ApplyTransactionOutputsFromGenesis => {
+            BootstrappingMode::ApplyTransactionOutputsFromGenesis
+            | BootstrappingMode::RestoreFromBackup => {
                 if let Some(transaction_outputs_with_proof) = transaction_outputs_with_proof {
                     utils::apply_transaction_outputs(
                         self.storage_synchronizer.clone(),
@@ -1371,7 +1483,8 @@ impl<
     ) -> Result<Option<LedgerInfoWithSignatures>, Error> {
         // Calculate the payload end version
         let num_versions = match self.get_bootstrapping_mode() {
-            BootstrappingMode::ApplyTransactionOutputsFromGenesis => {
+            BootstrappingMode::ApplyTransactionOutputsFromGenesis
+            | BootstrappingMode::RestoreFromBackup => {
                 if let Some(transaction_outputs_with_proof) = transaction_outputs_with_proof {
                     transaction_outputs_with_proof
                         .transactions_and_outputs
@@ -1479,6 +1592,32 @@ impl<
         }
     }
 
+    /// Returns the backup streamer
+    fn get_backup_streamer(&mut self) -> Result<&mut BackupStreamer, Error> {
+        self.backup_streamer
+            .as_mut()
+            .ok_or_else(|| Error::UnexpectedError("The backup streamer does not exist!".into()))
+    }
+
+    /// Returns the epoch ending ledger info at the version of the state
+    /// snapshot in the backup
+    async fn get_backup_state_snapshot_ledger_info(
+        &mut self,
+    ) -> Result<LedgerInfoWithSignatures, Error> {
+        let snapshot_version = self
+            .get_backup_streamer()?
+            .get_state_snapshot_version()
+            .await?;
+        self.verified_epoch_states
+            .get_epoch_ending_ledger_info(snapshot_version)
+            .ok_or_else(|| {
+                Error::UnexpectedError(format!(
+                    "The epoch ending ledger info for the state snapshot version {:?} is missing!",
+                    snapshot_version
+                ))
+            })
+    }
+
     /// Returns the speculative stream state
     fn get_speculative_stream_state(&mut self) -> Result<&mut SpeculativeStreamState, Error> {
         self.speculative_stream_state.as_mut().ok_or_else(|| {
@@ -1531,14 +1670,17 @@ impl<
         &mut self,
         notification_and_feedback: Option<NotificationAndFeedback>,
     ) -> Result<(), Error> {
+        // Streams from the backup storage are terminated by dropping them
         if let Some(active_data_stream) = &self.active_data_stream {
-            let data_stream_id = active_data_stream.data_stream_id;
-            utils::terminate_stream_with_feedback(
-                &mut self.streaming_client,
-                data_stream_id,
-                notification_and_feedback,
-            )
-            .await?;
+            if self.backup_streamer.is_none() {
+                let data_stream_id = active_data_stream.data_stream_id;
+                utils::terminate_stream_with_feedback(
+                    &mut self.streaming_client,
+                    data_stream_id,
+                    notification_and_feedback,
+                )
+                .await?;
+            }
         }
 
         self.active_data_stream = None;
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backup_streamer::BackupStreamer,
     driver::{DriverConfiguration, StateSyncDriver},
     driver_client::{ClientNotificationListener, DriverClient, DriverNotification},
     metadata_storage::MetadataStorageInterface,
@@ -162,6 +163,18 @@ impl DriverFactory {
             waypoint,
         );
 
+        // Create the backup streamer (if the node bootstraps from a backup)
+        let backup_streamer = if node_config
+            .state_sync
+            .state_sync_driver
+            .bootstrapping_mode
+            .is_restore_from_backup()
+        {
+            Some(BackupStreamer::new(node_config.storage.clone()))
+        } else {
+            None
+        };
+
         // Create the state sync driver
         let state_sync_driver = StateSyncDriver::new(
             client_notification_listener,
@@ -178,6 +191,7 @@ impl DriverFactory {
             streaming_service_client,
             storage.reader,
             time_service,
+            backup_streamer,
         );
 
         // Spawn the driver
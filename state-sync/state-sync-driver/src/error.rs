@@ -13,6 +13,8 @@ pub enum Error {
     AlreadyBootstrapped(String),
     #[error("Advertised data error: {0}")]
     AdvertisedDataError(String),
+    #[error("Failed to read from the backup storage: {0}")]
+    BackupStorageError(String),
     #[error("State sync has not yet finished bootstrapping! Error: {0}")]
     BootstrapNotComplete(String),
     #[error("Failed to send callback: {0}")]
@@ -55,6 +57,7 @@ impl Error {
         match self {
             Error::AlreadyBootstrapped(_) => "already_boostrapped",
             Error::AdvertisedDataError(_) => "advertised_data_error",
+            Error::BackupStorageError(_) => "backup_storage_error",
             Error::BootstrapNotComplete(_) => "bootstrap_not_complete",
             Error::CallbackSendFailed(_) => "callback_send_failed",
             Error::CriticalDataStreamTimeout(_) => "critical_data_stream_timeout",
@@ -4,6 +4,7 @@
 
 #![forbid(unsafe_code)]
 
+mod backup_streamer;
 mod bootstrapper;
 mod continuous_syncer;
 mod driver;
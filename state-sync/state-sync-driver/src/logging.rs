@@ -28,6 +28,7 @@ impl<'a> LogSchema<'a> {
 #[serde(rename_all = "snake_case")]
 pub enum LogEntry {
     AutoBootstrapping,
+    BackupStreamer,
     Bootstrapper,
     ClientNotification,
     ConsensusNotification,
@@ -0,0 +1,293 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::backup_streamer::{range_proof, single_leaf_range_proof, BackupStreamer};
+use aptos_backup_storage::{
+    manifest::transaction::{TransactionBackup, TransactionChunk},
+    metadata::Metadata,
+    storage::{local_fs::LocalFs, BackupStorage},
+};
+use aptos_config::config::StorageConfig;
+use aptos_crypto::{
+    hash::{CryptoHash, TransactionAccumulatorHasher, ACCUMULATOR_PLACEHOLDER_HASH},
+    HashValue,
+};
+use aptos_data_streaming_service::data_notification::DataPayload;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    contract_event::ContractEvent,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    proof::{
+        accumulator::InMemoryTransactionAccumulator, MerkleTreeInternalNode,
+        TransactionAccumulatorProof,
+    },
+    transaction::{ExecutionStatus, Transaction, TransactionInfo},
+    write_set::WriteSet,
+};
+use futures::StreamExt;
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+
+#[test]
+fn test_range_proof() {
+    // Create the leaves of an accumulator
+    let num_leaves = 21;
+    let leaves: Vec<_> = (0..num_leaves).map(|_| HashValue::random()).collect();
+    let root_hash = InMemoryTransactionAccumulator::from_leaves(&leaves).root_hash();
+
+    // Verify the range proofs for all ranges of leaves
+    for first_leaf_index in 0..num_leaves {
+        let accumulator = InMemoryTransactionAccumulator::from_leaves(&leaves[..first_leaf_index]);
+        for last_leaf_index in first_leaf_index..num_leaves {
+            let range_proof = range_proof(
+                &accumulator,
+                last_leaf_index as u64,
+                &leaves[last_leaf_index + 1..],
+            );
+            range_proof
+                .verify(
+                    root_hash,
+                    Some(first_leaf_index as u64),
+                    &leaves[first_leaf_index..=last_leaf_index],
+                )
+                .unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_get_all_transaction_outputs() {
+    // Create a local backup with transactions in chunks of 7
+    let num_transactions = 30;
+    let backup_dir = TempPath::new();
+    let transaction_infos =
+        create_local_transaction_backup(backup_dir.path(), num_transactions, 7).await;
+    let leaves: Vec<_> = transaction_infos.iter().map(CryptoHash::hash).collect();
+
+    // Create the backup streamer
+    let data_dir = TempPath::new();
+    let mut storage_config = StorageConfig::default();
+    storage_config.set_data_dir(data_dir.path().to_path_buf());
+    storage_config.backup_storage.local_fs_dir = Some(backup_dir.path().to_path_buf());
+    let mut backup_streamer = BackupStreamer::new(storage_config);
+
+    // Stream the outputs for ranges that start and end within or at the edges of chunks
+    for (start_version, end_version) in [(0, 29), (1, 6), (7, 13), (5, 20), (12, 12), (22, 27)] {
+        // Create the (epoch ending) ledger info at the end version
+        let root_hash =
+            InMemoryTransactionAccumulator::from_leaves(&leaves[..=end_version as usize])
+                .root_hash();
+        let block_info = BlockInfo::new(1, 0, HashValue::zero(), root_hash, end_version, 0, None);
+        let ledger_info = LedgerInfo::new(block_info, HashValue::zero());
+
+        // Verify all outputs are streamed and proven against the ledger info
+        let mut data_stream = backup_streamer
+            .get_all_transaction_outputs(start_version, end_version)
+            .await
+            .unwrap();
+        let mut next_version = start_version;
+        loop {
+            match data_stream.next().await.unwrap().data_payload {
+                DataPayload::TransactionOutputsWithProof(outputs_with_proof) => {
+                    outputs_with_proof
+                        .verify(&ledger_info, Some(next_version))
+                        .unwrap();
+                    next_version += outputs_with_proof.transactions_and_outputs.len() as u64;
+                },
+                DataPayload::EndOfStream => break,
+                data_payload => panic!("Unexpected data payload: {:?}", data_payload),
+            }
+        }
+        assert_eq!(next_version, end_version + 1);
+    }
+}
+
+#[test]
+fn test_single_leaf_range_proof() {
+    // Create an accumulator with 5 leaves:
+    //
+    //              root
+    //            /      \
+    //        h0123        h4pp
+    //       /    \       /    \
+    //     h01    h23   h4p    placeholder
+    //     / \    / \   / \
+    //    l0 l1  l2 l3 l4  placeholder
+    let leaves: Vec<_> = (0..5).map(|_| HashValue::random()).collect();
+    let placeholder = *ACCUMULATOR_PLACEHOLDER_HASH;
+    let h01 = hash_internal(leaves[0], leaves[1]);
+    let h23 = hash_internal(leaves[2], leaves[3]);
+    let h4p = hash_internal(leaves[4], placeholder);
+    let h0123 = hash_internal(h01, h23);
+    let h4pp = hash_internal(h4p, placeholder);
+    let root_hash = hash_internal(h0123, h4pp);
+
+    // Verify the range proofs for leaves with siblings on both sides
+    for (leaf_index, siblings) in [
+        (0, vec![leaves[1], h23, h4pp]),
+        (2, vec![leaves[3], h01, h4pp]),
+        (3, vec![leaves[2], h01, h4pp]),
+        (4, vec![placeholder, placeholder, h0123]),
+    ] {
+        let accumulator_proof = TransactionAccumulatorProof::new(siblings);
+        accumulator_proof
+            .verify(root_hash, leaves[leaf_index as usize], leaf_index)
+            .unwrap();
+
+        let range_proof = single_leaf_range_proof(leaf_index, &accumulator_proof);
+        range_proof
+            .verify(root_hash, Some(leaf_index), &[leaves[leaf_index as usize]])
+            .unwrap();
+
+        // Verify the range proof fails for a different leaf index
+        range_proof
+            .verify(root_hash, Some(1), &[leaves[leaf_index as usize]])
+            .unwrap_err();
+    }
+}
+
+/// Returns the hash of the internal node with the given children
+fn hash_internal(left: HashValue, right: HashValue) -> HashValue {
+    MerkleTreeInternalNode::<TransactionAccumulatorHasher>::new(left, right).hash()
+}
+
+/// Creates a local backup (in `backup_dir`) holding the given number of
+/// transactions (in chunks of `chunk_size`) and a state snapshot at version 0.
+/// Returns the transaction infos of all transactions.
+async fn create_local_transaction_backup(
+    backup_dir: &Path,
+    num_transactions: u64,
+    chunk_size: u64,
+) -> Vec<TransactionInfo> {
+    let storage = LocalFs::new(backup_dir.to_path_buf());
+
+    // Create the transactions (with empty outputs)
+    let records: Vec<(Transaction, TransactionInfo, Vec<ContractEvent>, WriteSet)> = (0
+        ..num_transactions)
+        .map(|_| {
+            let transaction = Transaction::StateCheckpoint(HashValue::random());
+            let write_set = WriteSet::default();
+            let transaction_info = TransactionInfo::new(
+                CryptoHash::hash(&transaction),
+                CryptoHash::hash(&write_set),
+                *ACCUMULATOR_PLACEHOLDER_HASH,
+                None,
+                0,
+                ExecutionStatus::Success,
+            );
+            (transaction, transaction_info, vec![], write_set)
+        })
+        .collect();
+    let leaves: Vec<_> = records
+        .iter()
+        .map(|(_, transaction_info, _, _)| transaction_info.hash())
+        .collect();
+
+    // Write the chunks and their proofs (against the ledger info at the last version)
+    let backup_handle = storage
+        .create_backup(&"transaction".parse().unwrap())
+        .await
+        .unwrap();
+    let last_version = num_transactions - 1;
+    let block_info = BlockInfo::new(
+        1,
+        0,
+        HashValue::zero(),
+        HashValue::zero(),
+        last_version,
+        0,
+        None,
+    );
+    let ledger_info = LedgerInfoWithSignatures::new(
+        LedgerInfo::new(block_info, HashValue::zero()),
+        AggregateSignature::empty(),
+    );
+    let mut chunks = vec![];
+    for first_version in (0..num_transactions).step_by(chunk_size as usize) {
+        let chunk_last_version = (first_version + chunk_size - 1).min(last_version);
+        let (transactions_handle, mut file) = storage
+            .create_for_write(
+                &backup_handle,
+                &format!("{}-.chunk", first_version).parse().unwrap(),
+            )
+            .await
+            .unwrap();
+        for record in &records[first_version as usize..=chunk_last_version as usize] {
+            let record = bcs::to_bytes(record).unwrap();
+            file.write_all(&(record.len() as u32).to_be_bytes())
+                .await
+                .unwrap();
+            file.write_all(&record).await.unwrap();
+        }
+        file.shutdown().await.unwrap();
+
+        let proof = range_proof(
+            &InMemoryTransactionAccumulator::from_leaves(&leaves[..first_version as usize]),
+            chunk_last_version,
+            &leaves[chunk_last_version as usize + 1..],
+        );
+        let (proof_handle, mut file) = storage
+            .create_for_write(
+                &backup_handle,
+                &format!("{}-{}.proof", first_version, chunk_last_version)
+                    .parse()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        file.write_all(&bcs::to_bytes(&(proof, ledger_info.clone())).unwrap())
+            .await
+            .unwrap();
+        file.shutdown().await.unwrap();
+
+        chunks.push(TransactionChunk {
+            first_version,
+            last_version: chunk_last_version,
+            transactions: transactions_handle,
+            proof: proof_handle,
+        });
+    }
+
+    // Write the transaction manifest and save the metadata
+    let manifest = TransactionBackup {
+        first_version: 0,
+        last_version,
+        chunks,
+    };
+    let (manifest_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"transaction.manifest".parse().unwrap())
+        .await
+        .unwrap();
+    file.write_all(&serde_json::to_vec(&manifest).unwrap())
+        .await
+        .unwrap();
+    file.shutdown().await.unwrap();
+    let metadata = Metadata::new_transaction_backup(0, last_version, manifest_handle);
+    storage
+        .save_metadata_line(&metadata.name(), &metadata.to_text_line().unwrap())
+        .await
+        .unwrap();
+
+    // Save the state snapshot metadata (the snapshot itself isn't read)
+    let backup_handle = storage
+        .create_backup(&"state_snapshot".parse().unwrap())
+        .await
+        .unwrap();
+    let (snapshot_manifest_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"state_snapshot.manifest".parse().unwrap())
+        .await
+        .unwrap();
+    file.shutdown().await.unwrap();
+    let metadata = Metadata::new_state_snapshot_backup(0, 0, snapshot_manifest_handle);
+    storage
+        .save_metadata_line(&metadata.name(), &metadata.to_text_line().unwrap())
+        .await
+        .unwrap();
+
+    records
+        .into_iter()
+        .map(|(_, transaction_info, _, _)| transaction_info)
+        .collect()
+}
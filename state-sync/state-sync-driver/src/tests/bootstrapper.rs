@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    backup_streamer::BackupStreamer,
     bootstrapper::{Bootstrapper, GENESIS_TRANSACTION_VERSION},
     driver::DriverConfiguration,
     error::Error,
@@ -12,29 +13,37 @@ use crate::{
         },
         utils::{
             create_data_stream_listener, create_empty_epoch_state, create_epoch_ending_ledger_info,
-            create_epoch_ending_ledger_info_for_epoch, create_full_node_driver_configuration,
-            create_global_summary, create_global_summary_with_version,
-            create_output_list_with_proof, create_random_epoch_ending_ledger_info,
-            create_transaction_list_with_proof,
+            create_epoch_ending_ledger_info_for_epoch, create_epoch_state,
+            create_full_node_driver_configuration, create_global_summary,
+            create_global_summary_with_version, create_output_list_with_proof,
+            create_random_epoch_ending_ledger_info, create_transaction_list_with_proof,
         },
     },
     utils::OutputFallbackHandler,
 };
-use aptos_config::config::BootstrappingMode;
+use aptos_backup_storage::{
+    manifest::epoch_ending::{EpochEndingBackup, EpochEndingChunk},
+    metadata::Metadata,
+    storage::{local_fs::LocalFs, BackupStorage},
+};
+use aptos_config::config::{BootstrappingMode, StorageConfig};
 use aptos_data_client::global_summary::GlobalDataSummary;
 use aptos_data_streaming_service::{
     data_notification::{DataNotification, DataPayload, NotificationId},
     streaming_client::{NotificationAndFeedback, NotificationFeedback},
 };
+use aptos_temppath::TempPath;
 use aptos_time_service::TimeService;
 use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
     transaction::{TransactionOutputListWithProof, Version},
     waypoint::Waypoint,
 };
 use claims::{assert_matches, assert_none, assert_ok};
 use futures::{channel::oneshot, FutureExt, SinkExt};
 use mockall::{predicate::eq, Sequence};
-use std::{sync::Arc, time::Duration};
+use std::{path::Path, sync::Arc, time::Duration};
+use tokio::io::AsyncWriteExt;
 
 #[tokio::test]
 async fn test_bootstrap_genesis_waypoint() {
@@ -1581,6 +1590,108 @@ async fn test_waypoint_satisfiable() {
     assert_matches!(error, Error::UnsatisfiableWaypoint(_));
 }
 
+#[tokio::test]
+async fn test_restore_from_backup_epoch_ending_ledger_infos() {
+    // Create the epoch ending ledger infos (epoch 0 is genesis)
+    let snapshot_epoch = 10;
+    let mut epoch_ending_ledger_infos = vec![create_epoch_ending_ledger_info()];
+    for epoch in 1..=snapshot_epoch {
+        epoch_ending_ledger_infos.push(create_random_epoch_ending_ledger_info(epoch * 10, epoch));
+    }
+
+    // Create a driver configuration with a waypoint at the epoch of the state snapshot
+    let mut driver_configuration = create_full_node_driver_configuration();
+    let waypoint = epoch_ending_ledger_infos.last().unwrap().clone();
+    driver_configuration.waypoint = Waypoint::new_any(waypoint.ledger_info());
+
+    // Create a local backup with the epoch ending ledger infos and a state snapshot
+    let backup_dir = TempPath::new();
+    create_local_backup(
+        backup_dir.path(),
+        &epoch_ending_ledger_infos,
+        snapshot_epoch,
+        waypoint.ledger_info().version(),
+    )
+    .await;
+
+    // Create the bootstrapper (restoring from the backup)
+    let data_dir = TempPath::new();
+    let mut bootstrapper =
+        create_bootstrapper_with_backup(driver_configuration, backup_dir.path(), data_dir.path());
+
+    // Drive progress until all epoch ending ledger infos have been fetched
+    let global_data_summary = GlobalDataSummary::empty();
+    while !bootstrapper
+        .get_verified_epoch_states()
+        .fetched_epoch_ending_ledger_infos()
+    {
+        drive_progress(&mut bootstrapper, &global_data_summary, false)
+            .await
+            .unwrap();
+    }
+
+    // Verify the waypoint was verified and that all ledger infos (after genesis) were fetched
+    let verified_epoch_states = bootstrapper.get_verified_epoch_states().clone();
+    assert!(verified_epoch_states.verified_waypoint());
+    let verified_ledger_infos = verified_epoch_states.all_epoch_ending_ledger_infos();
+    assert_eq!(verified_ledger_infos.len() as u64, snapshot_epoch);
+    for epoch_ending_ledger_info in &epoch_ending_ledger_infos[1..] {
+        assert!(verified_ledger_infos.contains(epoch_ending_ledger_info));
+    }
+}
+
+#[tokio::test]
+async fn test_restore_from_backup_waypoint_unsatisfiable() {
+    // Create the epoch ending ledger infos (epoch 0 is genesis)
+    let snapshot_epoch = 10;
+    let mut epoch_ending_ledger_infos = vec![create_epoch_ending_ledger_info()];
+    for epoch in 1..=snapshot_epoch {
+        epoch_ending_ledger_infos.push(create_random_epoch_ending_ledger_info(epoch * 10, epoch));
+    }
+
+    // Create a driver configuration with a waypoint beyond the state snapshot
+    let mut driver_configuration = create_full_node_driver_configuration();
+    let waypoint = create_random_epoch_ending_ledger_info(200, 20);
+    driver_configuration.waypoint = Waypoint::new_any(waypoint.ledger_info());
+
+    // Create a local backup with the epoch ending ledger infos and a state snapshot
+    let backup_dir = TempPath::new();
+    let snapshot_version = epoch_ending_ledger_infos
+        .last()
+        .unwrap()
+        .ledger_info()
+        .version();
+    create_local_backup(
+        backup_dir.path(),
+        &epoch_ending_ledger_infos,
+        snapshot_epoch,
+        snapshot_version,
+    )
+    .await;
+
+    // Create the bootstrapper (restoring from the backup)
+    let data_dir = TempPath::new();
+    let mut bootstrapper =
+        create_bootstrapper_with_backup(driver_configuration, backup_dir.path(), data_dir.path());
+
+    // Drive progress until an error is returned (as the backup can't satisfy the waypoint)
+    let global_data_summary = GlobalDataSummary::empty();
+    let error = loop {
+        if let Err(error) = drive_progress(&mut bootstrapper, &global_data_summary, false).await {
+            break error;
+        }
+    };
+    assert_matches!(error, Error::UnsatisfiableWaypoint(_));
+
+    // Verify all ledger infos in the backup were still fetched
+    let verified_epoch_states = bootstrapper.get_verified_epoch_states().clone();
+    assert!(!verified_epoch_states.verified_waypoint());
+    assert_eq!(
+        verified_epoch_states.all_epoch_ending_ledger_infos().len() as u64,
+        snapshot_epoch
+    );
+}
+
 /// Creates a bootstrapper for testing
 fn create_bootstrapper(
     driver_configuration: DriverConfiguration,
@@ -1628,6 +1739,7 @@ fn create_bootstrapper(
         mock_streaming_client,
         Arc::new(mock_database_reader),
         mock_storage_synchronizer,
+        None,
     );
 
     (bootstrapper, output_fallback_handler)
@@ -1683,9 +1795,169 @@ fn create_bootstrapper_with_storage(
         mock_streaming_client,
         Arc::new(mock_database_reader),
         mock_storage_synchronizer,
+        None,
     )
 }
 
+/// Creates a bootstrapper for testing that restores from the
+/// backup (in `backup_dir`) and starts with only genesis synced
+fn create_bootstrapper_with_backup(
+    driver_configuration: DriverConfiguration,
+    backup_dir: &Path,
+    data_dir: &Path,
+) -> Bootstrapper<MockMetadataStorage, MockStorageSynchronizer, MockStreamingClient> {
+    // Initialize the logger for tests
+    aptos_logger::Logger::init_for_testing();
+
+    // Create the mock storage synchronizer
+    let mock_storage_synchronizer = create_ready_storage_synchronizer(true);
+
+    // Create the mock metadata storage
+    let mut metadata_storage = MockMetadataStorage::new();
+    metadata_storage
+        .expect_previous_snapshot_sync_target()
+        .returning(|| Ok(None));
+
+    // Create the mock db reader with only genesis loaded (i.e., in epoch 1)
+    let mut mock_database_reader = create_mock_db_reader();
+    mock_database_reader
+        .expect_get_latest_epoch_state()
+        .returning(|| Ok(create_epoch_state(1)));
+    mock_database_reader
+        .expect_get_latest_ledger_info()
+        .returning(|| Ok(create_epoch_ending_ledger_info()));
+    mock_database_reader
+        .expect_get_latest_version()
+        .returning(|| Ok(0));
+
+    // Create the backup streamer
+    let mut storage_config = StorageConfig::default();
+    storage_config.set_data_dir(data_dir.to_path_buf());
+    storage_config.backup_storage.local_fs_dir = Some(backup_dir.to_path_buf());
+    let backup_streamer = BackupStreamer::new(storage_config);
+
+    // Create the output fallback handler
+    let output_fallback_handler =
+        OutputFallbackHandler::new(driver_configuration.clone(), TimeService::mock());
+
+    // Create the bootstrapper (the streaming client should never be used)
+    Bootstrapper::new(
+        driver_configuration,
+        metadata_storage,
+        output_fallback_handler,
+        create_mock_streaming_client(),
+        Arc::new(mock_database_reader),
+        mock_storage_synchronizer,
+        Some(backup_streamer),
+    )
+}
+
+/// Creates a local backup (in `backup_dir`) holding the given epoch ending
+/// ledger infos (starting at epoch 0), a state snapshot at the specified
+/// epoch and version, and the transactions up to the state snapshot.
+async fn create_local_backup(
+    backup_dir: &Path,
+    epoch_ending_ledger_infos: &[LedgerInfoWithSignatures],
+    snapshot_epoch: u64,
+    snapshot_version: Version,
+) {
+    let storage = LocalFs::new(backup_dir.to_path_buf());
+
+    // Write the epoch ending ledger infos as a single chunk of records
+    let backup_handle = storage
+        .create_backup(&"epoch_ending".parse().unwrap())
+        .await
+        .unwrap();
+    let (ledger_infos_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"ledger_infos.chunk".parse().unwrap())
+        .await
+        .unwrap();
+    for epoch_ending_ledger_info in epoch_ending_ledger_infos {
+        let record = bcs::to_bytes(epoch_ending_ledger_info).unwrap();
+        file.write_all(&(record.len() as u32).to_be_bytes())
+            .await
+            .unwrap();
+        file.write_all(&record).await.unwrap();
+    }
+    file.shutdown().await.unwrap();
+
+    // Write the epoch ending manifest
+    let last_epoch = epoch_ending_ledger_infos.len() as u64 - 1;
+    let manifest = EpochEndingBackup {
+        first_epoch: 0,
+        last_epoch,
+        waypoints: epoch_ending_ledger_infos
+            .iter()
+            .map(|ledger_info| Waypoint::new_epoch_boundary(ledger_info.ledger_info()).unwrap())
+            .collect(),
+        chunks: vec![EpochEndingChunk {
+            first_epoch: 0,
+            last_epoch,
+            ledger_infos: ledger_infos_handle,
+        }],
+    };
+    let (manifest_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"epoch_ending.manifest".parse().unwrap())
+        .await
+        .unwrap();
+    file.write_all(&serde_json::to_vec(&manifest).unwrap())
+        .await
+        .unwrap();
+    file.shutdown().await.unwrap();
+
+    // Save the epoch ending metadata
+    let last_version = epoch_ending_ledger_infos
+        .last()
+        .unwrap()
+        .ledger_info()
+        .version();
+    let metadata =
+        Metadata::new_epoch_ending_backup(0, last_epoch, 0, last_version, manifest_handle);
+    storage
+        .save_metadata_line(&metadata.name(), &metadata.to_text_line().unwrap())
+        .await
+        .unwrap();
+
+    // Save the state snapshot metadata. The snapshot manifest is left
+    // empty, as it isn't read when fetching the epoch ending ledger infos.
+    let backup_handle = storage
+        .create_backup(&"state_snapshot".parse().unwrap())
+        .await
+        .unwrap();
+    let (snapshot_manifest_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"state_snapshot.manifest".parse().unwrap())
+        .await
+        .unwrap();
+    file.shutdown().await.unwrap();
+    let metadata = Metadata::new_state_snapshot_backup(
+        snapshot_epoch,
+        snapshot_version,
+        snapshot_manifest_handle,
+    );
+    storage
+        .save_metadata_line(&metadata.name(), &metadata.to_text_line().unwrap())
+        .await
+        .unwrap();
+
+    // Save the transaction metadata (up to the state snapshot). The manifest
+    // is left empty, as the transactions aren't read either.
+    let backup_handle = storage
+        .create_backup(&"transaction".parse().unwrap())
+        .await
+        .unwrap();
+    let (transaction_manifest_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"transaction.manifest".parse().unwrap())
+        .await
+        .unwrap();
+    file.shutdown().await.unwrap();
+    let metadata =
+        Metadata::new_transaction_backup(0, snapshot_version, transaction_manifest_handle);
+    storage
+        .save_metadata_line(&metadata.name(), &metadata.to_text_line().unwrap())
+        .await
+        .unwrap();
+}
+
 /// Drives progress for the given bootstrapper. If `until_bootstrapped`
 /// is true this method will continue to drive the bootstrapper until
 /// bootstrapping is complete.
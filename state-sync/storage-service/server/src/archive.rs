@@ -0,0 +1,672 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+};
+use anyhow::{anyhow, ensure};
+use aptos_backup_storage::{
+    manifest::transaction::{TransactionBackup, TransactionChunk},
+    metadata::{
+        cache::{sync_and_load, MetadataCacheOpt},
+        view::MetadataView,
+    },
+    storage::{
+        command_adapter::{config::CommandAdapterConfig, CommandAdapter},
+        local_fs::LocalFs,
+        BackupStorage, FileHandle,
+    },
+    utils::{read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt},
+};
+use aptos_config::config::StorageConfig;
+use aptos_crypto::{
+    hash::{CryptoHash, TransactionAccumulatorHasher},
+    HashValue,
+};
+use aptos_logger::prelude::*;
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{position::Position, MerkleTreeInternalNode, TransactionAccumulatorRangeProof},
+    transaction::{
+        Transaction, TransactionAuxiliaryData, TransactionInfo, TransactionInfoListWithProof,
+        TransactionListWithProof, TransactionOutput, TransactionOutputListWithProof,
+        TransactionStatus, Version,
+    },
+    write_set::WriteSet,
+};
+use arc_swap::ArcSwapOption;
+use mini_moka::sync::Cache;
+use std::{cmp::min, collections::HashMap, future::Future, ops::Range, sync::Arc, time::Duration};
+use tokio::{io::BufReader, runtime::Handle};
+
+// The interval at which to reload the backup metadata (to find new backups)
+const METADATA_REFRESH_INTERVAL_SECS: u64 = 600; // 10 minutes
+
+// The maximum number of transaction chunks (read from the backup) to cache
+const MAX_CACHED_CHUNKS: u64 = 4;
+
+// The maximum number of transaction backup manifests to cache
+const MAX_CACHED_MANIFESTS: u64 = 100;
+
+// The maximum number of accumulator node hashes (read from the backup) to cache
+const MAX_CACHED_ACCUMULATOR_NODES: u64 = 10_000;
+
+/// Serves the transactions and outputs that have been pruned from storage out
+/// of a (read-only) backup storage, i.e., the archive.
+///
+/// Clients expect proofs relative to the ledger version they are syncing to,
+/// but the proofs in the backup are relative to the ledger infos that end the
+/// epochs of each chunk, and storage has pruned the accumulator nodes that are
+/// required to build the proofs. Thus, proofs are assembled from: (i) the
+/// accumulator nodes proven by the backup chunk holding the data; (ii) a proof
+/// (from storage) of the first version that hasn't been pruned; and (iii) the
+/// accumulator nodes proven by any other backup chunks, for the nodes that
+/// aren't covered by the first two.
+pub struct ArchiveReader {
+    // The backup storage and its metadata (loaded in the background)
+    archive: Arc<ArcSwapOption<LoadedArchive>>,
+
+    // The runtime on which to read from the backup storage
+    runtime: Handle,
+
+    // Caches for the transaction chunks, backup manifests and accumulator nodes
+    chunk_cache: Cache<Version, Arc<ArchivedChunk>>,
+    manifest_cache: Cache<FileHandle, Arc<TransactionBackup>>,
+    node_cache: Cache<Position, HashValue>,
+}
+
+/// The backup storage and its metadata
+struct LoadedArchive {
+    storage: Arc<dyn BackupStorage>,
+    metadata_view: MetadataView,
+}
+
+impl ArchiveReader {
+    pub fn new(storage_config: StorageConfig, runtime: Handle) -> Self {
+        // Load (and periodically reload) the backup metadata in the background
+        let archive = Arc::new(ArcSwapOption::empty());
+        runtime.spawn(refresh_archive(storage_config, archive.clone()));
+
+        Self {
+            archive,
+            runtime,
+            chunk_cache: Cache::new(MAX_CACHED_CHUNKS),
+            manifest_cache: Cache::new(MAX_CACHED_MANIFESTS),
+            node_cache: Cache::new(MAX_CACHED_ACCUMULATOR_NODES),
+        }
+    }
+
+    /// Returns the highest version of the continuous range of transactions
+    /// in the archive that starts at version 0 (if the archive has been
+    /// loaded). Transactions after a gap in the backups are never served,
+    /// as the chunks holding them can't be selected.
+    pub fn get_highest_archived_version(&self) -> Option<Version> {
+        let archive = self.archive.load_full()?;
+        archive.metadata_view.max_continuous_transaction_version()
+    }
+
+    /// Returns a list of transactions (pruned from storage) with a proof
+    /// relative to the `proof_version`, starting at `start_version`. Less
+    /// than `num_transactions` may be returned (e.g., if the transactions
+    /// span several backup chunks).
+    pub fn get_transactions(
+        &self,
+        storage: &Arc<dyn DbReader>,
+        start_version: Version,
+        num_transactions: u64,
+        proof_version: Version,
+        include_events: bool,
+    ) -> aptos_storage_service_types::Result<TransactionListWithProof, Error> {
+        let (chunk, indices, range_proof) =
+            self.get_archived_range(storage, start_version, num_transactions, proof_version)?;
+
+        let events = include_events.then(|| chunk.events[indices.clone()].to_vec());
+        Ok(TransactionListWithProof::new(
+            chunk.transactions[indices.clone()].to_vec(),
+            events,
+            Some(start_version),
+            TransactionInfoListWithProof::new(
+                range_proof,
+                chunk.transaction_infos[indices].to_vec(),
+            ),
+        ))
+    }
+
+    /// Returns a list of transaction outputs (pruned from storage) with a
+    /// proof relative to the `proof_version`, starting at `start_version`.
+    /// Less than `num_outputs` may be returned (e.g., if the outputs span
+    /// several backup chunks).
+    pub fn get_transaction_outputs(
+        &self,
+        storage: &Arc<dyn DbReader>,
+        start_version: Version,
+        num_outputs: u64,
+        proof_version: Version,
+    ) -> aptos_storage_service_types::Result<TransactionOutputListWithProof, Error> {
+        let (chunk, indices, range_proof) =
+            self.get_archived_range(storage, start_version, num_outputs, proof_version)?;
+
+        let transactions_and_outputs = indices
+            .clone()
+            .map(|index| {
+                let transaction_info = &chunk.transaction_infos[index];
+                let transaction_output = TransactionOutput::new(
+                    chunk.write_sets[index].clone(),
+                    chunk.events[index].clone(),
+                    transaction_info.gas_used(),
+                    TransactionStatus::Keep(transaction_info.status().clone()),
+                    TransactionAuxiliaryData::default(),
+                );
+                (chunk.transactions[index].clone(), transaction_output)
+            })
+            .collect();
+        Ok(TransactionOutputListWithProof::new(
+            transactions_and_outputs,
+            Some(start_version),
+            TransactionInfoListWithProof::new(
+                range_proof,
+                chunk.transaction_infos[indices].to_vec(),
+            ),
+        ))
+    }
+
+    /// Returns the chunk holding the data at `start_version`, the indices of
+    /// the data to serve in the chunk and its proof relative to `proof_version`.
+    fn get_archived_range(
+        &self,
+        storage: &Arc<dyn DbReader>,
+        start_version: Version,
+        num_items: u64,
+        proof_version: Version,
+    ) -> aptos_storage_service_types::Result<
+        (
+            Arc<ArchivedChunk>,
+            Range<usize>,
+            TransactionAccumulatorRangeProof,
+        ),
+        Error,
+    > {
+        // The archive only serves the versions that have been pruned from storage
+        let first_stored_version = storage.get_first_txn_version()?.ok_or_else(|| {
+            Error::StorageErrorEncountered("No transactions found in storage!".into())
+        })?;
+        if start_version >= first_stored_version || proof_version < first_stored_version {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Unable to serve the data from the archive! Start version: {:?}, proof version: {:?}, \
+                first version in storage: {:?}",
+                start_version, proof_version, first_stored_version
+            )));
+        }
+
+        // Only serve the data in the chunk (and before the first version in storage)
+        let chunk = self.get_chunk(start_version)?;
+        let end_version = start_version
+            .saturating_add(num_items.saturating_sub(1))
+            .min(chunk.last_version())
+            .min(first_stored_version - 1);
+        let indices = (start_version - chunk.first_version) as usize
+            ..(end_version - chunk.first_version + 1) as usize;
+
+        // Prove the first version in storage, relative to the proof version
+        let mut accumulator_nodes = AccumulatorNodes::new(proof_version);
+        let stored_transaction =
+            storage.get_transactions(first_stored_version, 1, proof_version, false)?;
+        let stored_transaction_info = stored_transaction
+            .proof
+            .transaction_infos
+            .first()
+            .ok_or_else(|| {
+                Error::StorageErrorEncountered(format!(
+                    "Missing transaction info at version: {:?}",
+                    first_stored_version
+                ))
+            })?;
+        let root_hash = accumulator_nodes.add_range_proof(
+            proof_version,
+            first_stored_version,
+            &[stored_transaction_info.hash()],
+            &stored_transaction
+                .proof
+                .ledger_info_to_transaction_infos_proof,
+        )?;
+
+        // Add the nodes proven by the chunk, and fetch any missing nodes from other chunks
+        chunk.add_proven_nodes(&mut accumulator_nodes)?;
+        let range_proof = loop {
+            match accumulator_nodes.get_range_proof(start_version, end_version) {
+                Ok(range_proof) => break range_proof,
+                Err(missing_positions) => {
+                    for position in missing_positions {
+                        self.fetch_accumulator_node(&mut accumulator_nodes, position)?;
+                    }
+                },
+            }
+        };
+
+        // Verify the proof before serving it
+        range_proof
+            .verify(
+                root_hash,
+                Some(start_version),
+                &chunk.transaction_info_hashes[indices.clone()],
+            )
+            .map_err(|error| {
+                Error::UnexpectedErrorEncountered(format!(
+                    "Failed to prove the archived data! Error: {:?}",
+                    error
+                ))
+            })?;
+
+        Ok((chunk, indices, range_proof))
+    }
+
+    /// Adds the accumulator node at the given position (which must be frozen)
+    /// to the accumulator nodes. If the node isn't cached, it is computed from
+    /// the proof of the backup chunk holding its rightmost leaf.
+    fn fetch_accumulator_node(
+        &self,
+        accumulator_nodes: &mut AccumulatorNodes,
+        position: Position,
+    ) -> aptos_storage_service_types::Result<(), Error> {
+        if let Some(hash) = self.node_cache.get(&position) {
+            accumulator_nodes.add_node(position, hash);
+            return Ok(());
+        }
+
+        let rightmost_leaf_index = position.right_most_child().to_inorder_index() / 2;
+        self.get_chunk(rightmost_leaf_index)?
+            .add_proven_nodes(accumulator_nodes)?;
+        let hash = accumulator_nodes.get_node(position).ok_or_else(|| {
+            Error::UnexpectedErrorEncountered(format!(
+                "The accumulator node at position {:?} is missing from the archive!",
+                position
+            ))
+        })?;
+        self.node_cache.insert(position, hash);
+
+        Ok(())
+    }
+
+    /// Returns the backup chunk holding the transaction at the given version
+    fn get_chunk(
+        &self,
+        version: Version,
+    ) -> aptos_storage_service_types::Result<Arc<ArchivedChunk>, Error> {
+        let archive = self.archive.load_full().ok_or_else(|| {
+            Error::StorageErrorEncountered("The archive has not been loaded yet!".into())
+        })?;
+
+        // Find the chunk in the backup manifests
+        let manifest_handle = archive
+            .metadata_view
+            .select_transaction_backups(version, version)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?
+            .into_iter()
+            .find(|backup| backup.first_version <= version && version <= backup.last_version)
+            .map(|backup| backup.manifest)
+            .ok_or_else(|| {
+                Error::StorageErrorEncountered(format!(
+                    "No transaction backup found in the archive for version: {:?}",
+                    version
+                ))
+            })?;
+        let manifest = match self.manifest_cache.get(&manifest_handle) {
+            Some(manifest) => manifest,
+            None => {
+                let storage = archive.storage.clone();
+                let handle = manifest_handle.clone();
+                let manifest: Arc<TransactionBackup> =
+                    Arc::new(self.block_on(async move { storage.load_json_file(&handle).await })?);
+                self.manifest_cache
+                    .insert(manifest_handle, manifest.clone());
+                manifest
+            },
+        };
+        let chunk = manifest
+            .chunks
+            .iter()
+            .find(|chunk| chunk.first_version <= version && version <= chunk.last_version)
+            .cloned()
+            .ok_or_else(|| {
+                Error::StorageErrorEncountered(format!(
+                    "No transaction chunk found in the archive for version: {:?}",
+                    version
+                ))
+            })?;
+
+        // Read the chunk (if it isn't cached)
+        if let Some(archived_chunk) = self.chunk_cache.get(&chunk.first_version) {
+            return Ok(archived_chunk);
+        }
+        let first_version = chunk.first_version;
+        let storage = archive.storage.clone();
+        let archived_chunk = Arc::new(self.block_on(ArchivedChunk::load(storage, chunk))?);
+        self.chunk_cache
+            .insert(first_version, archived_chunk.clone());
+
+        Ok(archived_chunk)
+    }
+
+    /// Runs the given backup storage read on the runtime and blocks on the result
+    fn block_on<T: Send + 'static>(
+        &self,
+        future: impl Future<Output = anyhow::Result<T>> + Send + 'static,
+    ) -> aptos_storage_service_types::Result<T, Error> {
+        futures::executor::block_on(self.runtime.spawn(future))
+            .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?
+            .map_err(|error| {
+                Error::StorageErrorEncountered(format!(
+                    "Failed to read from the archive! Error: {:?}",
+                    error
+                ))
+            })
+    }
+}
+
+/// Loads the backup storage and its metadata, and reloads the metadata
+/// periodically to find new backups.
+async fn refresh_archive(
+    storage_config: StorageConfig,
+    archive: Arc<ArcSwapOption<LoadedArchive>>,
+) {
+    loop {
+        match load_archive(&storage_config).await {
+            Ok(loaded_archive) => {
+                info!(LogSchema::new(LogEntry::ArchiveRefresh).message(&format!(
+                    "Loaded the archive! Highest archived version: {:?}",
+                    loaded_archive
+                        .metadata_view
+                        .max_continuous_transaction_version()
+                )));
+                archive.store(Some(Arc::new(loaded_archive)));
+            },
+            Err(error) => {
+                error!(LogSchema::new(LogEntry::ArchiveRefresh)
+                    .message(&format!("Failed to load the archive! Error: {:?}", error)));
+            },
+        }
+        tokio::time::sleep(Duration::from_secs(METADATA_REFRESH_INTERVAL_SECS)).await;
+    }
+}
+
+async fn load_archive(storage_config: &StorageConfig) -> anyhow::Result<LoadedArchive> {
+    let backup_storage_config = &storage_config.backup_storage;
+    let storage: Arc<dyn BackupStorage> = match (
+        &backup_storage_config.local_fs_dir,
+        &backup_storage_config.command_adapter_config,
+    ) {
+        (Some(local_fs_dir), None) => Arc::new(LocalFs::new(local_fs_dir.clone())),
+        (None, Some(command_adapter_config)) => Arc::new(CommandAdapter::new(
+            CommandAdapterConfig::load_from_file(command_adapter_config).await?,
+        )),
+        _ => return Err(anyhow!("Exactly one backup storage must be configured!")),
+    };
+
+    let metadata_view = sync_and_load(
+        &MetadataCacheOpt::new(Some(storage_config.backup_metadata_cache_dir())),
+        storage.clone(),
+        backup_storage_config.concurrent_downloads,
+    )
+    .await?;
+
+    Ok(LoadedArchive {
+        storage,
+        metadata_view,
+    })
+}
+
+/// A transaction chunk read from the backup, with its proof
+struct ArchivedChunk {
+    first_version: Version,
+    transactions: Vec<Transaction>,
+    transaction_infos: Vec<TransactionInfo>,
+    transaction_info_hashes: Vec<HashValue>,
+    events: Vec<Vec<ContractEvent>>,
+    write_sets: Vec<WriteSet>,
+    range_proof: TransactionAccumulatorRangeProof,
+    proof_version: Version,
+    proof_root_hash: HashValue,
+}
+
+impl ArchivedChunk {
+    async fn load(
+        storage: Arc<dyn BackupStorage>,
+        chunk: TransactionChunk,
+    ) -> anyhow::Result<Self> {
+        let mut file = BufReader::new(storage.open_for_read(&chunk.transactions).await?);
+        let mut transactions = vec![];
+        let mut transaction_infos = vec![];
+        let mut events = vec![];
+        let mut write_sets = vec![];
+        while let Some(record_bytes) = file.read_record_bytes().await? {
+            let (transaction, transaction_info, transaction_events, write_set): (
+                Transaction,
+                TransactionInfo,
+                Vec<ContractEvent>,
+                WriteSet,
+            ) = bcs::from_bytes(&record_bytes)?;
+            transactions.push(transaction);
+            transaction_infos.push(transaction_info);
+            events.push(transaction_events);
+            write_sets.push(write_set);
+        }
+        ensure!(
+            chunk.first_version + transactions.len() as Version == chunk.last_version + 1,
+            "The number of transactions in the chunk doesn't match the manifest! First version: {}, \
+            last version: {}, number of transactions: {}",
+            chunk.first_version,
+            chunk.last_version,
+            transactions.len(),
+        );
+
+        let (range_proof, ledger_info): (
+            TransactionAccumulatorRangeProof,
+            LedgerInfoWithSignatures,
+        ) = storage.load_bcs_file(&chunk.proof).await?;
+        let transaction_info_hashes = transaction_infos.iter().map(CryptoHash::hash).collect();
+
+        Ok(Self {
+            first_version: chunk.first_version,
+            transactions,
+            transaction_infos,
+            transaction_info_hashes,
+            events,
+            write_sets,
+            range_proof,
+            proof_version: ledger_info.ledger_info().version(),
+            proof_root_hash: ledger_info.ledger_info().transaction_accumulator_hash(),
+        })
+    }
+
+    /// Adds the accumulator nodes proven by the chunk, after checking that
+    /// the proof matches the ledger info of the chunk.
+    fn add_proven_nodes(
+        &self,
+        accumulator_nodes: &mut AccumulatorNodes,
+    ) -> aptos_storage_service_types::Result<(), Error> {
+        let root_hash = accumulator_nodes.add_range_proof(
+            self.proof_version,
+            self.first_version,
+            &self.transaction_info_hashes,
+            &self.range_proof,
+        )?;
+        if root_hash != self.proof_root_hash {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "The proof of the archived chunk doesn't match its ledger info! First version: {:?}",
+                self.first_version
+            )));
+        }
+        Ok(())
+    }
+
+    fn last_version(&self) -> Version {
+        self.first_version + self.transactions.len() as Version - 1
+    }
+}
+
+/// The hashes of the transaction accumulator nodes (at a ledger version) that
+/// are known, e.g., from range proofs of the accumulator at this version, or
+/// of the accumulators at older versions (for the nodes that were frozen).
+pub(crate) struct AccumulatorNodes {
+    ledger_version: Version,
+    nodes: HashMap<Position, HashValue>,
+}
+
+impl AccumulatorNodes {
+    pub(crate) fn new(ledger_version: Version) -> Self {
+        Self {
+            ledger_version,
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Adds the hash of a node that is frozen at the ledger version
+    pub(crate) fn add_node(&mut self, position: Position, hash: HashValue) {
+        self.nodes.insert(position, hash);
+    }
+
+    /// Returns the hash of the node at the given position (if known)
+    pub(crate) fn get_node(&self, position: Position) -> Option<HashValue> {
+        self.nodes.get(&position).copied()
+    }
+
+    /// Adds all nodes that can be computed from the given range proof (and
+    /// leaves), relative to the accumulator at `proof_version`. Returns the
+    /// root hash of that accumulator. If the proof version isn't the ledger
+    /// version, only the nodes frozen at both versions are added (as the
+    /// others differ between the accumulators).
+    pub(crate) fn add_range_proof(
+        &mut self,
+        proof_version: Version,
+        first_leaf_index: u64,
+        leaf_hashes: &[HashValue],
+        range_proof: &TransactionAccumulatorRangeProof,
+    ) -> aptos_storage_service_types::Result<HashValue, Error> {
+        if leaf_hashes.is_empty() {
+            return Err(Error::UnexpectedErrorEncountered(
+                "Unable to add a range proof without leaves!".into(),
+            ));
+        }
+
+        // Compute the nodes level by level, like range proof verification
+        let root_level = Position::root_from_leaf_index(proof_version).level();
+        let mut left_siblings = range_proof.left_siblings().iter();
+        let mut right_siblings = range_proof.right_siblings().iter();
+        let mut first_position = Position::from_leaf_index(first_leaf_index);
+        let mut hashes = leaf_hashes.to_vec();
+        loop {
+            let level = first_position.level();
+            let first_index = first_position.to_inorder_index() >> (level + 1);
+            for (index, hash) in (first_index..).zip(hashes.iter()) {
+                self.maybe_add_node(
+                    proof_version,
+                    Position::from_level_and_pos(level, index),
+                    *hash,
+                );
+            }
+            if level >= root_level {
+                break;
+            }
+
+            // Combine the first node with a sibling on the left (if it is a right child)
+            let mut parent_hashes = vec![];
+            let mut children = hashes.as_slice();
+            if first_position.is_right_child() {
+                let left_hash = *left_siblings.next().ok_or_else(|| {
+                    Error::UnexpectedErrorEncountered(
+                        "Range proof is missing a left sibling!".into(),
+                    )
+                })?;
+                self.maybe_add_node(proof_version, first_position.sibling(), left_hash);
+                parent_hashes.push(hash_internal_node(left_hash, children[0]));
+                children = &children[1..];
+            }
+
+            // Combine the children pairs, and the last node with a sibling
+            // on the right (if it is a left child).
+            let mut children_pairs = children.chunks_exact(2);
+            for children_pair in children_pairs.by_ref() {
+                parent_hashes.push(hash_internal_node(children_pair[0], children_pair[1]));
+            }
+            if let [last_hash] = children_pairs.remainder() {
+                let right_hash = *right_siblings.next().ok_or_else(|| {
+                    Error::UnexpectedErrorEncountered(
+                        "Range proof is missing a right sibling!".into(),
+                    )
+                })?;
+                let last_position =
+                    Position::from_level_and_pos(level, first_index + hashes.len() as u64 - 1);
+                self.maybe_add_node(proof_version, last_position.sibling(), right_hash);
+                parent_hashes.push(hash_internal_node(*last_hash, right_hash));
+            }
+
+            first_position = first_position.parent();
+            hashes = parent_hashes;
+        }
+
+        // Verify the proof has been consumed entirely
+        if hashes.len() != 1 || left_siblings.next().is_some() || right_siblings.next().is_some() {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Range proof doesn't match the accumulator at version: {:?}",
+                proof_version
+            )));
+        }
+        Ok(hashes[0])
+    }
+
+    /// Returns the range proof of the leaves in [`first_leaf_index`,
+    /// `last_leaf_index`] relative to the accumulator at the ledger version,
+    /// or the positions of the nodes that are missing to build it.
+    pub(crate) fn get_range_proof(
+        &self,
+        first_leaf_index: u64,
+        last_leaf_index: u64,
+    ) -> Result<TransactionAccumulatorRangeProof, Vec<Position>> {
+        let mut missing_positions = vec![];
+        let mut get_siblings = |leaf_index: u64, filter: fn(&Position) -> bool| {
+            let root_level = Position::root_from_leaf_index(self.ledger_version).level();
+            Position::from_leaf_index(leaf_index)
+                .iter_ancestor_sibling()
+                .take(root_level as usize)
+                .filter(filter)
+                .filter_map(|position| {
+                    let hash = self.get_node(position);
+                    if hash.is_none() {
+                        missing_positions.push(position);
+                    }
+                    hash
+                })
+                .collect::<Vec<_>>()
+        };
+        let left_siblings = get_siblings(first_leaf_index, |position| position.is_left_child());
+        let right_siblings = get_siblings(last_leaf_index, |position| position.is_right_child());
+
+        if missing_positions.is_empty() {
+            Ok(TransactionAccumulatorRangeProof::new(
+                left_siblings,
+                right_siblings,
+            ))
+        } else {
+            Err(missing_positions)
+        }
+    }
+
+    /// Adds the node (of the accumulator at `proof_version`) iff it is the
+    /// same in the accumulator at the ledger version, i.e., if the versions
+    /// are the same, or if the node is frozen at both versions.
+    fn maybe_add_node(&mut self, proof_version: Version, position: Position, hash: HashValue) {
+        if proof_version == self.ledger_version
+            || position.is_freezable(min(proof_version, self.ledger_version))
+        {
+            self.add_node(position, hash);
+        }
+    }
+}
+
+/// Returns the hash of the accumulator node with the given children
+fn hash_internal_node(left_child: HashValue, right_child: HashValue) -> HashValue {
+    MerkleTreeInternalNode::<TransactionAccumulatorHasher>::new(left_child, right_child).hash()
+}
@@ -87,6 +87,11 @@ impl<T: StorageReaderInterface> Handler<T> {
         request: StorageServiceRequest,
         response_sender: ResponseSender,
     ) {
+        // Compressed responses use zstd iff the peer negotiated the zstd protocol
+        let zstd_compression_level = (protocol_id == ProtocolId::StorageServiceRpcCompressedZstd)
+            .then_some(storage_service_config.zstd_compression_level);
+        let request = request.with_zstd_compression_level(zstd_compression_level);
+
         // Log the request
         trace!(LogSchema::new(LogEntry::ReceivedStorageRequest)
             .request(&request)
@@ -202,13 +207,19 @@ impl<T: StorageReaderInterface> Handler<T> {
         match &request.data_request {
             DataRequest::GetServerProtocolVersion => {
                 let data_response = self.get_server_protocol_version();
-                StorageServiceResponse::new(data_response, request.use_compression)
-                    .map_err(|error| error.into())
+                StorageServiceResponse::new_with_compression(
+                    data_response,
+                    request.get_compression_algorithm(),
+                )
+                .map_err(|error| error.into())
             },
             DataRequest::GetStorageServerSummary => {
                 let data_response = self.get_storage_server_summary();
-                StorageServiceResponse::new(data_response, request.use_compression)
-                    .map_err(|error| error.into())
+                StorageServiceResponse::new_with_compression(
+                    data_response,
+                    request.get_compression_algorithm(),
+                )
+                .map_err(|error| error.into())
             },
             _ => self.process_cachable_request(peer_network_id, request),
         }
@@ -424,8 +435,11 @@ impl<T: StorageReaderInterface> Handler<T> {
 
         // Create the storage response and time the operation
         let create_storage_response = || {
-            StorageServiceResponse::new(data_response, request.use_compression)
-                .map_err(|error| error.into())
+            StorageServiceResponse::new_with_compression(
+                data_response,
+                request.get_compression_algorithm(),
+            )
+            .map_err(|error| error.into())
         };
         let storage_response = utils::execute_and_time_duration(
             &metrics::STORAGE_RESPONSE_CREATION_LATENCY,
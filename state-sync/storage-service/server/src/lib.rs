@@ -35,6 +35,7 @@ use storage::StorageReaderInterface;
 use thiserror::Error;
 use tokio::runtime::Handle;
 
+pub mod archive;
 mod error;
 mod handler;
 mod logging;
@@ -36,6 +36,7 @@ impl<'a> LogSchema<'a> {
 #[derive(Clone, Copy, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LogEntry {
+    ArchiveRefresh,
     OptimisticFetchRefresh,
     OptimisticFetchRequest,
     OptimisticFetchResponse,
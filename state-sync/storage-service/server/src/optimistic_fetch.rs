@@ -127,7 +127,8 @@ impl OptimisticFetchRequest {
             request => unreachable!("Unexpected optimistic fetch request: {:?}", request),
         };
         let storage_request =
-            StorageServiceRequest::new(data_request, self.request.use_compression);
+            StorageServiceRequest::new(data_request, self.request.use_compression)
+                .with_zstd_compression_level(self.request.zstd_compression_level);
         Ok(storage_request)
     }
 
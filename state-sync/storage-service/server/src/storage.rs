@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::Error, metrics::increment_network_frame_overflow};
+use crate::{archive::ArchiveReader, error::Error, metrics::increment_network_frame_overflow};
 use aptos_config::config::StorageServiceConfig;
 use aptos_logger::debug;
 use aptos_storage_interface::{AptosDbError, DbReader, Result as StorageResult};
@@ -97,14 +97,29 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
 pub struct StorageReader {
     config: StorageServiceConfig,
     storage: Arc<dyn DbReader>,
+    archive_reader: Option<Arc<ArchiveReader>>,
 }
 
 impl StorageReader {
     pub fn new(config: StorageServiceConfig, storage: Arc<dyn DbReader>) -> Self {
+        Self::new_with_archive(config, storage, None)
+    }
+
+    /// Creates a storage reader that serves the data pruned from
+    /// storage out of the given archive (if any).
+    pub fn new_with_archive(
+        config: StorageServiceConfig,
+        storage: Arc<dyn DbReader>,
+        archive_reader: Option<ArchiveReader>,
+    ) -> Self {
         // Create a timed storage reader
         let storage = Arc::new(TimedStorageReader::new(storage));
 
-        Self { config, storage }
+        Self {
+            config,
+            storage,
+            archive_reader: archive_reader.map(Arc::new),
+        }
     }
 
     /// Returns the state values range held in the database (lowest to highest).
@@ -184,6 +199,96 @@ impl StorageReader {
             Ok(None)
         }
     }
+
+    /// Extends the given data range (held in the database) to also cover the
+    /// versions held in the archive. This is only possible if the range starts
+    /// at the first transaction in the database, and the continuous range of
+    /// transactions in the archive (starting at version 0) reaches it.
+    /// Otherwise, the advertised range would have a gap.
+    fn extend_with_archived_range(
+        &self,
+        data_range: Option<CompleteDataRange<Version>>,
+        transactions_range: &Option<CompleteDataRange<Version>>,
+    ) -> aptos_storage_service_types::Result<Option<CompleteDataRange<Version>>, Error> {
+        let highest_archived_version = self
+            .archive_reader
+            .as_ref()
+            .and_then(|archive_reader| archive_reader.get_highest_archived_version());
+        match (data_range, transactions_range, highest_archived_version) {
+            (Some(data_range), Some(transactions_range), Some(highest_archived_version))
+                if data_range.lowest() == transactions_range.lowest()
+                    && highest_archived_version.saturating_add(1) >= data_range.lowest() =>
+            {
+                let extended_range = CompleteDataRange::new(0, data_range.highest())
+                    .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
+                Ok(Some(extended_range))
+            },
+            _ => Ok(data_range),
+        }
+    }
+
+    /// Returns true iff the data at the given version has been pruned
+    /// from the database (and should be fetched from the archive).
+    fn is_archived(&self, version: Version) -> aptos_storage_service_types::Result<bool, Error> {
+        let first_transaction_version = self
+            .storage
+            .get_first_txn_version()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        Ok(matches!(first_transaction_version, Some(first_version) if version < first_version))
+    }
+
+    /// Fetches the transactions from the database (or the archive, if
+    /// the transactions have been pruned from the database).
+    fn fetch_transactions(
+        &self,
+        start_version: Version,
+        num_transactions: u64,
+        proof_version: Version,
+        include_events: bool,
+    ) -> aptos_storage_service_types::Result<TransactionListWithProof, Error> {
+        if let Some(archive_reader) = &self.archive_reader {
+            if self.is_archived(start_version)? {
+                return archive_reader.get_transactions(
+                    &self.storage,
+                    start_version,
+                    num_transactions,
+                    proof_version,
+                    include_events,
+                );
+            }
+        }
+        self.storage
+            .get_transactions(
+                start_version,
+                num_transactions,
+                proof_version,
+                include_events,
+            )
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))
+    }
+
+    /// Fetches the transaction outputs from the database (or the archive,
+    /// if the outputs have been pruned from the database).
+    fn fetch_transaction_outputs(
+        &self,
+        start_version: Version,
+        num_outputs: u64,
+        proof_version: Version,
+    ) -> aptos_storage_service_types::Result<TransactionOutputListWithProof, Error> {
+        if let Some(archive_reader) = &self.archive_reader {
+            if self.is_archived(start_version)? {
+                return archive_reader.get_transaction_outputs(
+                    &self.storage,
+                    start_version,
+                    num_outputs,
+                    proof_version,
+                );
+            }
+        }
+        self.storage
+            .get_transaction_outputs(start_version, num_outputs, proof_version)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))
+    }
 }
 
 impl StorageReaderInterface for StorageReader {
@@ -217,6 +322,11 @@ impl StorageReaderInterface for StorageReader {
         // Fetch the state values range
         let states = self.fetch_state_values_range(latest_version, &transactions)?;
 
+        // Extend the transaction and transaction output ranges with the archive
+        let transaction_outputs =
+            self.extend_with_archived_range(transaction_outputs, &transactions)?;
+        let transactions = self.extend_with_archived_range(transactions, &transactions)?;
+
         // Return the relevant data summary
         let data_summary = DataSummary {
             synced_ledger_info: Some(latest_ledger_info_with_sigs),
@@ -243,15 +353,12 @@ impl StorageReaderInterface for StorageReader {
 
         // Attempt to serve the request
         while num_transactions_to_fetch >= 1 {
-            let transaction_list_with_proof = self
-                .storage
-                .get_transactions(
-                    start_version,
-                    num_transactions_to_fetch,
-                    proof_version,
-                    include_events,
-                )
-                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            let transaction_list_with_proof = self.fetch_transactions(
+                start_version,
+                num_transactions_to_fetch,
+                proof_version,
+                include_events,
+            )?;
             if num_transactions_to_fetch == 1 {
                 return Ok(transaction_list_with_proof); // We cannot return less than a single item
             }
@@ -347,10 +454,8 @@ impl StorageReaderInterface for StorageReader {
 
         // Attempt to serve the request
         while num_outputs_to_fetch >= 1 {
-            let output_list_with_proof = self
-                .storage
-                .get_transaction_outputs(start_version, num_outputs_to_fetch, proof_version)
-                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            let output_list_with_proof =
+                self.fetch_transaction_outputs(start_version, num_outputs_to_fetch, proof_version)?;
             if num_outputs_to_fetch == 1 {
                 return Ok(output_list_with_proof); // We cannot return less than a single item
             }
@@ -398,10 +503,8 @@ impl StorageReaderInterface for StorageReader {
         // doesn't fit, return a transaction chunk instead.
         let mut num_output_reductions = 0;
         while num_output_reductions <= max_num_output_reductions {
-            let output_list_with_proof = self
-                .storage
-                .get_transaction_outputs(start_version, num_outputs_to_fetch, proof_version)
-                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            let output_list_with_proof =
+                self.fetch_transaction_outputs(start_version, num_outputs_to_fetch, proof_version)?;
             let (overflow_frame, num_bytes) = check_overflow_network_frame(
                 &output_list_with_proof,
                 self.config.max_network_chunk_bytes,
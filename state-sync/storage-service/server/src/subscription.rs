@@ -125,7 +125,8 @@ impl SubscriptionRequest {
             request => unreachable!("Unexpected subscription request: {:?}", request),
         };
         let storage_request =
-            StorageServiceRequest::new(data_request, self.request.use_compression);
+            StorageServiceRequest::new(data_request, self.request.use_compression)
+                .with_zstd_compression_level(self.request.zstd_compression_level);
         Ok(storage_request)
     }
 
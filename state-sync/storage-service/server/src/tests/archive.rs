@@ -0,0 +1,432 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    archive::{AccumulatorNodes, ArchiveReader},
+    storage::{StorageReader, StorageReaderInterface},
+    tests::mock,
+};
+use aptos_backup_storage::{
+    manifest::transaction::{TransactionBackup, TransactionChunk},
+    metadata::Metadata,
+    storage::{local_fs::LocalFs, BackupStorage},
+};
+use aptos_config::config::{StorageConfig, StorageServiceConfig};
+use aptos_crypto::{
+    hash::{CryptoHash, TransactionAccumulatorHasher, ACCUMULATOR_PLACEHOLDER_HASH},
+    HashValue,
+};
+use aptos_storage_service_types::responses::CompleteDataRange;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    contract_event::ContractEvent,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    proof::{position::Position, MerkleTreeInternalNode, TransactionAccumulatorRangeProof},
+    transaction::{
+        ExecutionStatus, Transaction, TransactionInfo, TransactionInfoListWithProof,
+        TransactionListWithProof, Version,
+    },
+    write_set::WriteSet,
+};
+use mockall::predicate::{always, eq};
+use std::{path::Path, sync::Arc, thread, time::Duration};
+use tokio::{io::AsyncWriteExt, runtime::Runtime};
+
+// The latest version in storage, and the first version that hasn't been pruned
+const LEDGER_VERSION: Version = 19;
+const FIRST_STORED_VERSION: Version = 12;
+
+#[test]
+fn test_accumulator_nodes_range_proof() {
+    // Create an accumulator with 13 leaves (i.e., at ledger version 12)
+    let leaves: Vec<_> = (0..13).map(|_| HashValue::random()).collect();
+    let ledger_version = 12;
+    let root_hash = get_root_hash(&leaves);
+
+    // Add the proof of the first version that hasn't been pruned (version 9)
+    let mut accumulator_nodes = AccumulatorNodes::new(ledger_version);
+    let proof_root_hash = accumulator_nodes
+        .add_range_proof(
+            ledger_version,
+            9,
+            &[leaves[9]],
+            &get_range_proof(&leaves, 9, 9),
+        )
+        .unwrap();
+    assert_eq!(proof_root_hash, root_hash);
+
+    // Add the proof of a chunk holding versions 2 to 4 (relative to version 6)
+    let proof_root_hash = accumulator_nodes
+        .add_range_proof(6, 2, &leaves[2..=4], &get_range_proof(&leaves[..=6], 2, 4))
+        .unwrap();
+    assert_eq!(proof_root_hash, get_root_hash(&leaves[..=6]));
+
+    // Verify the node holding versions 6 and 7 is missing (it isn't frozen at version 6)
+    let missing_positions = accumulator_nodes.get_range_proof(2, 4).unwrap_err();
+    assert_eq!(missing_positions, vec![Position::from_level_and_pos(1, 3)]);
+
+    // Add the proof of a chunk holding versions 5 to 8 (relative to version 10)
+    accumulator_nodes
+        .add_range_proof(
+            10,
+            5,
+            &leaves[5..=8],
+            &get_range_proof(&leaves[..=10], 5, 8),
+        )
+        .unwrap();
+
+    // Verify the range proof is now relative to the ledger version
+    let range_proof = accumulator_nodes.get_range_proof(2, 4).unwrap();
+    range_proof
+        .verify(root_hash, Some(2), &leaves[2..=4])
+        .unwrap();
+    assert_eq!(range_proof, get_range_proof(&leaves, 2, 4));
+}
+
+#[test]
+fn test_accumulator_nodes_invalid_proof() {
+    // Create an accumulator with 8 leaves
+    let leaves: Vec<_> = (0..8).map(|_| HashValue::random()).collect();
+    let mut accumulator_nodes = AccumulatorNodes::new(7);
+
+    // Verify that adding a proof for a different leaf index fails
+    let range_proof = get_range_proof(&leaves, 3, 3);
+    accumulator_nodes
+        .add_range_proof(7, 2, &[leaves[3]], &range_proof)
+        .unwrap_err();
+
+    // Verify that adding a proof without leaves fails
+    accumulator_nodes
+        .add_range_proof(7, 3, &[], &range_proof)
+        .unwrap_err();
+}
+
+#[test]
+fn test_storage_reader_archive_data_summary() {
+    // Create the transactions and a runtime to read the archive
+    let (transactions, transaction_infos) = create_transactions();
+    let runtime = Runtime::new().unwrap();
+
+    // Create an archive holding all pruned transactions (i.e., versions 0 to 11)
+    let backup_dir = TempPath::new();
+    runtime.block_on(async {
+        let storage = LocalFs::new(backup_dir.path().to_path_buf());
+        save_transaction_backup(&storage, &transactions, &transaction_infos, 0, 7, 9).await;
+        save_transaction_backup(&storage, &transactions, &transaction_infos, 8, 11, 11).await;
+    });
+
+    // Verify the data summary advertises the archived transactions and outputs
+    let data_dir = TempPath::new();
+    let storage_reader = create_storage_reader(
+        &runtime,
+        &transaction_infos,
+        backup_dir.path(),
+        data_dir.path(),
+    );
+    let data_summary = storage_reader.get_data_summary().unwrap();
+    let expected_range = CompleteDataRange::new(0, LEDGER_VERSION).unwrap();
+    assert_eq!(data_summary.transactions, Some(expected_range));
+    assert_eq!(data_summary.transaction_outputs, Some(expected_range));
+
+    // Verify the states are still only advertised from storage
+    let stored_range = CompleteDataRange::new(FIRST_STORED_VERSION, LEDGER_VERSION).unwrap();
+    assert_eq!(data_summary.states, Some(stored_range));
+
+    // Create an archive with a gap (i.e., versions 8 and 9 are missing)
+    let backup_dir = TempPath::new();
+    runtime.block_on(async {
+        let storage = LocalFs::new(backup_dir.path().to_path_buf());
+        save_transaction_backup(&storage, &transactions, &transaction_infos, 0, 7, 9).await;
+        save_transaction_backup(&storage, &transactions, &transaction_infos, 10, 11, 11).await;
+    });
+
+    // Verify the data summary only advertises the transactions and outputs in storage
+    let data_dir = TempPath::new();
+    let storage_reader = create_storage_reader(
+        &runtime,
+        &transaction_infos,
+        backup_dir.path(),
+        data_dir.path(),
+    );
+    let data_summary = storage_reader.get_data_summary().unwrap();
+    assert_eq!(data_summary.transactions, Some(stored_range));
+    assert_eq!(data_summary.transaction_outputs, Some(stored_range));
+}
+
+#[test]
+fn test_storage_reader_archive_transactions() {
+    // Create the transactions and a runtime to read the archive
+    let (transactions, transaction_infos) = create_transactions();
+    let runtime = Runtime::new().unwrap();
+
+    // Create an archive holding all pruned transactions (i.e., versions 0 to 11)
+    let backup_dir = TempPath::new();
+    runtime.block_on(async {
+        let storage = LocalFs::new(backup_dir.path().to_path_buf());
+        save_transaction_backup(&storage, &transactions, &transaction_infos, 0, 7, 9).await;
+        save_transaction_backup(&storage, &transactions, &transaction_infos, 8, 11, 11).await;
+    });
+
+    // Create the storage reader
+    let data_dir = TempPath::new();
+    let storage_reader = create_storage_reader(
+        &runtime,
+        &transaction_infos,
+        backup_dir.path(),
+        data_dir.path(),
+    );
+
+    // Request data that crosses the pruned boundary, with proofs relative to different versions
+    for proof_version in [15, LEDGER_VERSION] {
+        let ledger_info = create_ledger_info(&transaction_infos, proof_version);
+        for (start_version, expected_end_version) in [(6, 7), (8, 11)] {
+            // Verify the transactions are served from a single backup chunk
+            let transaction_list_with_proof = storage_reader
+                .get_transactions_with_proof(proof_version, start_version, 14, false)
+                .unwrap();
+            assert_eq!(
+                transaction_list_with_proof.transactions,
+                transactions[start_version as usize..=expected_end_version as usize].to_vec()
+            );
+
+            // Verify the transaction proof against the ledger info at the proof version
+            transaction_list_with_proof
+                .verify(ledger_info.ledger_info(), Some(start_version))
+                .unwrap();
+
+            // Verify the transaction outputs and their proof
+            let output_list_with_proof = storage_reader
+                .get_transaction_outputs_with_proof(proof_version, start_version, 14)
+                .unwrap();
+            assert_eq!(
+                output_list_with_proof.transactions_and_outputs.len() as Version,
+                expected_end_version - start_version + 1
+            );
+            output_list_with_proof
+                .verify(ledger_info.ledger_info(), Some(start_version))
+                .unwrap();
+        }
+    }
+}
+
+/// Returns the range proof of the leaves in [`first_leaf_index`,
+/// `last_leaf_index`] in the accumulator with the given leaves.
+fn get_range_proof(
+    leaves: &[HashValue],
+    first_leaf_index: u64,
+    last_leaf_index: u64,
+) -> TransactionAccumulatorRangeProof {
+    let root_level = Position::root_from_leaf_count(leaves.len() as u64).level() as usize;
+    let get_siblings = |leaf_index: u64, filter: fn(&Position) -> bool| {
+        Position::from_leaf_index(leaf_index)
+            .iter_ancestor_sibling()
+            .take(root_level)
+            .filter(filter)
+            .map(|position| get_node_hash(leaves, position))
+            .collect()
+    };
+    TransactionAccumulatorRangeProof::new(
+        get_siblings(first_leaf_index, |position| position.is_left_child()),
+        get_siblings(last_leaf_index, |position| position.is_right_child()),
+    )
+}
+
+/// Returns the root hash of the accumulator with the given leaves
+fn get_root_hash(leaves: &[HashValue]) -> HashValue {
+    get_node_hash(leaves, Position::root_from_leaf_count(leaves.len() as u64))
+}
+
+/// Returns the hash of the node at the given position, in the accumulator
+/// with the given leaves.
+fn get_node_hash(leaves: &[HashValue], position: Position) -> HashValue {
+    if position.is_placeholder(leaves.len() as u64 - 1) {
+        *ACCUMULATOR_PLACEHOLDER_HASH
+    } else if position.is_leaf() {
+        leaves[(position.to_inorder_index() / 2) as usize]
+    } else {
+        MerkleTreeInternalNode::<TransactionAccumulatorHasher>::new(
+            get_node_hash(leaves, position.left_child()),
+            get_node_hash(leaves, position.right_child()),
+        )
+        .hash()
+    }
+}
+
+/// Creates the transactions (and their infos) for all versions up to the ledger version
+fn create_transactions() -> (Vec<Transaction>, Vec<TransactionInfo>) {
+    (0..=LEDGER_VERSION)
+        .map(|_| {
+            let transaction = Transaction::StateCheckpoint(HashValue::random());
+            let transaction_info = TransactionInfo::new(
+                CryptoHash::hash(&transaction),
+                CryptoHash::hash(&WriteSet::default()),
+                *ACCUMULATOR_PLACEHOLDER_HASH,
+                None,
+                0,
+                ExecutionStatus::Success,
+            );
+            (transaction, transaction_info)
+        })
+        .unzip()
+}
+
+/// Creates a ledger info at the given version, holding the root hash of the
+/// accumulator with the given transaction infos.
+fn create_ledger_info(
+    transaction_infos: &[TransactionInfo],
+    version: Version,
+) -> LedgerInfoWithSignatures {
+    let leaves: Vec<_> = transaction_infos[..=version as usize]
+        .iter()
+        .map(CryptoHash::hash)
+        .collect();
+    let block_info = BlockInfo::new(
+        1,
+        0,
+        HashValue::zero(),
+        get_root_hash(&leaves),
+        version,
+        0,
+        None,
+    );
+    LedgerInfoWithSignatures::new(
+        LedgerInfo::new(block_info, HashValue::zero()),
+        AggregateSignature::empty(),
+    )
+}
+
+/// Creates a storage reader (with versions before the first stored version
+/// pruned) that serves the pruned data out of the backup in `backup_dir`.
+fn create_storage_reader(
+    runtime: &Runtime,
+    transaction_infos: &[TransactionInfo],
+    backup_dir: &Path,
+    data_dir: &Path,
+) -> StorageReader {
+    // Create the mock db reader
+    let latest_ledger_info = create_ledger_info(transaction_infos, LEDGER_VERSION);
+    let mut db_reader =
+        mock::create_mock_db_with_summary_updates(latest_ledger_info, FIRST_STORED_VERSION);
+
+    // Expect requests for the proofs of the first stored version
+    let leaves: Vec<_> = transaction_infos.iter().map(CryptoHash::hash).collect();
+    let stored_transaction_info = transaction_infos[FIRST_STORED_VERSION as usize].clone();
+    db_reader
+        .expect_get_transactions()
+        .with(eq(FIRST_STORED_VERSION), eq(1), always(), eq(false))
+        .returning(move |_, _, proof_version, _| {
+            let range_proof = get_range_proof(
+                &leaves[..=proof_version as usize],
+                FIRST_STORED_VERSION,
+                FIRST_STORED_VERSION,
+            );
+            Ok(TransactionListWithProof::new(
+                vec![],
+                None,
+                Some(FIRST_STORED_VERSION),
+                TransactionInfoListWithProof::new(range_proof, vec![
+                    stored_transaction_info.clone()
+                ]),
+            ))
+        });
+
+    // Create the archive reader and wait until the archive has been loaded
+    let mut storage_config = StorageConfig::default();
+    storage_config.set_data_dir(data_dir.to_path_buf());
+    storage_config.backup_storage.local_fs_dir = Some(backup_dir.to_path_buf());
+    let archive_reader = ArchiveReader::new(storage_config, runtime.handle().clone());
+    while archive_reader.get_highest_archived_version().is_none() {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    StorageReader::new_with_archive(
+        StorageServiceConfig::default(),
+        Arc::new(db_reader),
+        Some(archive_reader),
+    )
+}
+
+/// Saves a transaction backup (with a single chunk) holding the versions in
+/// [`first_version`, `last_version`], with a proof relative to `proof_version`.
+async fn save_transaction_backup(
+    storage: &LocalFs,
+    transactions: &[Transaction],
+    transaction_infos: &[TransactionInfo],
+    first_version: Version,
+    last_version: Version,
+    proof_version: Version,
+) {
+    let backup_handle = storage
+        .create_backup(
+            &format!("transaction_{}-{}", first_version, last_version)
+                .parse()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Write the transactions as records
+    let (transactions_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"transactions.chunk".parse().unwrap())
+        .await
+        .unwrap();
+    for version in first_version..=last_version {
+        let record = bcs::to_bytes(&(
+            &transactions[version as usize],
+            &transaction_infos[version as usize],
+            Vec::<ContractEvent>::new(),
+            WriteSet::default(),
+        ))
+        .unwrap();
+        file.write_all(&(record.len() as u32).to_be_bytes())
+            .await
+            .unwrap();
+        file.write_all(&record).await.unwrap();
+    }
+    file.shutdown().await.unwrap();
+
+    // Write the proof of the chunk
+    let leaves: Vec<_> = transaction_infos[..=proof_version as usize]
+        .iter()
+        .map(CryptoHash::hash)
+        .collect();
+    let proof = (
+        get_range_proof(&leaves, first_version, last_version),
+        create_ledger_info(transaction_infos, proof_version),
+    );
+    let (proof_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"transactions.proof".parse().unwrap())
+        .await
+        .unwrap();
+    file.write_all(&bcs::to_bytes(&proof).unwrap())
+        .await
+        .unwrap();
+    file.shutdown().await.unwrap();
+
+    // Write the manifest and save the metadata
+    let manifest = TransactionBackup {
+        first_version,
+        last_version,
+        chunks: vec![TransactionChunk {
+            first_version,
+            last_version,
+            transactions: transactions_handle,
+            proof: proof_handle,
+        }],
+    };
+    let (manifest_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"transaction.manifest".parse().unwrap())
+        .await
+        .unwrap();
+    file.write_all(&serde_json::to_vec(&manifest).unwrap())
+        .await
+        .unwrap();
+    file.shutdown().await.unwrap();
+    let metadata = Metadata::new_transaction_backup(first_version, last_version, manifest_handle);
+    storage
+        .save_metadata_line(&metadata.name(), &metadata.to_text_line().unwrap())
+        .await
+        .unwrap();
+}
@@ -4,7 +4,12 @@
 use crate::tests::{mock, mock::MockClient, utils};
 use aptos_config::config::StorageServiceConfig;
 use aptos_crypto::hash::HashValue;
-use aptos_storage_service_types::responses::{DataResponse, StorageServiceResponse};
+use aptos_network::protocols::wire::handshake::v1::ProtocolId;
+use aptos_storage_service_types::{
+    requests::{DataRequest, TransactionsWithProofRequest},
+    responses::{DataResponse, StorageServiceResponse},
+    StorageServiceRequest,
+};
 use aptos_types::{
     proof::definition::SparseMerkleRangeProof, state_store::state_value::StateValueChunkWithProof,
 };
@@ -81,6 +86,83 @@ async fn test_cachable_requests_compression() {
     }
 }
 
+#[tokio::test]
+async fn test_cachable_requests_zstd_compression() {
+    // Create test data
+    let start_version = 0;
+    let end_version = 454;
+    let proof_version = end_version;
+    let include_events = false;
+    let protocol_ids = [
+        ProtocolId::StorageServiceRpc,
+        ProtocolId::StorageServiceRpcCompressedZstd,
+    ];
+
+    // Create the mock db reader
+    let mut db_reader = mock::create_mock_db_reader();
+    let transaction_list_with_proof = utils::create_transaction_list_with_proof(
+        start_version,
+        end_version,
+        proof_version,
+        include_events,
+    );
+
+    // Expect the data to be fetched from storage once per compression algorithm
+    let transactions = transaction_list_with_proof.clone();
+    db_reader
+        .expect_get_transactions()
+        .times(protocol_ids.len())
+        .with(
+            eq(start_version),
+            eq(end_version - start_version + 1),
+            eq(proof_version),
+            eq(include_events),
+        )
+        .returning(move |_, _, _, _| Ok(transactions.clone()));
+
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+    utils::update_storage_server_summary(&mut service, end_version, 10);
+    tokio::spawn(service.start());
+
+    // Repeatedly fetch the data over both protocols and verify the responses
+    for _ in 0..10 {
+        for protocol_id in protocol_ids {
+            let data_request =
+                DataRequest::GetTransactionsWithProof(TransactionsWithProofRequest {
+                    proof_version,
+                    start_version,
+                    end_version,
+                    include_events,
+                });
+            let request = StorageServiceRequest::new(data_request, true);
+            let response = mock_client
+                .process_request_with_protocol(request, protocol_id)
+                .await
+                .unwrap();
+
+            // Verify the response is compressed using the negotiated algorithm
+            match (protocol_id, &response) {
+                (ProtocolId::StorageServiceRpc, StorageServiceResponse::CompressedResponse(..))
+                | (
+                    ProtocolId::StorageServiceRpcCompressedZstd,
+                    StorageServiceResponse::ZstdCompressedResponse(..),
+                ) => {},
+                _ => panic!(
+                    "Unexpected response for protocol {:?}: {:?}",
+                    protocol_id, response
+                ),
+            }
+            match response.get_data_response().unwrap() {
+                DataResponse::TransactionsWithProof(response) => {
+                    assert_eq!(response, transaction_list_with_proof);
+                },
+                _ => panic!("Expected transactions with proof but got: {:?}", response),
+            };
+        }
+    }
+}
+
 #[tokio::test]
 async fn test_cachable_requests_data_versions() {
     // Create test data
@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod archive;
 mod cache;
 mod epoch_ending;
 mod mock;
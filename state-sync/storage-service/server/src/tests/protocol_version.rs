@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::tests::{mock::MockClient, utils};
+use aptos_network::protocols::wire::handshake::v1::ProtocolId;
 use aptos_storage_service_types::{
     requests::DataRequest,
     responses::{DataResponse, ServerProtocolVersion, StorageServiceResponse},
+    StorageServiceRequest,
 };
 use claims::assert_matches;
 
@@ -31,6 +33,40 @@ async fn test_get_server_protocol_version() {
     );
 }
 
+#[tokio::test]
+async fn test_get_server_protocol_version_zstd() {
+    // Create the storage client and server
+    let (mut mock_client, service, _, _, _) = MockClient::new(None, None);
+    tokio::spawn(service.start());
+
+    // Process requests to fetch the protocol version over the zstd protocol
+    let expected_data_response = DataResponse::ServerProtocolVersion(ServerProtocolVersion {
+        protocol_version: PROTOCOL_VERSION,
+    });
+    for use_compression in [true, false] {
+        let request =
+            StorageServiceRequest::new(DataRequest::GetServerProtocolVersion, use_compression);
+        let response = mock_client
+            .process_request_with_protocol(request, ProtocolId::StorageServiceRpcCompressedZstd)
+            .await
+            .unwrap();
+
+        // Verify the response is only compressed (using zstd) if requested
+        if use_compression {
+            assert_matches!(
+                response,
+                StorageServiceResponse::ZstdCompressedResponse(_, _)
+            );
+        } else {
+            assert_matches!(response, StorageServiceResponse::RawResponse(_));
+        }
+        assert_eq!(
+            response.get_data_response().unwrap(),
+            expected_data_response
+        );
+    }
+}
+
 /// Sends a protocol version request and processes the response
 async fn get_protocol_version(
     mock_client: &mut MockClient,
@@ -97,7 +97,7 @@ pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
     response_sender: ResponseSender,
 ) -> aptos_storage_service_types::Result<DataResponse, Error> {
     // Handle the storage service request to fetch the missing data
-    let use_compression = missing_data_request.use_compression;
+    let compression_algorithm = missing_data_request.get_compression_algorithm();
     let handler = Handler::new(
         cached_storage_server_summary,
         optimistic_fetches,
@@ -161,16 +161,18 @@ pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
     };
 
     // Create the storage service response
-    let storage_response =
-        match StorageServiceResponse::new(transformed_data_response.clone(), use_compression) {
-            Ok(storage_response) => storage_response,
-            Err(error) => {
-                return Err(Error::UnexpectedErrorEncountered(format!(
-                    "Failed to create transformed response! Error: {:?}",
-                    error
-                )));
-            },
-        };
+    let storage_response = match StorageServiceResponse::new_with_compression(
+        transformed_data_response.clone(),
+        compression_algorithm,
+    ) {
+        Ok(storage_response) => storage_response,
+        Err(error) => {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Failed to create transformed response! Error: {:?}",
+                error
+            )));
+        },
+    };
 
     // Send the response to the peer
     handler.send_response(missing_data_request, Ok(storage_response), response_sender);
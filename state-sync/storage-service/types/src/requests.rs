@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::COMPRESSION_SUFFIX_LABEL;
+use aptos_compression::algorithm::CompressionAlgorithm;
 use aptos_types::transaction::Version;
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +11,11 @@ use serde::{Deserialize, Serialize};
 pub struct StorageServiceRequest {
     pub data_request: DataRequest, // The data to fetch from the storage service
     pub use_compression: bool,     // Whether or not the client wishes data to be compressed
+    /// The zstd compression level to use for compressed data (if zstd should
+    /// be used instead of LZ4). This is set by the server based on the negotiated
+    /// network protocol and its config (i.e., it is never sent over the wire).
+    #[serde(skip)]
+    pub zstd_compression_level: Option<i32>,
 }
 
 impl StorageServiceRequest {
@@ -17,6 +23,24 @@ impl StorageServiceRequest {
         Self {
             data_request,
             use_compression,
+            zstd_compression_level: None,
+        }
+    }
+
+    /// Sets the zstd compression level to use for compressed data (if any)
+    pub fn with_zstd_compression_level(mut self, zstd_compression_level: Option<i32>) -> Self {
+        self.zstd_compression_level = zstd_compression_level;
+        self
+    }
+
+    /// Returns the algorithm to compress the response with (if any)
+    pub fn get_compression_algorithm(&self) -> Option<CompressionAlgorithm> {
+        if !self.use_compression {
+            None
+        } else if let Some(zstd_compression_level) = self.zstd_compression_level {
+            Some(CompressionAlgorithm::Zstd(zstd_compression_level))
+        } else {
+            Some(CompressionAlgorithm::Lz4)
         }
     }
 
@@ -13,7 +13,9 @@ use crate::{
     responses::Error::DegenerateRangeError,
     Epoch, StorageServiceRequest, COMPRESSION_SUFFIX_LABEL,
 };
-use aptos_compression::{client::CompressionClient, CompressedData};
+use aptos_compression::{
+    algorithm::CompressionAlgorithm, client::CompressionClient, CompressedData,
+};
 use aptos_config::config::{
     AptosDataClientConfig, StorageServiceConfig, MAX_APPLICATION_MESSAGE_SIZE,
 };
@@ -59,27 +61,43 @@ impl From<aptos_compression::Error> for Error {
 pub enum StorageServiceResponse {
     CompressedResponse(String, CompressedData), // Store the label and the data (e.g., for logging/metrics)
     RawResponse(DataResponse),
+    ZstdCompressedResponse(String, CompressedData), // Only sent to peers that negotiated zstd
 }
 
 impl StorageServiceResponse {
-    /// Creates a new response and performs compression if required
+    /// Creates a new response and performs compression (using LZ4) if required
     pub fn new(data_response: DataResponse, perform_compression: bool) -> Result<Self, Error> {
-        if perform_compression {
+        let compression_algorithm = perform_compression.then_some(CompressionAlgorithm::Lz4);
+        Self::new_with_compression(data_response, compression_algorithm)
+    }
+
+    /// Creates a new response and performs compression using the given algorithm (if any)
+    pub fn new_with_compression(
+        data_response: DataResponse,
+        compression_algorithm: Option<CompressionAlgorithm>,
+    ) -> Result<Self, Error> {
+        if let Some(compression_algorithm) = compression_algorithm {
             // Serialize and compress the raw data
             let raw_data = bcs::to_bytes(&data_response)
                 .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
-            let compressed_data = aptos_compression::compress(
+            let compressed_data = aptos_compression::compress_with_algorithm(
                 raw_data,
                 CompressionClient::StateSync,
+                &compression_algorithm,
                 MAX_APPLICATION_MESSAGE_SIZE,
             )?;
 
             // Create the compressed response
             let label = data_response.get_label().to_string() + COMPRESSION_SUFFIX_LABEL;
-            Ok(StorageServiceResponse::CompressedResponse(
-                label,
-                compressed_data,
-            ))
+            match compression_algorithm {
+                CompressionAlgorithm::Lz4 => Ok(StorageServiceResponse::CompressedResponse(
+                    label,
+                    compressed_data,
+                )),
+                CompressionAlgorithm::Zstd(_) => Ok(
+                    StorageServiceResponse::ZstdCompressedResponse(label, compressed_data),
+                ),
+            }
         } else {
             Ok(StorageServiceResponse::RawResponse(data_response))
         }
@@ -89,23 +107,21 @@ impl StorageServiceResponse {
     pub fn get_data_response(&self) -> Result<DataResponse, Error> {
         match self {
             StorageServiceResponse::CompressedResponse(_, compressed_data) => {
-                let raw_data = aptos_compression::decompress(
-                    compressed_data,
-                    CompressionClient::StateSync,
-                    MAX_APPLICATION_MESSAGE_SIZE,
-                )?;
-                let data_response = bcs::from_bytes::<DataResponse>(&raw_data)
-                    .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
-                Ok(data_response)
+                decompress_data_response(compressed_data, &CompressionAlgorithm::Lz4)
             },
             StorageServiceResponse::RawResponse(data_response) => Ok(data_response.clone()),
+            StorageServiceResponse::ZstdCompressedResponse(_, compressed_data) => {
+                // The level used by the server isn't required for decompression
+                decompress_data_response(compressed_data, &CompressionAlgorithm::zstd())
+            },
         }
     }
 
     /// Returns a summary label for the response
     pub fn get_label(&self) -> String {
         match self {
-            StorageServiceResponse::CompressedResponse(label, _) => label.clone(),
+            StorageServiceResponse::CompressedResponse(label, _)
+            | StorageServiceResponse::ZstdCompressedResponse(label, _) => label.clone(),
             StorageServiceResponse::RawResponse(data_response) => {
                 data_response.get_label().to_string()
             },
@@ -114,10 +130,28 @@ impl StorageServiceResponse {
 
     /// Returns true iff the data response is compressed
     pub fn is_compressed(&self) -> bool {
-        matches!(self, Self::CompressedResponse(_, _))
+        matches!(
+            self,
+            Self::CompressedResponse(_, _) | Self::ZstdCompressedResponse(_, _)
+        )
     }
 }
 
+/// Decompresses and deserializes the given data response
+fn decompress_data_response(
+    compressed_data: &CompressedData,
+    compression_algorithm: &CompressionAlgorithm,
+) -> Result<DataResponse, Error> {
+    let raw_data = aptos_compression::decompress_with_algorithm(
+        compressed_data,
+        CompressionClient::StateSync,
+        compression_algorithm,
+        MAX_APPLICATION_MESSAGE_SIZE,
+    )?;
+    bcs::from_bytes::<DataResponse>(&raw_data)
+        .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))
+}
+
 /// A useful type to hold optional transaction data
 pub type TransactionOrOutputListWithProof = (
     Option<TransactionListWithProof>,
@@ -10,9 +10,13 @@ use crate::{
         SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{CompleteDataRange, DataSummary, ProtocolMetadata},
+    responses::{
+        CompleteDataRange, DataResponse, DataSummary, ProtocolMetadata, ServerProtocolVersion,
+        StorageServiceResponse,
+    },
     Epoch, StorageServiceRequest,
 };
+use aptos_compression::algorithm::CompressionAlgorithm;
 use aptos_config::config::AptosDataClientConfig;
 use aptos_crypto::hash::HashValue;
 use aptos_time_service::{TimeService, TimeServiceTrait};
@@ -46,6 +50,48 @@ fn test_complete_data_ranges() {
     assert_err!(CompleteDataRange::new(0, u64::MAX));
 }
 
+#[test]
+fn test_zstd_compressed_responses() {
+    // Create a zstd compression request
+    let data_request = DataRequest::GetServerProtocolVersion;
+    let request = StorageServiceRequest::new(data_request.clone(), true)
+        .with_zstd_compression_level(Some(19));
+    assert_eq!(
+        request.get_compression_algorithm(),
+        Some(CompressionAlgorithm::Zstd(19))
+    );
+
+    // Verify the zstd level is never sent over the wire
+    let lz4_request = StorageServiceRequest::new(data_request, true);
+    let request_bytes = bcs::to_bytes(&request).unwrap();
+    assert_eq!(request_bytes, bcs::to_bytes(&lz4_request).unwrap());
+    assert_eq!(
+        bcs::from_bytes::<StorageServiceRequest>(&request_bytes).unwrap(),
+        lz4_request
+    );
+
+    // Verify the response is compressed using zstd and can be decompressed
+    let data_response = DataResponse::ServerProtocolVersion(ServerProtocolVersion {
+        protocol_version: 1,
+    });
+    let response = StorageServiceResponse::new_with_compression(
+        data_response.clone(),
+        request.get_compression_algorithm(),
+    )
+    .unwrap();
+    assert!(matches!(
+        response,
+        StorageServiceResponse::ZstdCompressedResponse(_, _)
+    ));
+    assert!(response.is_compressed());
+    assert_eq!(response.get_data_response().unwrap(), data_response);
+
+    // Verify uncompressed requests aren't affected by the zstd level
+    let request = StorageServiceRequest::new(DataRequest::GetServerProtocolVersion, false)
+        .with_zstd_compression_level(Some(19));
+    assert_eq!(request.get_compression_algorithm(), None);
+}
+
 #[test]
 fn test_data_summary_service_epoch_ending_ledger_infos() {
     // Create a data client config and data summary
@@ -10,7 +10,7 @@ use crate::{
     },
     state_store::StateStore,
 };
-use aptos_crypto::hash::HashValue;
+use aptos_crypto::hash::{CryptoHash, HashValue};
 use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
 use aptos_types::{
     contract_event::ContractEvent,
@@ -18,10 +18,10 @@ use aptos_types::{
     proof::{SparseMerkleRangeProof, TransactionAccumulatorRangeProof, TransactionInfoWithProof},
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{Transaction, TransactionInfo, Version},
-    write_set::WriteSet,
+    write_set::{TransactionWrite, WriteSet},
 };
 use serde::{Deserialize, Serialize};
-use std::{fmt, sync::Arc};
+use std::{collections::BTreeMap, fmt, sync::Arc};
 
 /// `BackupHandler` provides functionalities for AptosDB data backup.
 #[derive(Clone)]
@@ -136,6 +136,34 @@ impl BackupHandler {
         Ok(Box::new(iterator))
     }
 
+    /// Gets an iterator which yields the state keys updated in `(base_version, version]` together
+    /// with their values at `version` (`None` if deleted), ordered by key hash. The write sets in
+    /// the range must not have been pruned.
+    pub fn get_state_diff_iter(
+        &self,
+        base_version: Version,
+        version: Version,
+    ) -> Result<impl Iterator<Item = Result<(StateKey, Option<StateValue>)>> + Send + Sync> {
+        ensure!(
+            version > base_version,
+            "Bad state diff range: ({}, {}]",
+            base_version,
+            version
+        );
+        let mut updates = BTreeMap::new();
+        for write_set_res in self
+            .ledger_db
+            .write_set_db()
+            .get_write_set_iter(base_version + 1, (version - base_version) as usize)?
+        {
+            for (key, op) in write_set_res?.iter() {
+                updates.insert(key.hash(), (key.clone(), op.as_state_value()));
+            }
+        }
+        BACKUP_STATE_SNAPSHOT_VERSION.set(version as i64);
+        Ok(updates.into_values().map(Ok))
+    }
+
     /// Gets the proof that proves a range of accounts.
     pub fn get_account_state_range_proof(
         &self,
@@ -55,6 +55,17 @@ impl RestoreHandler {
         )
     }
 
+    pub fn apply_state_diff(
+        &self,
+        base_version: Version,
+        version: Version,
+        updates: Vec<(StateKey, Option<StateValue>)>,
+        expected_root_hash: HashValue,
+    ) -> Result<()> {
+        self.state_store
+            .apply_state_diff(base_version, version, updates, expected_root_hash)
+    }
+
     pub fn reset_state_store(&self) {
         self.state_store.reset();
     }
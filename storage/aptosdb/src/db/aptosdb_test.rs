@@ -6,7 +6,7 @@ use crate::{
         get_first_seq_num_and_limit, test_helper,
         test_helper::{
             arb_blocks_to_commit, put_as_state_root, put_transaction_auxiliary_data,
-            put_transaction_infos,
+            put_transaction_infos, update_store,
         },
         AptosDB,
     },
@@ -14,12 +14,12 @@ use crate::{
     schema::stale_node_index::StaleNodeIndexSchema,
 };
 use aptos_config::config::{
-    EpochSnapshotPrunerConfig, LedgerPrunerConfig, PrunerConfig, RocksdbConfigs,
-    StateMerklePrunerConfig, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    EpochSnapshotPrunerConfig, LedgerHistoryRetentionConfig, LedgerPrunerConfig, PrunerConfig,
+    RocksdbConfigs, StateMerklePrunerConfig, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
 };
 use aptos_crypto::{hash::CryptoHash, HashValue};
-use aptos_storage_interface::{DbReader, ExecutedTrees, Order};
+use aptos_storage_interface::{DbReader, ExecutedTrees, Order, StateValueProof};
 use aptos_temppath::TempPath;
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
@@ -103,19 +103,28 @@ fn test_pruner_config() {
             StateMerklePrunerConfig {
                 enable,
                 prune_window: 20,
+                prune_window_secs: None,
+                max_db_size_bytes: None,
                 batch_size: 1,
             },
+            None, /* prune_window_source */
         );
         assert_eq!(state_merkle_pruner.is_pruner_enabled(), enable);
         assert_eq!(state_merkle_pruner.get_prune_window(), 20);
 
-        let ledger_pruner =
-            LedgerPrunerManager::new(Arc::clone(&aptos_db.ledger_db), LedgerPrunerConfig {
+        let ledger_pruner = LedgerPrunerManager::new(
+            Arc::clone(&aptos_db.ledger_db),
+            LedgerPrunerConfig {
                 enable,
                 prune_window: 100,
+                prune_window_secs: None,
+                max_db_size_bytes: None,
                 batch_size: 1,
                 user_pruning_window_offset: 0,
-            });
+                history_retention: LedgerHistoryRetentionConfig::default(),
+            },
+            None,
+        );
         assert_eq!(ledger_pruner.is_pruner_enabled(), enable);
         assert_eq!(ledger_pruner.get_prune_window(), 100);
     }
@@ -147,6 +156,55 @@ fn test_error_if_version_pruned() {
     assert!(db.error_if_ledger_pruned("Transaction", 10).is_ok());
 }
 
+#[test]
+fn test_get_state_value_with_proof_if_available() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let key = StateKey::raw(b"test_key");
+    let values: Vec<_> = (0..4u8).map(|i| StateValue::from(vec![i])).collect();
+    update_store(
+        &db.state_store,
+        values
+            .iter()
+            .map(|value| (key.clone(), Some(value.clone()))),
+        0, /* first_version */
+    );
+    db.state_store
+        .state_db
+        .state_merkle_pruner
+        .save_min_readable_version(2)
+        .unwrap();
+    db.state_store
+        .state_kv_pruner
+        .save_min_readable_version(1)
+        .unwrap();
+
+    // Within the state merkle prune window, the value comes with a proof.
+    let (value, proof) = db
+        .get_state_value_with_proof_if_available_by_version_ext(&key, 2)
+        .unwrap();
+    assert_eq!(value, Some(values[2].clone()));
+    assert!(proof.is_available());
+
+    // The tree at version 1 is pruned, the value is still read from the state KV DB.
+    let (value, proof) = db
+        .get_state_value_with_proof_if_available_by_version_ext(&key, 1)
+        .unwrap();
+    assert_eq!(value, Some(values[1].clone()));
+    assert_eq!(proof, StateValueProof::Unavailable);
+    assert!(db.get_state_value_with_proof_by_version(&key, 1).is_err());
+    assert_eq!(
+        db.get_state_value_by_version(&key, 1).unwrap(),
+        Some(values[1].clone())
+    );
+
+    // Once the state KV DB is pruned too, the read fails.
+    assert!(db
+        .get_state_value_with_proof_if_available_by_version_ext(&key, 0)
+        .is_err());
+    assert!(db.get_state_value_by_version(&key, 0).is_err());
+}
+
 #[test]
 fn test_get_transaction_auxiliary_data() {
     let tmp_dir = TempPath::new();
@@ -219,12 +277,17 @@ pub fn test_state_merkle_pruning_impl(
             ledger_pruner_config: LedgerPrunerConfig {
                 enable: true,
                 prune_window: 10,
+                prune_window_secs: None,
+                max_db_size_bytes: None,
                 batch_size: 1,
                 user_pruning_window_offset: 0,
+                history_retention: LedgerHistoryRetentionConfig::default(),
             },
             state_merkle_pruner_config: StateMerklePrunerConfig {
                 enable: true,
                 prune_window: 5,
+                prune_window_secs: None,
+                max_db_size_bytes: None,
                 batch_size: 1,
             },
             epoch_snapshot_pruner_config: EpochSnapshotPrunerConfig {
@@ -314,6 +377,120 @@ pub fn test_state_merkle_pruning_impl(
     }
 }
 
+pub fn test_history_retention_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let txns_to_commit: Vec<_> = input.iter().flat_map(|(txns, _)| txns.iter()).collect();
+    let sender = match txns_to_commit
+        .iter()
+        .find_map(|txn| txn.transaction().try_as_signed_user_txn())
+    {
+        Some(txn) => txn.sender(),
+        None => return,
+    };
+
+    // The window is too large for the pruner to be triggered by commits, pruning is triggered
+    // below instead.
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::open(
+        StorageDirPaths::from_path(tmp_dir),
+        /*readonly=*/ false,
+        PrunerConfig {
+            ledger_pruner_config: LedgerPrunerConfig {
+                enable: true,
+                prune_window: 1_000_000,
+                prune_window_secs: None,
+                max_db_size_bytes: None,
+                batch_size: 1,
+                user_pruning_window_offset: 0,
+                history_retention: LedgerHistoryRetentionConfig {
+                    accounts: vec![sender],
+                    modules: vec![],
+                },
+            },
+            ..NO_OP_STORAGE_PRUNER_CONFIG
+        },
+        RocksdbConfigs::default(),
+        false, /* enable_indexer */
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+    .unwrap();
+
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut next_ver: Version = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        next_ver += txns_to_commit.len() as u64;
+    }
+    let ledger_version = next_ver - 1;
+    let root_hashes: Vec<_> = (0..=ledger_version)
+        .map(|version| db.get_accumulator_root_hash(version).unwrap())
+        .collect();
+
+    // Prune all but the latest version.
+    db.ledger_pruner.set_worker_target_version(ledger_version);
+    db.ledger_pruner.wait_for_pruner().unwrap();
+    db.ledger_pruner
+        .save_min_readable_version(ledger_version)
+        .unwrap();
+
+    for (version, txn_to_commit) in txns_to_commit
+        .iter()
+        .enumerate()
+        .take(ledger_version as usize)
+    {
+        let version = version as Version;
+        let retained = db
+            .get_retained_transaction_by_version(version, ledger_version)
+            .unwrap();
+        let is_sent_by_sender = txn_to_commit
+            .transaction()
+            .try_as_signed_user_txn()
+            .map_or(false, |txn| txn.sender() == sender);
+        match retained {
+            Some(retained) => {
+                assert!(is_sent_by_sender);
+                assert_eq!(&retained.transaction, txn_to_commit.transaction());
+                assert_eq!(retained.events.as_slice(), txn_to_commit.events());
+                assert_eq!(&retained.write_set, txn_to_commit.write_set());
+                assert_eq!(
+                    retained.accumulator_root_hash,
+                    root_hashes[version as usize]
+                );
+                assert_eq!(
+                    db.get_transaction_version_by_hash(
+                        txn_to_commit.transaction().hash(),
+                        ledger_version
+                    )
+                    .unwrap(),
+                    Some(version)
+                );
+            },
+            None => {
+                assert!(!is_sent_by_sender);
+                assert!(db
+                    .get_transaction_by_version(version, ledger_version, true)
+                    .is_err());
+            },
+        }
+    }
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
 
@@ -322,4 +499,9 @@ proptest! {
         aptos_logger::Logger::new().init();
         test_state_merkle_pruning_impl(input);
     }
+
+    #[test]
+    fn test_history_retention(input in arb_blocks_to_commit()) {
+        test_history_retention_impl(input);
+    }
 }
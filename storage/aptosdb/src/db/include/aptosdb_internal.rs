@@ -15,16 +15,49 @@ impl AptosDB {
         let ledger_db = Arc::new(ledger_db);
         let state_merkle_db = Arc::new(state_merkle_db);
         let state_kv_db = Arc::new(state_kv_db);
+        // Time and size based prune windows are resolved from block timestamps and DB sizes. The
+        // ledger and state kv pruners share a window, as they are driven by the same config and
+        // their size limit covers both DBs.
+        let ledger_prune_window = {
+            let ledger_db = Arc::clone(&ledger_db);
+            let state_kv_db = Arc::clone(&state_kv_db);
+            Arc::new(PruneWindow::new(
+                "ledger_pruner",
+                pruner_config.ledger_pruner_config.prune_window,
+                pruner_config.ledger_pruner_config.prune_window_secs,
+                pruner_config.ledger_pruner_config.max_db_size_bytes,
+                Some(PruneWindowSource::new(
+                    Arc::clone(&ledger_db),
+                    skip_index_and_usage,
+                    Arc::new(move || {
+                        Ok(get_ledger_and_state_kv_db_size(&ledger_db, &state_kv_db)?)
+                    }),
+                )),
+            ))
+        };
+        let state_merkle_prune_window_source = {
+            let state_merkle_db = Arc::clone(&state_merkle_db);
+            PruneWindowSource::new(
+                Arc::clone(&ledger_db),
+                skip_index_and_usage,
+                Arc::new(move || Ok(get_state_merkle_db_size(&state_merkle_db)?)),
+            )
+        };
         let state_merkle_pruner = StateMerklePrunerManager::new(
             Arc::clone(&state_merkle_db),
             pruner_config.state_merkle_pruner_config,
+            Some(state_merkle_prune_window_source),
         );
         let epoch_snapshot_pruner = StateMerklePrunerManager::new(
             Arc::clone(&state_merkle_db),
             pruner_config.epoch_snapshot_pruner_config.into(),
+            None, /* prune_window_source */
+        );
+        let state_kv_pruner = StateKvPrunerManager::new_with_prune_window(
+            Arc::clone(&state_kv_db),
+            pruner_config.ledger_pruner_config.clone(),
+            Arc::clone(&ledger_prune_window),
         );
-        let state_kv_pruner =
-            StateKvPrunerManager::new(Arc::clone(&state_kv_db), pruner_config.ledger_pruner_config);
         let state_store = Arc::new(StateStore::new(
             Arc::clone(&ledger_db),
             Arc::clone(&state_merkle_db),
@@ -38,8 +71,11 @@ impl AptosDB {
             skip_index_and_usage,
         ));
 
-        let ledger_pruner =
-            LedgerPrunerManager::new(Arc::clone(&ledger_db), pruner_config.ledger_pruner_config);
+        let ledger_pruner = LedgerPrunerManager::new_with_prune_window(
+            Arc::clone(&ledger_db),
+            pruner_config.ledger_pruner_config,
+            ledger_prune_window,
+        );
 
         AptosDB {
             ledger_db: Arc::clone(&ledger_db),
@@ -176,6 +212,25 @@ impl AptosDB {
         Ok(())
     }
 
+    /// Like `error_if_ledger_pruned`, but lets through the transactions kept by the ledger pruner
+    /// beyond the prune window.
+    fn error_if_ledger_pruned_and_not_retained(
+        &self,
+        data_type: &str,
+        version: Version,
+    ) -> Result<()> {
+        if version < self.ledger_pruner.get_min_readable_version()
+            && self
+                .ledger_db
+                .metadata_db()
+                .get_retained_transaction_root_hash(version)?
+                .is_some()
+        {
+            return Ok(());
+        }
+        self.error_if_ledger_pruned(data_type, version)
+    }
+
     fn error_if_state_merkle_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self
             .state_store
@@ -204,6 +259,28 @@ impl AptosDB {
         }
     }
 
+    /// Returns whether the state Merkle tree at `version` is still there, i.e. whether
+    /// `error_if_state_merkle_pruned` lets the version through.
+    fn is_state_merkle_readable(&self, version: Version) -> Result<bool> {
+        if version
+            >= self
+                .state_store
+                .state_db
+                .state_merkle_pruner
+                .get_min_readable_version()
+        {
+            return Ok(true);
+        }
+
+        Ok(version
+            >= self
+                .state_store
+                .state_db
+                .epoch_snapshot_pruner
+                .get_min_readable_version()
+            && self.ledger_db.metadata_db().is_epoch_ending(version)?)
+    }
+
     fn error_if_state_kv_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self.state_store.state_kv_pruner.get_min_readable_version();
         ensure!(
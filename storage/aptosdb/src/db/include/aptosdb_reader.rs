@@ -131,6 +131,60 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_retained_transaction_by_version(
+        &self,
+        version: Version,
+        ledger_version: Version,
+    ) -> Result<Option<RetainedTransaction>> {
+        gauged_api("get_retained_transaction_by_version", || {
+            if version > ledger_version || version >= self.ledger_pruner.get_min_readable_version()
+            {
+                return Ok(None);
+            }
+            self.get_retained_transaction(version)
+        })
+    }
+
+    fn get_transaction_version_by_hash(
+        &self,
+        hash: HashValue,
+        ledger_version: Version,
+    ) -> Result<Option<Version>> {
+        gauged_api("get_transaction_version_by_hash", || {
+            self.ledger_db
+                .transaction_db()
+                .get_transaction_version_by_hash(&hash, ledger_version)
+        })
+    }
+
+    fn get_retained_account_transactions(
+        &self,
+        address: AccountAddress,
+        start_seq_num: u64,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<RetainedTransaction>> {
+        gauged_api("get_retained_account_transactions", || {
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+
+            let end_version = std::cmp::min(
+                self.ledger_pruner.get_min_readable_version(),
+                ledger_version.saturating_add(1),
+            );
+            // Transactions that are not kept are being pruned right now, skip them.
+            self.transaction_store
+                .get_account_transaction_versions_before(
+                    address,
+                    start_seq_num,
+                    limit,
+                    end_version,
+                )?
+                .into_iter()
+                .filter_map(|version| self.get_retained_transaction(version).transpose())
+                .collect()
+        })
+    }
+
     // ======================= State Synchronizer Internal APIs ===================================
     /// Returns batch of transactions for the purpose of synchronizing state to another node.
     ///
@@ -479,6 +533,28 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_value_with_proof_if_available_by_version_ext(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<(Option<StateValue>, StateValueProof)> {
+        gauged_api("get_state_value_with_proof_if_available_by_version_ext", || {
+            if self.is_state_merkle_readable(version)? {
+                let (value, proof) = self
+                    .state_store
+                    .get_state_value_with_proof_by_version_ext(state_key, version)?;
+                return Ok((value, StateValueProof::Available(proof)));
+            }
+
+            self.error_if_state_kv_pruned("StateValue", version)?;
+            Ok((
+                self.state_store
+                    .get_state_value_by_version(state_key, version)?,
+                StateValueProof::Unavailable,
+            ))
+        })
+    }
+
     fn get_latest_epoch_state(&self) -> Result<EpochState> {
         gauged_api("get_latest_epoch_state", || {
             let latest_ledger_info = self.ledger_db.metadata_db().get_latest_ledger_info()?;
@@ -522,7 +598,7 @@ impl DbReader for AptosDB {
 
     fn get_block_timestamp(&self, version: u64) -> Result<u64> {
         gauged_api("get_block_timestamp", || {
-            self.error_if_ledger_pruned("NewBlockEvent", version)?;
+            self.error_if_ledger_pruned_and_not_retained("NewBlockEvent", version)?;
             ensure!(
                 version <= self.get_latest_version()?,
                 "version older than latest version"
@@ -568,7 +644,7 @@ impl DbReader for AptosDB {
         version: Version,
     ) -> Result<(Version, Version, NewBlockEvent)> {
         gauged_api("get_block_info", || {
-            self.error_if_ledger_pruned("NewBlockEvent", version)?;
+            self.error_if_ledger_pruned_and_not_retained("NewBlockEvent", version)?;
 
             let latest_li = self.get_latest_ledger_info()?;
             let committed_version = latest_li.ledger_info().version();
@@ -876,6 +952,31 @@ impl AptosDB {
         Ok((lis, more))
     }
 
+    /// Returns the transaction at `version` if it's kept by the ledger pruner beyond the prune
+    /// window, with the transaction accumulator root hash recorded when the version was pruned.
+    fn get_retained_transaction(&self, version: Version) -> Result<Option<RetainedTransaction>> {
+        let accumulator_root_hash = match self
+            .ledger_db
+            .metadata_db()
+            .get_retained_transaction_root_hash(version)?
+        {
+            Some(root_hash) => root_hash,
+            None => return Ok(None),
+        };
+
+        Ok(Some(RetainedTransaction {
+            version,
+            transaction: self.ledger_db.transaction_db().get_transaction(version)?,
+            info: self
+                .ledger_db
+                .transaction_info_db()
+                .get_transaction_info(version)?,
+            events: self.ledger_db.event_db().get_events_by_version(version)?,
+            write_set: self.ledger_db.write_set_db().get_write_set(version)?,
+            accumulator_root_hash,
+        }))
+    }
+
     /// Returns the transaction with proof for a given version, or error if the transaction is not
     /// found.
     fn get_transaction_with_proof(
@@ -91,6 +91,21 @@ impl AptosDB {
     pub(crate) fn state_merkle_db(&self) -> Arc<StateMerkleDb> {
         self.state_store.state_db.state_merkle_db.clone()
     }
+
+    /// Waits for the ledger pruner to catch up with the latest committed version.
+    pub fn wait_for_ledger_pruner(&self) -> Result<()> {
+        self.ledger_pruner.wait_for_pruner()
+    }
+
+    /// Commits the buffered state and waits for the state merkle pruner to catch up with the
+    /// resulting snapshot.
+    pub fn commit_state_and_wait_for_state_merkle_pruner(&self) -> Result<()> {
+        self.buffered_state().lock().sync_commit();
+        self.state_store
+            .state_db
+            .state_merkle_pruner
+            .wait_for_pruner()
+    }
 }
 
 pub fn gather_state_updates_until_last_checkpoint(
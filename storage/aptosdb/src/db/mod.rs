@@ -15,7 +15,11 @@ use crate::{
         API_LATENCY_SECONDS, COMMITTED_TXNS, LATEST_TXN_VERSION, LEDGER_VERSION, NEXT_BLOCK_EPOCH,
         OTHER_TIMERS_SECONDS,
     },
-    pruner::{LedgerPrunerManager, PrunerManager, StateKvPrunerManager, StateMerklePrunerManager},
+    pruner::{
+        get_ledger_and_state_kv_db_size, get_state_merkle_db_size, LedgerPrunerManager,
+        PruneWindow, PruneWindowSource, PrunerManager, StateKvPrunerManager,
+        StateMerklePrunerManager,
+    },
     rocksdb_property_reporter::RocksdbPropertyReporter,
     schema::{
         block_info::BlockInfoSchema,
@@ -39,8 +43,9 @@ use aptos_schemadb::{ReadOptions, SchemaBatch};
 use aptos_scratchpad::SparseMerkleTree;
 use aptos_storage_interface::{
     cached_state_view::ShardedStateCache, db_anyhow as anyhow, db_ensure as ensure,
-    db_other_bail as bail, state_delta::StateDelta, AptosDbError, DbReader, DbWriter,
-    ExecutedTrees, Order, Result, StateSnapshotReceiver, MAX_REQUEST_LIMIT,
+    db_other_bail as bail, retained_transaction::RetainedTransaction, state_delta::StateDelta,
+    AptosDbError, DbReader, DbWriter, ExecutedTrees, Order, Result, StateSnapshotReceiver,
+    StateValueProof, MAX_REQUEST_LIMIT,
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{db_debugger::ShardingConfig, ledger_db::LedgerDb, state_merkle_db::StateMerkleDb};
+use crate::{
+    db_debugger::ShardingConfig, ledger_db::LedgerDb, state_kv_db::StateKvDb,
+    state_merkle_db::StateMerkleDb, AptosDB,
+};
 use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
 use aptos_storage_interface::Result;
 use aptos_types::nibble::{nibble_path::NibblePath, Nibble};
@@ -44,6 +47,18 @@ impl DbDir {
             true,
         )
     }
+
+    pub fn open_dbs(&self) -> Result<(LedgerDb, StateMerkleDb, StateKvDb)> {
+        AptosDB::open_dbs(
+            &StorageDirPaths::from_path(&self.db_dir),
+            RocksdbConfigs {
+                enable_storage_sharding: self.sharding_config.enable_storage_sharding,
+                ..Default::default()
+            },
+            /*readonly=*/ true,
+            /*max_num_nodes_per_lru_cache_shard=*/ 0,
+        )
+    }
 }
 
 impl AsRef<Path> for DbDir {
@@ -7,6 +7,7 @@ mod examine;
 pub mod ledger;
 pub mod state_tree;
 pub mod truncate;
+pub mod verify;
 
 use aptos_storage_interface::Result;
 use clap::Parser;
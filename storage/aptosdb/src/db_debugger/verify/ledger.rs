@@ -0,0 +1,266 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{check_read, Corruption};
+use crate::{
+    ledger_db::LedgerDb,
+    schema::{
+        event_by_key::EventByKeySchema, transaction_accumulator::TransactionAccumulatorSchema,
+    },
+};
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::{
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfo,
+    proof::{accumulator::InMemoryEventAccumulator, position::Position},
+    transaction::Version,
+};
+
+/// Verifies the transactions in `[first_version, first_version + num_versions)`: that each
+/// transaction info matches the transaction, write set and events, that it is the leaf of the
+/// transaction accumulator, and that the range is proven by the accumulator root in
+/// `ledger_info`. Optionally verifies the `transaction_by_hash` and `event_by_key` indices.
+pub(super) fn verify_transactions(
+    ledger_db: &LedgerDb,
+    ledger_info: &LedgerInfo,
+    first_version: Version,
+    num_versions: usize,
+    check_indices: bool,
+) -> Vec<Corruption> {
+    let mut corruptions = vec![];
+    let mut txn_info_hashes = Vec::with_capacity(num_versions);
+
+    for version in first_version..first_version + num_versions as u64 {
+        let txn_info = check_read(
+            &mut corruptions,
+            Some(version),
+            "transaction_info",
+            ledger_db
+                .transaction_info_db()
+                .get_transaction_info(version),
+        );
+        let txn = check_read(
+            &mut corruptions,
+            Some(version),
+            "transaction",
+            ledger_db.transaction_db().get_transaction(version),
+        );
+        let write_set = check_read(
+            &mut corruptions,
+            Some(version),
+            "write_set",
+            ledger_db.write_set_db().get_write_set(version),
+        );
+        let events = check_read(
+            &mut corruptions,
+            Some(version),
+            "events",
+            ledger_db.event_db().get_events_by_version(version),
+        );
+        let leaf = check_read(
+            &mut corruptions,
+            Some(version),
+            "transaction_accumulator",
+            ledger_db
+                .transaction_accumulator_db_raw()
+                .get::<TransactionAccumulatorSchema>(&Position::from_leaf_index(version)),
+        );
+
+        if let Some(txn_info) = &txn_info {
+            let txn_info_hash = txn_info.hash();
+            txn_info_hashes.push(txn_info_hash);
+            if let Some(leaf) = leaf {
+                if leaf != Some(txn_info_hash) {
+                    corruptions.push(Corruption::AccumulatorLeafMismatch {
+                        version,
+                        leaf,
+                        transaction_info: txn_info_hash,
+                    });
+                }
+            }
+            if let Some(txn) = &txn {
+                let computed = txn.hash();
+                if computed != txn_info.transaction_hash() {
+                    corruptions.push(Corruption::TransactionHashMismatch {
+                        version,
+                        transaction_info: txn_info.transaction_hash(),
+                        computed,
+                    });
+                }
+            }
+            if let Some(write_set) = &write_set {
+                let computed = CryptoHash::hash(write_set);
+                if computed != txn_info.state_change_hash() {
+                    corruptions.push(Corruption::WriteSetHashMismatch {
+                        version,
+                        transaction_info: txn_info.state_change_hash(),
+                        computed,
+                    });
+                }
+            }
+            if let Some(events) = &events {
+                let event_hashes: Vec<_> = events.iter().map(CryptoHash::hash).collect();
+                let computed = InMemoryEventAccumulator::from_leaves(&event_hashes).root_hash();
+                if computed != txn_info.event_root_hash() {
+                    corruptions.push(Corruption::EventRootHashMismatch {
+                        version,
+                        transaction_info: txn_info.event_root_hash(),
+                        computed,
+                    });
+                }
+            }
+        }
+
+        if check_indices {
+            if let Some(txn) = &txn {
+                verify_transaction_by_hash(ledger_db, version, txn.hash(), &mut corruptions);
+            }
+            if let Some(events) = &events {
+                verify_event_by_key(ledger_db, version, events, &mut corruptions);
+            }
+        }
+    }
+
+    // The range proof can only be checked if all transaction infos are available.
+    if txn_info_hashes.len() == num_versions {
+        let result = ledger_db
+            .transaction_accumulator_db()
+            .get_transaction_range_proof(
+                Some(first_version),
+                num_versions as u64,
+                ledger_info.version(),
+            )
+            .and_then(|proof| {
+                Ok(proof.verify(
+                    ledger_info.transaction_accumulator_hash(),
+                    Some(first_version),
+                    &txn_info_hashes,
+                )?)
+            });
+        if let Err(error) = result {
+            corruptions.push(Corruption::AccumulatorRangeProofFailure {
+                first_version,
+                num_versions,
+                error: error.to_string(),
+            });
+        }
+    }
+
+    corruptions
+}
+
+fn verify_transaction_by_hash(
+    ledger_db: &LedgerDb,
+    version: Version,
+    hash: aptos_crypto::HashValue,
+    corruptions: &mut Vec<Corruption>,
+) {
+    if let Some(indexed_version) = check_read(
+        corruptions,
+        Some(version),
+        "transaction_by_hash",
+        ledger_db
+            .transaction_db()
+            .get_transaction_version_by_hash(&hash, Version::MAX),
+    ) {
+        if indexed_version != Some(version) {
+            corruptions.push(Corruption::TransactionByHashMismatch {
+                version,
+                hash,
+                indexed_version,
+            });
+        }
+    }
+}
+
+fn verify_event_by_key(
+    ledger_db: &LedgerDb,
+    version: Version,
+    events: &[ContractEvent],
+    corruptions: &mut Vec<Corruption>,
+) {
+    for (idx, event) in events.iter().enumerate() {
+        // Only v1 events are indexed by key.
+        let ContractEvent::V1(event) = event else {
+            continue;
+        };
+        let key = (*event.key(), event.sequence_number());
+        if let Some(indexed) = check_read(
+            corruptions,
+            Some(version),
+            "event_by_key",
+            ledger_db.event_db_raw().get::<EventByKeySchema>(&key),
+        ) {
+            if indexed != Some((version, idx as u64)) {
+                corruptions.push(Corruption::EventByKeyMismatch {
+                    version,
+                    event_index: idx as u64,
+                    event_key: event.key().to_string(),
+                    sequence_number: event.sequence_number(),
+                    indexed,
+                });
+            }
+        }
+    }
+}
+
+/// Verifies that the transaction accumulator root at the version of each ledger info in
+/// `[start_version, end_version)` matches the root hash the ledger info commits to.
+pub(super) fn verify_ledger_infos(
+    ledger_db: &LedgerDb,
+    start_version: Version,
+    end_version: Version,
+) -> Vec<Corruption> {
+    let mut corruptions = vec![];
+    let ledger_metadata_db = ledger_db.metadata_db();
+    let Some(latest_ledger_info) = check_read(
+        &mut corruptions,
+        None,
+        "ledger_info",
+        ledger_metadata_db.get_latest_ledger_info(),
+    ) else {
+        return corruptions;
+    };
+    let Some(first_epoch) = check_read(
+        &mut corruptions,
+        Some(start_version),
+        "epoch",
+        ledger_metadata_db.get_epoch(start_version),
+    ) else {
+        return corruptions;
+    };
+
+    for epoch in first_epoch..=latest_ledger_info.ledger_info().epoch() {
+        let Some(ledger_info) = check_read(
+            &mut corruptions,
+            None,
+            "ledger_info",
+            ledger_metadata_db.get_latest_ledger_info_in_epoch(epoch),
+        ) else {
+            continue;
+        };
+        let ledger_info = ledger_info.ledger_info();
+        let version = ledger_info.version();
+        if version < start_version || version >= end_version {
+            continue;
+        }
+        if let Some(computed) = check_read(
+            &mut corruptions,
+            Some(version),
+            "transaction_accumulator",
+            ledger_db
+                .transaction_accumulator_db()
+                .get_root_hash(version),
+        ) {
+            if computed != ledger_info.transaction_accumulator_hash() {
+                corruptions.push(Corruption::AccumulatorRootMismatch {
+                    version,
+                    ledger_info: ledger_info.transaction_accumulator_hash(),
+                    computed,
+                });
+            }
+        }
+    }
+
+    corruptions
+}
@@ -0,0 +1,413 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verifies the consistency of a whole DB: the ledger (transactions, write sets, events, the
+//! transaction accumulator and indices) in a range of versions, and the state at a snapshot.
+
+mod ledger;
+mod state;
+
+use crate::db_debugger::common::DbDir;
+use aptos_crypto::HashValue;
+use aptos_storage_interface::{AptosDbError, Result};
+use aptos_types::transaction::Version;
+use clap::Parser;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::{fs::File, io::Write, path::PathBuf};
+
+#[derive(Parser)]
+#[clap(
+    about = "Verify the consistency of the ledger in a range of versions and of the state at a snapshot, printing a JSON report of any corruption found."
+)]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// First version to verify, defaults to the first version not pruned.
+    #[clap(long)]
+    start_version: Option<Version>,
+
+    /// Number of versions to verify, defaults to all versions up to the latest one.
+    #[clap(long)]
+    num_versions: Option<usize>,
+
+    /// Version of the state snapshot to verify, defaults to the latest snapshot in the range.
+    #[clap(long)]
+    state_snapshot_version: Option<Version>,
+
+    /// Skip verifying the state snapshot.
+    #[clap(long)]
+    skip_state: bool,
+
+    /// Skip verifying the `transaction_by_hash` and `event_by_key` indices, e.g. if the node writes
+    /// them to the internal indexer DB instead.
+    #[clap(long)]
+    skip_indices: bool,
+
+    /// Number of versions verified by a single task.
+    #[clap(long, default_value_t = 10_000)]
+    chunk_size: usize,
+
+    /// Number of threads, defaults to the number of CPUs.
+    #[clap(long)]
+    concurrency: Option<usize>,
+
+    /// Write the report to this file instead of stdout.
+    #[clap(long, value_parser)]
+    output: Option<PathBuf>,
+}
+
+/// A single inconsistency found in the DB.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Corruption {
+    /// Data could not be read, e.g. because it is missing or can't be deserialized.
+    ReadError {
+        version: Option<Version>,
+        data: &'static str,
+        error: String,
+    },
+    TransactionHashMismatch {
+        version: Version,
+        transaction_info: HashValue,
+        computed: HashValue,
+    },
+    WriteSetHashMismatch {
+        version: Version,
+        transaction_info: HashValue,
+        computed: HashValue,
+    },
+    EventRootHashMismatch {
+        version: Version,
+        transaction_info: HashValue,
+        computed: HashValue,
+    },
+    AccumulatorLeafMismatch {
+        version: Version,
+        leaf: Option<HashValue>,
+        transaction_info: HashValue,
+    },
+    AccumulatorRootMismatch {
+        version: Version,
+        ledger_info: HashValue,
+        computed: HashValue,
+    },
+    AccumulatorRangeProofFailure {
+        first_version: Version,
+        num_versions: usize,
+        error: String,
+    },
+    TransactionByHashMismatch {
+        version: Version,
+        hash: HashValue,
+        indexed_version: Option<Version>,
+    },
+    EventByKeyMismatch {
+        version: Version,
+        event_index: u64,
+        event_key: String,
+        sequence_number: u64,
+        indexed: Option<(Version, u64)>,
+    },
+    StateRootMismatch {
+        version: Version,
+        transaction_info: Option<HashValue>,
+        computed: HashValue,
+    },
+    MissingStateNode {
+        version: Version,
+        node_version: Version,
+        nibble_path: String,
+    },
+    StateNodeHashMismatch {
+        version: Version,
+        node_version: Version,
+        nibble_path: String,
+        expected: HashValue,
+        computed: HashValue,
+    },
+    StateKeyHashMismatch {
+        version: Version,
+        state_key: String,
+        leaf: HashValue,
+        computed: HashValue,
+    },
+    MissingStateValue {
+        version: Version,
+        state_key: String,
+        value_version: Version,
+    },
+    StateValueHashMismatch {
+        version: Version,
+        state_key: String,
+        value_version: Version,
+        leaf: HashValue,
+        computed: HashValue,
+    },
+}
+
+impl Corruption {
+    fn read_error(version: Option<Version>, data: &'static str, error: AptosDbError) -> Self {
+        Self::ReadError {
+            version,
+            data,
+            error: error.to_string(),
+        }
+    }
+}
+
+/// Records a read failure as a corruption, returning the value if the read succeeded.
+fn check_read<T>(
+    corruptions: &mut Vec<Corruption>,
+    version: Option<Version>,
+    data: &'static str,
+    result: Result<T>,
+) -> Option<T> {
+    result
+        .map_err(|error| corruptions.push(Corruption::read_error(version, data, error)))
+        .ok()
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub start_version: Version,
+    pub num_versions: usize,
+    pub state_snapshot_version: Option<Version>,
+    pub num_state_leaves: usize,
+    pub corruptions: Vec<Corruption>,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let (ledger_db, state_merkle_db, state_kv_db) = self.db_dir.open_dbs()?;
+        let ledger_metadata_db = ledger_db.metadata_db();
+        let ledger_info = ledger_metadata_db.get_latest_ledger_info()?;
+        let latest_version = ledger_info.ledger_info().version();
+
+        let start_version = match self.start_version {
+            Some(version) => version,
+            None => ledger_metadata_db.get_pruner_progress().unwrap_or(0),
+        };
+        let num_versions = match self.num_versions {
+            Some(num_versions) => num_versions,
+            None => (latest_version + 1).saturating_sub(start_version) as usize,
+        };
+        let end_version = start_version + num_versions as u64;
+        if end_version > latest_version + 1 {
+            return Err(AptosDbError::Other(format!(
+                "Range ends at {} but the latest version is {}",
+                end_version, latest_version
+            )));
+        }
+
+        let mut builder = rayon::ThreadPoolBuilder::new().thread_name(|i| format!("verify-{}", i));
+        if let Some(concurrency) = self.concurrency {
+            builder = builder.num_threads(concurrency);
+        }
+        let pool = builder
+            .build()
+            .map_err(|e| AptosDbError::Other(e.to_string()))?;
+
+        eprintln!(
+            "Verifying versions [{}, {}) against the LedgerInfo at version {}.",
+            start_version, end_version, latest_version
+        );
+        let chunk_size = self.chunk_size.max(1) as u64;
+        let mut corruptions: Vec<Corruption> = pool.install(|| {
+            (start_version..end_version)
+                .step_by(chunk_size as usize)
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .flat_map_iter(|first_version| {
+                    let num_versions = chunk_size.min(end_version - first_version) as usize;
+                    let corruptions = ledger::verify_transactions(
+                        &ledger_db,
+                        ledger_info.ledger_info(),
+                        first_version,
+                        num_versions,
+                        !self.skip_indices,
+                    );
+                    eprintln!(
+                        "Verified versions [{}, {}).",
+                        first_version,
+                        first_version + num_versions as u64
+                    );
+                    corruptions
+                })
+                .collect()
+        });
+        corruptions.extend(ledger::verify_ledger_infos(
+            &ledger_db,
+            start_version,
+            end_version,
+        ));
+
+        let mut state_snapshot_version = None;
+        let mut num_state_leaves = 0;
+        if !self.skip_state {
+            state_snapshot_version = match self.state_snapshot_version {
+                Some(version) => Some(version),
+                None => state_merkle_db.get_state_snapshot_version_before(end_version)?,
+            };
+            if let Some(version) = state_snapshot_version {
+                eprintln!("Verifying state snapshot at version {}.", version);
+                let (num_leaves, state_corruptions) = pool.install(|| {
+                    state::verify_state_snapshot(
+                        &ledger_db,
+                        &state_merkle_db,
+                        &state_kv_db,
+                        version,
+                    )
+                });
+                num_state_leaves = num_leaves;
+                corruptions.extend(state_corruptions);
+            } else {
+                eprintln!("No state snapshot found, skipping state verification.");
+            }
+        }
+
+        let report = Report {
+            start_version,
+            num_versions,
+            state_snapshot_version,
+            num_state_leaves,
+            corruptions,
+        };
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| AptosDbError::Other(e.to_string()))?;
+        match &self.output {
+            Some(path) => File::create(path)?.write_all(json.as_bytes())?,
+            None => println!("{}", json),
+        }
+
+        if report.corruptions.is_empty() {
+            eprintln!("Done, no corruption found.");
+            Ok(())
+        } else {
+            Err(AptosDbError::Other(format!(
+                "Found {} corruptions.",
+                report.corruptions.len()
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        db::{
+            test_helper::{arb_blocks_to_commit, update_in_memory_state},
+            AptosDB,
+        },
+        schema::{state_value::StateValueSchema, transaction::TransactionSchema},
+    };
+    use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
+    use aptos_jellyfish_merkle::{
+        node_type::{Node, NodeKey},
+        TreeReader,
+    };
+    use aptos_schemadb::SchemaBatch;
+    use aptos_temppath::TempPath;
+    use aptos_types::state_store::state_value::StateValue;
+    use proptest::prelude::*;
+
+    fn run_verify(db_dir: &TempPath) -> (Result<()>, serde_json::Value) {
+        let output = TempPath::new();
+        let result = Cmd::parse_from([
+            "verify",
+            "--db-dir",
+            db_dir.path().to_str().unwrap(),
+            "--output",
+            output.path().to_str().unwrap(),
+        ])
+        .run();
+        let report = serde_json::from_slice(&std::fs::read(output.path()).unwrap()).unwrap();
+        (result, report)
+    }
+
+    fn has_corruption(report: &serde_json::Value, kind: &str) -> bool {
+        report["corruptions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|corruption| corruption["kind"] == kind)
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(1))]
+
+        #[test]
+        fn test_verify(input in arb_blocks_to_commit()) {
+            let tmp_dir = TempPath::new();
+            let db = AptosDB::new_for_test(&tmp_dir);
+            let mut in_memory_state = db.state_store.buffered_state().lock().current_state().clone();
+            let mut version = 0;
+            for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+                update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+                db.save_transactions_for_test(
+                    txns_to_commit,
+                    version,
+                    version.checked_sub(1),
+                    Some(ledger_info_with_sigs),
+                    true, /* sync_commit */
+                    in_memory_state.clone(),
+                )
+                .unwrap();
+                version += txns_to_commit.len() as u64;
+            }
+            drop(db);
+
+            // A consistent DB passes.
+            let (result, report) = run_verify(&tmp_dir);
+            prop_assert!(result.is_ok());
+            prop_assert!(report["corruptions"].as_array().unwrap().is_empty());
+            let snapshot_version = report["state_snapshot_version"].as_u64().unwrap();
+
+            let (ledger_db, state_merkle_db, state_kv_db) = AptosDB::open_dbs(
+                &StorageDirPaths::from_path(&tmp_dir),
+                RocksdbConfigs::default(),
+                /*readonly=*/ false,
+                /*max_num_nodes_per_lru_cache_shard=*/ 0,
+            )
+            .unwrap();
+
+            // Delete a transaction.
+            let batch = SchemaBatch::new();
+            batch.delete::<TransactionSchema>(&(version - 1)).unwrap();
+            ledger_db.transaction_db_raw().write_schemas(batch).unwrap();
+
+            // Corrupt the value of the leftmost leaf of the state snapshot.
+            let mut node_key = NodeKey::new_empty_path(snapshot_version);
+            let leaf = loop {
+                match state_merkle_db.get_node(&node_key).unwrap() {
+                    Node::Internal(internal) => {
+                        let (nibble, child) = internal.children_sorted().next().unwrap();
+                        node_key = node_key.gen_child_node_key(child.version, *nibble);
+                    },
+                    Node::Leaf(leaf) => break leaf,
+                    Node::Null => unreachable!("The state snapshot is not empty."),
+                }
+            };
+            let (state_key, value_version) = leaf.value_index();
+            let batch = SchemaBatch::new();
+            batch
+                .put::<StateValueSchema>(
+                    &(state_key.clone(), *value_version),
+                    &Some(StateValue::from(b"corrupted".to_vec())),
+                )
+                .unwrap();
+            state_kv_db
+                .db_shard(state_key.get_shard_id())
+                .write_schemas(batch)
+                .unwrap();
+            drop((ledger_db, state_merkle_db, state_kv_db));
+
+            let (result, report) = run_verify(&tmp_dir);
+            prop_assert!(result.is_err());
+            prop_assert!(has_corruption(&report, "read_error"));
+            prop_assert!(has_corruption(&report, "state_value_hash_mismatch"));
+        }
+    }
+}
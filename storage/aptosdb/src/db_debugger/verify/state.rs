@@ -0,0 +1,212 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{check_read, Corruption};
+use crate::{
+    ledger_db::LedgerDb, schema::state_value::StateValueSchema, state_kv_db::StateKvDb,
+    state_merkle_db::StateMerkleDb,
+};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_jellyfish_merkle::{
+    node_type::{LeafNode, Node, NodeKey},
+    TreeReader,
+};
+use aptos_types::{state_store::state_key::StateKey, transaction::Version};
+use rayon::prelude::*;
+
+/// Walks the Jellyfish Merkle tree at `version`, verifying the hash of every node against the
+/// hash recorded in its parent and that every leaf matches the value in the state KV DB. Also
+/// verifies the root hash against the state checkpoint hash in the transaction info. Returns the
+/// number of leaves visited.
+pub(super) fn verify_state_snapshot(
+    ledger_db: &LedgerDb,
+    state_merkle_db: &StateMerkleDb,
+    state_kv_db: &StateKvDb,
+    version: Version,
+) -> (usize, Vec<Corruption>) {
+    let mut corruptions = vec![];
+    let root_key = NodeKey::new_empty_path(version);
+    let root = match check_read(
+        &mut corruptions,
+        Some(version),
+        "jellyfish_merkle_node",
+        state_merkle_db.get_node_option(&root_key, "verify"),
+    ) {
+        Some(Some(root)) => root,
+        Some(None) => {
+            corruptions.push(missing_node(version, &root_key));
+            return (0, corruptions);
+        },
+        None => return (0, corruptions),
+    };
+
+    if let Some(txn_info) = check_read(
+        &mut corruptions,
+        Some(version),
+        "transaction_info",
+        ledger_db
+            .transaction_info_db()
+            .get_transaction_info(version),
+    ) {
+        let computed = root.hash();
+        if txn_info.state_checkpoint_hash() != Some(computed) {
+            corruptions.push(Corruption::StateRootMismatch {
+                version,
+                transaction_info: txn_info.state_checkpoint_hash(),
+                computed,
+            });
+        }
+    }
+
+    let verifier = StateVerifier {
+        state_merkle_db,
+        state_kv_db,
+        version,
+    };
+    let num_leaves = match &root {
+        Node::Internal(internal) => {
+            // Subtrees below the root are verified in parallel.
+            let children: Vec<_> = internal
+                .children_sorted()
+                .map(|(nibble, child)| {
+                    (
+                        root_key.gen_child_node_key(child.version, *nibble),
+                        child.hash,
+                    )
+                })
+                .collect();
+            let results: Vec<_> = children
+                .into_par_iter()
+                .map(|(node_key, hash)| verifier.verify_subtree(node_key, hash))
+                .collect();
+            results
+                .into_iter()
+                .map(|(num_leaves, subtree_corruptions)| {
+                    corruptions.extend(subtree_corruptions);
+                    num_leaves
+                })
+                .sum()
+        },
+        Node::Leaf(leaf) => {
+            verifier.verify_leaf(leaf, &mut corruptions);
+            1
+        },
+        Node::Null => 0,
+    };
+
+    (num_leaves, corruptions)
+}
+
+fn missing_node(version: Version, node_key: &NodeKey) -> Corruption {
+    Corruption::MissingStateNode {
+        version,
+        node_version: node_key.version(),
+        nibble_path: format!("{:?}", node_key.nibble_path()),
+    }
+}
+
+struct StateVerifier<'a> {
+    state_merkle_db: &'a StateMerkleDb,
+    state_kv_db: &'a StateKvDb,
+    version: Version,
+}
+
+impl StateVerifier<'_> {
+    fn verify_subtree(
+        &self,
+        node_key: NodeKey,
+        expected_hash: HashValue,
+    ) -> (usize, Vec<Corruption>) {
+        let mut corruptions = vec![];
+        let mut num_leaves = 0;
+        let mut stack = vec![(node_key, expected_hash)];
+
+        while let Some((node_key, expected_hash)) = stack.pop() {
+            let node = match check_read(
+                &mut corruptions,
+                Some(self.version),
+                "jellyfish_merkle_node",
+                self.state_merkle_db.get_node_option(&node_key, "verify"),
+            ) {
+                Some(Some(node)) => node,
+                Some(None) => {
+                    corruptions.push(missing_node(self.version, &node_key));
+                    continue;
+                },
+                None => continue,
+            };
+
+            let computed = node.hash();
+            if computed != expected_hash {
+                corruptions.push(Corruption::StateNodeHashMismatch {
+                    version: self.version,
+                    node_version: node_key.version(),
+                    nibble_path: format!("{:?}", node_key.nibble_path()),
+                    expected: expected_hash,
+                    computed,
+                });
+            }
+
+            match &node {
+                Node::Internal(internal) => {
+                    stack.extend(internal.children_sorted().map(|(nibble, child)| {
+                        (
+                            node_key.gen_child_node_key(child.version, *nibble),
+                            child.hash,
+                        )
+                    }));
+                },
+                Node::Leaf(leaf) => {
+                    num_leaves += 1;
+                    self.verify_leaf(leaf, &mut corruptions);
+                },
+                Node::Null => {},
+            }
+        }
+
+        (num_leaves, corruptions)
+    }
+
+    fn verify_leaf(&self, leaf: &LeafNode<StateKey>, corruptions: &mut Vec<Corruption>) {
+        let (state_key, value_version) = leaf.value_index();
+        let computed = state_key.hash();
+        if computed != leaf.account_key() {
+            corruptions.push(Corruption::StateKeyHashMismatch {
+                version: self.version,
+                state_key: format!("{:?}", state_key),
+                leaf: leaf.account_key(),
+                computed,
+            });
+        }
+
+        let value = check_read(
+            corruptions,
+            Some(*value_version),
+            "state_value",
+            self.state_kv_db
+                .db_shard(state_key.get_shard_id())
+                .get::<StateValueSchema>(&(state_key.clone(), *value_version)),
+        );
+        match value {
+            Some(Some(Some(value))) => {
+                let computed = value.hash();
+                if computed != leaf.value_hash() {
+                    corruptions.push(Corruption::StateValueHashMismatch {
+                        version: self.version,
+                        state_key: format!("{:?}", state_key),
+                        value_version: *value_version,
+                        leaf: leaf.value_hash(),
+                        computed,
+                    });
+                }
+            },
+            // The leaf exists, so the value can't have been deleted at this version.
+            Some(_) => corruptions.push(Corruption::MissingStateValue {
+                version: self.version,
+                state_key: format!("{:?}", state_key),
+                value_version: *value_version,
+            }),
+            None => {},
+        }
+    }
+}
@@ -22,6 +22,7 @@ pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
         EVENT_BY_VERSION_CF_NAME,
         EVENT_CF_NAME,
         LEDGER_INFO_CF_NAME,
+        RETAINED_TRANSACTION_CF_NAME,
         STALE_STATE_VALUE_INDEX_CF_NAME,
         STATE_VALUE_CF_NAME,
         TRANSACTION_CF_NAME,
@@ -97,6 +98,7 @@ pub(super) fn ledger_metadata_db_column_families() -> Vec<ColumnFamilyName> {
         DB_METADATA_CF_NAME,
         EPOCH_BY_VERSION_CF_NAME,
         LEDGER_INFO_CF_NAME,
+        RETAINED_TRANSACTION_CF_NAME,
         VERSION_DATA_CF_NAME,
     ]
 }
@@ -44,7 +44,7 @@ impl FastSyncStorageWrapper {
         let db_main = AptosDB::open(
             config.storage.get_dir_paths(),
             /*readonly=*/ false,
-            config.storage.storage_pruner_config,
+            config.storage.storage_pruner_config.clone(),
             config.storage.rocksdb_configs,
             config.storage.enable_indexer,
             config.storage.buffered_state_target_items,
@@ -70,7 +70,7 @@ impl FastSyncStorageWrapper {
             let secondary_db = AptosDB::open(
                 StorageDirPaths::from_path(db_dir.as_path()),
                 /*readonly=*/ false,
-                config.storage.storage_pruner_config,
+                config.storage.storage_pruner_config.clone(),
                 config.storage.rocksdb_configs,
                 config.storage.enable_indexer,
                 config.storage.buffered_state_target_items,
@@ -8,11 +8,13 @@ use crate::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         epoch_by_version::EpochByVersionSchema,
         ledger_info::LedgerInfoSchema,
+        retained_transaction::RetainedTransactionSchema,
         version_data::VersionDataSchema,
     },
     utils::{get_progress, iterators::EpochEndingLedgerInfoIter},
 };
 use anyhow::anyhow;
+use aptos_crypto::HashValue;
 use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_storage_interface::{
     block_info::{BlockInfo, BlockInfoV0},
@@ -246,6 +248,11 @@ impl LedgerMetadataDb {
         Ok(())
     }
 
+    /// Returns whether `version` ends an epoch.
+    pub(crate) fn is_epoch_ending(&self, version: Version) -> Result<bool> {
+        Ok(self.db.get::<EpochByVersionSchema>(&version)?.is_some())
+    }
+
     /// Returns the latest ended epoch strictly before required version, i.e. if the passed in
     /// version ends an epoch, return one epoch early than that.
     pub(crate) fn get_previous_epoch_ending(
@@ -362,3 +369,36 @@ impl LedgerMetadataDb {
         }
     }
 }
+
+/// Retained transaction APIs.
+impl LedgerMetadataDb {
+    /// Returns the transaction accumulator root hash at `version` if the transaction at `version`
+    /// is kept by the ledger pruner beyond the prune window.
+    pub(crate) fn get_retained_transaction_root_hash(
+        &self,
+        version: Version,
+    ) -> Result<Option<HashValue>> {
+        self.db.get::<RetainedTransactionSchema>(&version)
+    }
+
+    /// Returns the versions in [begin, end) of the transactions kept by the ledger pruner.
+    pub(crate) fn get_retained_versions(
+        &self,
+        begin: Version,
+        end: Version,
+    ) -> Result<Vec<Version>> {
+        let mut iter = self
+            .db
+            .iter::<RetainedTransactionSchema>(ReadOptions::default())?;
+        iter.seek(&begin)?;
+        let mut versions = Vec::new();
+        for item in iter {
+            let (version, _root_hash) = item?;
+            if version >= end {
+                break;
+            }
+            versions.push(version);
+        }
+        Ok(versions)
+    }
+}
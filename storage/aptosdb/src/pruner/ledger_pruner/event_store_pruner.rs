@@ -3,7 +3,10 @@
 
 use crate::{
     ledger_db::LedgerDb,
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, ledger_pruner::history_retention::get_ranges_to_prune,
+        pruner_utils::get_or_initialize_subpruner_progress,
+    },
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
 };
 use aptos_logger::info;
@@ -24,9 +27,11 @@ impl DBSubPruner for EventStorePruner {
 
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
-        self.ledger_db
-            .event_db()
-            .prune_events(current_progress, target_version, &batch)?;
+        for range in get_ranges_to_prune(&self.ledger_db, current_progress, target_version)? {
+            self.ledger_db
+                .event_db()
+                .prune_events(range.start, range.end, &batch)?;
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::EventPrunerProgress,
             &DbMetadataValue::Version(target_version),
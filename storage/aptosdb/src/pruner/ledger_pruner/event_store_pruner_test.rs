@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{AptosDB, EventStore, LedgerPrunerManager, PrunerManager};
-use aptos_config::config::LedgerPrunerConfig;
+use aptos_config::config::{LedgerHistoryRetentionConfig, LedgerPrunerConfig};
 use aptos_proptest_helpers::Index;
 use aptos_schemadb::SchemaBatch;
 use aptos_temppath::TempPath;
@@ -72,12 +72,19 @@ fn verify_event_store_pruner(events: Vec<Vec<ContractEvent>>) {
     }
     aptos_db.ledger_db.event_db().write_schemas(batch).unwrap();
 
-    let pruner = LedgerPrunerManager::new(Arc::clone(&aptos_db.ledger_db), LedgerPrunerConfig {
-        enable: true,
-        prune_window: 0,
-        batch_size: 1,
-        user_pruning_window_offset: 0,
-    });
+    let pruner = LedgerPrunerManager::new(
+        Arc::clone(&aptos_db.ledger_db),
+        LedgerPrunerConfig {
+            enable: true,
+            prune_window: 0,
+            prune_window_secs: None,
+            max_db_size_bytes: None,
+            batch_size: 1,
+            user_pruning_window_offset: 0,
+            history_retention: LedgerHistoryRetentionConfig::default(),
+        },
+        None,
+    );
     // start pruning events batches of size 2 and verify transactions have been pruned from DB
     for i in (0..=num_versions).step_by(2) {
         pruner
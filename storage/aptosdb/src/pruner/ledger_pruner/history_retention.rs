@@ -0,0 +1,230 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module selects the transactions the ledger pruner keeps beyond the prune window, as
+//! configured by `LedgerHistoryRetentionConfig`.
+
+use crate::ledger_db::LedgerDb;
+use aptos_config::config::LedgerHistoryRetentionConfig;
+use aptos_crypto::HashValue;
+use aptos_storage_interface::Result;
+use aptos_types::{
+    account_address::AccountAddress,
+    contract_event::ContractEvent,
+    state_store::state_key::{inner::StateKeyInner, StateKey},
+    transaction::{MultisigTransactionPayload, Transaction, TransactionPayload, Version},
+    write_set::WriteSet,
+};
+use move_binary_format::CompiledScript;
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
+use std::{collections::HashSet, ops::Range, sync::Arc};
+
+#[derive(Debug)]
+pub(in crate::pruner) struct HistoryRetention {
+    ledger_db: Arc<LedgerDb>,
+    accounts: HashSet<AccountAddress>,
+    modules: HashSet<ModuleId>,
+}
+
+impl HistoryRetention {
+    pub fn new(ledger_db: Arc<LedgerDb>, config: &LedgerHistoryRetentionConfig) -> Result<Self> {
+        let modules = config
+            .parse_modules()?
+            .into_iter()
+            .map(|(address, name)| Ok(ModuleId::new(address, Identifier::new(name)?)))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            ledger_db,
+            accounts: config.accounts.iter().cloned().collect(),
+            modules,
+        })
+    }
+
+    /// Returns the versions in [begin, end) of the transactions to keep, each with the
+    /// transaction accumulator root hash at the version.
+    ///
+    /// The block metadata transaction of the block a kept transaction is in is kept as well, so
+    /// that the block and its timestamp can still be served. The block might go on after `end`,
+    /// so the rest of it is checked before its block metadata transaction is left to be pruned.
+    pub fn get_retained_transactions(
+        &self,
+        begin: Version,
+        end: Version,
+    ) -> Result<Vec<(Version, HashValue)>> {
+        if self.accounts.is_empty() && self.modules.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let num_versions = (end - begin) as usize;
+        let transactions = self
+            .ledger_db
+            .transaction_db()
+            .get_transaction_iter(begin, num_versions)?;
+        let events = self
+            .ledger_db
+            .event_db()
+            .get_events_by_version_iter(begin, num_versions)?;
+        let write_sets = self
+            .ledger_db
+            .write_set_db()
+            .get_write_set_iter(begin, num_versions)?;
+
+        let mut retained_versions = Vec::new();
+        // The block metadata transaction of the current block, if it's in the range and not kept
+        // yet.
+        let mut pending_block_start = None;
+        for (version, ((transaction, events), write_set)) in
+            (begin..end).zip(transactions.zip(events).zip(write_sets))
+        {
+            let transaction = transaction?;
+            if Self::is_block_start(&transaction) {
+                pending_block_start = Some(version);
+            }
+            if self.should_retain(&transaction, &events?, &write_set?) {
+                if let Some(block_start) = pending_block_start.take() {
+                    if block_start != version {
+                        retained_versions.push(block_start);
+                    }
+                }
+                retained_versions.push(version);
+            }
+        }
+        if let Some(block_start) = pending_block_start {
+            if self.should_retain_rest_of_block(end)? {
+                retained_versions.push(block_start);
+            }
+        }
+
+        retained_versions
+            .into_iter()
+            .map(|version| {
+                let root_hash = self
+                    .ledger_db
+                    .transaction_accumulator_db()
+                    .get_root_hash(version)?;
+                Ok((version, root_hash))
+            })
+            .collect()
+    }
+
+    /// Returns whether any transaction from `version` to the end of its block is to be kept.
+    fn should_retain_rest_of_block(&self, mut version: Version) -> Result<bool> {
+        let latest_version = self.ledger_db.metadata_db().get_latest_version()?;
+        while version <= latest_version {
+            let transaction = self.ledger_db.transaction_db().get_transaction(version)?;
+            if Self::is_block_start(&transaction) {
+                return Ok(false);
+            }
+            let events = self.ledger_db.event_db().get_events_by_version(version)?;
+            let write_set = self.ledger_db.write_set_db().get_write_set(version)?;
+            if self.should_retain(&transaction, &events, &write_set) {
+                return Ok(true);
+            }
+            version += 1;
+        }
+        Ok(false)
+    }
+
+    fn is_block_start(transaction: &Transaction) -> bool {
+        matches!(
+            transaction,
+            Transaction::BlockMetadata(_) | Transaction::BlockMetadataExt(_)
+        )
+    }
+
+    fn should_retain(
+        &self,
+        transaction: &Transaction,
+        events: &[ContractEvent],
+        write_set: &WriteSet,
+    ) -> bool {
+        if let Some(txn) = transaction.try_as_signed_user_txn() {
+            if self.accounts.contains(&txn.sender()) {
+                return true;
+            }
+            if self.is_retained_payload(txn.payload()) {
+                return true;
+            }
+        }
+
+        events.iter().any(|event| self.is_retained_event(event))
+            || write_set
+                .into_iter()
+                .any(|(state_key, _write_op)| self.is_retained_state_key(state_key))
+    }
+
+    /// Returns whether the payload calls an entry function of a listed module, or is a script
+    /// using one.
+    fn is_retained_payload(&self, payload: &TransactionPayload) -> bool {
+        if self.modules.is_empty() {
+            return false;
+        }
+        match payload {
+            TransactionPayload::EntryFunction(entry_function) => {
+                self.modules.contains(entry_function.module())
+            },
+            // Only the hash of the payload is known if it was stored on chain when the multisig
+            // transaction was created, in which case only its events and writes are matched.
+            TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+                Some(MultisigTransactionPayload::EntryFunction(entry_function)) => {
+                    self.modules.contains(entry_function.module())
+                },
+                None => false,
+            },
+            TransactionPayload::Script(script) => {
+                match CompiledScript::deserialize(script.code()) {
+                    Ok(script) => script
+                        .module_handles
+                        .iter()
+                        .any(|handle| self.modules.contains(&script.module_id_for_handle(handle))),
+                    Err(_) => false,
+                }
+            },
+            TransactionPayload::ModuleBundle(_) => false,
+        }
+    }
+
+    fn is_retained_event(&self, event: &ContractEvent) -> bool {
+        if let Some(event_key) = event.event_key() {
+            if self.accounts.contains(&event_key.get_creator_address()) {
+                return true;
+            }
+        }
+        match event.type_tag() {
+            TypeTag::Struct(struct_tag) => self.modules.contains(&struct_tag.module_id()),
+            _ => false,
+        }
+    }
+
+    fn is_retained_state_key(&self, state_key: &StateKey) -> bool {
+        match state_key.inner() {
+            StateKeyInner::AccessPath(access_path) => self.accounts.contains(&access_path.address),
+            StateKeyInner::TableItem { .. } | StateKeyInner::Raw(_) => false,
+        }
+    }
+}
+
+/// Returns the ranges left in [begin, end) after taking out the versions of the transactions kept
+/// by the ledger pruner, for the sub pruners to prune.
+pub(in crate::pruner) fn get_ranges_to_prune(
+    ledger_db: &LedgerDb,
+    begin: Version,
+    end: Version,
+) -> Result<Vec<Range<Version>>> {
+    let mut ranges = Vec::new();
+    let mut range_begin = begin;
+    for version in ledger_db.metadata_db().get_retained_versions(begin, end)? {
+        if range_begin < version {
+            ranges.push(range_begin..version);
+        }
+        range_begin = version + 1;
+    }
+    if range_begin < end {
+        ranges.push(range_begin..end);
+    }
+    Ok(ranges)
+}
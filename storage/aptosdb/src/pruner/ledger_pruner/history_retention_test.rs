@@ -0,0 +1,37 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    pruner::ledger_pruner::history_retention::get_ranges_to_prune,
+    schema::retained_transaction::RetainedTransactionSchema, AptosDB,
+};
+use aptos_crypto::HashValue;
+use aptos_schemadb::SchemaBatch;
+use aptos_temppath::TempPath;
+
+#[test]
+fn test_get_ranges_to_prune() {
+    let tmp_dir = TempPath::new();
+    let aptos_db = AptosDB::new_for_test(&tmp_dir);
+    let ledger_db = &aptos_db.ledger_db;
+
+    let batch = SchemaBatch::new();
+    for version in [3, 4, 7, 10] {
+        batch
+            .put::<RetainedTransactionSchema>(&version, &HashValue::random())
+            .unwrap();
+    }
+    ledger_db.metadata_db().write_schemas(batch).unwrap();
+
+    assert_eq!(get_ranges_to_prune(ledger_db, 0, 12).unwrap(), vec![
+        0..3,
+        5..7,
+        8..10,
+        11..12
+    ]);
+    assert_eq!(get_ranges_to_prune(ledger_db, 4, 7).unwrap(), vec![5..7]);
+    assert_eq!(get_ranges_to_prune(ledger_db, 3, 5).unwrap(), vec![]);
+    assert_eq!(get_ranges_to_prune(ledger_db, 11, 20).unwrap(), vec![
+        11..20
+    ]);
+}
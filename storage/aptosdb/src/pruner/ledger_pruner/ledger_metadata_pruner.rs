@@ -3,8 +3,10 @@
 
 use crate::schema::{
     db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+    retained_transaction::RetainedTransactionSchema,
     version_data::VersionDataSchema,
 };
+use aptos_crypto::HashValue;
 use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_storage_interface::{AptosDbError, Result};
 use aptos_types::transaction::Version;
@@ -39,15 +41,21 @@ impl LedgerMetadataPruner {
         Ok(LedgerMetadataPruner { ledger_metadata_db })
     }
 
+    /// Also indexes `retained_transactions`, in the same batch as the progress, so that the sub
+    /// pruners catching up after a restart skip them as well.
     pub(in crate::pruner) fn prune(
         &self,
         current_progress: Version,
         target_version: Version,
+        retained_transactions: &[(Version, HashValue)],
     ) -> Result<()> {
         let batch = SchemaBatch::new();
         for version in current_progress..target_version {
             batch.delete::<VersionDataSchema>(&version)?;
         }
+        for (version, root_hash) in retained_transactions {
+            batch.put::<RetainedTransactionSchema>(version, root_hash)?;
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::LedgerPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -5,7 +5,10 @@ use crate::{
     ledger_db::LedgerDb,
     metrics::{PRUNER_BATCH_SIZE, PRUNER_VERSIONS, PRUNER_WINDOW},
     pruner::{
-        ledger_pruner::LedgerPruner, pruner_manager::PrunerManager, pruner_utils,
+        ledger_pruner::LedgerPruner,
+        prune_window::{PruneWindow, PruneWindowSource},
+        pruner_manager::PrunerManager,
+        pruner_utils,
         pruner_worker::PrunerWorker,
     },
 };
@@ -19,8 +22,8 @@ use std::sync::{atomic::Ordering, Arc};
 pub(crate) struct LedgerPrunerManager {
     ledger_db: Arc<LedgerDb>,
     /// DB version window, which dictates how many version of other stores like transaction, ledger
-    /// info, events etc to keep.
-    prune_window: Version,
+    /// info, events etc to keep. It is shared with the state kv pruner.
+    prune_window: Arc<PruneWindow>,
     /// It is None iff the pruner is not enabled.
     pruner_worker: Option<PrunerWorker>,
     /// Ideal batch size of the versions to be sent to the ledger pruner
@@ -41,7 +44,7 @@ impl PrunerManager for LedgerPrunerManager {
     }
 
     fn get_prune_window(&self) -> Version {
-        self.prune_window
+        self.prune_window.get()
     }
 
     fn get_min_readable_version(&self) -> Version {
@@ -53,6 +56,7 @@ impl PrunerManager for LedgerPrunerManager {
         if self.is_pruner_enabled() {
             let adjusted_window = self
                 .prune_window
+                .get()
                 .saturating_sub(self.user_pruning_window_offset);
             let adjusted_cutoff = self.latest_version.lock().saturating_sub(adjusted_window);
             std::cmp::max(min_version, adjusted_cutoff)
@@ -65,14 +69,21 @@ impl PrunerManager for LedgerPrunerManager {
     fn maybe_set_pruner_target_db_version(&self, latest_version: Version) {
         *self.latest_version.lock() = latest_version;
 
+        if !self.is_pruner_enabled() {
+            return;
+        }
         let min_readable_version = self.get_min_readable_version();
+        let prune_window = self
+            .prune_window
+            .resolve(latest_version, min_readable_version);
         // Only wake up the ledger pruner if there are `ledger_pruner_pruning_batch_size` pending
         // versions.
-        if self.is_pruner_enabled()
-            && latest_version
-                >= min_readable_version + self.pruning_batch_size as u64 + self.prune_window
+        if latest_version
+            >= min_readable_version
+                .saturating_add(self.pruning_batch_size as u64)
+                .saturating_add(prune_window)
         {
-            self.set_pruner_target_db_version(latest_version);
+            self.set_pruner_target_db_version(latest_version, prune_window);
         }
     }
 
@@ -103,12 +114,34 @@ impl PrunerManager for LedgerPrunerManager {
 }
 
 impl LedgerPrunerManager {
-    /// Creates a worker thread that waits on a channel for pruning commands.
-    pub fn new(ledger_db: Arc<LedgerDb>, ledger_pruner_config: LedgerPrunerConfig) -> Self {
+    /// Creates a worker thread that waits on a channel for pruning commands. `prune_window_source`
+    /// is needed if the window is configured in time or capped in size.
+    pub fn new(
+        ledger_db: Arc<LedgerDb>,
+        ledger_pruner_config: LedgerPrunerConfig,
+        prune_window_source: Option<PruneWindowSource>,
+    ) -> Self {
+        let prune_window = Arc::new(PruneWindow::new(
+            "ledger_pruner",
+            ledger_pruner_config.prune_window,
+            ledger_pruner_config.prune_window_secs,
+            ledger_pruner_config.max_db_size_bytes,
+            prune_window_source,
+        ));
+        Self::new_with_prune_window(ledger_db, ledger_pruner_config, prune_window)
+    }
+
+    /// Like `new`, but with a window that may be shared with other pruners, so that they resolve
+    /// the same window.
+    pub fn new_with_prune_window(
+        ledger_db: Arc<LedgerDb>,
+        ledger_pruner_config: LedgerPrunerConfig,
+        prune_window: Arc<PruneWindow>,
+    ) -> Self {
         let pruner_worker = if ledger_pruner_config.enable {
             Some(Self::init_pruner(
                 Arc::clone(&ledger_db),
-                ledger_pruner_config,
+                &ledger_pruner_config,
             ))
         } else {
             None
@@ -123,7 +156,7 @@ impl LedgerPrunerManager {
 
         Self {
             ledger_db,
-            prune_window: ledger_pruner_config.prune_window,
+            prune_window,
             pruner_worker,
             pruning_batch_size: ledger_pruner_config.batch_size,
             latest_version: Arc::new(Mutex::new(min_readable_version)),
@@ -134,10 +167,12 @@ impl LedgerPrunerManager {
 
     fn init_pruner(
         ledger_db: Arc<LedgerDb>,
-        ledger_pruner_config: LedgerPrunerConfig,
+        ledger_pruner_config: &LedgerPrunerConfig,
     ) -> PrunerWorker {
-        let pruner =
-            Arc::new(LedgerPruner::new(ledger_db).expect("Failed to create ledger pruner."));
+        let pruner = Arc::new(
+            LedgerPruner::new(ledger_db, &ledger_pruner_config.history_retention)
+                .expect("Failed to create ledger pruner."),
+        );
 
         PRUNER_WINDOW
             .with_label_values(&["ledger_pruner"])
@@ -150,9 +185,9 @@ impl LedgerPrunerManager {
         PrunerWorker::new(pruner, ledger_pruner_config.batch_size, "ledger")
     }
 
-    fn set_pruner_target_db_version(&self, latest_version: Version) {
+    fn set_pruner_target_db_version(&self, latest_version: Version, prune_window: Version) {
         assert!(self.pruner_worker.is_some());
-        let min_readable_version = latest_version.saturating_sub(self.prune_window);
+        let min_readable_version = latest_version.saturating_sub(prune_window);
         self.min_readable_version
             .store(min_readable_version, Ordering::SeqCst);
 
@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod event_store_pruner;
+mod history_retention;
+#[cfg(test)]
+mod history_retention_test;
 mod ledger_metadata_pruner;
 pub(crate) mod ledger_pruner_manager;
 mod transaction_accumulator_pruner;
@@ -17,7 +20,8 @@ use crate::{
         db_pruner::DBPruner,
         db_sub_pruner::DBSubPruner,
         ledger_pruner::{
-            event_store_pruner::EventStorePruner, ledger_metadata_pruner::LedgerMetadataPruner,
+            event_store_pruner::EventStorePruner, history_retention::HistoryRetention,
+            ledger_metadata_pruner::LedgerMetadataPruner,
             transaction_accumulator_pruner::TransactionAccumulatorPruner,
             transaction_auxiliary_data_pruner::TransactionAuxiliaryDataPruner,
             transaction_info_pruner::TransactionInfoPruner, transaction_pruner::TransactionPruner,
@@ -27,6 +31,7 @@ use crate::{
     transaction_store::TransactionStore,
 };
 use anyhow::anyhow;
+use aptos_config::config::LedgerHistoryRetentionConfig;
 use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use aptos_logger::info;
 use aptos_storage_interface::Result;
@@ -48,6 +53,8 @@ pub(crate) struct LedgerPruner {
 
     ledger_metadata_pruner: Box<LedgerMetadataPruner>,
 
+    history_retention: HistoryRetention,
+
     sub_pruners: Vec<Box<dyn DBSubPruner + Send + Sync>>,
 }
 
@@ -69,8 +76,15 @@ impl DBPruner for LedgerPruner {
                 target_version = current_batch_target_version,
                 "Pruning ledger data."
             );
-            self.ledger_metadata_pruner
-                .prune(progress, current_batch_target_version)?;
+            // The sub pruners skip the transactions indexed as retained here.
+            let retained_transactions = self
+                .history_retention
+                .get_retained_transactions(progress, current_batch_target_version)?;
+            self.ledger_metadata_pruner.prune(
+                progress,
+                current_batch_target_version,
+                &retained_transactions,
+            )?;
 
             THREAD_MANAGER.get_background_pool().install(|| {
                 self.sub_pruners.par_iter().try_for_each(|sub_pruner| {
@@ -112,7 +126,10 @@ impl DBPruner for LedgerPruner {
 }
 
 impl LedgerPruner {
-    pub fn new(ledger_db: Arc<LedgerDb>) -> Result<Self> {
+    pub fn new(
+        ledger_db: Arc<LedgerDb>,
+        history_retention_config: &LedgerHistoryRetentionConfig,
+    ) -> Result<Self> {
         info!(name = LEDGER_PRUNER_NAME, "Initializing...");
 
         let ledger_metadata_pruner = Box::new(
@@ -127,6 +144,9 @@ impl LedgerPruner {
             "Created ledger metadata pruner, start catching up all sub pruners."
         );
 
+        let history_retention =
+            HistoryRetention::new(Arc::clone(&ledger_db), history_retention_config)?;
+
         let transaction_store = Arc::new(TransactionStore::new(Arc::clone(&ledger_db)));
 
         let event_store_pruner = Box::new(EventStorePruner::new(
@@ -161,6 +181,7 @@ impl LedgerPruner {
             target_version: AtomicVersion::new(metadata_progress),
             progress: AtomicVersion::new(metadata_progress),
             ledger_metadata_pruner,
+            history_retention,
             sub_pruners: vec![
                 event_store_pruner,
                 transaction_accumulator_pruner,
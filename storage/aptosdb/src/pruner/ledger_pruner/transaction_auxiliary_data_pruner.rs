@@ -3,7 +3,10 @@
 
 use crate::{
     ledger_db::{transaction_auxiliary_data_db::TransactionAuxiliaryDataDb, LedgerDb},
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, ledger_pruner::history_retention::get_ranges_to_prune,
+        pruner_utils::get_or_initialize_subpruner_progress,
+    },
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
 };
 use aptos_logger::info;
@@ -24,7 +27,9 @@ impl DBSubPruner for TransactionAuxiliaryDataPruner {
 
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
-        TransactionAuxiliaryDataDb::prune(current_progress, target_version, &batch)?;
+        for range in get_ranges_to_prune(&self.ledger_db, current_progress, target_version)? {
+            TransactionAuxiliaryDataDb::prune(range.start, range.end, &batch)?;
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::TransactionAuxiliaryDataPrunerProgress,
             &DbMetadataValue::Version(target_version),
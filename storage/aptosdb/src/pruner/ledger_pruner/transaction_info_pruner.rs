@@ -3,7 +3,10 @@
 
 use crate::{
     ledger_db::{transaction_info_db::TransactionInfoDb, LedgerDb},
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, ledger_pruner::history_retention::get_ranges_to_prune,
+        pruner_utils::get_or_initialize_subpruner_progress,
+    },
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
 };
 use aptos_logger::info;
@@ -24,7 +27,9 @@ impl DBSubPruner for TransactionInfoPruner {
 
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
-        TransactionInfoDb::prune(current_progress, target_version, &batch)?;
+        for range in get_ranges_to_prune(&self.ledger_db, current_progress, target_version)? {
+            TransactionInfoDb::prune(range.start, range.end, &batch)?;
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::TransactionInfoPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -3,7 +3,10 @@
 
 use crate::{
     ledger_db::LedgerDb,
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, ledger_pruner::history_retention::get_ranges_to_prune,
+        pruner_utils::get_or_initialize_subpruner_progress,
+    },
     schema::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         transaction::TransactionSchema,
@@ -29,18 +32,18 @@ impl DBSubPruner for TransactionPruner {
 
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
-        let candidate_transactions =
-            self.get_pruning_candidate_transactions(current_progress, target_version)?;
-        self.ledger_db
-            .transaction_db()
-            .prune_transaction_by_hash_indices(&candidate_transactions, &batch)?;
-        self.transaction_store
-            .prune_transaction_by_account(&candidate_transactions, &batch)?;
-        self.ledger_db.transaction_db().prune_transactions(
-            current_progress,
-            target_version,
-            &batch,
-        )?;
+        for range in get_ranges_to_prune(&self.ledger_db, current_progress, target_version)? {
+            let candidate_transactions =
+                self.get_pruning_candidate_transactions(range.start, range.end)?;
+            self.ledger_db
+                .transaction_db()
+                .prune_transaction_by_hash_indices(&candidate_transactions, &batch)?;
+            self.transaction_store
+                .prune_transaction_by_account(&candidate_transactions, &batch)?;
+            self.ledger_db
+                .transaction_db()
+                .prune_transactions(range.start, range.end, &batch)?;
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::TransactionPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -5,6 +5,9 @@
 mod db_pruner;
 mod db_sub_pruner;
 mod ledger_pruner;
+mod prune_window;
+#[cfg(test)]
+mod prune_window_test;
 mod pruner_manager;
 mod pruner_utils;
 mod pruner_worker;
@@ -12,6 +15,8 @@ mod state_kv_pruner;
 mod state_merkle_pruner;
 
 pub(crate) use ledger_pruner::ledger_pruner_manager::LedgerPrunerManager;
+pub(crate) use prune_window::{PruneWindow, PruneWindowSource};
 pub(crate) use pruner_manager::PrunerManager;
+pub(crate) use pruner_utils::{get_ledger_and_state_kv_db_size, get_state_merkle_db_size};
 pub(crate) use state_kv_pruner::state_kv_pruner_manager::StateKvPrunerManager;
 pub(crate) use state_merkle_pruner::state_merkle_pruner_manager::StateMerklePrunerManager;
@@ -0,0 +1,281 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides `PruneWindow`, which resolves the number of versions a pruner keeps. The
+//! window is configured either in versions or in seconds of history, and can be capped by a limit
+//! on the size of the DB being pruned.
+
+use crate::{
+    event_store::EventStore,
+    ledger_db::LedgerDb,
+    metrics::{PRUNER_EFFECTIVE_WINDOW, PRUNER_WINDOW_TIME_FALLBACK},
+};
+use aptos_config::config::MIN_SIZE_BASED_PRUNE_WINDOW;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_storage_interface::{AptosDbError, Result};
+use aptos_types::{account_config::new_block_event_key, transaction::Version};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// How often time and size based windows are resolved. RocksDB reclaims the space taken by pruned
+/// data lazily in compactions, so resolving more often would shrink a size based window again
+/// before the previous shrink shows up in the DB size.
+const RESOLVE_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Returns the size in bytes of the DBs a pruner prunes.
+pub(crate) type DbSizeFn = Arc<dyn Fn() -> Result<u64> + Send + Sync>;
+
+/// Where time and size based prune windows are resolved from.
+#[derive(Clone)]
+pub(crate) struct PruneWindowSource {
+    ledger_db: Arc<LedgerDb>,
+    event_store: Arc<EventStore>,
+    /// The event index is not written in this mode, so block timestamps are read from the block
+    /// index instead.
+    skip_index_and_usage: bool,
+    db_size: DbSizeFn,
+}
+
+impl PruneWindowSource {
+    pub fn new(ledger_db: Arc<LedgerDb>, skip_index_and_usage: bool, db_size: DbSizeFn) -> Self {
+        let event_store = Arc::new(EventStore::new(ledger_db.event_db().db_arc()));
+        Self {
+            ledger_db,
+            event_store,
+            skip_index_and_usage,
+            db_size,
+        }
+    }
+
+    /// Returns the first version of the first block starting at or after `version`.
+    fn get_first_block_version_at_or_after(&self, version: Version) -> Result<Version> {
+        if self.skip_index_and_usage {
+            let (block_version, _block_height) = self
+                .ledger_db
+                .metadata_db()
+                .get_block_height_at_or_after_version(version)?;
+            return Ok(block_version);
+        }
+
+        let (block_version, _idx, _seq_num) = self
+            .event_store
+            .lookup_event_at_or_after_version(&new_block_event_key(), version)?
+            .ok_or_else(|| {
+                AptosDbError::NotFound(format!("NewBlockEvent at or after version {version}"))
+            })?;
+        Ok(block_version)
+    }
+
+    /// Returns the first version the ledger pruner hasn't pruned, i.e., the first version block
+    /// timestamps might be available for.
+    fn get_ledger_min_readable_version(&self) -> Result<Version> {
+        match self.ledger_db.metadata_db().get_pruner_progress() {
+            Ok(version) => Ok(version),
+            // Nothing has been pruned yet.
+            Err(AptosDbError::NotFound(_)) => Ok(0),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the timestamp of the block `version` belongs to, in microseconds.
+    fn get_block_timestamp(&self, version: Version) -> Result<u64> {
+        if self.skip_index_and_usage {
+            let metadata_db = self.ledger_db.metadata_db();
+            let block_height = metadata_db.get_block_height_by_version(version)?;
+            let block_info = metadata_db.get_block_info(block_height)?.ok_or_else(|| {
+                AptosDbError::NotFound(format!("BlockInfo at height {block_height}"))
+            })?;
+            return Ok(block_info.timestamp_usecs());
+        }
+
+        let (_first_version, new_block_event) = self.event_store.get_block_metadata(version)?;
+        Ok(new_block_event.proposed_time())
+    }
+}
+
+/// The number of versions a pruner keeps.
+pub(crate) struct PruneWindow {
+    pruner_name: &'static str,
+    /// Window in versions, in effect unless `prune_window_secs` is set.
+    prune_window: Version,
+    /// Seconds of history to keep, counted back from the latest block.
+    prune_window_secs: Option<u64>,
+    /// Size limit of the DBs being pruned, which shrinks the window when exceeded.
+    max_db_size_bytes: Option<u64>,
+    /// It is None iff the window is fixed in versions.
+    source: Option<PruneWindowSource>,
+    /// The window resolved last time and when, for time and size based windows.
+    resolved: Mutex<Option<(Instant, Version)>>,
+}
+
+impl PruneWindow {
+    pub fn new(
+        pruner_name: &'static str,
+        prune_window: Version,
+        prune_window_secs: Option<u64>,
+        max_db_size_bytes: Option<u64>,
+        source: Option<PruneWindowSource>,
+    ) -> Self {
+        let is_fixed = prune_window_secs.is_none() && max_db_size_bytes.is_none();
+        assert!(
+            is_fixed || source.is_some(),
+            "Time and size based prune windows of {} need block timestamps and DB sizes.",
+            pruner_name,
+        );
+        if is_fixed {
+            PRUNER_EFFECTIVE_WINDOW
+                .with_label_values(&[pruner_name])
+                .set(prune_window as i64);
+        }
+
+        Self {
+            pruner_name,
+            prune_window,
+            prune_window_secs,
+            max_db_size_bytes,
+            source: if is_fixed { None } else { source },
+            resolved: Mutex::new(None),
+        }
+    }
+
+    /// Returns the window resolved last time. Time and size based windows keep everything until
+    /// they are first resolved.
+    pub fn get(&self) -> Version {
+        if self.source.is_none() {
+            return self.prune_window;
+        }
+        self.resolved
+            .lock()
+            .map_or(Version::MAX, |(_resolved_at, window)| window)
+    }
+
+    /// Resolves the window at `latest_version`, given that the versions before
+    /// `min_readable_version` are already pruned. Time and size based windows are resolved at most
+    /// once per `RESOLVE_INTERVAL`, and keep the previous window if resolving fails.
+    pub fn resolve(&self, latest_version: Version, min_readable_version: Version) -> Version {
+        let source = match self.source.as_ref() {
+            Some(source) => source,
+            None => return self.prune_window,
+        };
+
+        let mut resolved = self.resolved.lock();
+        let previous = *resolved;
+        if let Some((resolved_at, window)) = previous {
+            if resolved_at.elapsed() < RESOLVE_INTERVAL {
+                return window;
+            }
+        }
+
+        let window = self
+            .resolve_impl(source, latest_version, min_readable_version)
+            .unwrap_or_else(|error| {
+                warn!(
+                    pruner_name = self.pruner_name,
+                    error = ?error,
+                    "Failed to resolve prune window, keeping the previous one."
+                );
+                previous.map_or(Version::MAX, |(_resolved_at, window)| window)
+            });
+        *resolved = Some((Instant::now(), window));
+
+        PRUNER_EFFECTIVE_WINDOW
+            .with_label_values(&[self.pruner_name])
+            .set(window.min(i64::MAX as u64) as i64);
+        info!(
+            pruner_name = self.pruner_name,
+            latest_version = latest_version,
+            prune_window = window,
+            "Prune window resolved."
+        );
+        window
+    }
+
+    fn resolve_impl(
+        &self,
+        source: &PruneWindowSource,
+        latest_version: Version,
+        min_readable_version: Version,
+    ) -> Result<Version> {
+        let mut window = match self.prune_window_secs {
+            Some(secs) => {
+                match Self::resolve_by_time(source, secs, latest_version, min_readable_version)? {
+                    Some(window) => window,
+                    None => {
+                        warn!(
+                            pruner_name = self.pruner_name,
+                            min_readable_version = min_readable_version,
+                            "Block timestamps are pruned from the ledger DB, falling back to the \
+                             prune window in versions."
+                        );
+                        PRUNER_WINDOW_TIME_FALLBACK
+                            .with_label_values(&[self.pruner_name])
+                            .inc();
+                        self.prune_window
+                    },
+                }
+            },
+            None => self.prune_window,
+        };
+
+        if let Some(max_db_size_bytes) = self.max_db_size_bytes {
+            let db_size = (source.db_size)()?;
+            let num_versions = latest_version.saturating_sub(min_readable_version);
+            if db_size > 0 && num_versions > 0 {
+                // Assumes the DB size grows in proportion to the number of versions kept, which
+                // overestimates the shrink needed when a big part of the DB is the latest state.
+                let window_by_size = (num_versions as u128 * max_db_size_bytes as u128
+                    / db_size as u128)
+                    .min(Version::MAX as u128) as Version;
+                window = window.min(window_by_size.max(MIN_SIZE_BASED_PRUNE_WINDOW));
+            }
+        }
+
+        Ok(window)
+    }
+
+    /// Returns the number of versions since the first block proposed no more than `secs` seconds
+    /// before the block of `latest_version`, or None if the timestamps of the blocks since
+    /// `min_readable_version` are not all available anymore. That is the case for the state merkle
+    /// pruner when the ledger pruner keeps fewer versions.
+    fn resolve_by_time(
+        source: &PruneWindowSource,
+        secs: u64,
+        latest_version: Version,
+        min_readable_version: Version,
+    ) -> Result<Option<Version>> {
+        if source.get_ledger_min_readable_version()? > min_readable_version {
+            return Ok(None);
+        }
+
+        let cutoff_usecs = source
+            .get_block_timestamp(latest_version)?
+            .saturating_sub(secs.saturating_mul(1_000_000));
+
+        // The block the min readable version is in might have started before it, and can't be
+        // read anymore, so the search starts from the next block.
+        let first_block_version = std::cmp::min(
+            source.get_first_block_version_at_or_after(min_readable_version)?,
+            latest_version,
+        );
+        let mut begin = first_block_version;
+        let mut end = latest_version;
+        // Block timestamps don't decrease as versions grow.
+        while begin < end {
+            let mid = begin + (end - begin) / 2;
+            if source.get_block_timestamp(mid)? < cutoff_usecs {
+                begin = mid + 1;
+            } else {
+                end = mid;
+            }
+        }
+
+        if begin == first_block_version {
+            // All readable versions are in the window.
+            return Ok(Some(latest_version - min_readable_version));
+        }
+        Ok(Some(latest_version - begin))
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    pruner::prune_window::{PruneWindow, PruneWindowSource},
+    schema::{
+        block_by_version::BlockByVersionSchema,
+        block_info::BlockInfoSchema,
+        db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+    },
+    AptosDB,
+};
+use aptos_config::config::MIN_SIZE_BASED_PRUNE_WINDOW;
+use aptos_crypto::HashValue;
+use aptos_schemadb::SchemaBatch;
+use aptos_storage_interface::block_info::{BlockInfo, BlockInfoV0};
+use aptos_temppath::TempPath;
+use aptos_types::account_address::AccountAddress;
+use std::sync::Arc;
+
+const MAX_DB_SIZE_BYTES: u64 = 1 << 30;
+/// The window in versions of time based windows, in effect when block timestamps are missing.
+const PRUNE_WINDOW: u64 = 500;
+
+const NUM_BLOCKS: u64 = 100;
+const VERSIONS_PER_BLOCK: u64 = 10;
+const LATEST_VERSION: u64 = NUM_BLOCKS * VERSIONS_PER_BLOCK - 1;
+
+/// Writes `NUM_BLOCKS` blocks of `VERSIONS_PER_BLOCK` versions each to the block index, block `i`
+/// proposed at `i` seconds.
+fn put_blocks(aptos_db: &AptosDB) {
+    let batch = SchemaBatch::new();
+    for height in 0..NUM_BLOCKS {
+        let first_version = height * VERSIONS_PER_BLOCK;
+        let block_info = BlockInfo::V0(BlockInfoV0::new(
+            HashValue::zero(),
+            0, /* epoch */
+            height,
+            AccountAddress::ZERO,
+            height * 1_000_000,
+            first_version,
+        ));
+        batch.put::<BlockInfoSchema>(&height, &block_info).unwrap();
+        batch
+            .put::<BlockByVersionSchema>(&first_version, &height)
+            .unwrap();
+    }
+    aptos_db
+        .ledger_db
+        .metadata_db()
+        .write_schemas(batch)
+        .unwrap();
+}
+
+fn time_based_prune_window(aptos_db: &AptosDB, prune_window_secs: u64) -> PruneWindow {
+    PruneWindow::new(
+        "test_pruner",
+        PRUNE_WINDOW,
+        Some(prune_window_secs),
+        None, /* max_db_size_bytes */
+        Some(PruneWindowSource::new(
+            Arc::clone(&aptos_db.ledger_db),
+            true, /* skip_index_and_usage */
+            Arc::new(|| Ok(0)),
+        )),
+    )
+}
+
+fn size_based_prune_window(aptos_db: &AptosDB, db_size: u64) -> PruneWindow {
+    PruneWindow::new(
+        "test_pruner",
+        150_000_000,
+        None, /* prune_window_secs */
+        Some(MAX_DB_SIZE_BYTES),
+        Some(PruneWindowSource::new(
+            Arc::clone(&aptos_db.ledger_db),
+            false, /* skip_index_and_usage */
+            Arc::new(move || Ok(db_size)),
+        )),
+    )
+}
+
+#[test]
+fn test_fixed_prune_window() {
+    let prune_window = PruneWindow::new("test_pruner", 100, None, None, None);
+    assert_eq!(prune_window.get(), 100);
+    assert_eq!(prune_window.resolve(1000, 0), 100);
+}
+
+#[test]
+fn test_size_based_prune_window() {
+    let tmp_dir = TempPath::new();
+    let aptos_db = AptosDB::new_for_test(&tmp_dir);
+
+    // Keeps everything until resolved.
+    let prune_window = size_based_prune_window(&aptos_db, 4 * MAX_DB_SIZE_BYTES);
+    assert_eq!(prune_window.get(), u64::MAX);
+    // 10M versions take 4 times the limit.
+    assert_eq!(prune_window.resolve(12_000_000, 2_000_000), 2_500_000);
+    assert_eq!(prune_window.get(), 2_500_000);
+
+    // Under the limit, the configured window is in effect.
+    let prune_window = size_based_prune_window(&aptos_db, MAX_DB_SIZE_BYTES / 2);
+    assert_eq!(prune_window.resolve(200_000_000, 0), 150_000_000);
+
+    // The window never goes below the minimum.
+    let prune_window = size_based_prune_window(&aptos_db, 100 * MAX_DB_SIZE_BYTES);
+    assert_eq!(
+        prune_window.resolve(10_000_000, 0),
+        MIN_SIZE_BASED_PRUNE_WINDOW
+    );
+}
+
+#[test]
+fn test_time_based_prune_window() {
+    let tmp_dir = TempPath::new();
+    let aptos_db = AptosDB::new_for_test(&tmp_dir);
+    put_blocks(&aptos_db);
+
+    // The latest block is proposed at 99s, so the window starts at the block proposed at 79s.
+    let prune_window = time_based_prune_window(&aptos_db, 20);
+    assert_eq!(prune_window.get(), u64::MAX);
+    assert_eq!(
+        prune_window.resolve(LATEST_VERSION, 0),
+        LATEST_VERSION - 79 * VERSIONS_PER_BLOCK
+    );
+
+    // The window can't cover more than the history.
+    let prune_window = time_based_prune_window(&aptos_db, 1000);
+    assert_eq!(prune_window.resolve(LATEST_VERSION, 0), LATEST_VERSION);
+
+    // If all readable versions are within the window, they are all kept, including those of a
+    // partially pruned block.
+    let min_readable_version = 79 * VERSIONS_PER_BLOCK + 5;
+    let prune_window = time_based_prune_window(&aptos_db, 20);
+    assert_eq!(
+        prune_window.resolve(LATEST_VERSION, min_readable_version),
+        LATEST_VERSION - min_readable_version
+    );
+}
+
+#[test]
+fn test_time_based_prune_window_fallback() {
+    let tmp_dir = TempPath::new();
+    let aptos_db = AptosDB::new_for_test(&tmp_dir);
+    put_blocks(&aptos_db);
+
+    // The ledger pruner has pruned the block timestamps of some versions the pruner still keeps.
+    let batch = SchemaBatch::new();
+    batch
+        .put::<DbMetadataSchema>(
+            &DbMetadataKey::LedgerPrunerProgress,
+            &DbMetadataValue::Version(50 * VERSIONS_PER_BLOCK),
+        )
+        .unwrap();
+    aptos_db
+        .ledger_db
+        .metadata_db()
+        .write_schemas(batch)
+        .unwrap();
+
+    let prune_window = time_based_prune_window(&aptos_db, 20);
+    assert_eq!(prune_window.resolve(LATEST_VERSION, 0), PRUNE_WINDOW);
+
+    // Once the pruner catches up with the ledger pruner, the window is resolved by time again.
+    let prune_window = time_based_prune_window(&aptos_db, 20);
+    assert_eq!(
+        prune_window.resolve(LATEST_VERSION, 50 * VERSIONS_PER_BLOCK),
+        LATEST_VERSION - 79 * VERSIONS_PER_BLOCK
+    );
+}
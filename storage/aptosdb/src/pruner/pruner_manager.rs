@@ -44,7 +44,7 @@ pub trait PrunerManager: Sync {
         self.wait_for_pruner()
     }
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "fuzzing", feature = "consensus-only-perf-test"))]
     fn wait_for_pruner(&self) -> Result<()> {
         use aptos_storage_interface::{db_other_bail, AptosDbError};
         use std::{
@@ -4,6 +4,14 @@
 //! This module provides common utilities for the DB pruner.
 
 use crate::{
+    common::NUM_STATE_SHARDS,
+    db_options::{
+        event_db_column_families, ledger_db_column_families, ledger_metadata_db_column_families,
+        state_kv_db_column_families, state_merkle_db_column_families,
+        transaction_accumulator_db_column_families, transaction_auxiliary_data_db_column_families,
+        transaction_db_column_families, transaction_info_db_column_families,
+        write_set_db_column_families,
+    },
     ledger_db::LedgerDb,
     pruner::state_merkle_pruner::generics::StaleNodeIndexSchemaTrait,
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
@@ -13,7 +21,7 @@ use crate::{
 };
 use anyhow::Result;
 use aptos_jellyfish_merkle::StaleNodeIndex;
-use aptos_schemadb::{schema::KeyCodec, DB};
+use aptos_schemadb::{schema::KeyCodec, ColumnFamilyName, DB};
 use aptos_types::transaction::Version;
 
 pub(crate) fn get_ledger_pruner_progress(ledger_db: &LedgerDb) -> Result<Version> {
@@ -58,3 +66,72 @@ pub(crate) fn get_or_initialize_subpruner_progress(
         },
     )
 }
+
+/// Returns the total size of the SST files of the given column families, which is roughly the
+/// space they take on disk.
+fn get_sst_files_size(db: &DB, cf_names: Vec<ColumnFamilyName>) -> Result<u64> {
+    let mut size = 0;
+    for cf_name in cf_names {
+        size += db.get_property(cf_name, "rocksdb.total-sst-files-size")?;
+    }
+    Ok(size)
+}
+
+/// Returns the size of the ledger DB and the state K/V DB, which share the ledger pruner config.
+pub(crate) fn get_ledger_and_state_kv_db_size(
+    ledger_db: &LedgerDb,
+    state_kv_db: &StateKvDb,
+) -> Result<u64> {
+    // Without sharding, everything is in the same DB.
+    if !state_kv_db.enabled_sharding() {
+        return get_sst_files_size(&ledger_db.metadata_db_arc(), ledger_db_column_families());
+    }
+
+    let mut size = get_sst_files_size(
+        &ledger_db.metadata_db_arc(),
+        ledger_metadata_db_column_families(),
+    )?;
+    size += get_sst_files_size(ledger_db.event_db_raw(), event_db_column_families())?;
+    size += get_sst_files_size(
+        ledger_db.transaction_accumulator_db_raw(),
+        transaction_accumulator_db_column_families(),
+    )?;
+    size += get_sst_files_size(
+        ledger_db.transaction_auxiliary_data_db_raw(),
+        transaction_auxiliary_data_db_column_families(),
+    )?;
+    size += get_sst_files_size(
+        ledger_db.transaction_db_raw(),
+        transaction_db_column_families(),
+    )?;
+    size += get_sst_files_size(
+        ledger_db.transaction_info_db_raw(),
+        transaction_info_db_column_families(),
+    )?;
+    size += get_sst_files_size(ledger_db.write_set_db_raw(), write_set_db_column_families())?;
+    size += get_sst_files_size(state_kv_db.metadata_db(), state_kv_db_column_families())?;
+    for shard_id in 0..NUM_STATE_SHARDS {
+        size += get_sst_files_size(
+            state_kv_db.db_shard(shard_id as u8),
+            state_kv_db_column_families(),
+        )?;
+    }
+    Ok(size)
+}
+
+/// Returns the size of the state merkle DB.
+pub(crate) fn get_state_merkle_db_size(state_merkle_db: &StateMerkleDb) -> Result<u64> {
+    let mut size = get_sst_files_size(
+        state_merkle_db.metadata_db(),
+        state_merkle_db_column_families(),
+    )?;
+    if state_merkle_db.sharding_enabled() {
+        for shard_id in 0..NUM_STATE_SHARDS {
+            size += get_sst_files_size(
+                state_merkle_db.db_shard(shard_id as u8),
+                state_merkle_db_column_families(),
+            )?;
+        }
+    }
+    Ok(size)
+}
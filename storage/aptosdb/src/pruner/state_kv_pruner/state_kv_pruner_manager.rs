@@ -4,7 +4,10 @@
 use crate::{
     metrics::{PRUNER_BATCH_SIZE, PRUNER_VERSIONS, PRUNER_WINDOW},
     pruner::{
-        pruner_manager::PrunerManager, pruner_utils, pruner_worker::PrunerWorker,
+        prune_window::{PruneWindow, PruneWindowSource},
+        pruner_manager::PrunerManager,
+        pruner_utils,
+        pruner_worker::PrunerWorker,
         state_kv_pruner::StateKvPruner,
     },
     state_kv_db::StateKvDb,
@@ -17,8 +20,9 @@ use std::sync::{atomic::Ordering, Arc};
 /// The `PrunerManager` for `StateKvPruner`.
 pub(crate) struct StateKvPrunerManager {
     state_kv_db: Arc<StateKvDb>,
-    /// DB version window, which dictates how many version of state values to keep.
-    prune_window: Version,
+    /// DB version window, which dictates how many version of state values to keep. It is shared
+    /// with the ledger pruner.
+    prune_window: Arc<PruneWindow>,
     /// It is None iff the pruner is not enabled.
     pruner_worker: Option<PrunerWorker>,
     /// Ideal batch size of the versions to be sent to the state kv pruner.
@@ -35,7 +39,7 @@ impl PrunerManager for StateKvPrunerManager {
     }
 
     fn get_prune_window(&self) -> Version {
-        self.prune_window
+        self.prune_window.get()
     }
 
     fn get_min_readable_version(&self) -> Version {
@@ -44,13 +48,20 @@ impl PrunerManager for StateKvPrunerManager {
 
     /// Sets pruner target version when necessary.
     fn maybe_set_pruner_target_db_version(&self, latest_version: Version) {
+        if !self.is_pruner_enabled() {
+            return;
+        }
         let min_readable_version = self.get_min_readable_version();
+        let prune_window = self
+            .prune_window
+            .resolve(latest_version, min_readable_version);
         // Only wake up the state kv pruner if there are `ledger_pruner_pruning_batch_size` pending
-        if self.is_pruner_enabled()
-            && latest_version
-                >= min_readable_version + self.pruning_batch_size as u64 + self.prune_window
+        if latest_version
+            >= min_readable_version
+                .saturating_add(self.pruning_batch_size as u64)
+                .saturating_add(prune_window)
         {
-            self.set_pruner_target_db_version(latest_version);
+            self.set_pruner_target_db_version(latest_version, prune_window);
         }
     }
 
@@ -81,11 +92,32 @@ impl PrunerManager for StateKvPrunerManager {
 }
 
 impl StateKvPrunerManager {
-    pub fn new(state_kv_db: Arc<StateKvDb>, state_kv_pruner_config: LedgerPrunerConfig) -> Self {
+    pub fn new(
+        state_kv_db: Arc<StateKvDb>,
+        state_kv_pruner_config: LedgerPrunerConfig,
+        prune_window_source: Option<PruneWindowSource>,
+    ) -> Self {
+        let prune_window = Arc::new(PruneWindow::new(
+            "state_kv_pruner",
+            state_kv_pruner_config.prune_window,
+            state_kv_pruner_config.prune_window_secs,
+            state_kv_pruner_config.max_db_size_bytes,
+            prune_window_source,
+        ));
+        Self::new_with_prune_window(state_kv_db, state_kv_pruner_config, prune_window)
+    }
+
+    /// Like `new`, but with a window that may be shared with other pruners, so that they resolve
+    /// the same window.
+    pub fn new_with_prune_window(
+        state_kv_db: Arc<StateKvDb>,
+        state_kv_pruner_config: LedgerPrunerConfig,
+        prune_window: Arc<PruneWindow>,
+    ) -> Self {
         let pruner_worker = if state_kv_pruner_config.enable {
             Some(Self::init_pruner(
                 Arc::clone(&state_kv_db),
-                state_kv_pruner_config,
+                &state_kv_pruner_config,
             ))
         } else {
             None
@@ -100,7 +132,7 @@ impl StateKvPrunerManager {
 
         Self {
             state_kv_db,
-            prune_window: state_kv_pruner_config.prune_window,
+            prune_window,
             pruner_worker,
             pruning_batch_size: state_kv_pruner_config.batch_size,
             min_readable_version: AtomicVersion::new(min_readable_version),
@@ -109,7 +141,7 @@ impl StateKvPrunerManager {
 
     fn init_pruner(
         state_kv_db: Arc<StateKvDb>,
-        state_kv_pruner_config: LedgerPrunerConfig,
+        state_kv_pruner_config: &LedgerPrunerConfig,
     ) -> PrunerWorker {
         let pruner =
             Arc::new(StateKvPruner::new(state_kv_db).expect("Failed to create state kv pruner."));
@@ -125,9 +157,9 @@ impl StateKvPrunerManager {
         PrunerWorker::new(pruner, state_kv_pruner_config.batch_size, "state_kv")
     }
 
-    fn set_pruner_target_db_version(&self, latest_version: Version) {
+    fn set_pruner_target_db_version(&self, latest_version: Version, prune_window: Version) {
         assert!(self.pruner_worker.is_some());
-        let min_readable_version = latest_version.saturating_sub(self.prune_window);
+        let min_readable_version = latest_version.saturating_sub(prune_window);
         self.min_readable_version
             .store(min_readable_version, Ordering::SeqCst);
 
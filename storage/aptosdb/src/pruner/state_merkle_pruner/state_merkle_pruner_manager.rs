@@ -7,6 +7,7 @@
 use crate::{
     metrics::{PRUNER_BATCH_SIZE, PRUNER_VERSIONS, PRUNER_WINDOW},
     pruner::{
+        prune_window::{PruneWindow, PruneWindowSource},
         pruner_manager::PrunerManager,
         pruner_utils,
         pruner_worker::PrunerWorker,
@@ -36,7 +37,7 @@ where
 {
     state_merkle_db: Arc<StateMerkleDb>,
     /// DB version window, which dictates how many versions of state merkle data to keep.
-    prune_window: Version,
+    prune_window: PruneWindow,
     /// It is None iff the pruner is not enabled.
     pruner_worker: Option<PrunerWorker>,
     /// The minimal readable version for the state merkle data.
@@ -56,7 +57,7 @@ where
     }
 
     fn get_prune_window(&self) -> Version {
-        self.prune_window
+        self.prune_window.get()
     }
 
     fn get_min_readable_version(&self) -> Version {
@@ -65,9 +66,15 @@ where
 
     /// Sets pruner target version when necessary.
     fn maybe_set_pruner_target_db_version(&self, latest_version: Version) {
+        if !self.is_pruner_enabled() {
+            return;
+        }
         let min_readable_version = self.get_min_readable_version();
-        if self.is_pruner_enabled() && latest_version >= min_readable_version + self.prune_window {
-            self.set_pruner_target_db_version(latest_version);
+        let prune_window = self
+            .prune_window
+            .resolve(latest_version, min_readable_version);
+        if latest_version >= min_readable_version.saturating_add(prune_window) {
+            self.set_pruner_target_db_version(latest_version, prune_window);
         }
     }
 
@@ -102,10 +109,12 @@ impl<S: StaleNodeIndexSchemaTrait> StateMerklePrunerManager<S>
 where
     StaleNodeIndex: KeyCodec<S>,
 {
-    /// Creates a worker thread that waits on a channel for pruning commands.
+    /// Creates a worker thread that waits on a channel for pruning commands. `prune_window_source`
+    /// is needed if the window is configured in time or capped in size.
     pub fn new(
         state_merkle_db: Arc<StateMerkleDb>,
         state_merkle_pruner_config: StateMerklePrunerConfig,
+        prune_window_source: Option<PruneWindowSource>,
     ) -> Self {
         let pruner_worker = if state_merkle_pruner_config.enable {
             Some(Self::init_pruner(
@@ -125,7 +134,13 @@ where
 
         Self {
             state_merkle_db,
-            prune_window: state_merkle_pruner_config.prune_window,
+            prune_window: PruneWindow::new(
+                S::name(),
+                state_merkle_pruner_config.prune_window,
+                state_merkle_pruner_config.prune_window_secs,
+                state_merkle_pruner_config.max_db_size_bytes,
+                prune_window_source,
+            ),
             pruner_worker,
             min_readable_version: AtomicVersion::new(min_readable_version),
             _phantom: PhantomData,
@@ -156,10 +171,10 @@ where
         )
     }
 
-    fn set_pruner_target_db_version(&self, latest_version: Version) {
+    fn set_pruner_target_db_version(&self, latest_version: Version, prune_window: Version) {
         assert!(self.pruner_worker.is_some());
 
-        let min_readable_version = latest_version.saturating_sub(self.prune_window);
+        let min_readable_version = latest_version.saturating_sub(prune_window);
         self.min_readable_version
             .store(min_readable_version, Ordering::SeqCst);
 
@@ -15,7 +15,9 @@ use crate::{
     state_store::StateStore,
     utils::new_sharded_kv_schema_batch,
 };
-use aptos_config::config::{LedgerPrunerConfig, StateMerklePrunerConfig};
+use aptos_config::config::{
+    LedgerHistoryRetentionConfig, LedgerPrunerConfig, StateMerklePrunerConfig,
+};
 use aptos_crypto::HashValue;
 use aptos_schemadb::{ReadOptions, SchemaBatch};
 use aptos_storage_interface::{jmt_update_refs, jmt_updates, DbReader};
@@ -102,11 +104,17 @@ fn create_state_merkle_pruner_manager(
     state_merkle_db: &Arc<StateMerkleDb>,
     prune_batch_size: usize,
 ) -> StateMerklePrunerManager<StaleNodeIndexSchema> {
-    StateMerklePrunerManager::new(Arc::clone(state_merkle_db), StateMerklePrunerConfig {
-        enable: true,
-        prune_window: 0,
-        batch_size: prune_batch_size,
-    })
+    StateMerklePrunerManager::new(
+        Arc::clone(state_merkle_db),
+        StateMerklePrunerConfig {
+            enable: true,
+            prune_window: 0,
+            prune_window_secs: None,
+            max_db_size_bytes: None,
+            batch_size: prune_batch_size,
+        },
+        None,
+    )
 }
 
 #[test]
@@ -366,12 +374,19 @@ fn verify_state_value_pruner(inputs: Vec<Vec<(StateKey, Option<StateValue>)>>) {
 
     let mut version = 0;
     let mut current_state_values = HashMap::new();
-    let pruner = StateKvPrunerManager::new(Arc::clone(&db.state_kv_db), LedgerPrunerConfig {
-        enable: true,
-        prune_window: 0,
-        batch_size: 1,
-        user_pruning_window_offset: 0,
-    });
+    let pruner = StateKvPrunerManager::new(
+        Arc::clone(&db.state_kv_db),
+        LedgerPrunerConfig {
+            enable: true,
+            prune_window: 0,
+            prune_window_secs: None,
+            max_db_size_bytes: None,
+            batch_size: 1,
+            user_pruning_window_offset: 0,
+            history_retention: LedgerHistoryRetentionConfig::default(),
+        },
+        None,
+    );
     for batch in inputs {
         update_store(store, batch.clone().into_iter(), version);
         for (k, v) in batch.iter() {
@@ -0,0 +1,57 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the index of transactions kept by the ledger
+//! pruner beyond the prune window.
+//!
+//! The value is the transaction accumulator root hash at the version, because the transaction
+//! accumulator is pruned regardless.
+//! ```text
+//! |<--key-->|<--------value-------->|
+//! | version | accumulator root hash |
+//! ```
+//!
+//! `Version` is serialized in big endian so that records in RocksDB will be in order of it's
+//! numeric value.
+
+use crate::schema::{ensure_slice_len_eq, RETAINED_TRANSACTION_CF_NAME};
+use anyhow::Result;
+use aptos_crypto::HashValue;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt};
+use std::mem::size_of;
+
+define_schema!(
+    RetainedTransactionSchema,
+    Version,
+    HashValue,
+    RETAINED_TRANSACTION_CF_NAME
+);
+
+impl KeyCodec<RetainedTransactionSchema> for Version {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Version>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+impl ValueCodec<RetainedTransactionSchema> for HashValue {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::from_slice(data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test;
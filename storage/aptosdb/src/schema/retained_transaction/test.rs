@@ -0,0 +1,15 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(version in any::<Version>(), hash in any::<HashValue>()) {
+        assert_encode_decode::<RetainedTransactionSchema>(&version, &hash);
+    }
+}
+
+test_no_panic_decoding!(RetainedTransactionSchema);
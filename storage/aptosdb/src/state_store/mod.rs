@@ -46,7 +46,7 @@ use aptos_scratchpad::{SmtAncestors, SparseMerkleTree};
 use aptos_storage_interface::{
     async_proof_fetcher::AsyncProofFetcher,
     cached_state_view::{CachedStateView, ShardedStateCache},
-    db_ensure as ensure,
+    db_ensure as ensure, jmt_update_refs, jmt_updates,
     state_delta::StateDelta,
     AptosDbError, DbReader, Result, StateSnapshotReceiver,
 };
@@ -402,14 +402,17 @@ impl StateStore {
         let state_merkle_pruner = StateMerklePrunerManager::new(
             Arc::clone(&state_merkle_db),
             NO_OP_STORAGE_PRUNER_CONFIG.state_merkle_pruner_config,
+            None, /* prune_window_source */
         );
         let epoch_snapshot_pruner = StateMerklePrunerManager::new(
             Arc::clone(&state_merkle_db),
             NO_OP_STORAGE_PRUNER_CONFIG.state_merkle_pruner_config,
+            None, /* prune_window_source */
         );
         let state_kv_pruner = StateKvPrunerManager::new(
             Arc::clone(&state_kv_db),
             NO_OP_STORAGE_PRUNER_CONFIG.ledger_pruner_config,
+            None, /* prune_window_source */
         );
         let state_db = Arc::new(StateDb {
             ledger_db,
@@ -1056,6 +1059,141 @@ impl StateStore {
         )?))
     }
 
+    /// Applies `updates` on top of the state snapshot at `base_version`, committing a new state
+    /// snapshot at `version`. Nothing is committed if the resulting root hash doesn't match
+    /// `expected_root_hash`. Used to restore differential state snapshot backups.
+    pub fn apply_state_diff(
+        &self,
+        base_version: Version,
+        version: Version,
+        updates: Vec<(StateKey, Option<StateValue>)>,
+        expected_root_hash: HashValue,
+    ) -> Result<()> {
+        let _timer = OTHER_TIMERS_SECONDS
+            .with_label_values(&["apply_state_diff"])
+            .start_timer();
+        ensure!(
+            version > base_version,
+            "State diff version {} is not newer than its base version {}.",
+            version,
+            base_version,
+        );
+
+        let mut sharded_updates = create_empty_sharded_state_updates();
+        for (key, value) in updates {
+            sharded_updates[key.get_shard_id() as usize].insert(key, value);
+        }
+
+        let (shard_root_nodes, batches_for_shards): (Vec<_>, Vec<_>) = THREAD_MANAGER
+            .get_non_exe_cpu_pool()
+            .install(|| {
+                (0..NUM_STATE_SHARDS as u8)
+                    .into_par_iter()
+                    .map(|shard_id| {
+                        self.state_merkle_db.merklize_value_set_for_shard(
+                            shard_id,
+                            jmt_update_refs(&jmt_updates(
+                                &sharded_updates[shard_id as usize]
+                                    .iter()
+                                    .map(|(k, v)| (k, v.as_ref()))
+                                    .collect(),
+                            )),
+                            /*node_hashes=*/ None,
+                            version,
+                            Some(base_version),
+                            Some(base_version),
+                            /*previous_epoch_ending_version=*/ None,
+                        )
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })?
+            .into_iter()
+            .unzip();
+        let (root_hash, top_levels_batch) = self.state_merkle_db.calculate_top_levels(
+            shard_root_nodes,
+            version,
+            Some(base_version),
+            /*previous_epoch_ending_version=*/ None,
+        )?;
+        ensure!(
+            root_hash == expected_root_hash,
+            "Root hash mismatch after applying state diff at version {}. root hash: {}, expected: {}",
+            version,
+            root_hash,
+            expected_root_hash,
+        );
+
+        // Same as `put_stats_and_indices`, but against the values at `base_version` instead of
+        // those at `version - 1`.
+        let state_kv_metadata_batch = SchemaBatch::new();
+        let sharded_state_kv_batches = new_sharded_kv_schema_batch();
+        let usage_deltas = sharded_updates
+            .par_iter()
+            .zip(sharded_state_kv_batches.par_iter())
+            .map(|(updates, batch)| {
+                let mut items_delta = 0;
+                let mut bytes_delta = 0;
+                for (key, value) in updates {
+                    if let Some(value) = value {
+                        items_delta += 1;
+                        bytes_delta += (key.size() + value.size()) as i64;
+                    } else {
+                        batch.put::<StaleStateValueIndexSchema>(
+                            &StaleStateValueIndex {
+                                stale_since_version: version,
+                                version,
+                                state_key: key.clone(),
+                            },
+                            &(),
+                        )?;
+                    }
+                    if let Some((old_version, old_value)) = self
+                        .state_db
+                        .get_state_value_with_version_by_version(key, base_version)?
+                    {
+                        items_delta -= 1;
+                        bytes_delta -= (key.size() + old_value.size()) as i64;
+                        batch.put::<StaleStateValueIndexSchema>(
+                            &StaleStateValueIndex {
+                                stale_since_version: version,
+                                version: old_version,
+                                state_key: key.clone(),
+                            },
+                            &(),
+                        )?;
+                    }
+                    batch.put::<StateValueSchema>(&(key.clone(), version), value)?;
+                    if self.state_kv_db.enabled_sharding() {
+                        state_kv_metadata_batch
+                            .put::<StateValueIndexSchema>(&(key.clone(), version), &())?;
+                    }
+                }
+                Ok((items_delta, bytes_delta))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let base_usage = self.get_usage(Some(base_version))?;
+        let usage = if base_usage.is_untracked() {
+            base_usage
+        } else {
+            let (items_delta, bytes_delta) = usage_deltas
+                .into_iter()
+                .fold((0i64, 0i64), |(items, bytes), (i, b)| {
+                    (items + i, bytes + b)
+                });
+            StateStorageUsage::new(
+                (base_usage.items() as i64 + items_delta) as usize,
+                (base_usage.bytes() as i64 + bytes_delta) as usize,
+            )
+        };
+
+        self.state_kv_db
+            .commit(version, state_kv_metadata_batch, sharded_state_kv_batches)?;
+        self.state_merkle_db
+            .commit(version, top_levels_batch, batches_for_shards)?;
+        self.ledger_db.metadata_db().put_usage(version, usage)
+    }
+
     #[cfg(test)]
     pub fn get_all_jmt_nodes_referenced(
         &self,
@@ -77,6 +77,37 @@ impl TransactionStore {
         ))
     }
 
+    /// Gets the versions of at most `num_versions` transactions sent by an account, with minimum
+    /// sequence number `min_seq_num` and `version < end_version`. Unlike
+    /// `get_account_transaction_version_iter`, the sequence numbers can have gaps, where the
+    /// transactions are pruned.
+    pub fn get_account_transaction_versions_before(
+        &self,
+        address: AccountAddress,
+        min_seq_num: u64,
+        num_versions: u64,
+        end_version: Version,
+    ) -> Result<Vec<Version>> {
+        let mut iter = self
+            .ledger_db
+            .transaction_db_raw()
+            .iter::<TransactionByAccountSchema>(ReadOptions::default())?;
+        iter.seek(&(address, min_seq_num))?;
+
+        let mut versions = Vec::new();
+        for item in iter {
+            let ((txn_address, _seq_num), version) = item?;
+            if txn_address != address
+                || version >= end_version
+                || versions.len() as u64 >= num_versions
+            {
+                break;
+            }
+            versions.push(version);
+        }
+        Ok(versions)
+    }
+
     /// Prune the transaction by account store given a list of transaction
     pub fn prune_transaction_by_account(
         &self,
@@ -3,8 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod backup;
-pub mod manifest;
 pub mod restore;
 
 #[cfg(test)]
 pub mod tests;
+
+pub use aptos_backup_storage::manifest::epoch_ending as manifest;
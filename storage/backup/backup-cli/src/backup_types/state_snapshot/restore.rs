@@ -19,11 +19,11 @@ use crate::{
     storage::{BackupStorage, FileHandle},
     utils::{
         read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt, stream::StreamX,
-        GlobalRestoreOptions, RestoreRunMode,
+        GlobalRestoreOptions, RestoreRunMode, VerifyTreeStore,
     },
 };
 use anyhow::{anyhow, ensure, Result};
-use aptos_db::state_restore::StateSnapshotRestoreMode;
+use aptos_db::state_restore::{StateSnapshotRestore, StateSnapshotRestoreMode};
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_storage_interface::StateSnapshotReceiver;
@@ -71,6 +71,8 @@ pub struct StateSnapshotRestoreController {
     concurrent_downloads: usize,
     validate_modules: bool,
     restore_mode: StateSnapshotRestoreMode,
+    /// In verify mode, the tree is restored into this store instead of being discarded.
+    verify_tree_store: Option<Arc<VerifyTreeStore>>,
 }
 
 impl StateSnapshotRestoreController {
@@ -90,9 +92,17 @@ impl StateSnapshotRestoreController {
             concurrent_downloads: global_opt.concurrent_downloads,
             validate_modules: opt.validate_modules,
             restore_mode: opt.restore_mode,
+            verify_tree_store: None,
         }
     }
 
+    /// Keeps the verified tree in `verify_tree_store` in verify mode, so differential state
+    /// snapshots can be verified on top of it. Has no effect when restoring into a DB.
+    pub fn with_verify_tree_store(mut self, verify_tree_store: Arc<VerifyTreeStore>) -> Self {
+        self.verify_tree_store = Some(verify_tree_store);
+        self
+    }
+
     pub async fn run(self) -> Result<()> {
         let name = self.name();
         let start = Instant::now();
@@ -138,11 +148,21 @@ impl StateSnapshotRestoreController {
             epoch_history.verify_ledger_info(&li)?;
         }
 
-        let receiver = Arc::new(Mutex::new(Some(self.run_mode.get_state_restore_receiver(
-            self.version,
-            manifest.root_hash,
-            self.restore_mode,
-        )?)));
+        let receiver = match &self.verify_tree_store {
+            Some(tree_store) if self.run_mode.is_verify() => StateSnapshotRestore::new_overwrite(
+                tree_store,
+                tree_store,
+                self.version,
+                manifest.root_hash,
+                self.restore_mode,
+            )?,
+            _ => self.run_mode.get_state_restore_receiver(
+                self.version,
+                manifest.root_hash,
+                self.restore_mode,
+            )?,
+        };
+        let receiver = Arc::new(Mutex::new(Some(receiver)));
 
         let (ver_gauge, tgt_leaf_idx, leaf_idx) = if self.run_mode.is_verify() {
             (
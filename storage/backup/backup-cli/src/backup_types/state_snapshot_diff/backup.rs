@@ -0,0 +1,309 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    backup_types::{
+        state_snapshot::manifest::StateSnapshotBackup,
+        state_snapshot_diff::manifest::{
+            StateSnapshotDiffBackup, StateSnapshotDiffBase, StateSnapshotDiffChunk,
+        },
+    },
+    metadata::Metadata,
+    storage::{BackupHandleRef, BackupStorage, FileHandle, ShellSafeName},
+    utils::{
+        backup_service_client::BackupServiceClient, read_record_bytes::ReadRecordBytes,
+        should_cut_chunk, storage_ext::BackupStorageExt, GlobalBackupOpt,
+    },
+};
+use anyhow::{anyhow, ensure, Result};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_logger::prelude::*;
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    proof::TransactionInfoWithProof,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::Version,
+};
+use bytes::Bytes;
+use clap::Parser;
+use once_cell::sync::Lazy;
+use std::{convert::TryInto, str::FromStr, sync::Arc};
+use tokio::{io::AsyncWriteExt, time::Instant};
+
+#[derive(Parser)]
+pub struct StateSnapshotDiffBackupOpt {
+    #[clap(
+        long = "state-snapshot-epoch",
+        help = "Epoch at the end of which a state snapshot is to be taken."
+    )]
+    pub epoch: u64,
+
+    #[clap(
+        long = "base-state-manifest",
+        conflicts_with = "base_diff_manifest",
+        required_unless_present = "base_diff_manifest",
+        help = "Manifest of the full state snapshot to take the differential snapshot against."
+    )]
+    pub base_manifest: Option<FileHandle>,
+
+    #[clap(
+        long = "base-state-diff-manifest",
+        help = "Manifest of the differential state snapshot to take the differential snapshot \
+        against."
+    )]
+    pub base_diff_manifest: Option<FileHandle>,
+}
+
+impl StateSnapshotDiffBackupOpt {
+    fn base(&self) -> Result<StateSnapshotDiffBase> {
+        match (&self.base_manifest, &self.base_diff_manifest) {
+            (Some(manifest), None) => Ok(StateSnapshotDiffBase::Full(manifest.clone())),
+            (None, Some(manifest)) => Ok(StateSnapshotDiffBase::Diff(manifest.clone())),
+            _ => Err(anyhow!(
+                "Exactly one of the base state manifest and the base state diff manifest is expected."
+            )),
+        }
+    }
+}
+
+pub struct StateSnapshotDiffBackupController {
+    epoch: u64,
+    base: StateSnapshotDiffBase,
+    max_chunk_size: usize,
+    client: Arc<BackupServiceClient>,
+    storage: Arc<dyn BackupStorage>,
+}
+
+impl StateSnapshotDiffBackupController {
+    pub fn new(
+        opt: StateSnapshotDiffBackupOpt,
+        global_opt: GlobalBackupOpt,
+        client: Arc<BackupServiceClient>,
+        storage: Arc<dyn BackupStorage>,
+    ) -> Result<Self> {
+        Ok(Self {
+            epoch: opt.epoch,
+            base: opt.base()?,
+            max_chunk_size: global_opt.max_chunk_size,
+            client,
+            storage,
+        })
+    }
+
+    pub async fn run(self) -> Result<FileHandle> {
+        info!(
+            "State snapshot diff backup started, for epoch {}.",
+            self.epoch
+        );
+        let ret = self
+            .run_impl()
+            .await
+            .map_err(|e| anyhow!("State snapshot diff backup failed: {}", e))?;
+        info!("State snapshot diff backup succeeded. Manifest: {}", ret);
+        Ok(ret)
+    }
+
+    async fn run_impl(self) -> Result<FileHandle> {
+        let version = self.get_version_for_epoch_ending(self.epoch).await?;
+        let base_version = match &self.base {
+            StateSnapshotDiffBase::Full(manifest) => {
+                self.storage
+                    .load_json_file::<StateSnapshotBackup>(manifest)
+                    .await?
+                    .version
+            },
+            StateSnapshotDiffBase::Diff(manifest) => {
+                self.storage
+                    .load_json_file::<StateSnapshotDiffBackup>(manifest)
+                    .await?
+                    .version
+            },
+        };
+        ensure!(
+            base_version < version,
+            "Base state snapshot at version {} is not older than the one to take at version {}.",
+            base_version,
+            version,
+        );
+
+        let backup_handle = self
+            .storage
+            .create_backup_with_random_suffix(&format!(
+                "state_diff_epoch_{}_ver_{}_base_{}",
+                self.epoch, version, base_version
+            ))
+            .await?;
+
+        let mut chunks = vec![];
+        let mut chunk_bytes = vec![];
+        let mut chunk_first_idx: usize = 0;
+        let mut chunk_first_key = HashValue::zero();
+        let mut prev_key = HashValue::zero();
+        let mut current_idx: usize = 0;
+
+        let mut state_diff_file = self.client.get_state_diff(base_version, version).await?;
+        let start = Instant::now();
+        while let Some(record_bytes) = state_diff_file.read_record_bytes().await? {
+            if should_cut_chunk(&chunk_bytes, &record_bytes, self.max_chunk_size) {
+                let chunk = self
+                    .write_chunk(
+                        &backup_handle,
+                        &chunk_bytes,
+                        chunk_first_idx,
+                        current_idx - 1,
+                        chunk_first_key,
+                        prev_key,
+                    )
+                    .await?;
+                chunks.push(chunk);
+                chunk_bytes = vec![];
+
+                info!(
+                    last_idx = current_idx - 1,
+                    values_per_second = (current_idx as f64 / start.elapsed().as_secs_f64()) as u64,
+                    "Chunk written."
+                );
+            }
+
+            let key = Self::parse_key(&record_bytes)?;
+            if chunk_bytes.is_empty() {
+                chunk_first_idx = current_idx;
+                chunk_first_key = key;
+            }
+            chunk_bytes.extend((record_bytes.len() as u32).to_be_bytes());
+            chunk_bytes.extend(&record_bytes);
+            prev_key = key;
+            current_idx += 1;
+        }
+
+        if !chunk_bytes.is_empty() {
+            let chunk = self
+                .write_chunk(
+                    &backup_handle,
+                    &chunk_bytes,
+                    chunk_first_idx,
+                    current_idx - 1,
+                    chunk_first_key,
+                    prev_key,
+                )
+                .await?;
+            chunks.push(chunk);
+        }
+
+        self.write_manifest(&backup_handle, base_version, version, chunks)
+            .await
+    }
+}
+
+impl StateSnapshotDiffBackupController {
+    fn manifest_name() -> &'static ShellSafeName {
+        static NAME: Lazy<ShellSafeName> =
+            Lazy::new(|| ShellSafeName::from_str("state_diff.manifest").unwrap());
+        &NAME
+    }
+
+    fn proof_name() -> &'static ShellSafeName {
+        static NAME: Lazy<ShellSafeName> =
+            Lazy::new(|| ShellSafeName::from_str("state_diff.proof").unwrap());
+        &NAME
+    }
+
+    fn chunk_name(first_idx: usize) -> ShellSafeName {
+        format!("{}-.chunk", first_idx).try_into().unwrap()
+    }
+
+    fn parse_key(record: &Bytes) -> Result<HashValue> {
+        let (key, _): (StateKey, Option<StateValue>) = bcs::from_bytes(record)?;
+        Ok(key.hash())
+    }
+
+    async fn get_version_for_epoch_ending(&self, epoch: u64) -> Result<u64> {
+        let ledger_info: LedgerInfoWithSignatures = bcs::from_bytes(
+            self.client
+                .get_epoch_ending_ledger_infos(epoch, epoch + 1)
+                .await?
+                .read_record_bytes()
+                .await?
+                .ok_or_else(|| {
+                    anyhow!("Failed to get epoch ending ledger info for epoch {}", epoch)
+                })?
+                .as_ref(),
+        )?;
+        Ok(ledger_info.ledger_info().version())
+    }
+
+    async fn write_chunk(
+        &self,
+        backup_handle: &BackupHandleRef,
+        chunk_bytes: &[u8],
+        first_idx: usize,
+        last_idx: usize,
+        first_key: HashValue,
+        last_key: HashValue,
+    ) -> Result<StateSnapshotDiffChunk> {
+        let (chunk_handle, mut chunk_file) = self
+            .storage
+            .create_for_write(backup_handle, &Self::chunk_name(first_idx))
+            .await?;
+        chunk_file.write_all(chunk_bytes).await?;
+        chunk_file.shutdown().await?;
+
+        Ok(StateSnapshotDiffChunk {
+            first_idx,
+            last_idx,
+            first_key,
+            last_key,
+            blobs: chunk_handle,
+        })
+    }
+
+    async fn write_manifest(
+        &self,
+        backup_handle: &BackupHandleRef,
+        base_version: Version,
+        version: Version,
+        chunks: Vec<StateSnapshotDiffChunk>,
+    ) -> Result<FileHandle> {
+        let proof_bytes = self.client.get_state_root_proof(version).await?;
+        let (txn_info, _): (TransactionInfoWithProof, LedgerInfoWithSignatures) =
+            bcs::from_bytes(&proof_bytes)?;
+
+        let (proof_handle, mut proof_file) = self
+            .storage
+            .create_for_write(backup_handle, Self::proof_name())
+            .await?;
+        proof_file.write_all(&proof_bytes).await?;
+        proof_file.shutdown().await?;
+
+        let manifest = StateSnapshotDiffBackup {
+            base_version,
+            base: self.base.clone(),
+            version,
+            epoch: self.epoch,
+            root_hash: txn_info.transaction_info().ensure_state_checkpoint_hash()?,
+            chunks,
+            proof: proof_handle,
+        };
+
+        let (manifest_handle, mut manifest_file) = self
+            .storage
+            .create_for_write(backup_handle, Self::manifest_name())
+            .await?;
+        manifest_file
+            .write_all(&serde_json::to_vec(&manifest)?)
+            .await?;
+        manifest_file.shutdown().await?;
+
+        let metadata = Metadata::new_state_snapshot_diff_backup(
+            self.epoch,
+            version,
+            base_version,
+            manifest_handle.clone(),
+        );
+        self.storage
+            .save_metadata_line(&metadata.name(), &metadata.to_text_line()?)
+            .await?;
+
+        Ok(manifest_handle)
+    }
+}
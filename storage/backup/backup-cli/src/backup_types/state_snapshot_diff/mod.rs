@@ -0,0 +1,10 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod backup;
+pub mod restore;
+
+#[cfg(test)]
+pub mod tests;
+
+pub use aptos_backup_storage::manifest::state_snapshot_diff as manifest;
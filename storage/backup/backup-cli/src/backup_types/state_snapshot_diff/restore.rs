@@ -0,0 +1,279 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    backup_types::{
+        epoch_ending::restore::EpochHistory,
+        state_snapshot::{
+            manifest::StateSnapshotBackup,
+            restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        },
+        state_snapshot_diff::manifest::{StateSnapshotDiffBackup, StateSnapshotDiffBase},
+    },
+    metrics::OTHER_TIMERS_SECONDS,
+    storage::{BackupStorage, FileHandle},
+    utils::{
+        read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt, stream::StreamX,
+        GlobalRestoreOptions, VerifyTreeStore,
+    },
+};
+use anyhow::{anyhow, ensure, Result};
+use aptos_crypto::hash::CryptoHash;
+use aptos_db::state_restore::StateSnapshotRestoreMode;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    proof::TransactionInfoWithProof,
+    state_store::{state_key::StateKey, state_value::StateValue},
+};
+use clap::Parser;
+use futures::{stream, TryStreamExt};
+use std::sync::Arc;
+use tokio::time::Instant;
+
+#[derive(Parser)]
+pub struct StateSnapshotDiffRestoreOpt {
+    #[clap(
+        long = "state-diff-manifest",
+        help = "Manifest of the differential state snapshot to restore. Its chain of base \
+        snapshots is restored first, skipping those already in the DB."
+    )]
+    pub manifest_handle: FileHandle,
+}
+
+pub struct StateSnapshotDiffRestoreController {
+    storage: Arc<dyn BackupStorage>,
+    global_opt: GlobalRestoreOptions,
+    manifest_handle: FileHandle,
+    epoch_history: Option<Arc<EpochHistory>>,
+}
+
+impl StateSnapshotDiffRestoreController {
+    pub fn new(
+        opt: StateSnapshotDiffRestoreOpt,
+        global_opt: GlobalRestoreOptions,
+        storage: Arc<dyn BackupStorage>,
+        epoch_history: Option<Arc<EpochHistory>>,
+    ) -> Self {
+        Self {
+            storage,
+            global_opt,
+            manifest_handle: opt.manifest_handle,
+            epoch_history,
+        }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let name = self.name();
+        let start = Instant::now();
+        info!("{} started. Manifest: {}", name, self.manifest_handle);
+        self.run_impl()
+            .await
+            .map_err(|e| anyhow!("{} failed: {}", name, e))?;
+        info!(time = start.elapsed().as_secs(), "{} succeeded.", name);
+        Ok(())
+    }
+}
+
+impl StateSnapshotDiffRestoreController {
+    fn name(&self) -> String {
+        format!("state snapshot diff {}", self.global_opt.run_mode.name())
+    }
+
+    async fn run_impl(self) -> Result<()> {
+        let (full_manifest_handle, full_manifest, diffs) = self.load_chain().await?;
+        // In verify mode, the diffs are chained on top of an in-memory tree, so each is verified
+        // against the state the previous one produced.
+        let verify_tree_store = self
+            .global_opt
+            .run_mode
+            .is_verify()
+            .then(|| Arc::new(VerifyTreeStore::default()));
+        let target = diffs.last().expect("Chain contains at least one diff.");
+        if target.version > self.global_opt.target_version {
+            warn!(
+                "Trying to restore state snapshot diff to version {}, which is newer than the target version {}, skipping.",
+                target.version,
+                self.global_opt.target_version,
+            );
+            return Ok(());
+        }
+
+        // Resume from the latest snapshot in the chain that's already in the DB.
+        let num_applied = match self
+            .global_opt
+            .run_mode
+            .get_state_snapshot_before(target.version + 1)
+        {
+            Some((version, root_hash)) => diffs
+                .iter()
+                .position(|diff| diff.version == version && diff.root_hash == root_hash)
+                .map(|idx| idx + 1)
+                .or_else(|| {
+                    (version == full_manifest.version && root_hash == full_manifest.root_hash)
+                        .then_some(0)
+                }),
+            None => None,
+        };
+        let num_applied = match num_applied {
+            Some(num_applied) => {
+                info!(
+                    diffs_applied = num_applied,
+                    total_diffs = diffs.len(),
+                    "Resumed state snapshot diff restore."
+                );
+                num_applied
+            },
+            None => {
+                let mut controller = StateSnapshotRestoreController::new(
+                    StateSnapshotRestoreOpt {
+                        manifest_handle: full_manifest_handle,
+                        version: full_manifest.version,
+                        validate_modules: false,
+                        restore_mode: StateSnapshotRestoreMode::Default,
+                    },
+                    self.global_opt.clone(),
+                    Arc::clone(&self.storage),
+                    self.epoch_history.clone(),
+                );
+                if let Some(tree_store) = &verify_tree_store {
+                    controller = controller.with_verify_tree_store(Arc::clone(tree_store));
+                }
+                controller.run().await?;
+                0
+            },
+        };
+
+        for diff in diffs.into_iter().skip(num_applied) {
+            self.apply_diff(diff, verify_tree_store.clone()).await?;
+        }
+        self.global_opt.run_mode.finish();
+        Ok(())
+    }
+
+    /// Loads the chain of manifests the diff is based on, returning the full state snapshot at the
+    /// root of the chain and the diffs on top of it, oldest first.
+    async fn load_chain(
+        &self,
+    ) -> Result<(
+        FileHandle,
+        StateSnapshotBackup,
+        Vec<StateSnapshotDiffBackup>,
+    )> {
+        let mut diffs = vec![];
+        let mut manifest_handle = self.manifest_handle.clone();
+        let full_manifest_handle = loop {
+            let diff: StateSnapshotDiffBackup =
+                self.storage.load_json_file(&manifest_handle).await?;
+            let base = diff.base.clone();
+            diffs.push(diff);
+            match base {
+                StateSnapshotDiffBase::Full(handle) => break handle,
+                StateSnapshotDiffBase::Diff(handle) => manifest_handle = handle,
+            }
+        };
+        diffs.reverse();
+        let full_manifest: StateSnapshotBackup =
+            self.storage.load_json_file(&full_manifest_handle).await?;
+
+        let mut version = full_manifest.version;
+        for diff in &diffs {
+            ensure!(
+                diff.base_version == version && diff.version > version,
+                "State snapshot diff at version {} has base version {}, expected {}.",
+                diff.version,
+                diff.base_version,
+                version,
+            );
+            version = diff.version;
+        }
+
+        Ok((full_manifest_handle, full_manifest, diffs))
+    }
+
+    async fn apply_diff(
+        &self,
+        diff: StateSnapshotDiffBackup,
+        verify_tree_store: Option<Arc<VerifyTreeStore>>,
+    ) -> Result<()> {
+        let (txn_info_with_proof, li): (TransactionInfoWithProof, LedgerInfoWithSignatures) =
+            self.storage.load_bcs_file(&diff.proof).await?;
+        txn_info_with_proof.verify(li.ledger_info(), diff.version)?;
+        let state_root_hash = txn_info_with_proof
+            .transaction_info()
+            .ensure_state_checkpoint_hash()?;
+        ensure!(
+            state_root_hash == diff.root_hash,
+            "Root hash mismatch with that in proof. root hash: {}, expected: {}",
+            diff.root_hash,
+            state_root_hash,
+        );
+        if let Some(epoch_history) = self.epoch_history.as_ref() {
+            epoch_history.verify_ledger_info(&li)?;
+        }
+
+        let storage = self.storage.clone();
+        let futs_iter = diff.chunks.iter().map(|chunk| {
+            let storage = storage.clone();
+            let blobs = chunk.blobs.clone();
+            async move {
+                tokio::spawn(async move { Self::read_state_updates(&storage, blobs).await }).await?
+            }
+        });
+        let con = self.global_opt.concurrent_downloads;
+        let mut futs_stream = stream::iter(futs_iter).buffered_x(con * 2, con);
+
+        // The key hashes are checked to be strictly increasing so a key can't be updated twice.
+        let mut updates = Vec::with_capacity(diff.chunks.last().map_or(0, |c| c.last_idx + 1));
+        let mut prev_key_hash = None;
+        while let Some(chunk) = futs_stream.try_next().await? {
+            for (key, value) in chunk {
+                let key_hash = key.hash();
+                ensure!(
+                    prev_key_hash.map_or(true, |prev| prev < key_hash),
+                    "State snapshot diff at version {} is not ordered by key hash.",
+                    diff.version,
+                );
+                prev_key_hash = Some(key_hash);
+                updates.push((key, value));
+            }
+        }
+
+        info!(
+            version = diff.version,
+            base_version = diff.base_version,
+            num_updates = updates.len(),
+            "Applying state snapshot diff."
+        );
+        let _timer = OTHER_TIMERS_SECONDS
+            .with_label_values(&["apply_state_diff"])
+            .start_timer();
+        let run_mode = self.global_opt.run_mode.clone();
+        tokio::task::spawn_blocking(move || {
+            run_mode.apply_state_diff(
+                diff.base_version,
+                diff.version,
+                updates,
+                diff.root_hash,
+                verify_tree_store.as_deref(),
+            )
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn read_state_updates(
+        storage: &Arc<dyn BackupStorage>,
+        file_handle: FileHandle,
+    ) -> Result<Vec<(StateKey, Option<StateValue>)>> {
+        let mut file = storage.open_for_read(&file_handle).await?;
+
+        let mut chunk = vec![];
+
+        while let Some(record_bytes) = file.read_record_bytes().await? {
+            chunk.push(bcs::from_bytes(&record_bytes)?);
+        }
+
+        Ok(chunk)
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    backup_types::{
+        state_snapshot::backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
+        state_snapshot_diff::{
+            backup::{StateSnapshotDiffBackupController, StateSnapshotDiffBackupOpt},
+            manifest::StateSnapshotDiffBackup,
+            restore::{StateSnapshotDiffRestoreController, StateSnapshotDiffRestoreOpt},
+        },
+    },
+    storage::{local_fs::LocalFs, BackupStorage, FileHandle},
+    utils::{
+        backup_service_client::BackupServiceClient, storage_ext::BackupStorageExt,
+        test_utils::start_local_backup_service, ConcurrentDownloadsOpt, GlobalBackupOpt,
+        GlobalRestoreOpt, GlobalRestoreOptions, ReplayConcurrencyLevelOpt, RocksdbOpt,
+        TrustedWaypointOpt,
+    },
+};
+use aptos_db::AptosDB;
+use aptos_executor_test_helpers::integration_test_impl::test_execution_with_storage_impl;
+use aptos_storage_interface::DbReader;
+use aptos_temppath::TempPath;
+use std::{convert::TryInto, sync::Arc};
+use tokio::{runtime::Runtime, time::Duration};
+
+fn verify(rt: &Runtime, store: &Arc<dyn BackupStorage>, manifest_handle: &FileHandle) -> bool {
+    let global_opt: GlobalRestoreOptions = GlobalRestoreOpt {
+        dry_run: true,
+        db_dir: None,
+        target_version: None, // max
+        trusted_waypoints: TrustedWaypointOpt::default(),
+        rocksdb_opt: RocksdbOpt::default(),
+        concurrent_downloads: ConcurrentDownloadsOpt::default(),
+        replay_concurrency_level: ReplayConcurrencyLevelOpt::default(),
+    }
+    .try_into()
+    .unwrap();
+    rt.block_on(
+        StateSnapshotDiffRestoreController::new(
+            StateSnapshotDiffRestoreOpt {
+                manifest_handle: manifest_handle.clone(),
+            },
+            global_opt,
+            Arc::clone(store),
+            None, /* epoch_history */
+        )
+        .run(),
+    )
+    .is_ok()
+}
+
+#[test]
+fn end_to_end() {
+    let src_db = test_execution_with_storage_impl();
+    let tgt_db_dir = TempPath::new();
+    tgt_db_dir.create_as_dir().unwrap();
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let store: Arc<dyn BackupStorage> = Arc::new(LocalFs::new(backup_dir.path().to_path_buf()));
+
+    let latest_epoch = src_db.get_latest_epoch_state().unwrap().epoch;
+    let epoch_ending_lis = src_db
+        .get_epoch_ending_ledger_infos(0, latest_epoch)
+        .unwrap()
+        .ledger_info_with_sigs;
+    assert!(epoch_ending_lis.len() >= 3);
+    let [base_epoch, diff_epoch, target_epoch] =
+        [0, epoch_ending_lis.len() / 2, epoch_ending_lis.len() - 1]
+            .map(|idx| epoch_ending_lis[idx].ledger_info().epoch());
+    let target_version = epoch_ending_lis.last().unwrap().ledger_info().version();
+    let target_root_hash = src_db
+        .get_transactions(target_version, 1, target_version, false)
+        .unwrap()
+        .proof
+        .transaction_infos
+        .pop()
+        .unwrap()
+        .state_checkpoint_hash()
+        .unwrap();
+    let target_usage = src_db
+        .get_state_storage_usage(Some(target_version))
+        .unwrap();
+
+    let (rt, port) = start_local_backup_service(Arc::clone(&src_db));
+    let client = Arc::new(BackupServiceClient::new(format!(
+        "http://localhost:{}",
+        port
+    )));
+    let global_backup_opt = GlobalBackupOpt {
+        max_chunk_size: 500,
+    };
+
+    // Full snapshot, then a diff against it, then a diff against the diff.
+    let base_manifest = rt
+        .block_on(
+            StateSnapshotBackupController::new(
+                StateSnapshotBackupOpt { epoch: base_epoch },
+                global_backup_opt.clone(),
+                Arc::clone(&client),
+                Arc::clone(&store),
+            )
+            .run(),
+        )
+        .unwrap();
+    let diff_manifest = rt
+        .block_on(
+            StateSnapshotDiffBackupController::new(
+                StateSnapshotDiffBackupOpt {
+                    epoch: diff_epoch,
+                    base_manifest: Some(base_manifest),
+                    base_diff_manifest: None,
+                },
+                global_backup_opt.clone(),
+                Arc::clone(&client),
+                Arc::clone(&store),
+            )
+            .unwrap()
+            .run(),
+        )
+        .unwrap();
+    let target_manifest = rt
+        .block_on(
+            StateSnapshotDiffBackupController::new(
+                StateSnapshotDiffBackupOpt {
+                    epoch: target_epoch,
+                    base_manifest: None,
+                    base_diff_manifest: Some(diff_manifest.clone()),
+                },
+                global_backup_opt,
+                client,
+                Arc::clone(&store),
+            )
+            .unwrap()
+            .run(),
+        )
+        .unwrap();
+
+    assert!(verify(&rt, &store, &target_manifest));
+
+    let global_restore_opt: GlobalRestoreOptions = GlobalRestoreOpt {
+        dry_run: false,
+        db_dir: Some(tgt_db_dir.path().to_path_buf()),
+        target_version: None, // max
+        trusted_waypoints: TrustedWaypointOpt::default(),
+        rocksdb_opt: RocksdbOpt::default(),
+        concurrent_downloads: ConcurrentDownloadsOpt::default(),
+        replay_concurrency_level: ReplayConcurrencyLevelOpt::default(),
+    }
+    .try_into()
+    .unwrap();
+    // The second run finds the snapshot in the DB and does nothing.
+    for _ in 0..2 {
+        rt.block_on(
+            StateSnapshotDiffRestoreController::new(
+                StateSnapshotDiffRestoreOpt {
+                    manifest_handle: target_manifest.clone(),
+                },
+                global_restore_opt.clone(),
+                Arc::clone(&store),
+                None, /* epoch_history */
+            )
+            .run(),
+        )
+        .unwrap();
+    }
+    drop(global_restore_opt);
+
+    let tgt_db = AptosDB::new_readonly_for_test(&tgt_db_dir);
+    assert_eq!(
+        tgt_db
+            .get_state_snapshot_before(target_version + 1)
+            .unwrap()
+            .unwrap(),
+        (target_version, target_root_hash)
+    );
+    assert_eq!(
+        tgt_db
+            .get_state_storage_usage(Some(target_version))
+            .unwrap(),
+        target_usage
+    );
+
+    // Verify mode applies the diffs, so dropping an update from one fails the chain.
+    let diff: StateSnapshotDiffBackup = rt.block_on(store.load_json_file(&diff_manifest)).unwrap();
+    let blobs_path = backup_dir.path().join(&diff.chunks[0].blobs);
+    let blobs = std::fs::read(&blobs_path).unwrap();
+    let first_record_size = u32::from_be_bytes(blobs[..4].try_into().unwrap()) as usize;
+    std::fs::write(&blobs_path, &blobs[4 + first_record_size..]).unwrap();
+    assert!(!verify(&rt, &store, &target_manifest));
+
+    rt.shutdown_timeout(Duration::from_secs(1));
+}
@@ -4,8 +4,9 @@
 
 mod analysis;
 pub mod backup;
-pub mod manifest;
 pub mod restore;
 
 #[cfg(test)]
 pub mod tests;
+
+pub use aptos_backup_storage::manifest::transaction as manifest;
@@ -6,7 +6,7 @@
 
 pub mod backup_types;
 pub mod coordinators;
-pub mod metadata;
 pub mod metrics;
-pub mod storage;
 pub mod utils;
+
+pub use aptos_backup_storage::{metadata, storage};
@@ -89,6 +89,15 @@ impl BackupServiceClient {
         self.get(&format!("state_snapshot/{}", version)).await
     }
 
+    pub async fn get_state_diff(
+        &self,
+        base_version: Version,
+        version: Version,
+    ) -> Result<impl AsyncRead> {
+        self.get(&format!("state_diff/{}/{}", base_version, version))
+            .await
+    }
+
     pub async fn get_state_root_proof(&self, version: Version) -> Result<Vec<u8>> {
         let mut buf = Vec::new();
         self.get(&format!("state_root_proof/{}", version))
@@ -3,14 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod backup_service_client;
-pub(crate) mod error_notes;
-pub mod read_record_bytes;
-pub mod storage_ext;
-pub(crate) mod stream;
 
 #[cfg(any(test, feature = "testing"))]
 pub mod test_utils;
 
+pub use aptos_backup_storage::utils::{error_notes, read_record_bytes, storage_ext, stream};
 use aptos_config::config::{
     RocksdbConfig, RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
     DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
@@ -25,10 +22,15 @@ use aptos_db::{
         StateValueWriter,
     },
 };
-use aptos_infallible::duration_since_epoch;
-use aptos_jellyfish_merkle::{NodeBatch, TreeWriter};
+use aptos_infallible::{duration_since_epoch, RwLock};
+use aptos_jellyfish_merkle::{
+    node_type::{LeafNode, Node, NodeKey},
+    JellyfishMerkleTree, NodeBatch, TreeReader, TreeUpdateBatch, TreeWriter,
+};
 use aptos_logger::info;
-use aptos_storage_interface::{AptosDbError, Result};
+use aptos_storage_interface::{
+    db_ensure as ensure, jmt_update_refs, jmt_updates, AptosDbError, Result,
+};
 use aptos_types::{
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
@@ -37,14 +39,7 @@ use aptos_types::{
     waypoint::Waypoint,
 };
 use clap::Parser;
-use std::{
-    collections::HashMap,
-    convert::TryFrom,
-    mem::size_of,
-    path::{Path, PathBuf},
-    sync::Arc,
-};
-use tokio::fs::metadata;
+use std::{collections::HashMap, convert::TryFrom, mem::size_of, path::PathBuf, sync::Arc};
 
 #[derive(Clone, Parser)]
 pub struct GlobalBackupOpt {
@@ -184,6 +179,129 @@ impl StateValueWriter<StateKey, StateValue> for MockStore {
     }
 }
 
+/// An in-memory state tree, used in verify mode to check differential state snapshots. Unlike
+/// full snapshots, their chunks carry no range proofs, so they can only be verified by applying
+/// them on top of the tree of their base snapshot. Only the latest version of the tree is kept,
+/// which still means holding every node of the state tree in memory.
+#[derive(Default)]
+pub struct VerifyTreeStore {
+    nodes: RwLock<HashMap<NodeKey, Node<StateKey>>>,
+}
+
+impl TreeReader<StateKey> for VerifyTreeStore {
+    fn get_node_option(&self, node_key: &NodeKey, _tag: &str) -> Result<Option<Node<StateKey>>> {
+        Ok(self.nodes.read().get(node_key).cloned())
+    }
+
+    fn get_rightmost_leaf(
+        &self,
+        version: Version,
+    ) -> Result<Option<(NodeKey, LeafNode<StateKey>)>> {
+        Ok(self
+            .nodes
+            .read()
+            .iter()
+            .filter_map(|(node_key, node)| match node {
+                Node::Leaf(leaf_node) if node_key.version() == version => {
+                    Some((node_key.clone(), leaf_node.clone()))
+                },
+                _ => None,
+            })
+            .max_by_key(|(_, leaf_node)| leaf_node.account_key()))
+    }
+}
+
+impl TreeWriter<StateKey> for VerifyTreeStore {
+    fn write_node_batch(&self, node_batch: &NodeBatch<StateKey>) -> Result<()> {
+        self.nodes.write().extend(node_batch.clone());
+        Ok(())
+    }
+}
+
+impl StateValueWriter<StateKey, StateValue> for VerifyTreeStore {
+    fn write_kv_batch(
+        &self,
+        _version: Version,
+        _kv_batch: &StateValueBatch<StateKey, Option<StateValue>>,
+        _progress: StateSnapshotProgress,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_usage(&self, _version: Version, _usage: StateStorageUsage) -> Result<()> {
+        Ok(())
+    }
+
+    fn get_progress(&self, _version: Version) -> Result<Option<StateSnapshotProgress>> {
+        Ok(None)
+    }
+}
+
+impl VerifyTreeStore {
+    /// Applies `updates` on top of the tree at `base_version`, failing if the resulting root hash
+    /// doesn't match `expected_root_hash`. Nodes made stale by the updates are dropped, so the
+    /// next diff in the chain is applied on top of the tree at `version`.
+    pub fn apply_state_diff(
+        &self,
+        base_version: Version,
+        version: Version,
+        updates: Vec<(StateKey, Option<StateValue>)>,
+        expected_root_hash: HashValue,
+    ) -> Result<()> {
+        ensure!(
+            version > base_version,
+            "State diff version {} is not newer than its base version {}.",
+            version,
+            base_version,
+        );
+        let updates = jmt_updates(
+            &updates
+                .iter()
+                .map(|(key, value)| (key, value.as_ref()))
+                .collect(),
+        );
+
+        let tree = JellyfishMerkleTree::new(self);
+        let mut tree_update_batch = TreeUpdateBatch::new();
+        let mut shard_root_nodes = Vec::with_capacity(16);
+        for shard_id in 0..16u8 {
+            let (shard_root_node, shard_batch) = tree.batch_put_value_set_for_shard(
+                shard_id,
+                jmt_update_refs(&updates)
+                    .into_iter()
+                    .filter(|(key_hash, _)| key_hash.nibble(0) == shard_id)
+                    .collect(),
+                /*node_hashes=*/ None,
+                Some(base_version),
+                version,
+            )?;
+            tree_update_batch.combine(shard_batch);
+            shard_root_nodes.push(shard_root_node);
+        }
+        let (root_hash, top_levels_batch) =
+            tree.put_top_levels_nodes(shard_root_nodes, Some(base_version), version)?;
+        tree_update_batch.combine(top_levels_batch);
+        ensure!(
+            root_hash == expected_root_hash,
+            "Root hash mismatch after applying state diff at version {}. root hash: {}, expected: {}",
+            version,
+            root_hash,
+            expected_root_hash,
+        );
+
+        let TreeUpdateBatch {
+            node_batch,
+            stale_node_index_batch,
+        } = tree_update_batch;
+        let mut nodes = self.nodes.write();
+        for index in stale_node_index_batch.into_iter().flatten() {
+            nodes.remove(&index.node_key);
+        }
+        nodes.extend(node_batch.into_iter().flatten());
+        Ok(())
+    }
+}
+
 impl RestoreRunMode {
     pub fn name(&self) -> &'static str {
         match self {
@@ -224,6 +342,31 @@ impl RestoreRunMode {
         }
     }
 
+    /// Applies a differential state snapshot on top of the state snapshot at `base_version`. In
+    /// verify mode there is no state tree in the DB, so the diff is applied to `verify_tree_store`
+    /// instead, which must hold the tree at `base_version`.
+    pub fn apply_state_diff(
+        &self,
+        base_version: Version,
+        version: Version,
+        updates: Vec<(StateKey, Option<StateValue>)>,
+        expected_root_hash: HashValue,
+        verify_tree_store: Option<&VerifyTreeStore>,
+    ) -> Result<()> {
+        match self {
+            Self::Restore { restore_handler } => {
+                restore_handler.apply_state_diff(base_version, version, updates, expected_root_hash)
+            },
+            Self::Verify => verify_tree_store
+                .ok_or_else(|| {
+                    AptosDbError::Other(
+                        "Verifying a state snapshot diff requires the tree of its base.".into(),
+                    )
+                })?
+                .apply_state_diff(base_version, version, updates, expected_root_hash),
+        }
+    }
+
     pub fn finish(&self) {
         match self {
             Self::Restore { restore_handler } => {
@@ -389,25 +532,6 @@ pub(crate) fn should_cut_chunk(chunk: &[u8], record: &[u8], max_chunk_size: usiz
     !chunk.is_empty() && chunk.len() + record.len() + size_of::<u32>() > max_chunk_size
 }
 
-// TODO: use Path::exists() when Rust 1.5 stabilizes.
-pub(crate) async fn path_exists(path: &Path) -> bool {
-    metadata(&path).await.is_ok()
-}
-
-pub(crate) trait PathToString {
-    fn path_to_string(&self) -> Result<String>;
-}
-
-impl<T: AsRef<Path>> PathToString for T {
-    fn path_to_string(&self) -> Result<String> {
-        self.as_ref()
-            .to_path_buf()
-            .into_os_string()
-            .into_string()
-            .map_err(|s| AptosDbError::Other(format!("into_string failed for OsString '{:?}'", s)))
-    }
-}
-
 pub(crate) fn unix_timestamp_sec() -> i64 {
     duration_since_epoch().as_secs() as i64
 }
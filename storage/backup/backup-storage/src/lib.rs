@@ -0,0 +1,14 @@
+// Copyright © Aptos Foundation
+// Parts of the project are originally copyright © Meta Platforms, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::arithmetic_side_effects)]
+
+//! The storage of backups and their metadata and manifests, shared by the backup CLI and the
+//! services that read backups (e.g., to bootstrap a node or to serve historical data).
+
+pub mod manifest;
+pub mod metadata;
+pub mod metrics;
+pub mod storage;
+pub mod utils;
@@ -0,0 +1,8 @@
+// Copyright © Aptos Foundation
+// Parts of the project are originally copyright © Meta Platforms, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod epoch_ending;
+pub mod state_snapshot;
+pub mod state_snapshot_diff;
+pub mod transaction;
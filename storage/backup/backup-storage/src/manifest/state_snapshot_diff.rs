@@ -0,0 +1,58 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::storage::FileHandle;
+use aptos_crypto::HashValue;
+use aptos_types::transaction::Version;
+use serde::{Deserialize, Serialize};
+
+/// The state snapshot a differential state snapshot is taken against.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum StateSnapshotDiffBase {
+    /// A full state snapshot, whose manifest is a `StateSnapshotBackup`.
+    Full(FileHandle),
+    /// Another differential state snapshot, whose manifest is a `StateSnapshotDiffBackup`.
+    Diff(FileHandle),
+}
+
+/// A chunk of a differential state snapshot manifest, representing the updated keys in the key
+/// range [`first_key`, `last_key`] (right side inclusive).
+#[derive(Deserialize, Serialize)]
+pub struct StateSnapshotDiffChunk {
+    /// index of the first updated key in this chunk over all updated keys.
+    pub first_idx: usize,
+    /// index of the last updated key in this chunk over all updated keys.
+    pub last_idx: usize,
+    /// hash of the first updated key in this chunk.
+    pub first_key: HashValue,
+    /// hash of the last updated key in this chunk.
+    pub last_key: HashValue,
+    /// Repeated `len(record) + record` where `record` is BCS serialized tuple
+    /// `(key, Option<state_value>)`, `None` meaning the key is deleted.
+    pub blobs: FileHandle,
+}
+
+/// Differential state snapshot backup manifest, representing the state keys updated between the
+/// state snapshot at `base_version` and the one at `version`.
+///
+/// Unlike in a full state snapshot, chunks don't carry range proofs, since the updated keys aren't
+/// adjacent in the state tree. Instead, the restore applies all the updates on top of the base
+/// and verifies the resulting root hash against `proof`.
+#[derive(Deserialize, Serialize)]
+pub struct StateSnapshotDiffBackup {
+    /// Version of the state snapshot this backup is taken against.
+    pub base_version: Version,
+    /// Manifest of the state snapshot this backup is taken against.
+    pub base: StateSnapshotDiffBase,
+    /// Version at which this state snapshot is taken.
+    pub version: Version,
+    /// Epoch in which this state snapshot is taken.
+    pub epoch: u64,
+    /// Hash of the state tree root at `version`.
+    pub root_hash: HashValue,
+    /// All updated keys in chunks, ordered by key hash.
+    pub chunks: Vec<StateSnapshotDiffChunk>,
+    /// BCS serialized `Tuple(TransactionInfoWithProof, LedgerInfoWithSignatures)`, same as
+    /// `StateSnapshotBackup::proof`.
+    pub proof: FileHandle,
+}
@@ -4,7 +4,7 @@
 
 use crate::{
     metadata::{view::MetadataView, Metadata},
-    metrics::metadata::{NUM_META_DOWNLOAD, NUM_META_FILES, NUM_META_MISS},
+    metrics::{NUM_META_DOWNLOAD, NUM_META_FILES, NUM_META_MISS},
     storage::{BackupStorage, FileHandle},
     utils::{error_notes::ErrorNotes, stream::StreamX},
 };
@@ -14,9 +14,10 @@ use std::{cmp::Ordering, collections::HashMap, convert::TryInto};
 
 #[derive(Deserialize, Serialize)]
 #[allow(clippy::enum_variant_names)] // to introduce: BackupperId, etc
-pub(crate) enum Metadata {
+pub enum Metadata {
     EpochEndingBackup(EpochEndingBackupMeta),
     StateSnapshotBackup(StateSnapshotBackupMeta),
+    StateSnapshotDiffBackup(StateSnapshotDiffBackupMeta),
     TransactionBackup(TransactionBackupMeta),
     Identity(IdentityMeta),
     CompactionTimestamps(CompactionTimestampsMeta),
@@ -47,6 +48,20 @@ impl Metadata {
         })
     }
 
+    pub fn new_state_snapshot_diff_backup(
+        epoch: u64,
+        version: Version,
+        base_version: Version,
+        manifest: FileHandle,
+    ) -> Self {
+        Self::StateSnapshotDiffBackup(StateSnapshotDiffBackupMeta {
+            epoch,
+            version,
+            base_version,
+            manifest,
+        })
+    }
+
     pub fn new_transaction_backup(
         first_version: Version,
         last_version: Version,
@@ -112,6 +127,25 @@ impl Metadata {
         Ok((res, name.parse()?))
     }
 
+    pub fn compact_state_snapshot_diff_backup_range(
+        backup_metas: Vec<StateSnapshotDiffBackupMeta>,
+    ) -> Result<(Vec<TextLine>, ShellSafeName)> {
+        ensure!(
+            !backup_metas.is_empty(),
+            "compacting an empty metadata vector"
+        );
+        let name = format!(
+            "state_snapshot_diff_compacted_epoch_{}_{}.meta",
+            backup_metas[0].epoch,
+            backup_metas[backup_metas.len() - 1].epoch
+        );
+        let res: Vec<TextLine> = backup_metas
+            .into_iter()
+            .map(|e| Metadata::StateSnapshotDiffBackup(e).to_text_line())
+            .collect::<Result<_>>()?;
+        Ok((res, name.parse()?))
+    }
+
     pub fn compact_transaction_backup_range(
         backup_metas: Vec<TransactionBackupMeta>,
     ) -> Result<(Vec<TextLine>, ShellSafeName)> {
@@ -156,6 +190,10 @@ impl Metadata {
                 format!("epoch_ending_{}-{}.meta", e.first_epoch, e.last_epoch)
             },
             Self::StateSnapshotBackup(s) => format!("state_snapshot_ver_{}.meta", s.version),
+            Self::StateSnapshotDiffBackup(s) => format!(
+                "state_snapshot_diff_ver_{}_base_{}.meta",
+                s.version, s.base_version
+            ),
             Self::TransactionBackup(t) => {
                 format!("transaction_{}-{}.meta", t.first_version, t.last_version)
             },
@@ -189,6 +227,14 @@ pub struct StateSnapshotBackupMeta {
     pub manifest: FileHandle,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd)]
+pub struct StateSnapshotDiffBackupMeta {
+    pub epoch: u64,
+    pub version: Version,
+    pub base_version: Version,
+    pub manifest: FileHandle,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd)]
 pub struct TransactionBackupMeta {
     pub first_version: Version,
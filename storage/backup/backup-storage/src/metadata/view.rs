@@ -5,9 +5,9 @@
 use crate::{
     metadata::{
         CompactionTimestampsMeta, EpochEndingBackupMeta, IdentityMeta, Metadata,
-        StateSnapshotBackupMeta, TransactionBackupMeta,
+        StateSnapshotBackupMeta, StateSnapshotDiffBackupMeta, TransactionBackupMeta,
     },
-    metrics::backup::COMPACTED_TXN_VERSION,
+    metrics::COMPACTED_TXN_VERSION,
     storage::FileHandle,
 };
 use anyhow::{anyhow, ensure, Result};
@@ -20,6 +20,7 @@ use std::{fmt, str::FromStr};
 pub struct MetadataView {
     epoch_ending_backups: Vec<EpochEndingBackupMeta>,
     state_snapshot_backups: Vec<StateSnapshotBackupMeta>,
+    state_snapshot_diff_backups: Vec<StateSnapshotDiffBackupMeta>,
     transaction_backups: Vec<TransactionBackupMeta>,
     _identity: Option<IdentityMeta>,
     // The compaction timestamps of the file handles producing this view
@@ -30,6 +31,7 @@ impl MetadataView {
     pub(crate) fn new(metadata_vec: Vec<Metadata>, file_handles: Vec<FileHandle>) -> Self {
         let mut epoch_ending_backups = Vec::new();
         let mut state_snapshot_backups = Vec::new();
+        let mut state_snapshot_diff_backups = Vec::new();
         let mut transaction_backups = Vec::new();
         let mut identity = None;
         let mut compaction_timestamps = Vec::new();
@@ -38,6 +40,7 @@ impl MetadataView {
             match meta {
                 Metadata::EpochEndingBackup(e) => epoch_ending_backups.push(e),
                 Metadata::StateSnapshotBackup(s) => state_snapshot_backups.push(s),
+                Metadata::StateSnapshotDiffBackup(s) => state_snapshot_diff_backups.push(s),
                 Metadata::TransactionBackup(t) => transaction_backups.push(t),
                 Metadata::Identity(i) => identity = Some(i),
                 Metadata::CompactionTimestamps(t) => compaction_timestamps.push(t),
@@ -47,6 +50,8 @@ impl MetadataView {
         epoch_ending_backups.dedup();
         state_snapshot_backups.sort_unstable();
         state_snapshot_backups.dedup();
+        state_snapshot_diff_backups.sort_unstable();
+        state_snapshot_diff_backups.dedup();
         transaction_backups.sort_unstable();
         transaction_backups.dedup();
 
@@ -72,6 +77,7 @@ impl MetadataView {
         Self {
             epoch_ending_backups,
             state_snapshot_backups,
+            state_snapshot_diff_backups,
             transaction_backups,
             _identity: identity,
             compaction_timestamps: compaction_meta_opt,
@@ -126,6 +132,20 @@ impl MetadataView {
             .ok_or_else(|| anyhow!("State snapshot not found at version {}", version))
     }
 
+    /// Selects the latest differential state snapshot no newer than `target_version`.
+    pub fn select_state_snapshot_diff(
+        &self,
+        target_version: Version,
+    ) -> Result<Option<StateSnapshotDiffBackupMeta>> {
+        Ok(self
+            .state_snapshot_diff_backups
+            .iter()
+            .sorted()
+            .rev()
+            .find(|m| m.version <= target_version)
+            .map(Clone::clone))
+    }
+
     pub fn select_transaction_backups(
         &self,
         start_version: Version,
@@ -165,6 +185,22 @@ impl MetadataView {
             .map(|backup| backup.last_version))
     }
 
+    /// Returns the last version of the continuous range of transaction backups that starts at
+    /// version 0, i.e., the highest version `select_transaction_backups` can select. Backups after
+    /// the first gap (or overlap) are ignored.
+    pub fn max_continuous_transaction_version(&self) -> Option<Version> {
+        let mut next_ver = 0;
+        let mut max_ver = None;
+        for backup in self.transaction_backups.iter().sorted() {
+            if backup.first_version != next_ver {
+                break;
+            }
+            max_ver = Some(backup.last_version);
+            next_ver = backup.last_version + 1;
+        }
+        max_ver
+    }
+
     pub fn select_epoch_ending_backups(
         &self,
         target_version: Version,
@@ -235,6 +271,13 @@ impl MetadataView {
         Self::compact_backups(&self.state_snapshot_backups, compaction_cnt)
     }
 
+    pub fn compact_state_diff_backups(
+        &mut self,
+        compaction_cnt: usize,
+    ) -> Result<Vec<&[StateSnapshotDiffBackupMeta]>> {
+        Self::compact_backups(&self.state_snapshot_diff_backups, compaction_cnt)
+    }
+
     pub fn get_file_handles(&self) -> Vec<FileHandle> {
         self.select_latest_compaction_timestamps()
             .as_ref()
@@ -0,0 +1,526 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::storage::s3::config::{S3Config, S3Credentials};
+use anyhow::{ensure, format_err, Result};
+use aptos_logger::prelude::*;
+use aptos_retrier::fixed_retry_strategy;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::{
+    header::{HeaderMap, CONTENT_LENGTH},
+    Method, StatusCode, Url,
+};
+use sha2_0_10_6::{Digest, Sha256};
+use std::ops::Range;
+use thiserror::Error;
+
+/// Characters left as is when URI encoding for the canonical request, see
+/// https://docs.aws.amazon.com/AmazonS3/latest/API/sig-v4-header-based-auth.html
+const URI_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+/// Same with `URI_ENCODE_SET`, except that object keys keep their slashes.
+const URI_ENCODE_SET_KEEP_SLASH: &AsciiSet = &URI_ENCODE_SET.remove(b'/');
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Clone)]
+struct S3Request {
+    method: Method,
+    /// `None` for requests on the bucket itself.
+    key: Option<String>,
+    query: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+    body: Bytes,
+}
+
+impl S3Request {
+    fn object(method: Method, key: &str) -> Self {
+        Self {
+            method,
+            key: Some(key.to_string()),
+            query: Vec::new(),
+            headers: Vec::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    fn bucket(method: Method) -> Self {
+        Self {
+            method,
+            key: None,
+            query: Vec::new(),
+            headers: Vec::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    fn query(mut self, key: &str, value: impl ToString) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    fn header(mut self, key: &str, value: impl ToString) -> Self {
+        self.headers.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    fn body(mut self, body: Bytes) -> Self {
+        self.body = body;
+        self
+    }
+}
+
+struct S3Response {
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+/// A request answered with a non-success status.
+#[derive(Debug, Error)]
+#[error("S3 request {method} {uri} failed with status {status}: {body}")]
+struct UnexpectedStatus {
+    method: Method,
+    uri: String,
+    status: StatusCode,
+    body: String,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct CompletedPart {
+    pub part_number: usize,
+    pub etag: String,
+}
+
+/// A minimal client for the subset of the S3 API used by the backup storage. Requests are
+/// path-style and signed with AWS Signature Version 4, so any S3 compatible object store works.
+/// Requests failing with a server error, throttling or a timeout are retried, other failures
+/// (e.g., a missing object or denied access) are not.
+pub(super) struct S3Client {
+    http: reqwest::Client,
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    credentials: S3Credentials,
+    max_retries: usize,
+    retry_delay_ms: u64,
+}
+
+impl S3Client {
+    pub fn new(config: &S3Config) -> Result<Self> {
+        let credentials = match &config.credentials {
+            Some(credentials) => credentials.clone(),
+            None => S3Credentials::from_env()?,
+        };
+        let endpoint = Url::parse(&config.endpoint())?;
+        ensure!(
+            endpoint.host_str().is_some(),
+            "S3 endpoint {} has no host.",
+            endpoint,
+        );
+
+        Ok(Self {
+            http: reqwest::Client::new(),
+            endpoint,
+            bucket: config.bucket.clone(),
+            region: config.region.clone(),
+            credentials,
+            max_retries: config.max_retries,
+            retry_delay_ms: config.retry_delay_ms,
+        })
+    }
+
+    pub async fn put_object(&self, key: &str, body: Bytes) -> Result<()> {
+        self.send(S3Request::object(Method::PUT, key).body(body))
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn get_object(&self, key: &str) -> Result<Bytes> {
+        Ok(self.send(S3Request::object(Method::GET, key)).await?.body)
+    }
+
+    /// Returns `None` if the object doesn't exist.
+    pub async fn get_object_if_exists(&self, key: &str) -> Result<Option<Bytes>> {
+        match self.send(S3Request::object(Method::GET, key)).await {
+            Ok(response) => Ok(Some(response.body)),
+            Err(e) if status_of(&e) == Some(StatusCode::NOT_FOUND) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the size of the object, from the `Content-Length` of a HEAD request.
+    pub async fn get_object_size(&self, key: &str) -> Result<u64> {
+        let response = self.send(S3Request::object(Method::HEAD, key)).await?;
+        response
+            .headers
+            .get(CONTENT_LENGTH)
+            .ok_or_else(|| format_err!("No Content-Length for {}.", key))?
+            .to_str()?
+            .parse()
+            .map_err(|e| format_err!("Invalid Content-Length for {}: {}", key, e))
+    }
+
+    /// Reads `range` of the object, `range` must be non-empty.
+    pub async fn get_object_range(&self, key: &str, range: Range<u64>) -> Result<Bytes> {
+        let expected_len = range.end - range.start;
+        let request = S3Request::object(Method::GET, key)
+            .header("range", format!("bytes={}-{}", range.start, range.end - 1));
+        let body = self.send(request).await?.body;
+        ensure!(
+            body.len() as u64 == expected_len,
+            "Expecting {} bytes from {} at {:?}, got {}.",
+            expected_len,
+            key,
+            range,
+            body.len(),
+        );
+        Ok(body)
+    }
+
+    pub async fn copy_object(&self, src_key: &str, dst_key: &str) -> Result<()> {
+        let copy_source = format!(
+            "/{}/{}",
+            self.bucket,
+            utf8_percent_encode(src_key, URI_ENCODE_SET_KEEP_SLASH)
+        );
+        let request =
+            S3Request::object(Method::PUT, dst_key).header("x-amz-copy-source", copy_source);
+        let body = self.send(request).await?.body;
+        // S3 can report a failed copy in the body of a 200 response.
+        ensure_no_error_in_body(&body)
+    }
+
+    pub async fn delete_object(&self, key: &str) -> Result<()> {
+        self.send(S3Request::object(Method::DELETE, key))
+            .await
+            .map(|_| ())
+    }
+
+    /// Lists the keys of all objects starting with `prefix`.
+    pub async fn list_objects(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut request = S3Request::bucket(Method::GET)
+                .query("list-type", 2)
+                .query("prefix", prefix);
+            if let Some(token) = continuation_token.take() {
+                request = request.query("continuation-token", token);
+            }
+            let body = self.send(request).await?.body;
+            let body = std::str::from_utf8(&body)?;
+            keys.extend(xml_elements(body, "Key"));
+
+            if xml_elements(body, "IsTruncated")
+                .first()
+                .map(String::as_str)
+                != Some("true")
+            {
+                break;
+            }
+            continuation_token = Some(
+                xml_elements(body, "NextContinuationToken")
+                    .pop()
+                    .ok_or_else(|| format_err!("Truncated listing without continuation token."))?,
+            );
+        }
+        Ok(keys)
+    }
+
+    /// Returns the upload ID of the new multipart upload.
+    pub async fn create_multipart_upload(&self, key: &str) -> Result<String> {
+        let request = S3Request::object(Method::POST, key).query("uploads", "");
+        let body = self.send(request).await?.body;
+        xml_elements(std::str::from_utf8(&body)?, "UploadId")
+            .pop()
+            .ok_or_else(|| format_err!("No UploadId in response of creating upload for {}.", key))
+    }
+
+    pub async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: usize,
+        body: Bytes,
+    ) -> Result<CompletedPart> {
+        let request = S3Request::object(Method::PUT, key)
+            .query("partNumber", part_number)
+            .query("uploadId", upload_id)
+            .body(body);
+        let response = self.send(request).await?;
+        let etag = response
+            .headers
+            .get("etag")
+            .ok_or_else(|| format_err!("No ETag for part {} of {}.", part_number, key))?
+            .to_str()?
+            .to_string();
+        Ok(CompletedPart { part_number, etag })
+    }
+
+    /// `parts` must be sorted by part number.
+    pub async fn complete_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: &[CompletedPart],
+    ) -> Result<()> {
+        let mut body = String::from("<CompleteMultipartUpload>");
+        for part in parts {
+            body.push_str(&format!(
+                "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                part.part_number, part.etag
+            ));
+        }
+        body.push_str("</CompleteMultipartUpload>");
+
+        let request = S3Request::object(Method::POST, key)
+            .query("uploadId", upload_id)
+            .body(Bytes::from(body));
+        let body = self.send(request).await?.body;
+        // S3 can report a failed completion in the body of a 200 response.
+        ensure_no_error_in_body(&body)
+    }
+
+    pub async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> Result<()> {
+        let request = S3Request::object(Method::DELETE, key).query("uploadId", upload_id);
+        self.send(request).await.map(|_| ())
+    }
+
+    async fn send(&self, request: S3Request) -> Result<S3Response> {
+        let mut delays = fixed_retry_strategy(self.retry_delay_ms, self.max_retries);
+        loop {
+            let err = match self.send_once(request.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(err) => err,
+            };
+            match delays.next() {
+                Some(delay) if is_retryable(&err) => {
+                    debug!("{}. Retrying in {} ms..", err, delay.as_millis());
+                    tokio::time::sleep(delay).await;
+                },
+                _ => return Err(err),
+            }
+        }
+    }
+
+    async fn send_once(&self, request: S3Request) -> Result<S3Response> {
+        let canonical_uri = match &request.key {
+            Some(key) => format!(
+                "/{}/{}",
+                utf8_percent_encode(&self.bucket, URI_ENCODE_SET),
+                utf8_percent_encode(key, URI_ENCODE_SET_KEEP_SLASH)
+            ),
+            None => format!("/{}", utf8_percent_encode(&self.bucket, URI_ENCODE_SET)),
+        };
+        let canonical_query = canonical_query_string(&request.query);
+        let mut url = format!(
+            "{}{}",
+            self.endpoint.as_str().trim_end_matches('/'),
+            canonical_uri
+        );
+        if !canonical_query.is_empty() {
+            url.push('?');
+            url.push_str(&canonical_query);
+        }
+
+        let now = Utc::now();
+        let payload_hash = hex::encode(Sha256::digest(&request.body));
+        let mut headers = request.headers;
+        headers.push(("x-amz-content-sha256".to_string(), payload_hash.clone()));
+        headers.push(("x-amz-date".to_string(), amz_date(&now)));
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+        let authorization = authorization_header(
+            &self.credentials,
+            &self.region,
+            &now,
+            &request.method,
+            &host_header(&self.endpoint),
+            &canonical_uri,
+            &canonical_query,
+            &headers,
+            &payload_hash,
+        );
+
+        let mut builder = self.http.request(request.method.clone(), url);
+        for (key, value) in &headers {
+            builder = builder.header(key.as_str(), value.as_str());
+        }
+        let response = builder
+            .header("authorization", authorization)
+            .body(request.body)
+            .send()
+            .await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+        if !status.is_success() {
+            return Err(UnexpectedStatus {
+                method: request.method,
+                uri: canonical_uri,
+                status,
+                body: String::from_utf8_lossy(&body).to_string(),
+            }
+            .into());
+        }
+
+        Ok(S3Response { headers, body })
+    }
+}
+
+fn status_of(err: &anyhow::Error) -> Option<StatusCode> {
+    err.downcast_ref::<UnexpectedStatus>().map(|e| e.status)
+}
+
+/// Server errors, throttling and timeouts are transient, other client errors (e.g., 403 or 404)
+/// are not worth retrying.
+fn is_retryable(err: &anyhow::Error) -> bool {
+    if let Some(status) = status_of(err) {
+        return status.is_server_error()
+            || status == StatusCode::TOO_MANY_REQUESTS
+            || status == StatusCode::REQUEST_TIMEOUT;
+    }
+    // Connection failures (refused, reset, timed out) are transient as well.
+    err.downcast_ref::<reqwest::Error>().map_or(false, |e| {
+        e.is_timeout() || e.is_connect() || e.is_request() || e.is_body()
+    })
+}
+
+fn ensure_no_error_in_body(body: &[u8]) -> Result<()> {
+    let body = String::from_utf8_lossy(body);
+    ensure!(!body.contains("<Error>"), "S3 request failed: {}", body);
+    Ok(())
+}
+
+fn host_header(endpoint: &Url) -> String {
+    let host = endpoint.host_str().unwrap_or_default();
+    match endpoint.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    }
+}
+
+fn amz_date(time: &DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn canonical_query_string(query: &[(String, String)]) -> String {
+    let mut query = query
+        .iter()
+        .map(|(key, value)| {
+            (
+                utf8_percent_encode(key, URI_ENCODE_SET).to_string(),
+                utf8_percent_encode(value, URI_ENCODE_SET).to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    query.sort();
+    query
+        .into_iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC takes keys of any size.");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Computes the value of the `Authorization` header of a request signed with AWS Signature
+/// Version 4. All `headers` and the host are signed.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn authorization_header(
+    credentials: &S3Credentials,
+    region: &str,
+    time: &DateTime<Utc>,
+    method: &Method,
+    host: &str,
+    canonical_uri: &str,
+    canonical_query: &str,
+    headers: &[(String, String)],
+    payload_hash: &str,
+) -> String {
+    let mut canonical_headers = headers
+        .iter()
+        .map(|(key, value)| (key.to_lowercase(), value.trim().to_string()))
+        .chain(std::iter::once(("host".to_string(), host.to_string())))
+        .collect::<Vec<_>>();
+    canonical_headers.sort();
+    let signed_headers = canonical_headers
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method.as_str(),
+        canonical_uri,
+        canonical_query,
+        canonical_headers
+            .iter()
+            .map(|(key, value)| format!("{}:{}\n", key, value))
+            .collect::<String>(),
+        signed_headers,
+        payload_hash,
+    );
+
+    let date = time.format("%Y%m%d").to_string();
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date(time),
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes())),
+    );
+
+    let mut signing_key = hmac_sha256(
+        format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+        date.as_bytes(),
+    );
+    for part in [region, "s3", "aws4_request"] {
+        signing_key = hmac_sha256(&signing_key, part.as_bytes());
+    }
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+/// Extracts the text of all `<tag>` elements. The S3 responses we care about are simple enough
+/// that a full XML parser isn't needed.
+pub(super) fn xml_elements(xml: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+
+    let mut res = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        match rest.find(&close) {
+            Some(end) => {
+                res.push(
+                    rest[..end]
+                        .replace("&lt;", "<")
+                        .replace("&gt;", ">")
+                        .replace("&quot;", "\"")
+                        .replace("&apos;", "'")
+                        .replace("&amp;", "&"),
+                );
+                rest = &rest[end + close.len()..];
+            },
+            None => break,
+        }
+    }
+    res
+}
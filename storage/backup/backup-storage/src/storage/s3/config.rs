@@ -0,0 +1,112 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::utils::error_notes::ErrorNotes;
+use anyhow::{format_err, Result};
+use serde::Deserialize;
+use std::path::Path;
+use tokio::io::AsyncReadExt;
+
+/// The minimum part size accepted by S3 for all but the last part of a multipart upload.
+pub const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct S3Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Only needed for temporary credentials.
+    #[serde(default)]
+    pub session_token: Option<String>,
+}
+
+impl S3Credentials {
+    /// Reads the credentials from the standard `AWS_*` environment variables.
+    pub fn from_env() -> Result<Self> {
+        let var = |key: &str| {
+            std::env::var(key).map_err(|_| {
+                format_err!(
+                    "S3 credentials not configured and env var {} is not set.",
+                    key
+                )
+            })
+        };
+        Ok(Self {
+            access_key_id: var("AWS_ACCESS_KEY_ID")?,
+            secret_access_key: var("AWS_SECRET_ACCESS_KEY")?,
+            session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct S3Config {
+    /// Bucket holding the backups.
+    pub bucket: String,
+    /// Key prefix under which everything is stored, can be empty.
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default = "S3Config::default_region")]
+    pub region: String,
+    /// Endpoint of an S3 compatible object store, like "http://localhost:9000" for a local MinIO.
+    /// Defaults to the AWS S3 endpoint of the region. Requests are always path-style.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Read from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN` env
+    /// vars if not set.
+    #[serde(default)]
+    pub credentials: Option<S3Credentials>,
+    /// Size of each part in multipart uploads and ranged downloads, must be at least
+    /// `MIN_PART_SIZE` for AWS S3.
+    #[serde(default = "S3Config::default_part_size")]
+    pub part_size: usize,
+    /// Maximum number of parts being uploaded or downloaded concurrently for a single file.
+    #[serde(default = "S3Config::default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Number of times a request failing with a server error, throttling or a timeout is
+    /// retried before giving up.
+    #[serde(default = "S3Config::default_max_retries")]
+    pub max_retries: usize,
+    #[serde(default = "S3Config::default_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+}
+
+impl S3Config {
+    fn default_region() -> String {
+        "us-east-1".to_string()
+    }
+
+    fn default_part_size() -> usize {
+        16 * 1024 * 1024
+    }
+
+    fn default_max_concurrency() -> usize {
+        8
+    }
+
+    fn default_max_retries() -> usize {
+        5
+    }
+
+    fn default_retry_delay_ms() -> u64 {
+        1000
+    }
+
+    pub fn endpoint(&self) -> String {
+        self.endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", self.region))
+    }
+
+    pub async fn load_from_file(path: &Path) -> Result<Self> {
+        let path_str = path.to_str().unwrap_or_default();
+        let mut file = tokio::fs::File::open(path).await.err_notes(path_str)?;
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).await.err_notes(path_str)?;
+
+        Ok(serde_yaml::from_slice(&content)?)
+    }
+
+    pub fn load_from_str(content: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(content)?)
+    }
+}
@@ -0,0 +1,573 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+mod client;
+pub mod config;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    storage::{
+        s3::{
+            client::{CompletedPart, S3Client},
+            config::{S3Config, MIN_PART_SIZE},
+        },
+        BackupHandle, BackupHandleRef, BackupStorage, FileHandle, FileHandleRef, ShellSafeName,
+        TextLine,
+    },
+    utils::error_notes::ErrorNotes,
+};
+use anyhow::{ensure, format_err, Result};
+use aptos_logger::prelude::*;
+use async_trait::async_trait;
+use bytes::Bytes;
+use clap::Parser;
+use futures::{
+    future::BoxFuture,
+    ready,
+    stream::{self, BoxStream, FuturesUnordered},
+    task::{Context, Poll},
+    Future, FutureExt, StreamExt, TryStreamExt,
+};
+use serde::{Deserialize, Serialize};
+use sha2_0_10_6::{Digest, Sha256};
+use std::{cmp::min, io, mem, path::PathBuf, pin::Pin, str::FromStr, sync::Arc};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    task::JoinHandle,
+};
+use tokio_util::io::StreamReader;
+
+#[derive(Parser, Clone, Debug, Serialize, Deserialize)]
+pub struct S3Opt {
+    #[clap(long = "config", help = "Config file for the S3 backup storage.")]
+    config: PathBuf,
+}
+
+impl FromStr for S3Opt {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(S3Opt {
+            config: PathBuf::from(s),
+        })
+    }
+}
+
+/// The size and SHA-256 of a file, saved alongside every file written and verified when the
+/// file is read back.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct FileChecksum {
+    size: u64,
+    sha256: String,
+}
+
+impl FileChecksum {
+    fn new(size: u64, hasher: Sha256) -> Self {
+        Self {
+            size,
+            sha256: hex::encode(hasher.finalize()),
+        }
+    }
+}
+
+/// A BackupStorage that talks to S3 (or any S3 compatible object store) directly, without
+/// going through shell commands. See `S3Config`.
+///
+/// Files are uploaded in parts of `part_size` bytes with multipart uploads and downloaded with
+/// parallel ranged reads. A checksum file is saved for every file under `.checksums/` and the
+/// content is verified against it when read. Files without a checksum file (e.g., uploaded by
+/// other tools) are still readable, but not verified.
+pub struct S3 {
+    client: Arc<S3Client>,
+    prefix: String,
+    part_size: usize,
+    max_concurrency: usize,
+}
+
+impl S3 {
+    const CHECKSUMS_DIR: &'static str = ".checksums";
+    const METADATA_BACKUP_DIR: &'static str = "metadata_backup";
+    const METADATA_DIR: &'static str = "metadata";
+
+    pub fn new(config: S3Config) -> Result<Self> {
+        ensure!(
+            config.part_size >= MIN_PART_SIZE,
+            "part_size must be at least {} bytes.",
+            MIN_PART_SIZE
+        );
+        Self::new_with_any_part_size(config)
+    }
+
+    /// Like `new()`, but without the `MIN_PART_SIZE` check, so the tests can exercise
+    /// multipart uploads with small parts against the fake store.
+    fn new_with_any_part_size(config: S3Config) -> Result<Self> {
+        ensure!(config.part_size > 0, "part_size must be positive.");
+        ensure!(
+            config.max_concurrency > 0,
+            "max_concurrency must be positive."
+        );
+
+        Ok(Self {
+            client: Arc::new(S3Client::new(&config)?),
+            prefix: config.prefix.trim_matches('/').to_string(),
+            part_size: config.part_size,
+            max_concurrency: config.max_concurrency,
+        })
+    }
+
+    pub async fn new_with_opt(opt: S3Opt) -> Result<Self> {
+        Self::new(S3Config::load_from_file(&opt.config).await?)
+    }
+
+    fn object_key(&self, file_handle: &FileHandleRef) -> String {
+        if self.prefix.is_empty() {
+            file_handle.to_string()
+        } else {
+            format!("{}/{}", self.prefix, file_handle)
+        }
+    }
+
+    fn checksum_key(&self, file_handle: &FileHandleRef) -> String {
+        self.object_key(&format!("{}/{}", Self::CHECKSUMS_DIR, file_handle))
+    }
+
+    /// Returns `None` if the file has no checksum file.
+    async fn read_checksum(&self, file_handle: &FileHandleRef) -> Result<Option<FileChecksum>> {
+        let content = self
+            .client
+            .get_object_if_exists(&self.checksum_key(file_handle))
+            .await
+            .err_notes(file_handle)?;
+        Ok(match content {
+            Some(content) => Some(serde_json::from_slice(&content)?),
+            None => None,
+        })
+    }
+
+    async fn save_checksum(
+        &self,
+        file_handle: &FileHandleRef,
+        checksum: &FileChecksum,
+    ) -> Result<()> {
+        self.client
+            .put_object(
+                &self.checksum_key(file_handle),
+                serde_json::to_vec(checksum)?.into(),
+            )
+            .await
+    }
+
+    /// Moves a file and its checksum file (if any).
+    async fn move_file(&self, src: &FileHandleRef, dst: &FileHandleRef) -> Result<()> {
+        let has_checksum = self.read_checksum(src).await?.is_some();
+        self.client
+            .copy_object(&self.object_key(src), &self.object_key(dst))
+            .await?;
+        if has_checksum {
+            self.client
+                .copy_object(&self.checksum_key(src), &self.checksum_key(dst))
+                .await?;
+        }
+        self.client.delete_object(&self.object_key(src)).await?;
+        if has_checksum {
+            self.client.delete_object(&self.checksum_key(src)).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BackupStorage for S3 {
+    async fn create_backup(&self, name: &ShellSafeName) -> Result<BackupHandle> {
+        // There are no directories in an object store, nothing to create.
+        Ok(name.to_string())
+    }
+
+    async fn create_for_write(
+        &self,
+        backup_handle: &BackupHandleRef,
+        name: &ShellSafeName,
+    ) -> Result<(FileHandle, Box<dyn AsyncWrite + Send + Unpin>)> {
+        let file_handle = format!("{}/{}", backup_handle, name.as_ref());
+        let upload = Upload {
+            client: self.client.clone(),
+            key: self.object_key(&file_handle),
+            checksum_key: self.checksum_key(&file_handle),
+            max_concurrency: self.max_concurrency,
+            upload_id: None,
+            next_part_number: 1,
+            completed_parts: Vec::new(),
+            in_flight: FuturesUnordered::new(),
+        };
+        Ok((file_handle, Box::new(S3Writer::new(upload, self.part_size))))
+    }
+
+    async fn open_for_read(
+        &self,
+        file_handle: &FileHandleRef,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin>> {
+        let key = self.object_key(file_handle);
+        let checksum = self.read_checksum(file_handle).await?;
+        let size = match &checksum {
+            Some(checksum) => checksum.size,
+            None => {
+                warn!(
+                    file_handle = file_handle,
+                    "No checksum file found, reading without verification.",
+                );
+                self.client
+                    .get_object_size(&key)
+                    .await
+                    .err_notes(file_handle)?
+            },
+        };
+
+        let client = self.client.clone();
+        let part_size = self.part_size as u64;
+        let parts = stream::iter((0..size).step_by(self.part_size))
+            .map(move |start| {
+                let client = client.clone();
+                let key = key.clone();
+                async move {
+                    client
+                        .get_object_range(&key, start..min(start + part_size, size))
+                        .await
+                }
+            })
+            .buffered(self.max_concurrency)
+            .boxed();
+
+        let parts = match checksum {
+            Some(checksum) => verify_checksum(parts, checksum, file_handle.to_string()).boxed(),
+            None => parts,
+        };
+        let stream = parts
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            .boxed();
+        Ok(Box::new(StreamReader::new(stream)))
+    }
+
+    async fn list_metadata_files(&self) -> Result<Vec<FileHandle>> {
+        let root = self.object_key("");
+        let keys = self
+            .client
+            .list_objects(&self.object_key(&format!("{}/", Self::METADATA_DIR)))
+            .await?;
+        keys.into_iter()
+            .map(|key| {
+                key.strip_prefix(&root)
+                    .map(str::to_string)
+                    .ok_or_else(|| format_err!("Listed key {} not under {}.", key, root))
+            })
+            .collect()
+    }
+
+    async fn backup_metadata_file(&self, file_handle: &FileHandleRef) -> Result<()> {
+        let name = file_handle
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| format_err!("cannot extract filename from {}", file_handle))?;
+        let backup_handle = format!("{}/{}", Self::METADATA_BACKUP_DIR, name);
+        self.move_file(file_handle, &backup_handle).await
+    }
+
+    async fn save_metadata_lines(
+        &self,
+        name: &ShellSafeName,
+        lines: &[TextLine],
+    ) -> Result<FileHandle> {
+        let file_handle = format!("{}/{}", Self::METADATA_DIR, name.as_ref());
+        let content = lines
+            .iter()
+            .map(|e| e.as_ref())
+            .collect::<Vec<&str>>()
+            .join("");
+
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        let checksum = FileChecksum::new(content.len() as u64, hasher);
+        self.client
+            .put_object(&self.object_key(&file_handle), content.into())
+            .await
+            .err_notes(&file_handle)?;
+        self.save_checksum(&file_handle, &checksum).await?;
+
+        Ok(file_handle)
+    }
+}
+
+/// Verifies the content of `parts` against the checksum once all of them are read.
+fn verify_checksum(
+    parts: BoxStream<'static, Result<Bytes>>,
+    expected: FileChecksum,
+    file_handle: FileHandle,
+) -> impl futures::Stream<Item = Result<Bytes>> {
+    stream::try_unfold(
+        (parts, Sha256::new(), 0u64),
+        move |(mut parts, mut hasher, mut size)| {
+            let expected = expected.clone();
+            let file_handle = file_handle.clone();
+            async move {
+                match parts.try_next().await? {
+                    Some(bytes) => {
+                        hasher.update(&bytes);
+                        size += bytes.len() as u64;
+                        Ok(Some((bytes, (parts, hasher, size))))
+                    },
+                    None => {
+                        let actual = FileChecksum::new(size, hasher);
+                        ensure!(
+                            actual.size == expected.size && actual.sha256 == expected.sha256,
+                            "Checksum mismatch for {}. expected: {:?}, actual: {:?}",
+                            file_handle,
+                            expected,
+                            actual,
+                        );
+                        Ok(None)
+                    },
+                }
+            }
+        },
+    )
+}
+
+/// State of a file upload. The file is uploaded as a single object if it fits in one part,
+/// otherwise as a multipart upload with up to `max_concurrency` parts in flight.
+struct Upload {
+    client: Arc<S3Client>,
+    key: String,
+    checksum_key: String,
+    max_concurrency: usize,
+    /// Set once the multipart upload is created, on the first full part.
+    upload_id: Option<String>,
+    next_part_number: usize,
+    completed_parts: Vec<CompletedPart>,
+    in_flight: FuturesUnordered<JoinHandle<Result<CompletedPart>>>,
+}
+
+impl Upload {
+    async fn upload_part(mut self, part: Bytes) -> Result<Self> {
+        match self.push_part(part).await {
+            Ok(()) => Ok(self),
+            Err(e) => {
+                self.abort().await;
+                Err(e)
+            },
+        }
+    }
+
+    async fn finish(mut self, last_part: Bytes, checksum: FileChecksum) -> Result<()> {
+        if self.upload_id.is_none() {
+            self.client.put_object(&self.key, last_part).await?;
+        } else if let Err(e) = self.complete(last_part).await {
+            self.abort().await;
+            return Err(e);
+        }
+        // The checksum file is saved last, so a file is only readable if fully uploaded.
+        self.client
+            .put_object(&self.checksum_key, serde_json::to_vec(&checksum)?.into())
+            .await
+    }
+
+    async fn push_part(&mut self, part: Bytes) -> Result<()> {
+        let upload_id = match &self.upload_id {
+            Some(upload_id) => upload_id.clone(),
+            None => {
+                let upload_id = self.client.create_multipart_upload(&self.key).await?;
+                self.upload_id = Some(upload_id.clone());
+                upload_id
+            },
+        };
+        while self.in_flight.len() >= self.max_concurrency {
+            self.join_one().await?;
+        }
+
+        let client = self.client.clone();
+        let key = self.key.clone();
+        let part_number = self.next_part_number;
+        self.next_part_number += 1;
+        self.in_flight.push(tokio::spawn(async move {
+            client
+                .upload_part(&key, &upload_id, part_number, part)
+                .await
+        }));
+        Ok(())
+    }
+
+    async fn join_one(&mut self) -> Result<()> {
+        if let Some(res) = self.in_flight.next().await {
+            self.completed_parts.push(res??);
+        }
+        Ok(())
+    }
+
+    async fn complete(&mut self, last_part: Bytes) -> Result<()> {
+        if !last_part.is_empty() {
+            self.push_part(last_part).await?;
+        }
+        while !self.in_flight.is_empty() {
+            self.join_one().await?;
+        }
+        self.completed_parts.sort_by_key(|part| part.part_number);
+
+        let upload_id = self.upload_id.as_ref().expect("Multipart upload created.");
+        self.client
+            .complete_multipart_upload(&self.key, upload_id, &self.completed_parts)
+            .await
+    }
+
+    /// Best effort clean up of a failed multipart upload.
+    async fn abort(&mut self) {
+        for handle in self.in_flight.iter() {
+            handle.abort();
+        }
+        if let Some(upload_id) = &self.upload_id {
+            if let Err(e) = self
+                .client
+                .abort_multipart_upload(&self.key, upload_id)
+                .await
+            {
+                warn!(
+                    key = ?self.key,
+                    error = ?e,
+                    "Failed to abort multipart upload.",
+                );
+            }
+        }
+    }
+}
+
+enum UploadState {
+    Idle(Upload),
+    UploadingPart(BoxFuture<'static, Result<Upload>>),
+    Finishing(BoxFuture<'static, Result<()>>),
+    Finished,
+    Failed,
+}
+
+/// Buffers written bytes into parts and uploads them. The file is only complete once
+/// `shutdown()` succeeds.
+struct S3Writer {
+    state: UploadState,
+    buf: Vec<u8>,
+    part_size: usize,
+    hasher: Sha256,
+    size: u64,
+}
+
+impl S3Writer {
+    fn new(upload: Upload, part_size: usize) -> Self {
+        Self {
+            state: UploadState::Idle(upload),
+            buf: Vec::new(),
+            part_size,
+            hasher: Sha256::new(),
+            size: 0,
+        }
+    }
+
+    /// Waits for the previous part (if any) to be handed over.
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if let UploadState::UploadingPart(fut) = &mut self.state {
+            match ready!(fut.as_mut().poll(cx)) {
+                Ok(upload) => self.state = UploadState::Idle(upload),
+                Err(e) => {
+                    self.state = UploadState::Failed;
+                    return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, e)));
+                },
+            }
+        }
+
+        match self.state {
+            UploadState::Idle(_) => Poll::Ready(Ok(())),
+            _ => Poll::Ready(Err(io::ErrorKind::BrokenPipe.into())),
+        }
+    }
+
+    fn take_upload(&mut self) -> Upload {
+        match mem::replace(&mut self.state, UploadState::Failed) {
+            UploadState::Idle(upload) => upload,
+            _ => unreachable!("Only called when idle."),
+        }
+    }
+}
+
+impl AsyncWrite for S3Writer {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        let this = self.get_mut();
+        ready!(this.poll_idle(cx))?;
+
+        let len = min(buf.len(), this.part_size - this.buf.len());
+        this.buf.extend_from_slice(&buf[..len]);
+        this.hasher.update(&buf[..len]);
+        this.size += len as u64;
+
+        if this.buf.len() == this.part_size {
+            let part = Bytes::from(mem::take(&mut this.buf));
+            let upload = this.take_upload();
+            this.state = UploadState::UploadingPart(upload.upload_part(part).boxed());
+        }
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        self.get_mut().poll_idle(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        let this = self.get_mut();
+        if !matches!(
+            this.state,
+            UploadState::Finishing(_) | UploadState::Finished
+        ) {
+            ready!(this.poll_idle(cx))?;
+            let upload = this.take_upload();
+            let checksum = FileChecksum::new(this.size, mem::take(&mut this.hasher));
+            let last_part = Bytes::from(mem::take(&mut this.buf));
+            this.state = UploadState::Finishing(upload.finish(last_part, checksum).boxed());
+        }
+
+        if let UploadState::Finishing(fut) = &mut this.state {
+            let res = ready!(fut.as_mut().poll(cx));
+            this.state = match res {
+                Ok(()) => UploadState::Finished,
+                Err(_) => UploadState::Failed,
+            };
+            res.map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for S3Writer {
+    /// Aborts the multipart upload if the writer is dropped before `shutdown()`, otherwise the
+    /// uploaded parts would linger (and be billed) until a bucket lifecycle rule cleans them up.
+    fn drop(&mut self) {
+        let abort = match mem::replace(&mut self.state, UploadState::Failed) {
+            UploadState::Idle(mut upload) if upload.upload_id.is_some() => {
+                async move { upload.abort().await }.boxed()
+            },
+            // A failed part already aborts the upload.
+            UploadState::UploadingPart(fut) => async move {
+                if let Ok(mut upload) = fut.await {
+                    upload.abort().await
+                }
+            }
+            .boxed(),
+            _ => return,
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(abort);
+            },
+            Err(_) => warn!("Multipart upload dropped outside of a tokio runtime, not aborted."),
+        }
+    }
+}
@@ -5,7 +5,7 @@
 use aptos_logger::error;
 use std::fmt::{Debug, Display};
 
-pub(crate) trait ErrorNotes<T, E: Display, N: Debug> {
+pub trait ErrorNotes<T, E: Display, N: Debug> {
     fn err_notes(self, notes: N) -> Result<T, E>;
 }
 
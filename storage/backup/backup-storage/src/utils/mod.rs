@@ -0,0 +1,31 @@
+// Copyright © Aptos Foundation
+// Parts of the project are originally copyright © Meta Platforms, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod error_notes;
+pub mod read_record_bytes;
+pub mod storage_ext;
+pub mod stream;
+
+use anyhow::{anyhow, Result};
+use std::path::Path;
+use tokio::fs::metadata;
+
+// TODO: use Path::exists() when Rust 1.5 stabilizes.
+pub(crate) async fn path_exists(path: &Path) -> bool {
+    metadata(&path).await.is_ok()
+}
+
+pub(crate) trait PathToString {
+    fn path_to_string(&self) -> Result<String>;
+}
+
+impl<T: AsRef<Path>> PathToString for T {
+    fn path_to_string(&self) -> Result<String> {
+        self.as_ref()
+            .to_path_buf()
+            .into_os_string()
+            .into_string()
+            .map_err(|s| anyhow!("into_string failed for OsString '{:?}'", s))
+    }
+}
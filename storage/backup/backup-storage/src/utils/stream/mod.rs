@@ -10,7 +10,7 @@ mod try_buffered_x;
 use crate::utils::stream::{buffered_x::BufferedX, try_buffered_x::TryBufferedX};
 use futures::{Future, Stream, TryFuture, TryStream};
 
-pub(crate) trait StreamX: Stream {
+pub trait StreamX: Stream {
     fn buffered_x(self, n: usize, max_in_progress: usize) -> BufferedX<Self>
     where
         Self::Item: Future,
@@ -22,7 +22,7 @@ pub(crate) trait StreamX: Stream {
 
 impl<T: ?Sized> StreamX for T where T: Stream {}
 
-pub(crate) trait TryStreamX: TryStream {
+pub trait TryStreamX: TryStream {
     fn try_buffered_x(self, n: usize, max_in_progress: usize) -> TryBufferedX<Self>
     where
         Self::Ok: TryFuture<Error = Self::Error>,
@@ -7,6 +7,9 @@ use aptos_backup_cli::{
     backup_types::{
         epoch_ending::backup::{EpochEndingBackupController, EpochEndingBackupOpt},
         state_snapshot::backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
+        state_snapshot_diff::backup::{
+            StateSnapshotDiffBackupController, StateSnapshotDiffBackupOpt,
+        },
         transaction::backup::{TransactionBackupController, TransactionBackupOpt},
     },
     coordinators::{
@@ -98,6 +101,12 @@ enum BackupType {
         #[clap[flatten]]
         storage: DBToolStorageOpt,
     },
+    StateSnapshotDiff {
+        #[clap(flatten)]
+        opt: StateSnapshotDiffBackupOpt,
+        #[clap[flatten]]
+        storage: DBToolStorageOpt,
+    },
     Transaction {
         #[clap(flatten)]
         opt: TransactionBackupOpt,
@@ -193,6 +202,16 @@ impl Command {
                         .run()
                         .await?;
                     },
+                    BackupType::StateSnapshotDiff { opt, storage } => {
+                        StateSnapshotDiffBackupController::new(
+                            opt,
+                            global_opt,
+                            client,
+                            storage.init_storage().await?,
+                        )?
+                        .run()
+                        .await?;
+                    },
                     BackupType::Transaction { opt, storage } => {
                         TransactionBackupController::new(
                             opt,
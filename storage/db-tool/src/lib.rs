@@ -35,6 +35,8 @@ pub enum DBTool {
 
     #[clap(subcommand)]
     Restore(restore::Command),
+
+    Verify(db_debugger::verify::Cmd),
 }
 
 impl DBTool {
@@ -50,6 +52,7 @@ impl DBTool {
                 ret
             },
             DBTool::Restore(cmd) => cmd.run().await,
+            DBTool::Verify(cmd) => Ok(cmd.run()?),
         }
     }
 }
@@ -30,6 +30,18 @@ fn test_various_cmd_parsing() {
         "--local-fs-dir",
         ".",
     ]);
+    run_cmd(&[
+        "aptos-db-tool",
+        "backup",
+        "oneoff",
+        "state-snapshot-diff",
+        "--state-snapshot-epoch",
+        "10",
+        "--base-state-manifest",
+        "state_epoch_5_ver_500.abcd/state.manifest",
+        "--local-fs-dir",
+        ".",
+    ]);
     run_cmd(&[
         "aptos-db-tool",
         "backup",
@@ -64,6 +76,17 @@ fn test_various_cmd_parsing() {
         "--start-version",
         "Max",
     ]);
+    run_cmd(&[
+        "aptos-db-tool",
+        "verify",
+        "--db-dir",
+        ".",
+        "--start-version",
+        "100",
+        "--num-versions",
+        "1000",
+        "--skip-indices",
+    ]);
 }
 
 fn run_cmd(args: &[&str]) {
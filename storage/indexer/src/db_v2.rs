@@ -7,16 +7,20 @@
 /// and this file will be moved to /ecosystem/indexer-grpc/indexer-grpc-table-info.
 use crate::{
     metadata::{MetadataKey, MetadataValue},
-    schema::{indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema},
+    schema::{
+        event_by_type::EventByTypeSchema, indexer_metadata::IndexerMetadataSchema,
+        table_info::TableInfoSchema,
+    },
 };
 use aptos_logger::info;
-use aptos_schemadb::{SchemaBatch, DB};
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_storage_interface::{
-    db_other_bail as bail, state_view::DbStateViewAtVersion, AptosDbError, DbReader, Result,
+    db_other_bail as bail, state_view::DbStateViewAtVersion, AptosDbError, DbReader, Order, Result,
 };
 use aptos_types::{
     access_path::Path,
     account_address::AccountAddress,
+    contract_event::ContractEvent,
     state_store::{
         state_key::{inner::StateKeyInner, StateKey},
         table::{TableHandle, TableInfo},
@@ -130,6 +134,86 @@ impl IndexerAsyncV2 {
         Ok(())
     }
 
+    /// Indexes the events emitted by a contiguous range of transactions starting at
+    /// `first_version` by their type, so they can be looked up via `get_events_by_type`.
+    pub fn index_events_by_type(
+        &self,
+        first_version: Version,
+        events: &[&[ContractEvent]],
+    ) -> Result<()> {
+        let mut batch = SchemaBatch::new();
+        for (offset, txn_events) in events.iter().enumerate() {
+            let version = first_version + offset as Version;
+            for (index, event) in txn_events.iter().enumerate() {
+                batch.put::<EventByTypeSchema>(
+                    &(event.type_tag().clone(), version, index as u64),
+                    &(),
+                )?;
+            }
+        }
+        self.db.write_schemas(batch)?;
+        Ok(())
+    }
+
+    /// Returns the (version, index within the transaction) of events of the given type, starting
+    /// at `start_version` and going in the given order. Only versions that are both at or below
+    /// `ledger_version` and already processed by the indexer are returned.
+    ///
+    /// Up to `limit` events are returned, except that the result is extended to include all the
+    /// matching events of the last transaction.
+    pub fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        order: Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(Version, u64)>> {
+        // Versions after the latest processed version aren't indexed yet
+        let latest_processed_version = match self
+            .db
+            .get::<IndexerMetadataSchema>(&MetadataKey::LatestVersion)?
+        {
+            Some(metadata) if limit > 0 => metadata.expect_version(),
+            _ => return Ok(vec![]),
+        };
+        let max_version = std::cmp::min(ledger_version, latest_processed_version);
+
+        let iter = match order {
+            Order::Ascending => {
+                let mut iter = self.db.iter::<EventByTypeSchema>(ReadOptions::default())?;
+                iter.seek(&(type_tag.clone(), start_version, 0))?;
+                iter
+            },
+            Order::Descending => {
+                let mut iter = self
+                    .db
+                    .rev_iter::<EventByTypeSchema>(ReadOptions::default())?;
+                iter.seek_for_prev(&(
+                    type_tag.clone(),
+                    std::cmp::min(start_version, max_version),
+                    u64::MAX,
+                ))?;
+                iter
+            },
+        };
+
+        let mut result: Vec<(Version, u64)> = vec![];
+        for item in iter {
+            let ((event_type, version, index), ()) = item?;
+            if &event_type != type_tag || version > max_version {
+                break;
+            }
+            // Never stop in the middle of a transaction, so that the caller can continue from
+            // the next version without skipping any events
+            if result.len() as u64 >= limit && result.last().map(|(v, _)| *v) != Some(version) {
+                break;
+            }
+            result.push((version, index));
+        }
+        Ok(result)
+    }
+
     pub fn update_next_version(&self, end_version: u64) -> Result<()> {
         self.db.put::<IndexerMetadataSchema>(
             &MetadataKey::LatestVersion,
@@ -371,3 +455,122 @@ impl<'a, R: ModuleResolver> TableInfoParser<'a, R> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_ops::open_db;
+    use aptos_config::config::RocksdbConfig;
+    use aptos_temppath::TempPath;
+    use std::str::FromStr;
+
+    fn event(type_tag: &TypeTag) -> ContractEvent {
+        ContractEvent::new_v2(type_tag.clone(), vec![])
+    }
+
+    #[test]
+    fn test_get_events_by_type() {
+        let tmp_dir = TempPath::new();
+        let db = open_db(tmp_dir.path(), &RocksdbConfig::default()).unwrap();
+        let indexer = IndexerAsyncV2::new(db).unwrap();
+        let type_a = TypeTag::from_str("0x1::a::A").unwrap();
+        let type_b = TypeTag::from_str("0x1::b::B").unwrap();
+
+        // Index the events of versions 10 to 14
+        let events = vec![
+            vec![event(&type_a), event(&type_b)],
+            vec![],
+            vec![event(&type_a), event(&type_a), event(&type_b)],
+            vec![event(&type_b)],
+            vec![event(&type_a)],
+        ];
+        let events: Vec<&[ContractEvent]> = events.iter().map(Vec::as_slice).collect();
+        indexer.index_events_by_type(10, &events).unwrap();
+
+        // Nothing is returned before the versions are marked as processed
+        assert!(indexer
+            .get_events_by_type(&type_a, 0, Order::Ascending, 10, 14)
+            .unwrap()
+            .is_empty());
+
+        // Only the processed versions are returned
+        indexer.update_next_version(13).unwrap();
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_a, 0, Order::Ascending, 10, 14)
+                .unwrap(),
+            vec![(10, 0), (12, 0), (12, 1)]
+        );
+        indexer.update_next_version(15).unwrap();
+
+        // Events of each type are indexed separately, ordered by version and index
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_a, 0, Order::Ascending, 10, 14)
+                .unwrap(),
+            vec![(10, 0), (12, 0), (12, 1), (14, 0)]
+        );
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_b, 0, Order::Ascending, 10, 14)
+                .unwrap(),
+            vec![(10, 1), (12, 2), (13, 0)]
+        );
+        assert!(indexer
+            .get_events_by_type(&TypeTag::Bool, 0, Order::Ascending, 10, 14)
+            .unwrap()
+            .is_empty());
+
+        // Pages start at the given version and never end in the middle of a transaction
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_a, 11, Order::Ascending, 1, 14)
+                .unwrap(),
+            vec![(12, 0), (12, 1)]
+        );
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_a, 0, Order::Ascending, 2, 14)
+                .unwrap(),
+            vec![(10, 0), (12, 0), (12, 1)]
+        );
+        assert!(indexer
+            .get_events_by_type(&type_a, 0, Order::Ascending, 0, 14)
+            .unwrap()
+            .is_empty());
+
+        // Descending pages start at the given version and go backwards
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_a, 14, Order::Descending, 1, 14)
+                .unwrap(),
+            vec![(14, 0)]
+        );
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_a, 14, Order::Descending, 2, 14)
+                .unwrap(),
+            vec![(14, 0), (12, 1), (12, 0)]
+        );
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_b, 12, Order::Descending, 10, 14)
+                .unwrap(),
+            vec![(12, 2), (10, 1)]
+        );
+
+        // Versions after the ledger version are not returned
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_a, 0, Order::Ascending, 10, 13)
+                .unwrap(),
+            vec![(10, 0), (12, 0), (12, 1)]
+        );
+        assert_eq!(
+            indexer
+                .get_events_by_type(&type_a, u64::MAX, Order::Descending, 10, 13)
+                .unwrap(),
+            vec![(12, 1), (12, 0), (10, 0)]
+        );
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::db_v2::IndexerAsyncV2;
+use aptos_storage_interface::{Order, Result};
+use aptos_types::transaction::Version;
+use move_core_types::language_storage::TypeTag;
+
+/// Event by type reader is a thin interface for services that look up events by their Move type,
+/// via the index kept in the standalone indexer db. Services that serve events by type need to
+/// acquire this reader in the FN bootstrapping stage.
+pub trait EventByTypeReader: Send + Sync {
+    /// Returns the (version, index within the transaction) of events of the given type.
+    /// See [`IndexerAsyncV2::get_events_by_type`].
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        order: Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(Version, u64)>>;
+}
+
+impl EventByTypeReader for IndexerAsyncV2 {
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        order: Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(Version, u64)>> {
+        self.get_events_by_type(type_tag, start_version, order, limit, ledger_version)
+    }
+}
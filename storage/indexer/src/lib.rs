@@ -5,6 +5,7 @@
 mod db;
 pub mod db_ops;
 pub mod db_v2;
+pub mod event_by_type_reader;
 mod metadata;
 mod schema;
 pub mod table_info_reader;
@@ -43,10 +43,11 @@ mod executed_trees;
 mod metrics;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod mock;
+pub mod retained_transaction;
 pub mod state_delta;
 pub mod state_view;
 
-use crate::state_delta::StateDelta;
+use crate::{retained_transaction::RetainedTransaction, state_delta::StateDelta};
 use aptos_scratchpad::SparseMerkleTree;
 pub use aptos_types::block_info::BlockHeight;
 use aptos_types::state_store::state_key::prefix::StateKeyPrefix;
@@ -103,6 +104,23 @@ pub enum Order {
     Descending,
 }
 
+/// The proof of a state value read by
+/// `DbReader::get_state_value_with_proof_if_available_by_version_ext`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StateValueProof {
+    /// Proves the value against the state root hash at the version.
+    Available(SparseMerkleProofExt),
+    /// The state Merkle tree at the version is pruned, the value is read from the state KV DB
+    /// alone.
+    Unavailable,
+}
+
+impl StateValueProof {
+    pub fn is_available(&self) -> bool {
+        matches!(self, Self::Available(_))
+    }
+}
+
 macro_rules! delegate_read {
     ($(
         $(#[$($attr:meta)*])*
@@ -321,6 +339,36 @@ pub trait DbReader: Send + Sync {
             ledger_version: Version,
         ) -> Result<AccountTransactionsWithProof>;
 
+        /// Returns the transaction at `version` if it's older than the ledger prune window but
+        /// kept by the ledger pruner. Returns `None` for any other transaction, including the ones
+        /// within the window.
+        fn get_retained_transaction_by_version(
+            &self,
+            version: Version,
+            ledger_version: Version,
+        ) -> Result<Option<RetainedTransaction>>;
+
+        /// Returns the version of the transaction with `hash`, if it's committed no later than
+        /// `ledger_version` and not pruned.
+        fn get_transaction_version_by_hash(
+            &self,
+            hash: HashValue,
+            ledger_version: Version,
+        ) -> Result<Option<Version>>;
+
+        /// Returns the transactions sent by an account with `address` from sequence number
+        /// `seq_num` that are older than the ledger prune window but kept by the ledger pruner,
+        /// no more than `limit` of them. Stops at the first transaction within the window, which
+        /// can be read with `get_account_transactions`. The sequence numbers returned can have
+        /// gaps where transactions are pruned.
+        fn get_retained_account_transactions(
+            &self,
+            address: AccountAddress,
+            seq_num: u64,
+            limit: u64,
+            ledger_version: Version,
+        ) -> Result<Vec<RetainedTransaction>>;
+
         /// Returns proof of new state for a given ledger info with signatures relative to version known
         /// to client
         fn get_state_proof_with_ledger_info(
@@ -376,6 +424,17 @@ pub trait DbReader: Send + Sync {
             version: Version,
         ) -> Result<(Option<StateValue>, SparseMerkleProofExt)>;
 
+        /// Gets a state value by state key at version, along with the proof if the state Merkle
+        /// tree at the version is still there. Unlike `get_state_value_with_proof_by_version_ext`,
+        /// this doesn't fail once the state Merkle pruner removed the tree, but serves the value
+        /// from the state KV DB with `StateValueProof::Unavailable`, as long as the state KV DB
+        /// still has it.
+        fn get_state_value_with_proof_if_available_by_version_ext(
+            &self,
+            state_key: &StateKey,
+            version: Version,
+        ) -> Result<(Option<StateValue>, StateValueProof)>;
+
         /// Gets the latest ExecutedTrees no matter if db has been bootstrapped.
         /// Used by the Db-bootstrapper.
         fn get_latest_executed_trees(&self) -> Result<ExecutedTrees>;
@@ -0,0 +1,22 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::HashValue;
+use aptos_types::{
+    contract_event::ContractEvent,
+    transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
+};
+
+/// A transaction kept by the ledger pruner beyond the prune window. The transaction accumulator
+/// is pruned regardless, so it comes without proofs, but with the accumulator root hash at its
+/// version recorded when the rest of its version was pruned.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetainedTransaction {
+    pub version: Version,
+    pub transaction: Transaction,
+    pub info: TransactionInfo,
+    pub events: Vec<ContractEvent>,
+    pub write_set: WriteSet,
+    pub accumulator_root_hash: HashValue,
+}
@@ -2,7 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::DbReader;
+use crate::{DbReader, StateValueProof};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_types::{
     ledger_info::LedgerInfo,
@@ -28,8 +28,19 @@ impl DbStateView {
             if let Some(root_hash) = self.verify_against_state_root_hash {
                 let (value, proof) = self
                     .db
-                    .get_state_value_with_proof_by_version(key, version)?;
-                proof.verify(root_hash, CryptoHash::hash(key), value.as_ref())?;
+                    .get_state_value_with_proof_if_available_by_version_ext(key, version)?;
+                match proof {
+                    StateValueProof::Available(proof) => {
+                        proof.verify(root_hash, CryptoHash::hash(key), value.as_ref())?
+                    },
+                    // The value is still there, but can't be verified.
+                    StateValueProof::Unavailable => {
+                        return Err(StateviewError::Other(format!(
+                            "Proof of {:?} at version {} is unavailable, the state Merkle tree is pruned.",
+                            key, version
+                        )))
+                    },
+                }
                 value
             } else {
                 self.db.get_state_value_by_version(key, version)?
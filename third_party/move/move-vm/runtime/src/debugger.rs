@@ -0,0 +1,91 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hooks which allow an external debugger (e.g., a Debug Adapter Protocol server) to stop the
+//! interpreter before an instruction is executed and to inspect the call stack.
+//!
+//! A hook is installed process-wide via [`set_debug_hook`]. While a hook is installed, the
+//! interpreter asks it before every instruction whether execution should stop, and if so hands
+//! it a snapshot of all frames. The hook blocks for as long as execution should stay suspended.
+
+use move_core_types::{language_storage::ModuleId, value::MoveValue};
+use once_cell::sync::Lazy;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
+/// The location of an instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeLocation {
+    /// `None` for scripts.
+    pub module_id: Option<ModuleId>,
+    pub function_name: String,
+    pub pc: u16,
+}
+
+/// A local variable (or parameter) of a frame.
+#[derive(Clone, Debug)]
+pub struct LocalSnapshot {
+    /// Index of the local in the function, parameters come first.
+    pub index: usize,
+    /// The instantiated type of the local, e.g. `0x1::string::String` or `&mut u64`.
+    pub type_name: String,
+    /// The value of the local, decorated with field names. References are dereferenced. `None`
+    /// if the local has been moved out or is not yet assigned.
+    pub value: Option<MoveValue>,
+}
+
+/// A frame on the call stack.
+#[derive(Clone, Debug)]
+pub struct FrameSnapshot {
+    /// For callers, the location of the call instruction.
+    pub location: CodeLocation,
+    pub locals: Vec<LocalSnapshot>,
+}
+
+pub trait DebugHook: Send {
+    /// Called before an instruction is executed. `depth` is the number of frames on the call
+    /// stack, including the current one.
+    fn should_stop(
+        &mut self,
+        module_id: Option<&ModuleId>,
+        function_name: &str,
+        pc: u16,
+        depth: usize,
+    ) -> bool;
+
+    /// Called if `should_stop` returned true, with frames ordered from the outermost to the
+    /// current one. Execution resumes once this returns.
+    fn on_stop(&mut self, frames: Vec<FrameSnapshot>);
+}
+
+static HOOK_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+static HOOK: Lazy<Mutex<Option<Box<dyn DebugHook>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Installs the given hook, replacing any previous one, or removes it if `None` is passed.
+pub fn set_debug_hook(hook: Option<Box<dyn DebugHook>>) {
+    let mut guard = HOOK.lock().unwrap();
+    HOOK_INSTALLED.store(hook.is_some(), Ordering::Release);
+    *guard = hook;
+}
+
+pub(crate) fn is_hook_installed() -> bool {
+    HOOK_INSTALLED.load(Ordering::Acquire)
+}
+
+pub(crate) fn on_instruction(
+    module_id: Option<&ModuleId>,
+    function_name: &str,
+    pc: u16,
+    depth: usize,
+    snapshot: impl FnOnce() -> Vec<FrameSnapshot>,
+) {
+    let mut guard = HOOK.lock().unwrap();
+    if let Some(hook) = guard.as_mut() {
+        if hook.should_stop(module_id, function_name, pc, depth) {
+            hook.on_stop(snapshot());
+        }
+    }
+}
@@ -1061,6 +1061,32 @@ impl Interpreter {
         Ok(())
     }
 
+    /// Passes the current location to the installed debug hook and, if it decides to stop,
+    /// snapshots of all frames.
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    fn debug_hook_on_instruction(&self, current_frame: &Frame, resolver: &Resolver) {
+        crate::debugger::on_instruction(
+            current_frame.function.module_id(),
+            current_frame.function.name(),
+            current_frame.pc,
+            self.call_stack.0.len() + 1,
+            || {
+                let mut frames = self
+                    .call_stack
+                    .0
+                    .iter()
+                    .map(|frame| {
+                        frame.debug_snapshot(
+                            &frame.resolver(resolver.loader(), resolver.module_store()),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                frames.push(current_frame.debug_snapshot(resolver));
+                frames
+            },
+        )
+    }
+
     /// Generate a string which is the status of the interpreter: call stack, current bytecode
     /// stream, locals and operand stack.
     ///
@@ -2164,6 +2190,11 @@ impl Frame {
                     interpreter
                 );
 
+                #[cfg(any(debug_assertions, feature = "debugging"))]
+                if crate::debugger::is_hook_installed() {
+                    interpreter.debug_hook_on_instruction(self, resolver);
+                }
+
                 fail_point!("move_vm::interpreter_loop", |_| {
                     Err(
                         PartialVMError::new(StatusCode::VERIFIER_INVARIANT_VIOLATION).with_message(
@@ -2885,6 +2916,77 @@ impl Frame {
         &self.ty_args
     }
 
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    fn debug_snapshot(&self, resolver: &Resolver) -> crate::debugger::FrameSnapshot {
+        let locals = self
+            .function
+            .local_types()
+            .iter()
+            .enumerate()
+            .map(|(index, ty)| {
+                let ty = resolver.subst(ty, &self.ty_args).ok();
+                crate::debugger::LocalSnapshot {
+                    index,
+                    type_name: ty
+                        .as_ref()
+                        .map(|ty| Self::debug_type_name(ty, resolver))
+                        .unwrap_or_else(|| "?".to_string()),
+                    value: ty.and_then(|ty| self.debug_local_value(index, &ty, resolver)),
+                }
+            })
+            .collect();
+        crate::debugger::FrameSnapshot {
+            location: crate::debugger::CodeLocation {
+                module_id: self.function.module_id().cloned(),
+                function_name: self.function.name().to_string(),
+                pc: self.pc,
+            },
+            locals,
+        }
+    }
+
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    fn debug_type_name(ty: &Type, resolver: &Resolver) -> String {
+        match ty {
+            Type::Reference(inner) => format!("&{}", Self::debug_type_name(inner, resolver)),
+            Type::MutableReference(inner) => {
+                format!("&mut {}", Self::debug_type_name(inner, resolver))
+            },
+            _ => resolver
+                .loader()
+                .type_to_type_tag(ty)
+                .map(|tag| tag.to_canonical_string())
+                .unwrap_or_else(|_| "?".to_string()),
+        }
+    }
+
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    fn debug_local_value(
+        &self,
+        index: usize,
+        ty: &Type,
+        resolver: &Resolver,
+    ) -> Option<move_core_types::value::MoveValue> {
+        if self.locals.is_invalid(index).ok()? {
+            return None;
+        }
+        let value = self.locals.copy_loc(index).ok()?;
+        let (value, ty) = match ty {
+            Type::Reference(inner) | Type::MutableReference(inner) => (
+                value.value_as::<Reference>().ok()?.read_ref().ok()?,
+                inner.as_ref(),
+            ),
+            _ => (value, ty),
+        };
+        let layout = resolver.type_to_type_layout(ty).ok()?;
+        let annotated_layout = resolver.type_to_fully_annotated_layout(ty).ok()?;
+        Some(
+            value
+                .try_as_move_value(&layout)?
+                .decorate(&annotated_layout),
+        )
+    }
+
     fn resolver<'a>(
         &self,
         loader: &'a Loader,
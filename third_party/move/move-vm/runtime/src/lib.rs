@@ -27,5 +27,7 @@ pub mod module_traversal;
 // Only include debugging functionality in debug builds
 #[cfg(any(debug_assertions, feature = "debugging"))]
 mod debug;
+#[cfg(any(debug_assertions, feature = "debugging"))]
+pub mod debugger;
 
 mod access_control;
@@ -3825,16 +3825,14 @@ use move_core_types::value::{MoveStruct, MoveValue};
 
 impl ValueImpl {
     pub fn as_move_value(&self, layout: &MoveTypeLayout) -> MoveValue {
-        use MoveTypeLayout as L;
+        self.try_as_move_value(layout)
+            .unwrap_or_else(|| panic!("Cannot convert value {:?} as {:?}", self, layout))
+    }
 
-        if let L::Native(kind, layout) = layout {
-            panic!(
-                "impossible to get native layout ({:?}) with {}",
-                kind, layout
-            )
-        }
+    fn try_as_move_value(&self, layout: &MoveTypeLayout) -> Option<MoveValue> {
+        use MoveTypeLayout as L;
 
-        match (layout, &self) {
+        Some(match (layout, &self) {
             (L::U8, ValueImpl::U8(x)) => MoveValue::U8(*x),
             (L::U16, ValueImpl::U16(x)) => MoveValue::U16(*x),
             (L::U32, ValueImpl::U32(x)) => MoveValue::U32(*x),
@@ -3847,7 +3845,7 @@ impl ValueImpl {
             (L::Struct(struct_layout), ValueImpl::Container(Container::Struct(r))) => {
                 let mut fields = vec![];
                 for (v, field_layout) in r.borrow().iter().zip(struct_layout.fields().iter()) {
-                    fields.push(v.as_move_value(field_layout));
+                    fields.push(v.try_as_move_value(field_layout)?);
                 }
                 MoveValue::Struct(MoveStruct::new(fields))
             },
@@ -3866,27 +3864,22 @@ impl ValueImpl {
                 Container::Vec(r) => r
                     .borrow()
                     .iter()
-                    .map(|v| v.as_move_value(inner_layout.as_ref()))
-                    .collect(),
-                Container::Struct(_) => {
-                    panic!("got struct container when converting vec")
-                },
-                Container::Locals(_) => panic!("got locals container when converting vec"),
+                    .map(|v| v.try_as_move_value(inner_layout.as_ref()))
+                    .collect::<Option<_>>()?,
+                Container::Struct(_) | Container::Locals(_) => return None,
             }),
 
             (L::Signer, ValueImpl::Container(Container::Struct(r))) => {
                 let v = r.borrow();
-                if v.len() != 1 {
-                    panic!("Unexpected signer layout: {:?}", v);
-                }
-                match &v[0] {
-                    ValueImpl::Address(a) => MoveValue::Signer(*a),
-                    v => panic!("Unexpected non-address while converting signer: {:?}", v),
+                match v.as_slice() {
+                    [ValueImpl::Address(a)] => MoveValue::Signer(*a),
+                    _ => return None,
                 }
             },
 
-            (layout, val) => panic!("Cannot convert value {:?} as {:?}", val, layout),
-        }
+            // Native layouts (e.g., of delayed fields) cannot be converted.
+            _ => return None,
+        })
     }
 }
 
@@ -3895,4 +3888,10 @@ impl Value {
     pub fn as_move_value(&self, layout: &MoveTypeLayout) -> MoveValue {
         self.0.as_move_value(layout)
     }
+
+    /// Like `as_move_value`, but returns `None` instead of panicking if the value does not
+    /// match the layout.
+    pub fn try_as_move_value(&self, layout: &MoveTypeLayout) -> Option<MoveValue> {
+        self.0.try_as_move_value(layout)
+    }
 }
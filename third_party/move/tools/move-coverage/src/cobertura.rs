@@ -0,0 +1,230 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Writes coverage in the Cobertura XML format, with a package per Move package and a class per
+//! module.
+
+#![forbid(unsafe_code)]
+
+use crate::line_coverage::{num_lines_covered, LineHits, ModuleLineCoverage};
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+/// Writes the coverage of `modules`, all from the Move package `package_name`. Source file names
+/// are relative to `source_root` when under it.
+pub fn write_cobertura<W: Write>(
+    modules: &[ModuleLineCoverage],
+    package_name: &str,
+    source_root: &Path,
+    timestamp_secs: u64,
+    writer: &mut W,
+) -> io::Result<()> {
+    let lines_valid: usize = modules.iter().map(|module| module.lines.len()).sum();
+    let lines_covered: usize = modules.iter().map(|m| m.num_lines_covered()).sum();
+    let branches_valid: usize = modules.iter().map(|module| module.branches.len()).sum();
+    let branches_covered: usize = modules.iter().map(|m| m.num_branches_covered()).sum();
+    let line_rate = rate(lines_covered, lines_valid);
+    let branch_rate = rate(branches_covered, branches_valid);
+
+    writeln!(writer, r#"<?xml version="1.0" ?>"#)?;
+    writeln!(
+        writer,
+        r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+    )?;
+    writeln!(
+        writer,
+        r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="{}" timestamp="{}">"#,
+        line_rate,
+        branch_rate,
+        lines_covered,
+        lines_valid,
+        branches_covered,
+        branches_valid,
+        env!("CARGO_PKG_VERSION"),
+        timestamp_secs,
+    )?;
+    writeln!(writer, "  <sources>")?;
+    writeln!(
+        writer,
+        "    <source>{}</source>",
+        escape(&source_root.display().to_string())
+    )?;
+    writeln!(writer, "  </sources>")?;
+    writeln!(writer, "  <packages>")?;
+    writeln!(
+        writer,
+        r#"    <package name="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+        escape(package_name),
+        line_rate,
+        branch_rate,
+    )?;
+    writeln!(writer, "      <classes>")?;
+    for module in modules {
+        write_class(module, source_root, writer)?;
+    }
+    writeln!(writer, "      </classes>")?;
+    writeln!(writer, "    </package>")?;
+    writeln!(writer, "  </packages>")?;
+    writeln!(writer, "</coverage>")
+}
+
+fn write_class<W: Write>(
+    module: &ModuleLineCoverage,
+    source_root: &Path,
+    writer: &mut W,
+) -> io::Result<()> {
+    let filename = module
+        .source_path
+        .strip_prefix(source_root)
+        .unwrap_or(&module.source_path);
+    writeln!(
+        writer,
+        r#"        <class name="{}" filename="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+        escape(&module.module_id.short_str_lossless()),
+        escape(&filename.display().to_string()),
+        rate(module.num_lines_covered(), module.lines.len()),
+        rate(module.num_branches_covered(), module.branches.len()),
+    )?;
+
+    writeln!(writer, "          <methods>")?;
+    for function in module.functions.iter() {
+        writeln!(
+            writer,
+            r#"            <method name="{}" signature="" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            escape(&function.name),
+            rate(num_lines_covered(&function.lines), function.lines.len()),
+            branch_rate(module, &function.lines),
+        )?;
+        write_lines(module, &function.lines, "              ", writer)?;
+        writeln!(writer, "            </method>")?;
+    }
+    writeln!(writer, "          </methods>")?;
+    write_lines(module, &module.lines, "          ", writer)?;
+    writeln!(writer, "        </class>")
+}
+
+fn write_lines<W: Write>(
+    module: &ModuleLineCoverage,
+    lines: &LineHits,
+    indent: &str,
+    writer: &mut W,
+) -> io::Result<()> {
+    writeln!(writer, "{}<lines>", indent)?;
+    for (line, hits) in lines.iter() {
+        let num_branches = module.branches_at(*line).count();
+        if num_branches == 0 {
+            writeln!(
+                writer,
+                r#"{}  <line number="{}" hits="{}" branch="false"/>"#,
+                indent, line, hits
+            )?;
+        } else {
+            let num_covered = module
+                .branches_at(*line)
+                .filter(|branch| branch.hits.unwrap_or(0) > 0)
+                .count();
+            writeln!(
+                writer,
+                r#"{}  <line number="{}" hits="{}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+                indent,
+                line,
+                hits,
+                num_covered * 100 / num_branches,
+                num_covered,
+                num_branches,
+            )?;
+        }
+    }
+    writeln!(writer, "{}</lines>", indent)
+}
+
+/// The branch rate of the branches on `lines`.
+fn branch_rate(module: &ModuleLineCoverage, lines: &LineHits) -> f64 {
+    let (covered, valid) = lines
+        .keys()
+        .flat_map(|line| module.branches_at(*line))
+        .fold((0, 0), |(covered, valid), branch| {
+            let is_covered = branch.hits.unwrap_or(0) > 0;
+            (covered + is_covered as usize, valid + 1)
+        });
+    rate(covered, valid)
+}
+
+/// Nothing to cover counts as fully covered.
+fn rate(covered: usize, valid: usize) -> f64 {
+    if valid == 0 {
+        1.0
+    } else {
+        covered as f64 / valid as f64
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_coverage::tests::test_module_coverage;
+
+    #[test]
+    fn test_write_cobertura() {
+        let mut bytes = Vec::new();
+        write_cobertura(
+            &[test_module_coverage()],
+            "Package",
+            Path::new("/package"),
+            1_700_000_000,
+            &mut bytes,
+        )
+        .unwrap();
+        let xml = String::from_utf8(bytes).unwrap();
+
+        // 3 of 4 lines and 1 of 4 branch outcomes are covered.
+        assert!(xml.contains(&format!(
+            r#"<coverage line-rate="0.75" branch-rate="0.25" lines-covered="3" lines-valid="4" branches-covered="1" branches-valid="4" complexity="0" version="{}" timestamp="1700000000">"#,
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(xml.contains("<source>/package</source>"));
+        assert!(xml.contains(
+            r#"<package name="Package" line-rate="0.75" branch-rate="0.25" complexity="0">"#
+        ));
+        assert!(xml.contains(
+            r#"<class name="0x42::m" filename="sources/m.move" line-rate="0.75" branch-rate="0.25" complexity="0">"#
+        ));
+        assert!(xml.contains(
+            r#"<method name="f" signature="" line-rate="1" branch-rate="0.5" complexity="0">"#
+        ));
+        assert!(xml.contains(
+            r#"<method name="g" signature="" line-rate="0" branch-rate="0" complexity="0">"#
+        ));
+        assert!(xml.contains(
+            r#"<line number="3" hits="3" branch="true" condition-coverage="50% (1/2)"/>"#
+        ));
+        assert!(xml.contains(r#"<line number="4" hits="1" branch="false"/>"#));
+        assert!(xml.contains(
+            r#"<line number="11" hits="0" branch="true" condition-coverage="0% (0/2)"/>"#
+        ));
+    }
+
+    #[test]
+    fn test_rate() {
+        assert_eq!(rate(0, 0), 1.0);
+        assert_eq!(rate(1, 4), 0.25);
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"<a & 'b' "c">"#),
+            "&lt;a &amp; &apos;b&apos; &quot;c&quot;&gt;"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Writes coverage in the LCOV tracefile format, see `man geninfo`.
+
+#![forbid(unsafe_code)]
+
+use crate::line_coverage::ModuleLineCoverage;
+use std::io::{self, Write};
+
+/// Writes one record per module. Modules sharing a source file get a record each, which LCOV
+/// tools merge, so function names are qualified by the module name.
+pub fn write_lcov<W: Write>(modules: &[ModuleLineCoverage], writer: &mut W) -> io::Result<()> {
+    for module in modules {
+        let module_name = module.module_id.name();
+        writeln!(writer, "TN:")?;
+        writeln!(writer, "SF:{}", module.source_path.display())?;
+
+        for function in module.functions.iter() {
+            writeln!(
+                writer,
+                "FN:{},{}::{}",
+                function.line, module_name, function.name
+            )?;
+        }
+        for function in module.functions.iter() {
+            writeln!(
+                writer,
+                "FNDA:{},{}::{}",
+                function.hits, module_name, function.name
+            )?;
+        }
+        writeln!(writer, "FNF:{}", module.functions.len())?;
+        writeln!(
+            writer,
+            "FNH:{}",
+            module.functions.iter().filter(|f| f.hits > 0).count()
+        )?;
+
+        for branch in module.branches.iter() {
+            match branch.hits {
+                Some(hits) => writeln!(
+                    writer,
+                    "BRDA:{},{},{},{}",
+                    branch.line, branch.block, branch.branch, hits
+                )?,
+                None => writeln!(
+                    writer,
+                    "BRDA:{},{},{},-",
+                    branch.line, branch.block, branch.branch
+                )?,
+            }
+        }
+        writeln!(writer, "BRF:{}", module.branches.len())?;
+        writeln!(writer, "BRH:{}", module.num_branches_covered())?;
+
+        for (line, hits) in module.lines.iter() {
+            writeln!(writer, "DA:{},{}", line, hits)?;
+        }
+        writeln!(writer, "LF:{}", module.lines.len())?;
+        writeln!(writer, "LH:{}", module.num_lines_covered())?;
+        writeln!(writer, "end_of_record")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_coverage::tests::test_module_coverage;
+
+    #[test]
+    fn test_write_lcov() {
+        let mut bytes = Vec::new();
+        write_lcov(&[test_module_coverage()], &mut bytes).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            "TN:
+SF:/package/sources/m.move
+FN:2,m::f
+FN:10,m::g
+FNDA:3,m::f
+FNDA:0,m::g
+FNF:2
+FNH:1
+BRDA:3,0,0,1
+BRDA:3,0,1,0
+BRDA:11,1,0,-
+BRDA:11,1,1,-
+BRF:4
+BRH:1
+DA:3,3
+DA:4,1
+DA:6,2
+DA:11,0
+LF:4
+LH:3
+end_of_record
+"
+        );
+    }
+}
@@ -0,0 +1,392 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use crate::coverage_map::ExecCoverageMap;
+use anyhow::{ensure, format_err, Result};
+use codespan::{FileId, Files};
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Bytecode, CodeOffset, FunctionDefinitionIndex},
+    CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_core_types::language_storage::ModuleId;
+use move_ir_types::location::Loc;
+use std::{
+    cmp::{max, min},
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Execution counts keyed by 1-based source line.
+pub type LineHits = BTreeMap<u32, u64>;
+
+#[derive(Clone, Debug)]
+pub struct FunctionLineCoverage {
+    pub name: String,
+    /// The line the function is defined at.
+    pub line: u32,
+    /// The number of times the function was entered.
+    pub hits: u64,
+    pub lines: LineHits,
+}
+
+/// One outcome of a conditional jump.
+#[derive(Clone, Debug)]
+pub struct BranchCoverage {
+    pub line: u32,
+    /// Numbers the conditional jumps of a module in bytecode order.
+    pub block: u32,
+    /// 0 for jumping to the branch target, 1 for falling through.
+    pub branch: u32,
+    /// `None` if the conditional jump itself was never executed.
+    pub hits: Option<u64>,
+}
+
+/// Per function, per line and per branch coverage of a module, mapped to its source file.
+#[derive(Clone, Debug)]
+pub struct ModuleLineCoverage {
+    pub module_id: ModuleId,
+    pub source_path: PathBuf,
+    pub functions: Vec<FunctionLineCoverage>,
+    pub lines: LineHits,
+    pub branches: Vec<BranchCoverage>,
+}
+
+impl ModuleLineCoverage {
+    /// Maps the bytecode offsets of `module` to source lines with `source_map`. A line is as
+    /// covered as the most executed instruction starting on it.
+    ///
+    /// The coverage map only counts instructions, not the edges between them, so a branch
+    /// outcome is counted by executions of the instruction it leads to, capped by executions of
+    /// the conditional jump. This over-reports an outcome whose target is also reached from
+    /// elsewhere, e.g. the join point of an `if` without `else`.
+    pub fn new(
+        module: &CompiledModule,
+        coverage_map: &ExecCoverageMap,
+        source_map: &SourceMap,
+        source_path: &Path,
+    ) -> Result<Self> {
+        let file_contents = fs::read_to_string(source_path)?;
+        ensure!(
+            source_map.check(&file_contents),
+            "File contents of {} out of sync with source map",
+            source_path.display()
+        );
+        let mut files = Files::new();
+        let file_id = files.add(source_path.as_os_str().to_os_string(), file_contents);
+
+        let module_id = module.self_id();
+        let module_map = coverage_map
+            .module_maps
+            .get(&(*module_id.address(), module_id.name().to_owned()));
+
+        let mut functions = Vec::new();
+        let mut lines = LineHits::new();
+        let mut branches = Vec::new();
+        for (function_def_idx, function_def) in module.function_defs().iter().enumerate() {
+            // Native functions have no code to cover.
+            let code_unit = match &function_def.code {
+                Some(code_unit) => code_unit,
+                None => continue,
+            };
+            let fn_handle = module.function_handle_at(function_def.function);
+            let fn_name = module.identifier_at(fn_handle.name);
+            let function_def_idx = FunctionDefinitionIndex(function_def_idx as u16);
+            let function_coverage = module_map.and_then(|map| map.get_function_coverage(fn_name));
+            let hits_at = |code_offset: CodeOffset| {
+                function_coverage
+                    .and_then(|coverage| coverage.get(&(code_offset as u64)).copied())
+                    .unwrap_or(0)
+            };
+
+            let mut fn_lines = LineHits::new();
+            for (code_offset, instruction) in code_unit.code.iter().enumerate() {
+                let code_offset = code_offset as CodeOffset;
+                let line = line_of(
+                    &files,
+                    file_id,
+                    source_map.get_code_location(function_def_idx, code_offset)?,
+                )?;
+                let hits = hits_at(code_offset);
+                let line_hits = fn_lines.entry(line).or_insert(0);
+                *line_hits = max(*line_hits, hits);
+
+                if let Bytecode::BrTrue(target) | Bytecode::BrFalse(target) = instruction {
+                    let block = (branches.len() / 2) as u32;
+                    for (branch, successor) in [*target, code_offset + 1].into_iter().enumerate() {
+                        branches.push(BranchCoverage {
+                            line,
+                            block,
+                            branch: branch as u32,
+                            hits: (hits > 0).then(|| min(hits_at(successor), hits)),
+                        });
+                    }
+                }
+            }
+
+            for (line, hits) in fn_lines.iter() {
+                let line_hits = lines.entry(*line).or_insert(0);
+                *line_hits = max(*line_hits, *hits);
+            }
+            functions.push(FunctionLineCoverage {
+                name: fn_name.to_string(),
+                line: line_of(
+                    &files,
+                    file_id,
+                    source_map
+                        .get_function_source_map(function_def_idx)?
+                        .definition_location,
+                )?,
+                hits: hits_at(0),
+                lines: fn_lines,
+            });
+        }
+
+        Ok(Self {
+            module_id,
+            source_path: source_path.to_path_buf(),
+            functions,
+            lines,
+            branches,
+        })
+    }
+
+    pub fn num_lines_covered(&self) -> usize {
+        num_lines_covered(&self.lines)
+    }
+
+    pub fn num_branches_covered(&self) -> usize {
+        self.branches
+            .iter()
+            .filter(|branch| branch.hits.unwrap_or(0) > 0)
+            .count()
+    }
+
+    /// Returns the branches of the conditional jumps on `line`.
+    pub fn branches_at(&self, line: u32) -> impl Iterator<Item = &BranchCoverage> {
+        self.branches
+            .iter()
+            .filter(move |branch| branch.line == line)
+    }
+}
+
+pub fn num_lines_covered(lines: &LineHits) -> usize {
+    lines.values().filter(|hits| **hits > 0).count()
+}
+
+fn line_of(files: &Files<String>, file_id: FileId, loc: Loc) -> Result<u32> {
+    let location = files
+        .location(file_id, loc.start())
+        .map_err(|err| format_err!("Invalid source location {:?}: {:?}", loc, err))?;
+    Ok(location.line.0 + 1)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use move_compiler::{
+        compiled_unit::{CompiledUnit, NamedCompiledModule},
+        shared::{Flags, NumericalAddress},
+        Compiler,
+    };
+    use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+    use std::collections::BTreeSet;
+    use tempfile::TempDir;
+
+    /// A module with a covered function `f` branching on line 3, and an uncovered function `g`
+    /// branching on line 11.
+    pub(crate) fn test_module_coverage() -> ModuleLineCoverage {
+        let f_lines = LineHits::from([(3, 3), (4, 1), (6, 2)]);
+        let g_lines = LineHits::from([(11, 0)]);
+        ModuleLineCoverage {
+            module_id: ModuleId::new(
+                AccountAddress::from_hex_literal("0x42").unwrap(),
+                Identifier::new("m").unwrap(),
+            ),
+            source_path: PathBuf::from("/package/sources/m.move"),
+            functions: vec![
+                FunctionLineCoverage {
+                    name: "f".to_string(),
+                    line: 2,
+                    hits: 3,
+                    lines: f_lines.clone(),
+                },
+                FunctionLineCoverage {
+                    name: "g".to_string(),
+                    line: 10,
+                    hits: 0,
+                    lines: g_lines.clone(),
+                },
+            ],
+            lines: f_lines.into_iter().chain(g_lines).collect(),
+            branches: vec![
+                BranchCoverage {
+                    line: 3,
+                    block: 0,
+                    branch: 0,
+                    hits: Some(1),
+                },
+                BranchCoverage {
+                    line: 3,
+                    block: 0,
+                    branch: 1,
+                    hits: Some(0),
+                },
+                BranchCoverage {
+                    line: 11,
+                    block: 1,
+                    branch: 0,
+                    hits: None,
+                },
+                BranchCoverage {
+                    line: 11,
+                    block: 1,
+                    branch: 1,
+                    hits: None,
+                },
+            ],
+        }
+    }
+
+    const SOURCE: &str = r#"module 0x42::m {
+    public fun f(x: u64): u64 {
+        if (x > 10) {
+            x - 10
+        } else {
+            x + 1
+        }
+    }
+
+    public fun g(): u64 {
+        1
+    }
+}
+"#;
+
+    fn compile(dir: &TempDir) -> (PathBuf, CompiledModule, SourceMap) {
+        let source_path = dir.path().join("m.move");
+        fs::write(&source_path, SOURCE).unwrap();
+        let (_files, units) = Compiler::from_files(
+            vec![source_path.to_string_lossy().to_string()],
+            vec![],
+            BTreeMap::<String, NumericalAddress>::new(),
+            Flags::empty(),
+            &BTreeSet::new(),
+        )
+        .build_and_report()
+        .unwrap();
+        match units.into_iter().next().unwrap().into_compiled_unit() {
+            CompiledUnit::Module(NamedCompiledModule {
+                module, source_map, ..
+            }) => (source_path, module, source_map),
+            CompiledUnit::Script(_) => panic!("expected a module"),
+        }
+    }
+
+    #[test]
+    fn test_line_and_branch_hits() {
+        let dir = TempDir::new().unwrap();
+        let (source_path, module, source_map) = compile(&dir);
+        let code = &module
+            .function_defs()
+            .iter()
+            .find(|def| {
+                module
+                    .identifier_at(module.function_handle_at(def.function).name)
+                    .as_str()
+                    == "f"
+            })
+            .unwrap()
+            .code
+            .as_ref()
+            .unwrap()
+            .code;
+        let (jump, target) = code
+            .iter()
+            .enumerate()
+            .find_map(|(offset, instruction)| match instruction {
+                Bytecode::BrTrue(target) | Bytecode::BrFalse(target) => {
+                    Some((offset as u64, *target as u64))
+                },
+                _ => None,
+            })
+            .unwrap();
+
+        // `f` is called 3 times, the jump is taken once and falls through twice.
+        let mut coverage_map = ExecCoverageMap::new("test".to_string());
+        let mut insert = |pc, count| {
+            coverage_map.insert_multi(
+                *module.self_id().address(),
+                Identifier::new("m").unwrap(),
+                Identifier::new("f").unwrap(),
+                pc,
+                count,
+            )
+        };
+        for pc in 0..=jump {
+            insert(pc, 3);
+        }
+        insert(target, 1);
+        insert(jump + 1, 2);
+
+        let coverage =
+            ModuleLineCoverage::new(&module, &coverage_map, &source_map, &source_path).unwrap();
+        let functions: Vec<_> = coverage
+            .functions
+            .iter()
+            .map(|function| (function.name.as_str(), function.line, function.hits))
+            .collect();
+        assert_eq!(functions, vec![("f", 2, 3), ("g", 10, 0)]);
+        // The condition of the `if`.
+        assert_eq!(coverage.lines[&3], 3);
+        // The body of `g`.
+        assert_eq!(coverage.lines[&11], 0);
+
+        let branches: Vec<_> = coverage
+            .branches
+            .iter()
+            .map(|branch| (branch.line, branch.block, branch.branch, branch.hits))
+            .collect();
+        assert_eq!(branches, vec![(3, 0, 0, Some(1)), (3, 0, 1, Some(2))]);
+        assert_eq!(coverage.num_branches_covered(), 2);
+        assert_eq!(coverage.branches_at(3).count(), 2);
+    }
+
+    #[test]
+    fn test_not_executed() {
+        let dir = TempDir::new().unwrap();
+        let (source_path, module, source_map) = compile(&dir);
+        let coverage = ModuleLineCoverage::new(
+            &module,
+            &ExecCoverageMap::new("test".to_string()),
+            &source_map,
+            &source_path,
+        )
+        .unwrap();
+
+        assert!(coverage.functions.iter().all(|function| function.hits == 0));
+        assert!(!coverage.lines.is_empty());
+        assert_eq!(coverage.num_lines_covered(), 0);
+        assert!(coverage.branches.iter().all(|branch| branch.hits.is_none()));
+        assert_eq!(coverage.num_branches_covered(), 0);
+    }
+
+    #[test]
+    fn test_source_out_of_sync() {
+        let dir = TempDir::new().unwrap();
+        let (source_path, module, source_map) = compile(&dir);
+        fs::write(&source_path, SOURCE.replace("10", "20")).unwrap();
+
+        assert!(ModuleLineCoverage::new(
+            &module,
+            &ExecCoverageMap::new("test".to_string()),
+            &source_map,
+            &source_path,
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A Debug Adapter Protocol (DAP) server for the Move VM.
+//!
+//! Editors connect to the server via TCP and can set breakpoints on source lines, step over, into
+//! and out of functions, and inspect the locals of all frames on the call stack. Source lines
+//! are resolved with the source maps emitted by the compiler, see `SourceIndex`.
+//!
+//! A typical embedding looks like this:
+//!
+//! ```ignore
+//! let session = DebugSession::listen(port, index)?;
+//! session.install();
+//! let result = run_move_code();
+//! session.finish(exit_code);
+//! ```
+
+mod protocol;
+mod server;
+mod source;
+
+pub use server::DebugSession;
+pub use source::SourceIndex;
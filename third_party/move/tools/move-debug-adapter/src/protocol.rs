@@ -0,0 +1,191 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Framing and message types of the Debug Adapter Protocol. Each message is a JSON object
+//! preceded by a `Content-Length` header, see
+//! https://microsoft.github.io/debug-adapter-protocol/overview#base-protocol.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+
+const CONTENT_LENGTH: &str = "Content-Length:";
+
+fn invalid_data(msg: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// Reads the next message, returns `None` if the stream has been closed.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(len) = line.strip_prefix(CONTENT_LENGTH) {
+            content_length = Some(len.trim().parse::<usize>().map_err(invalid_data)?);
+        }
+    }
+    let content_length =
+        content_length.ok_or_else(|| invalid_data("Message without Content-Length header."))?;
+    let mut buf = vec![0; content_length];
+    reader.read_exact(&mut buf)?;
+    serde_json::from_slice(&buf).map(Some).map_err(invalid_data)
+}
+
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = serde_json::to_vec(message).map_err(invalid_data)?;
+    write!(writer, "{} {}\r\n\r\n", CONTENT_LENGTH, content.len())?;
+    writer.write_all(&content)?;
+    writer.flush()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub seq: i64,
+    pub command: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+impl Request {
+    pub fn from_message(message: Value) -> io::Result<Self> {
+        serde_json::from_value(message).map_err(invalid_data)
+    }
+
+    pub fn arguments<T: for<'a> Deserialize<'a>>(&self) -> io::Result<T> {
+        T::deserialize(&self.arguments).map_err(invalid_data)
+    }
+}
+
+pub fn response(seq: i64, request: &Request, body: Value) -> Value {
+    json!({
+        "seq": seq,
+        "type": "response",
+        "request_seq": request.seq,
+        "command": request.command,
+        "success": true,
+        "body": body,
+    })
+}
+
+pub fn error_response(seq: i64, request: &Request, message: &str) -> Value {
+    json!({
+        "seq": seq,
+        "type": "response",
+        "request_seq": request.seq,
+        "command": request.command,
+        "success": false,
+        "message": message,
+    })
+}
+
+pub fn event(seq: i64, event: &str, body: Value) -> Value {
+    json!({
+        "seq": seq,
+        "type": "event",
+        "event": event,
+        "body": body,
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeArguments {
+    #[serde(default)]
+    pub lines_start_at1: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchArguments {
+    #[serde(default)]
+    pub stop_on_entry: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Source {
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SourceBreakpoint {
+    pub line: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetBreakpointsArguments {
+    pub source: Source,
+    #[serde(default)]
+    pub breakpoints: Vec<SourceBreakpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopesArguments {
+    pub frame_id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariablesArguments {
+    pub variables_reference: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let mut buf = vec![];
+        let first = event(1, "initialized", json!({}));
+        let second = json!({"seq": 2, "type": "request", "command": "threads"});
+        write_message(&mut buf, &first).unwrap();
+        write_message(&mut buf, &second).unwrap();
+
+        let mut reader = Cursor::new(buf);
+        assert_eq!(read_message(&mut reader).unwrap(), Some(first));
+        let request = Request::from_message(read_message(&mut reader).unwrap().unwrap()).unwrap();
+        assert_eq!(request.seq, 2);
+        assert_eq!(request.command, "threads");
+        assert_eq!(request.arguments, Value::Null);
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_missing_content_length() {
+        let mut reader = Cursor::new(b"Content-Type: foo\r\n\r\n{}".to_vec());
+        assert!(read_message(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_set_breakpoints_arguments() {
+        let request = Request::from_message(json!({
+            "seq": 3,
+            "type": "request",
+            "command": "setBreakpoints",
+            "arguments": {
+                "source": {"name": "m.move", "path": "/tmp/m.move"},
+                "breakpoints": [{"line": 4}, {"line": 10, "column": 2}],
+            },
+        }))
+        .unwrap();
+        let args: SetBreakpointsArguments = request.arguments().unwrap();
+        assert_eq!(args.source.path.as_deref(), Some("/tmp/m.move"));
+        assert_eq!(
+            args.breakpoints
+                .iter()
+                .map(|bp| bp.line)
+                .collect::<Vec<_>>(),
+            vec![4, 10]
+        );
+    }
+}
@@ -0,0 +1,664 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    protocol::{
+        self, InitializeArguments, LaunchArguments, Request, ScopesArguments,
+        SetBreakpointsArguments, VariablesArguments,
+    },
+    source::{FileId, SourceIndex, SourceLine},
+};
+use move_core_types::{
+    language_storage::ModuleId,
+    value::{MoveStruct, MoveValue},
+};
+use move_vm_runtime::debugger::{self, DebugHook, FrameSnapshot};
+use serde_json::{json, Value};
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{self, BufReader},
+    net::{Ipv4Addr, Shutdown, TcpListener, TcpStream},
+    path::Path,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// The VM executes a single call stack at a time, which is exposed to the client as one thread.
+const THREAD_ID: i64 = 1;
+
+/// How long `DebugSession::finish` waits for the client to disconnect.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RunMode {
+    Continue,
+    Pause,
+    StepIn,
+    /// Stop at the next line executed by a frame at or below the given depth.
+    StepOver {
+        depth: usize,
+    },
+    /// Stop once the frame at the given depth has returned.
+    StepOut {
+        depth: usize,
+    },
+}
+
+/// The state of the VM while it is suspended.
+struct Stopped {
+    /// Innermost frame first, as presented to the client.
+    frames: Vec<FrameSnapshot>,
+    /// Variables reference of the locals of each frame.
+    scopes: Vec<i64>,
+    /// Children of each variables reference. References start at 1, 0 means no children.
+    variables: Vec<Vec<Value>>,
+}
+
+struct State {
+    mode: RunMode,
+    stop_on_entry: bool,
+    breakpoints: HashMap<FileId, BTreeSet<u64>>,
+    /// The line last executed by each frame on the call stack.
+    frame_lines: Vec<Option<SourceLine>>,
+    /// Set when the hook decided to stop, consumed when the VM is suspended.
+    pending_stop: Option<&'static str>,
+    stopped: Option<Stopped>,
+    configured: bool,
+    disconnected: bool,
+    lines_start_at1: bool,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            mode: RunMode::Continue,
+            stop_on_entry: false,
+            breakpoints: HashMap::new(),
+            frame_lines: vec![],
+            pending_stop: None,
+            stopped: None,
+            configured: false,
+            disconnected: false,
+            lines_start_at1: true,
+        }
+    }
+
+    /// Decides whether to stop before executing an instruction on the given line, at the given
+    /// call depth. Returns the reason reported to the client.
+    fn stop_reason(&mut self, line: Option<SourceLine>, depth: usize) -> Option<&'static str> {
+        self.frame_lines.resize(depth, None);
+        let frame_line = &mut self.frame_lines[depth - 1];
+        let new_line = line.is_some() && *frame_line != line;
+        *frame_line = line;
+
+        // Only stop at instructions which can be shown to the user.
+        let (file_id, line) = line?;
+        if self.disconnected {
+            return None;
+        }
+        if self.stop_on_entry {
+            self.stop_on_entry = false;
+            return Some("entry");
+        }
+        match self.mode {
+            RunMode::Pause => return Some("pause"),
+            RunMode::StepIn if new_line => return Some("step"),
+            RunMode::StepOver { depth: d } if new_line && depth <= d => return Some("step"),
+            RunMode::StepOut { depth: d } if depth < d => return Some("step"),
+            _ => {},
+        }
+        let hit_breakpoint = new_line
+            && self
+                .breakpoints
+                .get(&file_id)
+                .map_or(false, |lines| lines.contains(&line));
+        hit_breakpoint.then_some("breakpoint")
+    }
+
+    fn line_to_client(&self, line: u64) -> u64 {
+        if self.lines_start_at1 {
+            line
+        } else {
+            line - 1
+        }
+    }
+
+    fn line_from_client(&self, line: u64) -> u64 {
+        if self.lines_start_at1 {
+            line
+        } else {
+            line + 1
+        }
+    }
+}
+
+struct Shared {
+    index: SourceIndex,
+    state: Mutex<State>,
+    /// Signalled when the session is configured, the VM is resumed or the client disconnects.
+    changed: Condvar,
+    writer: Mutex<TcpStream>,
+    seq: AtomicI64,
+}
+
+impl Shared {
+    fn next_seq(&self) -> i64 {
+        self.seq.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn send(&self, message: Value) {
+        if let Err(err) = protocol::write_message(&mut *self.writer.lock().unwrap(), &message) {
+            eprintln!("[debug adapter] Failed to send message: {}", err);
+        }
+    }
+
+    fn send_event(&self, event: &str, body: Value) {
+        self.send(protocol::event(self.next_seq(), event, body))
+    }
+
+    fn disconnect(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.disconnected = true;
+        state.stopped = None;
+        self.changed.notify_all();
+    }
+
+    fn serve(&self, stream: TcpStream) {
+        let mut reader = BufReader::new(stream);
+        loop {
+            let request = match protocol::read_message(&mut reader)
+                .and_then(|message| message.map(Request::from_message).transpose())
+            {
+                Ok(Some(request)) => request,
+                Ok(None) => break,
+                Err(err) => {
+                    eprintln!("[debug adapter] Failed to read request: {}", err);
+                    break;
+                },
+            };
+            let response = match self.handle(&request) {
+                Ok(body) => protocol::response(self.next_seq(), &request, body),
+                Err(message) => protocol::error_response(self.next_seq(), &request, &message),
+            };
+            self.send(response);
+            match request.command.as_str() {
+                "initialize" => self.send_event("initialized", json!({})),
+                "disconnect" | "terminate" => break,
+                _ => {},
+            }
+        }
+        self.disconnect();
+    }
+
+    fn handle(&self, request: &Request) -> Result<Value, String> {
+        let mut state = self.state.lock().unwrap();
+        match request.command.as_str() {
+            "initialize" => {
+                let args: InitializeArguments = request.arguments().unwrap_or_default();
+                state.lines_start_at1 = args.lines_start_at1.unwrap_or(true);
+                Ok(json!({ "supportsConfigurationDoneRequest": true }))
+            },
+            "launch" | "attach" => {
+                let args: LaunchArguments = request.arguments().unwrap_or_default();
+                state.stop_on_entry = args.stop_on_entry;
+                Ok(json!({}))
+            },
+            "setBreakpoints" => {
+                let args: SetBreakpointsArguments =
+                    request.arguments().map_err(|err| err.to_string())?;
+                let file_id = args
+                    .source
+                    .path
+                    .and_then(|path| self.index.file_id(Path::new(&path)));
+                let lines = args
+                    .breakpoints
+                    .iter()
+                    .map(|bp| state.line_from_client(bp.line))
+                    .collect::<BTreeSet<_>>();
+                let breakpoints = args
+                    .breakpoints
+                    .iter()
+                    .map(|bp| {
+                        let line = state.line_from_client(bp.line);
+                        let verified =
+                            file_id.map_or(false, |file_id| self.index.has_code_at(file_id, line));
+                        json!({ "verified": verified, "line": bp.line })
+                    })
+                    .collect::<Vec<_>>();
+                if let Some(file_id) = file_id {
+                    state.breakpoints.insert(file_id, lines);
+                }
+                Ok(json!({ "breakpoints": breakpoints }))
+            },
+            "setExceptionBreakpoints" => Ok(json!({})),
+            "configurationDone" => {
+                state.configured = true;
+                self.changed.notify_all();
+                Ok(json!({}))
+            },
+            "threads" => Ok(json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] })),
+            "stackTrace" => {
+                let stopped = state.stopped.as_ref().ok_or("Not stopped.")?;
+                let frames = stopped
+                    .frames
+                    .iter()
+                    .enumerate()
+                    .map(|(i, frame)| self.stack_frame(&state, i as i64 + 1, frame))
+                    .collect::<Vec<_>>();
+                Ok(json!({ "stackFrames": frames, "totalFrames": frames.len() }))
+            },
+            "scopes" => {
+                let args: ScopesArguments = request.arguments().map_err(|err| err.to_string())?;
+                let stopped = state.stopped.as_ref().ok_or("Not stopped.")?;
+                let reference = usize::try_from(args.frame_id - 1)
+                    .ok()
+                    .and_then(|i| stopped.scopes.get(i))
+                    .ok_or("Unknown frame.")?;
+                Ok(json!({ "scopes": [{
+                    "name": "Locals",
+                    "presentationHint": "locals",
+                    "variablesReference": reference,
+                    "expensive": false,
+                }] }))
+            },
+            "variables" => {
+                let args: VariablesArguments =
+                    request.arguments().map_err(|err| err.to_string())?;
+                let stopped = state.stopped.as_ref().ok_or("Not stopped.")?;
+                let variables = usize::try_from(args.variables_reference - 1)
+                    .ok()
+                    .and_then(|i| stopped.variables.get(i))
+                    .ok_or("Unknown variables reference.")?;
+                Ok(json!({ "variables": variables }))
+            },
+            "continue" => {
+                self.resume(&mut state, |_| RunMode::Continue)?;
+                Ok(json!({ "allThreadsContinued": true }))
+            },
+            "next" => {
+                self.resume(&mut state, |depth| RunMode::StepOver { depth })?;
+                Ok(json!({}))
+            },
+            "stepIn" => {
+                self.resume(&mut state, |_| RunMode::StepIn)?;
+                Ok(json!({}))
+            },
+            "stepOut" => {
+                self.resume(&mut state, |depth| RunMode::StepOut { depth })?;
+                Ok(json!({}))
+            },
+            "pause" => {
+                state.mode = RunMode::Pause;
+                Ok(json!({}))
+            },
+            "disconnect" | "terminate" => Ok(json!({})),
+            command => Err(format!("Unsupported request `{}`.", command)),
+        }
+    }
+
+    /// Resumes the VM, with a run mode derived from the depth of the current frame.
+    fn resume(&self, state: &mut State, mode: impl FnOnce(usize) -> RunMode) -> Result<(), String> {
+        let stopped = state.stopped.take().ok_or("Not stopped.")?;
+        state.mode = mode(stopped.frames.len());
+        self.changed.notify_all();
+        Ok(())
+    }
+
+    fn stack_frame(&self, state: &State, id: i64, frame: &FrameSnapshot) -> Value {
+        let location = &frame.location;
+        let name = match &location.module_id {
+            Some(module_id) => format!(
+                "{}::{}",
+                module_id.short_str_lossless(),
+                location.function_name
+            ),
+            None => format!("script::{}", location.function_name),
+        };
+        let line = location.module_id.as_ref().and_then(|module_id| {
+            self.index
+                .line(module_id, &location.function_name, location.pc)
+        });
+        match line {
+            Some((file_id, line)) => {
+                let path = self.index.file_path(file_id);
+                json!({
+                    "id": id,
+                    "name": name,
+                    "source": {
+                        "name": path.file_name().map(|name| name.to_string_lossy()),
+                        "path": path.to_string_lossy(),
+                    },
+                    "line": state.line_to_client(line),
+                    "column": state.line_to_client(1),
+                })
+            },
+            None => json!({
+                "id": id,
+                "name": name,
+                "line": 0,
+                "column": 0,
+                "presentationHint": "subtle",
+            }),
+        }
+    }
+
+    fn stopped(&self, frames: Vec<FrameSnapshot>) -> Stopped {
+        let mut variables = VariablesBuilder::default();
+        let scopes = frames
+            .iter()
+            .map(|frame| {
+                let module_id = frame.location.module_id.as_ref();
+                let locals = frame
+                    .locals
+                    .iter()
+                    .map(|local| {
+                        let name = module_id
+                            .and_then(|module_id| {
+                                self.local_name(
+                                    module_id,
+                                    &frame.location.function_name,
+                                    local.index,
+                                )
+                            })
+                            .unwrap_or_else(|| format!("local{}", local.index));
+                        match &local.value {
+                            Some(value) => {
+                                variables.variable(name, Some(local.type_name.clone()), value)
+                            },
+                            None => json!({
+                                "name": name,
+                                "type": local.type_name,
+                                "value": "<no value>",
+                                "variablesReference": 0,
+                            }),
+                        }
+                    })
+                    .collect();
+                variables.add(locals)
+            })
+            .collect();
+        Stopped {
+            frames,
+            scopes,
+            variables: variables.variables,
+        }
+    }
+
+    fn local_name(
+        &self,
+        module_id: &ModuleId,
+        function_name: &str,
+        index: usize,
+    ) -> Option<String> {
+        self.index
+            .local_name(module_id, function_name, index)
+            .map(str::to_string)
+    }
+}
+
+#[derive(Default)]
+struct VariablesBuilder {
+    variables: Vec<Vec<Value>>,
+}
+
+impl VariablesBuilder {
+    fn add(&mut self, children: Vec<Value>) -> i64 {
+        self.variables.push(children);
+        self.variables.len() as i64
+    }
+
+    fn variable(&mut self, name: String, type_name: Option<String>, value: &MoveValue) -> Value {
+        let (display, reference) = match value {
+            MoveValue::Struct(s) => {
+                let (struct_name, fields) = match s {
+                    MoveStruct::WithTypes { type_, fields } => (
+                        type_.to_canonical_string(),
+                        fields
+                            .iter()
+                            .map(|(name, value)| (name.to_string(), value))
+                            .collect::<Vec<_>>(),
+                    ),
+                    MoveStruct::WithFields(fields) => (
+                        "struct".to_string(),
+                        fields
+                            .iter()
+                            .map(|(name, value)| (name.to_string(), value))
+                            .collect(),
+                    ),
+                    MoveStruct::Runtime(values) => (
+                        "struct".to_string(),
+                        values
+                            .iter()
+                            .enumerate()
+                            .map(|(i, value)| (i.to_string(), value))
+                            .collect(),
+                    ),
+                };
+                let children = fields
+                    .into_iter()
+                    .map(|(name, value)| self.variable(name, None, value))
+                    .collect();
+                (format!("{} {{..}}", struct_name), self.add(children))
+            },
+            MoveValue::Vector(elems) if elems.is_empty() => ("[]".to_string(), 0),
+            MoveValue::Vector(elems) if elems.iter().all(|e| matches!(e, MoveValue::U8(_))) => {
+                let bytes = elems
+                    .iter()
+                    .map(|e| match e {
+                        MoveValue::U8(b) => format!("{:02x}", b),
+                        _ => unreachable!(),
+                    })
+                    .collect::<String>();
+                (format!("0x{}", bytes), 0)
+            },
+            MoveValue::Vector(elems) => {
+                let children = elems
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| self.variable(format!("[{}]", i), None, value))
+                    .collect();
+                (format!("[..; {}]", elems.len()), self.add(children))
+            },
+            MoveValue::U8(v) => (v.to_string(), 0),
+            MoveValue::U16(v) => (v.to_string(), 0),
+            MoveValue::U32(v) => (v.to_string(), 0),
+            MoveValue::U64(v) => (v.to_string(), 0),
+            MoveValue::U128(v) => (v.to_string(), 0),
+            MoveValue::U256(v) => (v.to_string(), 0),
+            MoveValue::Bool(v) => (v.to_string(), 0),
+            MoveValue::Address(a) => (format!("@{}", a.to_hex_literal()), 0),
+            MoveValue::Signer(a) => (format!("signer(@{})", a.to_hex_literal()), 0),
+        };
+        let mut variable = json!({
+            "name": name,
+            "value": display,
+            "variablesReference": reference,
+        });
+        if let Some(type_name) = type_name {
+            variable["type"] = json!(type_name);
+        }
+        variable
+    }
+}
+
+struct SessionHook(Arc<Shared>);
+
+impl DebugHook for SessionHook {
+    fn should_stop(
+        &mut self,
+        module_id: Option<&ModuleId>,
+        function_name: &str,
+        pc: u16,
+        depth: usize,
+    ) -> bool {
+        let line = module_id.and_then(|module_id| self.0.index.line(module_id, function_name, pc));
+        let mut state = self.0.state.lock().unwrap();
+        state.pending_stop = state.stop_reason(line, depth);
+        state.pending_stop.is_some()
+    }
+
+    fn on_stop(&mut self, mut frames: Vec<FrameSnapshot>) {
+        frames.reverse();
+        let stopped = self.0.stopped(frames);
+        let reason = {
+            let mut state = self.0.state.lock().unwrap();
+            state.stopped = Some(stopped);
+            state.mode = RunMode::Continue;
+            state.pending_stop.take().unwrap_or("step")
+        };
+        self.0.send_event(
+            "stopped",
+            json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        );
+        let state = self.0.state.lock().unwrap();
+        drop(
+            self.0
+                .changed
+                .wait_while(state, |state| {
+                    state.stopped.is_some() && !state.disconnected
+                })
+                .unwrap(),
+        );
+    }
+}
+
+/// A debugging session with a single client, connected via TCP.
+pub struct DebugSession {
+    shared: Arc<Shared>,
+    server: JoinHandle<()>,
+}
+
+impl DebugSession {
+    /// Waits for a client to connect on `127.0.0.1:<port>` and to finish configuring the session,
+    /// e.g. by setting the initial breakpoints.
+    pub fn listen(port: u16, index: SourceIndex) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        eprintln!(
+            "Waiting for a debug adapter client to connect on {}",
+            listener.local_addr()?
+        );
+        let (stream, _) = listener.accept()?;
+        let shared = Arc::new(Shared {
+            index,
+            state: Mutex::new(State::new()),
+            changed: Condvar::new(),
+            writer: Mutex::new(stream.try_clone()?),
+            seq: AtomicI64::new(0),
+        });
+        let server = {
+            let shared = shared.clone();
+            thread::spawn(move || shared.serve(stream))
+        };
+        let state = shared.state.lock().unwrap();
+        drop(
+            shared
+                .changed
+                .wait_while(state, |state| !state.configured && !state.disconnected)
+                .unwrap(),
+        );
+        Ok(Self { shared, server })
+    }
+
+    /// Installs the session as the debug hook of the VM. From now on, all Move code executed in
+    /// this process is subject to the client's breakpoints and stepping commands.
+    pub fn install(&self) {
+        debugger::set_debug_hook(Some(Box::new(SessionHook(self.shared.clone()))));
+    }
+
+    /// Removes the debug hook, reports the exit code to the client and closes the connection.
+    pub fn finish(self, exit_code: i64) {
+        debugger::set_debug_hook(None);
+        self.shared
+            .send_event("exited", json!({ "exitCode": exit_code }));
+        self.shared.send_event("terminated", json!({}));
+
+        // Give the client the chance to disconnect gracefully.
+        let state = self.shared.state.lock().unwrap();
+        drop(
+            self.shared
+                .changed
+                .wait_timeout_while(state, DISCONNECT_TIMEOUT, |state| !state.disconnected)
+                .unwrap(),
+        );
+        let _ = self.shared.writer.lock().unwrap().shutdown(Shutdown::Both);
+        let _ = self.server.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_at_breakpoint_once_per_line() {
+        let mut state = State::new();
+        state.breakpoints.insert(0, BTreeSet::from([3]));
+        assert_eq!(state.stop_reason(Some((0, 2)), 1), None);
+        assert_eq!(state.stop_reason(Some((0, 3)), 1), Some("breakpoint"));
+        // Further instructions on the same line don't stop again.
+        assert_eq!(state.stop_reason(Some((0, 3)), 1), None);
+        assert_eq!(state.stop_reason(None, 1), None);
+        assert_eq!(state.stop_reason(Some((1, 3)), 1), None);
+    }
+
+    #[test]
+    fn test_stop_on_entry() {
+        let mut state = State::new();
+        state.stop_on_entry = true;
+        assert_eq!(state.stop_reason(None, 1), None);
+        assert_eq!(state.stop_reason(Some((0, 1)), 1), Some("entry"));
+        assert_eq!(state.stop_reason(Some((0, 2)), 1), None);
+    }
+
+    #[test]
+    fn test_step_over_skips_callees() {
+        let mut state = State::new();
+        state.mode = RunMode::StepOver { depth: 1 };
+        assert_eq!(state.stop_reason(Some((0, 1)), 1), Some("step"));
+        assert_eq!(state.stop_reason(Some((0, 10)), 2), None);
+        // Returning to the line of the call is not a new line.
+        assert_eq!(state.stop_reason(Some((0, 1)), 1), None);
+        assert_eq!(state.stop_reason(Some((0, 2)), 1), Some("step"));
+    }
+
+    #[test]
+    fn test_step_in_and_out() {
+        let mut state = State::new();
+        assert_eq!(state.stop_reason(Some((0, 1)), 1), None);
+        state.mode = RunMode::StepIn;
+        assert_eq!(state.stop_reason(Some((0, 10)), 2), Some("step"));
+        state.mode = RunMode::StepOut { depth: 2 };
+        assert_eq!(state.stop_reason(Some((0, 11)), 2), None);
+        assert_eq!(state.stop_reason(Some((0, 1)), 1), Some("step"));
+    }
+
+    #[test]
+    fn test_variables() {
+        let mut builder = VariablesBuilder::default();
+        let value = MoveValue::Vector(vec![
+            MoveValue::Bool(true),
+            MoveValue::Struct(MoveStruct::Runtime(vec![MoveValue::U64(7)])),
+        ]);
+        let variable = builder.variable("v".to_string(), Some("vector<T>".to_string()), &value);
+        assert_eq!(variable["value"], "[..; 2]");
+        assert_eq!(variable["type"], "vector<T>");
+        let reference = variable["variablesReference"].as_i64().unwrap();
+        let children = &builder.variables[reference as usize - 1];
+        assert_eq!(children[0]["value"], "true");
+        assert_eq!(children[1]["value"], "struct {..}");
+        let fields =
+            &builder.variables[children[1]["variablesReference"].as_i64().unwrap() as usize - 1];
+        assert_eq!(fields[0]["name"], "0");
+        assert_eq!(fields[0]["value"], "7");
+
+        let bytes = MoveValue::Vector(vec![MoveValue::U8(0xAB), MoveValue::U8(1)]);
+        assert_eq!(
+            builder.variable("b".to_string(), None, &bytes)["value"],
+            "0xab01"
+        );
+    }
+}
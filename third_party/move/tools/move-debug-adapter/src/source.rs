@@ -0,0 +1,185 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Maps code offsets of compiled functions to source lines, based on the source maps emitted by
+//! the compiler.
+
+use move_binary_format::{
+    access::ModuleAccess, file_format::FunctionDefinitionIndex, CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::files::FileHash;
+use move_core_types::language_storage::ModuleId;
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+/// Identifies a source file registered in a `SourceIndex`.
+pub type FileId = usize;
+
+/// A line in a source file, lines are 1-based.
+pub type SourceLine = (FileId, u64);
+
+struct SourceFile {
+    path: PathBuf,
+    /// Byte offsets at which each line starts.
+    line_starts: Vec<u32>,
+    /// Lines which have at least one instruction mapped to them.
+    lines_with_code: BTreeSet<u64>,
+}
+
+impl SourceFile {
+    fn new(path: PathBuf, contents: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(
+                contents
+                    .bytes()
+                    .enumerate()
+                    .filter(|(_, b)| *b == b'\n')
+                    .map(|(i, _)| i as u32 + 1),
+            )
+            .collect();
+        Self {
+            path,
+            line_starts,
+            lines_with_code: BTreeSet::new(),
+        }
+    }
+
+    fn line_of(&self, offset: u32) -> u64 {
+        self.line_starts.partition_point(|start| *start <= offset) as u64
+    }
+}
+
+struct FunctionInfo {
+    /// The source line of each instruction, if known.
+    lines: Vec<Option<SourceLine>>,
+    /// Names of parameters followed by locals.
+    local_names: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct SourceIndex {
+    files: Vec<SourceFile>,
+    files_by_hash: HashMap<FileHash, FileId>,
+    files_by_path: HashMap<PathBuf, FileId>,
+    functions: HashMap<ModuleId, HashMap<String, FunctionInfo>>,
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+impl SourceIndex {
+    /// Registers a source file. Must be called before the modules compiled from it are added.
+    pub fn add_file(&mut self, path: impl AsRef<Path>, contents: &str) {
+        let hash = FileHash::new(contents);
+        if self.files_by_hash.contains_key(&hash) {
+            return;
+        }
+        let path = normalize_path(path.as_ref());
+        let id = self.files.len();
+        self.files.push(SourceFile::new(path.clone(), contents));
+        self.files_by_hash.insert(hash, id);
+        self.files_by_path.insert(path, id);
+    }
+
+    pub fn add_module(&mut self, module: &CompiledModule, source_map: &SourceMap) {
+        let mut functions = HashMap::new();
+        for (idx, fdef) in module.function_defs().iter().enumerate() {
+            let handle = module.function_handle_at(fdef.function);
+            let name = module.identifier_at(handle.name).to_string();
+            let Ok(function_map) =
+                source_map.get_function_source_map(FunctionDefinitionIndex(idx as u16))
+            else {
+                continue;
+            };
+            let code_len = fdef.code.as_ref().map_or(0, |code| code.code.len());
+            let lines = (0..code_len)
+                .map(|pc| {
+                    let loc = function_map.get_code_location(pc as u16)?;
+                    let file_id = *self.files_by_hash.get(&loc.file_hash())?;
+                    let file = &mut self.files[file_id];
+                    let line = file.line_of(loc.start());
+                    file.lines_with_code.insert(line);
+                    Some((file_id, line))
+                })
+                .collect();
+            let local_names = function_map
+                .parameters
+                .iter()
+                .chain(function_map.locals.iter())
+                .map(|(name, _)| name.clone())
+                .collect();
+            functions.insert(name, FunctionInfo { lines, local_names });
+        }
+        self.functions.insert(module.self_id(), functions);
+    }
+
+    fn function(&self, module_id: &ModuleId, function_name: &str) -> Option<&FunctionInfo> {
+        self.functions.get(module_id)?.get(function_name)
+    }
+
+    /// Returns the source line of the instruction at `pc`.
+    pub fn line(&self, module_id: &ModuleId, function_name: &str, pc: u16) -> Option<SourceLine> {
+        *self
+            .function(module_id, function_name)?
+            .lines
+            .get(pc as usize)?
+    }
+
+    pub fn local_name(
+        &self,
+        module_id: &ModuleId,
+        function_name: &str,
+        index: usize,
+    ) -> Option<&str> {
+        self.function(module_id, function_name)?
+            .local_names
+            .get(index)
+            .map(String::as_str)
+    }
+
+    pub fn file_id(&self, path: &Path) -> Option<FileId> {
+        self.files_by_path.get(&normalize_path(path)).copied()
+    }
+
+    pub fn file_path(&self, file_id: FileId) -> &Path {
+        &self.files[file_id].path
+    }
+
+    /// Whether any instruction is mapped to the given line, i.e. whether a breakpoint on it can
+    /// be hit.
+    pub fn has_code_at(&self, file_id: FileId, line: u64) -> bool {
+        self.files[file_id].lines_with_code.contains(&line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_of() {
+        let file = SourceFile::new(
+            PathBuf::from("m.move"),
+            "module 0x1::m {\n\n  fun f() {}\n}",
+        );
+        assert_eq!(file.line_of(0), 1);
+        assert_eq!(file.line_of(15), 1);
+        assert_eq!(file.line_of(16), 2);
+        assert_eq!(file.line_of(17), 3);
+        assert_eq!(file.line_of(30), 4);
+    }
+
+    #[test]
+    fn test_add_file_dedups_by_contents() {
+        let mut index = SourceIndex::default();
+        index.add_file("a.move", "module 0x1::a {}");
+        index.add_file("b.move", "module 0x1::a {}");
+        assert_eq!(index.file_id(Path::new("a.move")), Some(0));
+        assert_eq!(index.file_id(Path::new("b.move")), None);
+        assert!(!index.has_code_at(0, 1));
+    }
+}
@@ -112,6 +112,12 @@ pub struct UnitTestingConfig {
     #[cfg(feature = "evm-backend")]
     #[clap(long = "evm")]
     pub evm: bool,
+
+    /// Wait for a Debug Adapter Protocol client to connect on this port and run the tests under
+    /// its control. Tests are run on a single thread.
+    #[cfg(feature = "debugging")]
+    #[clap(long = "debug_adapter_port")]
+    pub debug_adapter_port: Option<u16>,
 }
 
 fn format_module_id(module_id: &ModuleId) -> String {
@@ -142,6 +148,9 @@ impl UnitTestingConfig {
 
             #[cfg(feature = "evm-backend")]
             evm: false,
+
+            #[cfg(feature = "debugging")]
+            debug_adapter_port: None,
         }
     }
 
@@ -235,10 +244,35 @@ impl UnitTestingConfig {
             return Ok((shared_writer.into_inner().unwrap(), true));
         }
 
+        #[cfg(feature = "debugging")]
+        let debug_session = match self.debug_adapter_port {
+            Some(port) => {
+                let mut index = move_debug_adapter::SourceIndex::default();
+                for (file_name, contents) in test_plan.files.values() {
+                    index.add_file(file_name.as_str(), contents);
+                }
+                for info in test_plan.module_info.values() {
+                    index.add_module(&info.module, &info.source_map);
+                }
+                let session = move_debug_adapter::DebugSession::listen(port, index)?;
+                session.install();
+                Some(session)
+            },
+            None => None,
+        };
+        #[cfg(feature = "debugging")]
+        let num_threads = if debug_session.is_some() {
+            1
+        } else {
+            self.num_threads
+        };
+        #[cfg(not(feature = "debugging"))]
+        let num_threads = self.num_threads;
+
         writeln!(shared_writer.lock().unwrap(), "Running Move unit tests")?;
         let mut test_runner = TestRunner::new(
             self.gas_limit.unwrap_or(DEFAULT_EXECUTION_BOUND),
-            self.num_threads,
+            num_threads,
             self.report_storage_on_error,
             self.report_stacktrace_on_abort,
             test_plan,
@@ -265,6 +299,10 @@ impl UnitTestingConfig {
         }
 
         let ok = test_results.summarize(&shared_writer)?;
+        #[cfg(feature = "debugging")]
+        if let Some(session) = debug_session {
+            session.finish(if ok { 0 } else { 1 });
+        }
 
         let writer = shared_writer.into_inner().unwrap();
         Ok((writer, ok))